@@ -0,0 +1,139 @@
+//! Compares counting and truncating texts to a token budget with encoding them.
+//! Run with `cargo bench --bench count_tokens`.
+
+use rust_tokenizers::tokenizer::{BertTokenizer, Tokenizer, TruncationStrategy};
+use rust_tokenizers::vocab::{BertVocab, SpecialTokenMap, Vocab};
+use std::collections::HashMap;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+const WORDS: [&str; 16] = [
+    "the",
+    "tokenizer",
+    "counts",
+    "tokens",
+    "of",
+    "a",
+    "long",
+    "document",
+    "without",
+    "building",
+    "offsets",
+    "or",
+    "ids",
+    "unaffable",
+    "Ünïcödé",
+    "東京",
+];
+const NUM_TEXTS: usize = 2_000;
+const WORDS_PER_TEXT: usize = 200;
+const NUM_RUNS: usize = 5;
+
+fn tokenizer() -> BertTokenizer {
+    let mut values: Vec<String> = ["[UNK]", "[CLS]", "[SEP]", "[PAD]", "[MASK]", ",", "."]
+        .iter()
+        .map(|token| token.to_string())
+        .collect();
+    values.extend(
+        WORDS[..13]
+            .iter()
+            .chain(["un", "##aff", "##able", "unicode", "東", "京"].iter())
+            .map(|token| token.to_string()),
+    );
+    let values: HashMap<String, i64> = values
+        .into_iter()
+        .enumerate()
+        .map(|(id, token)| (token, id as i64))
+        .collect();
+    let special_token_map = SpecialTokenMap {
+        unk_token: "[UNK]".to_string(),
+        pad_token: Some("[PAD]".to_string()),
+        bos_token: None,
+        sep_token: Some("[SEP]".to_string()),
+        cls_token: Some("[CLS]".to_string()),
+        eos_token: None,
+        mask_token: Some("[MASK]".to_string()),
+        additional_special_tokens: None,
+    };
+    let vocab = BertVocab::from_values_and_special_token_map(values, special_token_map).unwrap();
+    BertTokenizer::from_existing_vocab(vocab, true, true)
+}
+
+fn texts() -> Vec<String> {
+    //deterministic linear congruential generator, the benchmark does not depend on a RNG crate
+    let mut state: u64 = 42;
+    (0..NUM_TEXTS)
+        .map(|_| {
+            let mut text = String::new();
+            for position in 0..WORDS_PER_TEXT {
+                state = state
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                text.push_str(WORDS[(state >> 33) as usize % WORDS.len()]);
+                text.push_str(match position % 12 {
+                    5 => ", ",
+                    11 => ". ",
+                    _ => " ",
+                });
+            }
+            text
+        })
+        .collect()
+}
+
+/// Returns the fastest of `NUM_RUNS` runs of `f` over all the texts
+fn bench<F: FnMut(&str)>(texts: &[String], mut f: F) -> Duration {
+    (0..NUM_RUNS)
+        .map(|_| {
+            let start = Instant::now();
+            for text in texts {
+                f(text);
+            }
+            start.elapsed()
+        })
+        .min()
+        .unwrap()
+}
+
+fn main() {
+    let tokenizer = tokenizer();
+    let texts = texts();
+    let max_tokens = WORDS_PER_TEXT / 2;
+
+    let encode = bench(&texts, |text| {
+        black_box(tokenizer.encode(
+            text,
+            None,
+            usize::MAX,
+            &TruncationStrategy::DoNotTruncate,
+            0,
+        ));
+    });
+    let encode_truncated = bench(&texts, |text| {
+        black_box(tokenizer.encode(text, None, max_tokens, &TruncationStrategy::LongestFirst, 0));
+    });
+    let count_tokens = bench(&texts, |text| {
+        black_box(tokenizer.count_tokens(text));
+    });
+    let truncate_text_to_tokens = bench(&texts, |text| {
+        black_box(tokenizer.truncate_text_to_tokens(text, max_tokens));
+    });
+
+    println!("{NUM_TEXTS} texts of {WORDS_PER_TEXT} words, best of {NUM_RUNS} runs (µs per text):");
+    for (name, duration) in [
+        ("encode".to_string(), encode),
+        ("count_tokens".to_string(), count_tokens),
+        (format!("encode ({max_tokens} tokens)"), encode_truncated),
+        (
+            format!("truncate_text_to_tokens ({max_tokens})"),
+            truncate_text_to_tokens,
+        ),
+    ] {
+        let per_text = duration.as_secs_f64() * 1e6 / NUM_TEXTS as f64;
+        println!("{name:<32}{per_text:>10.2}");
+    }
+    println!(
+        "count_tokens speed-up over encode: {:.1}x",
+        encode.as_secs_f64() / count_tokens.as_secs_f64()
+    );
+}
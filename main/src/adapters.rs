@@ -11,16 +11,31 @@
 
 //! # Adapter helpers to load datasets
 //! While this crate does not aim at providing built-in support for loading dataset, it exposes
-//! a few adapters for testing and benchmarking purposes (e.g. for SST2 sentence classification)
+//! a few adapters for testing and benchmarking purposes:
+//! - SST2 sentence classification (`read_sst2`)
+//! - JSON lines classification datasets with configurable fields (`read_jsonl`)
+//! - GLUE sentence pair tasks: MNLI, QNLI and QQP (`read_glue`)
+//! - SQuAD v1 and v2 question answering (`read_squad`)
+//! - CoNLL named entity recognition (`read_conll`)
+//!
+//! Apart from `read_sst2`, the readers return an iterator over the examples of the file, returning
+//! an error for each invalid example without interrupting the iteration. `read_jsonl`, `read_glue` and
+//! `read_conll` stream the file, `read_squad` parses the whole file before iterating over its questions.
 
 use crate::error::TokenizerError;
 use crate::error::TokenizerError::ValueError;
+use serde::Deserialize;
+use serde_json::Value;
 use std::fs::File;
+use std::io::{BufRead, BufReader, Lines};
+use std::iter::Enumerate;
+use std::path::Path;
+use std::sync::Arc;
 
 /// # Sentiment analysis label
 /// Enum to represent a binary sentiment (positive or negative). An additional variant is available for
 /// enums which have not yet been assigned.
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Label {
     Positive,
     Negative,
@@ -30,7 +45,7 @@ pub enum Label {
 /// # SST2 sample
 /// Contains a placeholder for up to 2 sentences (the SST2 dataset only contains one per example) and
 /// a label
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Example {
     pub sentence_1: String,
     pub sentence_2: String,
@@ -82,6 +97,25 @@ impl Example {
     }
 }
 
+/// # Classification label
+/// Label of a classification dataset example: a class index, a named label or an unassigned label
+/// for examples without label (e.g. test sets).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ClassLabel {
+    Class(usize),
+    Named(String),
+    Unassigned,
+}
+
+/// # Classification sample
+/// Contains up to 2 sentences (the second sentence is empty for single sentence tasks) and a label
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClassificationExample {
+    pub sentence_1: String,
+    pub sentence_2: String,
+    pub label: ClassLabel,
+}
+
 /// Reads a SST2 dataset file and returns a vector of SST2 examples
 ///
 ///  # Arguments
@@ -92,7 +126,7 @@ impl Example {
 /// - `Result<Vec<Example>, TokenizerError>` containing the examples with their corresponding label
 pub fn read_sst2(path: &str, sep: u8) -> Result<Vec<Example>, TokenizerError> {
     let mut examples: Vec<Example> = Vec::new();
-    let f = File::open(path).expect("Could not open source file.");
+    let f = open_dataset_file(path)?;
 
     let mut rdr = csv::ReaderBuilder::new()
         .has_headers(true)
@@ -102,8 +136,608 @@ pub fn read_sst2(path: &str, sep: u8) -> Result<Vec<Example>, TokenizerError> {
 
     for result in rdr.records() {
         let record = result?;
-        let example = Example::new(&record[0], "", &record[1])?;
+        let line_number = record.position().map_or(0, |position| position.line());
+        let field = |index: usize| {
+            record
+                .get(index)
+                .ok_or_else(|| invalid_line(line_number, &format!("missing column {index}")))
+        };
+        let example = Example::new(field(0)?, "", field(1)?)?;
         examples.push(example);
     }
     Ok(examples)
 }
+
+fn open_dataset_file<P: AsRef<Path>>(path: P) -> Result<File, TokenizerError> {
+    File::open(&path).map_err(|e| {
+        TokenizerError::FileNotFound(format!(
+            "{} dataset file not found :{}",
+            path.as_ref().display(),
+            e
+        ))
+    })
+}
+
+fn invalid_line(line_number: u64, reason: &str) -> TokenizerError {
+    ValueError(format!("invalid example on line {line_number}: {reason}"))
+}
+
+fn parse_jsonl_line(
+    line: &str,
+    line_number: u64,
+    text_field: &str,
+    text_pair_field: Option<&str>,
+    label_field: Option<&str>,
+) -> Result<ClassificationExample, TokenizerError> {
+    let value: Value =
+        serde_json::from_str(line).map_err(|e| invalid_line(line_number, &e.to_string()))?;
+    let string_field = |field: &str| match value.get(field) {
+        None | Some(Value::Null) => Ok(None),
+        Some(Value::String(text)) => Ok(Some(text.as_str())),
+        Some(_) => Err(invalid_line(
+            line_number,
+            &format!("field `{field}` is not a string"),
+        )),
+    };
+    let sentence_1 = string_field(text_field)?
+        .ok_or_else(|| invalid_line(line_number, &format!("missing field `{text_field}`")))?;
+    let sentence_2 = match text_pair_field {
+        Some(field) => string_field(field)?.unwrap_or_default(),
+        None => "",
+    };
+    let label = match label_field.and_then(|field| value.get(field)) {
+        None | Some(Value::Null) => ClassLabel::Unassigned,
+        Some(Value::Bool(label)) => ClassLabel::Class(*label as usize),
+        Some(Value::Number(label)) => ClassLabel::Class(
+            label
+                .as_u64()
+                .ok_or_else(|| invalid_line(line_number, "numeric labels must be class indices"))?
+                as usize,
+        ),
+        Some(Value::String(label)) => ClassLabel::Named(label.clone()),
+        Some(_) => return Err(invalid_line(line_number, "invalid label")),
+    };
+    Ok(ClassificationExample {
+        sentence_1: sentence_1.to_string(),
+        sentence_2: sentence_2.to_string(),
+        label,
+    })
+}
+
+/// Reads a JSON lines dataset file (one JSON object per line) and streams its examples. Blank lines
+/// are skipped. Numeric and boolean labels are read as `ClassLabel::Class`, string labels as
+/// `ClassLabel::Named` and missing labels as `ClassLabel::Unassigned`.
+///
+///  # Arguments
+/// - path (`&Path`): path to the JSON lines file
+/// - text_field (`&str`): field containing the first sentence
+/// - text_pair_field (`Option<&str>`): optional field containing the second sentence
+/// - label_field (`Option<&str>`): optional field containing the label
+///
+///  # Returns
+/// - `Iterator<Item = Result<ClassificationExample, TokenizerError>>` over the examples of the file
+///
+/// # Example
+///
+/// ```no_run
+/// use rust_tokenizers::adapters::read_jsonl;
+/// let examples = read_jsonl("path/to/train.jsonl", "text", None, Some("label"))
+///     .unwrap()
+///     .collect::<Result<Vec<_>, _>>()
+///     .unwrap();
+/// ```
+pub fn read_jsonl<P: AsRef<Path>>(
+    path: P,
+    text_field: &str,
+    text_pair_field: Option<&str>,
+    label_field: Option<&str>,
+) -> Result<impl Iterator<Item = Result<ClassificationExample, TokenizerError>>, TokenizerError> {
+    let reader = BufReader::new(open_dataset_file(path)?);
+    let text_field = text_field.to_string();
+    let text_pair_field = text_pair_field.map(str::to_string);
+    let label_field = label_field.map(str::to_string);
+    Ok(reader.lines().enumerate().filter_map(move |(index, line)| {
+        let line = match line {
+            Ok(line) => line,
+            Err(e) => return Some(Err(TokenizerError::IOError(e.to_string()))),
+        };
+        if line.trim().is_empty() {
+            return None;
+        }
+        Some(parse_jsonl_line(
+            &line,
+            index as u64 + 1,
+            &text_field,
+            text_pair_field.as_deref(),
+            label_field.as_deref(),
+        ))
+    }))
+}
+
+/// # GLUE sentence pair task
+/// Sentence pair classification tasks of the GLUE benchmark, read from their TSV files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GlueTask {
+    /// Multi-Genre Natural Language Inference (entailment, neutral, contradiction)
+    Mnli,
+    /// Question answering NLI (entailment, not_entailment)
+    Qnli,
+    /// Quora Question Pairs (not_duplicate, duplicate)
+    Qqp,
+}
+
+impl GlueTask {
+    /// Returns the names of the task classes, in the order of the class indices
+    pub fn labels(&self) -> &'static [&'static str] {
+        match self {
+            GlueTask::Mnli => &["entailment", "neutral", "contradiction"],
+            GlueTask::Qnli => &["entailment", "not_entailment"],
+            GlueTask::Qqp => &["not_duplicate", "duplicate"],
+        }
+    }
+
+    /// Returns the names of the (first sentence, second sentence, label) columns
+    fn columns(&self) -> (&'static str, &'static str, &'static str) {
+        match self {
+            GlueTask::Mnli => ("sentence1", "sentence2", "gold_label"),
+            GlueTask::Qnli => ("question", "sentence", "label"),
+            GlueTask::Qqp => ("question1", "question2", "is_duplicate"),
+        }
+    }
+
+    fn parse_label(&self, label: &str) -> Option<ClassLabel> {
+        let labels = self.labels();
+        match self {
+            GlueTask::Qqp => label
+                .parse::<usize>()
+                .ok()
+                .filter(|index| *index < labels.len()),
+            GlueTask::Mnli | GlueTask::Qnli => labels.iter().position(|name| *name == label),
+        }
+        .map(ClassLabel::Class)
+    }
+}
+
+/// Reads a GLUE sentence pair task file (TSV with a header row) and streams its examples. Labels are
+/// read as `ClassLabel::Class` indices into `GlueTask::labels`, test files without labels give
+/// `ClassLabel::Unassigned`.
+///
+///  # Arguments
+/// - path (`&Path`): path to the GLUE TSV file (e.g. `train.tsv`, `dev_matched.tsv`)
+/// - task (`GlueTask`): GLUE task of the file
+///
+///  # Returns
+/// - `Iterator<Item = Result<ClassificationExample, TokenizerError>>` over the examples of the file
+///
+/// # Example
+///
+/// ```no_run
+/// use rust_tokenizers::adapters::{read_glue, GlueTask};
+/// for example in read_glue("path/to/MNLI/dev_matched.tsv", GlueTask::Mnli).unwrap() {
+///     let example = example.unwrap();
+///     println!("{} / {} -> {:?}", example.sentence_1, example.sentence_2, example.label);
+/// }
+/// ```
+pub fn read_glue<P: AsRef<Path>>(
+    path: P,
+    task: GlueTask,
+) -> Result<impl Iterator<Item = Result<ClassificationExample, TokenizerError>>, TokenizerError> {
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(true)
+        .delimiter(b'\t')
+        .quoting(false)
+        .flexible(true)
+        .from_reader(open_dataset_file(path)?);
+    let headers = reader.headers()?.clone();
+    let column = |name: &str| headers.iter().position(|header| header == name);
+    let (text_column, text_pair_column, label_column) = task.columns();
+    let missing_column = |name: &str| ValueError(format!("missing column `{name}` for {task:?}"));
+    let text_column = column(text_column).ok_or_else(|| missing_column(text_column))?;
+    let text_pair_column =
+        column(text_pair_column).ok_or_else(|| missing_column(text_pair_column))?;
+    let label_column = column(label_column);
+    Ok(reader.into_records().map(move |record| {
+        let record = record?;
+        let line_number = record.position().map_or(0, |position| position.line());
+        let field = |index: usize| {
+            record
+                .get(index)
+                .ok_or_else(|| invalid_line(line_number, &format!("missing column {index}")))
+        };
+        let label = match label_column.and_then(|index| record.get(index)) {
+            None | Some("") => ClassLabel::Unassigned,
+            Some(label) => task
+                .parse_label(label)
+                .ok_or_else(|| invalid_line(line_number, &format!("invalid label `{label}`")))?,
+        };
+        Ok(ClassificationExample {
+            sentence_1: field(text_column)?.to_string(),
+            sentence_2: field(text_pair_column)?.to_string(),
+            label,
+        })
+    }))
+}
+
+/// # SQuAD answer
+/// Answer text with its character span (`start..end`, in Unicode characters) in the context.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SquadAnswer {
+    pub text: String,
+    pub start: usize,
+    pub end: usize,
+}
+
+/// # SQuAD sample
+/// Question on a context paragraph with its answers. Unanswerable questions (SQuAD v2) have no answers.
+/// The context is shared by the questions of a paragraph.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SquadExample {
+    pub id: String,
+    pub title: String,
+    pub context: Arc<str>,
+    pub question: String,
+    pub answers: Vec<SquadAnswer>,
+    pub is_impossible: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct SquadFile {
+    data: Vec<SquadArticle>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SquadArticle {
+    #[serde(default)]
+    title: String,
+    paragraphs: Vec<SquadParagraph>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SquadParagraph {
+    context: String,
+    qas: Vec<SquadQuestion>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SquadQuestion {
+    id: String,
+    question: String,
+    #[serde(default)]
+    answers: Vec<SquadRawAnswer>,
+    #[serde(default)]
+    is_impossible: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct SquadRawAnswer {
+    text: String,
+    answer_start: usize,
+}
+
+/// Reads a SQuAD v1 or v2 JSON file and iterates over its questions. The answer spans are validated
+/// against the context: an answer extending past the end of the context is returned as an error.
+///
+/// A SQuAD file is a single JSON document: the whole file is loaded and parsed by this function
+/// (returning an error if it is not a valid SQuAD file) before the questions are iterated over.
+///
+///  # Arguments
+/// - path (`&Path`): path to the SQuAD JSON file
+///
+///  # Returns
+/// - `Iterator<Item = Result<SquadExample, TokenizerError>>` over the questions of the file
+///
+/// # Example
+///
+/// ```no_run
+/// use rust_tokenizers::adapters::read_squad;
+/// for example in read_squad("path/to/dev-v2.0.json").unwrap() {
+///     let example = example.unwrap();
+///     println!("{}: {:?}", example.question, example.answers);
+/// }
+/// ```
+pub fn read_squad<P: AsRef<Path>>(
+    path: P,
+) -> Result<impl Iterator<Item = Result<SquadExample, TokenizerError>>, TokenizerError> {
+    let squad: SquadFile = serde_json::from_reader(BufReader::new(open_dataset_file(path)?))
+        .map_err(|e| ValueError(format!("invalid SQuAD file: {e}")))?;
+    Ok(squad.data.into_iter().flat_map(|article| {
+        let title = article.title;
+        article.paragraphs.into_iter().flat_map(move |paragraph| {
+            let title = title.clone();
+            let context: Arc<str> = paragraph.context.into();
+            let context_length = context.chars().count();
+            paragraph.qas.into_iter().map(move |question| {
+                let id = question.id;
+                let answers = question
+                    .answers
+                    .into_iter()
+                    .map(|answer| {
+                        let end = answer.answer_start + answer.text.chars().count();
+                        if end > context_length {
+                            return Err(ValueError(format!(
+                                "answer of question {id} ends after its context"
+                            )));
+                        }
+                        Ok(SquadAnswer {
+                            text: answer.text,
+                            start: answer.answer_start,
+                            end,
+                        })
+                    })
+                    .collect::<Result<Vec<SquadAnswer>, TokenizerError>>()?;
+                Ok(SquadExample {
+                    id,
+                    title: title.clone(),
+                    context: context.clone(),
+                    question: question.question,
+                    answers,
+                    is_impossible: question.is_impossible,
+                })
+            })
+        })
+    }))
+}
+
+/// # NER sample
+/// Sentence split into words, with the entity tag of each word.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NerExample {
+    pub words: Vec<String>,
+    pub tags: Vec<String>,
+}
+
+struct ConllSentences {
+    lines: Enumerate<Lines<BufReader<File>>>,
+    tag_column: Option<usize>,
+}
+
+impl Iterator for ConllSentences {
+    type Item = Result<NerExample, TokenizerError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut example = NerExample {
+            words: Vec::new(),
+            tags: Vec::new(),
+        };
+        for (index, line) in self.lines.by_ref() {
+            let line = match line {
+                Ok(line) => line,
+                Err(e) => return Some(Err(TokenizerError::IOError(e.to_string()))),
+            };
+            let columns: Vec<&str> = line.split_whitespace().collect();
+            if columns.is_empty() {
+                if example.words.is_empty() {
+                    continue;
+                }
+                return Some(Ok(example));
+            }
+            if columns[0] == "-DOCSTART-" {
+                continue;
+            }
+            let tag = match self.tag_column {
+                Some(tag_column) => columns.get(tag_column),
+                None if columns.len() > 1 => columns.last(),
+                None => None,
+            };
+            match tag {
+                Some(tag) => {
+                    example.words.push(columns[0].to_string());
+                    example.tags.push(tag.to_string());
+                }
+                None => return Some(Err(invalid_line(index as u64 + 1, "missing tag column"))),
+            }
+        }
+        (!example.words.is_empty()).then_some(Ok(example))
+    }
+}
+
+/// Reads a CoNLL-formatted NER file (one word per line with whitespace-separated columns, sentences
+/// separated by blank lines) and streams its sentences. The word is read from the first column and the
+/// tag from `tag_column` (the last column if `None`). `-DOCSTART-` lines are skipped.
+///
+///  # Arguments
+/// - path (`&Path`): path to the CoNLL file
+/// - tag_column (`Option<usize>`): index of the tag column (e.g. 3 for CoNLL-2003 NER tags)
+///
+///  # Returns
+/// - `Iterator<Item = Result<NerExample, TokenizerError>>` over the sentences of the file
+///
+/// # Example
+///
+/// ```no_run
+/// use rust_tokenizers::adapters::read_conll;
+/// for sentence in read_conll("path/to/eng.train", None).unwrap() {
+///     let sentence = sentence.unwrap();
+///     println!("{:?} {:?}", sentence.words, sentence.tags);
+/// }
+/// ```
+pub fn read_conll<P: AsRef<Path>>(
+    path: P,
+    tag_column: Option<usize>,
+) -> Result<impl Iterator<Item = Result<NerExample, TokenizerError>>, TokenizerError> {
+    Ok(ConllSentences {
+        lines: BufReader::new(open_dataset_file(path)?).lines().enumerate(),
+        tag_column,
+    })
+}
+
+//==============================
+// Unit tests
+//==============================
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn write_temp_file(content: &str) -> anyhow::Result<tempfile::TempPath> {
+        let mut file = tempfile::NamedTempFile::new()?;
+        write!(file, "{content}")?;
+        Ok(file.into_temp_path())
+    }
+
+    #[test]
+    fn test_read_sst2_missing_file() {
+        //        Given
+        let path = "path/to/missing/file.tsv";
+
+        //        When
+        let result = read_sst2(path, b'\t');
+
+        //        Then
+        assert!(matches!(result, Err(TokenizerError::FileNotFound(_))));
+    }
+
+    #[test]
+    fn test_read_sst2() -> anyhow::Result<()> {
+        //        Given
+        let sst2 = write_temp_file("sentence\tlabel\nit 's a charming journey\t1\ndull\t0\n")?;
+        let single_column = write_temp_file("sentence\nit 's a charming journey\n")?;
+
+        //        When
+        let examples = read_sst2(sst2.to_str().unwrap(), b'\t')?;
+        let result = read_sst2(single_column.to_str().unwrap(), b'\t');
+
+        //        Then
+        assert_eq!(examples.len(), 2);
+        assert_eq!(examples[0].sentence_1, "it 's a charming journey");
+        assert_eq!(examples[0].label, Label::Positive);
+        assert_eq!(examples[1].label, Label::Negative);
+        assert!(matches!(result, Err(TokenizerError::ValueError(_))));
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_jsonl_and_glue() -> anyhow::Result<()> {
+        //        Given
+        let jsonl = write_temp_file(
+            "{\"text\": \"Great movie\", \"label\": 1}\n\n\
+            {\"text\": \"Hello\", \"pair\": \"World\", \"label\": \"greeting\"}\n\
+            {\"text\": \"No label\"}\n\
+            {\"label\": 0}\n",
+        )?;
+        let mnli = write_temp_file(
+            "index\tsentence1\tsentence2\tlabel1\tgold_label\n\
+            0\tA man \"sleeps\tA person rests\tentailment\tentailment\n\
+            1\tIt rains\tIt is sunny\tcontradiction\tcontradiction\n\
+            2\tA cat\tA dog\tneutral\tunknown\n",
+        )?;
+
+        //        When
+        let jsonl_examples: Vec<_> =
+            read_jsonl(&jsonl, "text", Some("pair"), Some("label"))?.collect();
+        let glue_examples: Vec<_> = read_glue(&mnli, GlueTask::Mnli)?.collect();
+
+        //        Then
+        assert_eq!(jsonl_examples.len(), 4);
+        assert_eq!(
+            jsonl_examples[0].as_ref().unwrap().label,
+            ClassLabel::Class(1)
+        );
+        assert_eq!(
+            *jsonl_examples[1].as_ref().unwrap(),
+            ClassificationExample {
+                sentence_1: "Hello".to_string(),
+                sentence_2: "World".to_string(),
+                label: ClassLabel::Named("greeting".to_string()),
+            }
+        );
+        assert_eq!(
+            jsonl_examples[2].as_ref().unwrap().label,
+            ClassLabel::Unassigned
+        );
+        assert!(jsonl_examples[3].is_err());
+        assert_eq!(glue_examples.len(), 3);
+        assert_eq!(
+            *glue_examples[0].as_ref().unwrap(),
+            ClassificationExample {
+                sentence_1: "A man \"sleeps".to_string(),
+                sentence_2: "A person rests".to_string(),
+                label: ClassLabel::Class(0),
+            }
+        );
+        assert_eq!(
+            glue_examples[1].as_ref().unwrap().label,
+            ClassLabel::Class(2)
+        );
+        assert!(glue_examples[2].is_err());
+        assert!(read_glue(&mnli, GlueTask::Qqp).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_squad() -> anyhow::Result<()> {
+        //        Given
+        let squad = write_temp_file(
+            r#"{"version": "v2.0", "data": [{"title": "Paris", "paragraphs": [{
+                "context": "Café culture: Paris is the capital of France.",
+                "qas": [
+                    {"id": "q1", "question": "What is the capital of France?",
+                     "answers": [{"text": "Paris", "answer_start": 14}], "is_impossible": false},
+                    {"id": "q2", "question": "What is the capital of Spain?",
+                     "answers": [], "plausible_answers": [{"text": "Paris", "answer_start": 14}],
+                     "is_impossible": true},
+                    {"id": "q3", "question": "Invalid?",
+                     "answers": [{"text": "France.", "answer_start": 40}]}
+                ]}]}]}"#,
+        )?;
+
+        //        When
+        let examples: Vec<_> = read_squad(&squad)?.collect();
+
+        //        Then
+        assert_eq!(examples.len(), 3);
+        let answerable = examples[0].as_ref().unwrap();
+        assert_eq!(answerable.title, "Paris");
+        assert_eq!(
+            answerable.answers,
+            vec![SquadAnswer {
+                text: "Paris".to_string(),
+                start: 14,
+                end: 19
+            }]
+        );
+        let answer_text: String = answerable.context.chars().skip(14).take(5).collect();
+        assert_eq!(answer_text, "Paris");
+        let impossible = examples[1].as_ref().unwrap();
+        assert!(Arc::ptr_eq(&answerable.context, &impossible.context));
+        assert!(impossible.is_impossible);
+        assert!(impossible.answers.is_empty());
+        assert!(examples[2].is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_conll() -> anyhow::Result<()> {
+        //        Given
+        let conll = write_temp_file(
+            "-DOCSTART- -X- -X- O\n\n\
+            EU NNP B-NP B-ORG\n\
+            rejects VBZ B-VP O\n\
+            German JJ B-NP B-MISC\n\n\n\
+            Peter NNP B-NP B-PER\n\
+            Blackburn NNP I-NP I-PER\n",
+        )?;
+
+        //        When
+        let sentences = read_conll(&conll, None)?.collect::<Result<Vec<_>, _>>()?;
+        let pos_tags = read_conll(&conll, Some(1))?.collect::<Result<Vec<_>, _>>()?;
+
+        //        Then
+        assert_eq!(
+            sentences,
+            vec![
+                NerExample {
+                    words: vec!["EU".into(), "rejects".into(), "German".into()],
+                    tags: vec!["B-ORG".into(), "O".into(), "B-MISC".into()],
+                },
+                NerExample {
+                    words: vec!["Peter".into(), "Blackburn".into()],
+                    tags: vec!["B-PER".into(), "I-PER".into()],
+                },
+            ]
+        );
+        assert_eq!(pos_tags[1].tags, vec!["NNP", "NNP"]);
+        assert!(read_conll(&conll, Some(4))?.next().unwrap().is_err());
+        Ok(())
+    }
+}
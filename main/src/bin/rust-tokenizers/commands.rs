@@ -0,0 +1,694 @@
+// Copyright 2019-2020 Guillaume Becquin
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//     http://www.apache.org/licenses/LICENSE-2.0
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::loader::{
+    load_tokenizer, DtypeName, FormatName, SideName, TokenizerArgs, TruncationName,
+};
+use clap::{Args, Subcommand};
+use rust_tokenizers::error::TokenizerError;
+use rust_tokenizers::tokenizer::{read_documents, DocumentFormat, TokenDtype, TokenShardWriter};
+use rust_tokenizers::vocab::{
+    diff_merges, diff_vocabs, inspect_sentencepiece_model, inspect_vocab, BpePairVocab,
+};
+use rust_tokenizers::TokenizedInput;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::PathBuf;
+
+/// Input and output files, shared by all subcommands
+#[derive(Debug, Args)]
+pub struct InputOutputArgs {
+    /// Input file, read line by line (defaults to stdin)
+    #[arg(short, long)]
+    pub input: Option<PathBuf>,
+
+    /// Output file (defaults to stdout)
+    #[arg(short, long)]
+    pub output: Option<PathBuf>,
+
+    /// Number of lines processed in parallel
+    #[arg(long, default_value_t = 1024)]
+    pub batch_size: usize,
+}
+
+#[derive(Debug, Args)]
+pub struct TokenizeArgs {
+    #[command(flatten)]
+    pub tokenizer: TokenizerArgs,
+
+    #[command(flatten)]
+    pub io: InputOutputArgs,
+
+    /// Text to tokenize (the input lines are tokenized if no text is given)
+    pub text: Vec<String>,
+}
+
+#[derive(Debug, Args)]
+pub struct EncodeArgs {
+    #[command(flatten)]
+    pub tokenizer: TokenizerArgs,
+
+    #[command(flatten)]
+    pub io: InputOutputArgs,
+
+    /// Maximum length of the encoded sequences (including special tokens)
+    #[arg(long, default_value_t = 512)]
+    pub max_len: usize,
+
+    /// Truncation strategy for sequences longer than `max_len`
+    #[arg(long, value_enum, default_value = "longest-first")]
+    pub truncation: TruncationName,
+
+    /// Side of the sequences tokens are removed from when truncating
+    #[arg(long, value_enum, default_value = "right")]
+    pub truncation_side: SideName,
+
+    /// Number of overlapping tokens kept in the overflowing tokens
+    #[arg(long, default_value_t = 0)]
+    pub stride: usize,
+
+    /// Number of leading tokens kept by the head-tail truncation
+    #[arg(long, default_value_t = 128)]
+    pub head: usize,
+
+    /// Field of the JSON objects containing the text to encode
+    #[arg(long, default_value = "text")]
+    pub text_field: String,
+
+    /// Field of the JSON objects containing the (optional) second text of a pair
+    #[arg(long, default_value = "text_pair")]
+    pub pair_field: String,
+}
+
+#[derive(Debug, Args)]
+pub struct DecodeArgs {
+    #[command(flatten)]
+    pub tokenizer: TokenizerArgs,
+
+    #[command(flatten)]
+    pub io: InputOutputArgs,
+
+    /// Remove the special tokens from the decoded text
+    #[arg(long)]
+    pub skip_special_tokens: bool,
+
+    /// Clean up the spaces before punctuation and contractions
+    #[arg(long)]
+    pub clean_up_tokenization_spaces: bool,
+
+    /// Field of the JSON objects containing the token ids (lines may also be plain id arrays)
+    #[arg(long, default_value = "token_ids")]
+    pub ids_field: String,
+}
+
+#[derive(Debug, Args)]
+pub struct CountArgs {
+    #[command(flatten)]
+    pub tokenizer: TokenizerArgs,
+
+    #[command(flatten)]
+    pub io: InputOutputArgs,
+
+    /// Only print the total number of tokens
+    #[arg(long)]
+    pub total: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct ShardArgs {
+    #[command(flatten)]
+    pub tokenizer: TokenizerArgs,
+
+    /// Input files, read line by line in the given order
+    #[arg(required = true)]
+    pub input: Vec<PathBuf>,
+
+    /// Output directory of the shards and index
+    #[arg(short, long)]
+    pub output_dir: PathBuf,
+
+    /// Prefix of the shard and index file names
+    #[arg(long, default_value = "tokens")]
+    pub prefix: String,
+
+    /// Format of the input files (one document per line)
+    #[arg(long, value_enum, default_value = "text")]
+    pub format: FormatName,
+
+    /// Field of the JSON objects containing the document text
+    #[arg(long, default_value = "text")]
+    pub text_field: String,
+
+    /// Data type of the token ids in the shards
+    #[arg(long, value_enum, default_value = "auto")]
+    pub dtype: DtypeName,
+
+    /// Maximum number of tokens of a shard
+    #[arg(long, default_value_t = 100_000_000)]
+    pub shard_size: usize,
+
+    /// Number of documents encoded in parallel
+    #[arg(long, default_value_t = 1024)]
+    pub chunk_size: usize,
+
+    /// Separator token appended after each document
+    #[arg(long)]
+    pub eos_token: Option<String>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum VocabCommand {
+    /// Report the special tokens, duplicate ids, id gaps and inconsistencies of a vocabulary
+    Inspect(VocabInspectArgs),
+    /// Compare the vocabulary of a tokenizer with another vocabulary of the same type
+    Diff(VocabDiffArgs),
+    /// Compare two BPE merges files
+    DiffMerges(MergesDiffArgs),
+    /// Check that a SentencePiece model agrees with the vocabulary of a tokenizer
+    CheckModel(CheckModelArgs),
+}
+
+/// Report output, shared by the vocabulary subcommands
+#[derive(Debug, Args)]
+pub struct ReportArgs {
+    /// Output file (defaults to stdout)
+    #[arg(short, long)]
+    pub output: Option<PathBuf>,
+
+    /// Exit with an error if the report contains any issue or difference
+    #[arg(long)]
+    pub check: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct VocabInspectArgs {
+    #[command(flatten)]
+    pub tokenizer: TokenizerArgs,
+
+    #[command(flatten)]
+    pub report: ReportArgs,
+}
+
+#[derive(Debug, Args)]
+pub struct VocabDiffArgs {
+    #[command(flatten)]
+    pub tokenizer: TokenizerArgs,
+
+    #[command(flatten)]
+    pub report: ReportArgs,
+
+    /// Vocabulary file of the second vocabulary (defaults to `--vocab`)
+    #[arg(long)]
+    pub other_vocab: Option<PathBuf>,
+
+    /// Merges file of the second vocabulary (defaults to `--merges`)
+    #[arg(long)]
+    pub other_merges: Option<PathBuf>,
+
+    /// SentencePiece model file of the second vocabulary (defaults to `--model`)
+    #[arg(long)]
+    pub other_model: Option<PathBuf>,
+}
+
+#[derive(Debug, Args)]
+pub struct MergesDiffArgs {
+    /// First merges file
+    pub merges: PathBuf,
+
+    /// Second merges file
+    pub other_merges: PathBuf,
+
+    #[command(flatten)]
+    pub report: ReportArgs,
+}
+
+#[derive(Debug, Args)]
+pub struct CheckModelArgs {
+    #[command(flatten)]
+    pub tokenizer: TokenizerArgs,
+
+    #[command(flatten)]
+    pub report: ReportArgs,
+
+    /// SentencePiece model file to check (defaults to `--model`)
+    #[arg(long)]
+    pub sentencepiece_model: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum EncodeInput {
+    Single(String),
+    Pair(String, String),
+}
+
+fn io_error(error: io::Error) -> TokenizerError {
+    TokenizerError::IOError(error.to_string())
+}
+
+fn open_output(args: &InputOutputArgs) -> Result<Box<dyn Write>, TokenizerError> {
+    Ok(match &args.output {
+        Some(path) => Box::new(BufWriter::new(File::create(path).map_err(io_error)?)),
+        None => Box::new(BufWriter::new(io::stdout())),
+    })
+}
+
+/// Reads the input lines in batches of `batch_size`, calling `process` with each batch and the
+/// (1-based) line number of its first line.
+fn for_each_batch<F>(args: &InputOutputArgs, mut process: F) -> Result<(), TokenizerError>
+where
+    F: FnMut(&[String], usize) -> Result<(), TokenizerError>,
+{
+    let reader: Box<dyn BufRead> = match &args.input {
+        Some(path) => Box::new(BufReader::new(File::open(path).map_err(|e| {
+            TokenizerError::FileNotFound(format!("{} not found: {e}", path.display()))
+        })?)),
+        None => Box::new(BufReader::new(io::stdin())),
+    };
+    let batch_size = args.batch_size.max(1);
+    let mut batch = Vec::with_capacity(batch_size);
+    let mut first_line = 1;
+    for line in reader.lines() {
+        batch.push(line.map_err(io_error)?);
+        if batch.len() == batch_size {
+            process(&batch, first_line)?;
+            first_line += batch.len();
+            batch.clear();
+        }
+    }
+    if !batch.is_empty() {
+        process(&batch, first_line)?;
+    }
+    Ok(())
+}
+
+fn write_json_line(output: &mut dyn Write, value: &Value) -> Result<(), TokenizerError> {
+    writeln!(output, "{value}").map_err(io_error)
+}
+
+/// Blank lines are encoded as an empty text, so that each input line produces an output record
+fn parse_encode_line(
+    line: &str,
+    line_number: usize,
+    text_field: &str,
+    pair_field: &str,
+) -> Result<EncodeInput, TokenizerError> {
+    if line.trim().is_empty() {
+        return Ok(EncodeInput::Single(String::new()));
+    }
+    let invalid = |reason: &str| {
+        TokenizerError::ValueError(format!("Invalid input on line {line_number}: {reason}"))
+    };
+    match serde_json::from_str::<Value>(line).map_err(|e| invalid(&e.to_string()))? {
+        Value::String(text) => Ok(EncodeInput::Single(text)),
+        Value::Object(object) => {
+            let text = object
+                .get(text_field)
+                .and_then(Value::as_str)
+                .ok_or_else(|| invalid(&format!("missing string field `{text_field}`")))?;
+            match object.get(pair_field) {
+                None | Some(Value::Null) => Ok(EncodeInput::Single(text.to_string())),
+                Some(Value::String(pair)) => Ok(EncodeInput::Pair(text.to_string(), pair.clone())),
+                Some(_) => Err(invalid(&format!("field `{pair_field}` is not a string"))),
+            }
+        }
+        _ => Err(invalid("expected a JSON object or string")),
+    }
+}
+
+/// Blank lines are decoded as an empty sequence of ids, so that each input line produces an
+/// output record
+fn parse_decode_line(
+    line: &str,
+    line_number: usize,
+    ids_field: &str,
+) -> Result<Vec<i64>, TokenizerError> {
+    if line.trim().is_empty() {
+        return Ok(vec![]);
+    }
+    let invalid = |reason: &str| {
+        TokenizerError::ValueError(format!("Invalid input on line {line_number}: {reason}"))
+    };
+    let value: Value = serde_json::from_str(line).map_err(|e| invalid(&e.to_string()))?;
+    let ids = match &value {
+        Value::Array(ids) => ids,
+        Value::Object(object) => match object.get(ids_field) {
+            Some(Value::Array(ids)) => ids,
+            _ => return Err(invalid(&format!("missing array field `{ids_field}`"))),
+        },
+        _ => return Err(invalid("expected a JSON object or array")),
+    };
+    ids.iter()
+        .map(|id| {
+            id.as_i64()
+                .ok_or_else(|| invalid("token ids must be integers"))
+        })
+        .collect()
+}
+
+fn encoded_to_json(encoded: &TokenizedInput) -> Value {
+    json!({
+        "token_ids": encoded.token_ids,
+        "segment_ids": encoded.segment_ids,
+        "special_tokens_mask": encoded.special_tokens_mask,
+        "token_offsets": encoded.token_offsets,
+        "mask": encoded.mask,
+        "overflowing_tokens": encoded.overflowing_tokens,
+        "num_truncated_tokens": encoded.num_truncated_tokens,
+    })
+}
+
+pub fn tokenize(args: TokenizeArgs) -> Result<(), TokenizerError> {
+    let tokenizer = load_tokenizer(&args.tokenizer)?;
+    let mut output = open_output(&args.io)?;
+    let mut write_batch = |texts: &[String], _: usize| {
+        for tokens in tokenizer.tokenize_list_with_offsets(texts) {
+            write_json_line(
+                &mut output,
+                &json!({"tokens": tokens.tokens, "offsets": tokens.offsets}),
+            )?;
+        }
+        Ok(())
+    };
+    if args.text.is_empty() {
+        for_each_batch(&args.io, write_batch)?;
+    } else {
+        write_batch(&args.text, 1)?;
+    }
+    output.flush().map_err(io_error)
+}
+
+pub fn encode(args: EncodeArgs) -> Result<(), TokenizerError> {
+    let tokenizer = load_tokenizer(&args.tokenizer)?;
+    let truncation_strategy = args.truncation.strategy(args.truncation_side, args.head);
+    let mut output = open_output(&args.io)?;
+    for_each_batch(&args.io, |lines, first_line| {
+        let mut single_positions = Vec::new();
+        let mut single_texts = Vec::new();
+        let mut pair_positions = Vec::new();
+        let mut pair_texts = Vec::new();
+        for (index, line) in lines.iter().enumerate() {
+            match parse_encode_line(line, first_line + index, &args.text_field, &args.pair_field)? {
+                EncodeInput::Single(text) => {
+                    single_positions.push(index);
+                    single_texts.push(text);
+                }
+                EncodeInput::Pair(text, pair) => {
+                    pair_positions.push(index);
+                    pair_texts.push((text, pair));
+                }
+            }
+        }
+
+        let mut encoded: Vec<Option<TokenizedInput>> = vec![None; lines.len()];
+        let singles = tokenizer.encode_list(
+            &single_texts,
+            args.max_len,
+            &truncation_strategy,
+            args.stride,
+        );
+        let pairs = tokenizer.encode_pair_list(
+            &pair_texts,
+            args.max_len,
+            &truncation_strategy,
+            args.stride,
+        );
+        for (position, value) in single_positions
+            .into_iter()
+            .zip(singles)
+            .chain(pair_positions.into_iter().zip(pairs))
+        {
+            encoded[position] = Some(value);
+        }
+        for value in encoded.iter().flatten() {
+            write_json_line(&mut output, &encoded_to_json(value))?;
+        }
+        Ok(())
+    })?;
+    output.flush().map_err(io_error)
+}
+
+pub fn decode(args: DecodeArgs) -> Result<(), TokenizerError> {
+    let tokenizer = load_tokenizer(&args.tokenizer)?;
+    let mut output = open_output(&args.io)?;
+    for_each_batch(&args.io, |lines, first_line| {
+        let token_ids = lines
+            .iter()
+            .enumerate()
+            .map(|(index, line)| parse_decode_line(line, first_line + index, &args.ids_field))
+            .collect::<Result<Vec<Vec<i64>>, TokenizerError>>()?;
+        for text in tokenizer.decode_list(
+            &token_ids,
+            args.skip_special_tokens,
+            args.clean_up_tokenization_spaces,
+        ) {
+            write_json_line(&mut output, &json!({ "text": text }))?;
+        }
+        Ok(())
+    })?;
+    output.flush().map_err(io_error)
+}
+
+pub fn count(args: CountArgs) -> Result<(), TokenizerError> {
+    let tokenizer = load_tokenizer(&args.tokenizer)?;
+    let mut output = open_output(&args.io)?;
+    let mut total = 0;
+    for_each_batch(&args.io, |lines, _| {
+        for num_tokens in tokenizer.count_tokens_list(lines) {
+            total += num_tokens;
+            if !args.total {
+                writeln!(output, "{num_tokens}").map_err(io_error)?;
+            }
+        }
+        Ok(())
+    })?;
+    if args.total {
+        writeln!(output, "{total}").map_err(io_error)?;
+    }
+    output.flush().map_err(io_error)
+}
+
+pub fn shard(args: ShardArgs) -> Result<(), TokenizerError> {
+    let tokenizer = load_tokenizer(&args.tokenizer)?;
+    let vocab = tokenizer.vocab();
+    let eos_token_id = args
+        .eos_token
+        .as_ref()
+        .map(|token| {
+            vocab
+                .values()
+                .get(token)
+                .copied()
+                .ok_or_else(|| TokenizerError::TokenNotFound(token.clone()))
+        })
+        .transpose()?;
+    let dtype = match args.dtype {
+        DtypeName::Auto => TokenDtype::for_vocab_size(
+            vocab
+                .indices()
+                .keys()
+                .max()
+                .map_or(0, |max_id| *max_id as usize + 1),
+        ),
+        DtypeName::Uint16 => TokenDtype::U16,
+        DtypeName::Uint32 => TokenDtype::U32,
+    };
+    let format = match args.format {
+        FormatName::Text => DocumentFormat::Text,
+        FormatName::Jsonl => DocumentFormat::Jsonl {
+            text_field: args.text_field.clone(),
+        },
+    };
+
+    let mut writer = TokenShardWriter::new(&args.output_dir, &args.prefix, dtype, args.shard_size)?;
+    writer.set_eos_token_id(eos_token_id);
+    for path in &args.input {
+        let mut documents = read_documents(path, &format)?;
+        tokenizer.encode_to_shards(&mut documents, args.chunk_size, &mut writer)?;
+    }
+    let index_path = writer.index_path();
+    let index = writer.finish()?;
+    println!(
+        "{}",
+        json!({
+            "index": index_path,
+            "num_documents": index.num_documents(),
+            "num_tokens": index.num_tokens,
+            "num_shards": index.shards.len(),
+        })
+    );
+    Ok(())
+}
+
+fn write_report<T: Serialize>(
+    args: &ReportArgs,
+    report: &T,
+    has_issues: bool,
+) -> Result<(), TokenizerError> {
+    let mut output: Box<dyn Write> = match &args.output {
+        Some(path) => Box::new(BufWriter::new(File::create(path).map_err(io_error)?)),
+        None => Box::new(BufWriter::new(io::stdout())),
+    };
+    let report = serde_json::to_string_pretty(report)
+        .map_err(|e| TokenizerError::ValueError(e.to_string()))?;
+    writeln!(output, "{report}").map_err(io_error)?;
+    output.flush().map_err(io_error)?;
+    if args.check && has_issues {
+        return Err(TokenizerError::ValueError(
+            "the report contains issues".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+fn other_tokenizer_args(args: &VocabDiffArgs) -> Result<TokenizerArgs, TokenizerError> {
+    if args.other_vocab.is_none() && args.other_merges.is_none() && args.other_model.is_none() {
+        return Err(TokenizerError::ValueError(
+            "at least one of --other-vocab, --other-merges or --other-model is required"
+                .to_string(),
+        ));
+    }
+    let mut other = args.tokenizer.clone();
+    other.vocab = args.other_vocab.clone().or(other.vocab);
+    other.merges = args.other_merges.clone().or(other.merges);
+    other.model = args.other_model.clone().or(other.model);
+    Ok(other)
+}
+
+pub fn vocab(command: VocabCommand) -> Result<(), TokenizerError> {
+    match command {
+        VocabCommand::Inspect(args) => {
+            let tokenizer = load_tokenizer(&args.tokenizer)?;
+            let report = inspect_vocab(tokenizer.vocab());
+            write_report(&args.report, &report, !report.is_consistent())
+        }
+        VocabCommand::Diff(args) => {
+            let first = load_tokenizer(&args.tokenizer)?;
+            let second = load_tokenizer(&other_tokenizer_args(&args)?)?;
+            let diff = diff_vocabs(first.vocab(), second.vocab());
+            write_report(&args.report, &diff, !diff.is_empty())
+        }
+        VocabCommand::DiffMerges(args) => {
+            let first = BpePairVocab::from_file(&args.merges)?;
+            let second = BpePairVocab::from_file(&args.other_merges)?;
+            let diff = diff_merges(&first, &second);
+            write_report(&args.report, &diff, !diff.is_empty())
+        }
+        VocabCommand::CheckModel(args) => {
+            let model = args
+                .sentencepiece_model
+                .as_ref()
+                .or(args.tokenizer.model.as_ref())
+                .ok_or_else(|| {
+                    TokenizerError::ValueError(
+                        "--sentencepiece-model or --model is required".to_string(),
+                    )
+                })?;
+            let tokenizer = load_tokenizer(&args.tokenizer)?;
+            let report = inspect_sentencepiece_model(model, tokenizer.vocab())?;
+            write_report(&args.report, &report, !report.is_consistent())
+        }
+    }
+}
+
+//==============================
+// Unit tests
+//==============================
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_encode_line() {
+        //        Given
+        let test_tuples = [
+            (
+                r#"{"text": "Hello world"}"#,
+                EncodeInput::Single("Hello world".to_string()),
+            ),
+            (
+                r#"{"text": "Hello", "text_pair": "world", "label": 1}"#,
+                EncodeInput::Pair("Hello".to_string(), "world".to_string()),
+            ),
+            (
+                r#""Hello world""#,
+                EncodeInput::Single("Hello world".to_string()),
+            ),
+        ];
+
+        //        When & Then
+        for (line, expected) in test_tuples.iter() {
+            assert_eq!(
+                parse_encode_line(line, 1, "text", "text_pair").unwrap(),
+                *expected
+            );
+        }
+        assert!(parse_encode_line(r#"{"sentence": "Hello"}"#, 1, "text", "text_pair").is_err());
+        assert!(parse_encode_line("[1, 2]", 1, "text", "text_pair").is_err());
+        assert_eq!(
+            parse_encode_line("  ", 1, "text", "text_pair").unwrap(),
+            EncodeInput::Single(String::new())
+        );
+    }
+
+    #[test]
+    fn test_parse_decode_line() {
+        //        Given
+        let encoded = r#"{"token_ids": [101, 7592, 102], "segment_ids": [0, 0, 0]}"#;
+
+        //        When & Then
+        assert_eq!(
+            parse_decode_line(encoded, 1, "token_ids").unwrap(),
+            vec![101, 7592, 102]
+        );
+        assert_eq!(parse_decode_line("[1, 2]", 1, "ids").unwrap(), vec![1, 2]);
+        assert!(parse_decode_line(r#"[1, "a"]"#, 1, "ids").is_err());
+        assert!(parse_decode_line(encoded, 1, "ids").is_err());
+        assert_eq!(parse_decode_line("", 1, "ids").unwrap(), Vec::<i64>::new());
+    }
+
+    #[test]
+    fn test_other_tokenizer_args() {
+        //        Given
+        let args = |other_vocab: Option<&str>| VocabDiffArgs {
+            tokenizer: TokenizerArgs {
+                tokenizer: crate::loader::TokenizerName::Roberta,
+                vocab: Some(PathBuf::from("vocab.json")),
+                merges: Some(PathBuf::from("merges.txt")),
+                model: None,
+                lower_case: false,
+                upper_case: false,
+                strip_accents: false,
+                add_prefix_space: false,
+                language: "en".to_string(),
+                preset: crate::loader::PresetName::Gpt2,
+                kmer_size: 6,
+                kmer_stride: 1,
+            },
+            report: ReportArgs {
+                output: None,
+                check: false,
+            },
+            other_vocab: other_vocab.map(PathBuf::from),
+            other_merges: None,
+            other_model: None,
+        };
+
+        //        When
+        let other = other_tokenizer_args(&args(Some("other_vocab.json"))).unwrap();
+
+        //        Then
+        assert_eq!(other.vocab, Some(PathBuf::from("other_vocab.json")));
+        assert_eq!(other.merges, Some(PathBuf::from("merges.txt")));
+        assert!(other_tokenizer_args(&args(None)).is_err());
+    }
+}
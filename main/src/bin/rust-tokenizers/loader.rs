@@ -0,0 +1,463 @@
+// Copyright 2019-2020 Guillaume Becquin
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//     http://www.apache.org/licenses/LICENSE-2.0
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use clap::{Args, ValueEnum};
+use rust_tokenizers::error::TokenizerError;
+use rust_tokenizers::tokenizer::{
+    encode_to_shards, AlbertTokenizer, BertTokenizer, BigBirdTokenizer, ByT5Tokenizer,
+    ByteLevelBpePreset, ByteLevelBpeTokenizer, CanineTokenizer, ClipTokenizer, CtrlTokenizer,
+    DeBERTaTokenizer, DeBERTaV2Tokenizer, EsmTokenizer, FNetTokenizer, Gpt2Tokenizer,
+    KmerTokenizer, LongformerTokenizer, M2M100Tokenizer, MBart50Tokenizer, MPNetTokenizer,
+    MarianTokenizer, MultiThreadedTokenizer, NLLBTokenizer, OpenAiGptTokenizer, PegasusTokenizer,
+    PhoBertTokenizer, ProphetNetTokenizer, ReformerTokenizer, RobertaTokenizer,
+    SentencePieceBpeTokenizer, SentencePieceTokenizer, T5Tokenizer, TokenShardWriter,
+    TruncationSide, TruncationStrategy, Wav2Vec2CtcTokenizer, WhisperTokenizer,
+    XLMRobertaTokenizer, XLMTokenizer, XLNetTokenizer,
+};
+use rust_tokenizers::vocab::Vocab;
+use rust_tokenizers::{TokenizedInput, TokensWithOffsets};
+use std::marker::PhantomData;
+use std::path::PathBuf;
+
+/// Tokenizer types available from the command line
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum TokenizerName {
+    Bert,
+    Mpnet,
+    Prophetnet,
+    Roberta,
+    Longformer,
+    OpenaiGpt,
+    Gpt2,
+    Ctrl,
+    Deberta,
+    Clip,
+    Whisper,
+    Xlm,
+    Phobert,
+    ByteLevelBpe,
+    Albert,
+    Xlnet,
+    T5,
+    Reformer,
+    DebertaV2,
+    Bigbird,
+    Fnet,
+    Pegasus,
+    Mbart50,
+    XlmRoberta,
+    SentencePiece,
+    SentencePieceBpe,
+    Marian,
+    M2m100,
+    Nllb,
+    Byt5,
+    Canine,
+    Wav2vec2,
+    Esm,
+    Kmer,
+}
+
+/// Byte-level BPE presets available from the command line
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum PresetName {
+    Gpt2,
+    GptNeox,
+    Bloom,
+    Starcoder,
+    Falcon,
+}
+
+impl From<PresetName> for ByteLevelBpePreset {
+    fn from(preset: PresetName) -> Self {
+        match preset {
+            PresetName::Gpt2 => ByteLevelBpePreset::Gpt2,
+            PresetName::GptNeox => ByteLevelBpePreset::GptNeoX,
+            PresetName::Bloom => ByteLevelBpePreset::Bloom,
+            PresetName::Starcoder => ByteLevelBpePreset::StarCoder,
+            PresetName::Falcon => ByteLevelBpePreset::Falcon,
+        }
+    }
+}
+
+/// Truncation strategies available from the command line
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum TruncationName {
+    LongestFirst,
+    OnlyFirst,
+    OnlySecond,
+    HeadTail,
+    DoNotTruncate,
+}
+
+/// Truncation sides available from the command line
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum SideName {
+    Right,
+    Left,
+}
+
+impl TruncationName {
+    /// Returns the truncation strategy removing tokens from `side`, keeping `head` tokens for the
+    /// head and tail truncation
+    pub fn strategy(self, side: SideName, head: usize) -> TruncationStrategy {
+        let strategy = match self {
+            TruncationName::LongestFirst => TruncationStrategy::LongestFirst,
+            TruncationName::OnlyFirst => TruncationStrategy::OnlyFirst,
+            TruncationName::OnlySecond => TruncationStrategy::OnlySecond,
+            TruncationName::HeadTail => TruncationStrategy::HeadTail { head },
+            TruncationName::DoNotTruncate => TruncationStrategy::DoNotTruncate,
+        };
+        match side {
+            SideName::Right => strategy,
+            SideName::Left => strategy.with_side(TruncationSide::Left),
+        }
+    }
+}
+
+/// Shard data types available from the command line
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum DtypeName {
+    /// Smallest data type able to store the vocabulary ids
+    Auto,
+    Uint16,
+    Uint32,
+}
+
+/// Document file formats available from the command line
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum FormatName {
+    Text,
+    Jsonl,
+}
+
+/// Tokenizer selection and resources, shared by all subcommands
+#[derive(Debug, Clone, Args)]
+pub struct TokenizerArgs {
+    /// Tokenizer type
+    #[arg(short, long, value_enum)]
+    pub tokenizer: TokenizerName,
+
+    /// Vocabulary file. For the byte-level BPE tokenizer, a `tokenizer.json` file if no merges
+    /// are given
+    #[arg(long)]
+    pub vocab: Option<PathBuf>,
+
+    /// Merges file (BPE tokenizers)
+    #[arg(long)]
+    pub merges: Option<PathBuf>,
+
+    /// SentencePiece model file (SentencePiece-based tokenizers)
+    #[arg(long)]
+    pub model: Option<PathBuf>,
+
+    /// Lower-case the input text
+    #[arg(long)]
+    pub lower_case: bool,
+
+    /// Upper-case the input text (Wav2Vec2 tokenizer, for vocabularies of upper-case characters)
+    #[arg(long)]
+    pub upper_case: bool,
+
+    /// Strip accents from the input text
+    #[arg(long)]
+    pub strip_accents: bool,
+
+    /// Add a space in front of the input text
+    #[arg(long)]
+    pub add_prefix_space: bool,
+
+    /// Language of the input text (XLM tokenizer)
+    #[arg(long, default_value = "en")]
+    pub language: String,
+
+    /// Model family (byte-level BPE tokenizer)
+    #[arg(long, value_enum, default_value = "gpt2")]
+    pub preset: PresetName,
+
+    /// Length of the k-mers (k-mer tokenizer)
+    #[arg(long, default_value_t = 6)]
+    pub kmer_size: usize,
+
+    /// Number of residues between two consecutive k-mers (k-mer tokenizer)
+    #[arg(long, default_value_t = 1)]
+    pub kmer_stride: usize,
+}
+
+/// Object-safe view of a `MultiThreadedTokenizer`, independent of its vocabulary type
+pub trait CliTokenizer: Send + Sync {
+    fn tokenize_list_with_offsets(&self, text_list: &[String]) -> Vec<TokensWithOffsets>;
+
+    fn count_tokens_list(&self, text_list: &[String]) -> Vec<usize>;
+
+    fn encode_list(
+        &self,
+        text_list: &[String],
+        max_len: usize,
+        truncation_strategy: &TruncationStrategy,
+        stride: usize,
+    ) -> Vec<TokenizedInput>;
+
+    fn encode_pair_list(
+        &self,
+        text_list: &[(String, String)],
+        max_len: usize,
+        truncation_strategy: &TruncationStrategy,
+        stride: usize,
+    ) -> Vec<TokenizedInput>;
+
+    fn decode_list(
+        &self,
+        token_ids_list: &[Vec<i64>],
+        skip_special_tokens: bool,
+        clean_up_tokenization_spaces: bool,
+    ) -> Vec<String>;
+
+    fn encode_to_shards(
+        &self,
+        documents: &mut dyn Iterator<Item = Result<String, TokenizerError>>,
+        chunk_size: usize,
+        writer: &mut TokenShardWriter,
+    ) -> Result<(), TokenizerError>;
+
+    fn vocab(&self) -> &dyn Vocab;
+}
+
+struct TokenizerWrapper<T, V> {
+    tokenizer: T,
+    vocab_type: PhantomData<fn() -> V>,
+}
+
+impl<T, V> CliTokenizer for TokenizerWrapper<T, V>
+where
+    T: MultiThreadedTokenizer<V>,
+    V: Vocab,
+{
+    fn tokenize_list_with_offsets(&self, text_list: &[String]) -> Vec<TokensWithOffsets> {
+        MultiThreadedTokenizer::tokenize_list_with_offsets(&self.tokenizer, text_list)
+    }
+
+    fn count_tokens_list(&self, text_list: &[String]) -> Vec<usize> {
+        MultiThreadedTokenizer::count_tokens_list(&self.tokenizer, text_list)
+    }
+
+    fn encode_list(
+        &self,
+        text_list: &[String],
+        max_len: usize,
+        truncation_strategy: &TruncationStrategy,
+        stride: usize,
+    ) -> Vec<TokenizedInput> {
+        MultiThreadedTokenizer::encode_list(
+            &self.tokenizer,
+            text_list,
+            max_len,
+            truncation_strategy,
+            stride,
+        )
+    }
+
+    fn encode_pair_list(
+        &self,
+        text_list: &[(String, String)],
+        max_len: usize,
+        truncation_strategy: &TruncationStrategy,
+        stride: usize,
+    ) -> Vec<TokenizedInput> {
+        MultiThreadedTokenizer::encode_pair_list(
+            &self.tokenizer,
+            text_list,
+            max_len,
+            truncation_strategy,
+            stride,
+        )
+    }
+
+    fn decode_list(
+        &self,
+        token_ids_list: &[Vec<i64>],
+        skip_special_tokens: bool,
+        clean_up_tokenization_spaces: bool,
+    ) -> Vec<String> {
+        MultiThreadedTokenizer::decode_list(
+            &self.tokenizer,
+            token_ids_list,
+            skip_special_tokens,
+            clean_up_tokenization_spaces,
+        )
+    }
+
+    fn encode_to_shards(
+        &self,
+        documents: &mut dyn Iterator<Item = Result<String, TokenizerError>>,
+        chunk_size: usize,
+        writer: &mut TokenShardWriter,
+    ) -> Result<(), TokenizerError> {
+        encode_to_shards(&self.tokenizer, documents, chunk_size, writer)
+    }
+
+    fn vocab(&self) -> &dyn Vocab {
+        MultiThreadedTokenizer::vocab(&self.tokenizer)
+    }
+}
+
+fn wrap<T, V>(tokenizer: T) -> Box<dyn CliTokenizer>
+where
+    T: MultiThreadedTokenizer<V> + 'static,
+    V: Vocab + 'static,
+{
+    Box::new(TokenizerWrapper {
+        tokenizer,
+        vocab_type: PhantomData,
+    })
+}
+
+fn required<'a>(
+    path: &'a Option<PathBuf>,
+    flag: &str,
+    tokenizer: TokenizerName,
+) -> Result<&'a PathBuf, TokenizerError> {
+    path.as_ref().ok_or_else(|| {
+        let name = tokenizer
+            .to_possible_value()
+            .map(|value| value.get_name().to_string())
+            .unwrap_or_default();
+        TokenizerError::ValueError(format!("--{flag} is required for the {name} tokenizer"))
+    })
+}
+
+/// Loads the tokenizer selected on the command line
+pub fn load_tokenizer(args: &TokenizerArgs) -> Result<Box<dyn CliTokenizer>, TokenizerError> {
+    let name = args.tokenizer;
+    let vocab = || required(&args.vocab, "vocab", name);
+    let merges = || required(&args.merges, "merges", name);
+    let model = || required(&args.model, "model", name);
+    let lower_case = args.lower_case;
+    let strip_accents = args.strip_accents;
+    let add_prefix_space = args.add_prefix_space;
+
+    Ok(match name {
+        TokenizerName::Bert => wrap(BertTokenizer::from_file(
+            vocab()?,
+            lower_case,
+            strip_accents,
+        )?),
+        TokenizerName::Mpnet => wrap(MPNetTokenizer::from_file(
+            vocab()?,
+            lower_case,
+            strip_accents,
+        )?),
+        TokenizerName::Prophetnet => wrap(ProphetNetTokenizer::from_file(
+            vocab()?,
+            lower_case,
+            strip_accents,
+        )?),
+        TokenizerName::Roberta => wrap(RobertaTokenizer::from_file(
+            vocab()?,
+            merges()?,
+            lower_case,
+            add_prefix_space,
+        )?),
+        TokenizerName::Longformer => wrap(LongformerTokenizer::from_file(
+            vocab()?,
+            merges()?,
+            lower_case,
+            add_prefix_space,
+        )?),
+        TokenizerName::OpenaiGpt => wrap(OpenAiGptTokenizer::from_file(
+            vocab()?,
+            merges()?,
+            lower_case,
+        )?),
+        TokenizerName::Gpt2 => wrap(Gpt2Tokenizer::from_file(vocab()?, merges()?, lower_case)?),
+        TokenizerName::Ctrl => wrap(CtrlTokenizer::from_file(vocab()?, merges()?, lower_case)?),
+        TokenizerName::Deberta => wrap(DeBERTaTokenizer::from_file(
+            vocab()?,
+            merges()?,
+            lower_case,
+        )?),
+        TokenizerName::Clip => wrap(ClipTokenizer::from_file(vocab()?, merges()?)?),
+        TokenizerName::Whisper => wrap(WhisperTokenizer::from_file(vocab()?, merges()?)?),
+        TokenizerName::Xlm => wrap(XLMTokenizer::from_file(
+            vocab()?,
+            merges()?,
+            &args.language,
+            lower_case,
+        )?),
+        TokenizerName::Phobert => wrap(PhoBertTokenizer::from_file(vocab()?, merges()?)?),
+        TokenizerName::ByteLevelBpe => {
+            let mut tokenizer = match &args.merges {
+                Some(merges) => {
+                    ByteLevelBpeTokenizer::from_file(vocab()?, merges, args.preset.into())?
+                }
+                None => {
+                    ByteLevelBpeTokenizer::from_tokenizer_json_file(vocab()?, args.preset.into())?
+                }
+            };
+            tokenizer.set_add_prefix_space(add_prefix_space);
+            wrap(tokenizer)
+        }
+        TokenizerName::Albert => wrap(AlbertTokenizer::from_file(
+            model()?,
+            lower_case,
+            strip_accents,
+        )?),
+        TokenizerName::Xlnet => wrap(XLNetTokenizer::from_file(
+            model()?,
+            lower_case,
+            strip_accents,
+        )?),
+        TokenizerName::T5 => wrap(T5Tokenizer::from_file(model()?, lower_case)?),
+        TokenizerName::Reformer => wrap(ReformerTokenizer::from_file(model()?, lower_case)?),
+        TokenizerName::DebertaV2 => wrap(DeBERTaV2Tokenizer::from_file(
+            model()?,
+            lower_case,
+            strip_accents,
+            add_prefix_space,
+        )?),
+        TokenizerName::Bigbird => wrap(BigBirdTokenizer::from_file(model()?, lower_case)?),
+        TokenizerName::Fnet => wrap(FNetTokenizer::from_file(
+            model()?,
+            lower_case,
+            strip_accents,
+        )?),
+        TokenizerName::Pegasus => wrap(PegasusTokenizer::from_file(model()?, lower_case)?),
+        TokenizerName::Mbart50 => wrap(MBart50Tokenizer::from_file(model()?, lower_case)?),
+        TokenizerName::XlmRoberta => wrap(XLMRobertaTokenizer::from_file(model()?, lower_case)?),
+        TokenizerName::SentencePiece => {
+            wrap(SentencePieceTokenizer::from_file(model()?, lower_case)?)
+        }
+        TokenizerName::SentencePieceBpe => {
+            wrap(SentencePieceBpeTokenizer::from_file(model()?, lower_case)?)
+        }
+        TokenizerName::Marian => wrap(MarianTokenizer::from_files(vocab()?, model()?, lower_case)?),
+        TokenizerName::M2m100 => wrap(M2M100Tokenizer::from_files(vocab()?, model()?, lower_case)?),
+        TokenizerName::Nllb => wrap(NLLBTokenizer::from_files(vocab()?, model()?)?),
+        TokenizerName::Byt5 => wrap(ByT5Tokenizer::new()),
+        TokenizerName::Canine => wrap(CanineTokenizer::new()),
+        TokenizerName::Wav2vec2 => {
+            wrap(Wav2Vec2CtcTokenizer::from_file(vocab()?, args.upper_case)?)
+        }
+        TokenizerName::Esm => wrap(EsmTokenizer::from_file(vocab()?)?),
+        TokenizerName::Kmer => match &args.vocab {
+            Some(vocab) => wrap(KmerTokenizer::from_file(
+                vocab,
+                args.kmer_size,
+                args.kmer_stride,
+            )?),
+            None => wrap(KmerTokenizer::from_alphabet(
+                "ACGT",
+                args.kmer_size,
+                args.kmer_stride,
+            )?),
+        },
+    })
+}
@@ -0,0 +1,81 @@
+// Copyright 2019-2020 Guillaume Becquin
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//     http://www.apache.org/licenses/LICENSE-2.0
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # rust-tokenizers command-line interface
+//!
+//! Tokenizes, encodes, decodes and counts tokens of text files with any of the tokenizers of the
+//! crate, writes binary token shards for pretraining, and inspects or compares their
+//! vocabularies. Inputs are read line by line from a file or stdin and processed in batches with
+//! the multi-threaded tokenizers. Outputs are written as JSON lines to a file or stdout.
+//!
+//! ```bash
+//! echo "Hello world" | rust-tokenizers tokenize --tokenizer bert --vocab vocab.txt --lower-case
+//! rust-tokenizers encode --tokenizer roberta --vocab vocab.json --merges merges.txt \
+//!     --input data.jsonl --max-len 128 --stride 16 > encoded.jsonl
+//! rust-tokenizers decode --tokenizer roberta --vocab vocab.json --merges merges.txt \
+//!     --input encoded.jsonl --skip-special-tokens
+//! rust-tokenizers shard --tokenizer gpt2 --vocab vocab.json --merges merges.txt \
+//!     --eos-token "<|endoftext|>" --output-dir shards corpus_*.txt
+//! rust-tokenizers vocab inspect --tokenizer bert --vocab vocab.txt --check
+//! rust-tokenizers vocab diff-merges merges.txt other_merges.txt
+//! ```
+//!
+//! Requires the `cli` feature: `cargo install rust_tokenizers --features cli`.
+
+mod commands;
+mod loader;
+
+use crate::commands::{CountArgs, DecodeArgs, EncodeArgs, ShardArgs, TokenizeArgs, VocabCommand};
+use clap::{Parser, Subcommand};
+
+#[derive(Debug, Parser)]
+#[command(
+    name = "rust-tokenizers",
+    version,
+    about = "Tokenize, encode and decode text with rust_tokenizers"
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Tokenize text (arguments or input lines) and print the tokens with their offsets
+    Tokenize(TokenizeArgs),
+    /// Encode JSON lines into token ids, masks and offsets
+    Encode(EncodeArgs),
+    /// Decode token ids back to text
+    Decode(DecodeArgs),
+    /// Count the tokens of each input line
+    Count(CountArgs),
+    /// Encode text or JSON lines documents into binary token shards for pretraining
+    Shard(ShardArgs),
+    /// Inspect and compare vocabularies, merges and SentencePiece models
+    #[command(subcommand)]
+    Vocab(VocabCommand),
+}
+
+fn main() {
+    let cli = Cli::parse();
+    let result = match cli.command {
+        Command::Tokenize(args) => commands::tokenize(args),
+        Command::Encode(args) => commands::encode(args),
+        Command::Decode(args) => commands::decode(args),
+        Command::Count(args) => commands::count(args),
+        Command::Shard(args) => commands::shard(args),
+        Command::Vocab(command) => commands::vocab(command),
+    };
+    if let Err(error) = result {
+        eprintln!("error: {error}");
+        std::process::exit(1);
+    }
+}
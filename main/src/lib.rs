@@ -20,7 +20,10 @@
 //! - Sentence Piece (unigram model)
 //! - BERT
 //! - DistilBERT
+//! - MPNet
 //! - RoBERTa
+//! - Longformer
+//! - BigBird
 //! - FNet
 //! - GPT
 //! - GPT2
@@ -33,6 +36,15 @@
 //! - NLLB
 //! - DeBERTa
 //! - DeBERTa (v2)
+//! - CLIP
+//! - Whisper
+//! - XLM and FlauBERT (Moses pre-tokenization)
+//! - PhoBERT
+//! - Byte-level BPE (GPT-NeoX, Pythia, BLOOM, StarCoder, Falcon; from `tokenizer.json`)
+//! - ByT5
+//! - CANINE
+//! - Wav2Vec2 (CTC)
+//! - ESM and DNABERT (k-mers)
 //!
 //! The library is structured into vocabularies (for the encoding and decoding of the tokens and registration of special tokens)
 //! and tokenizers (splitting the input text into tokens). Generally, a tokenizer will contain a reference vocabulary that may
@@ -71,9 +83,9 @@ pub mod vocab;
 pub mod adapters;
 pub mod error;
 pub use tokenizer::base_tokenizer::{
-    ConsolidatableTokens, ConsolidatedTokenIterator, Mask, Offset, OffsetSize, Token,
-    TokenIdsWithOffsets, TokenIdsWithSpecialTokens, TokenRef, TokenTrait, TokenizedInput,
-    TokensWithOffsets,
+    CompactTokenizedInput, ConsolidatableTokens, ConsolidatedTokenIterator, Mask, Offset,
+    OffsetSize, Token, TokenId, TokenIdsWithOffsets, TokenIdsWithSpecialTokens, TokenRef,
+    TokenTrait, TokenizedInput, TokensWithOffsets, WordTokenCount,
 };
 
 #[macro_use]
@@ -12,13 +12,18 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::convert::TryFrom;
 use std::path::Path;
+use std::sync::RwLock;
 
 use crate::error::TokenizerError;
+use crate::tokenizer::dictionary_segmenter::DictionarySegmenter;
+use crate::tokenizer::offset_units::{OffsetConverter, OffsetUnit};
 use crate::tokenizer::tokenization_utils::{clean_text, lowercase};
 use crate::tokenizer::tokenization_utils::{
+    get_special_token_matcher, is_cjk_char, is_punctuation, is_whitespace, normalize_text,
     split_on_punct, split_on_special_tokens, strip_accents, tokenize_cjk_chars, truncate_sequences,
-    whitespace_tokenize,
+    whitespace_tokenize, SpecialTokenCache,
 };
 use crate::vocab::Vocab;
 use itertools::Itertools;
@@ -34,8 +39,54 @@ pub enum TruncationStrategy {
     OnlyFirst,
     /// Truncate only the second sequence
     OnlySecond,
+    /// Truncate only the first sequence, keeping its first `head` tokens and as many of its last
+    /// tokens as possible (removing tokens from the middle of the sequence)
+    HeadTail { head: usize },
     /// Do not truncate the sequences
     DoNotTruncate,
+    /// Apply a truncation strategy (`LongestFirst`, `OnlyFirst` or `OnlySecond`), removing tokens
+    /// from the given side of the sequences. Built with `TruncationStrategy::with_side`.
+    WithSide(Box<TruncationStrategy>, TruncationSide),
+}
+
+/// # Truncation side
+/// Side of the sequences tokens are removed from when truncating. Strategies remove tokens from
+/// the right (end of the sequences) unless a side is given with `TruncationStrategy::with_side`.
+#[derive(Debug, PartialEq, Clone, Copy, Eq)]
+pub enum TruncationSide {
+    /// Remove tokens from the end of the sequences
+    Right,
+    /// Remove tokens from the start of the sequences, keeping the most recent context
+    Left,
+}
+
+impl TruncationStrategy {
+    /// Returns the truncation strategy removing tokens from the given side of the sequences. The
+    /// side is ignored by the `HeadTail` and `DoNotTruncate` strategies.
+    ///
+    /// # Parameters
+    /// - side (`TruncationSide`): side of the sequences tokens are removed from
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_tokenizers::tokenizer::{TruncationSide, TruncationStrategy};
+    /// let truncation_strategy = TruncationStrategy::LongestFirst.with_side(TruncationSide::Left);
+    /// ```
+    pub fn with_side(self, side: TruncationSide) -> TruncationStrategy {
+        match self {
+            TruncationStrategy::WithSide(strategy, _) => strategy.with_side(side),
+            strategy => TruncationStrategy::WithSide(Box::new(strategy), side),
+        }
+    }
+
+    /// Returns the strategy applied and the side tokens are removed from
+    pub(crate) fn split_side(&self) -> (&TruncationStrategy, TruncationSide) {
+        match self {
+            TruncationStrategy::WithSide(strategy, side) => (strategy.split_side().0, *side),
+            strategy => (strategy, TruncationSide::Right),
+        }
+    }
 }
 
 /// Crate-wide primitive used to store offset positions
@@ -396,6 +447,151 @@ pub struct TokenizedInput {
     pub mask: Vec<Mask>,
 }
 
+/// # Token id type
+/// Integer types that token ids can be converted to for compact encoding outputs
+/// (see `CompactTokenizedInput`). Implemented for `i64`, `i32`, `u32` and `u16`.
+pub trait TokenId:
+    Copy + Send + Sync + std::fmt::Debug + PartialEq + TryFrom<i64> + Into<i64>
+{
+}
+
+impl TokenId for i64 {}
+impl TokenId for i32 {}
+impl TokenId for u32 {}
+impl TokenId for u16 {}
+
+fn convert_token_ids<I: TokenId>(token_ids: Vec<i64>) -> Result<Vec<I>, TokenizerError> {
+    token_ids
+        .into_iter()
+        .map(|id| {
+            I::try_from(id).map_err(|_| {
+                TokenizerError::ValueError(format!(
+                    "Token id {id} does not fit in the requested id type"
+                ))
+            })
+        })
+        .collect()
+}
+
+/// # Compact tokenized input
+/// Encoding output with token ids stored as a narrower integer type (`u32` by default), halving the
+/// memory used by the token ids kept after encoding and allowing them to be handed to runtimes
+/// expecting `u32`/`i32` inputs without further conversion. Tokenizers encode with `i64` ids: the
+/// compact input is obtained by converting a `TokenizedInput` once it is encoded.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Clone)]
+pub struct CompactTokenizedInput<I: TokenId = u32> {
+    /// Vector of token IDs
+    pub token_ids: Vec<I>,
+
+    /// Vector segments ids (for example for BERT segments are separated with a [SEP] marker, each incrementing the segment ID).
+    /// This vector has the same length as token_ids.
+    pub segment_ids: Vec<i8>,
+
+    /// Flags tokens as special tokens (1) or not (0). This vector has the same length as token_ids.
+    pub special_tokens_mask: Vec<i8>,
+
+    /// Vector containing overflowing tokens, populated following a truncation step
+    pub overflowing_tokens: Vec<I>,
+
+    /// Number of overflowing tokens following a truncation step. this equals the length `overflowing_tokens`
+    pub num_truncated_tokens: usize,
+
+    /// Offset information (as start and end positions) in relation to the original text. Tokens that can not be related to the
+    /// original source are registered as None.
+    pub token_offsets: Vec<Option<Offset>>,
+
+    /// Offset information (as a sequence of positions) in relation to the original text. Tokens that can not be related to the
+    /// original source are registered as None.
+    pub reference_offsets: Vec<Vec<OffsetSize>>,
+
+    /// Masks tokens providing information on the type of tokens. This vector has the same length as token_ids.
+    pub mask: Vec<Mask>,
+}
+
+impl TokenizedInput {
+    /// Converts the tokenized input to a `CompactTokenizedInput` with token ids of type `I`.
+    ///
+    /// # Returns
+    /// - `CompactTokenizedInput<I>`, or a `TokenizerError::ValueError` if a token id does not fit
+    ///   in the target type
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use rust_tokenizers::tokenizer::{BaseTokenizer, Tokenizer, TruncationStrategy};
+    /// use rust_tokenizers::vocab::BaseVocab;
+    /// use rust_tokenizers::CompactTokenizedInput;
+    /// let tokenizer: BaseTokenizer<BaseVocab> =
+    ///     BaseTokenizer::from_file("path/to/vocab/file", false, false).unwrap();
+    ///
+    /// let encoded_input = tokenizer.encode("Hello, world!", None, 5, &TruncationStrategy::LongestFirst, 0);
+    /// let compact_input: CompactTokenizedInput<u32> = encoded_input.into_compact().unwrap();
+    /// ```
+    pub fn into_compact<I: TokenId>(self) -> Result<CompactTokenizedInput<I>, TokenizerError> {
+        Ok(CompactTokenizedInput {
+            token_ids: convert_token_ids(self.token_ids)?,
+            segment_ids: self.segment_ids,
+            special_tokens_mask: self.special_tokens_mask,
+            overflowing_tokens: convert_token_ids(self.overflowing_tokens)?,
+            num_truncated_tokens: self.num_truncated_tokens,
+            token_offsets: self.token_offsets,
+            reference_offsets: self.reference_offsets,
+            mask: self.mask,
+        })
+    }
+
+    /// Converts the offsets of an input encoded from a single text to the given unit (Unicode
+    /// code points, UTF-8 bytes or UTF-16 code units). Offsets of inputs encoded from a pair of
+    /// texts relate to either text and can be converted with an `OffsetConverter` for each text.
+    ///
+    /// # Parameters
+    /// - text (`&str`): text the input was encoded from
+    /// - unit (`OffsetUnit`): unit of the returned offsets
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use rust_tokenizers::tokenizer::{BaseTokenizer, OffsetUnit, Tokenizer, TruncationStrategy};
+    /// use rust_tokenizers::vocab::BaseVocab;
+    /// let tokenizer: BaseTokenizer<BaseVocab> =
+    ///     BaseTokenizer::from_file("path/to/vocab/file", false, false).unwrap();
+    ///
+    /// let text = "Hello, wörld!";
+    /// let encoded_input = tokenizer.encode(text, None, 5, &TruncationStrategy::LongestFirst, 0);
+    /// let byte_input = encoded_input.into_offset_unit(text, OffsetUnit::Byte);
+    /// ```
+    pub fn into_offset_unit(self, text: &str, unit: OffsetUnit) -> TokenizedInput {
+        if unit == OffsetUnit::Char {
+            return self;
+        }
+        let converter = OffsetConverter::new(text);
+        TokenizedInput {
+            token_offsets: converter.convert_offsets(&self.token_offsets, unit),
+            reference_offsets: converter.convert_reference_offsets(&self.reference_offsets, unit),
+            ..self
+        }
+    }
+}
+
+impl<I: TokenId> From<CompactTokenizedInput<I>> for TokenizedInput {
+    fn from(other: CompactTokenizedInput<I>) -> Self {
+        TokenizedInput {
+            token_ids: other.token_ids.into_iter().map(Into::into).collect(),
+            segment_ids: other.segment_ids,
+            special_tokens_mask: other.special_tokens_mask,
+            overflowing_tokens: other
+                .overflowing_tokens
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+            num_truncated_tokens: other.num_truncated_tokens,
+            token_offsets: other.token_offsets,
+            reference_offsets: other.reference_offsets,
+            mask: other.mask,
+        }
+    }
+}
+
 /// # Encoded input with special tokens
 /// Intermediate tokenization steps before truncation to a maximum length, after encoding and addition of special tokens
 #[derive(Debug, Clone)]
@@ -460,6 +656,37 @@ pub struct TokenIdsWithOffsets {
     pub masks: Vec<Mask>,
 }
 
+/// # Word token count
+/// Word of a text (piece of the text produced by the pre-tokenization) with the number of tokens it
+/// is split into, as returned by `Tokenizer::count_word_tokens`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WordTokenCount {
+    /// Byte position of the start of the word in the text
+    pub begin: usize,
+    /// Byte position of the end of the word in the text
+    pub end: usize,
+    /// Number of tokens of the word
+    pub num_tokens: usize,
+}
+
+/// Adds `num_tokens` tokens to the word spanning `begin..end`, extending the last word of the list
+/// if it spans the same bytes
+pub(crate) fn push_word_tokens(
+    words: &mut Vec<WordTokenCount>,
+    begin: usize,
+    end: usize,
+    num_tokens: usize,
+) {
+    match words.last_mut() {
+        Some(word) if word.begin == begin && word.end == end => word.num_tokens += num_tokens,
+        _ => words.push(WordTokenCount {
+            begin,
+            end,
+            num_tokens,
+        }),
+    }
+}
+
 /// # Base trait for tokenizers
 pub trait Tokenizer<T: Vocab> {
     /// returns a reference to the tokenizer vocabulary
@@ -554,6 +781,40 @@ pub trait Tokenizer<T: Vocab> {
         }
     }
 
+    /// Tokenize a string, returning tokens with offset information expressed in the given unit
+    /// (Unicode code points, UTF-8 bytes or UTF-16 code units)
+    ///
+    /// # Parameters
+    /// - text : text (string-like) to tokenize
+    /// - unit (`OffsetUnit`): unit of the returned offsets
+    ///
+    /// # Returns
+    /// `TokensWithOffsets` with the tokens and their offset information
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use rust_tokenizers::tokenizer::{BaseTokenizer, OffsetUnit, Tokenizer};
+    /// use rust_tokenizers::vocab::BaseVocab;
+    /// let tokenizer: BaseTokenizer<BaseVocab> =
+    ///     BaseTokenizer::from_file("path/to/vocab/file", false, false).unwrap();
+    ///
+    /// let text = "Hello, wörld!";
+    /// let tokens = tokenizer.tokenize_with_offsets_in(text, OffsetUnit::Byte);
+    /// ```
+    fn tokenize_with_offsets_in(&self, text: &str, unit: OffsetUnit) -> TokensWithOffsets {
+        let tokens = self.tokenize_with_offsets(text);
+        if unit == OffsetUnit::Char {
+            return tokens;
+        }
+        let converter = OffsetConverter::new(text);
+        TokensWithOffsets {
+            offsets: converter.convert_offsets(&tokens.offsets, unit),
+            reference_offsets: converter.convert_reference_offsets(&tokens.reference_offsets, unit),
+            ..tokens
+        }
+    }
+
     /// Tokenize a TokenRef, returning a sequence of tokens
     ///
     /// # Parameters
@@ -648,6 +909,151 @@ pub trait Tokenizer<T: Vocab> {
             .collect()
     }
 
+    /// Split a string into words with the number of tokens of each word (without special tokens).
+    /// This is a lightweight alternative to `tokenize_to_tokens`, used to count tokens and truncate
+    /// texts without building the tokens and their offsets. The default implementation derives the
+    /// words from `tokenize_to_tokens` (one word per token); tokenizers based on the
+    /// `BaseTokenizer` pipeline (e.g. BERT) override it with a path that does not track offsets.
+    ///
+    /// # Parameters
+    /// - text : text (string-like) to split
+    ///
+    /// # Returns
+    /// `Vec<WordTokenCount>` with the byte span and number of tokens of each word
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use rust_tokenizers::tokenizer::{BertTokenizer, Tokenizer};
+    /// let strip_accents = false;
+    /// let lower_case = false;
+    /// let tokenizer =
+    ///     BertTokenizer::from_file("path/to/vocab/file", lower_case, strip_accents).unwrap();
+    ///
+    /// let words = tokenizer.count_word_tokens("Hello, world!");
+    /// ```
+    fn count_word_tokens(&self, text: &str) -> Vec<WordTokenCount> {
+        let initial_offsets = (0..text.chars().count() as OffsetSize).collect::<Vec<OffsetSize>>();
+        let byte_positions = text
+            .char_indices()
+            .map(|(position, _)| position)
+            .chain(std::iter::once(text.len()))
+            .collect::<Vec<usize>>();
+        let mut words = Vec::new();
+        for token in self.tokenize_to_tokens(TokenRef::new(text, &initial_offsets)) {
+            let (begin, end) = match (
+                token.reference_offsets.iter().min(),
+                token.reference_offsets.iter().max(),
+            ) {
+                (Some(begin), Some(end)) => (*begin as usize, *end as usize + 1),
+                _ => (token.offset.begin as usize, token.offset.end as usize),
+            };
+            let begin = byte_positions[begin.min(byte_positions.len() - 1)];
+            let end = byte_positions[end.min(byte_positions.len() - 1)];
+            words.push(WordTokenCount {
+                begin,
+                end,
+                num_tokens: 1,
+            });
+        }
+        words
+    }
+
+    /// Count the number of tokens of a string (without special tokens). The tokens are counted
+    /// from `count_word_tokens`, without building the tokens, their offsets or ids.
+    ///
+    /// # Parameters
+    /// - text : text (string-like) to tokenize
+    ///
+    /// # Returns
+    /// `usize` number of tokens of the text
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use rust_tokenizers::tokenizer::{BaseTokenizer, Tokenizer};
+    /// use rust_tokenizers::vocab::BaseVocab;
+    /// let strip_accents = false;
+    /// let lower_case = false;
+    /// let tokenizer: BaseTokenizer<BaseVocab> =
+    ///     BaseTokenizer::from_file("path/to/vocab/file", lower_case, strip_accents).unwrap();
+    ///
+    /// let num_tokens = tokenizer.count_tokens("Hello, world!");
+    /// ```
+    fn count_tokens(&self, text: &str) -> usize {
+        if text.trim().is_empty() {
+            return 0;
+        }
+        self.count_word_tokens(text)
+            .iter()
+            .map(|word| word.num_tokens)
+            .sum()
+    }
+
+    /// Truncate a string to the prefix covering its first `max_tokens` tokens (without special
+    /// tokens). The words of the text are counted with `count_word_tokens` and the text is cut after
+    /// the last character of the `max_tokens`-th token. Only the word containing this token is
+    /// tokenized with offsets, when the cut falls inside it. The text is returned as is if it fits
+    /// in the budget.
+    ///
+    /// # Parameters
+    /// - text : text (string-like) to truncate
+    /// - max_tokens (`usize`): maximum number of tokens of the prefix
+    ///
+    /// # Returns
+    /// `&str` longest prefix of the text fitting in `max_tokens` tokens
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use rust_tokenizers::tokenizer::{BaseTokenizer, Tokenizer};
+    /// use rust_tokenizers::vocab::BaseVocab;
+    /// let strip_accents = false;
+    /// let lower_case = false;
+    /// let tokenizer: BaseTokenizer<BaseVocab> =
+    ///     BaseTokenizer::from_file("path/to/vocab/file", lower_case, strip_accents).unwrap();
+    ///
+    /// let prefix = tokenizer.truncate_text_to_tokens("Hello, world!", 2);
+    /// ```
+    fn truncate_text_to_tokens<'a>(&self, text: &'a str, max_tokens: usize) -> &'a str {
+        if text.trim().is_empty() {
+            return text;
+        }
+        let words = self.count_word_tokens(text);
+        if words.iter().map(|word| word.num_tokens).sum::<usize>() <= max_tokens {
+            return text;
+        }
+        let mut end = 0;
+        let mut remaining_tokens = max_tokens;
+        for word in words {
+            if remaining_tokens >= word.num_tokens {
+                end = end.max(word.end);
+                remaining_tokens -= word.num_tokens;
+                continue;
+            }
+            if remaining_tokens > 0 {
+                //the cut falls inside the word: tokenize it with offsets to locate the cut
+                let word_text = &text[word.begin..word.end];
+                let initial_offsets =
+                    (0..word_text.chars().count() as OffsetSize).collect::<Vec<OffsetSize>>();
+                let word_end = self
+                    .tokenize_to_tokens(TokenRef::new(word_text, &initial_offsets))
+                    .iter()
+                    .take(remaining_tokens)
+                    .filter_map(|token| token.reference_offsets.last())
+                    .max()
+                    .map_or(0, |last| *last as usize + 1);
+                let word_end = word_text
+                    .char_indices()
+                    .nth(word_end)
+                    .map_or(word_text.len(), |(position, _)| position);
+                end = end.max(word.begin + word_end);
+            }
+            break;
+        }
+        &text[..end]
+    }
+
     /// Convert a slice of string-like to a vector ot token indices
     ///
     /// # Parameters
@@ -919,6 +1325,107 @@ pub trait Tokenizer<T: Vocab> {
             .collect()
     }
 
+    /// Encode a string-like text (tokenization followed by encoding), returning token ids as a
+    /// compact integer type `I` (e.g. `u32`). See `encode` for the description of the parameters.
+    ///
+    /// The text is encoded with `encode` and the token ids of the output are then converted with
+    /// `TokenizedInput::into_compact`: only the returned encoding is compact, the `i64` ids are
+    /// allocated while encoding and dropped after the conversion.
+    ///
+    /// # Returns
+    /// `CompactTokenizedInput<I>` containing the encoding output, or a `TokenizerError::ValueError`
+    /// if a token id does not fit in the target type
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use rust_tokenizers::tokenizer::{BaseTokenizer, Tokenizer, TruncationStrategy};
+    /// use rust_tokenizers::vocab::BaseVocab;
+    /// let strip_accents = false;
+    /// let lower_case = false;
+    /// let tokenizer: BaseTokenizer<BaseVocab> =
+    ///     BaseTokenizer::from_file("path/to/vocab/file", lower_case, strip_accents).unwrap();
+    ///
+    /// let encoded_input = tokenizer
+    ///     .encode_compact::<u32>(
+    ///         "Hello, world!",
+    ///         Some("How is it going?"),
+    ///         5,
+    ///         &TruncationStrategy::LongestFirst,
+    ///         2,
+    ///     )
+    ///     .unwrap();
+    /// ```
+    fn encode_compact<I: TokenId>(
+        &self,
+        text_1: &str,
+        text_2: Option<&str>,
+        max_len: usize,
+        truncation_strategy: &TruncationStrategy,
+        stride: usize,
+    ) -> Result<CompactTokenizedInput<I>, TokenizerError> {
+        self.encode(text_1, text_2, max_len, truncation_strategy, stride)
+            .into_compact()
+    }
+
+    /// Encode a sequence of string-like texts, returning token ids as a compact integer type `I`.
+    /// See `encode_list` for the description of the parameters. Each text is converted as soon as
+    /// it is encoded (see `encode_compact`), the `i64` ids of a single text are held at a time.
+    ///
+    /// # Returns
+    /// `Vec<CompactTokenizedInput<I>>` containing the encoding output for each provided text, or a
+    /// `TokenizerError::ValueError` if a token id does not fit in the target type
+    fn encode_list_compact<I: TokenId, S>(
+        &self,
+        text_list: &[S],
+        max_len: usize,
+        truncation_strategy: &TruncationStrategy,
+        stride: usize,
+    ) -> Result<Vec<CompactTokenizedInput<I>>, TokenizerError>
+    where
+        S: AsRef<str>,
+    {
+        text_list
+            .as_ref()
+            .iter()
+            .map(|text| {
+                self.encode_compact(text.as_ref(), None, max_len, truncation_strategy, stride)
+            })
+            .collect()
+    }
+
+    /// Encode a sequence of string-like text pairs, returning token ids as a compact integer type `I`.
+    /// See `encode_pair_list` for the description of the parameters. Each pair is converted as soon
+    /// as it is encoded (see `encode_compact`), the `i64` ids of a single pair are held at a time.
+    ///
+    /// # Returns
+    /// `Vec<CompactTokenizedInput<I>>` containing the encoding output for each provided pair, or a
+    /// `TokenizerError::ValueError` if a token id does not fit in the target type
+    fn encode_pair_list_compact<I: TokenId, S>(
+        &self,
+        text_list: &[(S, S)],
+        max_len: usize,
+        truncation_strategy: &TruncationStrategy,
+        stride: usize,
+    ) -> Result<Vec<CompactTokenizedInput<I>>, TokenizerError>
+    where
+        S: AsRef<str>,
+    {
+        text_list
+            .as_ref()
+            .iter()
+            .map(|text| {
+                self.encode_compact(
+                    text.0.as_ref(),
+                    Some(text.1.as_ref()),
+                    max_len,
+                    truncation_strategy,
+                    stride,
+                )
+            })
+            .collect()
+    }
+
     /// Decode a sequence of token indices to a sequence of Strings, optionally skipping special indices
     ///
     /// # Parameters
@@ -1299,6 +1806,77 @@ where
             .collect()
     }
 
+    /// Count the number of tokens of a list of strings (with multithreading). This calls
+    /// `count_tokens` on the list provided.
+    ///
+    /// # Parameters
+    /// - text_list: list of strings to count the tokens of
+    ///
+    /// # Returns
+    /// `Vec<usize>` with the number of tokens of each text
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use rust_tokenizers::tokenizer::{BaseTokenizer, MultiThreadedTokenizer};
+    /// use rust_tokenizers::vocab::BaseVocab;
+    /// let strip_accents = false;
+    /// let lower_case = false;
+    /// let tokenizer: BaseTokenizer<BaseVocab> =
+    ///     BaseTokenizer::from_file("path/to/vocab/file", lower_case, strip_accents).unwrap();
+    ///
+    /// let texts = ["Hello, world!", "Second sentence"];
+    /// let num_tokens = tokenizer.count_tokens_list(&texts);
+    /// ```
+    fn count_tokens_list<S>(&self, text_list: &[S]) -> Vec<usize>
+    where
+        S: AsRef<str> + Sync,
+    {
+        text_list
+            .as_ref()
+            .par_iter()
+            .map(|text| self.count_tokens(text.as_ref()))
+            .collect()
+    }
+
+    /// Truncate a list of strings to their longest prefix fitting in `max_tokens` tokens (with
+    /// multithreading). This calls `truncate_text_to_tokens` on the list provided.
+    ///
+    /// # Parameters
+    /// - text_list: list of strings to truncate
+    /// - max_tokens (`usize`): maximum number of tokens of the prefixes
+    ///
+    /// # Returns
+    /// `Vec<&str>` with the truncated texts
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use rust_tokenizers::tokenizer::{BaseTokenizer, MultiThreadedTokenizer};
+    /// use rust_tokenizers::vocab::BaseVocab;
+    /// let strip_accents = false;
+    /// let lower_case = false;
+    /// let tokenizer: BaseTokenizer<BaseVocab> =
+    ///     BaseTokenizer::from_file("path/to/vocab/file", lower_case, strip_accents).unwrap();
+    ///
+    /// let texts = ["Hello, world!", "Second sentence"];
+    /// let prefixes = tokenizer.truncate_text_list_to_tokens(&texts, 2);
+    /// ```
+    fn truncate_text_list_to_tokens<'a, S>(
+        &self,
+        text_list: &'a [S],
+        max_tokens: usize,
+    ) -> Vec<&'a str>
+    where
+        S: AsRef<str> + Sync,
+    {
+        text_list
+            .as_ref()
+            .par_iter()
+            .map(|text| self.truncate_text_to_tokens(text.as_ref(), max_tokens))
+            .collect()
+    }
+
     /// Multithreaded encoding of a sequence of string-like texts (tokenization followed by encoding). Not that in contrast
     /// with `encode` optional second text, each text provided is encoded independently.
     ///
@@ -1414,6 +1992,64 @@ where
             .collect()
     }
 
+    /// Multithreaded encoding of a sequence of string-like texts, returning token ids as a compact
+    /// integer type `I`. See `encode_list` for the description of the parameters. Each text is
+    /// converted as soon as it is encoded (see `Tokenizer::encode_compact`).
+    ///
+    /// # Returns
+    /// `Vec<CompactTokenizedInput<I>>` containing the encoding output for each provided text, or a
+    /// `TokenizerError::ValueError` if a token id does not fit in the target type
+    fn encode_list_compact<I: TokenId, S>(
+        &self,
+        text_list: &[S],
+        max_len: usize,
+        truncation_strategy: &TruncationStrategy,
+        stride: usize,
+    ) -> Result<Vec<CompactTokenizedInput<I>>, TokenizerError>
+    where
+        S: AsRef<str> + Sync,
+    {
+        text_list
+            .as_ref()
+            .par_iter()
+            .map(|text| {
+                self.encode_compact(text.as_ref(), None, max_len, truncation_strategy, stride)
+            })
+            .collect()
+    }
+
+    /// Multithreaded encoding of a sequence of string-like text pairs, returning token ids as a
+    /// compact integer type `I`. See `encode_pair_list` for the description of the parameters.
+    /// Each pair is converted as soon as it is encoded (see `Tokenizer::encode_compact`).
+    ///
+    /// # Returns
+    /// `Vec<CompactTokenizedInput<I>>` containing the encoding output for each provided pair, or a
+    /// `TokenizerError::ValueError` if a token id does not fit in the target type
+    fn encode_pair_list_compact<I: TokenId, S>(
+        &self,
+        text_list: &[(S, S)],
+        max_len: usize,
+        truncation_strategy: &TruncationStrategy,
+        stride: usize,
+    ) -> Result<Vec<CompactTokenizedInput<I>>, TokenizerError>
+    where
+        S: AsRef<str> + Sync,
+    {
+        text_list
+            .as_ref()
+            .par_iter()
+            .map(|text| {
+                self.encode_compact(
+                    text.0.as_ref(),
+                    Some(text.1.as_ref()),
+                    max_len,
+                    truncation_strategy,
+                    stride,
+                )
+            })
+            .collect()
+    }
+
     /// Multithreaded conversion a list of sequence of ids (integer) into a string, using the tokenizer and vocabulary
     /// with options to remove special tokens and clean up tokenization spaces. This calls `decode`
     /// for each provided sequence of ids
@@ -1472,8 +2108,10 @@ where
 /// This tokenizer is used as a pre-tokenizer step in the BERT and GPT tokenizers.
 pub struct BaseTokenizer<T: Vocab> {
     vocab: T,
+    special_token_cache: SpecialTokenCache,
     lower_case: bool,
     strip_accents: bool,
+    word_segmenter: Option<DictionarySegmenter>,
 }
 
 impl<T: Vocab + Sync> BaseTokenizer<T> {
@@ -1512,6 +2150,8 @@ impl<T: Vocab + Sync> BaseTokenizer<T> {
             vocab,
             lower_case,
             strip_accents,
+            special_token_cache: RwLock::new(None),
+            word_segmenter: None,
         })
     }
 
@@ -1543,6 +2183,8 @@ impl<T: Vocab + Sync> BaseTokenizer<T> {
             vocab,
             lower_case,
             strip_accents,
+            special_token_cache: RwLock::new(None),
+            word_segmenter: None,
         })
     }
 
@@ -1573,6 +2215,130 @@ impl<T: Vocab + Sync> BaseTokenizer<T> {
             vocab,
             lower_case,
             strip_accents,
+            special_token_cache: RwLock::new(None),
+            word_segmenter: None,
+        }
+    }
+
+    /// Sets a dictionary-based word segmenter, replacing the split of CJK text into single characters
+    ///
+    /// # Parameters
+    /// - word_segmenter (`Option<DictionarySegmenter>`): word segmenter (`None` restores the character split)
+    pub fn set_word_segmenter(&mut self, word_segmenter: Option<DictionarySegmenter>) {
+        self.word_segmenter = word_segmenter;
+    }
+
+    /// Runs the tokenization pipeline on a text without tracking offsets, calling `f` with the byte
+    /// span of each word (piece of the text after the whitespace, special token, punctuation and
+    /// CJK splits, without the trailing characters removed by the normalization) and the text of
+    /// each of its tokens. Used by `count_word_tokens`.
+    pub(crate) fn for_each_word_token<F>(&self, text: &str, mut f: F)
+    where
+        F: FnMut(usize, usize, &str),
+    {
+        let matcher = get_special_token_matcher(&self.vocab, &self.special_token_cache);
+        let mut buffer = String::new();
+        let mut word_begin = None;
+        //split on whitespace
+        for (position, character) in text
+            .char_indices()
+            .chain(std::iter::once((text.len(), ' ')))
+        {
+            if !is_whitespace(&character) {
+                word_begin.get_or_insert(position);
+                continue;
+            }
+            let begin = match word_begin.take() {
+                Some(begin) => begin,
+                None => continue,
+            };
+            //split on special tokens
+            let word = &text[begin..position];
+            let mut special_end = 0;
+            for special in matcher.find_iter(word) {
+                let preceding = word[special_end..special.start].trim_end();
+                self.split_word(begin + special_end, preceding, &mut buffer, &mut f);
+                f(
+                    begin + special.start,
+                    begin + special.end,
+                    &word[special.clone()],
+                );
+                special_end = special.end;
+            }
+            self.split_word(
+                begin + special_end,
+                &word[special_end..],
+                &mut buffer,
+                &mut f,
+            );
+        }
+    }
+
+    /// Splits a word without special tokens on punctuation and CJK characters (or with the word
+    /// segmenter), calling `f` with the normalized text of the tokens
+    fn split_word(
+        &self,
+        begin: usize,
+        word: &str,
+        buffer: &mut String,
+        f: &mut dyn FnMut(usize, usize, &str),
+    ) {
+        let mut piece_begin = 0;
+        for (position, character) in word.char_indices() {
+            let is_separator = if self.word_segmenter.is_some() {
+                is_punctuation(&character)
+            } else {
+                is_punctuation(&character) || is_cjk_char(&character)
+            };
+            if is_separator {
+                let end = position + character.len_utf8();
+                self.segment_piece(begin + piece_begin, &word[piece_begin..position], buffer, f);
+                self.segment_piece(begin + position, &word[position..end], buffer, f);
+                piece_begin = end;
+            }
+        }
+        self.segment_piece(begin + piece_begin, &word[piece_begin..], buffer, f);
+    }
+
+    /// Segments a piece with the word segmenter (if set) and normalizes its tokens, calling `f`
+    /// with the tokens that are not empty
+    fn segment_piece(
+        &self,
+        begin: usize,
+        piece: &str,
+        buffer: &mut String,
+        f: &mut dyn FnMut(usize, usize, &str),
+    ) {
+        //the word ends with the last character that is not removed by the normalization
+        let mut character_buffer = [0; 4];
+        let piece = piece.trim_end_matches(|character: char| {
+            let character = character.encode_utf8(&mut character_buffer);
+            normalize_text(character, self.lower_case, self.strip_accents, buffer);
+            buffer.is_empty()
+        });
+        if piece.is_empty() {
+            return;
+        }
+        let end = begin + piece.len();
+        match &self.word_segmenter {
+            Some(word_segmenter) => {
+                let initial_offsets =
+                    (0..piece.chars().count() as OffsetSize).collect::<Vec<OffsetSize>>();
+                for token in
+                    word_segmenter.tokenize_to_tokens(TokenRef::new(piece, &initial_offsets))
+                {
+                    normalize_text(&token.text, self.lower_case, self.strip_accents, buffer);
+                    if !buffer.is_empty() {
+                        f(begin, end, buffer);
+                    }
+                }
+            }
+            None => {
+                normalize_text(piece, self.lower_case, self.strip_accents, buffer);
+                if !buffer.is_empty() {
+                    f(begin, end, buffer);
+                }
+            }
         }
     }
 }
@@ -1591,24 +2357,25 @@ impl<T: Vocab + Sync + Send> Tokenizer<T> for BaseTokenizer<T> {
             .into_iter()
             .flat_map(|token| {
                 //split on special tokens
-                split_on_special_tokens(token, &self.vocab)
+                split_on_special_tokens(token, &self.vocab, &self.special_token_cache)
             })
             .flat_map(|token| {
                 //split on punctuation (with care for maintaining special values)
                 split_on_punct(token)
             })
             .flat_map(|token| {
-                //tokenize CJK characters so each character is one token
-                tokenize_cjk_chars(token)
-            })
-            .map(|token| {
                 // v-- this is where the token gets owned, all steps above handle TokenRefs (dealing with &str)
-                let mut token = Token {
-                    text: token.text.to_string(),
-                    offset: token.offset,
-                    reference_offsets: token.reference_offsets.to_vec(),
-                    mask: token.mask,
-                };
+                match &self.word_segmenter {
+                    //segment CJK text into words using the dictionary
+                    Some(word_segmenter) => word_segmenter.tokenize_to_tokens(token),
+                    //tokenize CJK characters so each character is one token
+                    None => tokenize_cjk_chars(token)
+                        .into_iter()
+                        .map(|token| token.to_owned())
+                        .collect(),
+                }
+            })
+            .map(|mut token| {
                 if token.mask != Mask::Special && token.mask != Mask::Unknown {
                     clean_text(&mut token, true);
                     //apply the necessary transformations to the actual tokens (unless it's a special value)
@@ -1626,6 +2393,14 @@ impl<T: Vocab + Sync + Send> Tokenizer<T> for BaseTokenizer<T> {
 
         tokens
     }
+
+    fn count_word_tokens(&self, text: &str) -> Vec<WordTokenCount> {
+        let mut words = Vec::new();
+        self.for_each_word_token(text, |begin, end, _| {
+            push_word_tokens(&mut words, begin, end, 1)
+        });
+        words
+    }
 }
 
 impl<T: Vocab + Sync + Send> MultiThreadedTokenizer<T> for BaseTokenizer<T> {}
@@ -2175,6 +2950,45 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_count_and_truncate_tokens() {
+        //        Given
+        let vocab = generate_test_vocab();
+        let base_tokenizer: BaseTokenizer<BertVocab> =
+            BaseTokenizer::from_existing_vocab(vocab, true, true);
+        let text = "Héllo, wörld! 中华人 ";
+        let texts = vec![text, "  ", "hello"];
+
+        //        When
+        let num_tokens = Tokenizer::count_tokens(&base_tokenizer, text);
+        let prefixes: Vec<&str> = (0..9)
+            .map(|max_tokens| Tokenizer::truncate_text_to_tokens(&base_tokenizer, text, max_tokens))
+            .collect();
+
+        //        Then
+        assert_eq!(num_tokens, Tokenizer::tokenize(&base_tokenizer, text).len());
+        assert_eq!(num_tokens, 7);
+        assert_eq!(
+            prefixes,
+            vec![
+                "",
+                "Héllo",
+                "Héllo,",
+                "Héllo, wörld",
+                "Héllo, wörld!",
+                "Héllo, wörld! 中",
+                "Héllo, wörld! 中华",
+                text,
+                text,
+            ]
+        );
+        assert_eq!(base_tokenizer.count_tokens_list(&texts), vec![7, 0, 1]);
+        assert_eq!(
+            base_tokenizer.truncate_text_list_to_tokens(&texts, 1),
+            vec!["Héllo", "  ", "hello"]
+        );
+    }
+
     #[test]
     fn test_encode_single_sentence() {
         //        Given
@@ -2468,6 +3282,132 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_encode_compact() -> anyhow::Result<()> {
+        //        Given
+        let vocab = generate_test_vocab();
+        let base_tokenizer: BaseTokenizer<BertVocab> =
+            BaseTokenizer::from_existing_vocab(vocab, true, true);
+        let truncation_strategy = TruncationStrategy::LongestFirst;
+        let source_texts = vec![("hello world!", "中华人民"), ("hello, unaffable", "world!")];
+
+        //        When
+        let compact_inputs: Vec<CompactTokenizedInput<u32>> = Tokenizer::encode_pair_list_compact(
+            &base_tokenizer,
+            &source_texts,
+            5,
+            &truncation_strategy,
+            0,
+        )?;
+        let compact_inputs_multithreaded: Vec<CompactTokenizedInput<u32>> =
+            MultiThreadedTokenizer::encode_pair_list_compact(
+                &base_tokenizer,
+                &source_texts,
+                5,
+                &truncation_strategy,
+                0,
+            )?;
+
+        //        Then
+        assert_eq!(compact_inputs, compact_inputs_multithreaded);
+        assert_eq!(compact_inputs[0].token_ids, vec![0u32, 1, 7, 8, 9]);
+        assert_eq!(compact_inputs[0].num_truncated_tokens, 2);
+        for ((text_1, text_2), compact_input) in source_texts.iter().zip(compact_inputs) {
+            let expected_input =
+                base_tokenizer.encode(text_1, Some(text_2), 5, &truncation_strategy, 0);
+            assert_eq!(TokenizedInput::from(compact_input), expected_input);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_into_compact_out_of_range() {
+        //        Given
+        let tokenized_input = TokenizedInput {
+            token_ids: vec![0, 70000],
+            segment_ids: vec![0, 0],
+            special_tokens_mask: vec![0, 0],
+            overflowing_tokens: vec![],
+            num_truncated_tokens: 0,
+            token_offsets: vec![None, None],
+            reference_offsets: vec![vec![], vec![]],
+            mask: vec![Mask::None, Mask::None],
+        };
+
+        //        When & Then
+        assert!(tokenized_input.clone().into_compact::<u32>().is_ok());
+        assert!(matches!(
+            tokenized_input.clone().into_compact::<u16>(),
+            Err(TokenizerError::ValueError(_))
+        ));
+        assert!(tokenized_input.into_compact::<i64>().is_ok());
+    }
+
+    #[test]
+    fn test_encode_compact_out_of_range() -> anyhow::Result<()> {
+        //        Given
+        let values: HashMap<String, i64> = [
+            ("[UNK]", 0),
+            ("[CLS]", 1),
+            ("[SEP]", 2),
+            ("hello", 3),
+            ("world", 70000),
+        ]
+        .iter()
+        .map(|(token, id)| (token.to_string(), *id))
+        .collect();
+        let special_token_map = SpecialTokenMap {
+            unk_token: "[UNK]".to_string(),
+            pad_token: None,
+            bos_token: None,
+            sep_token: Some("[SEP]".to_string()),
+            cls_token: Some("[CLS]".to_string()),
+            eos_token: None,
+            mask_token: None,
+            additional_special_tokens: None,
+        };
+        let vocab = BertVocab::from_values_and_special_token_map(values, special_token_map)?;
+        let base_tokenizer: BaseTokenizer<BertVocab> =
+            BaseTokenizer::from_existing_vocab(vocab, true, true);
+        let truncation_strategy = TruncationStrategy::LongestFirst;
+
+        //        When
+        let compact_hello =
+            base_tokenizer.encode_compact::<u16>("hello", None, 5, &truncation_strategy, 0)?;
+        let compact_world =
+            base_tokenizer.encode_compact::<u16>("hello world", None, 5, &truncation_strategy, 0);
+        let compact_list = Tokenizer::encode_list_compact::<u16, _>(
+            &base_tokenizer,
+            &["hello", "world"],
+            5,
+            &truncation_strategy,
+            0,
+        );
+        let compact_overflow = base_tokenizer.encode_compact::<u16>(
+            "hello hello world",
+            None,
+            2,
+            &truncation_strategy,
+            0,
+        );
+
+        //        Then
+        assert_eq!(compact_hello.token_ids, vec![3u16]);
+        assert!(matches!(compact_world, Err(TokenizerError::ValueError(_))));
+        assert!(matches!(compact_list, Err(TokenizerError::ValueError(_))));
+        assert!(matches!(
+            compact_overflow,
+            Err(TokenizerError::ValueError(_))
+        ));
+        assert_eq!(
+            base_tokenizer
+                .encode_compact::<u32>("hello world", None, 5, &truncation_strategy, 0)?
+                .token_ids,
+            vec![3u32, 70000]
+        );
+        Ok(())
+    }
+
     #[test]
     fn test_decode() {
         //        Given
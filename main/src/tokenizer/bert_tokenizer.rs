@@ -16,10 +16,11 @@ use std::path::Path;
 
 use crate::error::TokenizerError;
 use crate::tokenizer::base_tokenizer::{
-    BaseTokenizer, Mask, MultiThreadedTokenizer, Offset, OffsetSize, Token, TokenIdsWithOffsets,
-    TokenIdsWithSpecialTokens, TokenRef, Tokenizer,
+    push_word_tokens, BaseTokenizer, Mask, MultiThreadedTokenizer, Offset, OffsetSize, Token,
+    TokenIdsWithOffsets, TokenIdsWithSpecialTokens, TokenRef, Tokenizer, WordTokenCount,
 };
-use crate::tokenizer::tokenization_utils::tokenize_wordpiece;
+use crate::tokenizer::tokenization_utils::{count_wordpiece, tokenize_wordpiece};
+use crate::tokenizer::DictionarySegmenter;
 use crate::vocab::{BertVocab, Vocab};
 
 /// # BERT tokenizer
@@ -131,6 +132,29 @@ impl BertTokenizer {
             base_tokenizer,
         }
     }
+
+    /// Sets a dictionary-based word segmenter used as pre-tokenization step for CJK text (e.g.
+    /// MeCab-style segmentation for Japanese BERT models), instead of splitting CJK text into
+    /// single characters.
+    ///
+    /// # Parameters
+    /// - word_segmenter (`Option<DictionarySegmenter>`): word segmenter (`None` restores the character split)
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use rust_tokenizers::tokenizer::{
+    ///     BertTokenizer, DictionarySegmenter, LexiconFormat, Tokenizer,
+    /// };
+    /// let mut tokenizer = BertTokenizer::from_file("path/to/vocab/file", false, false).unwrap();
+    /// let segmenter =
+    ///     DictionarySegmenter::from_file("path/to/lexicon.csv", LexiconFormat::MecabCsv).unwrap();
+    ///
+    /// tokenizer.set_word_segmenter(Some(segmenter));
+    /// ```
+    pub fn set_word_segmenter(&mut self, word_segmenter: Option<DictionarySegmenter>) {
+        self.base_tokenizer.set_word_segmenter(word_segmenter);
+    }
 }
 
 impl Tokenizer<BertVocab> for BertTokenizer {
@@ -141,6 +165,16 @@ impl Tokenizer<BertVocab> for BertTokenizer {
         &mut self.vocab
     }
 
+    fn add_tokens(&mut self, tokens: &[&str]) {
+        self.vocab.add_tokens(tokens);
+        self.base_tokenizer.add_tokens(tokens);
+    }
+
+    fn add_extra_ids(&mut self, num_extra_ids: i64) {
+        self.vocab.add_extra_ids(num_extra_ids);
+        self.base_tokenizer.add_extra_ids(num_extra_ids);
+    }
+
     fn tokenize_to_tokens(&self, initial_token: TokenRef) -> Vec<Token> {
         //the base tokenizers does most of the work, we simply add a wordpiece tokenizer on top
         self.base_tokenizer
@@ -150,6 +184,16 @@ impl Tokenizer<BertVocab> for BertTokenizer {
             .collect()
     }
 
+    fn count_word_tokens(&self, text: &str) -> Vec<WordTokenCount> {
+        let mut words = Vec::new();
+        self.base_tokenizer
+            .for_each_word_token(text, |begin, end, token| {
+                let num_tokens = count_wordpiece(token, &self.vocab, 100);
+                push_word_tokens(&mut words, begin, end, num_tokens)
+            });
+        words
+    }
+
     fn convert_tokens_to_string(&self, tokens: Vec<String>) -> String {
         tokens.join(" ").replace(" ##", "").trim().to_owned()
     }
@@ -219,6 +263,7 @@ mod tests {
     use crate::vocab::BertVocab;
     use crate::TokenizedInput;
     use itertools::Itertools;
+    use proptest::prelude::*;
     use std::collections::HashMap;
 
     fn generate_test_vocab() -> BertVocab {
@@ -312,6 +357,59 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_bert_tokenizer_add_tokens() {
+        //        Given
+        let vocab = generate_test_vocab();
+        let mut bert_tokenizer: BertTokenizer =
+            BertTokenizer::from_existing_vocab(vocab, true, true);
+
+        //        When
+        bert_tokenizer.add_tokens(&["<new>"]);
+
+        //        Then
+        assert_eq!(
+            bert_tokenizer.tokenize("Hello<new>world!"),
+            vec!["hello", "<new>", "world", "!"]
+        );
+        assert_eq!(
+            bert_tokenizer.convert_tokens_to_ids(&["<new>"]),
+            vec![bert_tokenizer.vocab.values.len() as i64 - 1]
+        );
+    }
+
+    #[test]
+    fn test_bert_tokenizer_word_segmenter() {
+        //        Given
+        let vocab = generate_test_vocab();
+        let mut bert_tokenizer: BertTokenizer =
+            BertTokenizer::from_existing_vocab(vocab, true, true);
+        let lexicon: HashMap<String, f32> = [("中华".to_string(), 1.0), ("人".to_string(), 1.0)]
+            .iter()
+            .cloned()
+            .collect();
+        let mut word_segmenter = DictionarySegmenter::from_lexicon(lexicon);
+        word_segmenter.set_split_characters(true);
+
+        //        When
+        bert_tokenizer.set_word_segmenter(Some(word_segmenter));
+        let tokens = bert_tokenizer.tokenize_with_offsets("hello 中华人!");
+
+        //        Then
+        assert_eq!(tokens.tokens, vec!["hello", "中", "华", "人", "!"]);
+        assert_eq!(
+            tokens.masks,
+            vec![
+                Mask::None,
+                Mask::Begin,
+                Mask::Continuation,
+                Mask::CJK,
+                Mask::Punctuation
+            ]
+        );
+        assert_eq!(tokens.offsets[2], Some(Offset::new(7, 8)));
+    }
+
     #[test]
     fn test_bert_tokenizer_no_lower_casing() {
         //        Given
@@ -719,4 +817,115 @@ mod tests {
             expected_results
         );
     }
+
+    #[test]
+    fn test_count_and_truncate_tokens() {
+        //        Given
+        let vocab = generate_test_vocab();
+        let bert_tokenizer: BertTokenizer = BertTokenizer::from_existing_vocab(vocab, true, true);
+        let text = "Hello [MASK] Unaffable 中华!";
+
+        //        When
+        let words = bert_tokenizer.count_word_tokens(text);
+        let num_tokens = Tokenizer::count_tokens(&bert_tokenizer, text);
+        let prefixes: Vec<&str> = (0..10)
+            .map(|max_tokens| Tokenizer::truncate_text_to_tokens(&bert_tokenizer, text, max_tokens))
+            .collect();
+
+        //        Then
+        assert_eq!(
+            words
+                .iter()
+                .map(|word| (&text[word.begin..word.end], word.num_tokens))
+                .collect::<Vec<(&str, usize)>>(),
+            vec![
+                ("Hello", 1),
+                ("[MASK]", 1),
+                ("Unaffable", 3),
+                ("中", 1),
+                ("华", 1),
+                ("!", 1)
+            ]
+        );
+        assert_eq!(num_tokens, bert_tokenizer.tokenize(text).len());
+        assert_eq!(
+            prefixes,
+            vec![
+                "",
+                "Hello",
+                "Hello [MASK]",
+                "Hello [MASK] Una",
+                "Hello [MASK] Unaffa",
+                "Hello [MASK] Unaffable",
+                "Hello [MASK] Unaffable 中",
+                "Hello [MASK] Unaffable 中华",
+                text,
+                text,
+            ]
+        );
+    }
+
+    fn text_strategy() -> impl Strategy<Value = String> {
+        prop::collection::vec(
+            prop_oneof![
+                any::<char>(),
+                prop::char::range('a', 'z'),
+                Just(' '),
+                Just('\u{0}'),
+                Just('\u{301}'),
+                Just('!'),
+                prop::char::range('\u{4e00}', '\u{4e10}'),
+            ],
+            0..16,
+        )
+        .prop_map(|characters| characters.into_iter().collect())
+    }
+
+    fn text_with_pieces_strategy() -> impl Strategy<Value = String> {
+        prop::collection::vec(
+            prop_oneof![
+                text_strategy(),
+                prop::sample::select(vec![
+                    "una", "ffa", "ble", "Hello", "WÖRLD", "[MASK]", "[UNK]", "中华", "!", " ",
+                    "\t", "\u{0}",
+                ])
+                .prop_map(String::from),
+            ],
+            0..12,
+        )
+        .prop_map(|pieces| pieces.concat())
+    }
+
+    proptest! {
+        #[test]
+        fn prop_count_and_truncate_match_tokenize(
+            text in text_with_pieces_strategy(),
+            max_tokens in 0usize..16,
+            lower_case in any::<bool>(),
+        ) {
+            let vocab = generate_test_vocab();
+            let bert_tokenizer = BertTokenizer::from_existing_vocab(vocab, lower_case, lower_case);
+            let tokens = bert_tokenizer.tokenize_with_offsets(&text);
+            let expected_end = tokens.reference_offsets[..max_tokens.min(tokens.tokens.len())]
+                .iter()
+                .filter_map(|reference_offsets| reference_offsets.last())
+                .max()
+                .map_or(0, |last| *last as usize + 1);
+            let expected_end = text
+                .char_indices()
+                .nth(expected_end)
+                .map_or(text.len(), |(position, _)| position);
+            let expected_prefix = if text.trim().is_empty() || tokens.tokens.len() <= max_tokens {
+                text.as_str()
+            } else {
+                &text[..expected_end]
+            };
+
+            prop_assert_eq!(Tokenizer::count_tokens(&bert_tokenizer, &text), tokens.tokens.len());
+            prop_assert_eq!(
+                Tokenizer::truncate_text_to_tokens(&bert_tokenizer, &text, max_tokens),
+                expected_prefix
+            );
+        }
+    }
 }
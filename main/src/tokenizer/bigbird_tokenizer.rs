@@ -0,0 +1,182 @@
+// Copyright 2021 Google Research and The HuggingFace Inc. team.
+// Copyright 2019-2020 Guillaume Becquin
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//     http://www.apache.org/licenses/LICENSE-2.0
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::path::Path;
+
+use crate::error::TokenizerError;
+use crate::tokenizer::base_tokenizer::{TokenIdsWithOffsets, TokenIdsWithSpecialTokens};
+use crate::tokenizer::tokenization_utils::lstrip_special_token;
+use crate::tokenizer::{AlbertTokenizer, MultiThreadedTokenizer, Tokenizer};
+use crate::vocab::{AlbertVocab, BigBirdVocab, SentencePieceModel, SpecialTokenMap, Vocab};
+use crate::{Token, TokenRef};
+
+/// # BigBird tokenizer
+/// BigBird tokenizer, relying on an `AlbertTokenizer` (SentencePiece tokenization) for the
+/// tokenization. The `[MASK]` token absorbs the whitespace preceding it.
+///
+/// Sequence pairs are encoded as `[CLS] A [SEP] B [SEP]`, with a segment id of 1 for the second sequence.
+pub struct BigBirdTokenizer {
+    vocab: BigBirdVocab,
+    albert_tokenizer: AlbertTokenizer,
+}
+
+impl BigBirdTokenizer {
+    /// Create a new instance of a `BigBirdTokenizer`
+    /// Expects a SentencePiece protobuf file as an input.
+    ///
+    /// # Parameters
+    /// - path (`&str`): path to the SentencePiece model file
+    /// - lower_case (`bool`): flag indicating if the text should be lower-cased as part of the tokenization
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use rust_tokenizers::tokenizer::{BigBirdTokenizer, Tokenizer};
+    /// let lower_case = false;
+    /// let tokenizer = BigBirdTokenizer::from_file("path/to/vocab/file", lower_case).unwrap();
+    /// ```
+    pub fn from_file<P: AsRef<Path>>(
+        path: P,
+        lower_case: bool,
+    ) -> Result<BigBirdTokenizer, TokenizerError> {
+        let model = SentencePieceModel::from_file(&path)?;
+        let vocab = BigBirdVocab::from_file(path)?;
+        Ok(BigBirdTokenizer::from_existing_vocab_and_model(
+            vocab, model, lower_case,
+        ))
+    }
+
+    /// Create a new instance of a `BigBirdTokenizer`
+    /// Expects a SentencePiece protobuf file and special token mapping file as inputs.
+    ///
+    /// # Parameters
+    /// - path (`&str`): path to the SentencePiece model file
+    /// - lower_case (`bool`): flag indicating if the text should be lower-cased as part of the tokenization
+    /// - special_token_mapping_path (`&str`): path to a special token mapping file to overwrite default special tokens
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use rust_tokenizers::tokenizer::{BigBirdTokenizer, Tokenizer};
+    /// let lower_case = false;
+    /// let tokenizer = BigBirdTokenizer::from_file_with_special_token_mapping(
+    ///     "path/to/vocab/file",
+    ///     lower_case,
+    ///     "path/to/special/token/mapping/file",
+    /// )
+    /// .unwrap();
+    /// ```
+    pub fn from_file_with_special_token_mapping<P: AsRef<Path>, S: AsRef<Path>>(
+        path: P,
+        lower_case: bool,
+        special_token_mapping_path: S,
+    ) -> Result<BigBirdTokenizer, TokenizerError> {
+        let model = SentencePieceModel::from_file(&path)?;
+        let vocab =
+            BigBirdVocab::from_file_with_special_token_mapping(path, special_token_mapping_path)?;
+        Ok(BigBirdTokenizer::from_existing_vocab_and_model(
+            vocab, model, lower_case,
+        ))
+    }
+
+    /// Create a new instance of a `BigBirdTokenizer` from an existing vocabulary and model
+    ///
+    /// # Parameters
+    /// - vocab (`BigBirdVocab`): BigBird vocabulary
+    /// - model (`SentencePieceModel`): SentencePiece model
+    /// - lower_case (`bool`): flag indicating if the text should be lower-cased as part of the tokenization
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use rust_tokenizers::tokenizer::{BigBirdTokenizer, Tokenizer};
+    /// use rust_tokenizers::vocab::{BigBirdVocab, SentencePieceModel, Vocab};
+    /// let lower_case = false;
+    /// let vocab = BigBirdVocab::from_file("path/to/vocab/file").unwrap();
+    /// let model = SentencePieceModel::from_file("path/to/model/file").unwrap();
+    ///
+    /// let tokenizer = BigBirdTokenizer::from_existing_vocab_and_model(vocab, model, lower_case);
+    /// ```
+    pub fn from_existing_vocab_and_model(
+        vocab: BigBirdVocab,
+        model: SentencePieceModel,
+        lower_case: bool,
+    ) -> BigBirdTokenizer {
+        //the BOS and EOS defaults of the ALBERT vocabulary differ from the BigBird ones
+        let special_token_map = SpecialTokenMap {
+            bos_token: Some(vocab.get_bos_value().to_string()),
+            eos_token: Some(vocab.get_eos_value().to_string()),
+            ..vocab.special_token_map.clone()
+        };
+        let albert_vocab = AlbertVocab {
+            values: vocab.values.clone(),
+            indices: vocab.indices.clone(),
+            special_token_map,
+            special_values: vocab.special_values.clone(),
+            special_indices: vocab.special_indices.clone(),
+        };
+        BigBirdTokenizer {
+            vocab,
+            albert_tokenizer: AlbertTokenizer::from_existing_vocab_and_model(
+                albert_vocab,
+                model,
+                lower_case,
+                false,
+            ),
+        }
+    }
+}
+
+impl Tokenizer<BigBirdVocab> for BigBirdTokenizer {
+    fn vocab(&self) -> &BigBirdVocab {
+        &self.vocab
+    }
+    fn vocab_mut(&mut self) -> &mut BigBirdVocab {
+        &mut self.vocab
+    }
+
+    fn add_tokens(&mut self, tokens: &[&str]) {
+        self.vocab.add_tokens(tokens);
+        self.albert_tokenizer.add_tokens(tokens);
+    }
+
+    fn add_extra_ids(&mut self, num_extra_ids: i64) {
+        self.vocab.add_extra_ids(num_extra_ids);
+        self.albert_tokenizer.add_extra_ids(num_extra_ids);
+    }
+
+    fn tokenize_to_tokens(&self, text: TokenRef) -> Vec<Token> {
+        let mut tokens = self.albert_tokenizer.tokenize_to_tokens(text);
+        //the mask token absorbs the whitespace preceding it
+        lstrip_special_token(text, &mut tokens, self.vocab.get_mask_value());
+        tokens
+    }
+
+    fn convert_tokens_to_string(&self, tokens: Vec<String>) -> String {
+        // The leading SentencePiece whitespace is not part of the decoded text
+        self.albert_tokenizer
+            .convert_tokens_to_string(tokens)
+            .trim()
+            .to_owned()
+    }
+
+    fn build_input_with_special_tokens(
+        &self,
+        tokens_ids_with_offsets_1: TokenIdsWithOffsets,
+        tokens_ids_with_offsets_2: Option<TokenIdsWithOffsets>,
+    ) -> TokenIdsWithSpecialTokens {
+        self.albert_tokenizer
+            .build_input_with_special_tokens(tokens_ids_with_offsets_1, tokens_ids_with_offsets_2)
+    }
+}
+
+impl MultiThreadedTokenizer<BigBirdVocab> for BigBirdTokenizer {}
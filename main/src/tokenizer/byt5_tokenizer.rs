@@ -0,0 +1,279 @@
+// Copyright 2021 Google T5 Authors and HuggingFace Inc. team.
+// Copyright 2019-2020 Guillaume Becquin
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//     http://www.apache.org/licenses/LICENSE-2.0
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::tokenizer::base_tokenizer::{
+    Mask, Offset, Token, TokenIdsWithOffsets, TokenIdsWithSpecialTokens, TokenRef, Tokenizer,
+};
+use crate::tokenizer::tokenization_utils::{split_on_special_tokens, SpecialTokenCache};
+use crate::tokenizer::MultiThreadedTokenizer;
+use crate::vocab::{ByT5Vocab, Vocab};
+use std::convert::TryFrom;
+use std::sync::RwLock;
+
+/// # ByT5 tokenizer
+/// Byte-level tokenizer for ByT5, performing:
+/// - splitting on special characters
+/// - splitting the text into its UTF-8 bytes
+///
+/// Each byte token keeps the offset of the character it belongs to. The bytes of multi-byte
+/// characters are marked with `Mask::Begin`/`Mask::Continuation`.
+/// No vocabulary file is required.
+pub struct ByT5Tokenizer {
+    vocab: ByT5Vocab,
+    special_token_cache: SpecialTokenCache,
+    eos_token_id: i64,
+}
+
+impl ByT5Tokenizer {
+    /// Create a new instance of a `ByT5Tokenizer` with the default byte-level vocabulary (125 extra ids).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_tokenizers::tokenizer::{ByT5Tokenizer, Tokenizer};
+    /// let tokenizer = ByT5Tokenizer::new();
+    ///
+    /// assert_eq!(tokenizer.convert_tokens_to_ids(&["a"]), vec![100]);
+    /// ```
+    pub fn new() -> ByT5Tokenizer {
+        Self::from_existing_vocab(ByT5Vocab::default())
+    }
+
+    /// Create a new instance of a `ByT5Tokenizer` from an existing vocabulary
+    ///
+    /// # Parameters
+    /// - vocab (`ByT5Vocab`): ByT5 vocabulary
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_tokenizers::tokenizer::{ByT5Tokenizer, Tokenizer};
+    /// use rust_tokenizers::vocab::ByT5Vocab;
+    /// let vocab = ByT5Vocab::new(100);
+    ///
+    /// let tokenizer = ByT5Tokenizer::from_existing_vocab(vocab);
+    /// ```
+    pub fn from_existing_vocab(vocab: ByT5Vocab) -> ByT5Tokenizer {
+        let eos_token_id = vocab.token_to_id(vocab.get_eos_value());
+        ByT5Tokenizer {
+            vocab,
+            special_token_cache: RwLock::new(None),
+            eos_token_id,
+        }
+    }
+
+    fn ends_with_eos(&self, tokens: &TokenIdsWithOffsets) -> bool {
+        tokens.ids.last() == Some(&self.eos_token_id)
+    }
+}
+
+impl Default for ByT5Tokenizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Tokenizer<ByT5Vocab> for ByT5Tokenizer {
+    fn vocab(&self) -> &ByT5Vocab {
+        &self.vocab
+    }
+    fn vocab_mut(&mut self) -> &mut ByT5Vocab {
+        &mut self.vocab
+    }
+
+    fn tokenize_to_tokens(&self, initial_token: TokenRef) -> Vec<Token> {
+        let tokens = split_on_special_tokens(initial_token, &self.vocab, &self.special_token_cache);
+
+        let mut sub_tokens = Vec::new();
+        let mut buffer = [0u8; 4];
+        for token in tokens {
+            if token.mask != Mask::Special && token.mask != Mask::Unknown {
+                for (character, position) in token.text.chars().zip(token.reference_offsets) {
+                    let bytes = character.encode_utf8(&mut buffer).as_bytes();
+                    for (index, byte) in bytes.iter().enumerate() {
+                        let mask = match (bytes.len(), index) {
+                            (1, _) => Mask::None,
+                            (_, 0) => Mask::Begin,
+                            _ => Mask::Continuation,
+                        };
+                        sub_tokens.push(Token {
+                            text: char::from(*byte).to_string(),
+                            offset: Offset::new(*position, *position + 1),
+                            reference_offsets: vec![*position],
+                            mask,
+                        });
+                    }
+                }
+            } else {
+                sub_tokens.push(token.to_owned());
+            }
+        }
+        sub_tokens
+    }
+
+    fn convert_tokens_to_string(&self, tokens: Vec<String>) -> String {
+        let mut bytes: Vec<u8> = Vec::new();
+        for token in tokens {
+            if self.vocab.special_values.contains_key(&token) {
+                bytes.extend_from_slice(token.as_bytes());
+            } else {
+                for character in token.chars() {
+                    match u8::try_from(u32::from(character)) {
+                        Ok(byte) => bytes.push(byte),
+                        Err(_) => bytes.extend_from_slice(character.to_string().as_bytes()),
+                    }
+                }
+            }
+        }
+        String::from_utf8_lossy(&bytes).into_owned()
+    }
+
+    fn build_input_with_special_tokens(
+        &self,
+        mut tokens_ids_with_offsets_1: TokenIdsWithOffsets,
+        tokens_ids_with_offsets_2: Option<TokenIdsWithOffsets>,
+    ) -> TokenIdsWithSpecialTokens {
+        let mut token_segment_ids: Vec<i8> = vec![0; tokens_ids_with_offsets_1.ids.len()];
+        let mut special_tokens_mask: Vec<i8> = vec![0; tokens_ids_with_offsets_1.ids.len()];
+
+        if !self.ends_with_eos(&tokens_ids_with_offsets_1) {
+            token_segment_ids.push(0);
+            special_tokens_mask.push(1);
+            tokens_ids_with_offsets_1.ids.push(self.eos_token_id);
+            tokens_ids_with_offsets_1.offsets.push(None);
+            tokens_ids_with_offsets_1.reference_offsets.push(vec![]);
+            tokens_ids_with_offsets_1.masks.push(Mask::Special);
+        }
+        if let Some(tokens_ids_with_offsets_2_value) = tokens_ids_with_offsets_2 {
+            let length = tokens_ids_with_offsets_2_value.ids.len();
+            let ends_with_eos = self.ends_with_eos(&tokens_ids_with_offsets_2_value);
+            token_segment_ids.extend(vec![1; length]);
+            special_tokens_mask.extend(vec![0; length]);
+            tokens_ids_with_offsets_1
+                .ids
+                .extend(tokens_ids_with_offsets_2_value.ids);
+            tokens_ids_with_offsets_1
+                .offsets
+                .extend(tokens_ids_with_offsets_2_value.offsets);
+            tokens_ids_with_offsets_1
+                .reference_offsets
+                .extend(tokens_ids_with_offsets_2_value.reference_offsets);
+            tokens_ids_with_offsets_1
+                .masks
+                .extend(tokens_ids_with_offsets_2_value.masks);
+            if !ends_with_eos {
+                token_segment_ids.push(1);
+                special_tokens_mask.push(1);
+                tokens_ids_with_offsets_1.ids.push(self.eos_token_id);
+                tokens_ids_with_offsets_1.offsets.push(None);
+                tokens_ids_with_offsets_1.reference_offsets.push(vec![]);
+                tokens_ids_with_offsets_1.masks.push(Mask::Special);
+            }
+        };
+
+        TokenIdsWithSpecialTokens {
+            token_ids: tokens_ids_with_offsets_1.ids,
+            segment_ids: token_segment_ids,
+            special_tokens_mask,
+            token_offsets: tokens_ids_with_offsets_1.offsets,
+            reference_offsets: tokens_ids_with_offsets_1.reference_offsets,
+            mask: tokens_ids_with_offsets_1.masks,
+        }
+    }
+}
+
+impl MultiThreadedTokenizer<ByT5Vocab> for ByT5Tokenizer {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tokenizer::base_tokenizer::TruncationStrategy;
+    use crate::TokenizedInput;
+
+    #[test]
+    fn test_byt5_tokenizer() {
+        //        Given
+        let byt5_tokenizer = ByT5Tokenizer::new();
+        let test_tuples = [
+            ("hi", vec!["h", "i"]),
+            ("é!", vec!["Ã", "\u{a9}", "!"]),
+            ("a <extra_id_0> b", vec!["a", "<extra_id_0>", " ", "b"]),
+            ("", vec![]),
+        ];
+        let source_texts: Vec<&str> = test_tuples.iter().map(|v| v.0).collect();
+        let expected_results: Vec<Vec<&str>> = test_tuples.iter().map(|v| v.1.clone()).collect();
+
+        //        When & Then
+        for (source_text, expected_result) in test_tuples.iter() {
+            assert_eq!(byt5_tokenizer.tokenize(source_text), *expected_result);
+        }
+        assert_eq!(
+            MultiThreadedTokenizer::tokenize_list(&byt5_tokenizer, &source_texts),
+            expected_results
+        );
+    }
+
+    #[test]
+    fn test_encode() {
+        //        Given
+        let byt5_tokenizer = ByT5Tokenizer::new();
+        let expected_result = TokenizedInput {
+            token_ids: vec![107, 198, 172, 36, 1],
+            segment_ids: vec![0, 0, 0, 0, 0],
+            special_tokens_mask: vec![0, 0, 0, 0, 1],
+            overflowing_tokens: vec![],
+            num_truncated_tokens: 0,
+            token_offsets: vec![
+                Some(Offset::new(0, 1)),
+                Some(Offset::new(1, 2)),
+                Some(Offset::new(1, 2)),
+                Some(Offset::new(2, 3)),
+                None,
+            ],
+            reference_offsets: vec![vec![0], vec![1], vec![1], vec![2], vec![]],
+            mask: vec![
+                Mask::None,
+                Mask::Begin,
+                Mask::Continuation,
+                Mask::None,
+                Mask::Special,
+            ],
+        };
+
+        //        When
+        let encoded_input =
+            byt5_tokenizer.encode("hé!", None, 128, &TruncationStrategy::LongestFirst, 0);
+
+        //        Then
+        assert_eq!(encoded_input, expected_result);
+    }
+
+    #[test]
+    fn test_decode() {
+        //        Given
+        let byt5_tokenizer = ByT5Tokenizer::new();
+        let test_tuples = [
+            (vec![107, 198, 172, 36, 1], true, "hé!"),
+            (vec![107, 198, 172, 36, 1], false, "hé!</s>"),
+            (vec![107, 383, 198], false, "h<extra_id_0>\u{FFFD}"),
+            (vec![107, 255, 36], true, "h\u{FFFD}!"),
+        ];
+
+        //        When & Then
+        for (token_ids, skip_special_tokens, expected_result) in test_tuples.iter() {
+            assert_eq!(
+                byt5_tokenizer.decode(token_ids, *skip_special_tokens, false),
+                *expected_result
+            );
+        }
+    }
+}
@@ -0,0 +1,529 @@
+// Copyright 2018 The Open AI Team Authors
+// Copyright 2018 The HuggingFace Inc. team.
+// Copyright 2019-2020 Guillaume Becquin
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//     http://www.apache.org/licenses/LICENSE-2.0
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::error::TokenizerError;
+use crate::tokenizer::constants::UNICODE_TO_BYTES;
+use crate::tokenizer::tokenization_utils::{
+    bpe, fix_mask, is_whitespace, split_on_bpe_pairs, split_on_regex_isolated,
+    split_on_special_tokens, BpeCache, SpecialTokenCache,
+};
+use crate::tokenizer::{MultiThreadedTokenizer, Tokenizer};
+use crate::vocab::base_vocab::{read_json_file, SpecialTokenMap};
+use crate::vocab::bpe_vocab::{read_tokenizer_json_bpe_file, BpePairVocab};
+use crate::vocab::{Gpt2Vocab, Vocab};
+use crate::{Mask, Offset, Token, TokenRef};
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::RwLock;
+
+/// # Byte-level BPE pre-tokenization presets
+/// Pre-tokenization regex and special tokens of the byte-level BPE model families:
+/// - `Gpt2`: GPT-2 regex (contractions, letters, numbers and punctuation runs)
+/// - `GptNeoX`: GPT-NeoX and Pythia, GPT-2 regex with offsets trimmed of whitespace
+/// - `Bloom`: splits on whitespace and a set of punctuation marks, no contractions
+/// - `StarCoder`: GPT-2 regex with individual digits
+/// - `Falcon`: isolated punctuation and digits grouped by 3, no contractions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ByteLevelBpePreset {
+    Gpt2,
+    GptNeoX,
+    Bloom,
+    StarCoder,
+    Falcon,
+}
+
+impl ByteLevelBpePreset {
+    /// Returns the pre-tokenization regex of the model family
+    pub fn pattern(&self) -> &'static str {
+        match self {
+            ByteLevelBpePreset::Gpt2 | ByteLevelBpePreset::GptNeoX => {
+                r"'s|'t|'re|'ve|'m|'ll|'d| ?\p{L}+| ?\p{N}+| ?[^\s\p{L}\p{N}]+|\s+"
+            }
+            ByteLevelBpePreset::Bloom => r" ?[^(\s|[.,!?…。，、।۔،])]+",
+            ByteLevelBpePreset::StarCoder => {
+                r"'s|'t|'re|'ve|'m|'ll|'d| ?\p{L}+|\p{N}| ?[^\s\p{L}\p{N}]+|\s+"
+            }
+            ByteLevelBpePreset::Falcon => {
+                r" ?\p{L}+|[\p{P}[[:punct:]]]+|[0-9]{1,3}|\p{N}+| ?[^\s\p{L}\p{N}\p{P}[[:punct:]]]+|\s+"
+            }
+        }
+    }
+
+    /// Returns the special tokens of the model family
+    pub fn special_token_map(&self) -> SpecialTokenMap {
+        match self {
+            ByteLevelBpePreset::Bloom => SpecialTokenMap {
+                unk_token: "<unk>".to_string(),
+                pad_token: Some("<pad>".to_string()),
+                bos_token: Some("<s>".to_string()),
+                sep_token: None,
+                cls_token: None,
+                eos_token: Some("</s>".to_string()),
+                mask_token: None,
+                additional_special_tokens: None,
+            },
+            _ => SpecialTokenMap {
+                unk_token: "<|endoftext|>".to_string(),
+                pad_token: None,
+                bos_token: Some("<|endoftext|>".to_string()),
+                sep_token: None,
+                cls_token: None,
+                eos_token: Some("<|endoftext|>".to_string()),
+                mask_token: None,
+                additional_special_tokens: None,
+            },
+        }
+    }
+
+    /// Returns the default for trimming whitespace from the token offsets
+    pub fn trim_offsets(&self) -> bool {
+        matches!(self, ByteLevelBpePreset::GptNeoX)
+    }
+}
+
+/// # Byte-level BPE tokenizer
+/// Generic byte-level BPE tokenizer (GPT-NeoX, Pythia, BLOOM, StarCoder, Falcon...) performing:
+/// - splitting on special characters
+/// - (optional) prefix space addition
+/// - pre-tokenization with a configurable regex
+/// - BPE tokenization
+/// - (optional) trimming of whitespace from the token offsets
+pub struct ByteLevelBpeTokenizer {
+    vocab: Gpt2Vocab,
+    special_token_cache: SpecialTokenCache,
+    bpe_ranks: BpePairVocab,
+    cache: BpeCache,
+    pattern_lookahead: Regex,
+    pattern_tokenization: Regex,
+    add_prefix_space: bool,
+    trim_offsets: bool,
+}
+
+impl ByteLevelBpeTokenizer {
+    /// Create a new instance of a `ByteLevelBpeTokenizer`
+    /// Expects a vocabulary json file and a merges file as an input.
+    ///
+    /// # Parameters
+    /// - vocab_path (`&str`): path to the vocabulary file
+    /// - merges_path (`&str`): path to the merges file (use as part of the BPE encoding process)
+    /// - preset (`ByteLevelBpePreset`): model family defining the pre-tokenization and special tokens
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use rust_tokenizers::tokenizer::{ByteLevelBpePreset, ByteLevelBpeTokenizer, Tokenizer};
+    /// let tokenizer = ByteLevelBpeTokenizer::from_file(
+    ///     "path/to/vocab/file",
+    ///     "path/to/merges/file",
+    ///     ByteLevelBpePreset::GptNeoX,
+    /// )
+    /// .unwrap();
+    /// ```
+    pub fn from_file<P: AsRef<Path>, M: AsRef<Path>>(
+        vocab_path: P,
+        merges_path: M,
+        preset: ByteLevelBpePreset,
+    ) -> Result<ByteLevelBpeTokenizer, TokenizerError> {
+        let values = read_json_file(vocab_path)?;
+        let vocab =
+            Gpt2Vocab::from_values_and_special_token_map(values, preset.special_token_map())?;
+        let bpe_ranks = BpePairVocab::from_file(merges_path)?;
+        Ok(Self::from_existing_vocab_and_merges(
+            vocab, bpe_ranks, preset,
+        ))
+    }
+
+    /// Create a new instance of a `ByteLevelBpeTokenizer` from a `tokenizer.json` file containing
+    /// a BPE model. The added tokens of the file are registered as special tokens, in addition
+    /// to the special tokens of the preset.
+    ///
+    /// # Parameters
+    /// - path (`&str`): path to the `tokenizer.json` file
+    /// - preset (`ByteLevelBpePreset`): model family defining the pre-tokenization and special tokens
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use rust_tokenizers::tokenizer::{ByteLevelBpePreset, ByteLevelBpeTokenizer, Tokenizer};
+    /// let tokenizer = ByteLevelBpeTokenizer::from_tokenizer_json_file(
+    ///     "path/to/tokenizer.json",
+    ///     ByteLevelBpePreset::StarCoder,
+    /// )
+    /// .unwrap();
+    /// ```
+    pub fn from_tokenizer_json_file<P: AsRef<Path>>(
+        path: P,
+        preset: ByteLevelBpePreset,
+    ) -> Result<ByteLevelBpeTokenizer, TokenizerError> {
+        let model = read_tokenizer_json_bpe_file(path)?;
+        let mut values = model.values;
+        let mut special_token_map = preset.special_token_map();
+        let mut additional_special_tokens = HashSet::new();
+        for (token, id) in model.added_tokens {
+            values.insert(token.clone(), id);
+            additional_special_tokens.insert(token);
+        }
+        if !additional_special_tokens.is_empty() {
+            special_token_map.additional_special_tokens = Some(additional_special_tokens);
+        }
+        let vocab = Gpt2Vocab::from_values_and_special_token_map(values, special_token_map)?;
+        Ok(Self::from_existing_vocab_and_merges(
+            vocab,
+            model.merges,
+            preset,
+        ))
+    }
+
+    /// Create a new instance of a `ByteLevelBpeTokenizer` from an existing vocabulary and merges
+    ///
+    /// # Parameters
+    /// - vocab (`Gpt2Vocab`): GPT-like vocabulary
+    /// - merges (`BpePairVocab`): BPE pairs vocabulary
+    /// - preset (`ByteLevelBpePreset`): model family defining the pre-tokenization
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use rust_tokenizers::tokenizer::{ByteLevelBpePreset, ByteLevelBpeTokenizer, Tokenizer};
+    /// use rust_tokenizers::vocab::{BpePairVocab, Gpt2Vocab, Vocab};
+    /// let vocab = Gpt2Vocab::from_file("path/to/vocab/file").unwrap();
+    /// let merges = BpePairVocab::from_file("path/to/merges/file").unwrap();
+    ///
+    /// let tokenizer = ByteLevelBpeTokenizer::from_existing_vocab_and_merges(
+    ///     vocab,
+    ///     merges,
+    ///     ByteLevelBpePreset::Gpt2,
+    /// );
+    /// ```
+    pub fn from_existing_vocab_and_merges(
+        vocab: Gpt2Vocab,
+        merges: BpePairVocab,
+        preset: ByteLevelBpePreset,
+    ) -> ByteLevelBpeTokenizer {
+        ByteLevelBpeTokenizer {
+            vocab,
+            special_token_cache: RwLock::new(None),
+            bpe_ranks: merges,
+            cache: RwLock::new(HashMap::new()),
+            pattern_lookahead: Regex::new(r"\s+\S").unwrap(),
+            pattern_tokenization: Regex::new(preset.pattern()).unwrap(),
+            add_prefix_space: false,
+            trim_offsets: preset.trim_offsets(),
+        }
+    }
+
+    /// Sets the pre-tokenization regex. Text between matches of the regex is kept as separate
+    /// pre-tokens. Clears the BPE cache.
+    ///
+    /// # Parameters
+    /// - pattern (`&str`): pre-tokenization regex
+    pub fn set_pre_tokenizer_pattern(&mut self, pattern: &str) -> Result<(), TokenizerError> {
+        self.pattern_tokenization =
+            Regex::new(pattern).map_err(|e| TokenizerError::ValueError(e.to_string()))?;
+        if let Ok(mut cache) = self.cache.write() {
+            cache.clear();
+        }
+        Ok(())
+    }
+
+    /// Sets whether a space is added in front of the text if it does not start with whitespace,
+    /// so that the first word is tokenized like any other word.
+    pub fn set_add_prefix_space(&mut self, add_prefix_space: bool) {
+        self.add_prefix_space = add_prefix_space;
+    }
+
+    /// Sets whether leading and trailing whitespace is excluded from the token offsets.
+    pub fn set_trim_offsets(&mut self, trim_offsets: bool) {
+        self.trim_offsets = trim_offsets;
+    }
+
+    fn trim_token_offsets(token: &mut Token) {
+        let is_whitespace_byte = |character: &char| {
+            UNICODE_TO_BYTES
+                .get(character)
+                .is_some_and(|byte| byte.is_ascii_whitespace())
+        };
+        let leading = token
+            .text
+            .chars()
+            .take_while(|character| is_whitespace_byte(character))
+            .count();
+        let trailing = token
+            .text
+            .chars()
+            .rev()
+            .take_while(|character| is_whitespace_byte(character))
+            .count();
+        if leading + trailing >= token.reference_offsets.len() {
+            return;
+        }
+        token
+            .reference_offsets
+            .truncate(token.reference_offsets.len() - trailing);
+        token.reference_offsets.drain(..leading);
+        token.offset = Offset::new(
+            *token.reference_offsets.first().unwrap(),
+            *token.reference_offsets.last().unwrap() + 1,
+        );
+    }
+}
+
+impl Tokenizer<Gpt2Vocab> for ByteLevelBpeTokenizer {
+    fn vocab(&self) -> &Gpt2Vocab {
+        &self.vocab
+    }
+    fn vocab_mut(&mut self) -> &mut Gpt2Vocab {
+        &mut self.vocab
+    }
+
+    fn tokenize_to_tokens(&self, initial_token: TokenRef) -> Vec<Token> {
+        if initial_token.text.is_empty() {
+            return vec![];
+        }
+        let mut initial_token: Token = initial_token.to_owned();
+        if self.add_prefix_space && !is_whitespace(&initial_token.text.chars().next().unwrap()) {
+            initial_token.text.insert(0, ' ');
+            initial_token.reference_offsets.insert(0, 0);
+        };
+        let tokens = split_on_special_tokens(
+            initial_token.as_ref(),
+            &self.vocab,
+            &self.special_token_cache,
+        )
+        .into_iter()
+        .map(|token| token.to_owned())
+        .collect::<Vec<Token>>();
+
+        let mut sub_tokens = Vec::new();
+        for token in tokens.iter() {
+            if token.mask != Mask::Special && token.mask != Mask::Unknown {
+                for token in split_on_regex_isolated(
+                    token.as_ref(),
+                    &self.pattern_lookahead,
+                    &self.pattern_tokenization,
+                ) {
+                    sub_tokens.extend(split_on_bpe_pairs(
+                        token,
+                        bpe,
+                        &self.bpe_ranks,
+                        &self.cache,
+                        true,
+                    ));
+                }
+            } else {
+                sub_tokens.push(token.clone());
+            }
+        }
+
+        if self.trim_offsets {
+            for token in sub_tokens.iter_mut() {
+                if token.mask != Mask::Special && token.mask != Mask::Unknown {
+                    Self::trim_token_offsets(token);
+                }
+            }
+        }
+        fix_mask(&mut sub_tokens);
+        sub_tokens
+    }
+
+    fn convert_tokens_to_string(&self, tokens: Vec<String>) -> String {
+        let mut bytes = Vec::new();
+        for character in tokens.concat().chars() {
+            match UNICODE_TO_BYTES.get(&character) {
+                Some(byte) => bytes.push(*byte),
+                None => {
+                    let mut buffer = [0; 4];
+                    bytes.extend_from_slice(character.encode_utf8(&mut buffer).as_bytes());
+                }
+            }
+        }
+        let text = String::from_utf8_lossy(bytes.as_slice()).to_string();
+        match (self.add_prefix_space, text.strip_prefix(' ')) {
+            (true, Some(stripped)) => stripped.to_string(),
+            _ => text,
+        }
+    }
+}
+
+impl MultiThreadedTokenizer<Gpt2Vocab> for ByteLevelBpeTokenizer {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vocab::base_vocab::swap_key_values;
+
+    fn generate_test_vocab(special_tokens: &[&str]) -> Gpt2Vocab {
+        let mut values: HashMap<String, i64> = [
+            "t", "h", "e", "a", "r", "1", "2", "3", "4", ",", "Ġ", "the", "Ġear", "th", "Ġt", "he",
+            "Ġthe", "Ġe", "Ġea", "12",
+        ]
+        .iter()
+        .enumerate()
+        .map(|(id, token)| (token.to_string(), id as i64))
+        .collect();
+        let mut special_values = HashMap::new();
+        for token in special_tokens {
+            let id = values.len() as i64;
+            values.insert(token.to_string(), id);
+            special_values.insert(token.to_string(), id);
+        }
+        let special_token_map = SpecialTokenMap {
+            unk_token: special_tokens[0].to_string(),
+            pad_token: None,
+            bos_token: None,
+            sep_token: None,
+            cls_token: None,
+            eos_token: None,
+            mask_token: None,
+            additional_special_tokens: None,
+        };
+        let indices = swap_key_values(&values);
+        let special_indices = swap_key_values(&special_values);
+        Gpt2Vocab {
+            values,
+            indices,
+            special_token_map,
+            special_values,
+            special_indices,
+        }
+    }
+
+    fn generate_test_merges() -> BpePairVocab {
+        let values: HashMap<(String, String), i64> = [
+            ("Ġ", "t"),
+            ("h", "e"),
+            ("Ġt", "he"),
+            ("t", "h"),
+            ("t", "he"),
+            ("Ġ", "e"),
+            ("Ġe", "a"),
+            ("Ġea", "r"),
+            ("1", "2"),
+        ]
+        .iter()
+        .enumerate()
+        .map(|(id, (first, second))| ((first.to_string(), second.to_string()), id as i64))
+        .collect();
+        BpePairVocab { values }
+    }
+
+    #[test]
+    fn test_byte_level_bpe_presets() {
+        //        Given
+        let test_tuples = [
+            (
+                ByteLevelBpePreset::Gpt2,
+                "the ear 1234",
+                vec!["the", "Ġear", "Ġ", "12", "3", "4"],
+            ),
+            (
+                ByteLevelBpePreset::StarCoder,
+                "the ear 1234",
+                vec!["the", "Ġear", "Ġ", "1", "2", "3", "4"],
+            ),
+            (
+                ByteLevelBpePreset::Falcon,
+                "the ear 1234",
+                vec!["the", "Ġear", "Ġ", "12", "3", "4"],
+            ),
+            (
+                ByteLevelBpePreset::Bloom,
+                "the, ear",
+                vec!["the", ",", "Ġear"],
+            ),
+            (
+                ByteLevelBpePreset::Gpt2,
+                "the<|endoftext|> ear",
+                vec!["the", "<|endoftext|>", "Ġear"],
+            ),
+        ];
+
+        //        When & Then
+        for (preset, source_text, expected_tokens) in test_tuples.iter() {
+            let tokenizer = ByteLevelBpeTokenizer::from_existing_vocab_and_merges(
+                generate_test_vocab(&["<|endoftext|>"]),
+                generate_test_merges(),
+                *preset,
+            );
+            assert_eq!(
+                tokenizer.tokenize(source_text),
+                *expected_tokens,
+                "{preset:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_byte_level_bpe_prefix_space_and_trim_offsets() {
+        //        Given
+        let mut tokenizer = ByteLevelBpeTokenizer::from_existing_vocab_and_merges(
+            generate_test_vocab(&["<|endoftext|>"]),
+            generate_test_merges(),
+            ByteLevelBpePreset::Gpt2,
+        );
+        let source_text = "the ear";
+
+        //        When
+        let default_offsets = tokenizer.tokenize_with_offsets(source_text).offsets;
+        tokenizer.set_add_prefix_space(true);
+        tokenizer.set_trim_offsets(true);
+        let tokens = tokenizer.tokenize_with_offsets(source_text);
+        let ids = tokenizer.convert_tokens_to_ids(&tokens.tokens);
+
+        //        Then
+        assert_eq!(
+            default_offsets,
+            vec![Some(Offset::new(0, 3)), Some(Offset::new(3, 7))]
+        );
+        assert_eq!(tokens.tokens, vec!["Ġthe", "Ġear"]);
+        assert_eq!(
+            tokens.offsets,
+            vec![Some(Offset::new(0, 3)), Some(Offset::new(4, 7))]
+        );
+        assert_eq!(tokenizer.decode(&ids, false, false), source_text);
+    }
+
+    #[test]
+    fn test_byte_level_bpe_from_tokenizer_json_file() -> anyhow::Result<()> {
+        //        Given
+        let mut tokenizer_file = tempfile::NamedTempFile::new()?;
+        std::io::Write::write_all(
+            &mut tokenizer_file,
+            r#"{
+                "added_tokens": [
+                    {"id": 4, "content": "<fim_prefix>", "special": true},
+                    {"id": 5, "content": "<|endoftext|>", "special": true}
+                ],
+                "model": {
+                    "type": "BPE",
+                    "vocab": {"h": 0, "e": 1, "Ġ": 2, "he": 3, "Ġhe": 6},
+                    "merges": ["h e", ["Ġ", "he"]]
+                }
+            }"#
+            .as_bytes(),
+        )?;
+        let path = tokenizer_file.into_temp_path();
+
+        //        When
+        let tokenizer =
+            ByteLevelBpeTokenizer::from_tokenizer_json_file(&path, ByteLevelBpePreset::StarCoder)?;
+        let tokens = tokenizer.tokenize("<fim_prefix>he he");
+
+        //        Then
+        assert_eq!(tokens, vec!["<fim_prefix>", "he", "Ġhe"]);
+        assert_eq!(tokenizer.convert_tokens_to_ids(&tokens), vec![4, 3, 6]);
+        assert_eq!(Tokenizer::vocab(&tokenizer).token_to_id("<|endoftext|>"), 5);
+        Ok(())
+    }
+}
@@ -0,0 +1,237 @@
+// Copyright 2021 Google AI and HuggingFace Inc. team.
+// Copyright 2019-2020 Guillaume Becquin
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//     http://www.apache.org/licenses/LICENSE-2.0
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::tokenizer::base_tokenizer::{
+    Mask, Offset, OffsetSize, Token, TokenIdsWithOffsets, TokenIdsWithSpecialTokens, TokenRef,
+    Tokenizer,
+};
+use crate::tokenizer::tokenization_utils::{split_on_special_tokens, SpecialTokenCache};
+use crate::tokenizer::MultiThreadedTokenizer;
+use crate::vocab::{CanineVocab, Vocab};
+use std::sync::RwLock;
+
+/// # CANINE tokenizer
+/// Character-level tokenizer for CANINE, performing:
+/// - splitting on special characters
+/// - splitting the text into Unicode code points (including whitespaces)
+///
+/// The id of each character is its code point. No vocabulary file is required.
+pub struct CanineTokenizer {
+    vocab: CanineVocab,
+    special_token_cache: SpecialTokenCache,
+}
+
+impl CanineTokenizer {
+    /// Create a new instance of a `CanineTokenizer`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_tokenizers::tokenizer::{CanineTokenizer, Tokenizer};
+    /// let tokenizer = CanineTokenizer::new();
+    ///
+    /// assert_eq!(tokenizer.convert_tokens_to_ids(&["a"]), vec![97]);
+    /// ```
+    pub fn new() -> CanineTokenizer {
+        Self::from_existing_vocab(CanineVocab::default())
+    }
+
+    /// Create a new instance of a `CanineTokenizer` from an existing vocabulary
+    ///
+    /// # Parameters
+    /// - vocab (`CanineVocab`): CANINE vocabulary
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_tokenizers::tokenizer::{CanineTokenizer, Tokenizer};
+    /// use rust_tokenizers::vocab::CanineVocab;
+    /// let vocab = CanineVocab::new();
+    ///
+    /// let tokenizer = CanineTokenizer::from_existing_vocab(vocab);
+    /// ```
+    pub fn from_existing_vocab(vocab: CanineVocab) -> CanineTokenizer {
+        CanineTokenizer {
+            vocab,
+            special_token_cache: RwLock::new(None),
+        }
+    }
+}
+
+impl Default for CanineTokenizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Tokenizer<CanineVocab> for CanineTokenizer {
+    fn vocab(&self) -> &CanineVocab {
+        &self.vocab
+    }
+    fn vocab_mut(&mut self) -> &mut CanineVocab {
+        &mut self.vocab
+    }
+
+    fn tokenize_to_tokens(&self, initial_token: TokenRef) -> Vec<Token> {
+        let tokens = split_on_special_tokens(initial_token, &self.vocab, &self.special_token_cache);
+
+        let mut sub_tokens = Vec::new();
+        for token in tokens {
+            if token.mask != Mask::Special && token.mask != Mask::Unknown {
+                for (character, position) in token.text.chars().zip(token.reference_offsets) {
+                    sub_tokens.push(Token {
+                        text: character.to_string(),
+                        offset: Offset::new(*position, *position + 1),
+                        reference_offsets: vec![*position],
+                        mask: Mask::None,
+                    });
+                }
+            } else {
+                sub_tokens.push(token.to_owned());
+            }
+        }
+        sub_tokens
+    }
+
+    fn convert_tokens_to_string(&self, tokens: Vec<String>) -> String {
+        tokens.concat()
+    }
+
+    fn build_input_with_special_tokens(
+        &self,
+        tokens_ids_with_offsets_1: TokenIdsWithOffsets,
+        tokens_ids_with_offsets_2: Option<TokenIdsWithOffsets>,
+    ) -> TokenIdsWithSpecialTokens {
+        let mut output: Vec<i64> = vec![];
+        let mut token_segment_ids: Vec<i8> = vec![0; tokens_ids_with_offsets_1.ids.len() + 2];
+        let mut special_tokens_mask: Vec<i8> = vec![];
+        let mut offsets: Vec<Option<Offset>> = vec![];
+        let mut original_offsets: Vec<Vec<OffsetSize>> = vec![];
+        let mut mask: Vec<Mask> = vec![];
+        special_tokens_mask.push(1);
+        special_tokens_mask.extend(vec![0; tokens_ids_with_offsets_1.ids.len()]);
+        special_tokens_mask.push(1);
+        output.push(self.vocab.token_to_id(self.vocab.get_cls_value()));
+        output.extend(tokens_ids_with_offsets_1.ids);
+        output.push(self.vocab.token_to_id(self.vocab.get_sep_value()));
+        offsets.push(None);
+        offsets.extend(tokens_ids_with_offsets_1.offsets);
+        offsets.push(None);
+        original_offsets.push(vec![]);
+        original_offsets.extend(tokens_ids_with_offsets_1.reference_offsets);
+        original_offsets.push(vec![]);
+        mask.push(Mask::Special);
+        mask.extend(tokens_ids_with_offsets_1.masks);
+        mask.push(Mask::Special);
+        if let Some(tokens_ids_with_offsets_2_value) = tokens_ids_with_offsets_2 {
+            let length = tokens_ids_with_offsets_2_value.ids.len();
+            special_tokens_mask.extend(vec![0; length]);
+            special_tokens_mask.push(1);
+            token_segment_ids.extend(vec![1; length + 1]);
+            output.extend(tokens_ids_with_offsets_2_value.ids);
+            output.push(self.vocab.token_to_id(self.vocab.get_sep_value()));
+            offsets.extend(tokens_ids_with_offsets_2_value.offsets);
+            original_offsets.extend(tokens_ids_with_offsets_2_value.reference_offsets);
+            offsets.push(None);
+            original_offsets.push(vec![]);
+            mask.extend(tokens_ids_with_offsets_2_value.masks);
+            mask.push(Mask::Special);
+        }
+        TokenIdsWithSpecialTokens {
+            token_ids: output,
+            segment_ids: token_segment_ids,
+            special_tokens_mask,
+            token_offsets: offsets,
+            reference_offsets: original_offsets,
+            mask,
+        }
+    }
+}
+
+impl MultiThreadedTokenizer<CanineVocab> for CanineTokenizer {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tokenizer::base_tokenizer::TruncationStrategy;
+
+    #[test]
+    fn test_canine_tokenizer() {
+        //        Given
+        let canine_tokenizer = CanineTokenizer::new();
+        let test_tuples = [
+            ("hi !", vec!["h", "i", " ", "!"]),
+            ("é😀", vec!["é", "😀"]),
+            ("a[MASK]", vec!["a", "[MASK]"]),
+            ("", vec![]),
+        ];
+        let source_texts: Vec<&str> = test_tuples.iter().map(|v| v.0).collect();
+        let expected_results: Vec<Vec<&str>> = test_tuples.iter().map(|v| v.1.clone()).collect();
+
+        //        When & Then
+        for (source_text, expected_result) in test_tuples.iter() {
+            assert_eq!(canine_tokenizer.tokenize(source_text), *expected_result);
+        }
+        assert_eq!(
+            MultiThreadedTokenizer::tokenize_list(&canine_tokenizer, &source_texts),
+            expected_results
+        );
+    }
+
+    #[test]
+    fn test_encode_pair() {
+        //        Given
+        let canine_tokenizer = CanineTokenizer::new();
+
+        //        When
+        let encoded_input =
+            canine_tokenizer.encode("hé", Some("😀"), 128, &TruncationStrategy::LongestFirst, 0);
+
+        //        Then
+        assert_eq!(
+            encoded_input.token_ids,
+            vec![0xE000, 104, 0xE9, 0xE001, 0x1F600, 0xE001]
+        );
+        assert_eq!(encoded_input.segment_ids, vec![0, 0, 0, 0, 1, 1]);
+        assert_eq!(encoded_input.special_tokens_mask, vec![1, 0, 0, 1, 0, 1]);
+        assert_eq!(
+            encoded_input.token_offsets,
+            vec![
+                None,
+                Some(Offset::new(0, 1)),
+                Some(Offset::new(1, 2)),
+                None,
+                Some(Offset::new(0, 1)),
+                None
+            ]
+        );
+    }
+
+    #[test]
+    fn test_decode() {
+        //        Given
+        let canine_tokenizer = CanineTokenizer::new();
+        let test_tuples = [
+            (vec![0xE000, 104, 0xE9, 0xE001], true, "hé"),
+            (vec![0xE000, 104, 0xE9, 0xE001], false, "[CLS]hé[SEP]"),
+            (vec![104, 0xD800, 0x110000], true, "h\u{FFFD}\u{FFFD}"),
+        ];
+
+        //        When & Then
+        for (token_ids, skip_special_tokens, expected_result) in test_tuples.iter() {
+            assert_eq!(
+                canine_tokenizer.decode(token_ids, *skip_special_tokens, false),
+                *expected_result
+            );
+        }
+    }
+}
@@ -0,0 +1,787 @@
+// Copyright 2019-2020 Guillaume Becquin
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//     http://www.apache.org/licenses/LICENSE-2.0
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::error::TokenizerError;
+use crate::tokenizer::base_tokenizer::{Offset, OffsetSize, Tokenizer};
+use crate::vocab::Vocab;
+use aho_corasick::{AhoCorasick, MatchKind};
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use std::collections::{BTreeMap, HashMap};
+use std::fs::File;
+use std::io::BufReader;
+use std::ops::Range;
+use std::path::Path;
+use std::sync::Arc;
+
+mod parser;
+mod renderer;
+
+use parser::{contains_generation_block, Node, TemplateParser};
+use renderer::{Renderer, Value};
+
+/// # Chat message
+/// Message of a conversation, with a role (e.g. `system`, `user` or `assistant`) and a content.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChatMessage {
+    pub role: String,
+    pub content: String,
+}
+
+impl ChatMessage {
+    /// Create a new chat message
+    ///
+    /// # Parameters
+    /// - role (`&str`): role of the message author (`system`, `user`, `assistant`...)
+    /// - content (`&str`): content of the message
+    pub fn new<R: Into<String>, C: Into<String>>(role: R, content: C) -> ChatMessage {
+        ChatMessage {
+            role: role.into(),
+            content: content.into(),
+        }
+    }
+}
+
+/// # Rendered conversation
+/// Prompt rendered by a `ChatTemplate`, with the character spans generated by the assistant.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RenderedChat {
+    /// Rendered prompt
+    pub text: String,
+    /// Character ranges of the prompt produced by the assistant
+    pub assistant_spans: Vec<Range<usize>>,
+}
+
+/// # Encoded conversation
+/// Conversation rendered by a `ChatTemplate` and encoded without additional special tokens.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EncodedChat {
+    /// Token ids of the rendered prompt
+    pub token_ids: Vec<i64>,
+    /// Character offsets of the tokens in the rendered prompt
+    pub token_offsets: Vec<Option<Offset>>,
+    /// 1 for the tokens generated by the assistant (trained on), 0 otherwise
+    pub assistant_mask: Vec<i8>,
+}
+
+/// # Chat template
+/// Renders conversations into prompts for instruction-tuned models, using the Jinja templates
+/// found in the `chat_template` field of `tokenizer_config.json` files.
+///
+/// The following subset of Jinja is supported, with the `trim_blocks` and `lstrip_blocks`
+/// options enabled as in the Python reference implementation:
+/// - `{{ expression }}`, `{# comments #}` and whitespace control (`{%-`, `-%}`)
+/// - `if`/`elif`/`else`, `for` (with `loop` variables, `else` and inline `if` filter), `set`
+///   (including namespace attributes) statements
+/// - literals, lists, dictionaries, attribute and item access, slices, arithmetic, comparison,
+///   `~`, `in`, `and`/`or`/`not`, conditional expressions and `is` tests
+/// - common filters (`trim`, `length`, `upper`, `lower`, `default`, `join`, `tojson`...) and
+///   string methods (`strip`, `startswith`, `split`...)
+/// - `raise_exception`, `namespace` and `range` functions
+///
+/// Parts of the template generated by the assistant can be marked with
+/// `{% generation %}...{% endgeneration %}` blocks. Without them, the assistant turns are
+/// identified by rendering the conversation incrementally.
+#[derive(Debug, Clone)]
+pub struct ChatTemplate {
+    nodes: Vec<Node>,
+    has_generation_blocks: bool,
+    variables: HashMap<String, String>,
+}
+
+impl ChatTemplate {
+    /// Create a new instance of a `ChatTemplate` from a Jinja template string
+    ///
+    /// # Parameters
+    /// - template (`&str`): Jinja chat template
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_tokenizers::tokenizer::{ChatMessage, ChatTemplate};
+    /// let template = "{% for message in messages %}<|{{ message.role }}|>{{ message.content }}\n{% endfor %}";
+    /// let chat_template = ChatTemplate::new(template).unwrap();
+    ///
+    /// let prompt = chat_template
+    ///     .render(&[ChatMessage::new("user", "Hello")], false)
+    ///     .unwrap();
+    /// assert_eq!(prompt, "<|user|>Hello\n");
+    /// ```
+    pub fn new(template: &str) -> Result<ChatTemplate, TokenizerError> {
+        let (nodes, _) = TemplateParser::new(template)?.parse_nodes(&[])?;
+        let has_generation_blocks = contains_generation_block(&nodes);
+        Ok(ChatTemplate {
+            nodes,
+            has_generation_blocks,
+            variables: HashMap::new(),
+        })
+    }
+
+    /// Create a new instance of a `ChatTemplate` from a `tokenizer_config.json` file. The special
+    /// tokens of the configuration (`bos_token`, `eos_token`...) are registered as template
+    /// variables. If the configuration contains several named templates, the `default` one is used.
+    ///
+    /// # Parameters
+    /// - path (`&str`): path to the tokenizer configuration file
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use rust_tokenizers::tokenizer::ChatTemplate;
+    /// let chat_template =
+    ///     ChatTemplate::from_tokenizer_config_file("path/to/tokenizer_config.json").unwrap();
+    /// ```
+    pub fn from_tokenizer_config_file<P: AsRef<Path>>(
+        path: P,
+    ) -> Result<ChatTemplate, TokenizerError> {
+        let f = File::open(&path).map_err(|e| {
+            TokenizerError::FileNotFound(format!(
+                "{} tokenizer configuration file not found :{}",
+                path.as_ref().display(),
+                e
+            ))
+        })?;
+        let config: JsonValue = serde_json::from_reader(BufReader::new(f))
+            .map_err(|e| TokenizerError::VocabularyParsingError(e.to_string()))?;
+        let template = match config.get("chat_template") {
+            Some(JsonValue::String(template)) => template.as_str(),
+            Some(JsonValue::Array(templates)) => templates
+                .iter()
+                .find(|template| {
+                    template.get("name").and_then(JsonValue::as_str) == Some("default")
+                })
+                .and_then(|template| template.get("template"))
+                .and_then(JsonValue::as_str)
+                .ok_or_else(|| {
+                    TokenizerError::ValueError(
+                        "The tokenizer configuration has no default chat template".to_string(),
+                    )
+                })?,
+            _ => {
+                return Err(TokenizerError::ValueError(
+                    "The tokenizer configuration has no chat template".to_string(),
+                ))
+            }
+        };
+        let mut chat_template = ChatTemplate::new(template)?;
+        if let JsonValue::Object(entries) = &config {
+            for (key, value) in entries.iter().filter(|(key, _)| key.ends_with("_token")) {
+                let token = match value {
+                    JsonValue::String(token) => Some(token.as_str()),
+                    JsonValue::Object(token) => token.get("content").and_then(JsonValue::as_str),
+                    _ => None,
+                };
+                if let Some(token) = token {
+                    chat_template.set_variable(key, token);
+                }
+            }
+        }
+        Ok(chat_template)
+    }
+
+    /// Sets a string variable available to the template (e.g. `bos_token`, `eos_token`)
+    ///
+    /// # Parameters
+    /// - name (`&str`): variable name
+    /// - value (`&str`): variable value
+    pub fn set_variable(&mut self, name: &str, value: &str) {
+        self.variables.insert(name.to_string(), value.to_string());
+    }
+
+    /// Renders a conversation into a prompt
+    ///
+    /// # Parameters
+    /// - messages (`&[ChatMessage]`): conversation to render
+    /// - add_generation_prompt (`bool`): append the prompt starting an assistant turn
+    ///
+    /// # Returns
+    /// - `String` containing the rendered prompt
+    pub fn render(
+        &self,
+        messages: &[ChatMessage],
+        add_generation_prompt: bool,
+    ) -> Result<String, TokenizerError> {
+        Ok(self.render_spans(messages, add_generation_prompt)?.0)
+    }
+
+    /// Renders a conversation into a prompt, identifying the character spans generated by the
+    /// assistant. The spans are given by the `{% generation %}` blocks of the template if any,
+    /// otherwise by rendering the conversation up to each assistant message (the span then
+    /// includes the end of turn markers following the message).
+    ///
+    /// # Parameters
+    /// - messages (`&[ChatMessage]`): conversation to render
+    /// - add_generation_prompt (`bool`): append the prompt starting an assistant turn
+    ///
+    /// # Returns
+    /// - `RenderedChat` containing the rendered prompt and the assistant spans
+    pub fn render_with_assistant_spans(
+        &self,
+        messages: &[ChatMessage],
+        add_generation_prompt: bool,
+    ) -> Result<RenderedChat, TokenizerError> {
+        let (text, generation_spans) = self.render_spans(messages, add_generation_prompt)?;
+        if self.has_generation_blocks {
+            return Ok(RenderedChat {
+                text,
+                assistant_spans: generation_spans,
+            });
+        }
+        let mut assistant_spans = Vec::new();
+        for (index, message) in messages.iter().enumerate() {
+            if message.role != "assistant" {
+                continue;
+            }
+            let prefix = self.render(&messages[..index], true)?;
+            let turn = self.render(&messages[..=index], false)?;
+            if !turn.starts_with(&prefix) || !text.starts_with(&turn) {
+                return Err(TokenizerError::ValueError(
+                    "The chat template does not render conversations incrementally, \
+                    assistant turns must be marked with {% generation %} blocks"
+                        .to_string(),
+                ));
+            }
+            let span = prefix.chars().count()..turn.chars().count();
+            if !span.is_empty() {
+                assistant_spans.push(span);
+            }
+        }
+        Ok(RenderedChat {
+            text,
+            assistant_spans,
+        })
+    }
+
+    /// Renders and encodes a conversation, without adding the special tokens of the tokenizer.
+    /// Special tokens emitted by the template are encoded as control tokens: the special values
+    /// of the vocabulary (e.g. registered with `add_tokens`) and the token variables of the
+    /// template (`bos_token`, `eos_token`...) found in the vocabulary. The content of the
+    /// messages is tokenized as plain text: special token strings it contains are not converted
+    /// to control tokens.
+    ///
+    /// # Parameters
+    /// - tokenizer (`&T`): tokenizer used to encode the prompt
+    /// - messages (`&[ChatMessage]`): conversation to encode
+    /// - add_generation_prompt (`bool`): append the prompt starting an assistant turn
+    ///
+    /// # Returns
+    /// - `EncodedChat` containing the token ids, offsets and assistant mask
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use rust_tokenizers::tokenizer::{ChatMessage, ChatTemplate, Gpt2Tokenizer, Tokenizer};
+    /// let mut tokenizer =
+    ///     Gpt2Tokenizer::from_file("path/to/vocab/file", "path/to/merges/file", false).unwrap();
+    /// tokenizer.add_tokens(&["<|im_start|>", "<|im_end|>"]);
+    /// let chat_template =
+    ///     ChatTemplate::from_tokenizer_config_file("path/to/tokenizer_config.json").unwrap();
+    ///
+    /// let messages = [
+    ///     ChatMessage::new("user", "Hello"),
+    ///     ChatMessage::new("assistant", "Hi! How can I help?"),
+    /// ];
+    /// let encoded = chat_template.encode(&tokenizer, &messages, false).unwrap();
+    /// ```
+    pub fn encode<T, V>(
+        &self,
+        tokenizer: &T,
+        messages: &[ChatMessage],
+        add_generation_prompt: bool,
+    ) -> Result<EncodedChat, TokenizerError>
+    where
+        T: Tokenizer<V> + ?Sized,
+        V: Vocab,
+    {
+        let vocab = tokenizer.vocab();
+        let mut control_tokens = vocab.special_values().clone();
+        for (name, value) in &self.variables {
+            if let (true, Some(id)) = (name.ends_with("_token"), vocab.values().get(value)) {
+                control_tokens.insert(value.clone(), *id);
+            }
+        }
+        let control_matcher = build_matcher(control_tokens.keys())?;
+        let special_matcher = build_matcher(vocab.special_values().keys())?;
+
+        let rendered = self.render_with_assistant_spans(messages, add_generation_prompt)?;
+        // The conversation is rendered a second time with the control tokens of the messages
+        // escaped: the control tokens remaining in this rendering are emitted by the template.
+        let escaped_messages: Vec<ChatMessage> = messages
+            .iter()
+            .map(|message| ChatMessage {
+                role: message.role.clone(),
+                content: escape_control_tokens(&message.content, &control_matcher),
+            })
+            .collect();
+        let escaped_text = self.render(&escaped_messages, add_generation_prompt)?;
+        if escaped_text.chars().count() != rendered.text.chars().count() {
+            return Err(template_error(
+                "the template transforms the control tokens of the messages".to_string(),
+            ));
+        }
+        let byte_positions = char_byte_positions(&rendered.text);
+        let escaped_byte_positions = char_byte_positions(&escaped_text);
+
+        let mut token_ids = Vec::new();
+        let mut token_offsets = Vec::new();
+        let mut begin = 0;
+        for hit in control_matcher.find_iter(&escaped_text) {
+            let start = escaped_byte_positions.binary_search(&hit.start()).unwrap();
+            let end = escaped_byte_positions.binary_search(&hit.end()).unwrap();
+            tokenize_text(
+                tokenizer,
+                &rendered.text[byte_positions[begin]..byte_positions[start]],
+                begin,
+                &special_matcher,
+                &mut token_ids,
+                &mut token_offsets,
+            );
+            token_ids.push(control_tokens[&escaped_text[hit.range()]]);
+            token_offsets.push(Some(Offset::new(start as OffsetSize, end as OffsetSize)));
+            begin = end;
+        }
+        tokenize_text(
+            tokenizer,
+            &rendered.text[byte_positions[begin]..],
+            begin,
+            &special_matcher,
+            &mut token_ids,
+            &mut token_offsets,
+        );
+
+        let assistant_mask = token_offsets
+            .iter()
+            .map(|offset| match offset {
+                Some(offset)
+                    if rendered.assistant_spans.iter().any(|span| {
+                        (offset.begin as usize) < span.end && (offset.end as usize) > span.start
+                    }) =>
+                {
+                    1
+                }
+                _ => 0,
+            })
+            .collect();
+        Ok(EncodedChat {
+            token_ids,
+            token_offsets,
+            assistant_mask,
+        })
+    }
+
+    fn render_spans(
+        &self,
+        messages: &[ChatMessage],
+        add_generation_prompt: bool,
+    ) -> Result<(String, Vec<Range<usize>>), TokenizerError> {
+        let mut globals: HashMap<String, Value> = self
+            .variables
+            .iter()
+            .map(|(name, value)| (name.clone(), Value::Str(value.clone())))
+            .collect();
+        let messages = messages
+            .iter()
+            .map(|message| {
+                let mut fields = BTreeMap::new();
+                fields.insert("role".to_string(), Value::Str(message.role.clone()));
+                fields.insert("content".to_string(), Value::Str(message.content.clone()));
+                Value::Map(Arc::new(fields))
+            })
+            .collect();
+        globals.insert("messages".to_string(), Value::List(Arc::new(messages)));
+        globals.insert(
+            "add_generation_prompt".to_string(),
+            Value::Bool(add_generation_prompt),
+        );
+        let mut renderer = Renderer {
+            scopes: vec![globals],
+            output: String::new(),
+            output_chars: 0,
+            generation_spans: Vec::new(),
+        };
+        renderer.render_nodes(&self.nodes)?;
+        Ok((renderer.output, renderer.generation_spans))
+    }
+}
+
+fn template_error(message: String) -> TokenizerError {
+    TokenizerError::ValueError(format!("Chat template error: {message}"))
+}
+
+/// Returns the byte position of each character of a text, followed by the length of the text
+fn char_byte_positions(text: &str) -> Vec<usize> {
+    text.char_indices()
+        .map(|(position, _)| position)
+        .chain(std::iter::once(text.len()))
+        .collect()
+}
+
+/// Character replacing the first character of the control tokens found in the messages
+const ESCAPE_CHARACTER: char = '\u{E000}';
+
+fn build_matcher<'a>(
+    values: impl IntoIterator<Item = &'a String>,
+) -> Result<AhoCorasick, TokenizerError> {
+    AhoCorasick::builder()
+        .match_kind(MatchKind::LeftmostLongest)
+        .build(values)
+        .map_err(|e| TokenizerError::ValueError(e.to_string()))
+}
+
+fn escape_control_tokens(text: &str, control_matcher: &AhoCorasick) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut last_end = 0;
+    for hit in control_matcher.find_iter(text) {
+        let first_character = text[hit.start()..].chars().next().unwrap();
+        output.push_str(&text[last_end..hit.start()]);
+        output.push(ESCAPE_CHARACTER);
+        last_end = hit.start() + first_character.len_utf8();
+    }
+    output.push_str(&text[last_end..]);
+    output
+}
+
+/// Tokenizes a text as plain text, starting at the character `char_offset` of the prompt. The
+/// text is cut after the first character of the special values it contains, so that the
+/// tokenizer does not split them as special tokens.
+fn tokenize_text<T, V>(
+    tokenizer: &T,
+    text: &str,
+    char_offset: usize,
+    special_matcher: &AhoCorasick,
+    token_ids: &mut Vec<i64>,
+    token_offsets: &mut Vec<Option<Offset>>,
+) where
+    T: Tokenizer<V> + ?Sized,
+    V: Vocab,
+{
+    let mut start = 0;
+    let mut char_offset = char_offset;
+    while start < text.len() {
+        let end = match special_matcher.find(&text[start..]) {
+            Some(hit) => {
+                let first_character = text[start + hit.start()..].chars().next().unwrap();
+                start + hit.start() + first_character.len_utf8()
+            }
+            None => text.len(),
+        };
+        let piece = &text[start..end];
+        let tokens = tokenizer.tokenize_with_offsets(piece);
+        token_ids.extend(tokenizer.convert_tokens_to_ids(&tokens.tokens));
+        token_offsets.extend(tokens.offsets.into_iter().map(|offset| {
+            offset.map(|offset| {
+                Offset::new(
+                    offset.begin + char_offset as OffsetSize,
+                    offset.end + char_offset as OffsetSize,
+                )
+            })
+        }));
+        char_offset += piece.chars().count();
+        start = end;
+    }
+}
+
+//==============================
+// Unit tests
+//==============================
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tokenizer::BertTokenizer;
+    use crate::vocab::base_vocab::SpecialTokenMap;
+    use crate::vocab::BertVocab;
+
+    const CHATML_TEMPLATE: &str = "{% for message in messages %}{{'<|im_start|>' + message['role'] + '\n' + message['content'] + '<|im_end|>' + '\n'}}{% endfor %}{% if add_generation_prompt %}{{ '<|im_start|>assistant\n' }}{% endif %}";
+
+    const MISTRAL_TEMPLATE: &str = "{{ bos_token }}{% for message in messages %}{% if (message['role'] == 'user') != (loop.index0 % 2 == 0) %}{{ raise_exception('Conversation roles must alternate user/assistant/user/assistant/...') }}{% endif %}{% if message['role'] == 'user' %}{{ '[INST] ' + message['content'] + ' [/INST]' }}{% elif message['role'] == 'assistant' %}{{ message['content'] + eos_token}}{% else %}{{ raise_exception('Only user and assistant roles are supported!') }}{% endif %}{% endfor %}";
+
+    fn conversation() -> Vec<ChatMessage> {
+        vec![
+            ChatMessage::new("user", "Hello"),
+            ChatMessage::new("assistant", "Hi there"),
+            ChatMessage::new("user", "Bye"),
+        ]
+    }
+
+    #[test]
+    fn test_render_chat_templates() {
+        //        Given
+        let chatml = ChatTemplate::new(CHATML_TEMPLATE).unwrap();
+        let mut mistral = ChatTemplate::new(MISTRAL_TEMPLATE).unwrap();
+        mistral.set_variable("bos_token", "<s>");
+        mistral.set_variable("eos_token", "</s>");
+        let llama = ChatTemplate::new(
+            "{%- if messages[0]['role'] == 'system' %}
+    {%- set system_message = messages[0]['content'] | trim %}
+    {%- set loop_messages = messages[1:] %}
+{%- else %}
+    {%- set system_message = 'Be helpful.' %}
+    {%- set loop_messages = messages %}
+{%- endif %}
+{%- set ns = namespace(turns=0) %}
+<<SYS>>{{ system_message }}<</SYS>>
+{% for message in loop_messages if message.role != 'tool' %}
+    {% set ns.turns = ns.turns + 1 %}
+    {{ loop.index }}. {{ message.role | upper }}: {{ message.content.strip() ~ ('' if loop.last else ' |') }}
+{% endfor %}
+turns={{ ns.turns }} {{ messages | length }} {{ 'yes' if 'Bye' in messages[-1].content }}",
+        )
+        .unwrap();
+
+        //        When
+        let chatml_prompt = chatml.render(&conversation(), true).unwrap();
+        let mistral_prompt = mistral.render(&conversation(), false).unwrap();
+        let mut messages = vec![
+            ChatMessage::new("system", " Be brief. "),
+            ChatMessage::new("tool", "ignored"),
+        ];
+        messages.extend(conversation());
+        let llama_prompt = llama.render(&messages, false).unwrap();
+
+        //        Then
+        assert_eq!(
+            chatml_prompt,
+            "<|im_start|>user\nHello<|im_end|>\n<|im_start|>assistant\nHi there<|im_end|>\n\
+            <|im_start|>user\nBye<|im_end|>\n<|im_start|>assistant\n"
+        );
+        assert_eq!(
+            mistral_prompt,
+            "<s>[INST] Hello [/INST]Hi there</s>[INST] Bye [/INST]"
+        );
+        assert_eq!(
+            llama_prompt,
+            "<<SYS>>Be brief.<</SYS>>\n    1. USER: Hello |\n    2. ASSISTANT: Hi there |\n    \
+            3. USER: Bye\nturns=3 5 yes"
+        );
+        assert!(mistral
+            .render(&[ChatMessage::new("assistant", "Hi")], false)
+            .is_err());
+        assert!(ChatTemplate::new("{% if true %}unclosed").is_err());
+        assert!(ChatTemplate::new("{{ messages[0] }").is_err());
+    }
+
+    #[test]
+    fn test_render_expressions() {
+        //        Given
+        let template = ChatTemplate::new(
+            "  {% if x %}\n  a {{- ' b ' -}} c\n{%+ if y %}z{% endif %}{# c #}\n{{ [1,'a',none,true,2.0] }} {{ {'k': 'v'} | tojson }} {{ 7 // -2 }} {{ -7 % 3 }} {{ 'abc'[::-1] }} {{ [1,2,3][-2:] }} {{ 'a,b'.split(',') | join('-') }} {{ x | default('dflt') }} {{ 'Hi %s' ~ 1 }}{% endif %}",
+        )
+        .unwrap();
+        let mut variables = template.clone();
+        variables.set_variable("x", "set");
+
+        //        When
+        let undefined_prompt = template.render(&[], false).unwrap();
+        let prompt = variables.render(&[], false).unwrap();
+
+        //        Then
+        assert_eq!(undefined_prompt, "");
+        assert_eq!(
+            prompt,
+            "  a b c\n[1, 'a', None, True, 2.0] {\"k\": \"v\"} -4 2 cba [2, 3] a-b set Hi %s1"
+        );
+    }
+
+    #[test]
+    fn test_assistant_spans() {
+        //        Given
+        let chatml = ChatTemplate::new(CHATML_TEMPLATE).unwrap();
+        let generation = ChatTemplate::new(
+            "{% for message in messages %}{{ message.role }}: {% if message.role == 'assistant' %}{% generation %}{{ message.content }}{% endgeneration %}{% else %}{{ message.content }}{% endif %}\n{% endfor %}",
+        )
+        .unwrap();
+
+        //        When
+        let chatml_rendered = chatml
+            .render_with_assistant_spans(&conversation(), false)
+            .unwrap();
+        let generation_rendered = generation
+            .render_with_assistant_spans(&conversation(), false)
+            .unwrap();
+
+        //        Then
+        let span_text = |rendered: &RenderedChat| -> Vec<String> {
+            rendered
+                .assistant_spans
+                .iter()
+                .map(|span| {
+                    rendered
+                        .text
+                        .chars()
+                        .skip(span.start)
+                        .take(span.len())
+                        .collect()
+                })
+                .collect()
+        };
+        assert_eq!(span_text(&chatml_rendered), vec!["Hi there<|im_end|>\n"]);
+        assert_eq!(span_text(&generation_rendered), vec!["Hi there"]);
+    }
+
+    #[test]
+    fn test_encode_chat() {
+        //        Given
+        let values: HashMap<String, i64> = [
+            "[UNK]",
+            "[CLS]",
+            "[SEP]",
+            "[PAD]",
+            "[MASK]",
+            "user",
+            "assistant",
+            "hello",
+            "hi",
+            "there",
+            "bye",
+        ]
+        .iter()
+        .enumerate()
+        .map(|(id, token)| (token.to_string(), id as i64))
+        .collect();
+        let special_token_map = SpecialTokenMap {
+            unk_token: "[UNK]".to_string(),
+            pad_token: Some("[PAD]".to_string()),
+            bos_token: None,
+            sep_token: Some("[SEP]".to_string()),
+            cls_token: Some("[CLS]".to_string()),
+            eos_token: None,
+            mask_token: Some("[MASK]".to_string()),
+            additional_special_tokens: None,
+        };
+        let vocab =
+            BertVocab::from_values_and_special_token_map(values, special_token_map).unwrap();
+        let mut tokenizer = BertTokenizer::from_existing_vocab(vocab, true, true);
+        tokenizer.add_tokens(&["<|im_start|>", "<|im_end|>"]);
+        let chatml = ChatTemplate::new(CHATML_TEMPLATE).unwrap();
+
+        //        When
+        let encoded = chatml.encode(&tokenizer, &conversation(), false).unwrap();
+
+        //        Then
+        assert_eq!(
+            encoded.token_ids,
+            vec![11, 5, 7, 12, 11, 6, 8, 9, 12, 11, 5, 10, 12]
+        );
+        assert_eq!(
+            encoded.assistant_mask,
+            vec![0, 0, 0, 0, 0, 0, 1, 1, 1, 0, 0, 0, 0]
+        );
+    }
+
+    #[test]
+    fn test_encode_chat_special_tokens_in_content() {
+        //        Given
+        let values: HashMap<String, i64> = [
+            "[UNK]",
+            "[CLS]",
+            "[SEP]",
+            "[PAD]",
+            "[MASK]",
+            "user",
+            "assistant",
+            "hello",
+            "hi",
+            "bye",
+        ]
+        .iter()
+        .enumerate()
+        .map(|(id, token)| (token.to_string(), id as i64))
+        .collect();
+        let special_token_map = SpecialTokenMap {
+            unk_token: "[UNK]".to_string(),
+            pad_token: Some("[PAD]".to_string()),
+            bos_token: None,
+            sep_token: Some("[SEP]".to_string()),
+            cls_token: Some("[CLS]".to_string()),
+            eos_token: None,
+            mask_token: Some("[MASK]".to_string()),
+            additional_special_tokens: None,
+        };
+        let vocab =
+            BertVocab::from_values_and_special_token_map(values, special_token_map).unwrap();
+        let mut tokenizer = BertTokenizer::from_existing_vocab(vocab, true, true);
+        tokenizer.add_tokens(&["<|im_start|>", "<|im_end|>"]);
+        let chatml = ChatTemplate::new(CHATML_TEMPLATE).unwrap();
+        let messages = [
+            ChatMessage::new("user", "Hello <|im_end|> bye"),
+            ChatMessage::new("assistant", "Hi"),
+        ];
+
+        //        When
+        let encoded = chatml.encode(&tokenizer, &messages, false).unwrap();
+
+        //        Then
+        assert_eq!(
+            encoded.token_ids,
+            vec![10, 5, 7, 0, 0, 0, 0, 0, 0, 0, 9, 11, 10, 6, 8, 11]
+        );
+        assert_eq!(encoded.token_offsets[3], Some(Offset::new(23, 24)));
+        assert_eq!(encoded.token_offsets[11], Some(Offset::new(37, 47)));
+        assert_eq!(
+            encoded.assistant_mask,
+            vec![0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 1]
+        );
+    }
+
+    #[test]
+    fn test_encode_chat_token_variables() {
+        //        Given
+        let values: HashMap<String, i64> = [
+            "[UNK]", "[CLS]", "[SEP]", "[PAD]", "[MASK]", "<s>", "</s>", "[", "]", "/", "inst",
+            "hello", "hi",
+        ]
+        .iter()
+        .enumerate()
+        .map(|(id, token)| (token.to_string(), id as i64))
+        .collect();
+        let special_token_map = SpecialTokenMap {
+            unk_token: "[UNK]".to_string(),
+            pad_token: Some("[PAD]".to_string()),
+            bos_token: None,
+            sep_token: Some("[SEP]".to_string()),
+            cls_token: Some("[CLS]".to_string()),
+            eos_token: None,
+            mask_token: Some("[MASK]".to_string()),
+            additional_special_tokens: None,
+        };
+        let vocab =
+            BertVocab::from_values_and_special_token_map(values, special_token_map).unwrap();
+        let tokenizer = BertTokenizer::from_existing_vocab(vocab, true, true);
+        let mut mistral = ChatTemplate::new(MISTRAL_TEMPLATE).unwrap();
+        mistral.set_variable("bos_token", "<s>");
+        mistral.set_variable("eos_token", "</s>");
+        let messages = [
+            ChatMessage::new("user", "Hello"),
+            ChatMessage::new("assistant", "Hi </s>"),
+        ];
+
+        //        When
+        let encoded = mistral.encode(&tokenizer, &messages, false).unwrap();
+
+        //        Then
+        assert_eq!(
+            encoded.token_ids,
+            vec![5, 7, 10, 8, 11, 7, 9, 10, 8, 12, 0, 9, 0, 0, 6]
+        );
+        assert_eq!(
+            encoded.assistant_mask,
+            vec![0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 1, 1]
+        );
+    }
+
+    #[test]
+    fn test_chat_template_is_send_and_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<ChatTemplate>();
+        assert_send_sync::<RenderedChat>();
+        assert_send_sync::<EncodedChat>();
+    }
+}
@@ -0,0 +1,843 @@
+// Copyright 2019-2020 Guillaume Becquin
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//     http://www.apache.org/licenses/LICENSE-2.0
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::renderer::Value;
+use super::template_error;
+use crate::error::TokenizerError;
+
+//==============================
+// Template lexing
+//==============================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TagKind {
+    Expression,
+    Statement,
+    Comment,
+}
+
+#[derive(Debug)]
+enum Segment {
+    Text(String),
+    Tag {
+        kind: TagKind,
+        content: String,
+        trim_left: bool,
+        trim_right: bool,
+        keep_left: bool,
+        keep_right: bool,
+    },
+}
+
+fn find_tag_end(content: &str, closing: &str, skip_strings: bool) -> Option<usize> {
+    let mut quote: Option<char> = None;
+    let mut escaped = false;
+    for (position, character) in content.char_indices() {
+        match quote {
+            Some(quote_character) => {
+                if escaped {
+                    escaped = false;
+                } else if character == '\\' {
+                    escaped = true;
+                } else if character == quote_character {
+                    quote = None;
+                }
+            }
+            None => {
+                if skip_strings && (character == '\'' || character == '"') {
+                    quote = Some(character);
+                } else if content[position..].starts_with(closing) {
+                    return Some(position);
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Splits a template into text and tags, applying the whitespace control, `trim_blocks` and
+/// `lstrip_blocks` rules.
+fn lex_template(source: &str) -> Result<Vec<Segment>, TokenizerError> {
+    let mut segments = Vec::new();
+    let mut rest = source;
+    while !rest.is_empty() {
+        let tag_start = ["{{", "{%", "{#"]
+            .iter()
+            .filter_map(|opening| rest.find(opening))
+            .min();
+        let position = match tag_start {
+            Some(position) => position,
+            None => {
+                segments.push(Segment::Text(rest.to_string()));
+                break;
+            }
+        };
+        if position > 0 {
+            segments.push(Segment::Text(rest[..position].to_string()));
+        }
+        let (kind, closing) = match rest.as_bytes()[position + 1] {
+            b'{' => (TagKind::Expression, "}}"),
+            b'%' => (TagKind::Statement, "%}"),
+            _ => (TagKind::Comment, "#}"),
+        };
+        let inner = &rest[position + 2..];
+        let end = find_tag_end(inner, closing, kind != TagKind::Comment)
+            .ok_or_else(|| template_error(format!("unclosed tag, expected `{closing}`")))?;
+        let mut content = &inner[..end];
+        let (mut trim_left, mut trim_right, mut keep_left, mut keep_right) =
+            (false, false, false, false);
+        if let Some(stripped) = content.strip_prefix('-') {
+            trim_left = true;
+            content = stripped;
+        } else if let Some(stripped) = content.strip_prefix('+') {
+            keep_left = true;
+            content = stripped;
+        }
+        if let Some(stripped) = content.strip_suffix('-') {
+            trim_right = true;
+            content = stripped;
+        } else if let Some(stripped) = content.strip_suffix('+') {
+            keep_right = true;
+            content = stripped;
+        }
+        segments.push(Segment::Tag {
+            kind,
+            content: content.trim().to_string(),
+            trim_left,
+            trim_right,
+            keep_left,
+            keep_right,
+        });
+        rest = &inner[end + 2..];
+    }
+
+    // Whitespace before the tags is stripped first, as `lstrip_blocks` applies to the source
+    // lines (before `trim_blocks` removes the newline of the previous block)
+    for index in 1..segments.len() {
+        let (is_block, trim_left, keep_left) = match &segments[index] {
+            Segment::Tag {
+                kind,
+                trim_left,
+                keep_left,
+                ..
+            } => (*kind != TagKind::Expression, *trim_left, *keep_left),
+            Segment::Text(_) => continue,
+        };
+        if let Segment::Text(text) = &mut segments[index - 1] {
+            if trim_left {
+                text.truncate(text.trim_end().len());
+            } else if is_block && !keep_left {
+                let line_start = text.rfind('\n').map(|position| position + 1);
+                let at_line_start = line_start.is_some() || index == 1;
+                let line_start = line_start.unwrap_or(0);
+                if at_line_start && text[line_start..].chars().all(|c| c == ' ' || c == '\t') {
+                    text.truncate(line_start);
+                }
+            }
+        }
+    }
+    for index in 0..segments.len().saturating_sub(1) {
+        let (is_block, trim_right, keep_right) = match &segments[index] {
+            Segment::Tag {
+                kind,
+                trim_right,
+                keep_right,
+                ..
+            } => (*kind != TagKind::Expression, *trim_right, *keep_right),
+            Segment::Text(_) => continue,
+        };
+        if let Segment::Text(text) = &mut segments[index + 1] {
+            if trim_right {
+                *text = text.trim_start().to_string();
+            } else if is_block && !keep_right {
+                if let Some(stripped) = text.strip_prefix('\n') {
+                    *text = stripped.to_string();
+                } else if let Some(stripped) = text.strip_prefix("\r\n") {
+                    *text = stripped.to_string();
+                }
+            }
+        }
+    }
+    Ok(segments)
+}
+
+//==============================
+// Template parsing
+//==============================
+
+#[derive(Debug, Clone)]
+pub(super) enum Node {
+    Text(String),
+    Output(Expr),
+    If {
+        branches: Vec<(Expr, Vec<Node>)>,
+        otherwise: Vec<Node>,
+    },
+    For {
+        targets: Vec<String>,
+        iterable: Expr,
+        condition: Option<Expr>,
+        body: Vec<Node>,
+        otherwise: Vec<Node>,
+    },
+    Set {
+        name: String,
+        attribute: Option<String>,
+        value: Expr,
+    },
+    Generation(Vec<Node>),
+}
+
+pub(super) fn contains_generation_block(nodes: &[Node]) -> bool {
+    nodes.iter().any(|node| match node {
+        Node::Generation(_) => true,
+        Node::If {
+            branches,
+            otherwise,
+        } => {
+            branches
+                .iter()
+                .any(|(_, body)| contains_generation_block(body))
+                || contains_generation_block(otherwise)
+        }
+        Node::For {
+            body, otherwise, ..
+        } => contains_generation_block(body) || contains_generation_block(otherwise),
+        _ => false,
+    })
+}
+
+pub(super) struct TemplateParser {
+    segments: Vec<Segment>,
+    position: usize,
+}
+
+impl TemplateParser {
+    pub(super) fn new(template: &str) -> Result<TemplateParser, TokenizerError> {
+        Ok(TemplateParser {
+            segments: lex_template(template)?,
+            position: 0,
+        })
+    }
+
+    /// Parses nodes until one of the `end_keywords` statements, returning the nodes and the
+    /// content of the closing statement.
+    pub(super) fn parse_nodes(
+        &mut self,
+        end_keywords: &[&str],
+    ) -> Result<(Vec<Node>, String), TokenizerError> {
+        let mut nodes = Vec::new();
+        while self.position < self.segments.len() {
+            let position = self.position;
+            self.position += 1;
+            let (kind, content) = match &self.segments[position] {
+                Segment::Text(text) => {
+                    if !text.is_empty() {
+                        nodes.push(Node::Text(text.clone()));
+                    }
+                    continue;
+                }
+                Segment::Tag { kind, content, .. } => (*kind, content.clone()),
+            };
+            match kind {
+                TagKind::Comment => {}
+                TagKind::Expression => nodes.push(Node::Output(parse_expression(&content)?)),
+                TagKind::Statement => {
+                    let keyword = content.split_whitespace().next().unwrap_or_default();
+                    if end_keywords.contains(&keyword) {
+                        return Ok((nodes, content));
+                    }
+                    nodes.push(self.parse_statement(keyword, &content)?);
+                }
+            }
+        }
+        match end_keywords.last() {
+            Some(keyword) => Err(template_error(format!("missing `{{% {keyword} %}}`"))),
+            None => Ok((nodes, String::new())),
+        }
+    }
+
+    fn parse_statement(&mut self, keyword: &str, content: &str) -> Result<Node, TokenizerError> {
+        let arguments = content[keyword.len()..].trim();
+        match keyword {
+            "if" => {
+                let mut branches = Vec::new();
+                let mut condition = parse_expression(arguments)?;
+                loop {
+                    let (body, end) = self.parse_nodes(&["elif", "else", "endif"])?;
+                    branches.push((condition, body));
+                    if let Some(elif_condition) = end.strip_prefix("elif") {
+                        condition = parse_expression(elif_condition)?;
+                        continue;
+                    }
+                    let otherwise = if end.starts_with("else") {
+                        self.parse_nodes(&["endif"])?.0
+                    } else {
+                        Vec::new()
+                    };
+                    return Ok(Node::If {
+                        branches,
+                        otherwise,
+                    });
+                }
+            }
+            "for" => {
+                let mut parser = ExpressionParser::new(arguments)?;
+                let mut targets = vec![parser.expect_name()?];
+                while parser.consume_operator(",") {
+                    targets.push(parser.expect_name()?);
+                }
+                parser.expect_keyword("in")?;
+                let iterable = parser.parse_or()?;
+                let condition = if parser.consume_keyword("if") {
+                    Some(parser.parse_or()?)
+                } else {
+                    None
+                };
+                parser.expect_end()?;
+                let (body, end) = self.parse_nodes(&["else", "endfor"])?;
+                let otherwise = if end.starts_with("else") {
+                    self.parse_nodes(&["endfor"])?.0
+                } else {
+                    Vec::new()
+                };
+                Ok(Node::For {
+                    targets,
+                    iterable,
+                    condition,
+                    body,
+                    otherwise,
+                })
+            }
+            "set" => {
+                let mut parser = ExpressionParser::new(arguments)?;
+                let name = parser.expect_name()?;
+                let attribute = if parser.consume_operator(".") {
+                    Some(parser.expect_name()?)
+                } else {
+                    None
+                };
+                if !parser.consume_operator("=") {
+                    return Err(template_error(format!(
+                        "expected `=` in `{{% {content} %}}`"
+                    )));
+                }
+                let value = parser.parse_expression()?;
+                parser.expect_end()?;
+                Ok(Node::Set {
+                    name,
+                    attribute,
+                    value,
+                })
+            }
+            "generation" => Ok(Node::Generation(self.parse_nodes(&["endgeneration"])?.0)),
+            _ => Err(template_error(format!(
+                "unsupported statement `{{% {content} %}}`"
+            ))),
+        }
+    }
+}
+
+//==============================
+// Expression parsing
+//==============================
+
+#[derive(Debug, Clone, PartialEq)]
+enum ExprToken {
+    Name(String),
+    Str(String),
+    Int(i64),
+    Float(f64),
+    Operator(&'static str),
+}
+
+const OPERATORS: [&str; 25] = [
+    "==", "!=", "<=", ">=", "//", "**", "<", ">", "+", "-", "*", "/", "%", "~", "|", ".", ",", ":",
+    "(", ")", "[", "]", "{", "}", "=",
+];
+
+fn lex_expression(source: &str) -> Result<Vec<ExprToken>, TokenizerError> {
+    let characters: Vec<char> = source.chars().collect();
+    let mut tokens = Vec::new();
+    let mut position = 0;
+    while position < characters.len() {
+        let character = characters[position];
+        if character.is_whitespace() {
+            position += 1;
+        } else if character == '\'' || character == '"' {
+            let mut value = String::new();
+            position += 1;
+            loop {
+                match characters.get(position) {
+                    None => return Err(template_error(format!("unclosed string in `{source}`"))),
+                    Some(&c) if c == character => break,
+                    Some('\\') => {
+                        position += 1;
+                        match characters.get(position) {
+                            Some('n') => value.push('\n'),
+                            Some('t') => value.push('\t'),
+                            Some('r') => value.push('\r'),
+                            Some(&escaped) => value.push(escaped),
+                            None => {
+                                return Err(template_error(format!(
+                                    "unclosed string in `{source}`"
+                                )))
+                            }
+                        }
+                    }
+                    Some(&c) => value.push(c),
+                }
+                position += 1;
+            }
+            position += 1;
+            tokens.push(ExprToken::Str(value));
+        } else if character.is_ascii_digit() {
+            let start = position;
+            while position < characters.len()
+                && (characters[position].is_ascii_digit()
+                    || characters[position] == '_'
+                    || (characters[position] == '.'
+                        && characters
+                            .get(position + 1)
+                            .is_some_and(|c| c.is_ascii_digit())))
+            {
+                position += 1;
+            }
+            let number: String = characters[start..position]
+                .iter()
+                .filter(|c| **c != '_')
+                .collect();
+            let invalid = || template_error(format!("invalid number `{number}`"));
+            tokens.push(if number.contains('.') {
+                ExprToken::Float(number.parse().map_err(|_| invalid())?)
+            } else {
+                ExprToken::Int(number.parse().map_err(|_| invalid())?)
+            });
+        } else if character.is_alphabetic() || character == '_' {
+            let start = position;
+            while position < characters.len()
+                && (characters[position].is_alphanumeric() || characters[position] == '_')
+            {
+                position += 1;
+            }
+            tokens.push(ExprToken::Name(
+                characters[start..position].iter().collect(),
+            ));
+        } else {
+            let rest: String = characters[position..characters.len().min(position + 2)]
+                .iter()
+                .collect();
+            let operator = OPERATORS
+                .iter()
+                .find(|operator| rest.starts_with(**operator))
+                .ok_or_else(|| {
+                    template_error(format!("unexpected character `{character}` in `{source}`"))
+                })?;
+            position += operator.chars().count();
+            tokens.push(ExprToken::Operator(operator));
+        }
+    }
+    Ok(tokens)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum BinaryOperator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    FloorDivide,
+    Modulo,
+    Power,
+    Concat,
+    Equal,
+    NotEqual,
+    Less,
+    LessEqual,
+    Greater,
+    GreaterEqual,
+    In,
+    NotIn,
+    And,
+    Or,
+}
+
+#[derive(Debug, Clone)]
+pub(super) enum Expr {
+    Literal(Value),
+    List(Vec<Expr>),
+    Dict(Vec<(Expr, Expr)>),
+    Name(String),
+    Attribute(Box<Expr>, String),
+    Index(Box<Expr>, Box<Expr>),
+    Slice(Box<Expr>, [Option<Box<Expr>>; 3]),
+    Call(Box<Expr>, Vec<Expr>, Vec<(String, Expr)>),
+    Filter(Box<Expr>, String, Vec<Expr>, Vec<(String, Expr)>),
+    Test(Box<Expr>, String, bool),
+    Not(Box<Expr>),
+    Negate(Box<Expr>),
+    Binary(BinaryOperator, Box<Expr>, Box<Expr>),
+    Conditional(Box<Expr>, Box<Expr>, Option<Box<Expr>>),
+}
+
+fn parse_expression(source: &str) -> Result<Expr, TokenizerError> {
+    let mut parser = ExpressionParser::new(source)?;
+    let expression = parser.parse_expression()?;
+    parser.expect_end()?;
+    Ok(expression)
+}
+
+struct ExpressionParser {
+    source: String,
+    tokens: Vec<ExprToken>,
+    position: usize,
+}
+
+impl ExpressionParser {
+    fn new(source: &str) -> Result<ExpressionParser, TokenizerError> {
+        Ok(ExpressionParser {
+            source: source.to_string(),
+            tokens: lex_expression(source)?,
+            position: 0,
+        })
+    }
+
+    fn error(&self, message: &str) -> TokenizerError {
+        template_error(format!("{message} in `{}`", self.source))
+    }
+
+    fn peek(&self) -> Option<&ExprToken> {
+        self.tokens.get(self.position)
+    }
+
+    fn is_keyword(&self, keyword: &str) -> bool {
+        matches!(self.peek(), Some(ExprToken::Name(name)) if name == keyword)
+    }
+
+    fn is_operator(&self, operator: &str) -> bool {
+        matches!(self.peek(), Some(ExprToken::Operator(token)) if *token == operator)
+    }
+
+    fn consume_keyword(&mut self, keyword: &str) -> bool {
+        let found = self.is_keyword(keyword);
+        if found {
+            self.position += 1;
+        }
+        found
+    }
+
+    fn consume_operator(&mut self, operator: &str) -> bool {
+        let found = self.is_operator(operator);
+        if found {
+            self.position += 1;
+        }
+        found
+    }
+
+    fn expect_keyword(&mut self, keyword: &str) -> Result<(), TokenizerError> {
+        if self.consume_keyword(keyword) {
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected `{keyword}`")))
+        }
+    }
+
+    fn expect_operator(&mut self, operator: &str) -> Result<(), TokenizerError> {
+        if self.consume_operator(operator) {
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected `{operator}`")))
+        }
+    }
+
+    fn expect_name(&mut self) -> Result<String, TokenizerError> {
+        match self.peek() {
+            Some(ExprToken::Name(name)) => {
+                let name = name.clone();
+                self.position += 1;
+                Ok(name)
+            }
+            _ => Err(self.error("expected a name")),
+        }
+    }
+
+    fn expect_end(&self) -> Result<(), TokenizerError> {
+        match self.peek() {
+            None => Ok(()),
+            Some(token) => Err(self.error(&format!("unexpected token `{token:?}`"))),
+        }
+    }
+
+    fn parse_expression(&mut self) -> Result<Expr, TokenizerError> {
+        let expression = self.parse_or()?;
+        if self.consume_keyword("if") {
+            let condition = self.parse_or()?;
+            let otherwise = if self.consume_keyword("else") {
+                Some(Box::new(self.parse_expression()?))
+            } else {
+                None
+            };
+            return Ok(Expr::Conditional(
+                Box::new(condition),
+                Box::new(expression),
+                otherwise,
+            ));
+        }
+        Ok(expression)
+    }
+
+    fn parse_or(&mut self) -> Result<Expr, TokenizerError> {
+        let mut left = self.parse_and()?;
+        while self.consume_keyword("or") {
+            let right = self.parse_and()?;
+            left = Expr::Binary(BinaryOperator::Or, Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn parse_and(&mut self) -> Result<Expr, TokenizerError> {
+        let mut left = self.parse_not()?;
+        while self.consume_keyword("and") {
+            let right = self.parse_not()?;
+            left = Expr::Binary(BinaryOperator::And, Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn parse_not(&mut self) -> Result<Expr, TokenizerError> {
+        if self.consume_keyword("not") {
+            return Ok(Expr::Not(Box::new(self.parse_not()?)));
+        }
+        self.parse_compare()
+    }
+
+    fn parse_compare(&mut self) -> Result<Expr, TokenizerError> {
+        let mut left = self.parse_math1()?;
+        loop {
+            let operator = match self.peek() {
+                Some(ExprToken::Operator("==")) => BinaryOperator::Equal,
+                Some(ExprToken::Operator("!=")) => BinaryOperator::NotEqual,
+                Some(ExprToken::Operator("<")) => BinaryOperator::Less,
+                Some(ExprToken::Operator("<=")) => BinaryOperator::LessEqual,
+                Some(ExprToken::Operator(">")) => BinaryOperator::Greater,
+                Some(ExprToken::Operator(">=")) => BinaryOperator::GreaterEqual,
+                Some(ExprToken::Name(name)) if name == "in" => BinaryOperator::In,
+                Some(ExprToken::Name(name))
+                    if name == "not"
+                        && matches!(
+                            self.tokens.get(self.position + 1),
+                            Some(ExprToken::Name(next)) if next == "in"
+                        ) =>
+                {
+                    self.position += 1;
+                    BinaryOperator::NotIn
+                }
+                _ => return Ok(left),
+            };
+            self.position += 1;
+            let right = self.parse_math1()?;
+            left = Expr::Binary(operator, Box::new(left), Box::new(right));
+        }
+    }
+
+    fn parse_math1(&mut self) -> Result<Expr, TokenizerError> {
+        let mut left = self.parse_concat()?;
+        loop {
+            let operator = match self.peek() {
+                Some(ExprToken::Operator("+")) => BinaryOperator::Add,
+                Some(ExprToken::Operator("-")) => BinaryOperator::Subtract,
+                _ => return Ok(left),
+            };
+            self.position += 1;
+            let right = self.parse_concat()?;
+            left = Expr::Binary(operator, Box::new(left), Box::new(right));
+        }
+    }
+
+    fn parse_concat(&mut self) -> Result<Expr, TokenizerError> {
+        let mut left = self.parse_math2()?;
+        while self.consume_operator("~") {
+            let right = self.parse_math2()?;
+            left = Expr::Binary(BinaryOperator::Concat, Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn parse_math2(&mut self) -> Result<Expr, TokenizerError> {
+        let mut left = self.parse_power()?;
+        loop {
+            let operator = match self.peek() {
+                Some(ExprToken::Operator("*")) => BinaryOperator::Multiply,
+                Some(ExprToken::Operator("/")) => BinaryOperator::Divide,
+                Some(ExprToken::Operator("//")) => BinaryOperator::FloorDivide,
+                Some(ExprToken::Operator("%")) => BinaryOperator::Modulo,
+                _ => return Ok(left),
+            };
+            self.position += 1;
+            let right = self.parse_power()?;
+            left = Expr::Binary(operator, Box::new(left), Box::new(right));
+        }
+    }
+
+    fn parse_power(&mut self) -> Result<Expr, TokenizerError> {
+        let mut left = self.parse_unary()?;
+        while self.consume_operator("**") {
+            let right = self.parse_unary()?;
+            left = Expr::Binary(BinaryOperator::Power, Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn parse_unary(&mut self) -> Result<Expr, TokenizerError> {
+        if self.consume_operator("-") {
+            return Ok(Expr::Negate(Box::new(self.parse_unary()?)));
+        }
+        if self.consume_operator("+") {
+            return self.parse_unary();
+        }
+        let primary = self.parse_primary()?;
+        self.parse_postfix(primary)
+    }
+
+    fn parse_primary(&mut self) -> Result<Expr, TokenizerError> {
+        let token = self
+            .peek()
+            .cloned()
+            .ok_or_else(|| self.error("unexpected end of expression"))?;
+        self.position += 1;
+        Ok(match token {
+            ExprToken::Name(name) => match name.as_str() {
+                "true" | "True" => Expr::Literal(Value::Bool(true)),
+                "false" | "False" => Expr::Literal(Value::Bool(false)),
+                "none" | "None" => Expr::Literal(Value::None),
+                _ => Expr::Name(name),
+            },
+            ExprToken::Str(value) => Expr::Literal(Value::Str(value)),
+            ExprToken::Int(value) => Expr::Literal(Value::Int(value)),
+            ExprToken::Float(value) => Expr::Literal(Value::Float(value)),
+            ExprToken::Operator("(") => {
+                let expression = self.parse_expression()?;
+                self.expect_operator(")")?;
+                expression
+            }
+            ExprToken::Operator("[") => {
+                let mut items = Vec::new();
+                while !self.consume_operator("]") {
+                    if !items.is_empty() {
+                        self.expect_operator(",")?;
+                        if self.consume_operator("]") {
+                            break;
+                        }
+                    }
+                    items.push(self.parse_expression()?);
+                }
+                Expr::List(items)
+            }
+            ExprToken::Operator("{") => {
+                let mut items = Vec::new();
+                while !self.consume_operator("}") {
+                    if !items.is_empty() {
+                        self.expect_operator(",")?;
+                        if self.consume_operator("}") {
+                            break;
+                        }
+                    }
+                    let key = self.parse_expression()?;
+                    self.expect_operator(":")?;
+                    items.push((key, self.parse_expression()?));
+                }
+                Expr::Dict(items)
+            }
+            ExprToken::Operator(operator) => {
+                return Err(self.error(&format!("unexpected `{operator}`")))
+            }
+        })
+    }
+
+    fn parse_arguments(&mut self) -> Result<(Vec<Expr>, Vec<(String, Expr)>), TokenizerError> {
+        let mut arguments = Vec::new();
+        let mut keyword_arguments = Vec::new();
+        while !self.consume_operator(")") {
+            if !arguments.is_empty() || !keyword_arguments.is_empty() {
+                self.expect_operator(",")?;
+                if self.consume_operator(")") {
+                    break;
+                }
+            }
+            let is_keyword_argument = matches!(self.peek(), Some(ExprToken::Name(_)))
+                && matches!(
+                    self.tokens.get(self.position + 1),
+                    Some(ExprToken::Operator("="))
+                );
+            if is_keyword_argument {
+                let name = self.expect_name()?;
+                self.position += 1;
+                keyword_arguments.push((name, self.parse_expression()?));
+            } else {
+                arguments.push(self.parse_expression()?);
+            }
+        }
+        Ok((arguments, keyword_arguments))
+    }
+
+    fn parse_slice_bound(&mut self) -> Result<Option<Box<Expr>>, TokenizerError> {
+        if self.is_operator(":") || self.is_operator("]") {
+            Ok(None)
+        } else {
+            Ok(Some(Box::new(self.parse_expression()?)))
+        }
+    }
+
+    fn parse_postfix(&mut self, mut expression: Expr) -> Result<Expr, TokenizerError> {
+        loop {
+            if self.consume_operator(".") {
+                let attribute = self.expect_name()?;
+                expression = Expr::Attribute(Box::new(expression), attribute);
+            } else if self.consume_operator("[") {
+                let start = self.parse_slice_bound()?;
+                if self.consume_operator(":") {
+                    let stop = self.parse_slice_bound()?;
+                    let step = if self.consume_operator(":") {
+                        self.parse_slice_bound()?
+                    } else {
+                        None
+                    };
+                    expression = Expr::Slice(Box::new(expression), [start, stop, step]);
+                } else {
+                    let index = start.ok_or_else(|| self.error("expected an index"))?;
+                    expression = Expr::Index(Box::new(expression), index);
+                }
+                self.expect_operator("]")?;
+            } else if self.consume_operator("(") {
+                let (arguments, keyword_arguments) = self.parse_arguments()?;
+                expression = Expr::Call(Box::new(expression), arguments, keyword_arguments);
+            } else if self.consume_operator("|") {
+                let name = self.expect_name()?;
+                let (arguments, keyword_arguments) = if self.consume_operator("(") {
+                    self.parse_arguments()?
+                } else {
+                    (Vec::new(), Vec::new())
+                };
+                expression = Expr::Filter(Box::new(expression), name, arguments, keyword_arguments);
+            } else if self.consume_keyword("is") {
+                let negated = self.consume_keyword("not");
+                let name = match self.peek() {
+                    Some(ExprToken::Name(name)) => name.clone(),
+                    _ => return Err(self.error("expected a test name")),
+                };
+                self.position += 1;
+                expression = Expr::Test(Box::new(expression), name, negated);
+            } else {
+                return Ok(expression);
+            }
+        }
+    }
+}
@@ -0,0 +1,1051 @@
+// Copyright 2019-2020 Guillaume Becquin
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//     http://www.apache.org/licenses/LICENSE-2.0
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::parser::{BinaryOperator, Expr, Node};
+use super::template_error;
+use crate::error::TokenizerError;
+use serde_json::Value as JsonValue;
+use std::collections::{BTreeMap, HashMap};
+use std::convert::TryFrom;
+use std::ops::Range;
+use std::sync::Arc;
+
+//==============================
+// Values and rendering
+//==============================
+
+#[derive(Debug, Clone)]
+pub(super) enum Value {
+    Undefined,
+    None,
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Str(String),
+    List(Arc<Vec<Value>>),
+    Map(Arc<BTreeMap<String, Value>>),
+}
+
+impl Value {
+    fn type_name(&self) -> &'static str {
+        match self {
+            Value::Undefined => "undefined",
+            Value::None => "none",
+            Value::Bool(_) => "bool",
+            Value::Int(_) => "int",
+            Value::Float(_) => "float",
+            Value::Str(_) => "string",
+            Value::List(_) => "list",
+            Value::Map(_) => "dict",
+        }
+    }
+
+    fn is_truthy(&self) -> bool {
+        match self {
+            Value::Undefined | Value::None => false,
+            Value::Bool(value) => *value,
+            Value::Int(value) => *value != 0,
+            Value::Float(value) => *value != 0.0,
+            Value::Str(value) => !value.is_empty(),
+            Value::List(items) => !items.is_empty(),
+            Value::Map(items) => !items.is_empty(),
+        }
+    }
+
+    fn as_number(&self) -> Option<f64> {
+        match self {
+            Value::Int(value) => Some(*value as f64),
+            Value::Float(value) => Some(*value),
+            Value::Bool(value) => Some(*value as i64 as f64),
+            _ => None,
+        }
+    }
+
+    fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::Str(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// String conversion following the Python `str` conventions
+    fn to_output(&self) -> String {
+        match self {
+            Value::Undefined => String::new(),
+            Value::Str(value) => value.clone(),
+            _ => self.repr(),
+        }
+    }
+
+    /// Representation following the Python `repr` conventions
+    fn repr(&self) -> String {
+        match self {
+            Value::Undefined => String::new(),
+            Value::None => "None".to_string(),
+            Value::Bool(true) => "True".to_string(),
+            Value::Bool(false) => "False".to_string(),
+            Value::Int(value) => value.to_string(),
+            Value::Float(value) if value.is_finite() && value.fract() == 0.0 => {
+                format!("{value:.1}")
+            }
+            Value::Float(value) => value.to_string(),
+            Value::Str(value) => format!("'{}'", value.replace('\\', "\\\\").replace('\'', "\\'")),
+            Value::List(items) => format!(
+                "[{}]",
+                items.iter().map(Value::repr).collect::<Vec<_>>().join(", ")
+            ),
+            Value::Map(items) => format!(
+                "{{{}}}",
+                items
+                    .iter()
+                    .map(|(key, value)| format!("'{key}': {}", value.repr()))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        }
+    }
+
+    /// JSON serialization following the Python `json.dumps` conventions (separators and indentation)
+    fn to_json(&self, indent: Option<usize>, depth: usize) -> String {
+        let (item_separator, newline, closing_newline) = match indent {
+            Some(indent) => (
+                ",".to_string(),
+                format!("\n{}", " ".repeat(indent * (depth + 1))),
+                format!("\n{}", " ".repeat(indent * depth)),
+            ),
+            None => (", ".to_string(), String::new(), String::new()),
+        };
+        let join = |items: Vec<String>, opening: &str, closing: &str| {
+            if items.is_empty() {
+                return format!("{opening}{closing}");
+            }
+            format!(
+                "{opening}{newline}{}{closing_newline}{closing}",
+                items.join(&format!("{item_separator}{newline}"))
+            )
+        };
+        match self {
+            Value::Undefined | Value::None => "null".to_string(),
+            Value::Bool(value) => value.to_string(),
+            Value::Int(_) | Value::Float(_) => self.repr(),
+            Value::Str(value) => JsonValue::String(value.clone()).to_string(),
+            Value::List(items) => join(
+                items
+                    .iter()
+                    .map(|item| item.to_json(indent, depth + 1))
+                    .collect(),
+                "[",
+                "]",
+            ),
+            Value::Map(items) => join(
+                items
+                    .iter()
+                    .map(|(key, value)| {
+                        format!(
+                            "{}: {}",
+                            JsonValue::String(key.clone()),
+                            value.to_json(indent, depth + 1)
+                        )
+                    })
+                    .collect(),
+                "{",
+                "}",
+            ),
+        }
+    }
+
+    fn equals(&self, other: &Value) -> bool {
+        match (self, other) {
+            (Value::Undefined, Value::Undefined) | (Value::None, Value::None) => true,
+            (Value::Str(left), Value::Str(right)) => left == right,
+            (Value::List(left), Value::List(right)) => {
+                left.len() == right.len() && left.iter().zip(right.iter()).all(|(a, b)| a.equals(b))
+            }
+            (Value::Map(left), Value::Map(right)) => {
+                left.len() == right.len()
+                    && left
+                        .iter()
+                        .all(|(key, value)| right.get(key).is_some_and(|other| value.equals(other)))
+            }
+            _ => match (self.as_number(), other.as_number()) {
+                (Some(left), Some(right)) => left == right,
+                _ => false,
+            },
+        }
+    }
+
+    fn items(&self) -> Result<Vec<Value>, TokenizerError> {
+        match self {
+            Value::List(items) => Ok(items.as_ref().clone()),
+            Value::Map(items) => Ok(items.keys().cloned().map(Value::Str).collect()),
+            Value::Str(value) => Ok(value.chars().map(|c| Value::Str(c.to_string())).collect()),
+            Value::Undefined | Value::None => Ok(Vec::new()),
+            _ => Err(template_error(format!(
+                "{} value is not iterable",
+                self.type_name()
+            ))),
+        }
+    }
+}
+
+fn operand_error(operator: &str, left: &Value, right: &Value) -> TokenizerError {
+    template_error(format!(
+        "unsupported operand types for {operator}: {} and {}",
+        left.type_name(),
+        right.type_name()
+    ))
+}
+
+/// Resolves a Python-like (possibly negative) index for a sequence of length `length`
+fn resolve_index(index: i64, length: usize) -> Option<usize> {
+    let index = if index < 0 {
+        index + length as i64
+    } else {
+        index
+    };
+    (0..length as i64)
+        .contains(&index)
+        .then_some(index as usize)
+}
+
+/// Returns the positions selected by a Python-like slice of a sequence of length `length`
+fn slice_positions(
+    length: usize,
+    start: Option<i64>,
+    stop: Option<i64>,
+    step: Option<i64>,
+) -> Result<Vec<usize>, TokenizerError> {
+    let length = length as i64;
+    let step = step.unwrap_or(1);
+    if step == 0 {
+        return Err(template_error("slice step cannot be zero".to_string()));
+    }
+    let clamp = |bound: i64, low: i64, high: i64| {
+        let bound = if bound < 0 { bound + length } else { bound };
+        bound.clamp(low, high)
+    };
+    let mut positions = Vec::new();
+    if step > 0 {
+        let start = start.map_or(0, |bound| clamp(bound, 0, length));
+        let stop = stop.map_or(length, |bound| clamp(bound, 0, length));
+        let mut position = start;
+        while position < stop {
+            positions.push(position as usize);
+            position += step;
+        }
+    } else {
+        let start = start.map_or(length - 1, |bound| clamp(bound, -1, length - 1));
+        let stop = stop.map_or(-1, |bound| clamp(bound, -1, length - 1));
+        let mut position = start;
+        while position > stop {
+            positions.push(position as usize);
+            position += step;
+        }
+    }
+    Ok(positions)
+}
+
+pub(super) struct Renderer {
+    pub(super) scopes: Vec<HashMap<String, Value>>,
+    pub(super) output: String,
+    pub(super) output_chars: usize,
+    pub(super) generation_spans: Vec<Range<usize>>,
+}
+
+impl Renderer {
+    fn write(&mut self, text: &str) {
+        self.output.push_str(text);
+        self.output_chars += text.chars().count();
+    }
+
+    fn lookup(&self, name: &str) -> Value {
+        self.scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.get(name))
+            .cloned()
+            .unwrap_or(Value::Undefined)
+    }
+
+    pub(super) fn render_nodes(&mut self, nodes: &[Node]) -> Result<(), TokenizerError> {
+        for node in nodes {
+            match node {
+                Node::Text(text) => self.write(text),
+                Node::Output(expression) => {
+                    let value = self.eval(expression)?;
+                    self.write(&value.to_output());
+                }
+                Node::If {
+                    branches,
+                    otherwise,
+                } => {
+                    let mut selected = otherwise;
+                    for (condition, body) in branches {
+                        if self.eval(condition)?.is_truthy() {
+                            selected = body;
+                            break;
+                        }
+                    }
+                    self.render_nodes(selected)?;
+                }
+                Node::For {
+                    targets,
+                    iterable,
+                    condition,
+                    body,
+                    otherwise,
+                } => self.render_for(targets, iterable, condition.as_ref(), body, otherwise)?,
+                Node::Set {
+                    name,
+                    attribute,
+                    value,
+                } => {
+                    let value = self.eval(value)?;
+                    self.assign(name, attribute.as_deref(), value)?;
+                }
+                Node::Generation(body) => {
+                    let start = self.output_chars;
+                    self.render_nodes(body)?;
+                    if self.output_chars > start {
+                        self.generation_spans.push(start..self.output_chars);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn bind_targets(
+        scope: &mut HashMap<String, Value>,
+        targets: &[String],
+        item: Value,
+    ) -> Result<(), TokenizerError> {
+        if let [target] = targets {
+            scope.insert(target.clone(), item);
+            return Ok(());
+        }
+        let values = item.items()?;
+        if values.len() != targets.len() {
+            return Err(template_error(format!(
+                "cannot unpack {} values into {} loop variables",
+                values.len(),
+                targets.len()
+            )));
+        }
+        for (target, value) in targets.iter().zip(values) {
+            scope.insert(target.clone(), value);
+        }
+        Ok(())
+    }
+
+    fn render_for(
+        &mut self,
+        targets: &[String],
+        iterable: &Expr,
+        condition: Option<&Expr>,
+        body: &[Node],
+        otherwise: &[Node],
+    ) -> Result<(), TokenizerError> {
+        let mut items = Vec::new();
+        for item in self.eval(iterable)?.items()? {
+            let keep = match condition {
+                Some(condition) => {
+                    let mut scope = HashMap::new();
+                    Self::bind_targets(&mut scope, targets, item.clone())?;
+                    self.scopes.push(scope);
+                    let keep = self.eval(condition);
+                    self.scopes.pop();
+                    keep?.is_truthy()
+                }
+                None => true,
+            };
+            if keep {
+                items.push(item);
+            }
+        }
+        if items.is_empty() {
+            return self.render_nodes(otherwise);
+        }
+        let length = items.len();
+        for (index, item) in items.iter().enumerate() {
+            let mut scope = HashMap::new();
+            Self::bind_targets(&mut scope, targets, item.clone())?;
+            let mut loop_variables = BTreeMap::new();
+            for (name, value) in [
+                ("index", Value::Int(index as i64 + 1)),
+                ("index0", Value::Int(index as i64)),
+                ("revindex", Value::Int((length - index) as i64)),
+                ("revindex0", Value::Int((length - index - 1) as i64)),
+                ("first", Value::Bool(index == 0)),
+                ("last", Value::Bool(index == length - 1)),
+                ("length", Value::Int(length as i64)),
+                (
+                    "previtem",
+                    index
+                        .checked_sub(1)
+                        .map_or(Value::Undefined, |previous| items[previous].clone()),
+                ),
+                (
+                    "nextitem",
+                    items.get(index + 1).cloned().unwrap_or(Value::Undefined),
+                ),
+            ] {
+                loop_variables.insert(name.to_string(), value);
+            }
+            scope.insert("loop".to_string(), Value::Map(Arc::new(loop_variables)));
+            self.scopes.push(scope);
+            let result = self.render_nodes(body);
+            self.scopes.pop();
+            result?;
+        }
+        Ok(())
+    }
+
+    fn assign(
+        &mut self,
+        name: &str,
+        attribute: Option<&str>,
+        value: Value,
+    ) -> Result<(), TokenizerError> {
+        match attribute {
+            None => {
+                self.scopes
+                    .last_mut()
+                    .expect("the global scope is never removed")
+                    .insert(name.to_string(), value);
+            }
+            Some(attribute) => {
+                let target = self
+                    .scopes
+                    .iter_mut()
+                    .rev()
+                    .find_map(|scope| scope.get_mut(name))
+                    .ok_or_else(|| template_error(format!("`{name}` is undefined")))?;
+                match target {
+                    Value::Map(fields) => {
+                        Arc::make_mut(fields).insert(attribute.to_string(), value);
+                    }
+                    _ => {
+                        return Err(template_error(format!(
+                        "cannot set attribute `{attribute}` of `{name}`, which is not a namespace"
+                    )))
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn eval_arguments(&self, arguments: &[Expr]) -> Result<Vec<Value>, TokenizerError> {
+        arguments
+            .iter()
+            .map(|argument| self.eval(argument))
+            .collect()
+    }
+
+    fn eval(&self, expression: &Expr) -> Result<Value, TokenizerError> {
+        Ok(match expression {
+            Expr::Literal(value) => value.clone(),
+            Expr::List(items) => Value::List(Arc::new(self.eval_arguments(items)?)),
+            Expr::Dict(items) => {
+                let mut fields = BTreeMap::new();
+                for (key, value) in items {
+                    fields.insert(self.eval(key)?.to_output(), self.eval(value)?);
+                }
+                Value::Map(Arc::new(fields))
+            }
+            Expr::Name(name) => self.lookup(name),
+            Expr::Attribute(object, attribute) => {
+                get_item(&self.eval(object)?, &Value::Str(attribute.clone()))?
+            }
+            Expr::Index(object, index) => get_item(&self.eval(object)?, &self.eval(index)?)?,
+            Expr::Slice(object, bounds) => {
+                let object = self.eval(object)?;
+                let mut values = [None; 3];
+                for (value, bound) in values.iter_mut().zip(bounds.iter()) {
+                    if let Some(bound) = bound {
+                        *value = match self.eval(bound)? {
+                            Value::Int(bound) => Some(bound),
+                            Value::None => None,
+                            other => {
+                                return Err(template_error(format!(
+                                    "slice indices must be integers, got {}",
+                                    other.type_name()
+                                )))
+                            }
+                        };
+                    }
+                }
+                let [start, stop, step] = values;
+                match &object {
+                    Value::List(items) => Value::List(Arc::new(
+                        slice_positions(items.len(), start, stop, step)?
+                            .into_iter()
+                            .map(|position| items[position].clone())
+                            .collect(),
+                    )),
+                    Value::Str(value) => {
+                        let characters: Vec<char> = value.chars().collect();
+                        Value::Str(
+                            slice_positions(characters.len(), start, stop, step)?
+                                .into_iter()
+                                .map(|position| characters[position])
+                                .collect(),
+                        )
+                    }
+                    _ => {
+                        return Err(template_error(format!(
+                            "{} value cannot be sliced",
+                            object.type_name()
+                        )))
+                    }
+                }
+            }
+            Expr::Call(callee, arguments, keyword_arguments) => {
+                self.eval_call(callee, arguments, keyword_arguments)?
+            }
+            Expr::Filter(object, name, arguments, keyword_arguments) => {
+                let object = self.eval(object)?;
+                let arguments = self.eval_arguments(arguments)?;
+                let mut keywords = HashMap::new();
+                for (name, value) in keyword_arguments {
+                    keywords.insert(name.as_str(), self.eval(value)?);
+                }
+                apply_filter(name, object, &arguments, &keywords)?
+            }
+            Expr::Test(object, name, negated) => {
+                let object = self.eval(object)?;
+                let result = match name.as_str() {
+                    "defined" => !matches!(object, Value::Undefined),
+                    "undefined" => matches!(object, Value::Undefined),
+                    "none" => matches!(object, Value::None),
+                    "string" => matches!(object, Value::Str(_)),
+                    "number" => matches!(object, Value::Int(_) | Value::Float(_)),
+                    "integer" => matches!(object, Value::Int(_)),
+                    "float" => matches!(object, Value::Float(_)),
+                    "boolean" => matches!(object, Value::Bool(_)),
+                    "mapping" => matches!(object, Value::Map(_)),
+                    "sequence" | "iterable" => {
+                        matches!(object, Value::List(_) | Value::Str(_) | Value::Map(_))
+                    }
+                    "true" => matches!(object, Value::Bool(true)),
+                    "false" => matches!(object, Value::Bool(false)),
+                    "even" => matches!(object, Value::Int(value) if value % 2 == 0),
+                    "odd" => matches!(object, Value::Int(value) if value % 2 != 0),
+                    _ => return Err(template_error(format!("unknown test `{name}`"))),
+                };
+                Value::Bool(result != *negated)
+            }
+            Expr::Not(object) => Value::Bool(!self.eval(object)?.is_truthy()),
+            Expr::Negate(object) => match self.eval(object)? {
+                Value::Int(value) => Value::Int(-value),
+                Value::Float(value) => Value::Float(-value),
+                other => {
+                    return Err(template_error(format!(
+                        "bad operand type for unary -: {}",
+                        other.type_name()
+                    )))
+                }
+            },
+            Expr::Binary(BinaryOperator::And, left, right) => {
+                let left = self.eval(left)?;
+                if left.is_truthy() {
+                    self.eval(right)?
+                } else {
+                    left
+                }
+            }
+            Expr::Binary(BinaryOperator::Or, left, right) => {
+                let left = self.eval(left)?;
+                if left.is_truthy() {
+                    left
+                } else {
+                    self.eval(right)?
+                }
+            }
+            Expr::Binary(operator, left, right) => {
+                binary_operation(*operator, &self.eval(left)?, &self.eval(right)?)?
+            }
+            Expr::Conditional(condition, then, otherwise) => {
+                if self.eval(condition)?.is_truthy() {
+                    self.eval(then)?
+                } else {
+                    match otherwise {
+                        Some(otherwise) => self.eval(otherwise)?,
+                        None => Value::Undefined,
+                    }
+                }
+            }
+        })
+    }
+
+    fn eval_call(
+        &self,
+        callee: &Expr,
+        arguments: &[Expr],
+        keyword_arguments: &[(String, Expr)],
+    ) -> Result<Value, TokenizerError> {
+        let arguments = self.eval_arguments(arguments)?;
+        match callee {
+            Expr::Attribute(object, method) => call_method(&self.eval(object)?, method, &arguments),
+            Expr::Name(name) => match name.as_str() {
+                "raise_exception" => Err(TokenizerError::ValueError(
+                    arguments
+                        .first()
+                        .map(Value::to_output)
+                        .unwrap_or_else(|| "Chat template exception".to_string()),
+                )),
+                "namespace" => {
+                    let mut fields = BTreeMap::new();
+                    for (name, value) in keyword_arguments {
+                        fields.insert(name.clone(), self.eval(value)?);
+                    }
+                    Ok(Value::Map(Arc::new(fields)))
+                }
+                "range" => {
+                    let bounds = arguments
+                        .iter()
+                        .map(|argument| match argument {
+                            Value::Int(value) => Ok(*value),
+                            other => Err(template_error(format!(
+                                "range arguments must be integers, got {}",
+                                other.type_name()
+                            ))),
+                        })
+                        .collect::<Result<Vec<i64>, TokenizerError>>()?;
+                    let (start, stop, step) = match bounds.as_slice() {
+                        [stop] => (0, *stop, 1),
+                        [start, stop] => (*start, *stop, 1),
+                        [start, stop, step] => (*start, *stop, *step),
+                        _ => return Err(template_error("range expects 1 to 3 arguments".into())),
+                    };
+                    if step == 0 {
+                        return Err(template_error("range step cannot be zero".to_string()));
+                    }
+                    let mut values = Vec::new();
+                    let mut value = start;
+                    while (step > 0 && value < stop) || (step < 0 && value > stop) {
+                        values.push(Value::Int(value));
+                        value += step;
+                    }
+                    Ok(Value::List(Arc::new(values)))
+                }
+                _ => Err(template_error(format!("unknown function `{name}`"))),
+            },
+            _ => Err(template_error("expression is not callable".to_string())),
+        }
+    }
+}
+
+fn get_item(object: &Value, key: &Value) -> Result<Value, TokenizerError> {
+    Ok(match (object, key) {
+        (Value::Map(fields), _) => fields
+            .get(&key.to_output())
+            .cloned()
+            .unwrap_or(Value::Undefined),
+        (Value::List(items), Value::Int(index)) => resolve_index(*index, items.len())
+            .map_or(Value::Undefined, |index| items[index].clone()),
+        (Value::Str(value), Value::Int(index)) => {
+            let characters: Vec<char> = value.chars().collect();
+            resolve_index(*index, characters.len()).map_or(Value::Undefined, |index| {
+                Value::Str(characters[index].to_string())
+            })
+        }
+        (Value::Undefined, _) => {
+            return Err(template_error(format!(
+                "cannot access `{}` of an undefined value",
+                key.to_output()
+            )))
+        }
+        _ => Value::Undefined,
+    })
+}
+
+fn binary_operation(
+    operator: BinaryOperator,
+    left: &Value,
+    right: &Value,
+) -> Result<Value, TokenizerError> {
+    use BinaryOperator::*;
+    Ok(match operator {
+        Equal => Value::Bool(left.equals(right)),
+        NotEqual => Value::Bool(!left.equals(right)),
+        Concat => Value::Str(left.to_output() + &right.to_output()),
+        In | NotIn => {
+            let contained = match right {
+                Value::Str(haystack) => match left {
+                    Value::Str(needle) => haystack.contains(needle.as_str()),
+                    _ => return Err(operand_error("in", left, right)),
+                },
+                Value::List(items) => items.iter().any(|item| item.equals(left)),
+                Value::Map(fields) => fields.contains_key(&left.to_output()),
+                _ => return Err(operand_error("in", left, right)),
+            };
+            Value::Bool(contained == (operator == In))
+        }
+        Less | LessEqual | Greater | GreaterEqual => {
+            let ordering = match (left, right) {
+                (Value::Str(left), Value::Str(right)) => left.partial_cmp(right),
+                _ => match (left.as_number(), right.as_number()) {
+                    (Some(left), Some(right)) => left.partial_cmp(&right),
+                    _ => return Err(operand_error("comparison", left, right)),
+                },
+            };
+            Value::Bool(match ordering {
+                Some(ordering) => match operator {
+                    Less => ordering.is_lt(),
+                    LessEqual => ordering.is_le(),
+                    Greater => ordering.is_gt(),
+                    _ => ordering.is_ge(),
+                },
+                None => false,
+            })
+        }
+        Add => match (left, right) {
+            (Value::Str(left), Value::Str(right)) => Value::Str(left.clone() + right),
+            (Value::List(left), Value::List(right)) => {
+                Value::List(Arc::new(left.iter().chain(right.iter()).cloned().collect()))
+            }
+            _ => arithmetic(operator, left, right)?,
+        },
+        Multiply => match (left, right) {
+            (Value::Str(value), Value::Int(count)) | (Value::Int(count), Value::Str(value)) => {
+                Value::Str(value.repeat((*count).max(0) as usize))
+            }
+            _ => arithmetic(operator, left, right)?,
+        },
+        Subtract | Divide | FloorDivide | Modulo | Power => arithmetic(operator, left, right)?,
+        And | Or => unreachable!("boolean operators are evaluated lazily"),
+    })
+}
+
+fn arithmetic(
+    operator: BinaryOperator,
+    left: &Value,
+    right: &Value,
+) -> Result<Value, TokenizerError> {
+    use BinaryOperator::*;
+    let symbol = match operator {
+        Add => "+",
+        Subtract => "-",
+        Multiply => "*",
+        Divide => "/",
+        FloorDivide => "//",
+        Modulo => "%",
+        _ => "**",
+    };
+    let overflow = || template_error(format!("integer overflow in {symbol}"));
+    if let (Value::Int(a), Value::Int(b)) = (left, right) {
+        let (a, b) = (*a, *b);
+        if b == 0 && matches!(operator, Divide | FloorDivide | Modulo) {
+            return Err(template_error("division by zero".to_string()));
+        }
+        return Ok(match operator {
+            Add => Value::Int(a.checked_add(b).ok_or_else(overflow)?),
+            Subtract => Value::Int(a.checked_sub(b).ok_or_else(overflow)?),
+            Multiply => Value::Int(a.checked_mul(b).ok_or_else(overflow)?),
+            Divide => Value::Float(a as f64 / b as f64),
+            FloorDivide => {
+                let quotient = a / b;
+                Value::Int(if a % b != 0 && ((a < 0) != (b < 0)) {
+                    quotient - 1
+                } else {
+                    quotient
+                })
+            }
+            Modulo => {
+                let remainder = a % b;
+                Value::Int(if remainder != 0 && ((remainder < 0) != (b < 0)) {
+                    remainder + b
+                } else {
+                    remainder
+                })
+            }
+            _ if b >= 0 => Value::Int(
+                a.checked_pow(u32::try_from(b).map_err(|_| overflow())?)
+                    .ok_or_else(overflow)?,
+            ),
+            _ => Value::Float((a as f64).powf(b as f64)),
+        });
+    }
+    let (a, b) = match (left, right) {
+        (Value::Int(_) | Value::Float(_), Value::Int(_) | Value::Float(_)) => (
+            left.as_number().unwrap_or_default(),
+            right.as_number().unwrap_or_default(),
+        ),
+        _ => return Err(operand_error(symbol, left, right)),
+    };
+    if b == 0.0 && matches!(operator, Divide | FloorDivide | Modulo) {
+        return Err(template_error("division by zero".to_string()));
+    }
+    Ok(Value::Float(match operator {
+        Add => a + b,
+        Subtract => a - b,
+        Multiply => a * b,
+        Divide => a / b,
+        FloorDivide => (a / b).floor(),
+        Modulo => a - b * (a / b).floor(),
+        _ => a.powf(b),
+    }))
+}
+
+fn string_argument<'a>(
+    arguments: &'a [Value],
+    position: usize,
+    name: &str,
+) -> Result<Option<&'a str>, TokenizerError> {
+    match arguments.get(position) {
+        None | Some(Value::None) => Ok(None),
+        Some(Value::Str(value)) => Ok(Some(value)),
+        Some(other) => Err(template_error(format!(
+            "{name} expects string arguments, got {}",
+            other.type_name()
+        ))),
+    }
+}
+
+fn strip_characters<'a>(
+    value: &'a str,
+    characters: Option<&str>,
+    left: bool,
+    right: bool,
+) -> &'a str {
+    let matches = |c: char| match characters {
+        Some(characters) => characters.contains(c),
+        None => c.is_whitespace(),
+    };
+    let value = if left {
+        value.trim_start_matches(matches)
+    } else {
+        value
+    };
+    if right {
+        value.trim_end_matches(matches)
+    } else {
+        value
+    }
+}
+
+fn title_case(value: &str) -> String {
+    let mut output = String::with_capacity(value.len());
+    let mut previous_is_letter = false;
+    for character in value.chars() {
+        if previous_is_letter {
+            output.extend(character.to_lowercase());
+        } else {
+            output.extend(character.to_uppercase());
+        }
+        previous_is_letter = character.is_alphabetic();
+    }
+    output
+}
+
+fn capitalize(value: &str) -> String {
+    let mut characters = value.chars();
+    match characters.next() {
+        Some(first) => first
+            .to_uppercase()
+            .chain(characters.flat_map(char::to_lowercase))
+            .collect(),
+        None => String::new(),
+    }
+}
+
+fn call_method(object: &Value, method: &str, arguments: &[Value]) -> Result<Value, TokenizerError> {
+    let unknown = || {
+        template_error(format!(
+            "{} value has no method `{method}`",
+            object.type_name()
+        ))
+    };
+    match object {
+        Value::Str(value) => Ok(match method {
+            "strip" | "lstrip" | "rstrip" => {
+                let characters = string_argument(arguments, 0, method)?;
+                Value::Str(
+                    strip_characters(value, characters, method != "rstrip", method != "lstrip")
+                        .to_string(),
+                )
+            }
+            "upper" => Value::Str(value.to_uppercase()),
+            "lower" => Value::Str(value.to_lowercase()),
+            "title" => Value::Str(title_case(value)),
+            "capitalize" => Value::Str(capitalize(value)),
+            "startswith" | "endswith" => {
+                let affix = string_argument(arguments, 0, method)?.unwrap_or_default();
+                Value::Bool(if method == "startswith" {
+                    value.starts_with(affix)
+                } else {
+                    value.ends_with(affix)
+                })
+            }
+            "split" => {
+                let parts: Vec<Value> = match string_argument(arguments, 0, method)? {
+                    Some(separator) => value
+                        .split(separator)
+                        .map(|part| Value::Str(part.to_string()))
+                        .collect(),
+                    None => value
+                        .split_whitespace()
+                        .map(|part| Value::Str(part.to_string()))
+                        .collect(),
+                };
+                Value::List(Arc::new(parts))
+            }
+            "replace" => {
+                let old = string_argument(arguments, 0, method)?.unwrap_or_default();
+                let new = string_argument(arguments, 1, method)?.unwrap_or_default();
+                Value::Str(value.replace(old, new))
+            }
+            "join" => Value::Str(
+                arguments
+                    .first()
+                    .map(Value::items)
+                    .transpose()?
+                    .unwrap_or_default()
+                    .iter()
+                    .map(Value::to_output)
+                    .collect::<Vec<_>>()
+                    .join(value),
+            ),
+            _ => return Err(unknown()),
+        }),
+        Value::Map(fields) => Ok(match method {
+            "get" => {
+                let key = arguments.first().map(Value::to_output).unwrap_or_default();
+                fields
+                    .get(&key)
+                    .cloned()
+                    .or_else(|| arguments.get(1).cloned())
+                    .unwrap_or(Value::None)
+            }
+            "keys" => Value::List(Arc::new(fields.keys().cloned().map(Value::Str).collect())),
+            "values" => Value::List(Arc::new(fields.values().cloned().collect())),
+            "items" => Value::List(Arc::new(
+                fields
+                    .iter()
+                    .map(|(key, value)| {
+                        Value::List(Arc::new(vec![Value::Str(key.clone()), value.clone()]))
+                    })
+                    .collect(),
+            )),
+            _ => return Err(unknown()),
+        }),
+        _ => Err(unknown()),
+    }
+}
+
+fn apply_filter(
+    name: &str,
+    object: Value,
+    arguments: &[Value],
+    keyword_arguments: &HashMap<&str, Value>,
+) -> Result<Value, TokenizerError> {
+    let argument = |position: usize, keyword: &str| {
+        arguments
+            .get(position)
+            .or_else(|| keyword_arguments.get(keyword))
+    };
+    let expect_string = |object: &Value| {
+        object.as_str().map(str::to_string).ok_or_else(|| {
+            template_error(format!(
+                "filter `{name}` expects a string, got {}",
+                object.type_name()
+            ))
+        })
+    };
+    Ok(match name {
+        "trim" => Value::Str(expect_string(&object)?.trim().to_string()),
+        "upper" => Value::Str(expect_string(&object)?.to_uppercase()),
+        "lower" => Value::Str(expect_string(&object)?.to_lowercase()),
+        "title" => Value::Str(title_case(&expect_string(&object)?)),
+        "capitalize" => Value::Str(capitalize(&expect_string(&object)?)),
+        "replace" => {
+            let old = argument(0, "old").map(Value::to_output).unwrap_or_default();
+            let new = argument(1, "new").map(Value::to_output).unwrap_or_default();
+            Value::Str(expect_string(&object)?.replace(&old, &new))
+        }
+        "length" | "count" => Value::Int(match &object {
+            Value::Str(value) => value.chars().count() as i64,
+            Value::List(items) => items.len() as i64,
+            Value::Map(fields) => fields.len() as i64,
+            _ => {
+                return Err(template_error(format!(
+                    "{} value has no length",
+                    object.type_name()
+                )))
+            }
+        }),
+        "default" | "d" => {
+            let use_default = match argument(1, "boolean") {
+                Some(boolean) if boolean.is_truthy() => !object.is_truthy(),
+                _ => matches!(object, Value::Undefined),
+            };
+            if use_default {
+                argument(0, "default_value")
+                    .cloned()
+                    .unwrap_or(Value::Str(String::new()))
+            } else {
+                object
+            }
+        }
+        "first" => object
+            .items()?
+            .into_iter()
+            .next()
+            .unwrap_or(Value::Undefined),
+        "last" => object.items()?.pop().unwrap_or(Value::Undefined),
+        "list" => Value::List(Arc::new(object.items()?)),
+        "reverse" => match &object {
+            Value::Str(value) => Value::Str(value.chars().rev().collect()),
+            _ => Value::List(Arc::new(object.items()?.into_iter().rev().collect())),
+        },
+        "join" => {
+            let separator = argument(0, "d").map(Value::to_output).unwrap_or_default();
+            Value::Str(
+                object
+                    .items()?
+                    .iter()
+                    .map(Value::to_output)
+                    .collect::<Vec<_>>()
+                    .join(&separator),
+            )
+        }
+        "string" => Value::Str(object.to_output()),
+        "int" => Value::Int(match &object {
+            Value::Int(value) => *value,
+            Value::Float(value) => *value as i64,
+            Value::Bool(value) => *value as i64,
+            Value::Str(value) => value.trim().parse().unwrap_or_default(),
+            _ => 0,
+        }),
+        "abs" => match object {
+            Value::Int(value) => Value::Int(value.abs()),
+            Value::Float(value) => Value::Float(value.abs()),
+            other => {
+                return Err(template_error(format!(
+                    "bad operand type for abs: {}",
+                    other.type_name()
+                )))
+            }
+        },
+        "items" => call_method(&object, "items", &[])?,
+        "tojson" => {
+            let indent = match argument(0, "indent") {
+                Some(Value::Int(indent)) => Some((*indent).max(0) as usize),
+                _ => None,
+            };
+            Value::Str(object.to_json(indent, 0))
+        }
+        "safe" | "e" | "escape" => object,
+        _ => return Err(template_error(format!("unknown filter `{name}`"))),
+    })
+}
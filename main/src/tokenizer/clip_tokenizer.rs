@@ -0,0 +1,476 @@
+// Copyright 2021 The Open AI Team Authors and The HuggingFace Inc. team.
+// Copyright 2019-2020 Guillaume Becquin
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//     http://www.apache.org/licenses/LICENSE-2.0
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::error::TokenizerError;
+use crate::tokenizer::base_tokenizer::{
+    Mask, Offset, OffsetSize, Token, TokenIdsWithOffsets, TokenIdsWithSpecialTokens, TokenRef,
+    TokenizedInput, Tokenizer, TruncationStrategy,
+};
+use crate::tokenizer::constants::UNICODE_TO_BYTES;
+use crate::tokenizer::tokenization_utils::{
+    clean_text, collapse_whitespace, fix_mask, lowercase, openai_gpt_bpe, split_on_bpe_pairs,
+    split_on_regex, split_on_special_tokens, unescape_html, BpeCache, SpecialTokenCache,
+};
+use crate::tokenizer::MultiThreadedTokenizer;
+use crate::vocab::bpe_vocab::BpePairVocab;
+use crate::vocab::{ClipVocab, Vocab};
+use regex::Regex;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::RwLock;
+
+/// # CLIP tokenizer
+/// CLIP tokenizer performing:
+/// - splitting on special characters
+/// - text cleaning (HTML unescaping, removal of control characters and collapsing of whitespaces)
+///   and lower casing
+/// - splitting on words, single digits and punctuation
+/// - byte-level BPE tokenization, marking the end of words with a `</w>` suffix
+pub struct ClipTokenizer {
+    vocab: ClipVocab,
+    special_token_cache: SpecialTokenCache,
+    bpe_ranks: BpePairVocab,
+    cache: BpeCache,
+    pattern_tokenization: Regex,
+}
+
+impl ClipTokenizer {
+    /// Maximum number of tokens (including the start and end of text markers) processed by the
+    /// CLIP text encoder
+    pub const CONTEXT_LENGTH: usize = 77;
+
+    /// Create a new instance of a `ClipTokenizer`
+    /// Expects a vocabulary json file and a merges file as an input.
+    ///
+    /// # Parameters
+    /// - vocab_path (`&str`): path to the vocabulary file
+    /// - merges_path (`&str`): path to the merges file (use as part of the BPE encoding process)
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use rust_tokenizers::tokenizer::{ClipTokenizer, Tokenizer};
+    /// let tokenizer =
+    ///     ClipTokenizer::from_file("path/to/vocab/file", "path/to/merges/file").unwrap();
+    /// ```
+    pub fn from_file<P: AsRef<Path>, M: AsRef<Path>>(
+        vocab_path: P,
+        merges_path: M,
+    ) -> Result<ClipTokenizer, TokenizerError> {
+        let vocab = ClipVocab::from_file(vocab_path)?;
+        let bpe_ranks = BpePairVocab::from_file(merges_path)?;
+        Ok(Self::from_existing_vocab_and_merges(vocab, bpe_ranks))
+    }
+
+    /// Create a new instance of a `ClipTokenizer`
+    /// Expects a vocabulary json file and a merges file and special token mapping file as inputs.
+    ///
+    /// # Parameters
+    /// - vocab_path (`&str`): path to the vocabulary file
+    /// - merges_path (`&str`): path to the merges file (use as part of the BPE encoding process)
+    /// - special_token_mapping_path (`&str`): path to a special token mapping file to overwrite default special tokens
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use rust_tokenizers::tokenizer::{ClipTokenizer, Tokenizer};
+    /// let tokenizer = ClipTokenizer::from_file_with_special_token_mapping(
+    ///     "path/to/vocab/file",
+    ///     "path/to/merges/file",
+    ///     "path/to/special/token/mapping/file",
+    /// )
+    /// .unwrap();
+    /// ```
+    pub fn from_file_with_special_token_mapping<V: AsRef<Path>, M: AsRef<Path>, S: AsRef<Path>>(
+        vocab_path: V,
+        merges_path: M,
+        special_token_mapping_path: S,
+    ) -> Result<ClipTokenizer, TokenizerError> {
+        let vocab = ClipVocab::from_file_with_special_token_mapping(
+            vocab_path,
+            special_token_mapping_path,
+        )?;
+        let bpe_ranks = BpePairVocab::from_file(merges_path)?;
+        Ok(Self::from_existing_vocab_and_merges(vocab, bpe_ranks))
+    }
+
+    /// Create a new instance of a `ClipTokenizer` from an existing vocabulary and merges
+    ///
+    /// # Parameters
+    /// - vocab (`ClipVocab`): CLIP vocabulary
+    /// - merges (`BpePairVocab`): BPE pairs vocabulary
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use rust_tokenizers::tokenizer::{ClipTokenizer, Tokenizer};
+    /// use rust_tokenizers::vocab::{BpePairVocab, ClipVocab, Vocab};
+    /// let vocab = ClipVocab::from_file("path/to/vocab/file").unwrap();
+    /// let merges = BpePairVocab::from_file("path/to/merges/file").unwrap();
+    ///
+    /// let tokenizer = ClipTokenizer::from_existing_vocab_and_merges(vocab, merges);
+    /// ```
+    pub fn from_existing_vocab_and_merges(vocab: ClipVocab, merges: BpePairVocab) -> ClipTokenizer {
+        let cache = RwLock::new(HashMap::new());
+        let pattern_tokenization =
+            Regex::new(r"'s|'t|'re|'ve|'m|'ll|'d|\p{L}+|\p{N}|[^\s\p{L}\p{N}]+").unwrap();
+        ClipTokenizer {
+            vocab,
+            special_token_cache: RwLock::new(None),
+            bpe_ranks: merges,
+            cache,
+            pattern_tokenization,
+        }
+    }
+
+    /// Encode a text for the CLIP text encoder, truncating it to fit in the model context
+    /// (`ClipTokenizer::CONTEXT_LENGTH` tokens, including the start and end of text markers).
+    ///
+    /// # Parameters
+    /// - text (`&str`): text to encode
+    ///
+    /// # Returns
+    /// `TokenizedInput` containing the encoding output
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use rust_tokenizers::tokenizer::ClipTokenizer;
+    /// let tokenizer =
+    ///     ClipTokenizer::from_file("path/to/vocab/file", "path/to/merges/file").unwrap();
+    ///
+    /// let encoded_input = tokenizer.encode_to_context_length("a photo of a cat");
+    /// ```
+    pub fn encode_to_context_length(&self, text: &str) -> TokenizedInput {
+        self.encode(
+            text,
+            None,
+            Self::CONTEXT_LENGTH,
+            &TruncationStrategy::LongestFirst,
+            0,
+        )
+    }
+}
+
+impl Tokenizer<ClipVocab> for ClipTokenizer {
+    fn vocab(&self) -> &ClipVocab {
+        &self.vocab
+    }
+    fn vocab_mut(&mut self) -> &mut ClipVocab {
+        &mut self.vocab
+    }
+
+    fn tokenize_to_tokens(&self, initial_token: TokenRef) -> Vec<Token> {
+        let mut tokens =
+            split_on_special_tokens(initial_token, &self.vocab, &self.special_token_cache)
+                .into_iter()
+                .map(|token| token.to_owned())
+                .collect::<Vec<Token>>();
+
+        let mut sub_tokens = Vec::new();
+        for token in tokens.iter_mut() {
+            if token.mask != Mask::Special && token.mask != Mask::Unknown {
+                // HTML character references are unescaped twice, as in the reference implementation
+                unescape_html(token);
+                unescape_html(token);
+                clean_text(token, true);
+                collapse_whitespace(token);
+                lowercase(token);
+                for token in split_on_regex(token.as_ref(), &self.pattern_tokenization) {
+                    sub_tokens.extend(split_on_bpe_pairs(
+                        token,
+                        openai_gpt_bpe,
+                        &self.bpe_ranks,
+                        &self.cache,
+                        true,
+                    ));
+                }
+            } else {
+                sub_tokens.push(token.clone());
+            }
+        }
+
+        fix_mask(&mut sub_tokens);
+        sub_tokens
+    }
+
+    fn convert_tokens_to_string(&self, tokens: Vec<String>) -> String {
+        let mut bytes: Vec<u8> = Vec::new();
+        for character in tokens.concat().chars() {
+            match UNICODE_TO_BYTES.get(&character) {
+                Some(byte) => bytes.push(*byte),
+                None => bytes.extend_from_slice(character.to_string().as_bytes()),
+            }
+        }
+        String::from_utf8_lossy(&bytes)
+            .replace("</w>", " ")
+            .trim()
+            .to_owned()
+    }
+
+    fn build_input_with_special_tokens(
+        &self,
+        tokens_ids_with_offsets_1: TokenIdsWithOffsets,
+        tokens_ids_with_offsets_2: Option<TokenIdsWithOffsets>,
+    ) -> TokenIdsWithSpecialTokens {
+        let mut output: Vec<i64> = vec![];
+        let mut special_tokens_mask: Vec<i8> = vec![];
+        let mut offsets: Vec<Option<Offset>> = vec![];
+        let mut original_offsets: Vec<Vec<OffsetSize>> = vec![];
+        let mut mask: Vec<Mask> = vec![];
+        special_tokens_mask.push(1);
+        special_tokens_mask.extend(vec![0; tokens_ids_with_offsets_1.ids.len()]);
+        special_tokens_mask.push(1);
+        output.push(self.vocab.token_to_id(self.vocab.get_bos_value()));
+        output.extend(tokens_ids_with_offsets_1.ids);
+        output.push(self.vocab.token_to_id(self.vocab.get_eos_value()));
+        offsets.push(None);
+        offsets.extend(tokens_ids_with_offsets_1.offsets);
+        offsets.push(None);
+        original_offsets.push(vec![]);
+        original_offsets.extend(tokens_ids_with_offsets_1.reference_offsets);
+        original_offsets.push(vec![]);
+        mask.push(Mask::Special);
+        mask.extend(tokens_ids_with_offsets_1.masks);
+        mask.push(Mask::Special);
+        if let Some(tokens_ids_with_offsets_2_value) = tokens_ids_with_offsets_2 {
+            let length = tokens_ids_with_offsets_2_value.ids.len();
+            special_tokens_mask.push(1);
+            special_tokens_mask.extend(vec![0; length]);
+            special_tokens_mask.push(1);
+            output.push(self.vocab.token_to_id(self.vocab.get_eos_value()));
+            output.extend(tokens_ids_with_offsets_2_value.ids);
+            output.push(self.vocab.token_to_id(self.vocab.get_eos_value()));
+            offsets.push(None);
+            offsets.extend(tokens_ids_with_offsets_2_value.offsets);
+            offsets.push(None);
+            original_offsets.push(vec![]);
+            original_offsets.extend(tokens_ids_with_offsets_2_value.reference_offsets);
+            original_offsets.push(vec![]);
+            mask.push(Mask::Special);
+            mask.extend(tokens_ids_with_offsets_2_value.masks);
+            mask.push(Mask::Special);
+        }
+        // CLIP does not use token type ids: all segment ids are set to 0
+        let token_segment_ids: Vec<i8> = vec![0; output.len()];
+        TokenIdsWithSpecialTokens {
+            token_ids: output,
+            segment_ids: token_segment_ids,
+            special_tokens_mask,
+            token_offsets: offsets,
+            reference_offsets: original_offsets,
+            mask,
+        }
+    }
+}
+
+impl MultiThreadedTokenizer<ClipVocab> for ClipTokenizer {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vocab::base_vocab::{swap_key_values, SpecialTokenMap};
+    use crate::TokenizedInput;
+
+    fn generate_test_vocab() -> ClipVocab {
+        let values: HashMap<String, i64> = [
+            ("hello</w>".to_owned(), 0),
+            ("world</w>".to_owned(), 1),
+            ("!</w>".to_owned(), 2),
+            ("<|startoftext|>".to_owned(), 3),
+            ("<|endoftext|>".to_owned(), 4),
+            ("he".to_owned(), 5),
+            ("l".to_owned(), 6),
+            ("l</w>".to_owned(), 7),
+            ("1</w>".to_owned(), 8),
+            ("2</w>".to_owned(), 9),
+            ("Ã".to_owned(), 11),
+            ("©</w>".to_owned(), 12),
+        ]
+        .iter()
+        .cloned()
+        .collect();
+
+        let special_token_map = SpecialTokenMap {
+            unk_token: "<|endoftext|>".to_string(),
+            pad_token: Some("<|endoftext|>".to_string()),
+            bos_token: Some("<|startoftext|>".to_string()),
+            sep_token: None,
+            cls_token: None,
+            eos_token: Some("<|endoftext|>".to_string()),
+            mask_token: None,
+            additional_special_tokens: None,
+        };
+
+        let special_values: HashMap<String, i64> = [
+            ("<|startoftext|>".to_owned(), 3),
+            ("<|endoftext|>".to_owned(), 4),
+        ]
+        .iter()
+        .cloned()
+        .collect();
+
+        let indices = swap_key_values(&values);
+        let special_indices = swap_key_values(&special_values);
+
+        ClipVocab {
+            values,
+            indices,
+            special_token_map,
+            special_values,
+            special_indices,
+        }
+    }
+
+    fn generate_test_merges() -> BpePairVocab {
+        let values: HashMap<(String, String), i64> = [
+            (("h".to_owned(), "e".to_owned()), 0),
+            (("l".to_owned(), "l".to_owned()), 1),
+            (("he".to_owned(), "ll".to_owned()), 2),
+            (("hell".to_owned(), "o</w>".to_owned()), 3),
+            (("w".to_owned(), "o".to_owned()), 4),
+            (("wo".to_owned(), "r".to_owned()), 5),
+            (("wor".to_owned(), "l".to_owned()), 6),
+            (("worl".to_owned(), "d</w>".to_owned()), 7),
+        ]
+        .iter()
+        .cloned()
+        .collect();
+
+        BpePairVocab { values }
+    }
+
+    #[test]
+    fn test_clip_tokenizer() {
+        //        Given
+        let clip_tokenizer = ClipTokenizer::from_existing_vocab_and_merges(
+            generate_test_vocab(),
+            generate_test_merges(),
+        );
+        let test_tuples = [
+            ("Hello   World!", vec!["hello</w>", "world</w>", "!</w>"]),
+            ("hello\n\tworld", vec!["hello</w>", "world</w>"]),
+            ("hell", vec!["he", "l", "l</w>"]),
+            ("12", vec!["1</w>", "2</w>"]),
+            ("É", vec!["Ã", "©</w>"]),
+            (
+                "<|startoftext|>hello<|endoftext|>",
+                vec!["<|startoftext|>", "hello</w>", "<|endoftext|>"],
+            ),
+            ("", vec![]),
+            ("  ", vec![]),
+            ("hello &amp; world", vec!["hello</w>", "&</w>", "world</w>"]),
+            ("&amp;lt;hello&gt;", vec!["<</w>", "hello</w>", "></w>"]),
+            ("hello\u{a0}&nbsp; world", vec!["hello</w>", "world</w>"]),
+        ];
+        let source_texts: Vec<&str> = test_tuples.iter().map(|v| v.0).collect();
+        let expected_results: Vec<Vec<&str>> = test_tuples.iter().map(|v| v.1.clone()).collect();
+
+        //        When & Then
+        for (source_text, expected_result) in test_tuples.iter() {
+            assert_eq!(clip_tokenizer.tokenize(source_text), *expected_result);
+        }
+
+        assert_eq!(
+            Tokenizer::tokenize_list(&clip_tokenizer, &source_texts),
+            expected_results
+        );
+        assert_eq!(
+            MultiThreadedTokenizer::tokenize_list(&clip_tokenizer, &source_texts),
+            expected_results
+        );
+    }
+
+    #[test]
+    fn test_encode() {
+        //        Given
+        let clip_tokenizer = ClipTokenizer::from_existing_vocab_and_merges(
+            generate_test_vocab(),
+            generate_test_merges(),
+        );
+        let expected_result = TokenizedInput {
+            token_ids: vec![3, 0, 1, 2, 4],
+            segment_ids: vec![0, 0, 0, 0, 0],
+            special_tokens_mask: vec![1, 0, 0, 0, 1],
+            overflowing_tokens: vec![],
+            num_truncated_tokens: 0,
+            token_offsets: vec![
+                None,
+                Some(Offset::new(0, 5)),
+                Some(Offset::new(8, 13)),
+                Some(Offset::new(13, 14)),
+                None,
+            ],
+            reference_offsets: vec![
+                vec![],
+                vec![0, 1, 2, 3, 4],
+                vec![8, 9, 10, 11, 12],
+                vec![13],
+                vec![],
+            ],
+            mask: vec![
+                Mask::Special,
+                Mask::None,
+                Mask::None,
+                Mask::None,
+                Mask::Special,
+            ],
+        };
+
+        //        When
+        let encoded_input = clip_tokenizer.encode_to_context_length("Hello   World!");
+
+        //        Then
+        assert_eq!(encoded_input, expected_result);
+    }
+
+    #[test]
+    fn test_encode_to_context_length() {
+        //        Given
+        let clip_tokenizer = ClipTokenizer::from_existing_vocab_and_merges(
+            generate_test_vocab(),
+            generate_test_merges(),
+        );
+        let text = "hello ".repeat(100);
+
+        //        When
+        let encoded_input = clip_tokenizer.encode_to_context_length(&text);
+
+        //        Then
+        assert_eq!(encoded_input.token_ids.len(), ClipTokenizer::CONTEXT_LENGTH);
+        assert_eq!(encoded_input.token_ids[0], 3);
+        assert_eq!(*encoded_input.token_ids.last().unwrap(), 4);
+        assert_eq!(encoded_input.num_truncated_tokens, 25);
+    }
+
+    #[test]
+    fn test_decode() {
+        //        Given
+        let clip_tokenizer = ClipTokenizer::from_existing_vocab_and_merges(
+            generate_test_vocab(),
+            generate_test_merges(),
+        );
+        let test_tuples = [
+            (vec![3, 0, 1, 2, 4], "hello world!"),
+            (vec![5, 6, 7], "hell"),
+            (vec![11, 12], "é"),
+        ];
+
+        //        When & Then
+        for (token_ids, expected_result) in test_tuples.iter() {
+            assert_eq!(
+                clip_tokenizer.decode(token_ids, true, true),
+                *expected_result
+            );
+        }
+    }
+}
@@ -629,6 +629,48 @@ lazy_static! {
         .into_iter()
         .collect()
     };
+    pub static ref HTML_ENTITIES: HashMap<&'static str, char> = {
+        vec![
+            ("amp", '&'),
+            ("lt", '<'),
+            ("gt", '>'),
+            ("quot", '"'),
+            ("apos", '\''),
+            ("nbsp", '\u{a0}'),
+            ("iexcl", '¡'),
+            ("cent", '¢'),
+            ("pound", '£'),
+            ("yen", '¥'),
+            ("sect", '§'),
+            ("copy", '©'),
+            ("laquo", '«'),
+            ("shy", '\u{ad}'),
+            ("reg", '®'),
+            ("deg", '°'),
+            ("plusmn", '±'),
+            ("para", '¶'),
+            ("middot", '·'),
+            ("raquo", '»'),
+            ("frac14", '¼'),
+            ("frac12", '½'),
+            ("frac34", '¾'),
+            ("iquest", '¿'),
+            ("times", '×'),
+            ("divide", '÷'),
+            ("ndash", '–'),
+            ("mdash", '—'),
+            ("lsquo", '‘'),
+            ("rsquo", '’'),
+            ("ldquo", '“'),
+            ("rdquo", '”'),
+            ("bull", '•'),
+            ("hellip", '…'),
+            ("euro", '€'),
+            ("trade", '™'),
+        ]
+        .into_iter()
+        .collect()
+    };
     pub static ref UNICODE_TO_BYTES: HashMap<char, u8> = BYTES_TO_UNICODE
         .clone()
         .into_iter()
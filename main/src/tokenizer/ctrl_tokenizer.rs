@@ -15,7 +15,7 @@
 use crate::error::TokenizerError;
 use crate::tokenizer::tokenization_utils::{
     ctrl_bpe, fix_mask, lowercase, split_on_bpe_pairs, split_on_regex, split_on_special_tokens,
-    BpeCache,
+    BpeCache, SpecialTokenCache,
 };
 use crate::tokenizer::{MultiThreadedTokenizer, Tokenizer};
 use crate::vocab::bpe_vocab::BpePairVocab;
@@ -34,6 +34,7 @@ use std::sync::RwLock;
 /// - BPE tokenization
 pub struct CtrlTokenizer {
     vocab: OpenAiGptVocab,
+    special_token_cache: SpecialTokenCache,
     bpe_ranks: BpePairVocab,
     cache: BpeCache,
     regex_pattern: Regex,
@@ -72,6 +73,7 @@ impl CtrlTokenizer {
             cache,
             regex_pattern,
             lower_case,
+            special_token_cache: RwLock::new(None),
         })
     }
 
@@ -116,6 +118,7 @@ impl CtrlTokenizer {
             cache,
             regex_pattern,
             lower_case,
+            special_token_cache: RwLock::new(None),
         })
     }
 
@@ -150,6 +153,7 @@ impl CtrlTokenizer {
             cache,
             regex_pattern,
             lower_case,
+            special_token_cache: RwLock::new(None),
         }
     }
 }
@@ -163,10 +167,11 @@ impl Tokenizer<OpenAiGptVocab> for CtrlTokenizer {
     }
 
     fn tokenize_to_tokens(&self, initial_token: TokenRef) -> Vec<Token> {
-        let mut tokens = split_on_special_tokens(initial_token, &self.vocab)
-            .into_iter()
-            .map(|token| token.to_owned())
-            .collect::<Vec<Token>>();
+        let mut tokens =
+            split_on_special_tokens(initial_token, &self.vocab, &self.special_token_cache)
+                .into_iter()
+                .map(|token| token.to_owned())
+                .collect::<Vec<Token>>();
         let mut sub_tokens = Vec::new();
 
         for token in tokens.iter_mut() {
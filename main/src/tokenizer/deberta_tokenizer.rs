@@ -15,6 +15,7 @@ use crate::error::TokenizerError;
 use crate::tokenizer::constants::UNICODE_TO_BYTES;
 use crate::tokenizer::tokenization_utils::{
     bpe, fix_mask, split_on_bpe_pairs, split_on_regex_with_lookahead, split_on_special_tokens,
+    SpecialTokenCache,
 };
 use crate::tokenizer::tokenization_utils::{lowercase, BpeCache};
 use crate::tokenizer::{MultiThreadedTokenizer, Tokenizer};
@@ -38,6 +39,7 @@ use std::sync::RwLock;
 /// - BPE tokenization
 pub struct DeBERTaTokenizer {
     vocab: DeBERTaVocab,
+    special_token_cache: SpecialTokenCache,
     bpe_ranks: BpePairVocab,
     cache: BpeCache,
     pattern_lookahead: Regex,
@@ -82,6 +84,7 @@ impl DeBERTaTokenizer {
             pattern_lookahead,
             pattern_tokenization,
             lower_case,
+            special_token_cache: RwLock::new(None),
         })
     }
 
@@ -129,6 +132,7 @@ impl DeBERTaTokenizer {
             pattern_lookahead,
             pattern_tokenization,
             lower_case,
+            special_token_cache: RwLock::new(None),
         })
     }
 
@@ -167,6 +171,7 @@ impl DeBERTaTokenizer {
             pattern_lookahead,
             pattern_tokenization,
             lower_case,
+            special_token_cache: RwLock::new(None),
         }
     }
 }
@@ -180,10 +185,11 @@ impl Tokenizer<DeBERTaVocab> for DeBERTaTokenizer {
     }
 
     fn tokenize_to_tokens(&self, initial_token: TokenRef) -> Vec<Token> {
-        let mut tokens = split_on_special_tokens(initial_token, &self.vocab)
-            .into_iter()
-            .map(|token| token.to_owned())
-            .collect::<Vec<Token>>();
+        let mut tokens =
+            split_on_special_tokens(initial_token, &self.vocab, &self.special_token_cache)
+                .into_iter()
+                .map(|token| token.to_owned())
+                .collect::<Vec<Token>>();
 
         let mut sub_tokens = Vec::new();
         for token in tokens.iter_mut() {
@@ -14,6 +14,7 @@
 use crate::error::TokenizerError;
 use crate::tokenizer::tokenization_utils::{
     clean_text, decompose_nfkc, is_whitespace, split_on_special_tokens, strip_accents,
+    SpecialTokenCache,
 };
 use crate::tokenizer::tokenization_utils::{lowercase, unknown_byte_fallback};
 use crate::tokenizer::{MultiThreadedTokenizer, Tokenizer};
@@ -23,6 +24,7 @@ use crate::{
 };
 use std::iter::Iterator;
 use std::path::Path;
+use std::sync::RwLock;
 
 /// # DeBERTaV2 tokenizer
 /// DeBERTa (v2) tokenizer (based on SentencePiece) performing:
@@ -35,6 +37,7 @@ use std::path::Path;
 pub struct DeBERTaV2Tokenizer {
     model: SentencePieceModel,
     vocab: DeBERTaV2Vocab,
+    special_token_cache: SpecialTokenCache,
     lower_case: bool,
     strip_accents: bool,
     add_prefix_space: bool,
@@ -78,6 +81,7 @@ impl DeBERTaV2Tokenizer {
             lower_case,
             strip_accents,
             add_prefix_space,
+            special_token_cache: RwLock::new(None),
         })
     }
 
@@ -122,6 +126,7 @@ impl DeBERTaV2Tokenizer {
             lower_case,
             strip_accents,
             add_prefix_space,
+            special_token_cache: RwLock::new(None),
         })
     }
 
@@ -166,6 +171,7 @@ impl DeBERTaV2Tokenizer {
             lower_case,
             strip_accents,
             add_prefix_space,
+            special_token_cache: RwLock::new(None),
         }
     }
 
@@ -234,10 +240,14 @@ impl Tokenizer<DeBERTaV2Vocab> for DeBERTaV2Tokenizer {
             initial_token.reference_offsets.insert(0, 0);
         };
 
-        let mut tokens = split_on_special_tokens(initial_token.as_ref(), &self.vocab)
-            .into_iter()
-            .map(|token| token.to_owned())
-            .collect::<Vec<Token>>();
+        let mut tokens = split_on_special_tokens(
+            initial_token.as_ref(),
+            &self.vocab,
+            &self.special_token_cache,
+        )
+        .into_iter()
+        .map(|token| token.to_owned())
+        .collect::<Vec<Token>>();
 
         let mut sub_tokens: Vec<Token> = Vec::new();
         for token in tokens.iter_mut() {
@@ -0,0 +1,462 @@
+// Copyright 2019-2021 Guillaume Becquin
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//     http://www.apache.org/licenses/LICENSE-2.0
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::error::TokenizerError;
+use crate::tokenizer::base_tokenizer::{Mask, Offset, OffsetSize, Token, TokenRef};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+/// # Lexicon file formats
+/// Formats of the lexicon files that can be loaded by the `DictionarySegmenter`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LexiconFormat {
+    /// MeCab dictionary CSV (IPADIC, UniDic): `surface,left_id,right_id,cost,...`. The word cost
+    /// is used directly (connection costs are not used).
+    MecabCsv,
+    /// jieba-style dictionary: `word frequency [tag]` separated by whitespace. The cost of a word
+    /// is its negative log-probability.
+    Jieba,
+}
+
+/// # Dictionary-based word segmenter
+/// Splits texts without word delimiters (Japanese, Chinese) into words, finding the segmentation with
+/// the lowest total cost (Viterbi search) over a user-supplied lexicon. Characters that can not be
+/// covered by the lexicon are grouped into unknown words by character class (runs of Latin letters,
+/// digits or katakana, or single ideographs), with a cost higher than any lexicon entry by default.
+///
+/// The segmenter only applies to tokens containing CJK ideographs or kana. It can be used on its
+/// own, or as the pre-tokenization step of the `BertTokenizer` (see `BertTokenizer::set_word_segmenter`),
+/// replacing the default character-level split of CJK text. When `split_characters` is set, words
+/// are further split into characters carrying `Mask::Begin`/`Mask::Continuation` masks, as required
+/// for whole-word masking with character-level Chinese models.
+#[derive(Debug, Clone)]
+pub struct DictionarySegmenter {
+    lexicon: HashMap<String, f32>,
+    max_word_length: usize,
+    unknown_cost: f32,
+    split_characters: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CharacterClass {
+    Ideograph,
+    Hiragana,
+    Katakana,
+    Alphabetic,
+    Numeric,
+    Other,
+}
+
+impl DictionarySegmenter {
+    /// Create a new instance of a `DictionarySegmenter` from a lexicon mapping words to their cost
+    ///
+    /// # Parameters
+    /// - lexicon (`HashMap<String, f32>`): mapping from words to their cost (lower costs are preferred)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_tokenizers::tokenizer::DictionarySegmenter;
+    /// use std::collections::HashMap;
+    /// let lexicon: HashMap<String, f32> = [("東京".to_string(), 1.0), ("都".to_string(), 2.0)]
+    ///     .iter()
+    ///     .cloned()
+    ///     .collect();
+    /// let segmenter = DictionarySegmenter::from_lexicon(lexicon);
+    ///
+    /// assert_eq!(segmenter.tokenize("東京都"), vec!["東京", "都"]);
+    /// ```
+    pub fn from_lexicon(lexicon: HashMap<String, f32>) -> DictionarySegmenter {
+        let max_word_length = lexicon
+            .keys()
+            .map(|word| word.chars().count())
+            .max()
+            .unwrap_or(0);
+        let unknown_cost = lexicon.values().copied().fold(0f32, f32::max) + 1.0;
+        DictionarySegmenter {
+            lexicon,
+            max_word_length,
+            unknown_cost,
+            split_characters: false,
+        }
+    }
+
+    /// Create a new instance of a `DictionarySegmenter` from a lexicon file
+    ///
+    /// # Parameters
+    /// - path (`&str`): path to the lexicon file
+    /// - format (`LexiconFormat`): format of the lexicon file
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use rust_tokenizers::tokenizer::{DictionarySegmenter, LexiconFormat};
+    /// let segmenter =
+    ///     DictionarySegmenter::from_file("path/to/dict.txt", LexiconFormat::Jieba).unwrap();
+    /// ```
+    pub fn from_file<P: AsRef<Path>>(
+        path: P,
+        format: LexiconFormat,
+    ) -> Result<DictionarySegmenter, TokenizerError> {
+        let f = File::open(&path).map_err(|e| {
+            TokenizerError::FileNotFound(format!(
+                "{} lexicon file not found :{}",
+                path.as_ref().display(),
+                e
+            ))
+        })?;
+        let lexicon = match format {
+            LexiconFormat::MecabCsv => read_mecab_csv(f)?,
+            LexiconFormat::Jieba => read_jieba_dict(f)?,
+        };
+        Ok(Self::from_lexicon(lexicon))
+    }
+
+    /// Sets the cost of unknown words (defaults to the highest lexicon cost + 1)
+    pub fn set_unknown_cost(&mut self, unknown_cost: f32) {
+        self.unknown_cost = unknown_cost;
+    }
+
+    /// Sets whether CJK words should be split into characters with `Mask::Begin`/`Mask::Continuation`
+    /// masks (defaults to `false`)
+    pub fn set_split_characters(&mut self, split_characters: bool) {
+        self.split_characters = split_characters;
+    }
+
+    /// Segments a token into words. Tokens without CJK ideographs or kana are returned unchanged.
+    ///
+    /// # Parameters
+    /// - token (`TokenRef`): token to segment
+    ///
+    /// # Returns
+    /// `Vec<Token>` containing the words (or characters if `split_characters` is set)
+    pub fn tokenize_to_tokens(&self, token: TokenRef) -> Vec<Token> {
+        if token.mask == Mask::Special
+            || token.mask == Mask::Unknown
+            || !token.text.chars().any(is_cjk_or_kana)
+        {
+            return vec![token.to_owned()];
+        }
+        let characters: Vec<char> = token.text.chars().collect();
+        let mut tokens = vec![];
+        for (start, end) in self.viterbi(&characters) {
+            let reference_offsets = &token.reference_offsets[start..end];
+            if self.split_characters
+                && end - start > 1
+                && characters[start..end].iter().all(|c| is_cjk_or_kana(*c))
+            {
+                for (index, character) in characters[start..end].iter().enumerate() {
+                    let mask = if index == 0 {
+                        Mask::Begin
+                    } else {
+                        Mask::Continuation
+                    };
+                    tokens.push(make_token(
+                        character.to_string(),
+                        &reference_offsets[index..index + 1],
+                        mask,
+                    ));
+                }
+            } else {
+                let mask = if end - start == 1 && is_cjk_or_kana(characters[start]) {
+                    Mask::CJK
+                } else {
+                    Mask::None
+                };
+                tokens.push(make_token(
+                    characters[start..end].iter().collect(),
+                    reference_offsets,
+                    mask,
+                ));
+            }
+        }
+        tokens
+    }
+
+    /// Segments a text into words
+    ///
+    /// # Parameters
+    /// - text (`&str`): text to segment
+    ///
+    /// # Returns
+    /// `Vec<String>` containing the words
+    pub fn tokenize(&self, text: &str) -> Vec<String> {
+        let offsets = (0..text.chars().count() as OffsetSize).collect::<Vec<OffsetSize>>();
+        self.tokenize_to_tokens(TokenRef::new(text, &offsets))
+            .into_iter()
+            .map(|token| token.text)
+            .collect()
+    }
+
+    /// Returns the (start, end) character positions of the lowest cost segmentation
+    fn viterbi(&self, characters: &[char]) -> Vec<(usize, usize)> {
+        let length = characters.len();
+        let mut costs = vec![f32::INFINITY; length + 1];
+        let mut previous = vec![0usize; length + 1];
+        costs[0] = 0f32;
+
+        let mut relax = |costs: &mut Vec<f32>, start: usize, end: usize, cost: f32| {
+            if costs[start] + cost < costs[end] {
+                costs[end] = costs[start] + cost;
+                previous[end] = start;
+            }
+        };
+
+        let mut word = String::new();
+        for start in 0..length {
+            word.clear();
+            for end in start + 1..=length.min(start + self.max_word_length) {
+                word.push(characters[end - 1]);
+                if let Some(cost) = self.lexicon.get(&word) {
+                    relax(&mut costs, start, end, *cost);
+                }
+            }
+            let class = CharacterClass::from_char(characters[start]);
+            let mut end = start + 1;
+            if class != CharacterClass::Ideograph {
+                while end < length && CharacterClass::from_char(characters[end]) == class {
+                    end += 1;
+                }
+            }
+            relax(&mut costs, start, end, self.unknown_cost);
+            if end > start + 1 {
+                relax(&mut costs, start, start + 1, self.unknown_cost);
+            }
+        }
+
+        let mut spans = vec![];
+        let mut end = length;
+        while end > 0 {
+            let start = previous[end];
+            spans.push((start, end));
+            end = start;
+        }
+        spans.reverse();
+        spans
+    }
+}
+
+impl CharacterClass {
+    fn from_char(character: char) -> CharacterClass {
+        match character as u32 {
+            0x3040..=0x309F => CharacterClass::Hiragana,
+            0x30A0..=0x30FF | 0x31F0..=0x31FF | 0xFF66..=0xFF9F => CharacterClass::Katakana,
+            _ if is_ideograph(character) => CharacterClass::Ideograph,
+            _ if character.is_numeric() => CharacterClass::Numeric,
+            _ if character.is_alphabetic() => CharacterClass::Alphabetic,
+            _ => CharacterClass::Other,
+        }
+    }
+}
+
+fn is_ideograph(character: char) -> bool {
+    matches!(
+        character as u32,
+        0x4E00..=0x9FFF
+            | 0x3400..=0x4DBF
+            | 0x20000..=0x2A6DF
+            | 0x2A700..=0x2CEAF
+            | 0xF900..=0xFAFF
+            | 0x2F800..=0x2FA1F
+            | 0x3005
+    )
+}
+
+fn is_cjk_or_kana(character: char) -> bool {
+    is_ideograph(character)
+        || matches!(
+            CharacterClass::from_char(character),
+            CharacterClass::Hiragana | CharacterClass::Katakana
+        )
+}
+
+fn make_token(text: String, reference_offsets: &[OffsetSize], mask: Mask) -> Token {
+    Token {
+        text,
+        offset: Offset::new(
+            reference_offsets[0],
+            reference_offsets[reference_offsets.len() - 1] + 1,
+        ),
+        reference_offsets: reference_offsets.to_vec(),
+        mask,
+    }
+}
+
+fn read_mecab_csv(f: File) -> Result<HashMap<String, f32>, TokenizerError> {
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .from_reader(f);
+    let mut lexicon: HashMap<String, f32> = HashMap::new();
+    for record in reader.records() {
+        let record = record?;
+        let (surface, cost) = match (record.get(0), record.get(3)) {
+            (Some(surface), Some(cost)) => (surface, cost),
+            _ => {
+                return Err(TokenizerError::VocabularyParsingError(format!(
+                    "Invalid MeCab lexicon entry: {record:?}"
+                )))
+            }
+        };
+        let cost = cost.trim().parse::<f32>().map_err(|e| {
+            TokenizerError::VocabularyParsingError(format!("Invalid MeCab word cost {cost}: {e}"))
+        })?;
+        insert_min_cost(&mut lexicon, surface, cost);
+    }
+    Ok(lexicon)
+}
+
+fn read_jieba_dict(f: File) -> Result<HashMap<String, f32>, TokenizerError> {
+    let mut frequencies: Vec<(String, f64)> = vec![];
+    for line in BufReader::new(f).lines() {
+        let line = line.map_err(|e| TokenizerError::IOError(e.to_string()))?;
+        let mut fields = line.split_whitespace();
+        let word = match fields.next() {
+            Some(word) => word,
+            None => continue,
+        };
+        let frequency = match fields.next() {
+            Some(frequency) => frequency.parse::<f64>().map_err(|e| {
+                TokenizerError::VocabularyParsingError(format!(
+                    "Invalid jieba word frequency {frequency}: {e}"
+                ))
+            })?,
+            None => 1.0,
+        };
+        frequencies.push((word.to_string(), frequency.max(1.0)));
+    }
+    let log_total = frequencies
+        .iter()
+        .map(|(_, frequency)| frequency)
+        .sum::<f64>()
+        .ln();
+    let mut lexicon: HashMap<String, f32> = HashMap::new();
+    for (word, frequency) in frequencies {
+        insert_min_cost(&mut lexicon, &word, (log_total - frequency.ln()) as f32);
+    }
+    Ok(lexicon)
+}
+
+fn insert_min_cost(lexicon: &mut HashMap<String, f32>, word: &str, cost: f32) {
+    let entry = lexicon.entry(word.to_string()).or_insert(cost);
+    if cost < *entry {
+        *entry = cost;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn generate_test_segmenter() -> DictionarySegmenter {
+        let lexicon: HashMap<String, f32> = [
+            ("東京", 3.0),
+            ("東", 4.0),
+            ("京都", 3.0),
+            ("都", 3.0),
+            ("に", 1.0),
+            ("住む", 2.0),
+            ("住", 3.0),
+            ("む", 3.0),
+        ]
+        .iter()
+        .map(|(word, cost)| (word.to_string(), *cost))
+        .collect();
+        DictionarySegmenter::from_lexicon(lexicon)
+    }
+
+    #[test]
+    fn test_dictionary_segmenter() {
+        //        Given
+        let segmenter = generate_test_segmenter();
+        let test_tuples = [
+            ("東京都に住む", vec!["東京", "都", "に", "住む"]),
+            ("東京にIBMで", vec!["東京", "に", "IBM", "で"]),
+            ("コンピュータ2024", vec!["コンピュータ", "2024"]),
+            ("hello", vec!["hello"]),
+        ];
+
+        //        When & Then
+        for (source_text, expected_result) in test_tuples.iter() {
+            assert_eq!(segmenter.tokenize(source_text), *expected_result);
+        }
+    }
+
+    #[test]
+    fn test_offsets_and_masks() {
+        //        Given
+        let mut segmenter = generate_test_segmenter();
+        let offsets = [10, 11, 12, 13];
+
+        //        When
+        let words = segmenter.tokenize_to_tokens(TokenRef::new("京都に住", &offsets));
+        segmenter.set_split_characters(true);
+        let characters = segmenter.tokenize_to_tokens(TokenRef::new("京都に住", &offsets));
+
+        //        Then
+        assert_eq!(words[0].text, "京都");
+        assert_eq!(words[0].offset, Offset::new(10, 12));
+        assert_eq!(words[0].reference_offsets, vec![10, 11]);
+        assert_eq!(words[0].mask, Mask::None);
+        assert_eq!(words[1].mask, Mask::CJK);
+        assert_eq!(
+            characters
+                .iter()
+                .map(|token| (token.text.as_str(), token.mask))
+                .collect::<Vec<_>>(),
+            vec![
+                ("京", Mask::Begin),
+                ("都", Mask::Continuation),
+                ("に", Mask::CJK),
+                ("住", Mask::CJK)
+            ]
+        );
+        assert_eq!(characters[1].offset, Offset::new(11, 12));
+    }
+
+    #[test]
+    fn test_lexicon_files() -> anyhow::Result<()> {
+        //        Given
+        let mut mecab_file = tempfile::NamedTempFile::new()?;
+        writeln!(
+            mecab_file,
+            "東京,1293,1293,3003,名詞,固有名詞,地域,一般,*,*,東京"
+        )?;
+        writeln!(mecab_file, "東,1285,1285,8000,名詞,一般,*,*,*,*,東")?;
+        writeln!(
+            mecab_file,
+            "京都,1293,1293,2135,名詞,固有名詞,地域,一般,*,*,京都"
+        )?;
+        writeln!(mecab_file, "都,1303,1303,6500,名詞,接尾,地域,*,*,*,都")?;
+        let mut jieba_file = tempfile::NamedTempFile::new()?;
+        writeln!(jieba_file, "北京 100 ns")?;
+        writeln!(jieba_file, "大学 100 n")?;
+        writeln!(jieba_file, "北京大学 1000 nt")?;
+        writeln!(jieba_file, "北 10 ns")?;
+
+        //        When
+        let mecab_segmenter =
+            DictionarySegmenter::from_file(mecab_file.path(), LexiconFormat::MecabCsv)?;
+        let jieba_segmenter =
+            DictionarySegmenter::from_file(jieba_file.path(), LexiconFormat::Jieba)?;
+
+        //        Then
+        assert_eq!(mecab_segmenter.tokenize("東京都"), vec!["東京", "都"]);
+        assert_eq!(
+            jieba_segmenter.tokenize("北京大学生"),
+            vec!["北京大学", "生"]
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,285 @@
+// Copyright 2022 Meta and The HuggingFace Inc. team.
+// Copyright 2019-2020 Guillaume Becquin
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//     http://www.apache.org/licenses/LICENSE-2.0
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::error::TokenizerError;
+use crate::tokenizer::base_tokenizer::{
+    Mask, Offset, OffsetSize, Token, TokenIdsWithOffsets, TokenIdsWithSpecialTokens, TokenRef,
+    Tokenizer,
+};
+use crate::tokenizer::tokenization_utils::SpecialTokenCache;
+use crate::tokenizer::tokenization_utils::{is_whitespace, split_on_special_tokens};
+use crate::tokenizer::MultiThreadedTokenizer;
+use crate::vocab::{EsmVocab, Vocab};
+use std::path::Path;
+use std::sync::RwLock;
+
+/// # ESM tokenizer
+/// Protein sequence tokenizer for the ESM family of models (ESM-1b, ESM-2), performing:
+/// - splitting on special characters
+/// - splitting the sequence into single residues (whitespace is ignored)
+///
+/// The offsets of each token point to the position of its residue in the input sequence.
+pub struct EsmTokenizer {
+    vocab: EsmVocab,
+    special_token_cache: SpecialTokenCache,
+}
+
+impl EsmTokenizer {
+    /// Create a new instance of a `EsmTokenizer`
+    /// Expects a flat vocabulary file (one token per line) as an input.
+    ///
+    /// # Parameters
+    /// - path (`&str`): path to the vocabulary file
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use rust_tokenizers::tokenizer::{EsmTokenizer, Tokenizer};
+    /// let tokenizer = EsmTokenizer::from_file("path/to/vocab/file").unwrap();
+    /// ```
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<EsmTokenizer, TokenizerError> {
+        let vocab = EsmVocab::from_file(path)?;
+        Ok(Self::from_existing_vocab(vocab))
+    }
+
+    /// Create a new instance of a `EsmTokenizer`
+    /// Expects a flat vocabulary file and a special token mapping file as inputs.
+    ///
+    /// # Parameters
+    /// - path (`&str`): path to the vocabulary file
+    /// - special_token_mapping_path (`&str`): path to a special token mapping file to overwrite default special tokens
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use rust_tokenizers::tokenizer::{EsmTokenizer, Tokenizer};
+    /// let tokenizer = EsmTokenizer::from_file_with_special_token_mapping(
+    ///     "path/to/vocab/file",
+    ///     "path/to/special/token/mapping/file",
+    /// )
+    /// .unwrap();
+    /// ```
+    pub fn from_file_with_special_token_mapping<P: AsRef<Path>, S: AsRef<Path>>(
+        path: P,
+        special_token_mapping_path: S,
+    ) -> Result<EsmTokenizer, TokenizerError> {
+        let vocab =
+            EsmVocab::from_file_with_special_token_mapping(path, special_token_mapping_path)?;
+        Ok(Self::from_existing_vocab(vocab))
+    }
+
+    /// Create a new instance of a `EsmTokenizer` from an existing vocabulary
+    ///
+    /// # Parameters
+    /// - vocab (`EsmVocab`): ESM vocabulary
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use rust_tokenizers::tokenizer::{EsmTokenizer, Tokenizer};
+    /// use rust_tokenizers::vocab::{EsmVocab, Vocab};
+    /// let vocab = EsmVocab::from_file("path/to/vocab/file").unwrap();
+    ///
+    /// let tokenizer = EsmTokenizer::from_existing_vocab(vocab);
+    /// ```
+    pub fn from_existing_vocab(vocab: EsmVocab) -> EsmTokenizer {
+        EsmTokenizer {
+            vocab,
+            special_token_cache: RwLock::new(None),
+        }
+    }
+}
+
+impl Tokenizer<EsmVocab> for EsmTokenizer {
+    fn vocab(&self) -> &EsmVocab {
+        &self.vocab
+    }
+    fn vocab_mut(&mut self) -> &mut EsmVocab {
+        &mut self.vocab
+    }
+
+    fn tokenize_to_tokens(&self, initial_token: TokenRef) -> Vec<Token> {
+        let mut tokens: Vec<Token> = Vec::new();
+        for token in split_on_special_tokens(initial_token, &self.vocab, &self.special_token_cache)
+        {
+            if token.mask == Mask::Special || token.mask == Mask::Unknown {
+                tokens.push(token.to_owned());
+                continue;
+            }
+            for (character, &reference_offset) in
+                token.text.chars().zip(token.reference_offsets.iter())
+            {
+                if is_whitespace(&character) {
+                    continue;
+                }
+                tokens.push(Token {
+                    text: character.to_string(),
+                    offset: Offset::new(reference_offset, reference_offset + 1),
+                    reference_offsets: vec![reference_offset],
+                    mask: Mask::None,
+                });
+            }
+        }
+        tokens
+    }
+
+    fn convert_tokens_to_string(&self, tokens: Vec<String>) -> String {
+        tokens.concat()
+    }
+
+    fn build_input_with_special_tokens(
+        &self,
+        tokens_ids_with_offsets_1: TokenIdsWithOffsets,
+        tokens_ids_with_offsets_2: Option<TokenIdsWithOffsets>,
+    ) -> TokenIdsWithSpecialTokens {
+        let mut output: Vec<i64> = vec![];
+        let mut token_segment_ids: Vec<i8> = vec![0; tokens_ids_with_offsets_1.ids.len() + 2];
+        let mut special_tokens_mask: Vec<i8> = vec![];
+        let mut offsets: Vec<Option<Offset>> = vec![];
+        let mut original_offsets: Vec<Vec<OffsetSize>> = vec![];
+        let mut mask: Vec<Mask> = vec![];
+        special_tokens_mask.push(1);
+        special_tokens_mask.extend(vec![0; tokens_ids_with_offsets_1.ids.len()]);
+        special_tokens_mask.push(1);
+        output.push(self.vocab.token_to_id(self.vocab.get_cls_value()));
+        output.extend(tokens_ids_with_offsets_1.ids);
+        output.push(self.vocab.token_to_id(self.vocab.get_eos_value()));
+        offsets.push(None);
+        offsets.extend(tokens_ids_with_offsets_1.offsets);
+        offsets.push(None);
+        original_offsets.push(vec![]);
+        original_offsets.extend(tokens_ids_with_offsets_1.reference_offsets);
+        original_offsets.push(vec![]);
+        mask.push(Mask::Special);
+        mask.extend(tokens_ids_with_offsets_1.masks);
+        mask.push(Mask::Special);
+        if let Some(tokens_ids_with_offsets_2_value) = tokens_ids_with_offsets_2 {
+            let length = tokens_ids_with_offsets_2_value.ids.len();
+            special_tokens_mask.extend(vec![0; length]);
+            special_tokens_mask.push(1);
+            token_segment_ids.extend(vec![1; length + 1]);
+            output.extend(tokens_ids_with_offsets_2_value.ids);
+            output.push(self.vocab.token_to_id(self.vocab.get_eos_value()));
+            offsets.extend(tokens_ids_with_offsets_2_value.offsets);
+            offsets.push(None);
+            original_offsets.extend(tokens_ids_with_offsets_2_value.reference_offsets);
+            original_offsets.push(vec![]);
+            mask.extend(tokens_ids_with_offsets_2_value.masks);
+            mask.push(Mask::Special);
+        }
+        TokenIdsWithSpecialTokens {
+            token_ids: output,
+            segment_ids: token_segment_ids,
+            special_tokens_mask,
+            token_offsets: offsets,
+            reference_offsets: original_offsets,
+            mask,
+        }
+    }
+}
+
+impl MultiThreadedTokenizer<EsmVocab> for EsmTokenizer {}
+
+//==============================
+// Unit tests
+//==============================
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tokenizer::TruncationStrategy;
+    use crate::vocab::base_vocab::{swap_key_values, SpecialTokenMap};
+    use std::collections::HashMap;
+
+    fn generate_test_vocab() -> EsmVocab {
+        let values: HashMap<String, i64> = [
+            "<cls>", "<pad>", "<eos>", "<unk>", "L", "A", "G", "V", "M", "K", "<mask>",
+        ]
+        .iter()
+        .enumerate()
+        .map(|(id, token)| (token.to_string(), id as i64))
+        .collect();
+        let special_token_map = SpecialTokenMap {
+            unk_token: "<unk>".to_string(),
+            pad_token: Some("<pad>".to_string()),
+            bos_token: None,
+            sep_token: None,
+            cls_token: Some("<cls>".to_string()),
+            eos_token: Some("<eos>".to_string()),
+            mask_token: Some("<mask>".to_string()),
+            additional_special_tokens: None,
+        };
+        let mut special_values = HashMap::new();
+        special_token_map
+            .register_special_values(&values, &mut special_values)
+            .unwrap();
+        let indices = swap_key_values(&values);
+        let special_indices = swap_key_values(&special_values);
+        EsmVocab {
+            values,
+            indices,
+            special_token_map,
+            special_values,
+            special_indices,
+        }
+    }
+
+    #[test]
+    fn test_esm_tokenizer() {
+        //        Given
+        let tokenizer = EsmTokenizer::from_existing_vocab(generate_test_vocab());
+        let test_tuples = [
+            ("MKVL", vec!["M", "K", "V", "L"]),
+            ("MK<mask>L", vec!["M", "K", "<mask>", "L"]),
+            ("MK VL", vec!["M", "K", "V", "L"]),
+            ("MXL", vec!["M", "X", "L"]),
+        ];
+
+        //        When & Then
+        for (source_text, expected_tokens) in test_tuples.iter() {
+            assert_eq!(tokenizer.tokenize(source_text), *expected_tokens);
+        }
+        assert_eq!(
+            tokenizer.convert_tokens_to_ids(&tokenizer.tokenize("MXL")),
+            vec![8, 3, 4]
+        );
+    }
+
+    #[test]
+    fn test_esm_encode() {
+        //        Given
+        let tokenizer = EsmTokenizer::from_existing_vocab(generate_test_vocab());
+
+        //        When
+        let encoded = tokenizer.encode(
+            "MK <mask>L",
+            None,
+            128,
+            &TruncationStrategy::LongestFirst,
+            0,
+        );
+
+        //        Then
+        assert_eq!(encoded.token_ids, vec![0, 8, 9, 10, 4, 2]);
+        assert_eq!(
+            encoded.token_offsets,
+            vec![
+                None,
+                Some(Offset::new(0, 1)),
+                Some(Offset::new(1, 2)),
+                Some(Offset::new(3, 9)),
+                Some(Offset::new(9, 10)),
+                None
+            ]
+        );
+        assert_eq!(tokenizer.decode(&encoded.token_ids, true, false), "MKL");
+    }
+}
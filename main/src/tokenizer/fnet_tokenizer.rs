@@ -11,11 +11,12 @@
 // limitations under the License.
 
 use std::path::Path;
+use std::sync::RwLock;
 
 use crate::error::TokenizerError;
 use crate::tokenizer::tokenization_utils::{
     clean_text, decompose_nfkc, is_whitespace, lowercase, replace_string, split_on_special_tokens,
-    strip_accents,
+    strip_accents, SpecialTokenCache,
 };
 use crate::vocab::{FNetVocab, SentencePieceBpeModel};
 
@@ -36,6 +37,7 @@ use crate::{Mask, Offset, OffsetSize, Token, TokenRef};
 pub struct FNetTokenizer {
     model: SentencePieceBpeModel,
     vocab: FNetVocab,
+    special_token_cache: SpecialTokenCache,
     lower_case: bool,
     strip_accents: bool,
 }
@@ -70,6 +72,7 @@ impl FNetTokenizer {
             vocab,
             lower_case,
             strip_accents,
+            special_token_cache: RwLock::new(None),
         })
     }
 
@@ -110,6 +113,7 @@ impl FNetTokenizer {
             vocab,
             lower_case,
             strip_accents,
+            special_token_cache: RwLock::new(None),
         })
     }
 
@@ -145,6 +149,7 @@ impl FNetTokenizer {
             vocab,
             lower_case,
             strip_accents,
+            special_token_cache: RwLock::new(None),
         }
     }
 
@@ -201,7 +206,7 @@ impl Tokenizer<FNetVocab> for FNetTokenizer {
     }
 
     fn tokenize_to_tokens(&self, text: TokenRef) -> Vec<Token> {
-        let mut tokens = split_on_special_tokens(text, &self.vocab)
+        let mut tokens = split_on_special_tokens(text, &self.vocab, &self.special_token_cache)
             .into_iter()
             .map(|token| token.to_owned())
             .collect::<Vec<Token>>();
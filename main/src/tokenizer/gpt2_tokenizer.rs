@@ -16,6 +16,7 @@ use crate::error::TokenizerError;
 use crate::tokenizer::constants::UNICODE_TO_BYTES;
 use crate::tokenizer::tokenization_utils::{
     bpe, fix_mask, split_on_bpe_pairs, split_on_regex_with_lookahead, split_on_special_tokens,
+    SpecialTokenCache,
 };
 use crate::tokenizer::tokenization_utils::{lowercase, BpeCache};
 use crate::tokenizer::{MultiThreadedTokenizer, Tokenizer};
@@ -37,6 +38,7 @@ use std::sync::RwLock;
 /// - BPE tokenization
 pub struct Gpt2Tokenizer {
     vocab: Gpt2Vocab,
+    special_token_cache: SpecialTokenCache,
     bpe_ranks: BpePairVocab,
     cache: BpeCache,
     pattern_lookahead: Regex,
@@ -80,6 +82,7 @@ impl Gpt2Tokenizer {
             pattern_lookahead,
             pattern_tokenization,
             lower_case,
+            special_token_cache: RwLock::new(None),
         })
     }
 
@@ -128,6 +131,7 @@ impl Gpt2Tokenizer {
             pattern_lookahead,
             pattern_tokenization,
             lower_case,
+            special_token_cache: RwLock::new(None),
         })
     }
 
@@ -166,6 +170,7 @@ impl Gpt2Tokenizer {
             pattern_lookahead,
             pattern_tokenization,
             lower_case,
+            special_token_cache: RwLock::new(None),
         }
     }
 }
@@ -179,10 +184,11 @@ impl Tokenizer<Gpt2Vocab> for Gpt2Tokenizer {
     }
 
     fn tokenize_to_tokens(&self, initial_token: TokenRef) -> Vec<Token> {
-        let mut tokens = split_on_special_tokens(initial_token, &self.vocab)
-            .into_iter()
-            .map(|token| token.to_owned())
-            .collect::<Vec<Token>>();
+        let mut tokens =
+            split_on_special_tokens(initial_token, &self.vocab, &self.special_token_cache)
+                .into_iter()
+                .map(|token| token.to_owned())
+                .collect::<Vec<Token>>();
 
         let mut sub_tokens = Vec::new();
         for token in tokens.iter_mut() {
@@ -0,0 +1,361 @@
+// Copyright 2021 DNABERT Authors
+// Copyright 2019-2020 Guillaume Becquin
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//     http://www.apache.org/licenses/LICENSE-2.0
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::error::TokenizerError;
+use crate::tokenizer::base_tokenizer::{
+    Mask, Offset, OffsetSize, Token, TokenIdsWithOffsets, TokenIdsWithSpecialTokens, TokenRef,
+    Tokenizer,
+};
+use crate::tokenizer::tokenization_utils::{
+    is_whitespace, split_on_special_tokens, SpecialTokenCache,
+};
+use crate::tokenizer::MultiThreadedTokenizer;
+use crate::vocab::base_vocab::SpecialTokenMap;
+use crate::vocab::{BertVocab, Vocab};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::RwLock;
+
+/// # K-mer tokenizer
+/// Nucleotide sequence tokenizer for DNABERT-like models, performing:
+/// - splitting on special characters
+/// - whitespace splitting
+/// - splitting each sequence into k-mers (sub-sequences of `k` residues), starting every
+///   `stride` residues. With a stride of 1, k-mers overlap (e.g. `ATGCG` with `k=3` is
+///   tokenized as `ATG`, `TGC`, `GCG`).
+///
+/// Sequences shorter than `k` are kept as a single token. When the stride does not evenly divide
+/// the sequence length the last residues are not covered by any k-mer.
+/// The offsets of each token span the residues of its k-mer in the input sequence.
+/// Uses a BERT-like vocabulary (`[CLS]`, `[SEP]`, `[PAD]`, `[MASK]` and `[UNK]` special tokens).
+pub struct KmerTokenizer {
+    vocab: BertVocab,
+    special_token_cache: SpecialTokenCache,
+    k: usize,
+    stride: usize,
+}
+
+impl KmerTokenizer {
+    /// Create a new instance of a `KmerTokenizer`
+    /// Expects a flat vocabulary file (one k-mer per line, e.g. DNABERT `vocab.txt`) as an input.
+    ///
+    /// # Parameters
+    /// - path (`&str`): path to the vocabulary file
+    /// - k (`usize`): length of the k-mers
+    /// - stride (`usize`): number of residues between the start of two consecutive k-mers
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use rust_tokenizers::tokenizer::{KmerTokenizer, Tokenizer};
+    /// let tokenizer = KmerTokenizer::from_file("path/to/vocab/file", 6, 1).unwrap();
+    /// ```
+    pub fn from_file<P: AsRef<Path>>(
+        path: P,
+        k: usize,
+        stride: usize,
+    ) -> Result<KmerTokenizer, TokenizerError> {
+        let vocab = BertVocab::from_file(path)?;
+        Self::from_existing_vocab(vocab, k, stride)
+    }
+
+    /// Create a new instance of a `KmerTokenizer` with a vocabulary generated from an alphabet.
+    /// The vocabulary contains the special tokens (`[PAD]`, `[UNK]`, `[CLS]`, `[SEP]`, `[MASK]`)
+    /// followed by all k-mers of the alphabet, in lexicographic order of the alphabet.
+    ///
+    /// # Parameters
+    /// - alphabet (`&str`): residues of the sequences (e.g. `ACGT`)
+    /// - k (`usize`): length of the k-mers
+    /// - stride (`usize`): number of residues between the start of two consecutive k-mers
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_tokenizers::tokenizer::{KmerTokenizer, Tokenizer};
+    /// let tokenizer = KmerTokenizer::from_alphabet("ACGT", 3, 1).unwrap();
+    ///
+    /// assert_eq!(tokenizer.tokenize("ACGTA"), vec!["ACG", "CGT", "GTA"]);
+    /// assert_eq!(tokenizer.convert_tokens_to_ids(&["AAA", "AAC"]), vec![5, 6]);
+    /// ```
+    pub fn from_alphabet(
+        alphabet: &str,
+        k: usize,
+        stride: usize,
+    ) -> Result<KmerTokenizer, TokenizerError> {
+        let special_token_map = SpecialTokenMap {
+            unk_token: "[UNK]".to_string(),
+            pad_token: Some("[PAD]".to_string()),
+            bos_token: None,
+            sep_token: Some("[SEP]".to_string()),
+            cls_token: Some("[CLS]".to_string()),
+            eos_token: None,
+            mask_token: Some("[MASK]".to_string()),
+            additional_special_tokens: None,
+        };
+        let mut tokens: Vec<String> = ["[PAD]", "[UNK]", "[CLS]", "[SEP]", "[MASK]"]
+            .iter()
+            .map(|token| token.to_string())
+            .collect();
+        let mut kmers = vec![String::new()];
+        for _ in 0..k {
+            kmers = kmers
+                .iter()
+                .flat_map(|prefix| {
+                    alphabet
+                        .chars()
+                        .map(move |residue| format!("{prefix}{residue}"))
+                })
+                .collect();
+        }
+        tokens.extend(kmers);
+        let values: HashMap<String, i64> = tokens
+            .into_iter()
+            .enumerate()
+            .map(|(id, token)| (token, id as i64))
+            .collect();
+        let vocab = BertVocab::from_values_and_special_token_map(values, special_token_map)?;
+        Self::from_existing_vocab(vocab, k, stride)
+    }
+
+    /// Create a new instance of a `KmerTokenizer` from an existing vocabulary
+    ///
+    /// # Parameters
+    /// - vocab (`BertVocab`): vocabulary of k-mers
+    /// - k (`usize`): length of the k-mers
+    /// - stride (`usize`): number of residues between the start of two consecutive k-mers
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use rust_tokenizers::tokenizer::{KmerTokenizer, Tokenizer};
+    /// use rust_tokenizers::vocab::{BertVocab, Vocab};
+    /// let vocab = BertVocab::from_file("path/to/vocab/file").unwrap();
+    ///
+    /// let tokenizer = KmerTokenizer::from_existing_vocab(vocab, 6, 1).unwrap();
+    /// ```
+    pub fn from_existing_vocab(
+        vocab: BertVocab,
+        k: usize,
+        stride: usize,
+    ) -> Result<KmerTokenizer, TokenizerError> {
+        if k == 0 || stride == 0 {
+            return Err(TokenizerError::ValueError(format!(
+                "The k-mer length and stride must be strictly positive, got k={k}, stride={stride}"
+            )));
+        }
+        Ok(KmerTokenizer {
+            vocab,
+            special_token_cache: RwLock::new(None),
+            k,
+            stride,
+        })
+    }
+
+    /// Returns the length of the k-mers
+    pub fn k(&self) -> usize {
+        self.k
+    }
+
+    /// Returns the number of residues between the start of two consecutive k-mers
+    pub fn stride(&self) -> usize {
+        self.stride
+    }
+
+    fn split_into_kmers(&self, residues: &[(char, OffsetSize)], tokens: &mut Vec<Token>) {
+        let starts: Vec<usize> = if residues.len() <= self.k {
+            vec![0]
+        } else {
+            (0..=residues.len() - self.k).step_by(self.stride).collect()
+        };
+        for start in starts {
+            let kmer = &residues[start..residues.len().min(start + self.k)];
+            let reference_offsets: Vec<OffsetSize> =
+                kmer.iter().map(|(_, offset)| *offset).collect();
+            tokens.push(Token {
+                text: kmer.iter().map(|(residue, _)| residue).collect(),
+                offset: Offset::new(
+                    reference_offsets[0],
+                    reference_offsets[reference_offsets.len() - 1] + 1,
+                ),
+                reference_offsets,
+                mask: Mask::None,
+            });
+        }
+    }
+}
+
+impl Tokenizer<BertVocab> for KmerTokenizer {
+    fn vocab(&self) -> &BertVocab {
+        &self.vocab
+    }
+    fn vocab_mut(&mut self) -> &mut BertVocab {
+        &mut self.vocab
+    }
+
+    fn tokenize_to_tokens(&self, initial_token: TokenRef) -> Vec<Token> {
+        let mut tokens: Vec<Token> = Vec::new();
+        for token in split_on_special_tokens(initial_token, &self.vocab, &self.special_token_cache)
+        {
+            if token.mask == Mask::Special || token.mask == Mask::Unknown {
+                tokens.push(token.to_owned());
+                continue;
+            }
+            let mut residues: Vec<(char, OffsetSize)> = Vec::new();
+            for (character, &reference_offset) in
+                token.text.chars().zip(token.reference_offsets.iter())
+            {
+                if is_whitespace(&character) {
+                    if !residues.is_empty() {
+                        self.split_into_kmers(&residues, &mut tokens);
+                        residues.clear();
+                    }
+                } else {
+                    residues.push((character, reference_offset));
+                }
+            }
+            if !residues.is_empty() {
+                self.split_into_kmers(&residues, &mut tokens);
+            }
+        }
+        tokens
+    }
+
+    /// Rebuilds the sequences from the k-mers: overlapping k-mers contribute their last `stride`
+    /// residues, special tokens and sequences shorter than `k` are separated by a space.
+    fn convert_tokens_to_string(&self, tokens: Vec<String>) -> String {
+        let mut words: Vec<String> = Vec::new();
+        let mut previous_is_kmer = false;
+        for token in tokens {
+            let is_kmer = token.chars().count() == self.k
+                && !self.vocab.special_values.contains_key(token.as_str());
+            match words.last_mut() {
+                Some(word) if is_kmer && previous_is_kmer && self.stride < self.k => {
+                    word.extend(token.chars().skip(self.k - self.stride));
+                }
+                Some(word) if is_kmer && previous_is_kmer => word.push_str(&token),
+                _ => words.push(token),
+            }
+            previous_is_kmer = is_kmer;
+        }
+        words.join(" ")
+    }
+
+    fn build_input_with_special_tokens(
+        &self,
+        tokens_ids_with_offsets_1: TokenIdsWithOffsets,
+        tokens_ids_with_offsets_2: Option<TokenIdsWithOffsets>,
+    ) -> TokenIdsWithSpecialTokens {
+        let mut output: Vec<i64> = vec![];
+        let mut token_segment_ids: Vec<i8> = vec![0; tokens_ids_with_offsets_1.ids.len() + 2];
+        let mut special_tokens_mask: Vec<i8> = vec![];
+        let mut offsets: Vec<Option<Offset>> = vec![];
+        let mut original_offsets: Vec<Vec<OffsetSize>> = vec![];
+        let mut mask: Vec<Mask> = vec![];
+        special_tokens_mask.push(1);
+        special_tokens_mask.extend(vec![0; tokens_ids_with_offsets_1.ids.len()]);
+        special_tokens_mask.push(1);
+        output.push(self.vocab.token_to_id(self.vocab.get_cls_value()));
+        output.extend(tokens_ids_with_offsets_1.ids);
+        output.push(self.vocab.token_to_id(self.vocab.get_sep_value()));
+        offsets.push(None);
+        offsets.extend(tokens_ids_with_offsets_1.offsets);
+        offsets.push(None);
+        original_offsets.push(vec![]);
+        original_offsets.extend(tokens_ids_with_offsets_1.reference_offsets);
+        original_offsets.push(vec![]);
+        mask.push(Mask::Special);
+        mask.extend(tokens_ids_with_offsets_1.masks);
+        mask.push(Mask::Special);
+        if let Some(tokens_ids_with_offsets_2_value) = tokens_ids_with_offsets_2 {
+            let length = tokens_ids_with_offsets_2_value.ids.len();
+            special_tokens_mask.extend(vec![0; length]);
+            special_tokens_mask.push(1);
+            token_segment_ids.extend(vec![1; length + 1]);
+            output.extend(tokens_ids_with_offsets_2_value.ids);
+            output.push(self.vocab.token_to_id(self.vocab.get_sep_value()));
+            offsets.extend(tokens_ids_with_offsets_2_value.offsets);
+            offsets.push(None);
+            original_offsets.extend(tokens_ids_with_offsets_2_value.reference_offsets);
+            original_offsets.push(vec![]);
+            mask.extend(tokens_ids_with_offsets_2_value.masks);
+            mask.push(Mask::Special);
+        }
+        TokenIdsWithSpecialTokens {
+            token_ids: output,
+            segment_ids: token_segment_ids,
+            special_tokens_mask,
+            token_offsets: offsets,
+            reference_offsets: original_offsets,
+            mask,
+        }
+    }
+}
+
+impl MultiThreadedTokenizer<BertVocab> for KmerTokenizer {}
+
+//==============================
+// Unit tests
+//==============================
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tokenizer::TruncationStrategy;
+
+    #[test]
+    fn test_kmer_tokenizer() {
+        //        Given
+        let test_tuples = [
+            (3, 1, "ATGCG", vec!["ATG", "TGC", "GCG"]),
+            (3, 3, "ATGCGTA", vec!["ATG", "CGT"]),
+            (3, 1, "AT GCGT", vec!["AT", "GCG", "CGT"]),
+            (2, 1, "AC[MASK]GT", vec!["AC", "[MASK]", "GT"]),
+            (3, 1, "ANGC", vec!["ANG", "NGC"]),
+        ];
+
+        //        When & Then
+        for (k, stride, source_text, expected_tokens) in test_tuples.iter() {
+            let tokenizer = KmerTokenizer::from_alphabet("ACGT", *k, *stride).unwrap();
+            assert_eq!(tokenizer.tokenize(source_text), *expected_tokens);
+        }
+        let tokenizer = KmerTokenizer::from_alphabet("ACGT", 3, 1).unwrap();
+        assert_eq!(Tokenizer::vocab(&tokenizer).values.len(), 5 + 64);
+        assert_eq!(tokenizer.convert_tokens_to_ids(&["ANG"]), vec![1]);
+        assert!(KmerTokenizer::from_alphabet("ACGT", 3, 0).is_err());
+    }
+
+    #[test]
+    fn test_kmer_encode_decode() {
+        //        Given
+        let tokenizer = KmerTokenizer::from_alphabet("ACGT", 3, 1).unwrap();
+
+        //        When
+        let encoded = tokenizer.encode("ATGCG", None, 128, &TruncationStrategy::LongestFirst, 0);
+
+        //        Then
+        assert_eq!(encoded.token_ids.len(), 5);
+        assert_eq!(
+            encoded.token_offsets,
+            vec![
+                None,
+                Some(Offset::new(0, 3)),
+                Some(Offset::new(1, 4)),
+                Some(Offset::new(2, 5)),
+                None
+            ]
+        );
+        assert_eq!(tokenizer.decode(&encoded.token_ids, true, false), "ATGCG");
+        assert_eq!(
+            tokenizer.decode(&encoded.token_ids, false, false),
+            "[CLS] ATGCG [SEP]"
+        );
+    }
+}
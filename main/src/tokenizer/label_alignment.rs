@@ -0,0 +1,510 @@
+// Copyright 2019-2020 Guillaume Becquin
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//     http://www.apache.org/licenses/LICENSE-2.0
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::error::TokenizerError;
+use crate::tokenizer::base_tokenizer::{Mask, Offset, OffsetSize};
+use std::collections::HashMap;
+use std::ops::Range;
+
+/// Label id of the tokens ignored by the loss of token classification models (special tokens,
+/// padding and unlabelled sub-tokens)
+pub const LABEL_PAD_ID: i64 = -100;
+
+/// # Label alignment strategy
+/// Indicates how the label of a word is assigned to its sub-tokens
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LabelAlignment {
+    /// Label only the first sub-token of each word, the other sub-tokens are ignored
+    FirstSubToken,
+    /// Label all sub-tokens of a word with the word label
+    AllSubTokens,
+    /// Label all sub-tokens of a word, continuation sub-tokens of a `B-X` word being labelled `I-X`
+    PropagateBio,
+}
+
+/// # Word alignment of an encoding
+/// Index of the word of each token (`None` for special tokens and tokens that cannot be related to
+/// a word) and character offsets of each word in the original text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WordAlignment {
+    pub word_ids: Vec<Option<usize>>,
+    pub word_offsets: Vec<Option<Offset>>,
+}
+
+impl WordAlignment {
+    /// Create a new instance of a `WordAlignment` from the words identified by the tokenizer: a
+    /// token with a `Mask::Continuation` belongs to the word of the previous token, any other
+    /// token that is not special starts a new word.
+    ///
+    /// # Parameters
+    /// - token_offsets (`&[Option<Offset>]`): offsets of the tokens of the encoding
+    /// - masks (`&[Mask]`): masks of the tokens of the encoding
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use rust_tokenizers::tokenizer::{BertTokenizer, Tokenizer, TruncationStrategy, WordAlignment};
+    /// let tokenizer = BertTokenizer::from_file("path/to/vocab", true, true).unwrap();
+    /// let encoding = tokenizer.encode(
+    ///     "John lives in New York",
+    ///     None,
+    ///     128,
+    ///     &TruncationStrategy::LongestFirst,
+    ///     0,
+    /// );
+    /// let alignment = WordAlignment::from_masks(&encoding.token_offsets, &encoding.mask);
+    /// ```
+    pub fn from_masks(token_offsets: &[Option<Offset>], masks: &[Mask]) -> WordAlignment {
+        let mut word_ids = Vec::with_capacity(masks.len());
+        let mut word_offsets: Vec<Option<Offset>> = Vec::new();
+        let mut previous_word = None;
+        for (offset, mask) in token_offsets.iter().zip(masks) {
+            let word_id = match mask {
+                Mask::Special => None,
+                Mask::Continuation if previous_word.is_some() => previous_word,
+                _ => {
+                    word_offsets.push(None);
+                    Some(word_offsets.len() - 1)
+                }
+            };
+            if let (Some(word_id), Some(offset)) = (word_id, offset) {
+                let word_offset = &mut word_offsets[word_id];
+                *word_offset = Some(match word_offset {
+                    Some(word_offset) => Offset {
+                        begin: word_offset.begin.min(offset.begin),
+                        end: word_offset.end.max(offset.end),
+                    },
+                    None => *offset,
+                });
+            }
+            word_ids.push(word_id);
+            previous_word = word_id;
+        }
+        WordAlignment {
+            word_ids,
+            word_offsets,
+        }
+    }
+
+    /// Create a new instance of a `WordAlignment` from the character offsets of pre-split words
+    /// (for example obtained with `join_words`). Each token that is not special is assigned to the
+    /// word overlapping its offsets. The word offsets must be sorted.
+    ///
+    /// # Parameters
+    /// - token_offsets (`&[Option<Offset>]`): offsets of the tokens of the encoding
+    /// - masks (`&[Mask]`): masks of the tokens of the encoding
+    /// - word_offsets (`&[Offset]`): character offsets of the words in the original text
+    pub fn from_word_offsets(
+        token_offsets: &[Option<Offset>],
+        masks: &[Mask],
+        word_offsets: &[Offset],
+    ) -> WordAlignment {
+        let word_ids = token_offsets
+            .iter()
+            .zip(masks)
+            .map(|(offset, mask)| {
+                let offset = offset.filter(|_| *mask != Mask::Special)?;
+                let word_id =
+                    word_offsets.partition_point(|word_offset| word_offset.end <= offset.begin);
+                word_offsets
+                    .get(word_id)
+                    .filter(|word_offset| word_offset.begin < offset.end.max(offset.begin + 1))
+                    .map(|_| word_id)
+            })
+            .collect();
+        WordAlignment {
+            word_ids,
+            word_offsets: word_offsets.iter().copied().map(Some).collect(),
+        }
+    }
+
+    /// Returns the number of words of the alignment
+    pub fn num_words(&self) -> usize {
+        self.word_offsets.len()
+    }
+}
+
+/// # Entity span
+/// Entity decoded from word labels, with its range of words and its character offsets in the
+/// original text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EntitySpan {
+    pub label: String,
+    pub words: Range<usize>,
+    pub offset: Option<Offset>,
+}
+
+/// Joins pre-split words with single spaces, returning the text and the character offsets of the
+/// words (to be used with `WordAlignment::from_word_offsets` after encoding the text).
+///
+/// # Example
+///
+/// ```
+/// use rust_tokenizers::tokenizer::join_words;
+/// use rust_tokenizers::Offset;
+/// let (text, word_offsets) = join_words(&["New", "York"]);
+/// assert_eq!(text, "New York");
+/// assert_eq!(word_offsets[1], Offset { begin: 4, end: 8 });
+/// ```
+pub fn join_words<S: AsRef<str>>(words: &[S]) -> (String, Vec<Offset>) {
+    let mut text = String::new();
+    let mut word_offsets = Vec::with_capacity(words.len());
+    let mut position = 0;
+    for (index, word) in words.iter().enumerate() {
+        if index > 0 {
+            text.push(' ');
+            position += 1;
+        }
+        let length = word.as_ref().chars().count() as OffsetSize;
+        text.push_str(word.as_ref());
+        word_offsets.push(Offset {
+            begin: position,
+            end: position + length,
+        });
+        position += length;
+    }
+    (text, word_offsets)
+}
+
+/// Expands word labels to the tokens of an encoding. Special tokens, tokens without a word and
+/// the continuation sub-tokens of `LabelAlignment::FirstSubToken` are not labelled (`None`).
+///
+/// # Parameters
+/// - word_labels: label of each word of the alignment
+/// - alignment (`&WordAlignment`): word alignment of the encoding
+/// - label_alignment (`LabelAlignment`): assignment of the word labels to sub-tokens
+///
+/// # Returns
+/// - `Vec<Option<String>>` label of each token of the encoding
+///
+/// # Example
+///
+/// ```no_run
+/// use rust_tokenizers::tokenizer::{
+///     align_labels, join_words, labels_to_ids, BertTokenizer, LabelAlignment, Tokenizer,
+///     TruncationStrategy, WordAlignment,
+/// };
+/// use std::collections::HashMap;
+/// let tokenizer = BertTokenizer::from_file("path/to/vocab", true, true).unwrap();
+/// let (text, word_offsets) = join_words(&["John", "lives", "in", "New", "York"]);
+/// let encoding = tokenizer.encode(&text, None, 128, &TruncationStrategy::LongestFirst, 0);
+/// let alignment =
+///     WordAlignment::from_word_offsets(&encoding.token_offsets, &encoding.mask, &word_offsets);
+///
+/// let token_labels = align_labels(
+///     &["B-PER", "O", "O", "B-LOC", "I-LOC"],
+///     &alignment,
+///     LabelAlignment::FirstSubToken,
+/// )
+/// .unwrap();
+/// let label_ids: HashMap<String, i64> = [("O", 0), ("B-PER", 1), ("B-LOC", 2), ("I-LOC", 3)]
+///     .iter()
+///     .map(|(label, id)| (label.to_string(), *id))
+///     .collect();
+/// let token_label_ids = labels_to_ids(&token_labels, &label_ids).unwrap();
+/// ```
+pub fn align_labels<S: AsRef<str>>(
+    word_labels: &[S],
+    alignment: &WordAlignment,
+    label_alignment: LabelAlignment,
+) -> Result<Vec<Option<String>>, TokenizerError> {
+    if word_labels.len() != alignment.num_words() {
+        return Err(TokenizerError::ValueError(format!(
+            "Expected {} word labels, got {}",
+            alignment.num_words(),
+            word_labels.len()
+        )));
+    }
+    let mut previous_word = None;
+    Ok(alignment
+        .word_ids
+        .iter()
+        .map(|word_id| {
+            let is_first_sub_token = *word_id != previous_word;
+            previous_word = *word_id;
+            let label = word_labels[(*word_id)?].as_ref();
+            match label_alignment {
+                _ if is_first_sub_token => Some(label.to_string()),
+                LabelAlignment::FirstSubToken => None,
+                LabelAlignment::AllSubTokens => Some(label.to_string()),
+                LabelAlignment::PropagateBio => Some(match label.strip_prefix("B-") {
+                    Some(entity) => format!("I-{entity}"),
+                    None => label.to_string(),
+                }),
+            }
+        })
+        .collect())
+}
+
+/// Converts token labels to label ids, unlabelled tokens being assigned `LABEL_PAD_ID` (-100).
+///
+/// # Parameters
+/// - token_labels (`&[Option<String>]`): label of each token (as returned by `align_labels`)
+/// - label_ids (`&HashMap<String, i64>`): id of each label
+///
+/// # Returns
+/// - `Vec<i64>` label id of each token
+pub fn labels_to_ids(
+    token_labels: &[Option<String>],
+    label_ids: &HashMap<String, i64>,
+) -> Result<Vec<i64>, TokenizerError> {
+    token_labels
+        .iter()
+        .map(|label| match label {
+            Some(label) => label_ids
+                .get(label)
+                .copied()
+                .ok_or_else(|| TokenizerError::ValueError(format!("Unknown label {label}"))),
+            None => Ok(LABEL_PAD_ID),
+        })
+        .collect()
+}
+
+/// Maps predicted token labels back to words, using the label of the first sub-token of each word.
+/// Words without tokens (for example truncated) are not labelled (`None`).
+///
+/// # Parameters
+/// - token_labels: predicted label of each token of the encoding
+/// - alignment (`&WordAlignment`): word alignment of the encoding
+///
+/// # Returns
+/// - `Vec<Option<String>>` label of each word of the alignment
+pub fn token_labels_to_word_labels<S: AsRef<str>>(
+    token_labels: &[S],
+    alignment: &WordAlignment,
+) -> Vec<Option<String>> {
+    let mut word_labels = vec![None; alignment.num_words()];
+    for (label, word_id) in token_labels.iter().zip(&alignment.word_ids) {
+        if let Some(word_id) = word_id {
+            word_labels[*word_id].get_or_insert_with(|| label.as_ref().to_string());
+        }
+    }
+    word_labels
+}
+
+/// Decodes the entity spans of BIO word labels. An entity starts at a `B-X` label or at an `I-X`
+/// (or prefix-less `X`) label that does not continue an entity of type `X`, and ends at an `O` or
+/// missing label. The character offsets of the entities are computed from the word offsets.
+///
+/// # Parameters
+/// - word_labels: label of each word of the alignment
+/// - alignment (`&WordAlignment`): word alignment of the encoding
+///
+/// # Returns
+/// - `Vec<EntitySpan>` entities with their words and character offsets
+///
+/// # Example
+///
+/// ```
+/// use rust_tokenizers::tokenizer::{join_words, word_labels_to_spans, WordAlignment};
+/// use rust_tokenizers::Offset;
+/// let (text, word_offsets) = join_words(&["John", "lives", "in", "New", "York"]);
+/// let alignment = WordAlignment::from_word_offsets(&[], &[], &word_offsets);
+///
+/// let entities = word_labels_to_spans(
+///     &[Some("B-PER"), Some("O"), Some("O"), Some("B-LOC"), Some("I-LOC")],
+///     &alignment,
+/// );
+/// assert_eq!(entities[1].label, "LOC");
+/// assert_eq!(entities[1].offset, Some(Offset { begin: 14, end: 22 }));
+/// ```
+pub fn word_labels_to_spans<S: AsRef<str>>(
+    word_labels: &[Option<S>],
+    alignment: &WordAlignment,
+) -> Vec<EntitySpan> {
+    let mut entities: Vec<EntitySpan> = Vec::new();
+    let mut current: Option<EntitySpan> = None;
+    for (word_id, label) in word_labels.iter().enumerate() {
+        let label = label.as_ref().map(|label| label.as_ref());
+        let (entity, is_begin) = match label {
+            None | Some("O") => (None, false),
+            Some(label) => match label.strip_prefix("B-") {
+                Some(entity) => (Some(entity), true),
+                None => (Some(label.strip_prefix("I-").unwrap_or(label)), false),
+            },
+        };
+        match (&mut current, entity) {
+            (Some(span), Some(entity)) if !is_begin && span.label == entity => {
+                span.words.end = word_id + 1;
+                continue;
+            }
+            _ => entities.extend(current.take()),
+        }
+        current = entity.map(|entity| EntitySpan {
+            label: entity.to_string(),
+            words: word_id..word_id + 1,
+            offset: None,
+        });
+    }
+    entities.extend(current);
+    for entity in entities.iter_mut() {
+        entity.offset = alignment
+            .word_offsets
+            .get(entity.words.clone())
+            .into_iter()
+            .flatten()
+            .flatten()
+            .fold(None, |span: Option<Offset>, offset| {
+                Some(match span {
+                    Some(span) => Offset {
+                        begin: span.begin.min(offset.begin),
+                        end: span.end.max(offset.end),
+                    },
+                    None => *offset,
+                })
+            });
+    }
+    entities
+}
+
+//==============================
+// Unit tests
+//==============================
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tokenizer::{BertTokenizer, Tokenizer, TruncationStrategy};
+    use crate::vocab::{BertVocab, SpecialTokenMap, Vocab};
+
+    fn tokenizer() -> BertTokenizer {
+        let values: HashMap<String, i64> = vec![
+            "[UNK]", "[CLS]", "[SEP]", "[PAD]", "[MASK]", "john", "##ton", "lives", "in", "new",
+            "york",
+        ]
+        .into_iter()
+        .enumerate()
+        .map(|(id, token)| (token.to_string(), id as i64))
+        .collect();
+        let special_token_map = SpecialTokenMap {
+            unk_token: "[UNK]".to_string(),
+            pad_token: Some("[PAD]".to_string()),
+            bos_token: None,
+            sep_token: Some("[SEP]".to_string()),
+            cls_token: Some("[CLS]".to_string()),
+            eos_token: None,
+            mask_token: Some("[MASK]".to_string()),
+            additional_special_tokens: None,
+        };
+        let vocab =
+            BertVocab::from_values_and_special_token_map(values, special_token_map).unwrap();
+        BertTokenizer::from_existing_vocab(vocab, true, true)
+    }
+
+    #[test]
+    fn test_align_labels() {
+        //        Given
+        let tokenizer = tokenizer();
+        let (text, word_offsets) = join_words(&["Johnton", "lives", "in", "New", "York"]);
+        let word_labels = ["B-PER", "O", "O", "B-LOC", "I-LOC"];
+        let encoding = tokenizer.encode(&text, None, 128, &TruncationStrategy::LongestFirst, 0);
+        let label_ids: HashMap<String, i64> = vec![("O", 0), ("B-PER", 1), ("I-PER", 2)]
+            .into_iter()
+            .chain(vec![("B-LOC", 3), ("I-LOC", 4)])
+            .map(|(label, id)| (label.to_string(), id))
+            .collect();
+
+        //        When
+        let alignment = WordAlignment::from_word_offsets(
+            &encoding.token_offsets,
+            &encoding.mask,
+            &word_offsets,
+        );
+        let mask_alignment = WordAlignment::from_masks(&encoding.token_offsets, &encoding.mask);
+        let aligned_labels: Vec<Vec<i64>> = vec![
+            LabelAlignment::FirstSubToken,
+            LabelAlignment::AllSubTokens,
+            LabelAlignment::PropagateBio,
+        ]
+        .into_iter()
+        .map(|label_alignment| {
+            let labels = align_labels(&word_labels, &alignment, label_alignment).unwrap();
+            labels_to_ids(&labels, &label_ids).unwrap()
+        })
+        .collect();
+
+        //        Then
+        assert_eq!(encoding.token_ids, vec![1, 5, 6, 7, 8, 9, 10, 2]);
+        assert_eq!(
+            alignment.word_ids,
+            vec![
+                None,
+                Some(0),
+                Some(0),
+                Some(1),
+                Some(2),
+                Some(3),
+                Some(4),
+                None
+            ]
+        );
+        assert_eq!(mask_alignment, alignment);
+        assert_eq!(
+            aligned_labels,
+            vec![
+                vec![-100, 1, -100, 0, 0, 3, 4, -100],
+                vec![-100, 1, 1, 0, 0, 3, 4, -100],
+                vec![-100, 1, 2, 0, 0, 3, 4, -100],
+            ]
+        );
+        assert!(align_labels(&word_labels[..4], &alignment, LabelAlignment::AllSubTokens).is_err());
+    }
+
+    #[test]
+    fn test_token_labels_to_spans() {
+        //        Given
+        let (text, word_offsets) = join_words(&["Johnton", "lives", "in", "New", "York"]);
+        let encoding = tokenizer().encode(&text, None, 128, &TruncationStrategy::LongestFirst, 0);
+        let alignment = WordAlignment::from_word_offsets(
+            &encoding.token_offsets,
+            &encoding.mask,
+            &word_offsets,
+        );
+        let predictions = ["O", "B-PER", "I-LOC", "O", "I-LOC", "B-LOC", "I-LOC", "O"];
+
+        //        When
+        let word_labels = token_labels_to_word_labels(&predictions, &alignment);
+        let entities = word_labels_to_spans(&word_labels, &alignment);
+
+        //        Then
+        assert_eq!(
+            word_labels,
+            vec![
+                Some("B-PER".to_string()),
+                Some("O".to_string()),
+                Some("I-LOC".to_string()),
+                Some("B-LOC".to_string()),
+                Some("I-LOC".to_string()),
+            ]
+        );
+        assert_eq!(
+            entities,
+            vec![
+                EntitySpan {
+                    label: "PER".to_string(),
+                    words: 0..1,
+                    offset: Some(Offset { begin: 0, end: 7 }),
+                },
+                EntitySpan {
+                    label: "LOC".to_string(),
+                    words: 2..3,
+                    offset: Some(Offset { begin: 14, end: 16 }),
+                },
+                EntitySpan {
+                    label: "LOC".to_string(),
+                    words: 3..5,
+                    offset: Some(Offset { begin: 17, end: 25 }),
+                },
+            ]
+        );
+        let entity_text: String = text.chars().skip(17).take(8).collect();
+        assert_eq!(entity_text, "New York");
+    }
+}
@@ -0,0 +1,718 @@
+// Copyright 2019-2021 Guillaume Becquin
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//     http://www.apache.org/licenses/LICENSE-2.0
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::error::TokenizerError;
+use crate::tokenizer::base_tokenizer::{
+    Mask, TokenIdsWithOffsets, TokenizedInput, Tokenizer, TruncationStrategy,
+};
+use crate::tokenizer::label_alignment::{join_words, WordAlignment, LABEL_PAD_ID};
+use crate::tokenizer::tokenization_utils::{truncate_sequences, truncation_range};
+use crate::tokenizer::{BertTokenizer, RobertaTokenizer};
+use crate::vocab::{BertVocab, RobertaVocab, Vocab};
+
+/// Bounding box of a word, given as `[x0, y0, x1, y1]` (usually normalized to a 0-1000 scale)
+pub type BoundingBox = [u32; 4];
+
+/// # Bounding boxes assigned to special tokens
+/// The default values follow LayoutLM/LayoutLMv2 (`[0, 0, 0, 0]` for the class and padding tokens,
+/// `[1000, 1000, 1000, 1000]` for the separator). LayoutLMv3 uses `[0, 0, 0, 0]` for all special
+/// tokens (see `SpecialTokenBoxes::zeros`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SpecialTokenBoxes {
+    /// Box of the leading special token (e.g. `[CLS]` or `<s>`)
+    pub cls: BoundingBox,
+    /// Box of the trailing special tokens (e.g. `[SEP]` or `</s>`)
+    pub sep: BoundingBox,
+    /// Box of padding tokens
+    pub pad: BoundingBox,
+}
+
+impl Default for SpecialTokenBoxes {
+    fn default() -> Self {
+        SpecialTokenBoxes {
+            cls: [0, 0, 0, 0],
+            sep: [1000, 1000, 1000, 1000],
+            pad: [0, 0, 0, 0],
+        }
+    }
+}
+
+impl SpecialTokenBoxes {
+    /// Special token boxes set to `[0, 0, 0, 0]` for all special tokens (LayoutLMv3 convention)
+    pub fn zeros() -> Self {
+        SpecialTokenBoxes {
+            cls: [0, 0, 0, 0],
+            sep: [0, 0, 0, 0],
+            pad: [0, 0, 0, 0],
+        }
+    }
+}
+
+/// # Tokenized input with bounding boxes
+/// Encoding output for document understanding models (LayoutLM family). All vectors are aligned
+/// with `tokenized_input.token_ids`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokenizedInputWithBoxes {
+    /// Encoding of the words joined by single spaces. Offsets refer to this joined text.
+    pub tokenized_input: TokenizedInput,
+
+    /// Bounding box for each token. Sub-tokens inherit the box of their word.
+    pub bbox: Vec<BoundingBox>,
+
+    /// Index of the word each token originates from (None for special and padding tokens)
+    pub word_ids: Vec<Option<usize>>,
+
+    /// Token labels, if word labels were provided. The word label is set on the first sub-token
+    /// of each word, all other positions are set to `LABEL_PAD_ID`.
+    pub labels: Option<Vec<i64>>,
+
+    /// Attention mask (1 for tokens, 0 for padding)
+    pub attention_mask: Vec<i8>,
+}
+
+impl TokenizedInputWithBoxes {
+    /// Pads the encoding to the given length. Inputs already longer than `length` are left unchanged.
+    ///
+    /// # Parameters
+    /// - length (`usize`): target length
+    /// - pad_token_id (`i64`): id of the padding token (see `LayoutTokenizer::pad_token_id`)
+    /// - pad_box (`BoundingBox`): bounding box of padding tokens
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use rust_tokenizers::tokenizer::{
+    ///     BertTokenizer, LayoutTokenizer, SpecialTokenBoxes, TruncationStrategy,
+    /// };
+    /// let tokenizer = BertTokenizer::from_file("path/to/vocab/file", true, true).unwrap();
+    /// let special_token_boxes = SpecialTokenBoxes::default();
+    ///
+    /// let mut encoded_input = tokenizer
+    ///     .encode_with_boxes(
+    ///         &["Invoice", "total"],
+    ///         &[[10, 10, 80, 20], [90, 10, 130, 20]],
+    ///         None,
+    ///         16,
+    ///         &TruncationStrategy::LongestFirst,
+    ///         0,
+    ///         &special_token_boxes,
+    ///     )
+    ///     .unwrap();
+    /// encoded_input.pad_to_length(16, tokenizer.pad_token_id(), special_token_boxes.pad);
+    /// ```
+    pub fn pad_to_length(&mut self, length: usize, pad_token_id: i64, pad_box: BoundingBox) {
+        let current_length = self.tokenized_input.token_ids.len();
+        if current_length >= length {
+            return;
+        }
+        let num_pad = length - current_length;
+        let input = &mut self.tokenized_input;
+        input.token_ids.extend(vec![pad_token_id; num_pad]);
+        input.segment_ids.extend(vec![0; num_pad]);
+        input.special_tokens_mask.extend(vec![1; num_pad]);
+        input.token_offsets.extend(vec![None; num_pad]);
+        input.reference_offsets.extend(vec![vec![]; num_pad]);
+        input.mask.extend(vec![Mask::Special; num_pad]);
+        self.bbox.extend(vec![pad_box; num_pad]);
+        self.word_ids.extend(vec![None; num_pad]);
+        if let Some(labels) = self.labels.as_mut() {
+            labels.extend(vec![LABEL_PAD_ID; num_pad]);
+        }
+        self.attention_mask.extend(vec![0; num_pad]);
+    }
+}
+
+/// # Bounding box aware encoding
+/// Extension of the `Tokenizer` trait for document understanding models (LayoutLM, LayoutLMv2,
+/// LayoutLMv3), where the input is a sequence of words, each carrying a bounding box.
+pub trait LayoutTokenizer<T: Vocab>: Tokenizer<T> {
+    /// Returns the id of the padding token
+    fn pad_token_id(&self) -> i64;
+
+    /// Encode a sequence of words with their bounding boxes. Sub-tokens inherit the box (and the
+    /// word index) of the word they originate from, special tokens are given the boxes provided in
+    /// `special_token_boxes`. If the sequence exceeds `max_len`, it is truncated and the remaining
+    /// tokens are reported as overflowing tokens.
+    ///
+    /// # Parameters
+    /// - words (`&[&str]`): words to encode
+    /// - boxes (`&[BoundingBox]`): bounding box for each word
+    /// - word_labels (`Option<&[i64]>`): optional label for each word
+    /// - max_len (`usize`): maximum sequence length, including special tokens
+    /// - truncation_strategy (`&TruncationStrategy`): strategy to follow for the truncation, if required
+    /// - stride (`usize`): number of tokens from the truncated sequence to repeat in the overflowing tokens
+    /// - special_token_boxes (`&SpecialTokenBoxes`): boxes assigned to special tokens
+    ///
+    /// # Returns
+    /// - `TokenizedInputWithBoxes` containing the encoding with aligned bounding boxes, word ids and labels.
+    ///   A `TokenizerError::ValueError` is returned if the number of boxes or labels does not match the number of words.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use rust_tokenizers::tokenizer::{
+    ///     BertTokenizer, LayoutTokenizer, SpecialTokenBoxes, TruncationStrategy,
+    /// };
+    /// let tokenizer = BertTokenizer::from_file("path/to/vocab/file", true, true).unwrap();
+    ///
+    /// let encoded_input = tokenizer
+    ///     .encode_with_boxes(
+    ///         &["Invoice", "total:", "$1,200"],
+    ///         &[[10, 10, 80, 20], [90, 10, 130, 20], [140, 10, 200, 20]],
+    ///         Some(&[0, 1, 2]),
+    ///         512,
+    ///         &TruncationStrategy::LongestFirst,
+    ///         0,
+    ///         &SpecialTokenBoxes::default(),
+    ///     )
+    ///     .unwrap();
+    /// ```
+    #[allow(clippy::too_many_arguments)]
+    fn encode_with_boxes(
+        &self,
+        words: &[&str],
+        boxes: &[BoundingBox],
+        word_labels: Option<&[i64]>,
+        max_len: usize,
+        truncation_strategy: &TruncationStrategy,
+        stride: usize,
+        special_token_boxes: &SpecialTokenBoxes,
+    ) -> Result<TokenizedInputWithBoxes, TokenizerError> {
+        check_word_inputs(words, boxes, word_labels)?;
+        let (token_ids_with_offsets, mut word_ids) = tokenize_words(self, words);
+
+        let num_special_tokens = num_special_tokens(self);
+        let total_len = token_ids_with_offsets.ids.len() + num_special_tokens;
+        let num_truncated_tokens = total_len.saturating_sub(max_len);
+        let num_tokens = token_ids_with_offsets.ids.len();
+        let (token_ids_with_offsets, _, overflowing_tokens, _) = truncate_sequences(
+            token_ids_with_offsets,
+            None,
+            num_truncated_tokens,
+            truncation_strategy,
+            stride,
+        )?;
+        if num_truncated_tokens > 0 {
+            word_ids.drain(truncation_range(
+                num_tokens,
+                num_truncated_tokens,
+                truncation_strategy,
+            ));
+        }
+
+        let mut output = build_input_with_boxes(
+            self,
+            token_ids_with_offsets,
+            word_ids,
+            boxes,
+            word_labels,
+            special_token_boxes,
+        );
+        output.tokenized_input.overflowing_tokens = overflowing_tokens;
+        output.tokenized_input.num_truncated_tokens = num_truncated_tokens;
+        Ok(output)
+    }
+
+    /// Encode a sequence of words with their bounding boxes into overlapping windows. Each window
+    /// contains at most `max_len` tokens (including special tokens), and consecutive windows share
+    /// `stride` tokens. Each window is a complete encoding with its own special tokens, boxes, word
+    /// ids and labels (the first sub-token of a word in a window carries the word label).
+    ///
+    /// # Parameters
+    /// - words (`&[&str]`): words to encode
+    /// - boxes (`&[BoundingBox]`): bounding box for each word
+    /// - word_labels (`Option<&[i64]>`): optional label for each word
+    /// - max_len (`usize`): maximum window length, including special tokens
+    /// - stride (`usize`): number of tokens shared by consecutive windows
+    /// - special_token_boxes (`&SpecialTokenBoxes`): boxes assigned to special tokens
+    ///
+    /// # Returns
+    /// - `Vec<TokenizedInputWithBoxes>` containing one encoding per window (at least one)
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use rust_tokenizers::tokenizer::{BertTokenizer, LayoutTokenizer, SpecialTokenBoxes};
+    /// let tokenizer = BertTokenizer::from_file("path/to/vocab/file", true, true).unwrap();
+    ///
+    /// let windows = tokenizer
+    ///     .encode_windows_with_boxes(
+    ///         &["Invoice", "total:", "$1,200"],
+    ///         &[[10, 10, 80, 20], [90, 10, 130, 20], [140, 10, 200, 20]],
+    ///         None,
+    ///         6,
+    ///         2,
+    ///         &SpecialTokenBoxes::default(),
+    ///     )
+    ///     .unwrap();
+    /// ```
+    fn encode_windows_with_boxes(
+        &self,
+        words: &[&str],
+        boxes: &[BoundingBox],
+        word_labels: Option<&[i64]>,
+        max_len: usize,
+        stride: usize,
+        special_token_boxes: &SpecialTokenBoxes,
+    ) -> Result<Vec<TokenizedInputWithBoxes>, TokenizerError> {
+        check_word_inputs(words, boxes, word_labels)?;
+        let (token_ids_with_offsets, word_ids) = tokenize_words(self, words);
+
+        let window_size = max_len.saturating_sub(num_special_tokens(self));
+        if window_size <= stride {
+            return Err(TokenizerError::ValueError(format!(
+                "Maximum length {max_len} leaves no room for windows with a stride of {stride}"
+            )));
+        }
+        let num_tokens = token_ids_with_offsets.ids.len();
+        let mut windows = vec![];
+        let mut start = 0;
+        loop {
+            let end = (start + window_size).min(num_tokens);
+            let window = TokenIdsWithOffsets {
+                ids: token_ids_with_offsets.ids[start..end].to_vec(),
+                offsets: token_ids_with_offsets.offsets[start..end].to_vec(),
+                reference_offsets: token_ids_with_offsets.reference_offsets[start..end].to_vec(),
+                masks: token_ids_with_offsets.masks[start..end].to_vec(),
+            };
+            windows.push(build_input_with_boxes(
+                self,
+                window,
+                word_ids[start..end].to_vec(),
+                boxes,
+                word_labels,
+                special_token_boxes,
+            ));
+            if end == num_tokens {
+                break;
+            }
+            start = end - stride;
+        }
+        Ok(windows)
+    }
+}
+
+impl LayoutTokenizer<BertVocab> for BertTokenizer {
+    fn pad_token_id(&self) -> i64 {
+        let vocab = Tokenizer::vocab(self);
+        vocab.token_to_id(vocab.get_pad_value())
+    }
+}
+
+impl LayoutTokenizer<RobertaVocab> for RobertaTokenizer {
+    fn pad_token_id(&self) -> i64 {
+        let vocab = Tokenizer::vocab(self);
+        vocab.token_to_id(vocab.get_pad_value())
+    }
+}
+
+fn check_word_inputs(
+    words: &[&str],
+    boxes: &[BoundingBox],
+    word_labels: Option<&[i64]>,
+) -> Result<(), TokenizerError> {
+    if boxes.len() != words.len() {
+        return Err(TokenizerError::ValueError(format!(
+            "Number of boxes ({}) does not match the number of words ({})",
+            boxes.len(),
+            words.len()
+        )));
+    }
+    if let Some(labels) = word_labels {
+        if labels.len() != words.len() {
+            return Err(TokenizerError::ValueError(format!(
+                "Number of labels ({}) does not match the number of words ({})",
+                labels.len(),
+                words.len()
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Tokenizes the words joined by single spaces and maps every token back to the word it
+/// originates from.
+fn tokenize_words<T: Vocab, U: Tokenizer<T> + ?Sized>(
+    tokenizer: &U,
+    words: &[&str],
+) -> (TokenIdsWithOffsets, Vec<Option<usize>>) {
+    let (text, word_offsets) = join_words(words);
+    let tokens = tokenizer.tokenize_with_offsets(&text);
+    let alignment = WordAlignment::from_word_offsets(&tokens.offsets, &tokens.masks, &word_offsets);
+    let token_ids_with_offsets = TokenIdsWithOffsets {
+        ids: tokenizer.convert_tokens_to_ids(&tokens.tokens),
+        offsets: tokens.offsets,
+        reference_offsets: tokens.reference_offsets,
+        masks: tokens.masks,
+    };
+    (token_ids_with_offsets, alignment.word_ids)
+}
+
+fn num_special_tokens<T: Vocab, U: Tokenizer<T> + ?Sized>(tokenizer: &U) -> usize {
+    tokenizer
+        .build_input_with_special_tokens(
+            TokenIdsWithOffsets {
+                ids: vec![],
+                offsets: vec![],
+                reference_offsets: vec![],
+                masks: vec![],
+            },
+            None,
+        )
+        .token_ids
+        .len()
+}
+
+fn build_input_with_boxes<T: Vocab, U: Tokenizer<T> + ?Sized>(
+    tokenizer: &U,
+    token_ids_with_offsets: TokenIdsWithOffsets,
+    word_ids: Vec<Option<usize>>,
+    boxes: &[BoundingBox],
+    word_labels: Option<&[i64]>,
+    special_token_boxes: &SpecialTokenBoxes,
+) -> TokenizedInputWithBoxes {
+    let merged_input = tokenizer.build_input_with_special_tokens(token_ids_with_offsets, None);
+
+    let mut content_word_ids = word_ids.into_iter();
+    let mut output_word_ids = Vec::with_capacity(merged_input.token_ids.len());
+    let mut bbox = Vec::with_capacity(merged_input.token_ids.len());
+    for (position, is_special) in merged_input.special_tokens_mask.iter().enumerate() {
+        if *is_special == 1 {
+            output_word_ids.push(None);
+            bbox.push(if position == 0 {
+                special_token_boxes.cls
+            } else {
+                special_token_boxes.sep
+            });
+        } else {
+            let word_id = content_word_ids.next().flatten();
+            output_word_ids.push(word_id);
+            bbox.push(word_id.map_or(special_token_boxes.pad, |word_id| boxes[word_id]));
+        }
+    }
+
+    let labels = word_labels.map(|word_labels| {
+        let mut previous_word_id = None;
+        output_word_ids
+            .iter()
+            .map(|word_id| {
+                let label = match word_id {
+                    Some(word_id) if previous_word_id != Some(*word_id) => word_labels[*word_id],
+                    _ => LABEL_PAD_ID,
+                };
+                previous_word_id = *word_id;
+                label
+            })
+            .collect()
+    });
+    let attention_mask = vec![1; merged_input.token_ids.len()];
+
+    TokenizedInputWithBoxes {
+        tokenized_input: TokenizedInput {
+            token_ids: merged_input.token_ids,
+            segment_ids: merged_input.segment_ids,
+            special_tokens_mask: merged_input.special_tokens_mask,
+            overflowing_tokens: vec![],
+            num_truncated_tokens: 0,
+            token_offsets: merged_input.token_offsets,
+            reference_offsets: merged_input.reference_offsets,
+            mask: merged_input.mask,
+        },
+        bbox,
+        word_ids: output_word_ids,
+        labels,
+        attention_mask,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vocab::base_vocab::{swap_key_values, SpecialTokenMap};
+    use crate::vocab::BpePairVocab;
+    use std::collections::HashMap;
+
+    fn generate_test_vocab() -> BertVocab {
+        let values: HashMap<String, i64> = [
+            ("hello".to_owned(), 0),
+            ("world".to_owned(), 1),
+            ("[UNK]".to_owned(), 2),
+            ("!".to_owned(), 3),
+            ("[CLS]".to_owned(), 4),
+            ("[SEP]".to_owned(), 5),
+            ("[MASK]".to_owned(), 6),
+            ("[PAD]".to_owned(), 7),
+            ("total".to_owned(), 8),
+            ("in".to_owned(), 9),
+            ("##voice".to_owned(), 10),
+            (":".to_owned(), 11),
+        ]
+        .iter()
+        .cloned()
+        .collect();
+
+        let special_values: HashMap<String, i64> = [
+            ("[UNK]".to_owned(), 2),
+            ("[CLS]".to_owned(), 4),
+            ("[SEP]".to_owned(), 5),
+            ("[MASK]".to_owned(), 6),
+            ("[PAD]".to_owned(), 7),
+        ]
+        .iter()
+        .cloned()
+        .collect();
+
+        let indices = swap_key_values(&values);
+        let special_indices = swap_key_values(&special_values);
+
+        BertVocab {
+            values,
+            indices,
+            special_token_map: SpecialTokenMap {
+                unk_token: "[UNK]".to_string(),
+                pad_token: Some("[PAD]".to_string()),
+                bos_token: None,
+                sep_token: Some("[SEP]".to_string()),
+                cls_token: Some("[CLS]".to_string()),
+                eos_token: None,
+                mask_token: Some("[MASK]".to_string()),
+                additional_special_tokens: None,
+            },
+            special_values,
+            special_indices,
+        }
+    }
+
+    const WORDS: [&str; 3] = ["Invoice", "total:", "hello"];
+    const BOXES: [BoundingBox; 3] = [[1, 1, 2, 2], [3, 3, 4, 4], [5, 5, 6, 6]];
+
+    #[test]
+    fn test_encode_with_boxes() -> anyhow::Result<()> {
+        //        Given
+        let tokenizer = BertTokenizer::from_existing_vocab(generate_test_vocab(), true, true);
+        let special_token_boxes = SpecialTokenBoxes::default();
+
+        //        When
+        let output = tokenizer.encode_with_boxes(
+            &WORDS,
+            &BOXES,
+            Some(&[1, 2, 3]),
+            128,
+            &TruncationStrategy::LongestFirst,
+            0,
+            &special_token_boxes,
+        )?;
+
+        //        Then
+        assert_eq!(
+            output.tokenized_input.token_ids,
+            vec![4, 9, 10, 8, 11, 0, 5]
+        );
+        assert_eq!(
+            output.bbox,
+            vec![
+                [0, 0, 0, 0],
+                [1, 1, 2, 2],
+                [1, 1, 2, 2],
+                [3, 3, 4, 4],
+                [3, 3, 4, 4],
+                [5, 5, 6, 6],
+                [1000, 1000, 1000, 1000]
+            ]
+        );
+        assert_eq!(
+            output.word_ids,
+            vec![None, Some(0), Some(0), Some(1), Some(1), Some(2), None]
+        );
+        assert_eq!(output.labels, Some(vec![-100, 1, -100, 2, -100, 3, -100]));
+        assert_eq!(output.attention_mask, vec![1; 7]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_encode_with_boxes_truncation_and_padding() -> anyhow::Result<()> {
+        //        Given
+        let tokenizer = BertTokenizer::from_existing_vocab(generate_test_vocab(), true, true);
+        let special_token_boxes = SpecialTokenBoxes::zeros();
+
+        //        When
+        let mut output = tokenizer.encode_with_boxes(
+            &WORDS,
+            &BOXES,
+            Some(&[1, 2, 3]),
+            5,
+            &TruncationStrategy::LongestFirst,
+            0,
+            &special_token_boxes,
+        )?;
+        output.pad_to_length(7, tokenizer.pad_token_id(), special_token_boxes.pad);
+
+        //        Then
+        assert_eq!(output.tokenized_input.token_ids, vec![4, 9, 10, 8, 5, 7, 7]);
+        assert_eq!(output.tokenized_input.overflowing_tokens, vec![11, 0]);
+        assert_eq!(output.tokenized_input.num_truncated_tokens, 2);
+        assert_eq!(
+            output.bbox,
+            vec![
+                [0, 0, 0, 0],
+                [1, 1, 2, 2],
+                [1, 1, 2, 2],
+                [3, 3, 4, 4],
+                [0, 0, 0, 0],
+                [0, 0, 0, 0],
+                [0, 0, 0, 0]
+            ]
+        );
+        assert_eq!(
+            output.labels,
+            Some(vec![-100, 1, -100, 2, -100, -100, -100])
+        );
+        assert_eq!(output.attention_mask, vec![1, 1, 1, 1, 1, 0, 0]);
+        assert_eq!(output.tokenized_input.segment_ids.len(), 7);
+        assert_eq!(output.tokenized_input.mask.len(), 7);
+        Ok(())
+    }
+
+    #[test]
+    fn test_encode_windows_with_boxes() -> anyhow::Result<()> {
+        //        Given
+        let tokenizer = BertTokenizer::from_existing_vocab(generate_test_vocab(), true, true);
+        let special_token_boxes = SpecialTokenBoxes::default();
+
+        //        When
+        let windows = tokenizer.encode_windows_with_boxes(
+            &WORDS,
+            &BOXES,
+            Some(&[1, 2, 3]),
+            5,
+            1,
+            &special_token_boxes,
+        )?;
+
+        //        Then
+        let token_ids: Vec<Vec<i64>> = windows
+            .iter()
+            .map(|window| window.tokenized_input.token_ids.clone())
+            .collect();
+        assert_eq!(token_ids, vec![vec![4, 9, 10, 8, 5], vec![4, 8, 11, 0, 5]]);
+        assert_eq!(
+            windows[1].word_ids,
+            vec![None, Some(1), Some(1), Some(2), None]
+        );
+        assert_eq!(windows[1].labels, Some(vec![-100, 2, -100, 3, -100]));
+        assert_eq!(windows[1].bbox[1], [3, 3, 4, 4]);
+        assert!(tokenizer
+            .encode_windows_with_boxes(&WORDS, &BOXES, None, 3, 1, &special_token_boxes)
+            .is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_encode_with_boxes_roberta() -> anyhow::Result<()> {
+        //        Given
+        let values: HashMap<String, i64> = [
+            ("<s>".to_owned(), 0),
+            ("<pad>".to_owned(), 1),
+            ("</s>".to_owned(), 2),
+            ("<unk>".to_owned(), 3),
+            ("hello".to_owned(), 4),
+            ("Ġworld".to_owned(), 5),
+            ("Ġwor".to_owned(), 6),
+            ("ld".to_owned(), 7),
+        ]
+        .iter()
+        .cloned()
+        .collect();
+        let vocab = RobertaVocab::from_values_and_special_token_map(
+            values,
+            SpecialTokenMap {
+                unk_token: "<unk>".to_string(),
+                pad_token: Some("<pad>".to_string()),
+                bos_token: Some("<s>".to_string()),
+                sep_token: Some("</s>".to_string()),
+                cls_token: Some("<s>".to_string()),
+                eos_token: Some("</s>".to_string()),
+                mask_token: None,
+                additional_special_tokens: None,
+            },
+        )?;
+        let merges = BpePairVocab {
+            values: [
+                (("h".to_owned(), "e".to_owned()), 0),
+                (("l".to_owned(), "l".to_owned()), 1),
+                (("he".to_owned(), "ll".to_owned()), 2),
+                (("hell".to_owned(), "o".to_owned()), 3),
+                (("Ġ".to_owned(), "w".to_owned()), 4),
+                (("Ġw".to_owned(), "o".to_owned()), 5),
+                (("Ġwo".to_owned(), "r".to_owned()), 6),
+                (("l".to_owned(), "d".to_owned()), 7),
+                (("Ġwor".to_owned(), "ld".to_owned()), 8),
+            ]
+            .iter()
+            .cloned()
+            .collect(),
+        };
+        let tokenizer =
+            RobertaTokenizer::from_existing_vocab_and_merges(vocab, merges, false, false);
+
+        //        When
+        let output = tokenizer.encode_with_boxes(
+            &["hello", "world"],
+            &BOXES[..2],
+            None,
+            128,
+            &TruncationStrategy::LongestFirst,
+            0,
+            &SpecialTokenBoxes::zeros(),
+        )?;
+
+        //        Then
+        assert_eq!(output.tokenized_input.token_ids, vec![0, 4, 5, 2]);
+        assert_eq!(output.word_ids, vec![None, Some(0), Some(1), None]);
+        assert_eq!(
+            output.bbox,
+            vec![[0, 0, 0, 0], [1, 1, 2, 2], [3, 3, 4, 4], [0, 0, 0, 0]]
+        );
+        assert_eq!(output.labels, None);
+        assert_eq!(tokenizer.pad_token_id(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_encode_with_boxes_length_mismatch() {
+        //        Given
+        let tokenizer = BertTokenizer::from_existing_vocab(generate_test_vocab(), true, true);
+
+        //        When
+        let wrong_boxes = tokenizer.encode_with_boxes(
+            &WORDS,
+            &BOXES[..2],
+            None,
+            128,
+            &TruncationStrategy::LongestFirst,
+            0,
+            &SpecialTokenBoxes::default(),
+        );
+        let wrong_labels = tokenizer.encode_with_boxes(
+            &WORDS,
+            &BOXES,
+            Some(&[1]),
+            128,
+            &TruncationStrategy::LongestFirst,
+            0,
+            &SpecialTokenBoxes::default(),
+        );
+
+        //        Then
+        assert!(wrong_boxes.is_err());
+        assert!(wrong_labels.is_err());
+    }
+}
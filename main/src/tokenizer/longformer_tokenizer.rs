@@ -0,0 +1,258 @@
+// Copyright 2020 The Allen Institute for AI team and The HuggingFace Inc. team.
+// Copyright 2019-2020 Guillaume Becquin
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//     http://www.apache.org/licenses/LICENSE-2.0
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::error::TokenizerError;
+use crate::tokenizer::base_tokenizer::{
+    Mask, Offset, OffsetSize, Token, TokenIdsWithOffsets, TokenIdsWithSpecialTokens, TokenRef,
+    Tokenizer,
+};
+use crate::tokenizer::tokenization_utils::lstrip_special_token;
+use crate::tokenizer::{MultiThreadedTokenizer, RobertaTokenizer};
+use crate::vocab::bpe_vocab::BpePairVocab;
+use crate::vocab::{LongformerVocab, RobertaVocab, Vocab};
+use std::path::Path;
+
+/// # Longformer tokenizer
+/// Longformer tokenizer, relying on a `RobertaTokenizer` (byte-level BPE tokenization) for the
+/// tokenization. The `<mask>` token absorbs the whitespace preceding it.
+///
+/// Sequence pairs are encoded as `<s> A </s></s> B </s>`, with a segment id of 0 for all tokens.
+pub struct LongformerTokenizer {
+    vocab: LongformerVocab,
+    roberta_tokenizer: RobertaTokenizer,
+}
+
+impl LongformerTokenizer {
+    /// Create a new instance of a `LongformerTokenizer`
+    /// Expects a vocabulary json file and a merges file as an input.
+    ///
+    /// # Parameters
+    /// - vocab_path (`&str`): path to the vocabulary file
+    /// - merges_path (`&str`): path to the merges file (use as part of the BPE encoding process)
+    /// - lower_case (`bool`): flag indicating if the text should be lower-cased as part of the tokenization
+    /// - add_prefix_space (`bool`): flag indicating if a space should be added before the text
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use rust_tokenizers::tokenizer::{LongformerTokenizer, Tokenizer};
+    /// let lower_case = false;
+    /// let add_prefix_space = true;
+    /// let tokenizer = LongformerTokenizer::from_file(
+    ///     "path/to/vocab/file",
+    ///     "path/to/merges/file",
+    ///     lower_case,
+    ///     add_prefix_space,
+    /// )
+    /// .unwrap();
+    /// ```
+    pub fn from_file<P: AsRef<Path>, M: AsRef<Path>>(
+        vocab_path: P,
+        merges_path: M,
+        lower_case: bool,
+        add_prefix_space: bool,
+    ) -> Result<LongformerTokenizer, TokenizerError> {
+        let vocab = LongformerVocab::from_file(vocab_path)?;
+        let merges = BpePairVocab::from_file(merges_path)?;
+        Ok(LongformerTokenizer::from_existing_vocab_and_merges(
+            vocab,
+            merges,
+            lower_case,
+            add_prefix_space,
+        ))
+    }
+
+    /// Create a new instance of a `LongformerTokenizer`
+    /// Expects a vocabulary json file and a merges file and special token mapping file as inputs.
+    ///
+    /// # Parameters
+    /// - vocab_path (`&str`): path to the vocabulary file
+    /// - merges_path (`&str`): path to the merges file (use as part of the BPE encoding process)
+    /// - lower_case (`bool`): flag indicating if the text should be lower-cased as part of the tokenization
+    /// - add_prefix_space (`bool`): flag indicating if a space should be added before the text
+    /// - special_token_mapping_path (`&str`): path to a special token mapping file to overwrite default special tokens
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use rust_tokenizers::tokenizer::{LongformerTokenizer, Tokenizer};
+    ///
+    /// let lower_case = false;
+    /// let add_prefix_space = true;
+    /// let tokenizer = LongformerTokenizer::from_file_with_special_token_mapping(
+    ///     "path/to/vocab/file",
+    ///     "path/to/merges/file",
+    ///     lower_case,
+    ///     add_prefix_space,
+    ///     "path/to/special/token/mapping/file",
+    /// )
+    /// .unwrap();
+    /// ```
+    pub fn from_file_with_special_token_mapping<V: AsRef<Path>, M: AsRef<Path>, S: AsRef<Path>>(
+        vocab_path: V,
+        merges_path: M,
+        lower_case: bool,
+        add_prefix_space: bool,
+        special_token_mapping_path: S,
+    ) -> Result<LongformerTokenizer, TokenizerError> {
+        let vocab = LongformerVocab::from_file_with_special_token_mapping(
+            vocab_path,
+            special_token_mapping_path,
+        )?;
+        let merges = BpePairVocab::from_file(merges_path)?;
+        Ok(LongformerTokenizer::from_existing_vocab_and_merges(
+            vocab,
+            merges,
+            lower_case,
+            add_prefix_space,
+        ))
+    }
+
+    /// Create a new instance of a `LongformerTokenizer` from an existing vocabulary and merges
+    ///
+    /// # Parameters
+    /// - vocab (`LongformerVocab`): Longformer vocabulary
+    /// - merges (`BpePairVocab`): BPE pairs vocabulary
+    /// - lower_case (`bool`): flag indicating if the text should be lower-cased as part of the tokenization
+    /// - add_prefix_space (`bool`): flag indicating if a space should be added before the text
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use rust_tokenizers::tokenizer::{LongformerTokenizer, Tokenizer};
+    /// use rust_tokenizers::vocab::{BpePairVocab, LongformerVocab, Vocab};
+    /// let lower_case = false;
+    /// let add_prefix_space = true;
+    /// let vocab = LongformerVocab::from_file("path/to/vocab/file").unwrap();
+    /// let merges = BpePairVocab::from_file("path/to/merges/file").unwrap();
+    ///
+    /// let tokenizer = LongformerTokenizer::from_existing_vocab_and_merges(
+    ///     vocab,
+    ///     merges,
+    ///     lower_case,
+    ///     add_prefix_space,
+    /// );
+    /// ```
+    pub fn from_existing_vocab_and_merges(
+        vocab: LongformerVocab,
+        merges: BpePairVocab,
+        lower_case: bool,
+        add_prefix_space: bool,
+    ) -> LongformerTokenizer {
+        let roberta_vocab = RobertaVocab {
+            values: vocab.values.clone(),
+            indices: vocab.indices.clone(),
+            special_token_map: vocab.special_token_map.clone(),
+            special_values: vocab.special_values.clone(),
+            special_indices: vocab.special_indices.clone(),
+        };
+        LongformerTokenizer {
+            vocab,
+            roberta_tokenizer: RobertaTokenizer::from_existing_vocab_and_merges(
+                roberta_vocab,
+                merges,
+                lower_case,
+                add_prefix_space,
+            ),
+        }
+    }
+}
+
+impl Tokenizer<LongformerVocab> for LongformerTokenizer {
+    fn vocab(&self) -> &LongformerVocab {
+        &self.vocab
+    }
+    fn vocab_mut(&mut self) -> &mut LongformerVocab {
+        &mut self.vocab
+    }
+
+    fn add_tokens(&mut self, tokens: &[&str]) {
+        self.vocab.add_tokens(tokens);
+        self.roberta_tokenizer.add_tokens(tokens);
+    }
+
+    fn add_extra_ids(&mut self, num_extra_ids: i64) {
+        self.vocab.add_extra_ids(num_extra_ids);
+        self.roberta_tokenizer.add_extra_ids(num_extra_ids);
+    }
+
+    fn tokenize_to_tokens(&self, initial_token: TokenRef) -> Vec<Token> {
+        let mut tokens = self.roberta_tokenizer.tokenize_to_tokens(initial_token);
+        //the mask token absorbs the whitespace preceding it
+        lstrip_special_token(initial_token, &mut tokens, self.vocab.get_mask_value());
+        tokens
+    }
+
+    fn convert_tokens_to_string(&self, tokens: Vec<String>) -> String {
+        self.roberta_tokenizer.convert_tokens_to_string(tokens)
+    }
+
+    fn build_input_with_special_tokens(
+        &self,
+        tokens_ids_with_offsets_1: TokenIdsWithOffsets,
+        tokens_ids_with_offsets_2: Option<TokenIdsWithOffsets>,
+    ) -> TokenIdsWithSpecialTokens {
+        let vocab = &self.vocab;
+        let mut output: Vec<i64> = vec![];
+        let mut token_segment_ids: Vec<i8> = vec![];
+        let mut special_tokens_mask: Vec<i8> = vec![];
+        let mut offsets: Vec<Option<Offset>> = vec![];
+        let mut original_offsets: Vec<Vec<OffsetSize>> = vec![];
+        let mut mask: Vec<Mask> = vec![];
+        special_tokens_mask.push(1);
+        special_tokens_mask.extend(vec![0; tokens_ids_with_offsets_1.ids.len()]);
+        special_tokens_mask.push(1);
+        token_segment_ids.extend(vec![0; tokens_ids_with_offsets_1.ids.len() + 2]);
+        output.push(vocab.token_to_id(vocab.get_cls_value()));
+        output.extend(tokens_ids_with_offsets_1.ids);
+        output.push(vocab.token_to_id(vocab.get_sep_value()));
+        offsets.push(None);
+        offsets.extend(tokens_ids_with_offsets_1.offsets);
+        offsets.push(None);
+        original_offsets.push(vec![]);
+        original_offsets.extend(tokens_ids_with_offsets_1.reference_offsets);
+        original_offsets.push(vec![]);
+        mask.push(Mask::Special);
+        mask.extend(tokens_ids_with_offsets_1.masks);
+        mask.push(Mask::Special);
+        if let Some(tokens_ids_with_offsets_2_value) = tokens_ids_with_offsets_2 {
+            // The second sequence is preceded by a double separator and shares the segment id 0
+            let length = tokens_ids_with_offsets_2_value.ids.len();
+            special_tokens_mask.push(1);
+            special_tokens_mask.extend(vec![0; length]);
+            special_tokens_mask.push(1);
+            token_segment_ids.extend(vec![0; length + 2]);
+            output.push(vocab.token_to_id(vocab.get_sep_value()));
+            output.extend(tokens_ids_with_offsets_2_value.ids);
+            output.push(vocab.token_to_id(vocab.get_sep_value()));
+            offsets.push(None);
+            offsets.extend(tokens_ids_with_offsets_2_value.offsets);
+            offsets.push(None);
+            original_offsets.push(vec![]);
+            original_offsets.extend(tokens_ids_with_offsets_2_value.reference_offsets);
+            original_offsets.push(vec![]);
+            mask.push(Mask::Special);
+            mask.extend(tokens_ids_with_offsets_2_value.masks);
+            mask.push(Mask::Special);
+        }
+        TokenIdsWithSpecialTokens {
+            token_ids: output,
+            segment_ids: token_segment_ids,
+            special_tokens_mask,
+            token_offsets: offsets,
+            reference_offsets: original_offsets,
+            mask,
+        }
+    }
+}
+
+impl MultiThreadedTokenizer<LongformerVocab> for LongformerTokenizer {}
@@ -10,6 +10,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashSet;
 use std::path::Path;
 
 use crate::error::TokenizerError;
@@ -17,10 +18,11 @@ use crate::tokenizer::base_tokenizer::{
     Mask, Offset, OffsetSize, Token, TokenIdsWithOffsets, TokenIdsWithSpecialTokens, TokenRef,
 };
 use crate::tokenizer::tokenization_utils::{
-    clean_text, decompose_nfkc, is_whitespace, lowercase, split_on_language_code,
+    clean_text, decompose_nfkc, is_whitespace, language_codes_bytes, lowercase,
+    required_special_token_id, split_on_language_code,
 };
 use crate::tokenizer::{MultiThreadedTokenizer, Tokenizer};
-use crate::vocab::{M2M100Vocab, SentencePieceBpeModel, Vocab};
+use crate::vocab::{M2M100Vocab, SentencePieceBpeModel, SpecialTokenValues, Vocab};
 
 /// # M2M100 tokenizer
 /// M2M100 tokenizer performing:
@@ -29,11 +31,16 @@ use crate::vocab::{M2M100Vocab, SentencePieceBpeModel, Vocab};
 /// - NFKC decomposition
 /// - (optional) lower casing
 /// - SentencePiece decomposition
+///
+/// The tokenizer is generic over its vocabulary, allowing the use of a memory-mapped `MmapVocab`
+/// compiled from a `M2M100Vocab`.
 #[allow(clippy::upper_case_acronyms)]
-pub struct M2M100Tokenizer {
+pub struct M2M100Tokenizer<V = M2M100Vocab> {
     model: SentencePieceBpeModel,
-    vocab: M2M100Vocab,
+    vocab: V,
+    language_codes_bytes: HashSet<Vec<u8>>,
     lower_case: bool,
+    eos_id: i64,
 }
 
 impl M2M100Tokenizer {
@@ -64,12 +71,7 @@ impl M2M100Tokenizer {
     ) -> Result<M2M100Tokenizer, TokenizerError> {
         let vocab = M2M100Vocab::from_file(vocab_path)?;
         let model = SentencePieceBpeModel::from_file(model_path)?;
-
-        Ok(M2M100Tokenizer {
-            model,
-            vocab,
-            lower_case,
-        })
+        M2M100Tokenizer::from_existing_vocab_and_model(vocab, model, lower_case)
     }
 
     /// Create a new instance of a `M2M100Tokenizer`
@@ -105,18 +107,17 @@ impl M2M100Tokenizer {
             special_token_mapping_path,
         )?;
         let model = SentencePieceBpeModel::from_file(model_path)?;
-
-        Ok(M2M100Tokenizer {
-            model,
-            vocab,
-            lower_case,
-        })
+        M2M100Tokenizer::from_existing_vocab_and_model(vocab, model, lower_case)
     }
+}
 
-    /// Create a new instance of a `M2M100Tokenizer` from an existing vocabulary and model
+impl<V: SpecialTokenValues> M2M100Tokenizer<V> {
+    /// Create a new instance of a `M2M100Tokenizer` from an existing vocabulary and model.
+    /// Returns a `TokenizerError::TokenNotFound` if the vocabulary does not contain the end of
+    /// sequence token.
     ///
     /// # Parameters
-    /// - vocab (`M2M100Vocab`): vocabulary
+    /// - vocab (`M2M100Vocab` or `MmapVocab`): vocabulary
     /// - model (`SentencePieceBpeModel`): SentencePiece BPE model
     /// - lower_case (`bool`): flag indicating if the text should be lower-cased as part of the tokenization
     ///
@@ -129,31 +130,36 @@ impl M2M100Tokenizer {
     /// let vocab = M2M100Vocab::from_file("path/to/vocab/file").unwrap();
     /// let model = SentencePieceBpeModel::from_file("path/to/model/file").unwrap();
     ///
-    /// let tokenizer = M2M100Tokenizer::from_existing_vocab_and_model(vocab, model, lower_case);
+    /// let tokenizer =
+    ///     M2M100Tokenizer::from_existing_vocab_and_model(vocab, model, lower_case).unwrap();
     /// ```
     pub fn from_existing_vocab_and_model(
-        vocab: M2M100Vocab,
+        vocab: V,
         model: SentencePieceBpeModel,
         lower_case: bool,
-    ) -> M2M100Tokenizer {
-        M2M100Tokenizer {
+    ) -> Result<M2M100Tokenizer<V>, TokenizerError> {
+        let eos_id = required_special_token_id(&vocab, vocab.eos_value(), "end of sequence")?;
+        let language_codes_bytes = language_codes_bytes(&vocab, 7);
+        Ok(M2M100Tokenizer {
             model,
             vocab,
+            language_codes_bytes,
             lower_case,
-        }
+            eos_id,
+        })
     }
 }
 
-impl Tokenizer<M2M100Vocab> for M2M100Tokenizer {
-    fn vocab(&self) -> &M2M100Vocab {
+impl<V: SpecialTokenValues> Tokenizer<V> for M2M100Tokenizer<V> {
+    fn vocab(&self) -> &V {
         &self.vocab
     }
-    fn vocab_mut(&mut self) -> &mut M2M100Vocab {
+    fn vocab_mut(&mut self) -> &mut V {
         &mut self.vocab
     }
 
     fn tokenize_to_tokens(&self, text: TokenRef) -> Vec<Token> {
-        let tokens = split_on_language_code(text, 7, &self.vocab.language_codes_bytes);
+        let tokens = split_on_language_code(text, 7, &self.language_codes_bytes);
         let (code_token, mut token) = match tokens.len() {
             0 => {
                 return vec![];
@@ -237,7 +243,7 @@ impl Tokenizer<M2M100Vocab> for M2M100Tokenizer {
             token_segment_ids.push(0);
         }
         special_tokens_mask.push(1);
-        output.push(self.vocab.token_to_id(self.vocab.get_eos_value()));
+        output.push(self.eos_id);
         offsets.push(None);
         original_offsets.push(vec![]);
         mask.push(Mask::Special);
@@ -253,4 +259,4 @@ impl Tokenizer<M2M100Vocab> for M2M100Tokenizer {
     }
 }
 
-impl MultiThreadedTokenizer<M2M100Vocab> for M2M100Tokenizer {}
+impl<V: SpecialTokenValues + Send + Sync> MultiThreadedTokenizer<V> for M2M100Tokenizer<V> {}
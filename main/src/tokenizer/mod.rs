@@ -13,16 +13,23 @@
 //!
 //! This module contains the tokenizers to split an input text in a sequence of tokens.
 //! These rely on the vocabularies for defining the subtokens a given word should be decomposed to.
-//! There are 3 main classes of tokenizers implemented in this crate:
+//! There are 4 main classes of tokenizers implemented in this crate:
 //! - WordPiece tokenizers
 //!     - BERT
 //!     - DistilBERT
+//!     - MPNet
 //! - Byte-Pair Encoding tokenizers:
 //!     - GPT
 //!     - GPT2
 //!     - RoBERTa
+//!     - Longformer
 //!     - CTRL
 //!     - DeBERTa
+//!     - CLIP
+//!     - Whisper
+//!     - XLM (and FlauBERT)
+//!     - PhoBERT
+//!     - Byte-level BPE families (GPT-NeoX, Pythia, BLOOM, StarCoder, Falcon)
 //! - SentencePiece (Unigram) tokenizers:
 //!     - SentencePiece
 //!     - ALBERT
@@ -32,54 +39,143 @@
 //!     - Marian
 //!     - Reformer
 //!     - DeBERTa (v2)
+//!     - BigBird
+//! - Byte and character-level tokenizers (no vocabulary file required):
+//!     - ByT5
+//!     - CANINE
+//! - Character-level CTC tokenizers (speech recognition):
+//!     - Wav2Vec2
+//! - Biological sequence tokenizers:
+//!     - ESM (protein residues)
+//!     - K-mer (DNABERT nucleotide k-mers)
+//!
+//! The XLM and PhoBERT tokenizers rely on `MosesPreTokenizer`, a Rust implementation of the Moses
+//! tokenizer that can also be used on its own. Japanese and Chinese text can be segmented into words
+//! with a `DictionarySegmenter` (Viterbi search over a MeCab or jieba lexicon), which can be set as the
+//! pre-tokenization step of the BERT tokenizer.
+//!
+//! The BERT and RoBERTa tokenizers implement `LayoutTokenizer`, encoding words with bounding boxes
+//! for document understanding models (LayoutLM family).
+//!
+//! Conversations can be rendered into prompts for instruction-tuned models with a `ChatTemplate`,
+//! which evaluates the Jinja chat templates of `tokenizer_config.json` files and encodes the
+//! prompt with any tokenizer, masking the tokens generated by the assistant.
+//!
+//! Word labels for token classification (e.g. NER tags) are expanded to sub-tokens with
+//! `align_labels`, using the `WordAlignment` of an encoding. Predicted token labels can be mapped
+//! back to words and to entity character spans in the original text.
+//!
+//! Offsets are expressed in Unicode code points. They can be converted to UTF-8 bytes (to slice a
+//! Rust `&str`) or UTF-16 code units (to slice a JavaScript string) with `tokenize_with_offsets_in`,
+//! `TokenizedInput::into_offset_unit` or an `OffsetConverter`.
+//!
+//! Questions and contexts are encoded for extractive question answering with `encode_qa`, splitting
+//! long contexts into overlapping windows that answer spans are mapped to. The start and end logits
+//! predicted for these windows are decoded back to answers in the context with `decode_qa_answers`.
+//!
+//! Large corpora can be encoded for pretraining with `encode_to_shards`, which streams text or JSON
+//! lines documents through a multi-threaded tokenizer and writes the token ids to fixed-size binary
+//! shards (`TokenShardWriter`) with an index of the document boundaries.
 //!
 //! All tokenizers are `Send`, `Sync` and support multi-threaded tokenization and encoding.
 
 mod albert_tokenizer;
 pub(crate) mod base_tokenizer;
 mod bert_tokenizer;
+mod bigbird_tokenizer;
+mod byt5_tokenizer;
+mod byte_level_bpe_tokenizer;
+mod canine_tokenizer;
+mod chat_template;
+mod clip_tokenizer;
 mod constants;
 mod ctrl_tokenizer;
 mod deberta_tokenizer;
 mod deberta_v2_tokenizer;
+mod dictionary_segmenter;
+mod esm_tokenizer;
 mod fnet_tokenizer;
 mod gpt2_tokenizer;
+mod kmer_tokenizer;
+mod label_alignment;
+mod layout_tokenizer;
+mod longformer_tokenizer;
 mod m2m100_tokenizer;
 mod marian_tokenizer;
 mod mbart50_tokenizer;
+mod moses_pre_tokenizer;
+mod mpnet_tokenizer;
 mod nllb_tokenizer;
+mod offset_units;
 mod openai_gpt_tokenizer;
 mod pegasus_tokenizer;
+mod phobert_tokenizer;
 mod prophetnet_tokenizer;
+mod question_answering;
 mod reformer_tokenizer;
 mod roberta_tokenizer;
 mod sentence_piece_bpe_tokenizer;
 mod sentence_piece_tokenizer;
 mod t5_tokenizer;
+mod token_shards;
 pub(crate) mod tokenization_utils;
+mod wav2vec2_tokenizer;
+mod whisper_tokenizer;
 mod xlm_roberta_tokenizer;
+mod xlm_tokenizer;
 mod xlnet_tokenizer;
 
 pub use albert_tokenizer::AlbertTokenizer;
-pub use base_tokenizer::{BaseTokenizer, MultiThreadedTokenizer, Tokenizer, TruncationStrategy};
+pub use base_tokenizer::{
+    BaseTokenizer, MultiThreadedTokenizer, Tokenizer, TruncationSide, TruncationStrategy,
+};
 pub use bert_tokenizer::BertTokenizer;
+pub use bigbird_tokenizer::BigBirdTokenizer;
+pub use byt5_tokenizer::ByT5Tokenizer;
+pub use byte_level_bpe_tokenizer::{ByteLevelBpePreset, ByteLevelBpeTokenizer};
+pub use canine_tokenizer::CanineTokenizer;
+pub use chat_template::{ChatMessage, ChatTemplate, EncodedChat, RenderedChat};
+pub use clip_tokenizer::ClipTokenizer;
 pub use ctrl_tokenizer::CtrlTokenizer;
 pub use deberta_tokenizer::DeBERTaTokenizer;
 pub use deberta_v2_tokenizer::DeBERTaV2Tokenizer;
+pub use dictionary_segmenter::{DictionarySegmenter, LexiconFormat};
+pub use esm_tokenizer::EsmTokenizer;
 pub use fnet_tokenizer::FNetTokenizer;
 pub use gpt2_tokenizer::Gpt2Tokenizer;
+pub use kmer_tokenizer::KmerTokenizer;
+pub use label_alignment::{
+    align_labels, join_words, labels_to_ids, token_labels_to_word_labels, word_labels_to_spans,
+    EntitySpan, LabelAlignment, WordAlignment, LABEL_PAD_ID,
+};
+pub use layout_tokenizer::{
+    BoundingBox, LayoutTokenizer, SpecialTokenBoxes, TokenizedInputWithBoxes,
+};
+pub use longformer_tokenizer::LongformerTokenizer;
 pub use m2m100_tokenizer::M2M100Tokenizer;
 pub use marian_tokenizer::MarianTokenizer;
 pub use mbart50_tokenizer::MBart50Tokenizer;
+pub use moses_pre_tokenizer::MosesPreTokenizer;
+pub use mpnet_tokenizer::MPNetTokenizer;
 pub use nllb_tokenizer::NLLBTokenizer;
+pub use offset_units::{OffsetConverter, OffsetUnit};
 pub use openai_gpt_tokenizer::OpenAiGptTokenizer;
 pub use pegasus_tokenizer::PegasusTokenizer;
+pub use phobert_tokenizer::PhoBertTokenizer;
 pub use prophetnet_tokenizer::ProphetNetTokenizer;
+pub use question_answering::{decode_qa_answers, encode_qa, QaAnswer, QaWindow};
 pub use reformer_tokenizer::ReformerTokenizer;
 pub use roberta_tokenizer::RobertaTokenizer;
 pub use sentence_piece_bpe_tokenizer::SentencePieceBpeTokenizer;
 pub use sentence_piece_tokenizer::SentencePieceTokenizer;
 pub use t5_tokenizer::T5Tokenizer;
+pub use token_shards::{
+    encode_to_shards, read_documents, DocumentFormat, ShardIndex, ShardInfo, TokenDtype,
+    TokenShardWriter,
+};
 pub use tokenization_utils::truncate_sequences;
+pub use wav2vec2_tokenizer::{CtcCharOffset, CtcDecoding, CtcWordOffset, Wav2Vec2CtcTokenizer};
+pub use whisper_tokenizer::{WhisperTask, WhisperTokenizer};
 pub use xlm_roberta_tokenizer::XLMRobertaTokenizer;
+pub use xlm_tokenizer::XLMTokenizer;
 pub use xlnet_tokenizer::XLNetTokenizer;
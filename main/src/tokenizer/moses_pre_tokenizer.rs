@@ -0,0 +1,643 @@
+// Copyright 2016-2019 Philipp Koehn, Liling Tan and the Moses contributors.
+// Copyright 2019-2020 Guillaume Becquin
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//     http://www.apache.org/licenses/LICENSE-2.0
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::tokenizer::base_tokenizer::{Mask, Offset, OffsetSize, Token, TokenRef};
+use regex::Regex;
+use std::collections::HashSet;
+use unicode_normalization::char::is_combining_mark;
+
+/// Non-breaking prefixes: words that are usually followed by a period that does not end a sentence
+const NON_BREAKING_PREFIXES_EN: &[&str] = &[
+    "Adj", "Adm", "Adv", "Asst", "Bart", "Bldg", "Brig", "Bros", "Capt", "Cmdr", "Col", "Comdr",
+    "Con", "Corp", "Cpl", "DR", "Dr", "Drs", "Ens", "Gen", "Gov", "Hon", "Hr", "Hosp", "Insp",
+    "Lt", "MM", "MR", "MRS", "MS", "Maj", "Messrs", "Mlle", "Mme", "Mr", "Mrs", "Ms", "Msgr", "Op",
+    "Ord", "Pfc", "Ph", "Prof", "Pvt", "Rep", "Reps", "Res", "Rev", "Rt", "Sen", "Sens", "Sfc",
+    "Sgt", "Sr", "St", "Supt", "Surg", "v", "vs", "i.e", "rev", "e.g", "Jan", "Feb", "Mar", "Apr",
+    "Jun", "Jul", "Aug", "Sep", "Sept", "Oct", "Nov", "Dec", "etc", "Inc", "Ltd", "Co",
+];
+const NON_BREAKING_PREFIXES_FR: &[&str] = &[
+    "M", "MM", "Mme", "Mmes", "Mlle", "Mlles", "Dr", "Pr", "Prof", "Me", "Mgr", "St", "Ste", "av",
+    "apr", "J.-C", "etc", "cf", "vol", "chap", "fig", "éd", "janv", "févr", "avr", "juil", "sept",
+    "oct", "nov", "déc", "boul", "env",
+];
+const NON_BREAKING_PREFIXES_DE: &[&str] = &[
+    "Dr", "Prof", "Hr", "Fr", "bzw", "ca", "usw", "vgl", "z.B", "u.a", "d.h", "evtl", "ggf",
+    "inkl", "Jan", "Feb", "Mär", "Apr", "Jun", "Jul", "Aug", "Sep", "Sept", "Okt", "Nov", "Dez",
+    "Str", "Abs", "Bd", "Hrsg", "St",
+];
+const NON_BREAKING_PREFIXES_ES: &[&str] = &[
+    "Sr", "Sra", "Srta", "Sres", "Dr", "Dra", "Ud", "Uds", "Vd", "Vds", "Lic", "Ing", "Prof",
+    "etc", "pág", "págs", "ej", "aprox", "Av", "Avda", "Sto", "Sta", "ene", "feb", "mar", "abr",
+    "jun", "jul", "ago", "sep", "oct", "nov", "dic",
+];
+const NON_BREAKING_PREFIXES_IT: &[&str] = &[
+    "Sig", "Sigg", "Sig.ra", "Dott", "Dott.ssa", "Prof", "Avv", "Ing", "Geom", "Rag", "On", "S",
+    "Sen", "ecc", "pag", "pagg", "cap", "vol", "es", "gen", "feb", "mar", "apr", "mag", "giu",
+    "lug", "ago", "set", "ott", "nov", "dic",
+];
+const NON_BREAKING_PREFIXES_PT: &[&str] = &[
+    "Sr", "Sra", "Srs", "Sras", "Dr", "Dra", "Prof", "Profa", "Eng", "Av", "etc", "pág", "págs",
+    "ex", "jan", "fev", "mar", "abr", "mai", "jun", "jul", "ago", "set", "out", "nov", "dez",
+];
+const NON_BREAKING_PREFIXES_NL: &[&str] = &[
+    "dhr", "mevr", "mr", "dr", "prof", "ir", "ing", "drs", "bijv", "bv", "blz", "ca", "enz", "etc",
+    "jan", "feb", "mrt", "apr", "jun", "jul", "aug", "sep", "sept", "okt", "nov", "dec", "St",
+];
+/// Non-breaking prefixes only applying if the following word is a number (e.g. `No. 5`)
+const NUMERIC_ONLY_PREFIXES_EN: &[&str] = &["No", "Nos", "Art", "Nr", "pp"];
+const NUMERIC_ONLY_PREFIXES_FR: &[&str] = &["no", "nos", "art", "p", "pp"];
+const NUMERIC_ONLY_PREFIXES_DE: &[&str] = &["Nr", "Art", "S", "Nrn"];
+const NUMERIC_ONLY_PREFIXES_ES: &[&str] = &["No", "Nº", "núm", "art", "p"];
+const NUMERIC_ONLY_PREFIXES_IT: &[&str] = &["n", "nr", "art", "p", "pp"];
+const NUMERIC_ONLY_PREFIXES_PT: &[&str] = &["No", "Nº", "n", "art", "p", "pp"];
+const NUMERIC_ONLY_PREFIXES_NL: &[&str] = &["nr", "art", "p", "pp"];
+
+/// Languages for which the apostrophe is attached to the preceding word (elision, e.g. `l' homme`)
+const ELISION_LANGUAGES: &[&str] = &["fr", "it", "ga", "ca"];
+
+type CharOffsets = Vec<(char, OffsetSize)>;
+
+/// # Moses pre-tokenizer
+/// Rust implementation of the Moses tokenizer (as used by XLM, FlauBERT or fastBPE-based models),
+/// splitting a text into words and punctuation:
+/// - (optional) normalization of the Unicode and Moses punctuation (quotes, dashes, full-width characters...)
+/// - removal of non-printing characters
+/// - splitting of the punctuation, keeping commas within numbers, hyphenated words and
+///   language-specific apostrophes (e.g. `it 's` in English, `l' homme` in French)
+/// - splitting of final periods, except after language-aware non-breaking prefixes (e.g. `Mr.`)
+/// - (optional) XML escaping of special characters (e.g. `&` to `&amp;`)
+///
+/// Protected patterns are never split. The offsets of the tokens always refer to the original
+/// input, including for normalized or escaped characters.
+#[derive(Debug, Clone)]
+pub struct MosesPreTokenizer {
+    language: String,
+    non_breaking_prefixes: HashSet<String>,
+    numeric_only_prefixes: HashSet<String>,
+    normalize_punctuation: bool,
+    escape: bool,
+    protected_patterns: Vec<Regex>,
+}
+
+impl MosesPreTokenizer {
+    /// Create a new instance of a `MosesPreTokenizer` for the given language, normalizing the
+    /// punctuation and escaping special characters. Languages without a list of non-breaking
+    /// prefixes (all but `en`, `fr`, `de`, `es`, `it`, `pt` and `nl`) use the English prefixes.
+    ///
+    /// # Parameters
+    /// - language (`&str`): ISO 639-1 code of the language of the texts to tokenize (e.g. `"en"`)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_tokenizers::tokenizer::MosesPreTokenizer;
+    /// let mut pre_tokenizer = MosesPreTokenizer::new("en");
+    /// pre_tokenizer.set_escape(false);
+    ///
+    /// assert_eq!(
+    ///     pre_tokenizer.tokenize("Mr. Smith isn't here."),
+    ///     vec!["Mr.", "Smith", "isn", "'t", "here", "."]
+    /// );
+    /// ```
+    pub fn new(language: &str) -> MosesPreTokenizer {
+        let (non_breaking_prefixes, numeric_only_prefixes) = match language {
+            "fr" => (NON_BREAKING_PREFIXES_FR, NUMERIC_ONLY_PREFIXES_FR),
+            "de" => (NON_BREAKING_PREFIXES_DE, NUMERIC_ONLY_PREFIXES_DE),
+            "es" => (NON_BREAKING_PREFIXES_ES, NUMERIC_ONLY_PREFIXES_ES),
+            "it" => (NON_BREAKING_PREFIXES_IT, NUMERIC_ONLY_PREFIXES_IT),
+            "pt" => (NON_BREAKING_PREFIXES_PT, NUMERIC_ONLY_PREFIXES_PT),
+            "nl" => (NON_BREAKING_PREFIXES_NL, NUMERIC_ONLY_PREFIXES_NL),
+            _ => (NON_BREAKING_PREFIXES_EN, NUMERIC_ONLY_PREFIXES_EN),
+        };
+        // Single upper case letters (initials) are non-breaking prefixes in all languages
+        let non_breaking_prefixes = non_breaking_prefixes
+            .iter()
+            .map(|prefix| prefix.to_string())
+            .chain(('A'..='Z').map(|letter| letter.to_string()))
+            .collect();
+        let numeric_only_prefixes = numeric_only_prefixes
+            .iter()
+            .map(|prefix| prefix.to_string())
+            .collect();
+        MosesPreTokenizer {
+            language: language.to_string(),
+            non_breaking_prefixes,
+            numeric_only_prefixes,
+            normalize_punctuation: true,
+            escape: true,
+            protected_patterns: vec![],
+        }
+    }
+
+    /// Sets whether special characters (`&`, `|`, `<`, `>`, `'`, `"`, `[`, `]`) should be XML-escaped
+    pub fn set_escape(&mut self, escape: bool) {
+        self.escape = escape;
+    }
+
+    /// Sets whether the Unicode and Moses punctuation should be normalized
+    pub fn set_normalize_punctuation(&mut self, normalize_punctuation: bool) {
+        self.normalize_punctuation = normalize_punctuation;
+    }
+
+    /// Adds non-breaking prefixes, not followed by a sentence split when ending with a period.
+    ///
+    /// # Parameters
+    /// - prefixes (`&[&str]`): prefixes to add (without the trailing period)
+    /// - numeric_only (`bool`): flag indicating if the prefixes are non-breaking only when followed by a number
+    pub fn add_non_breaking_prefixes(&mut self, prefixes: &[&str], numeric_only: bool) {
+        let target = if numeric_only {
+            &mut self.numeric_only_prefixes
+        } else {
+            &mut self.non_breaking_prefixes
+        };
+        target.extend(prefixes.iter().map(|prefix| prefix.to_string()));
+    }
+
+    /// Adds a protected pattern: text matching the pattern is never split
+    ///
+    /// # Parameters
+    /// - pattern (`Regex`): pattern to protect (e.g. URLs or `_`-joined words)
+    pub fn add_protected_pattern(&mut self, pattern: Regex) {
+        self.protected_patterns.push(pattern);
+    }
+
+    /// Returns the language of the pre-tokenizer
+    pub fn language(&self) -> &str {
+        &self.language
+    }
+
+    /// Splits a token into Moses tokens, preserving its offsets
+    ///
+    /// # Parameters
+    /// - token (`TokenRef`): token to split
+    ///
+    /// # Returns
+    /// `Vec<Token>` containing the Moses tokens
+    pub fn tokenize_to_tokens(&self, token: TokenRef) -> Vec<Token> {
+        let characters: CharOffsets = token
+            .text
+            .chars()
+            .zip(token.reference_offsets.iter().copied())
+            .collect();
+        let characters = self.normalize(&characters);
+        let protected = self.protected_spans(&characters);
+
+        let mut tokens: Vec<(CharOffsets, bool)> = vec![];
+        let mut position = 0;
+        while position < characters.len() {
+            let start = position;
+            while position < characters.len()
+                && !(characters[position].0.is_whitespace() && protected[position].is_none())
+            {
+                position += 1;
+            }
+            if position > start {
+                self.split_word(
+                    &characters[start..position],
+                    &protected[start..position],
+                    &mut tokens,
+                );
+            }
+            position += 1;
+        }
+
+        let tokens = self.split_final_periods(tokens);
+        tokens
+            .into_iter()
+            .map(|(characters, protected)| {
+                let characters = if self.escape && !protected {
+                    escape(&characters)
+                } else {
+                    characters
+                };
+                let reference_offsets: Vec<OffsetSize> =
+                    characters.iter().map(|(_, offset)| *offset).collect();
+                Token {
+                    text: characters.iter().map(|(character, _)| character).collect(),
+                    offset: Offset::new(
+                        reference_offsets[0],
+                        reference_offsets[reference_offsets.len() - 1] + 1,
+                    ),
+                    reference_offsets,
+                    mask: Mask::None,
+                }
+            })
+            .collect()
+    }
+
+    /// Splits a text into Moses tokens
+    ///
+    /// # Parameters
+    /// - text (`&str`): text to split
+    ///
+    /// # Returns
+    /// `Vec<String>` containing the Moses tokens
+    pub fn tokenize(&self, text: &str) -> Vec<String> {
+        let offsets = (0..text.chars().count() as OffsetSize).collect::<Vec<OffsetSize>>();
+        self.tokenize_to_tokens(TokenRef::new(text, &offsets))
+            .into_iter()
+            .map(|token| token.text)
+            .collect()
+    }
+
+    fn normalize(&self, characters: &[(char, OffsetSize)]) -> CharOffsets {
+        let mut output = Vec::with_capacity(characters.len());
+        let mut position = 0;
+        while position < characters.len() {
+            let (character, offset) = characters[position];
+            if self.normalize_punctuation {
+                let next = characters.get(position + 1).map(|(next, _)| *next);
+                if matches!(
+                    (character, next),
+                    ('\'', Some('\'')) | ('`', Some('`')) | ('´', Some('´'))
+                ) {
+                    output.push(('"', offset));
+                    position += 2;
+                    continue;
+                }
+                if let Some(replacement) = normalize_character(character) {
+                    output.extend(replacement.chars().map(|character| (character, offset)));
+                    position += 1;
+                    continue;
+                }
+            }
+            if !is_non_printing(character) {
+                output.push((character, offset));
+            }
+            position += 1;
+        }
+        output
+    }
+
+    fn protected_spans(&self, characters: &[(char, OffsetSize)]) -> Vec<Option<usize>> {
+        let mut protected = vec![None; characters.len()];
+        if self.protected_patterns.is_empty() {
+            return protected;
+        }
+        let text: String = characters.iter().map(|(character, _)| character).collect();
+        let char_positions: Vec<usize> = text.char_indices().map(|(index, _)| index).collect();
+        let mut span_id = 0;
+        for pattern in &self.protected_patterns {
+            for hit in pattern.find_iter(&text) {
+                let start = char_positions.partition_point(|&index| index < hit.start());
+                let end = char_positions.partition_point(|&index| index < hit.end());
+                if start < end && protected[start..end].iter().all(Option::is_none) {
+                    protected[start..end]
+                        .iter_mut()
+                        .for_each(|v| *v = Some(span_id));
+                    span_id += 1;
+                }
+            }
+        }
+        protected
+    }
+
+    fn split_word(
+        &self,
+        word: &[(char, OffsetSize)],
+        protected: &[Option<usize>],
+        tokens: &mut Vec<(CharOffsets, bool)>,
+    ) {
+        let mut current: CharOffsets = vec![];
+        let mut position = 0;
+        while position < word.len() {
+            if let Some(span_id) = protected[position] {
+                self.split_segment(&current, tokens);
+                current.clear();
+                let start = position;
+                while position < word.len() && protected[position] == Some(span_id) {
+                    position += 1;
+                }
+                tokens.push((word[start..position].to_vec(), true));
+                continue;
+            }
+            let character = word[position].0;
+            if character.is_alphanumeric()
+                || is_combining_mark(character)
+                || matches!(character, '.' | '\'' | ',' | '-')
+            {
+                current.push(word[position]);
+            } else {
+                self.split_segment(&current, tokens);
+                current.clear();
+                tokens.push((vec![word[position]], false));
+            }
+            position += 1;
+        }
+        self.split_segment(&current, tokens);
+    }
+
+    /// Splits a segment made of alphanumeric characters, periods, apostrophes, commas and hyphens
+    fn split_segment(&self, segment: &[(char, OffsetSize)], tokens: &mut Vec<(CharOffsets, bool)>) {
+        fn flush(current: &mut CharOffsets, tokens: &mut Vec<(CharOffsets, bool)>) {
+            if !current.is_empty() {
+                tokens.push((std::mem::take(current), false));
+            }
+        }
+
+        let mut current: CharOffsets = vec![];
+        let mut position = 0;
+        while position < segment.len() {
+            let character = segment[position].0;
+            let previous = position.checked_sub(1).map(|previous| segment[previous].0);
+            let next = segment.get(position + 1).map(|(next, _)| *next);
+            match character {
+                ',' => {
+                    if previous.is_some_and(|c| c.is_numeric())
+                        && next.is_some_and(|c| c.is_numeric())
+                    {
+                        current.push(segment[position]);
+                    } else {
+                        flush(&mut current, tokens);
+                        tokens.push((vec![segment[position]], false));
+                    }
+                }
+                '.' if next == Some('.') => {
+                    let start = position;
+                    while position < segment.len() && segment[position].0 == '.' {
+                        position += 1;
+                    }
+                    flush(&mut current, tokens);
+                    tokens.push((segment[start..position].to_vec(), false));
+                    continue;
+                }
+                '\'' => {
+                    let previous_alpha = previous.is_some_and(|c| c.is_alphabetic());
+                    let previous_numeric = previous.is_some_and(|c| c.is_numeric());
+                    let next_alpha = next.is_some_and(|c| c.is_alphabetic());
+                    if self.language == "en" {
+                        if (previous_alpha && next_alpha) || (previous_numeric && next == Some('s'))
+                        {
+                            // The apostrophe is attached to the following word (`it 's`)
+                            flush(&mut current, tokens);
+                            current.push(segment[position]);
+                        } else if previous_numeric && next_alpha {
+                            current.push(segment[position]);
+                        } else {
+                            flush(&mut current, tokens);
+                            tokens.push((vec![segment[position]], false));
+                        }
+                    } else if ELISION_LANGUAGES.contains(&self.language.as_str())
+                        && previous_alpha
+                        && next_alpha
+                    {
+                        // The apostrophe is attached to the preceding word (`l' homme`)
+                        current.push(segment[position]);
+                        flush(&mut current, tokens);
+                    } else {
+                        flush(&mut current, tokens);
+                        tokens.push((vec![segment[position]], false));
+                    }
+                }
+                _ => current.push(segment[position]),
+            }
+            position += 1;
+        }
+        flush(&mut current, tokens);
+    }
+
+    /// Splits the final period of tokens, unless the token is a non-breaking prefix, an acronym, or
+    /// is followed by a lower-cased word (or a number for numeric-only prefixes)
+    fn split_final_periods(&self, tokens: Vec<(CharOffsets, bool)>) -> Vec<(CharOffsets, bool)> {
+        let next_first_characters: Vec<Option<char>> = tokens
+            .iter()
+            .skip(1)
+            .map(|(characters, _)| characters.first().map(|(character, _)| *character))
+            .chain(std::iter::once(None))
+            .collect();
+        let mut output = Vec::with_capacity(tokens.len());
+        for ((mut characters, protected), next_first_character) in
+            tokens.into_iter().zip(next_first_characters)
+        {
+            let ends_with_period = characters.len() > 1
+                && characters.last().map(|(character, _)| *character) == Some('.');
+            if protected || !ends_with_period {
+                output.push((characters, protected));
+                continue;
+            }
+            let prefix: String = characters[..characters.len() - 1]
+                .iter()
+                .map(|(character, _)| character)
+                .collect();
+            let keep = (prefix.contains('.') && prefix.chars().any(char::is_alphabetic))
+                || self.non_breaking_prefixes.contains(&prefix)
+                || next_first_character.is_some_and(char::is_lowercase)
+                || (self.numeric_only_prefixes.contains(&prefix)
+                    && next_first_character.is_some_and(|c| c.is_ascii_digit()));
+            if keep || prefix.chars().all(|character| character == '.') {
+                output.push((characters, false));
+            } else {
+                let period = characters.pop().unwrap();
+                output.push((characters, false));
+                output.push((vec![period], false));
+            }
+        }
+        output
+    }
+}
+
+/// Normalization of the Unicode punctuation (e.g. full-width characters) and of the Moses punctuation
+fn normalize_character(character: char) -> Option<&'static str> {
+    Some(match character {
+        '，' | '、' => ",",
+        '。' | '．' => ". ",
+        '∶' | '：' => ":",
+        '？' => "?",
+        '！' => "!",
+        '；' => ";",
+        '（' => "(",
+        '）' => ")",
+        '～' => "~",
+        '％' => "%",
+        '〈' => "<",
+        '〉' => ">",
+        '【' => "[",
+        '】' => "]",
+        '━' | '–' => "-",
+        '—' => " - ",
+        '《' | '》' | '「' | '」' | '„' | '“' | '”' | '«' | '»' => "\"",
+        '´' | '‘' | '‚' | '’' => "'",
+        '…' => "...",
+        '０' => "0",
+        '１' => "1",
+        '２' => "2",
+        '３' => "3",
+        '４' => "4",
+        '５' => "5",
+        '６' => "6",
+        '７' => "7",
+        '８' => "8",
+        '９' => "9",
+        _ => return None,
+    })
+}
+
+fn is_non_printing(character: char) -> bool {
+    (character.is_control() && !character.is_whitespace())
+        || matches!(
+            character,
+            '\u{200B}' | '\u{200C}' | '\u{200D}' | '\u{2060}' | '\u{FEFF}'
+        )
+}
+
+fn escape(characters: &[(char, OffsetSize)]) -> CharOffsets {
+    let mut output = Vec::with_capacity(characters.len());
+    for (character, offset) in characters {
+        let escaped = match character {
+            '&' => "&amp;",
+            '|' => "&#124;",
+            '<' => "&lt;",
+            '>' => "&gt;",
+            '\'' => "&apos;",
+            '"' => "&quot;",
+            '[' => "&#91;",
+            ']' => "&#93;",
+            _ => {
+                output.push((*character, *offset));
+                continue;
+            }
+        };
+        output.extend(escaped.chars().map(|character| (character, *offset)));
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_moses_pre_tokenizer() {
+        //        Given
+        let pre_tokenizer = MosesPreTokenizer::new("en");
+        let test_tuples = [
+            (
+                "Hello, world! It's 1,000.5 dollars.",
+                vec![
+                    "Hello", ",", "world", "!", "It", "&apos;s", "1,000.5", "dollars", ".",
+                ],
+            ),
+            (
+                "Mr. Smith met Dr. Who in the U.S. yesterday.",
+                vec![
+                    "Mr.",
+                    "Smith",
+                    "met",
+                    "Dr.",
+                    "Who",
+                    "in",
+                    "the",
+                    "U.S.",
+                    "yesterday",
+                    ".",
+                ],
+            ),
+            (
+                "See No. 5 and No. more",
+                vec!["See", "No.", "5", "and", "No.", "more"],
+            ),
+            ("Wait... what?", vec!["Wait", "...", "what", "?"]),
+            (
+                "a well-known \"R&D\" lab",
+                vec![
+                    "a",
+                    "well-known",
+                    "&quot;",
+                    "R",
+                    "&amp;",
+                    "D",
+                    "&quot;",
+                    "lab",
+                ],
+            ),
+            ("", vec![]),
+            ("   ", vec![]),
+        ];
+
+        //        When & Then
+        for (source_text, expected_result) in test_tuples.iter() {
+            assert_eq!(pre_tokenizer.tokenize(source_text), *expected_result);
+        }
+    }
+
+    #[test]
+    fn test_moses_language_specific_rules() {
+        //        Given
+        let mut french_pre_tokenizer = MosesPreTokenizer::new("fr");
+        french_pre_tokenizer.set_escape(false);
+        let mut german_pre_tokenizer = MosesPreTokenizer::new("de");
+        german_pre_tokenizer.set_escape(false);
+
+        //        When & Then
+        assert_eq!(
+            french_pre_tokenizer.tokenize("L'homme d’affaires « M. Dupont »."),
+            vec!["L'", "homme", "d'", "affaires", "\"", "M.", "Dupont", "\"", "."]
+        );
+        assert_eq!(
+            german_pre_tokenizer.tokenize("Das ist z.B. gut，oder？"),
+            vec!["Das", "ist", "z.B.", "gut", ",", "oder", "?"]
+        );
+    }
+
+    #[test]
+    fn test_moses_offsets_and_protected_patterns() {
+        //        Given
+        let mut pre_tokenizer = MosesPreTokenizer::new("vi");
+        pre_tokenizer.add_protected_pattern(Regex::new(r"\w+(?:_\w+)+").unwrap());
+        let text = "Tôi ở Hà_Nội… (vâng) & «tốt».";
+        let offsets = (0..text.chars().count() as OffsetSize).collect::<Vec<OffsetSize>>();
+
+        //        When
+        let tokens = pre_tokenizer.tokenize_to_tokens(TokenRef::new(text, &offsets));
+
+        //        Then
+        let texts: Vec<&str> = tokens.iter().map(|token| token.text.as_str()).collect();
+        assert_eq!(
+            texts,
+            vec![
+                "Tôi",
+                "ở",
+                "Hà_Nội",
+                "...",
+                "(",
+                "vâng",
+                ")",
+                "&amp;",
+                "&quot;",
+                "tốt",
+                "&quot;",
+                "."
+            ]
+        );
+        let token_offsets: Vec<Offset> = tokens.iter().map(|token| token.offset).collect();
+        assert_eq!(
+            token_offsets,
+            vec![
+                Offset::new(0, 3),
+                Offset::new(4, 5),
+                Offset::new(6, 12),
+                Offset::new(12, 13),
+                Offset::new(14, 15),
+                Offset::new(15, 19),
+                Offset::new(19, 20),
+                Offset::new(21, 22),
+                Offset::new(23, 24),
+                Offset::new(24, 27),
+                Offset::new(27, 28),
+                Offset::new(28, 29),
+            ]
+        );
+        for token in tokens.iter() {
+            assert_eq!(token.text.chars().count(), token.reference_offsets.len());
+        }
+    }
+}
@@ -0,0 +1,300 @@
+// Copyright 2018 The HuggingFace Inc. team, Microsoft Corporation.
+// Copyright 2019-2020 Guillaume Becquin
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//     http://www.apache.org/licenses/LICENSE-2.0
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::path::Path;
+
+use crate::error::TokenizerError;
+use crate::tokenizer::base_tokenizer::{
+    push_word_tokens, BaseTokenizer, Mask, MultiThreadedTokenizer, Offset, OffsetSize, Token,
+    TokenIdsWithOffsets, TokenIdsWithSpecialTokens, TokenRef, Tokenizer, WordTokenCount,
+};
+use crate::tokenizer::tokenization_utils::{
+    count_wordpiece, lstrip_special_token, tokenize_wordpiece,
+};
+use crate::vocab::{MPNetVocab, Vocab};
+
+/// # MPNet tokenizer
+/// MPNet tokenizer performing:
+/// - BaseTokenizer tokenization (see `BaseTokenizer` for more details)
+/// - WordPiece tokenization
+///
+/// Sequence pairs are encoded as `<s> A </s></s> B </s>`, with a segment id of 0 for all tokens.
+pub struct MPNetTokenizer {
+    vocab: MPNetVocab,
+    base_tokenizer: BaseTokenizer<MPNetVocab>,
+}
+
+impl MPNetTokenizer {
+    /// Create a new instance of a `MPNetTokenizer`
+    /// Expects a vocabulary flat-file as an input.
+    ///
+    /// # Parameters
+    /// - path (`&str`): path to the vocabulary file
+    /// - lower_case (`bool`): flag indicating if the text should be lower-cased as part of the tokenization
+    /// - strip_accents (`bool`): flag indicating if accents should be stripped from the text
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use rust_tokenizers::tokenizer::{MPNetTokenizer, Tokenizer};
+    /// let strip_accents = false;
+    /// let lower_case = false;
+    /// let tokenizer =
+    ///     MPNetTokenizer::from_file("path/to/vocab/file", lower_case, strip_accents).unwrap();
+    /// ```
+    pub fn from_file<P: AsRef<Path>>(
+        path: P,
+        lower_case: bool,
+        strip_accents: bool,
+    ) -> Result<MPNetTokenizer, TokenizerError> {
+        let vocab = MPNetVocab::from_file(path)?;
+        let base_tokenizer =
+            BaseTokenizer::from_existing_vocab(vocab.clone(), lower_case, strip_accents);
+        Ok(MPNetTokenizer {
+            vocab,
+            base_tokenizer,
+        })
+    }
+
+    /// Create a new instance of a `MPNetTokenizer`
+    /// Expects a vocabulary flat-file and special token mapping file as inputs.
+    ///
+    /// # Parameters
+    /// - path (`&str`): path to the vocabulary file
+    /// - lower_case (`bool`): flag indicating if the text should be lower-cased as part of the tokenization
+    /// - strip_accents (`bool`): flag indicating if accents should be stripped from the text
+    /// - special_token_mapping_path (`&str`): path to a special token mapping file to overwrite default special tokens
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use rust_tokenizers::tokenizer::{MPNetTokenizer, Tokenizer};
+    /// let strip_accents = false;
+    /// let lower_case = false;
+    /// let tokenizer = MPNetTokenizer::from_file_with_special_token_mapping(
+    ///     "path/to/vocab/file",
+    ///     lower_case,
+    ///     strip_accents,
+    ///     "path/to/special/token/mapping/file",
+    /// )
+    /// .unwrap();
+    /// ```
+    pub fn from_file_with_special_token_mapping<P: AsRef<Path>, S: AsRef<Path>>(
+        path: P,
+        lower_case: bool,
+        strip_accents: bool,
+        special_token_mapping_path: S,
+    ) -> Result<MPNetTokenizer, TokenizerError> {
+        let vocab =
+            MPNetVocab::from_file_with_special_token_mapping(path, special_token_mapping_path)?;
+        let base_tokenizer =
+            BaseTokenizer::from_existing_vocab(vocab.clone(), lower_case, strip_accents);
+        Ok(MPNetTokenizer {
+            vocab,
+            base_tokenizer,
+        })
+    }
+    /// Create a new instance of a `MPNetTokenizer` from an existing vocabulary
+    ///
+    /// # Parameters
+    /// - vocab (`MPNetVocab`): MPNet vocabulary
+    /// - lower_case (`bool`): flag indicating if the text should be lower-cased as part of the tokenization
+    /// - strip_accents (`bool`): flag indicating if accents should be stripped from the text
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use rust_tokenizers::tokenizer::{MPNetTokenizer, Tokenizer};
+    /// use rust_tokenizers::vocab::{MPNetVocab, Vocab};
+    /// let strip_accents = false;
+    /// let lower_case = false;
+    /// let vocab = MPNetVocab::from_file("path/to/vocab/file").unwrap();
+    ///
+    /// let tokenizer = MPNetTokenizer::from_existing_vocab(vocab, lower_case, strip_accents);
+    /// ```
+    pub fn from_existing_vocab(
+        vocab: MPNetVocab,
+        lower_case: bool,
+        strip_accents: bool,
+    ) -> MPNetTokenizer {
+        let base_tokenizer =
+            BaseTokenizer::from_existing_vocab(vocab.clone(), lower_case, strip_accents);
+        MPNetTokenizer {
+            vocab,
+            base_tokenizer,
+        }
+    }
+}
+
+impl Tokenizer<MPNetVocab> for MPNetTokenizer {
+    fn vocab(&self) -> &MPNetVocab {
+        &self.vocab
+    }
+    fn vocab_mut(&mut self) -> &mut MPNetVocab {
+        &mut self.vocab
+    }
+
+    fn add_tokens(&mut self, tokens: &[&str]) {
+        self.vocab.add_tokens(tokens);
+        self.base_tokenizer.add_tokens(tokens);
+    }
+
+    fn add_extra_ids(&mut self, num_extra_ids: i64) {
+        self.vocab.add_extra_ids(num_extra_ids);
+        self.base_tokenizer.add_extra_ids(num_extra_ids);
+    }
+
+    fn tokenize_to_tokens(&self, initial_token: TokenRef) -> Vec<Token> {
+        //the base tokenizers does most of the work, we simply add a wordpiece tokenizer on top
+        let mut tokens: Vec<Token> = self
+            .base_tokenizer
+            .tokenize_to_tokens(initial_token)
+            .into_iter()
+            .flat_map(|token| tokenize_wordpiece(token.as_ref(), &self.vocab, 100))
+            .collect();
+        //the mask token absorbs the whitespace preceding it
+        lstrip_special_token(initial_token, &mut tokens, self.vocab.get_mask_value());
+        tokens
+    }
+
+    fn count_word_tokens(&self, text: &str) -> Vec<WordTokenCount> {
+        let mut words = Vec::new();
+        self.base_tokenizer
+            .for_each_word_token(text, |begin, end, token| {
+                let num_tokens = count_wordpiece(token, &self.vocab, 100);
+                push_word_tokens(&mut words, begin, end, num_tokens)
+            });
+        words
+    }
+
+    fn convert_tokens_to_string(&self, tokens: Vec<String>) -> String {
+        tokens.join(" ").replace(" ##", "").trim().to_owned()
+    }
+
+    fn build_input_with_special_tokens(
+        &self,
+        tokens_ids_with_offsets_1: TokenIdsWithOffsets,
+        tokens_ids_with_offsets_2: Option<TokenIdsWithOffsets>,
+    ) -> TokenIdsWithSpecialTokens {
+        let mut output: Vec<i64> = vec![];
+        let mut token_segment_ids: Vec<i8> = vec![];
+        let mut special_tokens_mask: Vec<i8> = vec![];
+        let mut offsets: Vec<Option<Offset>> = vec![];
+        let mut original_offsets: Vec<Vec<OffsetSize>> = vec![];
+        let mut mask: Vec<Mask> = vec![];
+        special_tokens_mask.push(1);
+        special_tokens_mask.extend(vec![0; tokens_ids_with_offsets_1.ids.len()]);
+        special_tokens_mask.push(1);
+        token_segment_ids.extend(vec![0; tokens_ids_with_offsets_1.ids.len() + 2]);
+        output.push(self.vocab.token_to_id(self.vocab.get_cls_value()));
+        output.extend(tokens_ids_with_offsets_1.ids);
+        output.push(self.vocab.token_to_id(self.vocab.get_sep_value()));
+        offsets.push(None);
+        offsets.extend(tokens_ids_with_offsets_1.offsets);
+        offsets.push(None);
+        original_offsets.push(vec![]);
+        original_offsets.extend(tokens_ids_with_offsets_1.reference_offsets);
+        original_offsets.push(vec![]);
+        mask.push(Mask::Special);
+        mask.extend(tokens_ids_with_offsets_1.masks);
+        mask.push(Mask::Special);
+        if let Some(tokens_ids_with_offsets_2_value) = tokens_ids_with_offsets_2 {
+            // The second sequence is preceded by a double separator and shares the segment id 0
+            let length = tokens_ids_with_offsets_2_value.ids.len();
+            special_tokens_mask.push(1);
+            special_tokens_mask.extend(vec![0; length]);
+            special_tokens_mask.push(1);
+            token_segment_ids.extend(vec![0; length + 2]);
+            output.push(self.vocab.token_to_id(self.vocab.get_sep_value()));
+            output.extend(tokens_ids_with_offsets_2_value.ids);
+            output.push(self.vocab.token_to_id(self.vocab.get_sep_value()));
+            offsets.push(None);
+            offsets.extend(tokens_ids_with_offsets_2_value.offsets);
+            offsets.push(None);
+            original_offsets.push(vec![]);
+            original_offsets.extend(tokens_ids_with_offsets_2_value.reference_offsets);
+            original_offsets.push(vec![]);
+            mask.push(Mask::Special);
+            mask.extend(tokens_ids_with_offsets_2_value.masks);
+            mask.push(Mask::Special);
+        }
+        TokenIdsWithSpecialTokens {
+            token_ids: output,
+            segment_ids: token_segment_ids,
+            special_tokens_mask,
+            token_offsets: offsets,
+            reference_offsets: original_offsets,
+            mask,
+        }
+    }
+}
+
+impl MultiThreadedTokenizer<MPNetVocab> for MPNetTokenizer {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tokenizer::TruncationStrategy;
+    use crate::vocab::SpecialTokenMap;
+    use std::collections::HashMap;
+
+    fn generate_test_vocab() -> MPNetVocab {
+        let values: HashMap<String, i64> = vec![
+            "<s>", "<pad>", "</s>", "<unk>", "[UNK]", "hello", "world", "!", "the", "dog", "<mask>",
+        ]
+        .into_iter()
+        .enumerate()
+        .map(|(id, token)| (token.to_string(), id as i64))
+        .collect();
+        let special_token_map = SpecialTokenMap {
+            unk_token: "[UNK]".to_string(),
+            pad_token: Some("<pad>".to_string()),
+            bos_token: Some("<s>".to_string()),
+            sep_token: Some("</s>".to_string()),
+            cls_token: Some("<s>".to_string()),
+            eos_token: Some("</s>".to_string()),
+            mask_token: Some("<mask>".to_string()),
+            additional_special_tokens: None,
+        };
+        MPNetVocab::from_values_and_special_token_map(values, special_token_map).unwrap()
+    }
+
+    #[test]
+    fn test_encode_sentence_pair() {
+        //        Given
+        let mut mpnet_tokenizer =
+            MPNetTokenizer::from_existing_vocab(generate_test_vocab(), true, true);
+        mpnet_tokenizer.add_tokens(&["<new>"]);
+
+        //        When
+        let encoding = mpnet_tokenizer.encode(
+            "Hello <mask> world!",
+            Some("the <new> dog"),
+            128,
+            &TruncationStrategy::LongestFirst,
+            0,
+        );
+
+        //        Then
+        assert_eq!(encoding.token_ids, vec![0, 5, 10, 6, 7, 2, 2, 8, 11, 9, 2]);
+        assert_eq!(encoding.segment_ids, vec![0; 11]);
+        assert_eq!(
+            encoding.special_tokens_mask,
+            vec![1, 0, 0, 0, 0, 1, 1, 0, 0, 0, 1]
+        );
+        assert_eq!(
+            mpnet_tokenizer.decode(&encoding.token_ids, true, true),
+            "hello world! the dog"
+        );
+        assert_eq!(mpnet_tokenizer.count_tokens("Hello <mask> world!"), 4);
+    }
+}
@@ -10,24 +10,33 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashSet;
 use std::path::Path;
 
 use crate::vocab::EXTENDED_FAIRSEQ_LANGUAGE_CODES;
 use crate::{
     error::TokenizerError,
-    vocab::{NLLBVocab, SentencePieceBpeModel, Vocab},
+    vocab::{NLLBVocab, SentencePieceBpeModel, SpecialTokenValues, Vocab},
     Mask, Offset, OffsetSize, Token, TokenIdsWithOffsets, TokenIdsWithSpecialTokens,
 };
 
 use super::{
-    tokenization_utils::{clean_text, decompose_nfkc, is_whitespace, split_on_language_code},
+    tokenization_utils::{
+        clean_text, decompose_nfkc, is_whitespace, language_codes_bytes, required_special_token_id,
+        split_on_language_code,
+    },
     MultiThreadedTokenizer, Tokenizer,
 };
 
-pub struct NLLBTokenizer {
+/// # NLLB tokenizer
+/// The tokenizer is generic over its vocabulary, allowing the use of a memory-mapped `MmapVocab`
+/// compiled from a `NLLBVocab`.
+pub struct NLLBTokenizer<V = NLLBVocab> {
     model: SentencePieceBpeModel,
-    vocab: NLLBVocab,
+    vocab: V,
+    language_codes_bytes: HashSet<Vec<u8>>,
     src_lang: String,
+    eos_id: i64,
 }
 
 impl NLLBTokenizer {
@@ -38,13 +47,7 @@ impl NLLBTokenizer {
     ) -> Result<Self, TokenizerError> {
         let model = SentencePieceBpeModel::from_file(model_path)?;
         let vocab = NLLBVocab::from_file_with_special_token_mapping(vocab_path, special_tokens)?;
-        let src_lang = String::from("eng_Latn");
-
-        Ok(Self {
-            model,
-            vocab,
-            src_lang,
-        })
+        NLLBTokenizer::from_existing_vocab_and_model(vocab, model)
     }
 
     pub fn from_files<V: AsRef<Path>, M: AsRef<Path>>(
@@ -53,11 +56,41 @@ impl NLLBTokenizer {
     ) -> Result<Self, TokenizerError> {
         let model = SentencePieceBpeModel::from_file(model_path)?;
         let vocab = NLLBVocab::from_file(vocab_path)?;
-        let src_lang = String::from("eng_Latn");
-        Ok(Self {
+        NLLBTokenizer::from_existing_vocab_and_model(vocab, model)
+    }
+}
+
+impl<V: SpecialTokenValues> NLLBTokenizer<V> {
+    /// Create a new instance of a `NLLBTokenizer` from an existing vocabulary and model.
+    /// Returns a `TokenizerError::TokenNotFound` if the vocabulary does not contain the end of
+    /// sequence token.
+    ///
+    /// # Parameters
+    /// - vocab (`NLLBVocab` or `MmapVocab`): vocabulary
+    /// - model (`SentencePieceBpeModel`): SentencePiece BPE model
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use rust_tokenizers::tokenizer::{NLLBTokenizer, Tokenizer};
+    /// use rust_tokenizers::vocab::{MmapVocab, SentencePieceBpeModel, Vocab};
+    /// let vocab = MmapVocab::from_file("path/to/vocab.bin").unwrap();
+    /// let model = SentencePieceBpeModel::from_file("path/to/model/file").unwrap();
+    ///
+    /// let tokenizer = NLLBTokenizer::from_existing_vocab_and_model(vocab, model).unwrap();
+    /// ```
+    pub fn from_existing_vocab_and_model(
+        vocab: V,
+        model: SentencePieceBpeModel,
+    ) -> Result<NLLBTokenizer<V>, TokenizerError> {
+        let eos_id = required_special_token_id(&vocab, vocab.eos_value(), "end of sequence")?;
+        let language_codes_bytes = language_codes_bytes(&vocab, 8);
+        Ok(NLLBTokenizer {
             model,
             vocab,
-            src_lang,
+            language_codes_bytes,
+            src_lang: String::from("eng_Latn"),
+            eos_id,
         })
     }
 
@@ -73,16 +106,16 @@ impl NLLBTokenizer {
     }
 }
 
-impl Tokenizer<NLLBVocab> for NLLBTokenizer {
-    fn vocab(&self) -> &NLLBVocab {
+impl<V: SpecialTokenValues> Tokenizer<V> for NLLBTokenizer<V> {
+    fn vocab(&self) -> &V {
         &self.vocab
     }
-    fn vocab_mut(&mut self) -> &mut NLLBVocab {
+    fn vocab_mut(&mut self) -> &mut V {
         &mut self.vocab
     }
 
     fn tokenize_to_tokens(&self, text: crate::TokenRef) -> Vec<crate::Token> {
-        let tokens = split_on_language_code(text, 8, &self.vocab.language_codes_bytes);
+        let tokens = split_on_language_code(text, 8, &self.language_codes_bytes);
         let (code_token, mut token) = match tokens.len() {
             0 => {
                 return vec![];
@@ -153,7 +186,7 @@ impl Tokenizer<NLLBVocab> for NLLBTokenizer {
         }
         special_tokens_mask.push(1);
         special_tokens_mask.push(1);
-        output.push(self.vocab.token_to_id(self.vocab.get_eos_value()));
+        output.push(self.eos_id);
         output.push(self.vocab.token_to_id(&self.src_lang));
         offsets.push(None);
         offsets.push(None);
@@ -173,4 +206,4 @@ impl Tokenizer<NLLBVocab> for NLLBTokenizer {
     }
 }
 
-impl MultiThreadedTokenizer<NLLBVocab> for NLLBTokenizer {}
+impl<V: SpecialTokenValues + Send + Sync> MultiThreadedTokenizer<V> for NLLBTokenizer<V> {}
@@ -0,0 +1,315 @@
+// Copyright 2019-2020 Guillaume Becquin
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//     http://www.apache.org/licenses/LICENSE-2.0
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::tokenizer::base_tokenizer::{Offset, OffsetSize};
+use serde::{Deserialize, Serialize};
+
+/// # Offset unit
+/// Unit in which offsets relate tokens to the original text
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OffsetUnit {
+    /// Unicode code points (`char`), the unit of the offsets returned by the tokenizers
+    Char,
+    /// UTF-8 bytes, for slicing a Rust `&str`
+    Byte,
+    /// UTF-16 code units, for slicing a JavaScript or Java string
+    Utf16,
+}
+
+/// # Offset converter
+/// Converts offsets expressed in Unicode code points to UTF-8 byte or UTF-16 code unit offsets
+/// for a given text. Positions are pre-computed so that each conversion is a lookup.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OffsetConverter {
+    byte_positions: Vec<OffsetSize>,
+    utf16_positions: Vec<OffsetSize>,
+}
+
+impl OffsetConverter {
+    /// Create a new instance of an `OffsetConverter` for a text
+    ///
+    /// # Parameters
+    /// - text (`&str`): text the offsets refer to
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_tokenizers::tokenizer::{OffsetConverter, OffsetUnit};
+    /// use rust_tokenizers::Offset;
+    /// let converter = OffsetConverter::new("東京 🚀 café");
+    ///
+    /// assert_eq!(
+    ///     converter.convert_offset(Offset::new(3, 4), OffsetUnit::Byte),
+    ///     Some(Offset::new(7, 11))
+    /// );
+    /// assert_eq!(
+    ///     converter.convert_offset(Offset::new(3, 4), OffsetUnit::Utf16),
+    ///     Some(Offset::new(3, 5))
+    /// );
+    /// ```
+    pub fn new(text: &str) -> OffsetConverter {
+        let num_chars = text.chars().count();
+        let mut byte_positions = Vec::with_capacity(num_chars + 1);
+        let mut utf16_positions = Vec::with_capacity(num_chars + 1);
+        let mut utf16_position = 0;
+        for (byte_position, character) in text.char_indices() {
+            byte_positions.push(byte_position as OffsetSize);
+            utf16_positions.push(utf16_position as OffsetSize);
+            utf16_position += character.len_utf16();
+        }
+        byte_positions.push(text.len() as OffsetSize);
+        utf16_positions.push(utf16_position as OffsetSize);
+        OffsetConverter {
+            byte_positions,
+            utf16_positions,
+        }
+    }
+
+    /// Number of Unicode code points of the text
+    pub fn num_chars(&self) -> usize {
+        self.byte_positions.len() - 1
+    }
+
+    /// Convert a position of the text (in Unicode code points) to the target unit. Returns `None`
+    /// if the position is past the end of the text.
+    ///
+    /// # Parameters
+    /// - position (`OffsetSize`): position in Unicode code points
+    /// - unit (`OffsetUnit`): target unit
+    pub fn convert_position(&self, position: OffsetSize, unit: OffsetUnit) -> Option<OffsetSize> {
+        match unit {
+            OffsetUnit::Char => {
+                Some(position).filter(|position| *position as usize <= self.num_chars())
+            }
+            OffsetUnit::Byte => self.byte_positions.get(position as usize).copied(),
+            OffsetUnit::Utf16 => self.utf16_positions.get(position as usize).copied(),
+        }
+    }
+
+    /// Convert an offset of the text (in Unicode code points) to the target unit. Returns `None`
+    /// if the offset is past the end of the text.
+    ///
+    /// # Parameters
+    /// - offset (`Offset`): offset in Unicode code points
+    /// - unit (`OffsetUnit`): target unit
+    pub fn convert_offset(&self, offset: Offset, unit: OffsetUnit) -> Option<Offset> {
+        Some(Offset::new(
+            self.convert_position(offset.begin, unit)?,
+            self.convert_position(offset.end, unit)?,
+        ))
+    }
+
+    /// Convert token offsets (in Unicode code points) to the target unit. Offsets past the end of
+    /// the text are converted to `None`.
+    ///
+    /// # Parameters
+    /// - offsets (`&[Option<Offset>]`): token offsets in Unicode code points
+    /// - unit (`OffsetUnit`): target unit
+    pub fn convert_offsets(
+        &self,
+        offsets: &[Option<Offset>],
+        unit: OffsetUnit,
+    ) -> Vec<Option<Offset>> {
+        offsets
+            .iter()
+            .map(|offset| offset.and_then(|offset| self.convert_offset(offset, unit)))
+            .collect()
+    }
+
+    /// Convert token reference offsets (sequences of Unicode code point positions) to the target
+    /// unit. Each code point is expanded to the positions of all its bytes or UTF-16 code units,
+    /// positions past the end of the text are dropped.
+    ///
+    /// # Parameters
+    /// - reference_offsets (`&[Vec<OffsetSize>]`): token reference offsets in Unicode code points
+    /// - unit (`OffsetUnit`): target unit
+    pub fn convert_reference_offsets(
+        &self,
+        reference_offsets: &[Vec<OffsetSize>],
+        unit: OffsetUnit,
+    ) -> Vec<Vec<OffsetSize>> {
+        reference_offsets
+            .iter()
+            .map(|positions| {
+                positions
+                    .iter()
+                    .filter_map(|position| {
+                        Some(
+                            self.convert_position(*position, unit)?
+                                ..self.convert_position(*position + 1, unit)?,
+                        )
+                    })
+                    .flatten()
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tokenizer::{BertTokenizer, Tokenizer, TruncationStrategy};
+    use crate::vocab::{BertVocab, SpecialTokenMap, Vocab};
+    use proptest::prelude::*;
+    use std::collections::HashMap;
+
+    fn tokenizer() -> BertTokenizer {
+        let values: HashMap<String, i64> = vec![
+            "[UNK]", "[CLS]", "[SEP]", "[PAD]", "[MASK]", "東", "京", "cafe", "naive", "!",
+        ]
+        .into_iter()
+        .enumerate()
+        .map(|(id, token)| (token.to_string(), id as i64))
+        .collect();
+        let special_token_map = SpecialTokenMap {
+            unk_token: "[UNK]".to_string(),
+            pad_token: Some("[PAD]".to_string()),
+            bos_token: None,
+            sep_token: Some("[SEP]".to_string()),
+            cls_token: Some("[CLS]".to_string()),
+            eos_token: None,
+            mask_token: Some("[MASK]".to_string()),
+            additional_special_tokens: None,
+        };
+        let vocab =
+            BertVocab::from_values_and_special_token_map(values, special_token_map).unwrap();
+        BertTokenizer::from_existing_vocab(vocab, false, false)
+    }
+
+    fn char_slice(text: &str, offset: Offset) -> String {
+        text.chars()
+            .skip(offset.begin as usize)
+            .take((offset.end - offset.begin) as usize)
+            .collect()
+    }
+
+    fn text_strategy() -> impl Strategy<Value = String> {
+        prop::collection::vec(
+            prop_oneof![
+                any::<char>(),
+                prop::char::range('a', 'z'),
+                Just(' '),
+                prop::char::range('\u{4e00}', '\u{9fff}'),
+                prop::char::range('\u{1f300}', '\u{1faff}'),
+                prop::char::range('\u{0300}', '\u{036f}'),
+            ],
+            0..32,
+        )
+        .prop_map(|characters| characters.into_iter().collect())
+    }
+
+    #[test]
+    fn test_offset_units() {
+        //        Given
+        let tokenizer = tokenizer();
+        let text = "東京 🚀 cafe\u{301} naïve!";
+
+        //        When
+        let encoding = tokenizer.encode(text, None, 128, &TruncationStrategy::LongestFirst, 0);
+        let byte_input = encoding.clone().into_offset_unit(text, OffsetUnit::Byte);
+        let utf16_input = encoding.clone().into_offset_unit(text, OffsetUnit::Utf16);
+
+        //        Then
+        let tokens: Vec<&str> = byte_input
+            .token_offsets
+            .iter()
+            .flatten()
+            .map(|offset| &text[offset.begin as usize..offset.end as usize])
+            .collect();
+        assert_eq!(tokens, vec!["東", "京", "🚀", "cafe\u{301}", "naïve", "!"]);
+        let utf16_text: Vec<u16> = text.encode_utf16().collect();
+        let tokens: Vec<String> = utf16_input
+            .token_offsets
+            .iter()
+            .flatten()
+            .map(|offset| {
+                String::from_utf16(&utf16_text[offset.begin as usize..offset.end as usize]).unwrap()
+            })
+            .collect();
+        assert_eq!(tokens, vec!["東", "京", "🚀", "cafe\u{301}", "naïve", "!"]);
+        assert_eq!(byte_input.reference_offsets[3], vec![7, 8, 9, 10]);
+        assert_eq!(utf16_input.reference_offsets[3], vec![3, 4]);
+        assert_eq!(
+            encoding.clone().into_offset_unit(text, OffsetUnit::Char),
+            encoding
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn prop_converted_offsets_slice_the_same_text(
+            text in text_strategy(),
+            begin in 0usize..40,
+            length in 0usize..40,
+        ) {
+            let converter = OffsetConverter::new(&text);
+            let num_chars = text.chars().count();
+            let begin = begin.min(num_chars) as OffsetSize;
+            let end = (begin as usize + length).min(num_chars) as OffsetSize;
+            let offset = Offset::new(begin, end);
+            let expected = char_slice(&text, offset);
+
+            let bytes = converter.convert_offset(offset, OffsetUnit::Byte).unwrap();
+            let utf16 = converter.convert_offset(offset, OffsetUnit::Utf16).unwrap();
+            let utf16_text: Vec<u16> = text.encode_utf16().collect();
+
+            prop_assert_eq!(&text[bytes.begin as usize..bytes.end as usize], expected.as_str());
+            prop_assert_eq!(
+                String::from_utf16(&utf16_text[utf16.begin as usize..utf16.end as usize]).unwrap(),
+                expected
+            );
+            prop_assert_eq!(
+                converter.convert_position(num_chars as OffsetSize + 1, OffsetUnit::Byte),
+                None
+            );
+        }
+
+        #[test]
+        fn prop_token_offsets_are_valid_in_all_units(text in text_strategy()) {
+            let tokenizer = tokenizer();
+            let tokens = tokenizer.tokenize_with_offsets(&text);
+            let byte_tokens = tokenizer.tokenize_with_offsets_in(&text, OffsetUnit::Byte);
+            let utf16_tokens = tokenizer.tokenize_with_offsets_in(&text, OffsetUnit::Utf16);
+            let utf16_text: Vec<u16> = text.encode_utf16().collect();
+
+            for ((offset, bytes), utf16) in tokens
+                .offsets
+                .iter()
+                .zip(&byte_tokens.offsets)
+                .zip(&utf16_tokens.offsets)
+            {
+                let (offset, bytes, utf16) = match (offset, bytes, utf16) {
+                    (Some(offset), Some(bytes), Some(utf16)) => (offset, bytes, utf16),
+                    (None, None, None) => continue,
+                    _ => return Err(TestCaseError::fail("offsets conversion changed a None offset")),
+                };
+                let expected = char_slice(&text, *offset);
+                prop_assert_eq!(text.get(bytes.begin as usize..bytes.end as usize), Some(expected.as_str()));
+                prop_assert_eq!(
+                    String::from_utf16(&utf16_text[utf16.begin as usize..utf16.end as usize]).unwrap(),
+                    expected
+                );
+            }
+            for (positions, byte_positions) in tokens
+                .reference_offsets
+                .iter()
+                .zip(&byte_tokens.reference_offsets)
+            {
+                let expected: usize = positions
+                    .iter()
+                    .map(|position| text.chars().nth(*position as usize).map_or(0, char::len_utf8))
+                    .sum();
+                prop_assert_eq!(byte_positions.len(), expected);
+            }
+        }
+    }
+}
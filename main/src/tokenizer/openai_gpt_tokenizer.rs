@@ -153,6 +153,16 @@ impl Tokenizer<OpenAiGptVocab> for OpenAiGptTokenizer {
         &mut self.vocab
     }
 
+    fn add_tokens(&mut self, tokens: &[&str]) {
+        self.vocab.add_tokens(tokens);
+        self.base_tokenizer.add_tokens(tokens);
+    }
+
+    fn add_extra_ids(&mut self, num_extra_ids: i64) {
+        self.vocab.add_extra_ids(num_extra_ids);
+        self.base_tokenizer.add_extra_ids(num_extra_ids);
+    }
+
     fn tokenize_to_tokens(&self, initial_token: TokenRef) -> Vec<Token> {
         let tokens: Vec<Token> = self
             .base_tokenizer
@@ -282,6 +292,28 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_openai_gpt_tokenizer_add_tokens() {
+        //        Given
+        let vocab = generate_test_vocab();
+        let merges = generate_test_merges();
+        let mut openai_gpt_tokenizer: OpenAiGptTokenizer =
+            OpenAiGptTokenizer::from_existing_vocab_and_merges(vocab, merges, true);
+
+        //        When
+        openai_gpt_tokenizer.add_tokens(&["<new>"]);
+
+        //        Then
+        assert_eq!(
+            openai_gpt_tokenizer.tokenize("The<new>earth"),
+            vec!["the</w>", "<new>", "ea", "rth</w>"]
+        );
+        assert_eq!(
+            openai_gpt_tokenizer.convert_tokens_to_ids(&["<new>"]),
+            vec![11]
+        );
+    }
+
     #[test]
     fn test_openai_gpt_tokenizer_no_lower_casing() {
         //        Given
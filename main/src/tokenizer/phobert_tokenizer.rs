@@ -0,0 +1,411 @@
+// Copyright 2020 VinAI Research and The HuggingFace Inc. team.
+// Copyright 2019-2020 Guillaume Becquin
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//     http://www.apache.org/licenses/LICENSE-2.0
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::error::TokenizerError;
+use crate::tokenizer::base_tokenizer::{
+    Mask, Offset, OffsetSize, Token, TokenIdsWithOffsets, TokenIdsWithSpecialTokens, TokenRef,
+    Tokenizer,
+};
+use crate::tokenizer::tokenization_utils::{
+    ctrl_bpe, fix_mask, split_on_bpe_pairs, split_on_special_tokens, BpeCache, SpecialTokenCache,
+};
+use crate::tokenizer::{MosesPreTokenizer, MultiThreadedTokenizer};
+use crate::vocab::bpe_vocab::BpePairVocab;
+use crate::vocab::{PhoBertVocab, Vocab};
+use regex::Regex;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::RwLock;
+
+/// # PhoBERT tokenizer
+/// PhoBERT tokenizer (fastBPE-based, also suitable for BERTweet-style models) performing:
+/// - splitting on special characters
+/// - Moses pre-tokenization, keeping word-segmented tokens (e.g. `sinh_viên`) intact
+/// - BPE tokenization (`@@` continuation suffix)
+///
+/// The input text is expected to be word-segmented (syllables of a word joined by `_`, as produced
+/// by VnCoreNLP's RDRSegmenter).
+pub struct PhoBertTokenizer {
+    vocab: PhoBertVocab,
+    special_token_cache: SpecialTokenCache,
+    bpe_ranks: BpePairVocab,
+    cache: BpeCache,
+    moses_pre_tokenizer: MosesPreTokenizer,
+}
+
+impl PhoBertTokenizer {
+    /// Create a new instance of a `PhoBertTokenizer`
+    /// Expects a fairseq dictionary file (`dict.txt`) and a fastBPE codes file as an input.
+    ///
+    /// # Parameters
+    /// - vocab_path (`&str`): path to the vocabulary file
+    /// - merges_path (`&str`): path to the BPE codes file (use as part of the BPE encoding process)
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use rust_tokenizers::tokenizer::{PhoBertTokenizer, Tokenizer};
+    /// let tokenizer =
+    ///     PhoBertTokenizer::from_file("path/to/dict/file", "path/to/bpe/codes/file").unwrap();
+    /// ```
+    pub fn from_file<V: AsRef<Path>, M: AsRef<Path>>(
+        vocab_path: V,
+        merges_path: M,
+    ) -> Result<PhoBertTokenizer, TokenizerError> {
+        let vocab = PhoBertVocab::from_file(vocab_path)?;
+        let bpe_ranks = BpePairVocab::from_fastbpe_file(merges_path)?;
+        Ok(Self::from_existing_vocab_and_merges(vocab, bpe_ranks))
+    }
+
+    /// Create a new instance of a `PhoBertTokenizer`
+    /// Expects a fairseq dictionary file, a fastBPE codes file and special token mapping file as inputs.
+    ///
+    /// # Parameters
+    /// - vocab_path (`&str`): path to the vocabulary file
+    /// - merges_path (`&str`): path to the BPE codes file (use as part of the BPE encoding process)
+    /// - special_token_mapping_path (`&str`): path to a special token mapping file to overwrite default special tokens
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use rust_tokenizers::tokenizer::{PhoBertTokenizer, Tokenizer};
+    /// let tokenizer = PhoBertTokenizer::from_file_with_special_token_mapping(
+    ///     "path/to/dict/file",
+    ///     "path/to/bpe/codes/file",
+    ///     "path/to/special/token/mapping/file",
+    /// )
+    /// .unwrap();
+    /// ```
+    pub fn from_file_with_special_token_mapping<V: AsRef<Path>, M: AsRef<Path>, S: AsRef<Path>>(
+        vocab_path: V,
+        merges_path: M,
+        special_token_mapping_path: S,
+    ) -> Result<PhoBertTokenizer, TokenizerError> {
+        let vocab = PhoBertVocab::from_file_with_special_token_mapping(
+            vocab_path,
+            special_token_mapping_path,
+        )?;
+        let bpe_ranks = BpePairVocab::from_fastbpe_file(merges_path)?;
+        Ok(Self::from_existing_vocab_and_merges(vocab, bpe_ranks))
+    }
+
+    /// Create a new instance of a `PhoBertTokenizer` from an existing vocabulary and merges
+    ///
+    /// # Parameters
+    /// - vocab (`PhoBertVocab`): PhoBERT vocabulary
+    /// - merges (`BpePairVocab`): BPE pairs vocabulary
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use rust_tokenizers::tokenizer::{PhoBertTokenizer, Tokenizer};
+    /// use rust_tokenizers::vocab::{BpePairVocab, PhoBertVocab, Vocab};
+    /// let vocab = PhoBertVocab::from_file("path/to/dict/file").unwrap();
+    /// let merges = BpePairVocab::from_fastbpe_file("path/to/bpe/codes/file").unwrap();
+    ///
+    /// let tokenizer = PhoBertTokenizer::from_existing_vocab_and_merges(vocab, merges);
+    /// ```
+    pub fn from_existing_vocab_and_merges(
+        vocab: PhoBertVocab,
+        merges: BpePairVocab,
+    ) -> PhoBertTokenizer {
+        let mut moses_pre_tokenizer = MosesPreTokenizer::new("vi");
+        moses_pre_tokenizer.set_escape(false);
+        moses_pre_tokenizer.set_normalize_punctuation(false);
+        moses_pre_tokenizer.add_protected_pattern(Regex::new(r"\w+(?:_\w+)+").unwrap());
+        PhoBertTokenizer {
+            vocab,
+            special_token_cache: RwLock::new(None),
+            bpe_ranks: merges,
+            cache: RwLock::new(HashMap::new()),
+            moses_pre_tokenizer,
+        }
+    }
+}
+
+impl Tokenizer<PhoBertVocab> for PhoBertTokenizer {
+    fn vocab(&self) -> &PhoBertVocab {
+        &self.vocab
+    }
+    fn vocab_mut(&mut self) -> &mut PhoBertVocab {
+        &mut self.vocab
+    }
+
+    fn tokenize_to_tokens(&self, initial_token: TokenRef) -> Vec<Token> {
+        let tokens = split_on_special_tokens(initial_token, &self.vocab, &self.special_token_cache);
+
+        let mut sub_tokens = Vec::new();
+        for token in tokens {
+            if token.mask != Mask::Special && token.mask != Mask::Unknown {
+                for word in self.moses_pre_tokenizer.tokenize_to_tokens(token) {
+                    sub_tokens.extend(split_on_bpe_pairs(
+                        word.as_ref(),
+                        ctrl_bpe,
+                        &self.bpe_ranks,
+                        &self.cache,
+                        false,
+                    ));
+                }
+            } else {
+                sub_tokens.push(token.to_owned());
+            }
+        }
+        fix_mask(&mut sub_tokens);
+        sub_tokens
+    }
+
+    fn convert_tokens_to_string(&self, tokens: Vec<String>) -> String {
+        tokens.join(" ").replace("@@ ", "").trim().to_owned()
+    }
+
+    fn build_input_with_special_tokens(
+        &self,
+        tokens_ids_with_offsets_1: TokenIdsWithOffsets,
+        tokens_ids_with_offsets_2: Option<TokenIdsWithOffsets>,
+    ) -> TokenIdsWithSpecialTokens {
+        let mut output: Vec<i64> = vec![];
+        let mut token_segment_ids: Vec<i8> = vec![];
+        let mut special_tokens_mask: Vec<i8> = vec![];
+        let mut offsets: Vec<Option<Offset>> = vec![];
+        let mut original_offsets: Vec<Vec<OffsetSize>> = vec![];
+        let mut mask: Vec<Mask> = vec![];
+        special_tokens_mask.push(1);
+        special_tokens_mask.extend(vec![0; tokens_ids_with_offsets_1.ids.len()]);
+        special_tokens_mask.push(1);
+        token_segment_ids.extend(vec![0; tokens_ids_with_offsets_1.ids.len() + 2]);
+        output.push(self.vocab.token_to_id(self.vocab.get_cls_value()));
+        output.extend(tokens_ids_with_offsets_1.ids);
+        output.push(self.vocab.token_to_id(self.vocab.get_sep_value()));
+        offsets.push(None);
+        offsets.extend(tokens_ids_with_offsets_1.offsets);
+        offsets.push(None);
+        original_offsets.push(vec![]);
+        original_offsets.extend(tokens_ids_with_offsets_1.reference_offsets);
+        original_offsets.push(vec![]);
+        mask.push(Mask::Special);
+        mask.extend(tokens_ids_with_offsets_1.masks);
+        mask.push(Mask::Special);
+        if let Some(tokens_ids_with_offsets_2_value) = tokens_ids_with_offsets_2 {
+            let length = tokens_ids_with_offsets_2_value.ids.len();
+            special_tokens_mask.push(1);
+            special_tokens_mask.extend(vec![0; length]);
+            special_tokens_mask.push(1);
+            token_segment_ids.extend(vec![0; length + 2]);
+            output.push(self.vocab.token_to_id(self.vocab.get_sep_value()));
+            output.extend(tokens_ids_with_offsets_2_value.ids);
+            output.push(self.vocab.token_to_id(self.vocab.get_sep_value()));
+            offsets.push(None);
+            offsets.extend(tokens_ids_with_offsets_2_value.offsets);
+            offsets.push(None);
+            original_offsets.push(vec![]);
+            original_offsets.extend(tokens_ids_with_offsets_2_value.reference_offsets);
+            original_offsets.push(vec![]);
+            mask.push(Mask::Special);
+            mask.extend(tokens_ids_with_offsets_2_value.masks);
+            mask.push(Mask::Special);
+        }
+        TokenIdsWithSpecialTokens {
+            token_ids: output,
+            segment_ids: token_segment_ids,
+            special_tokens_mask,
+            token_offsets: offsets,
+            reference_offsets: original_offsets,
+            mask,
+        }
+    }
+}
+
+impl MultiThreadedTokenizer<PhoBertVocab> for PhoBertTokenizer {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tokenizer::base_tokenizer::TruncationStrategy;
+    use crate::vocab::base_vocab::{swap_key_values, SpecialTokenMap};
+
+    fn generate_test_vocab() -> PhoBertVocab {
+        let values: HashMap<String, i64> = [
+            ("<s>".to_owned(), 0),
+            ("<pad>".to_owned(), 1),
+            ("</s>".to_owned(), 2),
+            ("<unk>".to_owned(), 3),
+            ("Tôi".to_owned(), 4),
+            ("là".to_owned(), 5),
+            ("sinh_viên".to_owned(), 6),
+            ("giỏ@@".to_owned(), 7),
+            ("i".to_owned(), 8),
+            (".".to_owned(), 9),
+            ("<mask>".to_owned(), 10),
+        ]
+        .iter()
+        .cloned()
+        .collect();
+
+        let special_token_map = SpecialTokenMap {
+            unk_token: "<unk>".to_string(),
+            pad_token: Some("<pad>".to_string()),
+            bos_token: Some("<s>".to_string()),
+            sep_token: Some("</s>".to_string()),
+            cls_token: Some("<s>".to_string()),
+            eos_token: Some("</s>".to_string()),
+            mask_token: Some("<mask>".to_string()),
+            additional_special_tokens: None,
+        };
+        let special_values: HashMap<String, i64> = [
+            ("<s>".to_owned(), 0),
+            ("<pad>".to_owned(), 1),
+            ("</s>".to_owned(), 2),
+            ("<unk>".to_owned(), 3),
+            ("<mask>".to_owned(), 10),
+        ]
+        .iter()
+        .cloned()
+        .collect();
+
+        let indices = swap_key_values(&values);
+        let special_indices = swap_key_values(&special_values);
+
+        PhoBertVocab {
+            values,
+            indices,
+            special_token_map,
+            special_values,
+            special_indices,
+        }
+    }
+
+    fn generate_test_merges() -> BpePairVocab {
+        let values: HashMap<(String, String), i64> = [
+            (("T".to_owned(), "ô".to_owned()), 0),
+            (("Tô".to_owned(), "i</w>".to_owned()), 1),
+            (("l".to_owned(), "à</w>".to_owned()), 2),
+            (("g".to_owned(), "i".to_owned()), 3),
+            (("gi".to_owned(), "ỏ".to_owned()), 4),
+            (("s".to_owned(), "i".to_owned()), 5),
+            (("si".to_owned(), "n".to_owned()), 6),
+            (("sin".to_owned(), "h".to_owned()), 7),
+            (("sinh".to_owned(), "_".to_owned()), 8),
+            (("sinh_".to_owned(), "v".to_owned()), 9),
+            (("sinh_v".to_owned(), "i".to_owned()), 10),
+            (("sinh_vi".to_owned(), "ê".to_owned()), 11),
+            (("sinh_viê".to_owned(), "n</w>".to_owned()), 12),
+        ]
+        .iter()
+        .cloned()
+        .collect();
+
+        BpePairVocab { values }
+    }
+
+    #[test]
+    fn test_phobert_tokenizer() {
+        //        Given
+        let vocab = generate_test_vocab();
+        let merges = generate_test_merges();
+        let phobert_tokenizer = PhoBertTokenizer::from_existing_vocab_and_merges(vocab, merges);
+        let test_tuples = [
+            (
+                "Tôi là sinh_viên giỏi.",
+                vec!["Tôi", "là", "sinh_viên", "giỏ@@", "i", "."],
+            ),
+            ("Tôi <mask>", vec!["Tôi", "<mask>"]),
+            ("", vec![]),
+        ];
+        let source_texts: Vec<&str> = test_tuples.iter().map(|v| v.0).collect();
+        let expected_results: Vec<Vec<&str>> = test_tuples.iter().map(|v| v.1.clone()).collect();
+
+        //        When & Then
+        for (source_text, expected_result) in test_tuples.iter() {
+            assert_eq!(phobert_tokenizer.tokenize(source_text), *expected_result);
+        }
+
+        assert_eq!(
+            MultiThreadedTokenizer::tokenize_list(&phobert_tokenizer, &source_texts),
+            expected_results
+        );
+    }
+
+    #[test]
+    fn test_encode_pair() {
+        //        Given
+        let vocab = generate_test_vocab();
+        let merges = generate_test_merges();
+        let phobert_tokenizer = PhoBertTokenizer::from_existing_vocab_and_merges(vocab, merges);
+
+        //        When
+        let encoded_input = phobert_tokenizer.encode(
+            "sinh_viên giỏi",
+            Some("Tôi"),
+            128,
+            &TruncationStrategy::LongestFirst,
+            0,
+        );
+
+        //        Then
+        assert_eq!(encoded_input.token_ids, vec![0, 6, 7, 8, 2, 2, 4, 2]);
+        assert_eq!(encoded_input.segment_ids, vec![0; 8]);
+        assert_eq!(
+            encoded_input.special_tokens_mask,
+            vec![1, 0, 0, 0, 1, 1, 0, 1]
+        );
+        assert_eq!(
+            encoded_input.token_offsets,
+            vec![
+                None,
+                Some(Offset::new(0, 9)),
+                Some(Offset::new(10, 13)),
+                Some(Offset::new(13, 14)),
+                None,
+                None,
+                Some(Offset::new(0, 3)),
+                None
+            ]
+        );
+        assert_eq!(
+            encoded_input.mask,
+            vec![
+                Mask::Special,
+                Mask::None,
+                Mask::Begin,
+                Mask::Continuation,
+                Mask::Special,
+                Mask::Special,
+                Mask::None,
+                Mask::Special
+            ]
+        );
+    }
+
+    #[test]
+    fn test_decode() {
+        //        Given
+        let vocab = generate_test_vocab();
+        let merges = generate_test_merges();
+        let phobert_tokenizer = PhoBertTokenizer::from_existing_vocab_and_merges(vocab, merges);
+        let test_tuples = [
+            (
+                vec![0, 4, 5, 6, 7, 8, 9, 2],
+                true,
+                "Tôi là sinh_viên giỏi .",
+            ),
+            (vec![0, 4, 2], false, "<s> Tôi </s>"),
+        ];
+
+        //        When & Then
+        for (token_ids, skip_special_tokens, expected_result) in test_tuples.iter() {
+            assert_eq!(
+                phobert_tokenizer.decode(token_ids, *skip_special_tokens, false),
+                *expected_result
+            );
+        }
+    }
+}
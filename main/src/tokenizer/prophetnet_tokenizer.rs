@@ -15,10 +15,10 @@ use std::path::Path;
 
 use crate::error::TokenizerError;
 use crate::tokenizer::base_tokenizer::{
-    BaseTokenizer, Mask, MultiThreadedTokenizer, Offset, OffsetSize, Token, TokenIdsWithOffsets,
-    TokenIdsWithSpecialTokens, TokenRef, Tokenizer,
+    push_word_tokens, BaseTokenizer, Mask, MultiThreadedTokenizer, Offset, OffsetSize, Token,
+    TokenIdsWithOffsets, TokenIdsWithSpecialTokens, TokenRef, Tokenizer, WordTokenCount,
 };
-use crate::tokenizer::tokenization_utils::tokenize_wordpiece;
+use crate::tokenizer::tokenization_utils::{count_wordpiece, tokenize_wordpiece};
 use crate::vocab::{ProphetNetVocab, Vocab};
 
 /// # ProphetNet tokenizer
@@ -143,6 +143,16 @@ impl Tokenizer<ProphetNetVocab> for ProphetNetTokenizer {
         &mut self.vocab
     }
 
+    fn add_tokens(&mut self, tokens: &[&str]) {
+        self.vocab.add_tokens(tokens);
+        self.base_tokenizer.add_tokens(tokens);
+    }
+
+    fn add_extra_ids(&mut self, num_extra_ids: i64) {
+        self.vocab.add_extra_ids(num_extra_ids);
+        self.base_tokenizer.add_extra_ids(num_extra_ids);
+    }
+
     fn tokenize_to_tokens(&self, initial_token: TokenRef) -> Vec<Token> {
         //the base tokenizers does most of the work, we simply add a wordpiece tokenizer on top
         self.base_tokenizer
@@ -152,6 +162,16 @@ impl Tokenizer<ProphetNetVocab> for ProphetNetTokenizer {
             .collect()
     }
 
+    fn count_word_tokens(&self, text: &str) -> Vec<WordTokenCount> {
+        let mut words = Vec::new();
+        self.base_tokenizer
+            .for_each_word_token(text, |begin, end, token| {
+                let num_tokens = count_wordpiece(token, &self.vocab, 100);
+                push_word_tokens(&mut words, begin, end, num_tokens)
+            });
+        words
+    }
+
     fn convert_tokens_to_string(&self, tokens: Vec<String>) -> String {
         tokens.join(" ").replace(" ##", "").trim().to_owned()
     }
@@ -312,6 +332,27 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_prophetnet_tokenizer_add_tokens() {
+        //        Given
+        let vocab = generate_test_vocab();
+        let mut prophetnet_tokenizer: ProphetNetTokenizer =
+            ProphetNetTokenizer::from_existing_vocab(vocab, true, true);
+
+        //        When
+        prophetnet_tokenizer.add_tokens(&["<new>"]);
+
+        //        Then
+        assert_eq!(
+            prophetnet_tokenizer.tokenize("Hello<new>world!"),
+            vec!["hello", "<new>", "world", "!"]
+        );
+        assert_eq!(
+            prophetnet_tokenizer.convert_tokens_to_ids(&["<new>"]),
+            vec![15]
+        );
+    }
+
     #[test]
     fn test_prophetnet_tokenizer_no_lower_casing() {
         //        Given
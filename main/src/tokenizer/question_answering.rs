@@ -0,0 +1,539 @@
+// Copyright 2019-2020 Guillaume Becquin
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//     http://www.apache.org/licenses/LICENSE-2.0
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::error::TokenizerError;
+use crate::tokenizer::base_tokenizer::{
+    Offset, OffsetSize, TokenIdsWithOffsets, TokenizedInput, Tokenizer,
+};
+use crate::vocab::{SpecialTokenValues, Vocab};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::ops::Range;
+
+/// # Question answering window
+/// Encoding of the question with a window of the context, as returned by `encode_qa`.
+/// The first window is identical to the encoding of the question and context with
+/// `TruncationStrategy::OnlySecond`, the following windows cover the rest of the context with
+/// `stride` tokens overlapping with the previous window.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QaWindow {
+    /// Encoding of the question and context window (offsets of the context tokens refer to the context)
+    pub encoding: TokenizedInput,
+    /// Positions of the context tokens in the encoding
+    pub context_positions: Range<usize>,
+    /// Position of the classification token, used as target for answers outside of the window
+    pub cls_index: usize,
+}
+
+impl QaWindow {
+    /// Map an answer span of the context to start and end token positions in the window.
+    /// Returns `(cls_index, cls_index)` if the answer is not fully contained in the window.
+    ///
+    /// # Parameters
+    /// - start (`usize`): start of the answer in the context (in Unicode characters)
+    /// - end (`usize`): end of the answer in the context (in Unicode characters, exclusive)
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use rust_tokenizers::tokenizer::{encode_qa, BertTokenizer};
+    /// let tokenizer = BertTokenizer::from_file("path/to/vocab", true, true).unwrap();
+    /// let context = "John lives in New York";
+    /// let windows = encode_qa(&tokenizer, "Where does John live?", context, 384, 128).unwrap();
+    /// let positions: Vec<(usize, usize)> = windows
+    ///     .iter()
+    ///     .map(|window| window.answer_positions(14, 22))
+    ///     .collect();
+    /// ```
+    pub fn answer_positions(&self, start: usize, end: usize) -> (usize, usize) {
+        let offsets = &self.encoding.token_offsets;
+        let context_offsets = || {
+            self.context_positions
+                .clone()
+                .filter_map(move |position| offsets[position].map(|offset| (position, offset)))
+        };
+        let covers_start = context_offsets()
+            .next()
+            .is_some_and(|(_, offset)| offset.begin as usize <= start);
+        let covers_end = context_offsets()
+            .next_back()
+            .is_some_and(|(_, offset)| offset.end as usize >= end);
+        if start >= end || !covers_start || !covers_end {
+            return (self.cls_index, self.cls_index);
+        }
+        let start_position = context_offsets()
+            .find(|(_, offset)| offset.end as usize > start)
+            .map(|(position, _)| position);
+        let end_position = context_offsets()
+            .rev()
+            .find(|(_, offset)| (offset.begin as usize) < end)
+            .map(|(position, _)| position);
+        match (start_position, end_position) {
+            (Some(start_position), Some(end_position)) if start_position <= end_position => {
+                (start_position, end_position)
+            }
+            _ => (self.cls_index, self.cls_index),
+        }
+    }
+}
+
+/// # Question answering prediction
+/// Answer span decoded from the start and end logits of a model.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QaAnswer {
+    /// Text of the answer
+    pub text: String,
+    /// Span of the answer in the context (in Unicode characters)
+    pub offset: Offset,
+    /// Probability of the span (product of the start and end probabilities in its window)
+    pub score: f32,
+    /// Index of the window the answer was found in
+    pub window: usize,
+}
+
+/// Encode a question and a context for extractive question answering. The context is split in
+/// windows of at most `max_len` tokens (including the question and special tokens), consecutive
+/// windows sharing `stride` context tokens. The question is never truncated.
+///
+/// # Parameters
+/// - tokenizer: tokenizer used to encode the question and context
+/// - question (`&str`): question
+/// - context (`&str`): context containing the answer
+/// - max_len (`usize`): maximum length of each window
+/// - stride (`usize`): number of context tokens shared by consecutive windows
+///
+/// # Returns
+/// - `Vec<QaWindow>` encodings of the context windows, with at least one window
+///
+/// # Example
+///
+/// ```no_run
+/// use rust_tokenizers::tokenizer::{encode_qa, BertTokenizer};
+/// let tokenizer = BertTokenizer::from_file("path/to/vocab", true, true).unwrap();
+/// let windows = encode_qa(
+///     &tokenizer,
+///     "Where does John live?",
+///     "John lives in New York",
+///     384,
+///     128,
+/// )
+/// .unwrap();
+/// ```
+pub fn encode_qa<T, V>(
+    tokenizer: &T,
+    question: &str,
+    context: &str,
+    max_len: usize,
+    stride: usize,
+) -> Result<Vec<QaWindow>, TokenizerError>
+where
+    T: Tokenizer<V> + ?Sized,
+    V: SpecialTokenValues,
+{
+    let question = token_ids_with_offsets(tokenizer, question);
+    let context = token_ids_with_offsets(tokenizer, context);
+    let num_special_tokens = tokenizer
+        .build_input_with_special_tokens(empty_sequence(), Some(empty_sequence()))
+        .token_ids
+        .len();
+
+    let window_size = max_len.saturating_sub(question.ids.len() + num_special_tokens);
+    if window_size <= stride {
+        return Err(TokenizerError::ValueError(format!(
+            "Maximum length {max_len} leaves no room for context windows with a stride of {stride}"
+        )));
+    }
+    let num_tokens = context.ids.len();
+    let mut windows = vec![];
+    let mut start = 0;
+    loop {
+        let end = (start + window_size).min(num_tokens);
+        let window = TokenIdsWithOffsets {
+            ids: context.ids[start..end].to_vec(),
+            offsets: context.offsets[start..end].to_vec(),
+            reference_offsets: context.reference_offsets[start..end].to_vec(),
+            masks: context.masks[start..end].to_vec(),
+        };
+        windows.push(build_window(
+            tokenizer,
+            question.clone(),
+            window,
+            num_tokens - end,
+        ));
+        if end == num_tokens {
+            break;
+        }
+        start = end - stride;
+    }
+    Ok(windows)
+}
+
+/// Decode the `top_k` most likely answers from the start and end logits predicted for each window.
+/// Spans must lie within the context tokens of a window and have at most `max_answer_len` tokens.
+/// The logits of the other tokens are masked before computing the start and end probabilities. An
+/// answer found in several windows is returned once, with its highest score. Windows without
+/// context tokens (e.g. for an empty context) have no answer.
+///
+/// # Parameters
+/// - context (`&str`): context the windows were built from
+/// - windows (`&[QaWindow]`): windows returned by `encode_qa`
+/// - start_logits (`&[Vec<f32>]`): start logits of each window
+/// - end_logits (`&[Vec<f32>]`): end logits of each window
+/// - top_k (`usize`): maximum number of answers to return
+/// - max_answer_len (`usize`): maximum length of an answer (in tokens)
+///
+/// # Returns
+/// - `Vec<QaAnswer>` answers sorted by decreasing score
+///
+/// # Example
+///
+/// ```no_run
+/// use rust_tokenizers::tokenizer::{decode_qa_answers, encode_qa, BertTokenizer};
+/// let tokenizer = BertTokenizer::from_file("path/to/vocab", true, true).unwrap();
+/// let context = "John lives in New York";
+/// let windows = encode_qa(&tokenizer, "Where does John live?", context, 384, 128).unwrap();
+/// // Logits predicted by a question answering model for each window
+/// let start_logits: Vec<Vec<f32>> = vec![];
+/// let end_logits: Vec<Vec<f32>> = vec![];
+/// let answers = decode_qa_answers(context, &windows, &start_logits, &end_logits, 5, 30).unwrap();
+/// ```
+pub fn decode_qa_answers(
+    context: &str,
+    windows: &[QaWindow],
+    start_logits: &[Vec<f32>],
+    end_logits: &[Vec<f32>],
+    top_k: usize,
+    max_answer_len: usize,
+) -> Result<Vec<QaAnswer>, TokenizerError> {
+    if start_logits.len() != windows.len() || end_logits.len() != windows.len() {
+        return Err(TokenizerError::ValueError(format!(
+            "Number of start ({}) and end ({}) logits does not match the number of windows ({})",
+            start_logits.len(),
+            end_logits.len(),
+            windows.len()
+        )));
+    }
+    let mut answers: HashMap<(OffsetSize, OffsetSize), QaAnswer> = HashMap::new();
+    for (window_index, ((window, start_logits), end_logits)) in
+        windows.iter().zip(start_logits).zip(end_logits).enumerate()
+    {
+        let num_tokens = window.encoding.token_ids.len();
+        if start_logits.len() != num_tokens || end_logits.len() != num_tokens {
+            return Err(TokenizerError::ValueError(format!(
+                "Number of logits does not match the length of window {window_index} ({num_tokens})"
+            )));
+        }
+        if window.context_positions.is_empty() {
+            continue;
+        }
+        let start_probabilities = context_softmax(start_logits, &window.context_positions);
+        let end_probabilities = context_softmax(end_logits, &window.context_positions);
+        let offsets = &window.encoding.token_offsets;
+        for start in window.context_positions.clone() {
+            let start_offset = match offsets[start] {
+                Some(offset) => offset,
+                None => continue,
+            };
+            let last = (start + max_answer_len).min(window.context_positions.end);
+            for end in start..last {
+                let end_offset = match offsets[end] {
+                    Some(offset) => offset,
+                    None => continue,
+                };
+                let score = start_probabilities[start] * end_probabilities[end];
+                let offset = Offset::new(start_offset.begin, end_offset.end);
+                if answers
+                    .get(&(offset.begin, offset.end))
+                    .is_none_or(|answer| answer.score < score)
+                {
+                    answers.insert(
+                        (offset.begin, offset.end),
+                        QaAnswer {
+                            text: context_span(context, offset),
+                            offset,
+                            score,
+                            window: window_index,
+                        },
+                    );
+                }
+            }
+        }
+    }
+    let mut answers: Vec<QaAnswer> = answers.into_values().collect();
+    answers.sort_by(|a, b| {
+        b.score
+            .partial_cmp(&a.score)
+            .unwrap_or(Ordering::Equal)
+            .then(a.offset.begin.cmp(&b.offset.begin))
+            .then(a.offset.end.cmp(&b.offset.end))
+    });
+    answers.truncate(top_k);
+    Ok(answers)
+}
+
+fn token_ids_with_offsets<T: Vocab, U: Tokenizer<T> + ?Sized>(
+    tokenizer: &U,
+    text: &str,
+) -> TokenIdsWithOffsets {
+    let tokens = tokenizer.tokenize_with_offsets(text);
+    TokenIdsWithOffsets {
+        ids: tokenizer.convert_tokens_to_ids(&tokens.tokens),
+        offsets: tokens.offsets,
+        reference_offsets: tokens.reference_offsets,
+        masks: tokens.masks,
+    }
+}
+
+fn empty_sequence() -> TokenIdsWithOffsets {
+    TokenIdsWithOffsets {
+        ids: vec![],
+        offsets: vec![],
+        reference_offsets: vec![],
+        masks: vec![],
+    }
+}
+
+fn build_window<T: SpecialTokenValues, U: Tokenizer<T> + ?Sized>(
+    tokenizer: &U,
+    question: TokenIdsWithOffsets,
+    window: TokenIdsWithOffsets,
+    num_truncated_tokens: usize,
+) -> QaWindow {
+    let num_question_tokens = question.ids.len();
+    let num_context_tokens = window.ids.len();
+    let encoding = tokenizer.build_input_with_special_tokens(question, Some(window));
+    // The question tokens precede the context tokens, both being contiguous in the encoding
+    let context_start = encoding
+        .special_tokens_mask
+        .iter()
+        .enumerate()
+        .filter(|(_, special)| **special == 0)
+        .map(|(position, _)| position)
+        .nth(num_question_tokens)
+        .unwrap_or(encoding.token_ids.len());
+    // The classification token is not necessarily the first special token (e.g. XLNet appends it)
+    let vocab = tokenizer.vocab();
+    let cls_id = vocab
+        .cls_value()
+        .map(|cls_value| vocab.token_to_id(cls_value));
+    let cls_index = encoding
+        .token_ids
+        .iter()
+        .zip(encoding.special_tokens_mask.iter())
+        .position(|(token_id, special)| *special == 1 && Some(*token_id) == cls_id)
+        .or_else(|| {
+            encoding
+                .special_tokens_mask
+                .iter()
+                .position(|special| *special == 1)
+        })
+        .unwrap_or(0);
+    QaWindow {
+        encoding: TokenizedInput {
+            token_ids: encoding.token_ids,
+            segment_ids: encoding.segment_ids,
+            special_tokens_mask: encoding.special_tokens_mask,
+            overflowing_tokens: vec![],
+            num_truncated_tokens,
+            token_offsets: encoding.token_offsets,
+            reference_offsets: encoding.reference_offsets,
+            mask: encoding.mask,
+        },
+        context_positions: context_start..context_start + num_context_tokens,
+        cls_index,
+    }
+}
+
+fn context_softmax(logits: &[f32], context_positions: &Range<usize>) -> Vec<f32> {
+    let max_logit = logits[context_positions.clone()]
+        .iter()
+        .cloned()
+        .fold(f32::NEG_INFINITY, f32::max);
+    let exponentials: Vec<f32> = logits
+        .iter()
+        .enumerate()
+        .map(|(position, logit)| {
+            if context_positions.contains(&position) {
+                (logit - max_logit).exp()
+            } else {
+                0.0
+            }
+        })
+        .collect();
+    let sum: f32 = exponentials.iter().sum();
+    exponentials.iter().map(|value| value / sum).collect()
+}
+
+fn context_span(context: &str, offset: Offset) -> String {
+    context
+        .chars()
+        .skip(offset.begin as usize)
+        .take((offset.end - offset.begin) as usize)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tokenizer::{BertTokenizer, TruncationStrategy, XLNetTokenizer};
+    use crate::vocab::{BertVocab, SentencePieceModel, SpecialTokenMap, XLNetVocab};
+
+    fn tokenizer() -> BertTokenizer {
+        let values: HashMap<String, i64> = vec![
+            "[UNK]", "[CLS]", "[SEP]", "[PAD]", "[MASK]", "where", "does", "john", "live", "?",
+            "lives", "in", "new", "york", "with", "his", "dog",
+        ]
+        .into_iter()
+        .enumerate()
+        .map(|(id, token)| (token.to_string(), id as i64))
+        .collect();
+        let special_token_map = SpecialTokenMap {
+            unk_token: "[UNK]".to_string(),
+            pad_token: Some("[PAD]".to_string()),
+            bos_token: None,
+            sep_token: Some("[SEP]".to_string()),
+            cls_token: Some("[CLS]".to_string()),
+            eos_token: None,
+            mask_token: Some("[MASK]".to_string()),
+            additional_special_tokens: None,
+        };
+        let vocab =
+            BertVocab::from_values_and_special_token_map(values, special_token_map).unwrap();
+        BertTokenizer::from_existing_vocab(vocab, true, true)
+    }
+
+    #[test]
+    fn test_encode_qa_windows() {
+        //        Given
+        let tokenizer = tokenizer();
+        let question = "Where does John live?";
+        let context = "John lives in New York with his dog";
+
+        //        When
+        let windows = encode_qa(&tokenizer, question, context, 12, 2).unwrap();
+        let positions: Vec<(usize, usize)> = windows
+            .iter()
+            .map(|window| window.answer_positions(14, 22))
+            .collect();
+
+        //        Then
+        let encoding = tokenizer.encode(
+            question,
+            Some(context),
+            12,
+            &TruncationStrategy::OnlySecond,
+            0,
+        );
+        assert_eq!(windows.len(), 3);
+        assert_eq!(windows[0].encoding.token_ids, encoding.token_ids);
+        assert_eq!(windows[0].encoding.token_offsets, encoding.token_offsets);
+        assert_eq!(
+            windows
+                .iter()
+                .map(|window| window.context_positions.clone())
+                .collect::<Vec<_>>(),
+            vec![7..11, 7..11, 7..11]
+        );
+        assert_eq!(positions, vec![(0, 0), (8, 9), (0, 0)]);
+        assert!(windows.iter().all(|window| window.cls_index == 0));
+        assert!(encode_qa(&tokenizer, question, context, 10, 2).is_err());
+    }
+
+    #[test]
+    fn test_decode_qa_answers() {
+        //        Given
+        let tokenizer = tokenizer();
+        let context = "John lives in New York with his dog";
+        let windows = encode_qa(&tokenizer, "Where does John live?", context, 12, 2).unwrap();
+        let mut start_logits = vec![vec![0.0; 12]; 3];
+        let mut end_logits = vec![vec![0.0; 12]; 3];
+        start_logits[1][8] = 5.0;
+        end_logits[1][9] = 5.0;
+        start_logits[1][0] = 10.0;
+
+        //        When
+        let answers =
+            decode_qa_answers(context, &windows, &start_logits, &end_logits, 2, 3).unwrap();
+
+        //        Then
+        assert_eq!(answers.len(), 2);
+        assert_eq!(answers[0].text, "New York");
+        assert_eq!(answers[0].offset, Offset::new(14, 22));
+        assert_eq!(answers[0].window, 1);
+        assert!(answers[0].score > answers[1].score);
+        assert!(answers[0].score < 1.0);
+        assert!(
+            decode_qa_answers(context, &windows, &start_logits[..2], &end_logits, 2, 3).is_err()
+        );
+    }
+
+    #[test]
+    fn test_encode_qa_cls_index() -> anyhow::Result<()> {
+        //        Given
+        let model_path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/fixtures/bigbird/spiece.model"
+        );
+        let values: HashMap<String, i64> = vec![
+            "<unk>", "<s>", "</s>", "<cls>", "<sep>", "<pad>", "<mask>", "▁hello", "▁world",
+        ]
+        .into_iter()
+        .enumerate()
+        .map(|(id, token)| (token.to_string(), id as i64))
+        .collect();
+        let special_token_map = SpecialTokenMap {
+            unk_token: "<unk>".to_string(),
+            pad_token: Some("<pad>".to_string()),
+            bos_token: Some("<s>".to_string()),
+            sep_token: Some("<sep>".to_string()),
+            cls_token: Some("<cls>".to_string()),
+            eos_token: Some("</s>".to_string()),
+            mask_token: Some("<mask>".to_string()),
+            additional_special_tokens: None,
+        };
+        let vocab = XLNetVocab::from_values_and_special_token_map(values, special_token_map)?;
+        let model = SentencePieceModel::from_file(model_path)?;
+        let tokenizer = XLNetTokenizer::from_existing_vocab_and_model(vocab, model, false, false);
+
+        //        When
+        let windows = encode_qa(&tokenizer, "hello", "hello world", 32, 2)?;
+
+        //        Then
+        assert_eq!(windows.len(), 1);
+        let encoding = &windows[0].encoding;
+        assert_eq!(windows[0].cls_index, encoding.token_ids.len() - 1);
+        assert_eq!(encoding.token_ids[windows[0].cls_index], 3);
+        assert_eq!(
+            windows[0].answer_positions(0, 5),
+            (
+                windows[0].context_positions.start,
+                windows[0].context_positions.start
+            )
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_qa_answers_empty_context() {
+        //        Given
+        let tokenizer = tokenizer();
+        let windows = encode_qa(&tokenizer, "Where does John live?", "", 12, 2).unwrap();
+        let logits = vec![vec![0.0; windows[0].encoding.token_ids.len()]];
+
+        //        When
+        let answers = decode_qa_answers("", &windows, &logits, &logits, 2, 3).unwrap();
+
+        //        Then
+        assert_eq!(windows.len(), 1);
+        assert!(windows[0].context_positions.is_empty());
+        assert!(answers.is_empty());
+    }
+}
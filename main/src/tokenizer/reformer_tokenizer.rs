@@ -15,7 +15,7 @@ use crate::error::TokenizerError;
 use crate::tokenizer::base_tokenizer::{Token, TokenRef};
 use crate::tokenizer::tokenization_utils::{
     bpe, clean_text, decompose_nfkc, fix_mask, is_whitespace, lowercase, split_on_bpe_pairs,
-    split_on_special_tokens, whitespace_tokenize, BpeCache,
+    split_on_special_tokens, whitespace_tokenize, BpeCache, SpecialTokenCache,
 };
 use crate::tokenizer::{MultiThreadedTokenizer, Tokenizer};
 use crate::vocab::{BpePairVocab, ReformerVocab, Vocab};
@@ -27,6 +27,7 @@ use std::sync::RwLock;
 /// # Reformer tokenizer
 pub struct ReformerTokenizer {
     vocab: ReformerVocab,
+    special_token_cache: SpecialTokenCache,
     bpe_ranks: BpePairVocab,
     cache: BpeCache,
     lower_case: bool,
@@ -60,6 +61,7 @@ impl ReformerTokenizer {
             bpe_ranks,
             cache,
             lower_case,
+            special_token_cache: RwLock::new(None),
         })
     }
 
@@ -98,6 +100,7 @@ impl ReformerTokenizer {
             bpe_ranks,
             cache,
             lower_case,
+            special_token_cache: RwLock::new(None),
         })
     }
 }
@@ -111,7 +114,7 @@ impl Tokenizer<ReformerVocab> for ReformerTokenizer {
     }
 
     fn tokenize_to_tokens(&self, text: TokenRef) -> Vec<Token> {
-        let mut tokens = split_on_special_tokens(text, &self.vocab)
+        let mut tokens = split_on_special_tokens(text, &self.vocab, &self.special_token_cache)
             .into_iter()
             .flat_map(whitespace_tokenize)
             .map(|token| token.to_owned())
@@ -20,7 +20,7 @@ use crate::tokenizer::base_tokenizer::{
 use crate::tokenizer::constants::UNICODE_TO_BYTES;
 use crate::tokenizer::tokenization_utils::{
     bpe, fix_mask, is_whitespace, split_on_bpe_pairs, split_on_regex_with_lookahead,
-    split_on_special_tokens,
+    split_on_special_tokens, SpecialTokenCache,
 };
 use crate::tokenizer::tokenization_utils::{lowercase, BpeCache};
 use crate::tokenizer::MultiThreadedTokenizer;
@@ -41,6 +41,7 @@ use std::sync::RwLock;
 /// - BPE tokenization
 pub struct RobertaTokenizer {
     vocab: RobertaVocab,
+    special_token_cache: SpecialTokenCache,
     bpe_ranks: BpePairVocab,
     cache: BpeCache,
     pattern_lookahead: Regex,
@@ -93,6 +94,7 @@ impl RobertaTokenizer {
             pattern_tokenization,
             lower_case,
             add_prefix_space,
+            special_token_cache: RwLock::new(None),
         })
     }
 
@@ -146,6 +148,7 @@ impl RobertaTokenizer {
             pattern_tokenization,
             lower_case,
             add_prefix_space,
+            special_token_cache: RwLock::new(None),
         })
     }
 
@@ -192,6 +195,7 @@ impl RobertaTokenizer {
             pattern_tokenization,
             lower_case,
             add_prefix_space,
+            special_token_cache: RwLock::new(None),
         }
     }
 }
@@ -213,10 +217,14 @@ impl Tokenizer<RobertaVocab> for RobertaTokenizer {
             initial_token.text.insert(0, ' ');
             initial_token.reference_offsets.insert(0, 0);
         };
-        let mut tokens: Vec<Token> = split_on_special_tokens(initial_token.as_ref(), &self.vocab)
-            .into_iter()
-            .map(|token| token.to_owned())
-            .collect::<Vec<Token>>();
+        let mut tokens: Vec<Token> = split_on_special_tokens(
+            initial_token.as_ref(),
+            &self.vocab,
+            &self.special_token_cache,
+        )
+        .into_iter()
+        .map(|token| token.to_owned())
+        .collect::<Vec<Token>>();
 
         let mut sub_tokens = Vec::new();
         for token in tokens.iter_mut() {
@@ -11,10 +11,12 @@
 // limitations under the License.
 
 use std::path::Path;
+use std::sync::RwLock;
 
 use crate::error::TokenizerError;
 use crate::tokenizer::tokenization_utils::{
     clean_text, decompose_nfkc, is_whitespace, lowercase, split_on_special_tokens,
+    SpecialTokenCache,
 };
 use crate::tokenizer::{MultiThreadedTokenizer, Tokenizer};
 use crate::vocab::{SentencePieceModel, T5Vocab, Vocab};
@@ -30,6 +32,7 @@ use crate::{Mask, Token, TokenIdsWithOffsets, TokenIdsWithSpecialTokens, TokenRe
 pub struct T5Tokenizer {
     model: SentencePieceModel,
     vocab: T5Vocab,
+    special_token_cache: SpecialTokenCache,
     lower_case: bool,
     eos_token_id: i64,
 }
@@ -62,6 +65,7 @@ impl T5Tokenizer {
             vocab,
             lower_case,
             eos_token_id,
+            special_token_cache: RwLock::new(None),
         })
     }
 
@@ -99,6 +103,7 @@ impl T5Tokenizer {
             vocab,
             lower_case,
             eos_token_id,
+            special_token_cache: RwLock::new(None),
         })
     }
 
@@ -131,6 +136,7 @@ impl T5Tokenizer {
             vocab,
             lower_case,
             eos_token_id,
+            special_token_cache: RwLock::new(None),
         }
     }
 
@@ -152,7 +158,7 @@ impl Tokenizer<T5Vocab> for T5Tokenizer {
     }
 
     fn tokenize_to_tokens(&self, text: TokenRef) -> Vec<Token> {
-        let mut tokens = split_on_special_tokens(text, &self.vocab)
+        let mut tokens = split_on_special_tokens(text, &self.vocab, &self.special_token_cache)
             .into_iter()
             .map(|token| token.to_owned())
             .collect::<Vec<Token>>();
@@ -0,0 +1,492 @@
+// Copyright 2019-2020 Guillaume Becquin
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//     http://www.apache.org/licenses/LICENSE-2.0
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::adapters::read_jsonl;
+use crate::error::TokenizerError;
+use crate::tokenizer::base_tokenizer::{MultiThreadedTokenizer, Tokenizer};
+use crate::vocab::Vocab;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+/// # Token shard data type
+/// Unsigned integer type of the token ids stored in the shards (little-endian).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TokenDtype {
+    #[serde(rename = "uint16")]
+    U16,
+    #[serde(rename = "uint32")]
+    U32,
+}
+
+impl TokenDtype {
+    /// Returns the smallest data type able to store the ids of a vocabulary of `vocab_size` tokens
+    pub fn for_vocab_size(vocab_size: usize) -> TokenDtype {
+        if vocab_size <= u16::MAX as usize + 1 {
+            TokenDtype::U16
+        } else {
+            TokenDtype::U32
+        }
+    }
+
+    /// Returns the size in bytes of a token id
+    pub fn size_of(&self) -> usize {
+        match self {
+            TokenDtype::U16 => 2,
+            TokenDtype::U32 => 4,
+        }
+    }
+
+    fn max_value(&self) -> i64 {
+        match self {
+            TokenDtype::U16 => u16::MAX as i64,
+            TokenDtype::U32 => u32::MAX as i64,
+        }
+    }
+}
+
+/// # Token shard
+/// File name (relative to the output directory) and number of tokens of a shard.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ShardInfo {
+    pub file_name: String,
+    pub num_tokens: usize,
+}
+
+/// # Token shards index
+/// Describes the shards written by a `TokenShardWriter`. The shards are the consecutive parts of a
+/// single flat token stream: `document_offsets` contains the position of the first token of each
+/// document in this stream, followed by the total number of tokens.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ShardIndex {
+    pub dtype: TokenDtype,
+    pub shard_size: usize,
+    pub eos_token_id: Option<i64>,
+    pub num_tokens: u64,
+    pub shards: Vec<ShardInfo>,
+    pub document_offsets: Vec<u64>,
+}
+
+impl ShardIndex {
+    /// Reads a shard index from a JSON file written by `TokenShardWriter::finish`
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<ShardIndex, TokenizerError> {
+        let file = File::open(&path).map_err(|e| {
+            TokenizerError::FileNotFound(format!(
+                "{} shard index not found :{}",
+                path.as_ref().display(),
+                e
+            ))
+        })?;
+        serde_json::from_reader(BufReader::new(file))
+            .map_err(|e| TokenizerError::ValueError(format!("invalid shard index: {e}")))
+    }
+
+    /// Returns the number of documents stored in the shards
+    pub fn num_documents(&self) -> usize {
+        self.document_offsets.len().saturating_sub(1)
+    }
+
+    /// Returns the range of positions of a document in the token stream (including its EOS separator)
+    pub fn document_range(&self, document: usize) -> Option<Range<u64>> {
+        Some(*self.document_offsets.get(document)?..*self.document_offsets.get(document + 1)?)
+    }
+}
+
+/// # Token shard writer
+/// Appends encoded documents to a flat stream of token ids, split into shards of `shard_size` tokens
+/// (`{prefix}_00000.bin`, `{prefix}_00001.bin`...). A document may start in a shard and end in the
+/// next one. `finish` writes the index of the shards and document boundaries to `{prefix}.index.json`.
+pub struct TokenShardWriter {
+    output_dir: PathBuf,
+    prefix: String,
+    dtype: TokenDtype,
+    shard_size: usize,
+    eos_token_id: Option<i64>,
+    shard: Option<BufWriter<File>>,
+    shards: Vec<ShardInfo>,
+    document_offsets: Vec<u64>,
+    num_tokens: u64,
+}
+
+impl TokenShardWriter {
+    /// Create a new instance of a `TokenShardWriter`, creating the output directory if needed.
+    ///
+    /// # Parameters
+    /// - output_dir (`&Path`): directory of the shards and index
+    /// - prefix (`&str`): prefix of the shard and index file names
+    /// - dtype (`TokenDtype`): data type of the token ids in the shards
+    /// - shard_size (`usize`): maximum number of tokens of a shard
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use rust_tokenizers::tokenizer::{TokenDtype, TokenShardWriter};
+    /// let mut writer = TokenShardWriter::new("path/to/shards", "train", TokenDtype::U16, 1 << 20)
+    ///     .unwrap();
+    /// writer.set_eos_token_id(Some(50256));
+    /// writer.write_document(&[15496, 995]).unwrap();
+    /// let index = writer.finish().unwrap();
+    /// ```
+    pub fn new<P: AsRef<Path>>(
+        output_dir: P,
+        prefix: &str,
+        dtype: TokenDtype,
+        shard_size: usize,
+    ) -> Result<TokenShardWriter, TokenizerError> {
+        if shard_size == 0 {
+            return Err(TokenizerError::ValueError(
+                "the shard size must be positive".to_string(),
+            ));
+        }
+        fs::create_dir_all(&output_dir).map_err(|e| TokenizerError::IOError(format!("{e}")))?;
+        Ok(TokenShardWriter {
+            output_dir: output_dir.as_ref().to_path_buf(),
+            prefix: prefix.to_string(),
+            dtype,
+            shard_size,
+            eos_token_id: None,
+            shard: None,
+            shards: Vec::new(),
+            document_offsets: vec![0],
+            num_tokens: 0,
+        })
+    }
+
+    /// Sets the id of the separator appended after each document (none by default)
+    pub fn set_eos_token_id(&mut self, eos_token_id: Option<i64>) {
+        self.eos_token_id = eos_token_id;
+    }
+
+    /// Returns the path of the index file written by `finish`
+    pub fn index_path(&self) -> PathBuf {
+        self.output_dir.join(format!("{}.index.json", self.prefix))
+    }
+
+    fn write_token(&mut self, token_id: i64) -> Result<(), TokenizerError> {
+        if self
+            .shards
+            .last()
+            .is_none_or(|shard| shard.num_tokens == self.shard_size)
+        {
+            self.flush_shard()?;
+            let file_name = format!("{}_{:05}.bin", self.prefix, self.shards.len());
+            let file = File::create(self.output_dir.join(&file_name))
+                .map_err(|e| TokenizerError::IOError(format!("{e}")))?;
+            self.shard = Some(BufWriter::new(file));
+            self.shards.push(ShardInfo {
+                file_name,
+                num_tokens: 0,
+            });
+        }
+        let shard = self.shard.as_mut().unwrap();
+        match self.dtype {
+            TokenDtype::U16 => shard.write_all(&(token_id as u16).to_le_bytes()),
+            TokenDtype::U32 => shard.write_all(&(token_id as u32).to_le_bytes()),
+        }
+        .map_err(|e| TokenizerError::IOError(format!("{e}")))?;
+        self.shards.last_mut().unwrap().num_tokens += 1;
+        self.num_tokens += 1;
+        Ok(())
+    }
+
+    fn flush_shard(&mut self) -> Result<(), TokenizerError> {
+        if let Some(mut shard) = self.shard.take() {
+            shard
+                .flush()
+                .map_err(|e| TokenizerError::IOError(format!("{e}")))?;
+        }
+        Ok(())
+    }
+
+    /// Appends a document to the shards, followed by the EOS separator if one is set. Returns an
+    /// error if a token id cannot be represented with the data type of the writer.
+    pub fn write_document(&mut self, token_ids: &[i64]) -> Result<(), TokenizerError> {
+        let max_value = self.dtype.max_value();
+        if let Some(token_id) = token_ids
+            .iter()
+            .chain(self.eos_token_id.iter())
+            .find(|token_id| !(0..=max_value).contains(*token_id))
+        {
+            return Err(TokenizerError::ValueError(format!(
+                "token id {token_id} cannot be stored as {:?}",
+                self.dtype
+            )));
+        }
+        let eos_token_id = self.eos_token_id;
+        for token_id in token_ids.iter().chain(eos_token_id.iter()) {
+            self.write_token(*token_id)?;
+        }
+        self.document_offsets.push(self.num_tokens);
+        Ok(())
+    }
+
+    /// Flushes the last shard and writes the index file, returning the index
+    pub fn finish(mut self) -> Result<ShardIndex, TokenizerError> {
+        self.flush_shard()?;
+        let index_path = self.index_path();
+        let index = ShardIndex {
+            dtype: self.dtype,
+            shard_size: self.shard_size,
+            eos_token_id: self.eos_token_id,
+            num_tokens: self.num_tokens,
+            shards: self.shards,
+            document_offsets: self.document_offsets,
+        };
+        let file = File::create(index_path).map_err(|e| TokenizerError::IOError(format!("{e}")))?;
+        serde_json::to_writer(BufWriter::new(file), &index)
+            .map_err(|e| TokenizerError::IOError(format!("{e}")))?;
+        Ok(index)
+    }
+}
+
+/// # Document file format
+/// Format of the files read by `read_documents`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DocumentFormat {
+    /// One document per (non-empty) line
+    Text,
+    /// One JSON object per line, with the document text in `text_field`
+    Jsonl { text_field: String },
+}
+
+/// Streams the documents of a text or JSON lines file. Empty lines are skipped.
+///
+/// # Parameters
+/// - path (`&Path`): path to the documents file
+/// - format (`&DocumentFormat`): format of the file
+///
+/// # Returns
+/// - `Iterator<Item = Result<String, TokenizerError>>` over the documents of the file
+pub fn read_documents<P: AsRef<Path>>(
+    path: P,
+    format: &DocumentFormat,
+) -> Result<Box<dyn Iterator<Item = Result<String, TokenizerError>>>, TokenizerError> {
+    Ok(match format {
+        DocumentFormat::Text => {
+            let file = File::open(&path).map_err(|e| {
+                TokenizerError::FileNotFound(format!(
+                    "{} documents file not found :{}",
+                    path.as_ref().display(),
+                    e
+                ))
+            })?;
+            Box::new(
+                BufReader::new(file)
+                    .lines()
+                    .map(|line| line.map_err(|e| TokenizerError::IOError(format!("{e}"))))
+                    .filter(|line| line.as_ref().map_or(true, |line| !line.trim().is_empty())),
+            )
+        }
+        DocumentFormat::Jsonl { text_field } => Box::new(
+            read_jsonl(path.as_ref().to_path_buf(), text_field, None, None)?
+                .map(|example| example.map(|example| example.sentence_1)),
+        ),
+    })
+}
+
+/// Tokenizes and encodes a stream of documents into token shards. The documents are read in chunks
+/// of `chunk_size` documents (bounding the memory usage), each chunk being encoded in parallel. The
+/// documents are written in their input order, making the shards independent of the number of threads.
+/// No special tokens are added apart from the EOS separator of the writer.
+///
+/// # Parameters
+/// - tokenizer: multi-threaded tokenizer used to encode the documents
+/// - documents: iterator over the documents text
+/// - chunk_size (`usize`): number of documents encoded in parallel
+/// - writer (`&mut TokenShardWriter`): writer of the shards
+///
+/// # Example
+///
+/// ```no_run
+/// use rust_tokenizers::tokenizer::{
+///     encode_to_shards, read_documents, DocumentFormat, Gpt2Tokenizer, TokenDtype,
+///     TokenShardWriter,
+/// };
+/// let tokenizer =
+///     Gpt2Tokenizer::from_file("path/to/vocab.json", "path/to/merges.txt", false).unwrap();
+/// let documents = read_documents("path/to/corpus.txt", &DocumentFormat::Text).unwrap();
+/// let mut writer =
+///     TokenShardWriter::new("path/to/shards", "train", TokenDtype::U16, 100_000_000).unwrap();
+/// writer.set_eos_token_id(Some(50256));
+/// encode_to_shards(&tokenizer, documents, 1024, &mut writer).unwrap();
+/// let index = writer.finish().unwrap();
+/// ```
+pub fn encode_to_shards<T, V, I>(
+    tokenizer: &T,
+    documents: I,
+    chunk_size: usize,
+    writer: &mut TokenShardWriter,
+) -> Result<(), TokenizerError>
+where
+    T: MultiThreadedTokenizer<V> + ?Sized,
+    V: Vocab,
+    I: IntoIterator<Item = Result<String, TokenizerError>>,
+{
+    let chunk_size = chunk_size.max(1);
+    let mut documents = documents.into_iter();
+    let mut chunk = Vec::with_capacity(chunk_size);
+    loop {
+        chunk.clear();
+        for document in documents.by_ref().take(chunk_size) {
+            chunk.push(document?);
+        }
+        if chunk.is_empty() {
+            return Ok(());
+        }
+        let token_ids: Vec<Vec<i64>> = chunk
+            .par_iter()
+            .map(|text| {
+                let tokens = Tokenizer::tokenize(tokenizer, text);
+                Tokenizer::convert_tokens_to_ids(tokenizer, &tokens)
+            })
+            .collect();
+        for document_ids in token_ids {
+            writer.write_document(&document_ids)?;
+        }
+    }
+}
+
+//==============================
+// Unit tests
+//==============================
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tokenizer::BertTokenizer;
+    use crate::vocab::{BertVocab, SpecialTokenMap};
+    use std::collections::HashMap;
+
+    fn tokenizer() -> BertTokenizer {
+        let values: HashMap<String, i64> = vec![
+            "[UNK]", "[CLS]", "[SEP]", "[PAD]", "[MASK]", "hello", "world", "the", "cat", ".",
+        ]
+        .into_iter()
+        .enumerate()
+        .map(|(id, token)| (token.to_string(), id as i64))
+        .collect();
+        let special_token_map = SpecialTokenMap {
+            unk_token: "[UNK]".to_string(),
+            pad_token: Some("[PAD]".to_string()),
+            bos_token: None,
+            sep_token: Some("[SEP]".to_string()),
+            cls_token: Some("[CLS]".to_string()),
+            eos_token: None,
+            mask_token: Some("[MASK]".to_string()),
+            additional_special_tokens: None,
+        };
+        let vocab =
+            BertVocab::from_values_and_special_token_map(values, special_token_map).unwrap();
+        BertTokenizer::from_existing_vocab(vocab, true, true)
+    }
+
+    fn read_u16_tokens(path: &Path) -> Vec<u16> {
+        fs::read(path)
+            .unwrap()
+            .chunks(2)
+            .map(|bytes| u16::from_le_bytes([bytes[0], bytes[1]]))
+            .collect()
+    }
+
+    #[test]
+    fn test_write_token_shards() -> anyhow::Result<()> {
+        //        Given
+        let output_dir = tempfile::tempdir()?;
+        let mut writer = TokenShardWriter::new(output_dir.path(), "train", TokenDtype::U16, 4)?;
+        writer.set_eos_token_id(Some(2));
+
+        //        When
+        writer.write_document(&[5, 6])?;
+        writer.write_document(&[7, 8, 9])?;
+        writer.write_document(&[])?;
+        let invalid = writer.write_document(&[70_000]);
+        let index = writer.finish()?;
+
+        //        Then
+        assert!(invalid.is_err());
+        assert_eq!(index.num_tokens, 8);
+        assert_eq!(index.document_offsets, vec![0, 3, 7, 8]);
+        assert_eq!(index.num_documents(), 3);
+        assert_eq!(index.document_range(1), Some(3..7));
+        assert_eq!(
+            index.shards,
+            vec![
+                ShardInfo {
+                    file_name: "train_00000.bin".to_string(),
+                    num_tokens: 4
+                },
+                ShardInfo {
+                    file_name: "train_00001.bin".to_string(),
+                    num_tokens: 4
+                },
+            ]
+        );
+        assert_eq!(
+            read_u16_tokens(&output_dir.path().join("train_00000.bin")),
+            vec![5, 6, 2, 7]
+        );
+        assert_eq!(
+            read_u16_tokens(&output_dir.path().join("train_00001.bin")),
+            vec![8, 9, 2, 2]
+        );
+        assert_eq!(
+            ShardIndex::from_file(output_dir.path().join("train.index.json"))?,
+            index
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_encode_to_shards_deterministic() -> anyhow::Result<()> {
+        //        Given
+        let tokenizer = tokenizer();
+        let documents: Vec<String> = (0..100)
+            .map(|index| "hello world. the cat. ".repeat(index % 7))
+            .collect();
+        let encode = |num_threads: usize, chunk_size: usize| -> anyhow::Result<Vec<u8>> {
+            let output_dir = tempfile::tempdir()?;
+            let mut writer =
+                TokenShardWriter::new(output_dir.path(), "train", TokenDtype::U32, 64)?;
+            writer.set_eos_token_id(Some(2));
+            let pool = rayon::ThreadPoolBuilder::new()
+                .num_threads(num_threads)
+                .build()?;
+            pool.install(|| {
+                encode_to_shards(
+                    &tokenizer,
+                    documents.iter().cloned().map(Ok),
+                    chunk_size,
+                    &mut writer,
+                )
+            })?;
+            let index = writer.finish()?;
+            let mut bytes = Vec::new();
+            for shard in &index.shards {
+                bytes.extend(fs::read(output_dir.path().join(&shard.file_name))?);
+            }
+            Ok(bytes)
+        };
+
+        //        When
+        let single_thread = encode(1, 16)?;
+        let multi_thread = encode(4, 7)?;
+
+        //        Then
+        assert_eq!(single_thread, multi_thread);
+        assert_eq!(
+            &single_thread[..4 * 7],
+            &[2, 0, 0, 0, 5, 0, 0, 0, 6, 0, 0, 0, 9, 0, 0, 0, 7, 0, 0, 0, 8, 0, 0, 0, 9, 0, 0, 0]
+        );
+        Ok(())
+    }
+}
@@ -13,26 +13,88 @@
 // limitations under the License.
 
 use crate::error::TokenizerError;
-use crate::tokenizer::base_tokenizer::{TokenIdsWithOffsets, TruncationStrategy};
+use crate::tokenizer::base_tokenizer::{TokenIdsWithOffsets, TruncationSide, TruncationStrategy};
 use crate::tokenizer::constants::{
-    ACCENT_MARKERS, ADDITIONAL_WHITESPACE_CHARS, BYTES_TO_UNICODE, CONTROL_CHARS,
+    ACCENT_MARKERS, ADDITIONAL_WHITESPACE_CHARS, BYTES_TO_UNICODE, CONTROL_CHARS, HTML_ENTITIES,
     PUNCTUATION_CHARS, WHITESPACE_CHARS,
 };
 use crate::vocab::bpe_vocab::{BpePairRef, BpePairVocab};
 use crate::vocab::Vocab;
 use crate::{Mask, Offset, OffsetSize, Token, TokenRef};
+use aho_corasick::{AhoCorasick, MatchKind};
 use regex::Regex;
 use std::borrow::BorrowMut;
 use std::char;
 use std::char::REPLACEMENT_CHARACTER;
 use std::cmp::{min, Ordering};
 use std::collections::{HashMap, HashSet};
-use std::sync::RwLock;
+use std::ops::Range;
+use std::sync::{Arc, RwLock};
 use unicode_normalization::char::decompose_canonical;
 use unicode_normalization_alignments::UnicodeNormalization;
 
 pub type BpeCache = RwLock<HashMap<String, (Vec<String>, Vec<usize>)>>;
 
+pub type SpecialTokenCache = RwLock<Option<Arc<SpecialTokenMatcher>>>;
+
+/// # Special token matcher
+/// Aho-Corasick automaton (leftmost-longest) built over the special values of a vocabulary. This
+/// allows splitting a text on special tokens in a single pass, irrespective of the number of special
+/// values registered (e.g. extra ids, language codes or tokens added with `add_tokens`).
+pub struct SpecialTokenMatcher {
+    automaton: AhoCorasick,
+    num_special_values: usize,
+}
+
+impl SpecialTokenMatcher {
+    /// Builds a new matcher from the special values of a vocabulary
+    pub fn new(vocab: &impl Vocab) -> SpecialTokenMatcher {
+        let automaton = AhoCorasick::builder()
+            .match_kind(MatchKind::LeftmostLongest)
+            .build(vocab.special_values().keys())
+            .unwrap();
+        SpecialTokenMatcher {
+            automaton,
+            num_special_values: vocab.special_values().len(),
+        }
+    }
+
+    /// Returns true if the matcher was built from the current special values of the vocabulary.
+    /// Special values are only ever added to a vocabulary (`add_tokens`, `add_extra_ids`), so a
+    /// change in their count indicates the automaton needs to be rebuilt.
+    pub fn is_up_to_date(&self, vocab: &impl Vocab) -> bool {
+        self.num_special_values == vocab.special_values().len()
+    }
+
+    /// Returns the byte ranges of the special values found in a text
+    pub(crate) fn find_iter<'a>(
+        &'a self,
+        text: &'a str,
+    ) -> impl Iterator<Item = Range<usize>> + 'a {
+        self.automaton.find_iter(text).map(|hit| hit.range())
+    }
+}
+
+/// Returns the special token matcher stored in the cache, (re)building it if it is missing or
+/// outdated with respect to the vocabulary provided.
+pub(crate) fn get_special_token_matcher(
+    vocab: &impl Vocab,
+    cache: &SpecialTokenCache,
+) -> Arc<SpecialTokenMatcher> {
+    if let Ok(cache) = cache.read() {
+        if let Some(matcher) = cache.as_ref() {
+            if matcher.is_up_to_date(vocab) {
+                return matcher.clone();
+            }
+        }
+    }
+    let matcher = Arc::new(SpecialTokenMatcher::new(vocab));
+    if let Ok(mut cache) = cache.try_write() {
+        *cache = Some(matcher.clone());
+    }
+    matcher
+}
+
 ///Cleans text by removing control characters and normalizing whitespace
 pub fn clean_text(token: &mut Token, strict: bool) {
     let capacity = token.text.capacity();
@@ -58,6 +120,38 @@ pub fn clean_text(token: &mut Token, strict: bool) {
     token.offset.end = *token.reference_offsets.last().unwrap_or(&0) + 1;
 }
 
+/// Writes the text to `output` with the transformations applied to the `BaseTokenizer` tokens
+/// (`clean_text`, optional `lowercase` and `strip_accents`), without keeping track of the offsets
+pub fn normalize_text(text: &str, lower_case: bool, strip_accents: bool, output: &mut String) {
+    output.clear();
+    for character in text.chars() {
+        if is_control(&character, true) || character == '\x00' || character == REPLACEMENT_CHARACTER
+        {
+            continue;
+        }
+        if is_whitespace(&character) {
+            output.push(' ');
+            continue;
+        }
+        let mut push_character = |c: char| {
+            if strip_accents {
+                decompose_canonical(c, |c| {
+                    if !ACCENT_MARKERS.contains(&(c as u32)) {
+                        output.push(c);
+                    }
+                });
+            } else {
+                output.push(c);
+            }
+        };
+        if lower_case {
+            character.to_lowercase().for_each(&mut push_character);
+        } else {
+            push_character(character);
+        }
+    }
+}
+
 /// Replaces a pattern &str by a replacement &str keeping track of the offsets
 /// (all new characters in replacement have the same reference offset as the first pattern character as these may have a different size)
 pub fn replace_string(token: &mut Token, pattern: &str, replacement_string: &str) {
@@ -84,23 +178,115 @@ pub fn replace_string(token: &mut Token, pattern: &str, replacement_string: &str
     }
 }
 
-///Split a text on special tokens (like BOS/EOS/UNK markers), depending on the vocabulary
-pub fn split_on_special_tokens<'a>(token: TokenRef<'a>, vocab: &impl Vocab) -> Vec<TokenRef<'a>> {
-    let test_substr = |s: &str| {
-        for special_value in vocab.special_values().keys() {
-            if s.starts_with(special_value.as_str()) {
-                return (
+/// Replaces HTML character references (e.g. `&amp;`, `&#39;` or `&#x27;`) by the character they
+/// refer to, keeping track of the offsets (the decoded character has the reference offset of the
+/// `&` of the character reference). Named references are decoded for the entities listed in
+/// `HTML_ENTITIES`, other references are left unchanged.
+pub fn unescape_html(token: &mut Token) {
+    if !token.text.contains('&') {
+        return;
+    }
+    let characters: Vec<char> = token.text.chars().collect();
+    let mut unescaped_string = String::with_capacity(token.text.len());
+    let mut character_mapping: Vec<OffsetSize> = Vec::with_capacity(characters.len());
+    let mut position = 0;
+    while position < characters.len() {
+        if characters[position] == '&' {
+            if let Some((character, length)) =
+                parse_html_character_reference(&characters[position..])
+            {
+                unescaped_string.push(character);
+                character_mapping.push(token.reference_offsets[position]);
+                position += length;
+                continue;
+            }
+        }
+        unescaped_string.push(characters[position]);
+        character_mapping.push(token.reference_offsets[position]);
+        position += 1;
+    }
+    token.text = unescaped_string;
+    token.reference_offsets = character_mapping;
+}
+
+/// Parses the HTML character reference at the start of `characters` (which starts with `&`),
+/// returning the character it refers to and the number of characters of the reference
+fn parse_html_character_reference(characters: &[char]) -> Option<(char, usize)> {
+    let end = characters.iter().take(32).position(|c| *c == ';')?;
+    let name: String = characters[1..end].iter().collect();
+    let character = match name.strip_prefix('#') {
+        Some(number) => {
+            let code_point = match number.strip_prefix(|c| c == 'x' || c == 'X') {
+                Some(hexadecimal) => u32::from_str_radix(hexadecimal, 16).ok()?,
+                None => number.parse::<u32>().ok()?,
+            };
+            char::from_u32(code_point).filter(|character| *character != '\x00')?
+        }
+        None => *HTML_ENTITIES.get(name.as_str())?,
+    };
+    Some((character, end + 1))
+}
+
+/// Collapses runs of whitespace into a single space and removes leading and trailing whitespace,
+/// keeping track of the offsets
+pub fn collapse_whitespace(token: &mut Token) {
+    let capacity = token.text.capacity();
+    let mut collapsed_string = String::with_capacity(capacity);
+    let mut character_mapping: Vec<OffsetSize> = Vec::with_capacity(capacity);
+    let mut pending_whitespace = None;
+    for (character, position) in token.text.chars().zip(token.reference_offsets.iter()) {
+        if character.is_whitespace() {
+            if !collapsed_string.is_empty() && pending_whitespace.is_none() {
+                pending_whitespace = Some(*position);
+            }
+            continue;
+        }
+        if let Some(whitespace_position) = pending_whitespace.take() {
+            collapsed_string.push(' ');
+            character_mapping.push(whitespace_position);
+        }
+        collapsed_string.push(character);
+        character_mapping.push(*position);
+    }
+    token.text = collapsed_string;
+    token.reference_offsets = character_mapping;
+    token.offset.begin = *token.reference_offsets.first().unwrap_or(&0);
+    token.offset.end = *token.reference_offsets.last().unwrap_or(&0) + 1;
+}
+
+///Split a text on special tokens (like BOS/EOS/UNK markers), depending on the vocabulary.
+///The special values are matched using an Aho-Corasick automaton stored in the cache provided,
+///which is rebuilt automatically when new special values are added to the vocabulary.
+pub fn split_on_special_tokens<'a>(
+    token: TokenRef<'a>,
+    vocab: &impl Vocab,
+    cache: &SpecialTokenCache,
+) -> Vec<TokenRef<'a>> {
+    let matches: Vec<(usize, usize, usize, Mask)> = if token.mask == Mask::None {
+        get_special_token_matcher(vocab, cache)
+            .automaton
+            .find_iter(token.text)
+            .map(|hit| {
+                let special_value = &token.text[hit.start()..hit.end()];
+                (
+                    hit.start(),
                     special_value.len(),
                     special_value.chars().count(),
-                    if vocab.get_unknown_value() == special_value.as_str() {
+                    if vocab.get_unknown_value() == special_value {
                         Mask::Unknown
                     } else {
                         Mask::Special
                     },
-                );
-            }
-        }
-        (0, 0, Mask::None)
+                )
+            })
+            .collect()
+    } else {
+        vec![]
+    };
+    let text_len = token.text.len();
+    let test_substr = |s: &str| match matches.binary_search_by_key(&(text_len - s.len()), |m| m.0) {
+        Ok(idx) => (matches[idx].1, matches[idx].2, matches[idx].3),
+        Err(_) => (0, 0, Mask::None),
     };
     split_on_substr(token, test_substr, true)
 }
@@ -110,7 +296,7 @@ pub fn tokenize_cjk_chars(token: TokenRef) -> Vec<TokenRef> {
     split_on_char(token, is_cjk_char, true, Mask::CJK)
 }
 
-fn is_cjk_char(character: &char) -> bool {
+pub(crate) fn is_cjk_char(character: &char) -> bool {
     let u32_char = *character as u32;
     (0x4E00..=0x9FFF).contains(&u32_char)
         | (0x3400..=0x4DBF).contains(&u32_char)
@@ -359,6 +545,67 @@ pub fn split_on_regex_with_lookahead<'a>(
     }
 }
 
+/// Split a token on a pre-tokenization regex, keeping the text between matches as separate
+/// tokens (isolated splitting). As for `split_on_regex_with_lookahead`, whitespace runs followed by
+/// a non-whitespace character are first split before their last character so that it can be
+/// attached to the following word.
+pub fn split_on_regex_isolated<'a>(
+    token: TokenRef<'a>,
+    pattern_lookahead: &Regex,
+    pattern_tokenization: &Regex,
+) -> Vec<TokenRef<'a>> {
+    if token.mask == Mask::None {
+        let mut splits: Vec<&str> = vec![];
+        let mut i: usize = 0;
+        for hit in pattern_lookahead.find_iter(token.text) {
+            let mut hit_chars = hit.as_str().chars().rev();
+            let start = hit_chars.next().unwrap();
+            let sep = hit_chars.next().unwrap();
+            let end_byte = hit.end() - sep.len_utf8() - start.len_utf8();
+            splits.push(&token.text[i..end_byte]);
+            i = end_byte;
+        }
+        splits.push(&token.text[i..]);
+
+        let mut sub_words: Vec<&str> = vec![];
+        for split in splits {
+            let mut end_byte = 0;
+            for hit in pattern_tokenization.find_iter(split) {
+                if hit.start() > end_byte {
+                    sub_words.push(&split[end_byte..hit.start()]);
+                }
+                sub_words.push(hit.as_str());
+                end_byte = hit.end();
+            }
+            if end_byte < split.len() {
+                sub_words.push(&split[end_byte..]);
+            }
+        }
+
+        let mut output_tokens: Vec<TokenRef> = Vec::with_capacity(sub_words.len());
+        let mut begin_char: usize = 0;
+        for sub_word in sub_words
+            .into_iter()
+            .filter(|sub_word| !sub_word.is_empty())
+        {
+            let end_char = begin_char + sub_word.chars().count();
+            output_tokens.push(TokenRef {
+                text: sub_word,
+                offset: Offset::new(
+                    token.offset.begin + begin_char as OffsetSize,
+                    token.offset.begin + end_char as OffsetSize,
+                ),
+                reference_offsets: &token.reference_offsets[begin_char..end_char],
+                mask: Default::default(),
+            });
+            begin_char = end_char;
+        }
+        output_tokens
+    } else {
+        vec![token]
+    }
+}
+
 pub fn split_on_regex<'a>(token: TokenRef<'a>, pattern_tokenization: &Regex) -> Vec<TokenRef<'a>> {
     let mut tokens: Vec<TokenRef<'a>> = Vec::new();
     let mut begin_char: usize = 0usize;
@@ -589,6 +836,37 @@ pub fn tokenize_wordpiece(token: TokenRef, vocab: &impl Vocab, max_word_len: usi
     tokens
 }
 
+///Counts the word pieces a token is split into by `tokenize_wordpiece`, without building them
+pub fn count_wordpiece(text: &str, vocab: &impl Vocab, max_word_len: usize) -> usize {
+    if text.chars().count() > max_word_len {
+        return 1;
+    }
+    let mut substr = String::with_capacity(text.len() + 2);
+    let mut num_pieces = 0;
+    let mut start = 0;
+    while start < text.len() {
+        let mut end = text.len();
+        loop {
+            substr.clear();
+            if start > 0 {
+                substr.push_str("##");
+            }
+            substr.push_str(&text[start..end]);
+            if vocab.values().contains_key(substr.as_str()) {
+                break;
+            }
+            match text[start..end].char_indices().last() {
+                Some((position, _)) if position > 0 => end = start + position,
+                //out of vocabulary, the token is replaced by a single unknown token
+                _ => return 1,
+            }
+        }
+        num_pieces += 1;
+        start = end;
+    }
+    num_pieces
+}
+
 /// # Truncates a sequence pair in place to the maximum length.
 ///
 ///   * tokens_1: list of tokenized input ids. Can be obtained from a string by chaining the
@@ -607,10 +885,12 @@ pub fn tokenize_wordpiece(token: TokenRef, vocab: &impl Vocab, max_word_len: usi
 ///           Overflowing tokens only contains overflow from the first sequence.
 ///       - TruncationStrategy::OnlyFirst: Only truncate the first sequence. raise an error if the first sequence is shorter or equal to than num_tokens_to_remove.
 ///       - TruncationStrategy::OnlySecond: Only truncate the second sequence
+///       - TruncationStrategy::HeadTail: Only truncate the first sequence, keeping its first `head` tokens and removing tokens from the middle
 ///       - TruncationStrategy::DoNotTruncate: Does not truncate (raise an error if the input sequence is longer than max_length)
+///       - TruncationStrategy::WithSide: applies one of the strategies above, removing the tokens from the given side (`TruncationSide::Left` removes them from the start)
 ///   * stride
 ///       If set to a number along with max_length, the overflowing tokens returned will contain some tokens
-///       from the main sequence returned. The value of this argument defines the number of additional tokens.
+///       from the main sequence returned. The value of this argument defines the number of additional tokens, taken before the removed tokens (or after them for left truncation).
 pub fn truncate_sequences(
     mut token_ids_with_offsets_1: TokenIdsWithOffsets,
     mut token_ids_with_offsets_2: Option<TokenIdsWithOffsets>,
@@ -634,54 +914,19 @@ pub fn truncate_sequences(
             Vec::new(),
         ))
     } else if let Some(token_ids_with_offsets_2_value) = token_ids_with_offsets_2.borrow_mut() {
-        match truncation_strategy {
+        let (strategy, side) = truncation_strategy.split_side();
+        match strategy {
             TruncationStrategy::LongestFirst => {
                 if (token_ids_with_offsets_1.ids.len() + token_ids_with_offsets_2_value.ids.len())
                     >= num_tokens_to_remove
                 {
-                    let mut overflow_tokens: Vec<i64> =
-                        Vec::with_capacity(num_tokens_to_remove + stride);
-                    let mut overflow_offsets: Vec<Option<Offset>> =
-                        Vec::with_capacity(num_tokens_to_remove + stride);
-                    for _ in 0..num_tokens_to_remove {
-                        if token_ids_with_offsets_1.ids.len()
-                            >= token_ids_with_offsets_2_value.ids.len()
-                        {
-                            overflow_tokens.insert(0, token_ids_with_offsets_1.ids.pop().unwrap());
-                            if !token_ids_with_offsets_1.offsets.is_empty() {
-                                overflow_offsets
-                                    .insert(0, token_ids_with_offsets_1.offsets.pop().unwrap());
-                            }
-                            token_ids_with_offsets_1.reference_offsets.pop();
-                            if !token_ids_with_offsets_1.masks.is_empty() {
-                                token_ids_with_offsets_1.masks.pop();
-                            }
-                        } else {
-                            overflow_tokens
-                                .insert(0, token_ids_with_offsets_2_value.ids.pop().unwrap());
-                            if !token_ids_with_offsets_2_value.offsets.is_empty() {
-                                overflow_offsets.insert(
-                                    0,
-                                    token_ids_with_offsets_2_value.offsets.pop().unwrap(),
-                                );
-                            }
-                            token_ids_with_offsets_2_value.reference_offsets.pop();
-                            if !token_ids_with_offsets_2_value.masks.is_empty() {
-                                token_ids_with_offsets_2_value.masks.pop();
-                            }
-                        }
-                    }
-                    let window_len = min(token_ids_with_offsets_1.ids.len(), stride);
-                    if window_len > 0 {
-                        let slice: &[i64] = &token_ids_with_offsets_1.ids
-                            [token_ids_with_offsets_1.ids.len() - window_len..];
-                        overflow_tokens.splice(0..0, slice.iter().cloned());
-                        if !token_ids_with_offsets_1.offsets.is_empty() {
-                            let offset_slice: &[Option<Offset>] = &token_ids_with_offsets_1.offsets
-                                [token_ids_with_offsets_1.offsets.len() - window_len..];
-                            overflow_offsets.splice(0..0, offset_slice.iter().cloned());
-                        }
-                    }
+                    let (overflow_tokens, overflow_offsets) = truncate_longest_first(
+                        &mut token_ids_with_offsets_1,
+                        token_ids_with_offsets_2_value,
+                        num_tokens_to_remove,
+                        side == TruncationSide::Left,
+                        stride,
+                    );
                     Ok((
                         token_ids_with_offsets_1,
                         token_ids_with_offsets_2,
@@ -694,16 +939,15 @@ pub fn truncate_sequences(
                     ))
                 }
             }
-            TruncationStrategy::OnlyFirst => {
+            TruncationStrategy::OnlyFirst | TruncationStrategy::HeadTail { .. } => {
                 if token_ids_with_offsets_1.ids.len() >= num_tokens_to_remove {
-                    let (overflow_tokens, overflow_offsets) = truncate_with_overflow(
-                        &mut token_ids_with_offsets_1.ids,
-                        token_ids_with_offsets_1.offsets.as_mut(),
-                        token_ids_with_offsets_1.reference_offsets.as_mut(),
-                        token_ids_with_offsets_1.masks.as_mut(),
+                    let removed = truncation_range(
+                        token_ids_with_offsets_1.ids.len(),
                         num_tokens_to_remove,
-                        stride,
+                        truncation_strategy,
                     );
+                    let (overflow_tokens, overflow_offsets) =
+                        truncate_with_overflow(&mut token_ids_with_offsets_1, removed, stride);
                     Ok((
                         token_ids_with_offsets_1,
                         token_ids_with_offsets_2,
@@ -718,14 +962,13 @@ pub fn truncate_sequences(
             }
             TruncationStrategy::OnlySecond => {
                 if token_ids_with_offsets_2_value.ids.len() >= num_tokens_to_remove {
-                    let (overflow_tokens, overflow_offsets) = truncate_with_overflow(
-                        &mut token_ids_with_offsets_2_value.ids,
-                        token_ids_with_offsets_2_value.offsets.as_mut(),
-                        token_ids_with_offsets_2_value.reference_offsets.as_mut(),
-                        token_ids_with_offsets_2_value.masks.as_mut(),
+                    let removed = truncation_range(
+                        token_ids_with_offsets_2_value.ids.len(),
                         num_tokens_to_remove,
-                        stride,
+                        truncation_strategy,
                     );
+                    let (overflow_tokens, overflow_offsets) =
+                        truncate_with_overflow(token_ids_with_offsets_2_value, removed, stride);
                     Ok((
                         token_ids_with_offsets_1,
                         token_ids_with_offsets_2,
@@ -741,18 +984,22 @@ pub fn truncate_sequences(
             TruncationStrategy::DoNotTruncate => Err(TokenizerError::ValueError(
                 "Truncation needed but no truncation requested".into(),
             )),
+            TruncationStrategy::WithSide(..) => {
+                unreachable!("split_side returns the strategy applied")
+            }
         }
     } else if token_ids_with_offsets_1.ids.len() >= num_tokens_to_remove {
-        match truncation_strategy {
-            TruncationStrategy::LongestFirst | TruncationStrategy::OnlyFirst => {
-                let (overflow_tokens, overflow_offsets) = truncate_with_overflow(
-                    &mut token_ids_with_offsets_1.ids,
-                    &mut token_ids_with_offsets_1.offsets,
-                    &mut token_ids_with_offsets_1.reference_offsets,
-                    &mut token_ids_with_offsets_1.masks,
+        match truncation_strategy.split_side().0 {
+            TruncationStrategy::LongestFirst
+            | TruncationStrategy::OnlyFirst
+            | TruncationStrategy::HeadTail { .. } => {
+                let removed = truncation_range(
+                    token_ids_with_offsets_1.ids.len(),
                     num_tokens_to_remove,
-                    stride,
+                    truncation_strategy,
                 );
+                let (overflow_tokens, overflow_offsets) =
+                    truncate_with_overflow(&mut token_ids_with_offsets_1, removed, stride);
                 Ok((
                     token_ids_with_offsets_1,
                     token_ids_with_offsets_2,
@@ -766,6 +1013,9 @@ pub fn truncate_sequences(
             TruncationStrategy::DoNotTruncate => Err(TokenizerError::ValueError(
                 "Truncation needed but no truncation requested".into(),
             )),
+            TruncationStrategy::WithSide(..) => {
+                unreachable!("split_side returns the strategy applied")
+            }
         }
     } else {
         Err(TokenizerError::ValueError(
@@ -774,38 +1024,125 @@ pub fn truncate_sequences(
     }
 }
 
-fn truncate_with_overflow(
-    sequence: &mut Vec<i64>,
-    offsets: &mut Vec<Option<Offset>>,
-    original_positions: &mut Vec<Vec<OffsetSize>>,
-    mask: &mut Vec<Mask>,
+/// Returns the range of tokens removed from a sequence of `length` tokens truncated with a
+/// single-sequence strategy (`length` must be at least `num_tokens_to_remove`).
+pub(crate) fn truncation_range(
+    length: usize,
     num_tokens_to_remove: usize,
+    truncation_strategy: &TruncationStrategy,
+) -> Range<usize> {
+    match truncation_strategy.split_side() {
+        (TruncationStrategy::HeadTail { head }, _) => {
+            let head = min(*head, length - num_tokens_to_remove);
+            head..head + num_tokens_to_remove
+        }
+        (_, TruncationSide::Left) => 0..num_tokens_to_remove,
+        (_, TruncationSide::Right) => length - num_tokens_to_remove..length,
+    }
+}
+
+fn remove_tokens(token_ids_with_offsets: &mut TokenIdsWithOffsets, removed: Range<usize>) {
+    token_ids_with_offsets.ids.drain(removed.clone());
+    if !token_ids_with_offsets.offsets.is_empty() {
+        token_ids_with_offsets.offsets.drain(removed.clone());
+    }
+    if !token_ids_with_offsets.reference_offsets.is_empty() {
+        token_ids_with_offsets
+            .reference_offsets
+            .drain(removed.clone());
+    }
+    if !token_ids_with_offsets.masks.is_empty() {
+        token_ids_with_offsets.masks.drain(removed);
+    }
+}
+
+fn truncate_with_overflow(
+    token_ids_with_offsets: &mut TokenIdsWithOffsets,
+    removed: Range<usize>,
     stride: usize,
 ) -> (Vec<i64>, Vec<Option<Offset>>) {
-    if !offsets.is_empty() {
-        assert_eq!(sequence.len(), offsets.len());
+    let length = token_ids_with_offsets.ids.len();
+    if !token_ids_with_offsets.offsets.is_empty() {
+        assert_eq!(length, token_ids_with_offsets.offsets.len());
     }
-    if !mask.is_empty() {
-        assert_eq!(sequence.len(), mask.len());
+    if !token_ids_with_offsets.masks.is_empty() {
+        assert_eq!(length, token_ids_with_offsets.masks.len());
     }
-    let cutoff = sequence.len() - num_tokens_to_remove;
-    let mut overflow_tokens = sequence.split_off(cutoff);
-    let mut overflow_offsets = if !offsets.is_empty() {
-        offsets.split_off(cutoff)
+    // The stride window is taken from the tokens kept next to the removed range: after it when
+    // truncating from the left, before it otherwise.
+    let overflow = if removed.start == 0 && removed.end < length {
+        removed.start..min(removed.end + stride, length)
+    } else {
+        removed.start - min(removed.start, stride)..removed.end
+    };
+    let overflow_tokens = token_ids_with_offsets.ids[overflow.clone()].to_vec();
+    let overflow_offsets = if !token_ids_with_offsets.offsets.is_empty() {
+        token_ids_with_offsets.offsets[overflow].to_vec()
     } else {
         Vec::new()
     };
-    if !mask.is_empty() {
-        mask.truncate(cutoff);
-        original_positions.truncate(cutoff);
+    remove_tokens(token_ids_with_offsets, removed);
+    (overflow_tokens, overflow_offsets)
+}
+
+fn truncate_longest_first(
+    token_ids_with_offsets_1: &mut TokenIdsWithOffsets,
+    token_ids_with_offsets_2: &mut TokenIdsWithOffsets,
+    num_tokens_to_remove: usize,
+    from_left: bool,
+    stride: usize,
+) -> (Vec<i64>, Vec<Option<Offset>>) {
+    let (length_1, length_2) = (
+        token_ids_with_offsets_1.ids.len(),
+        token_ids_with_offsets_2.ids.len(),
+    );
+    let mut overflow_tokens: Vec<i64> = Vec::with_capacity(num_tokens_to_remove + stride);
+    let mut overflow_offsets: Vec<Option<Offset>> =
+        Vec::with_capacity(num_tokens_to_remove + stride);
+    let (mut removed_1, mut removed_2) = (0, 0);
+    for _ in 0..num_tokens_to_remove {
+        let (sequence, removed, length) = if length_1 - removed_1 >= length_2 - removed_2 {
+            (&*token_ids_with_offsets_1, &mut removed_1, length_1)
+        } else {
+            (&*token_ids_with_offsets_2, &mut removed_2, length_2)
+        };
+        let position = if from_left {
+            *removed
+        } else {
+            length - 1 - *removed
+        };
+        overflow_tokens.push(sequence.ids[position]);
+        if !sequence.offsets.is_empty() {
+            overflow_offsets.push(sequence.offsets[position]);
+        }
+        *removed += 1;
     }
-    let window_len = min(sequence.len(), stride);
-    if window_len > 0 {
-        let slice: &[i64] = &sequence[sequence.len() - window_len..];
-        overflow_tokens.splice(0..0, slice.iter().cloned());
-        if !offsets.is_empty() {
-            let offset_slice: &[Option<Offset>] = &offsets[offsets.len() - window_len..];
-            overflow_offsets.splice(0..0, offset_slice.iter().cloned());
+    if from_left {
+        remove_tokens(token_ids_with_offsets_1, 0..removed_1);
+        remove_tokens(token_ids_with_offsets_2, 0..removed_2);
+        let window_len = min(token_ids_with_offsets_1.ids.len(), stride);
+        overflow_tokens.extend_from_slice(&token_ids_with_offsets_1.ids[..window_len]);
+        if !token_ids_with_offsets_1.offsets.is_empty() {
+            overflow_offsets.extend_from_slice(&token_ids_with_offsets_1.offsets[..window_len]);
+        }
+    } else {
+        overflow_tokens.reverse();
+        overflow_offsets.reverse();
+        remove_tokens(token_ids_with_offsets_1, length_1 - removed_1..length_1);
+        remove_tokens(token_ids_with_offsets_2, length_2 - removed_2..length_2);
+        let window_len = min(token_ids_with_offsets_1.ids.len(), stride);
+        let window_start = token_ids_with_offsets_1.ids.len() - window_len;
+        overflow_tokens.splice(
+            0..0,
+            token_ids_with_offsets_1.ids[window_start..].iter().cloned(),
+        );
+        if !token_ids_with_offsets_1.offsets.is_empty() {
+            overflow_offsets.splice(
+                0..0,
+                token_ids_with_offsets_1.offsets[window_start..]
+                    .iter()
+                    .cloned(),
+            );
         }
     }
     (overflow_tokens, overflow_offsets)
@@ -1064,6 +1401,71 @@ where
     tokens
 }
 
+/// Returns the id of a special token required by a tokenizer to build its inputs (e.g. the
+/// classification or end of sequence token), or a `TokenizerError::TokenNotFound` if the
+/// vocabulary does not define it.
+pub(crate) fn required_special_token_id(
+    vocab: &impl Vocab,
+    value: Option<&str>,
+    name: &str,
+) -> Result<i64, TokenizerError> {
+    value
+        .and_then(|value| {
+            vocab
+                .special_values()
+                .get(value)
+                .or_else(|| vocab.values().get(value))
+        })
+        .copied()
+        .ok_or_else(|| {
+            TokenizerError::TokenNotFound(format!(
+                "The {name} token required by the tokenizer could not be found in the vocabulary"
+            ))
+        })
+}
+
+/// Extends the special tokens matching `special_value` to the whitespace preceding them in the
+/// initial token, for special tokens that strip the space on their left (e.g. the `<mask>` token of
+/// the MPNet and Longformer models). The whitespace is removed from the preceding text when
+/// splitting on special tokens, the special token covers it in its offsets.
+pub(crate) fn lstrip_special_token(
+    initial_token: TokenRef,
+    tokens: &mut [Token],
+    special_value: &str,
+) {
+    if !tokens
+        .iter()
+        .any(|token| token.mask == Mask::Special && token.text == special_value)
+    {
+        return;
+    }
+    let characters: Vec<char> = initial_token.text.chars().collect();
+    for token in tokens
+        .iter_mut()
+        .filter(|token| token.mask == Mask::Special && token.text == special_value)
+    {
+        let position = match token.reference_offsets.first().and_then(|first| {
+            initial_token
+                .reference_offsets
+                .iter()
+                .position(|offset| offset == first)
+        }) {
+            Some(position) => position,
+            None => continue,
+        };
+        let mut begin = position;
+        while begin > 0 && is_whitespace(&characters[begin - 1]) {
+            begin -= 1;
+        }
+        if begin < position {
+            let mut reference_offsets = initial_token.reference_offsets[begin..position].to_vec();
+            reference_offsets.extend_from_slice(&token.reference_offsets);
+            token.offset.begin = reference_offsets[0];
+            token.reference_offsets = reference_offsets;
+        }
+    }
+}
+
 pub fn fix_mask(tokens: &mut Vec<Token>) {
     for i in 1..tokens.len() {
         if tokens[i].mask == Mask::Continuation && tokens[i - 1].mask == Mask::None {
@@ -1074,6 +1476,16 @@ pub fn fix_mask(tokens: &mut Vec<Token>) {
     }
 }
 
+/// Language codes of a vocabulary, registered as special tokens of `code_length` bytes
+pub(crate) fn language_codes_bytes(vocab: &impl Vocab, code_length: usize) -> HashSet<Vec<u8>> {
+    vocab
+        .special_values()
+        .keys()
+        .filter(|token| token.len() == code_length)
+        .map(|token| token.as_bytes().to_vec())
+        .collect()
+}
+
 pub(crate) fn split_on_language_code<'a>(
     token: TokenRef<'a>,
     code_length: usize,
@@ -1258,10 +1670,67 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_unescape_html() {
+        //        Given
+        let test_tuples = [
+            (
+                "Fish &amp; chips",
+                "Fish & chips",
+                vec![0, 1, 2, 3, 4, 5, 10, 11, 12, 13, 14, 15],
+            ),
+            ("&lt;b&gt;", "<b>", vec![0, 4, 5]),
+            ("it&#39;s", "it's", vec![0, 1, 2, 7]),
+            ("&#x1F600;!", "😀!", vec![0, 9]),
+            ("&amp;amp;", "&amp;", vec![0, 5, 6, 7, 8]),
+            (
+                "a & b &unknown; &#xZZ; &#0;",
+                "a & b &unknown; &#xZZ; &#0;",
+                (0..27).collect(),
+            ),
+        ];
+
+        //        When & Then
+        for (source_text, expected_text, expected_offsets) in test_tuples.iter() {
+            let mut token = Token::new(source_text.to_string());
+            unescape_html(&mut token);
+            assert_eq!(token.text, *expected_text);
+            assert_eq!(token.reference_offsets, *expected_offsets);
+        }
+    }
+
+    #[test]
+    fn test_collapse_whitespace() {
+        //        Given
+        let test_tuples = [
+            (
+                "  hello   world \t ",
+                "hello world",
+                vec![2, 3, 4, 5, 6, 7, 10, 11, 12, 13, 14],
+            ),
+            (
+                "hello\u{a0}\nworld",
+                "hello world",
+                vec![0, 1, 2, 3, 4, 5, 7, 8, 9, 10, 11],
+            ),
+            ("hello", "hello", vec![0, 1, 2, 3, 4]),
+            ("   ", "", vec![]),
+        ];
+
+        //        When & Then
+        for (source_text, expected_text, expected_offsets) in test_tuples.iter() {
+            let mut token = Token::new(source_text.to_string());
+            collapse_whitespace(&mut token);
+            assert_eq!(token.text, *expected_text);
+            assert_eq!(token.reference_offsets, *expected_offsets);
+        }
+    }
+
     #[test]
     fn test_split_on_special_tokens() {
         //        Given
         let vocab = generate_test_vocab();
+        let cache: SpecialTokenCache = RwLock::new(None);
         let test_tuples = [
             (
                 "Sentence with [MASK] token.",
@@ -1290,15 +1759,101 @@ mod tests {
         for (source_text, expected_tokens) in test_tuples.iter() {
             let offsets =
                 (0..source_text.chars().count() as OffsetSize).collect::<Vec<OffsetSize>>();
-            let tokens: Vec<&str> =
-                split_on_special_tokens(TokenRef::new(source_text, offsets.as_slice()), &vocab)
-                    .into_iter()
-                    .map(|t| t.text)
-                    .collect();
+            let tokens: Vec<&str> = split_on_special_tokens(
+                TokenRef::new(source_text, offsets.as_slice()),
+                &vocab,
+                &cache,
+            )
+            .into_iter()
+            .map(|t| t.text)
+            .collect();
             assert_eq!(tokens, *expected_tokens);
         }
     }
 
+    #[test]
+    fn test_split_on_special_tokens_offsets_and_masks() {
+        //        Given
+        let mut vocab = generate_test_vocab();
+        let cache: SpecialTokenCache = RwLock::new(None);
+        vocab.add_tokens(&["[MASK]_2", "<extra>"]);
+        let source_text = "[UNK]a[MASK]_2 b[MASK] <extra>";
+        let offsets = (0..source_text.chars().count() as OffsetSize).collect::<Vec<OffsetSize>>();
+
+        //        When
+        let tokens = split_on_special_tokens(
+            TokenRef::new(source_text, offsets.as_slice()),
+            &vocab,
+            &cache,
+        );
+
+        //        Then
+        assert_eq!(
+            tokens.iter().map(|t| t.text).collect::<Vec<&str>>(),
+            vec!["[UNK]", "a", "[MASK]_2", " b", "[MASK]", "<extra>"]
+        );
+        assert_eq!(
+            tokens.iter().map(|t| t.offset).collect::<Vec<Offset>>(),
+            vec![
+                Offset::new(0, 5),
+                Offset::new(5, 6),
+                Offset::new(6, 14),
+                Offset::new(14, 16),
+                Offset::new(16, 22),
+                Offset::new(23, 30),
+            ]
+        );
+        assert_eq!(
+            tokens.iter().map(|t| t.mask).collect::<Vec<Mask>>(),
+            vec![
+                Mask::Unknown,
+                Mask::None,
+                Mask::Special,
+                Mask::None,
+                Mask::Special,
+                Mask::Special,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_special_token_matcher_rebuilt_on_added_tokens() {
+        //        Given
+        let mut vocab = generate_test_vocab();
+        let cache: SpecialTokenCache = RwLock::new(None);
+        let source_text = "hello <new_token> world";
+        let offsets = (0..source_text.chars().count() as OffsetSize).collect::<Vec<OffsetSize>>();
+
+        //        When
+        let tokens_before: Vec<&str> = split_on_special_tokens(
+            TokenRef::new(source_text, offsets.as_slice()),
+            &vocab,
+            &cache,
+        )
+        .into_iter()
+        .map(|t| t.text)
+        .collect();
+        vocab.add_tokens(&["<new_token>"]);
+        let tokens_after: Vec<&str> = split_on_special_tokens(
+            TokenRef::new(source_text, offsets.as_slice()),
+            &vocab,
+            &cache,
+        )
+        .into_iter()
+        .map(|t| t.text)
+        .collect();
+
+        //        Then
+        assert_eq!(tokens_before, vec!["hello <new_token> world"]);
+        assert_eq!(tokens_after, vec!["hello", "<new_token>", " world"]);
+        assert!(cache
+            .read()
+            .unwrap()
+            .as_ref()
+            .unwrap()
+            .is_up_to_date(&vocab));
+    }
+
     #[test]
     fn test_tokenize_cjk_chars() {
         //        Given
@@ -2471,6 +3026,141 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_truncate_left_and_head_tail() {
+        //        Given
+        let sequence = |ids: Range<i64>| TokenIdsWithOffsets {
+            ids: ids.clone().collect(),
+            offsets: ids
+                .clone()
+                .map(|id| {
+                    Some(Offset {
+                        begin: id as OffsetSize,
+                        end: id as OffsetSize + 1,
+                    })
+                })
+                .collect(),
+            reference_offsets: ids.clone().map(|id| vec![id as OffsetSize]).collect(),
+            masks: ids.map(|_| Mask::None).collect(),
+        };
+        let offsets = |ids: &[i64]| -> Vec<Option<Offset>> {
+            sequence(0..20)
+                .offsets
+                .into_iter()
+                .enumerate()
+                .filter(|(id, _)| ids.contains(&(*id as i64)))
+                .map(|(_, offset)| offset)
+                .collect()
+        };
+        let test_tuples = vec![
+            (
+                (
+                    3,
+                    TruncationStrategy::OnlyFirst.with_side(TruncationSide::Left),
+                    2,
+                ),
+                (vec![3, 4, 5, 6, 7, 8, 9], vec![0, 1, 2, 3, 4]),
+            ),
+            (
+                (
+                    3,
+                    TruncationStrategy::LongestFirst.with_side(TruncationSide::Left),
+                    0,
+                ),
+                (vec![3, 4, 5, 6, 7, 8, 9], vec![0, 1, 2]),
+            ),
+            (
+                (3, TruncationStrategy::HeadTail { head: 2 }, 1),
+                (vec![0, 1, 5, 6, 7, 8, 9], vec![1, 2, 3, 4]),
+            ),
+            (
+                (
+                    3,
+                    TruncationStrategy::HeadTail { head: 2 }.with_side(TruncationSide::Left),
+                    1,
+                ),
+                (vec![0, 1, 5, 6, 7, 8, 9], vec![1, 2, 3, 4]),
+            ),
+            (
+                (
+                    3,
+                    TruncationStrategy::OnlyFirst
+                        .with_side(TruncationSide::Left)
+                        .with_side(TruncationSide::Right),
+                    1,
+                ),
+                (vec![0, 1, 2, 3, 4, 5, 6], vec![6, 7, 8, 9]),
+            ),
+            (
+                (5, TruncationStrategy::HeadTail { head: 8 }, 0),
+                (vec![0, 1, 2, 3, 4], vec![5, 6, 7, 8, 9]),
+            ),
+            (
+                (3, TruncationStrategy::HeadTail { head: 0 }, 1),
+                (vec![3, 4, 5, 6, 7, 8, 9], vec![0, 1, 2, 3]),
+            ),
+        ];
+
+        //        When & Then
+        for ((num_tokens_to_remove, truncation_strategy, stride), (kept, overflow)) in test_tuples {
+            let (truncated, _, overflow_tokens, overflow_offsets) = truncate_sequences(
+                sequence(0..10),
+                None,
+                num_tokens_to_remove,
+                &truncation_strategy,
+                stride,
+            )
+            .unwrap();
+            assert_eq!(truncated.ids, kept);
+            assert_eq!(truncated.offsets, offsets(&kept));
+            assert_eq!(
+                truncated.reference_offsets,
+                kept.iter()
+                    .map(|id| vec![*id as OffsetSize])
+                    .collect::<Vec<_>>()
+            );
+            assert_eq!(truncated.masks.len(), kept.len());
+            assert_eq!(overflow_tokens, overflow);
+            assert_eq!(overflow_offsets, offsets(&overflow));
+        }
+
+        let (truncated_1, truncated_2, overflow_tokens, _) = truncate_sequences(
+            sequence(0..10),
+            Some(sequence(10..14)),
+            8,
+            &TruncationStrategy::LongestFirst.with_side(TruncationSide::Left),
+            1,
+        )
+        .unwrap();
+        assert_eq!(truncated_1.ids, vec![7, 8, 9]);
+        assert_eq!(truncated_2.unwrap().ids, vec![11, 12, 13]);
+        assert_eq!(overflow_tokens, vec![0, 1, 2, 3, 4, 5, 6, 10, 7]);
+
+        let (truncated_1, truncated_2, overflow_tokens, overflow_offsets) = truncate_sequences(
+            sequence(0..10),
+            Some(sequence(10..14)),
+            2,
+            &TruncationStrategy::OnlySecond.with_side(TruncationSide::Left),
+            1,
+        )
+        .unwrap();
+        let truncated_2 = truncated_2.unwrap();
+        assert_eq!(truncated_1.ids, (0..10).collect::<Vec<i64>>());
+        assert_eq!(truncated_2.ids, vec![12, 13]);
+        assert_eq!(truncated_2.masks.len(), 2);
+        assert_eq!(overflow_tokens, vec![10, 11, 12]);
+        assert_eq!(overflow_offsets, offsets(&[10, 11, 12]));
+
+        assert!(truncate_sequences(
+            sequence(0..10),
+            None,
+            2,
+            &TruncationStrategy::OnlySecond.with_side(TruncationSide::Left),
+            0
+        )
+        .is_err());
+    }
+
     #[test]
     fn test_get_pair() {
         //        Given
@@ -0,0 +1,486 @@
+// Copyright 2021 The Facebook Inc. and The HuggingFace Inc. team.
+// Copyright 2019-2021 Guillaume Becquin
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//     http://www.apache.org/licenses/LICENSE-2.0
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::error::TokenizerError;
+use crate::tokenizer::base_tokenizer::{Mask, Offset, Token, TokenRef, Tokenizer};
+use crate::tokenizer::tokenization_utils::SpecialTokenCache;
+use crate::tokenizer::tokenization_utils::{is_whitespace, split_on_special_tokens};
+use crate::tokenizer::MultiThreadedTokenizer;
+use crate::vocab::{CharVocab, Vocab};
+use std::path::Path;
+use std::sync::RwLock;
+
+const DEFAULT_WORD_DELIMITER_TOKEN: &str = "|";
+
+/// # CTC character offset
+/// Decoded character with the range of frames (`start_offset` inclusive, `end_offset` exclusive)
+/// it was predicted for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CtcCharOffset {
+    pub char: String,
+    pub start_offset: usize,
+    pub end_offset: usize,
+}
+
+/// # CTC word offset
+/// Decoded word with the range of frames (`start_offset` inclusive, `end_offset` exclusive)
+/// it was predicted for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CtcWordOffset {
+    pub word: String,
+    pub start_offset: usize,
+    pub end_offset: usize,
+}
+
+/// # CTC decoding output
+/// Decoded text with optional character and word frame offsets (see `Wav2Vec2CtcTokenizer::decode_with_offsets`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CtcDecoding {
+    pub text: String,
+    pub char_offsets: Option<Vec<CtcCharOffset>>,
+    pub word_offsets: Option<Vec<CtcWordOffset>>,
+}
+
+/// # Wav2Vec2 CTC tokenizer
+/// Character-level tokenizer for CTC speech recognition models (Wav2Vec2, HuBERT...), performing:
+/// - splitting on special tokens
+/// - (optional) upper casing
+/// - splitting the text into characters, whitespaces being replaced by the word delimiter token (`|` by default)
+///
+/// Decoding follows the CTC rules: repeated predictions are collapsed (token grouping), the padding
+/// token (CTC blank) is removed and word delimiters are converted back to spaces. The ids passed for
+/// decoding are expected to be one prediction per frame, allowing the mapping of characters and words
+/// back to frames.
+pub struct Wav2Vec2CtcTokenizer {
+    vocab: CharVocab,
+    special_token_cache: SpecialTokenCache,
+    upper_case: bool,
+    word_delimiter_token: String,
+    group_tokens: bool,
+}
+
+impl Wav2Vec2CtcTokenizer {
+    /// Create a new instance of a `Wav2Vec2CtcTokenizer`
+    /// Expects a JSON character vocabulary file as an input.
+    ///
+    /// # Parameters
+    /// - path (`&str`): path to the vocabulary file
+    /// - upper_case (`bool`): flag indicating if the text should be upper-cased as part of the tokenization
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use rust_tokenizers::tokenizer::{Tokenizer, Wav2Vec2CtcTokenizer};
+    /// let upper_case = true;
+    /// let tokenizer = Wav2Vec2CtcTokenizer::from_file("path/to/vocab/file", upper_case).unwrap();
+    /// ```
+    pub fn from_file<P: AsRef<Path>>(
+        path: P,
+        upper_case: bool,
+    ) -> Result<Wav2Vec2CtcTokenizer, TokenizerError> {
+        let vocab = CharVocab::from_file(path)?;
+        Ok(Self::from_existing_vocab(vocab, upper_case))
+    }
+
+    /// Create a new instance of a `Wav2Vec2CtcTokenizer`
+    /// Expects a JSON character vocabulary file and special token mapping file as inputs.
+    ///
+    /// # Parameters
+    /// - path (`&str`): path to the vocabulary file
+    /// - upper_case (`bool`): flag indicating if the text should be upper-cased as part of the tokenization
+    /// - special_token_mapping_path (`&str`): path to a special token mapping file to overwrite default special tokens
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use rust_tokenizers::tokenizer::{Tokenizer, Wav2Vec2CtcTokenizer};
+    /// let upper_case = true;
+    /// let tokenizer = Wav2Vec2CtcTokenizer::from_file_with_special_token_mapping(
+    ///     "path/to/vocab/file",
+    ///     upper_case,
+    ///     "path/to/special/token/mapping/file",
+    /// )
+    /// .unwrap();
+    /// ```
+    pub fn from_file_with_special_token_mapping<P: AsRef<Path>, S: AsRef<Path>>(
+        path: P,
+        upper_case: bool,
+        special_token_mapping_path: S,
+    ) -> Result<Wav2Vec2CtcTokenizer, TokenizerError> {
+        let vocab =
+            CharVocab::from_file_with_special_token_mapping(path, special_token_mapping_path)?;
+        Ok(Self::from_existing_vocab(vocab, upper_case))
+    }
+
+    /// Create a new instance of a `Wav2Vec2CtcTokenizer` from an existing vocabulary
+    ///
+    /// # Parameters
+    /// - vocab (`CharVocab`): character vocabulary
+    /// - upper_case (`bool`): flag indicating if the text should be upper-cased as part of the tokenization
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use rust_tokenizers::tokenizer::{Tokenizer, Wav2Vec2CtcTokenizer};
+    /// use rust_tokenizers::vocab::{CharVocab, Vocab};
+    /// let upper_case = true;
+    /// let vocab = CharVocab::from_file("path/to/vocab/file").unwrap();
+    ///
+    /// let tokenizer = Wav2Vec2CtcTokenizer::from_existing_vocab(vocab, upper_case);
+    /// ```
+    pub fn from_existing_vocab(vocab: CharVocab, upper_case: bool) -> Wav2Vec2CtcTokenizer {
+        Wav2Vec2CtcTokenizer {
+            vocab,
+            special_token_cache: RwLock::new(None),
+            upper_case,
+            word_delimiter_token: DEFAULT_WORD_DELIMITER_TOKEN.to_string(),
+            group_tokens: true,
+        }
+    }
+
+    /// Sets the token used to delimit words (`|` by default)
+    pub fn set_word_delimiter_token(&mut self, word_delimiter_token: &str) {
+        self.word_delimiter_token = word_delimiter_token.to_string();
+    }
+
+    /// Returns the token used to delimit words
+    pub fn word_delimiter_token(&self) -> &str {
+        &self.word_delimiter_token
+    }
+
+    /// Sets whether repeated predictions should be collapsed when decoding (defaults to `true`).
+    /// Grouping should be turned off to decode label sequences (e.g. the output of `encode`) rather
+    /// than model predictions.
+    pub fn set_group_tokens(&mut self, group_tokens: bool) {
+        self.group_tokens = group_tokens;
+    }
+
+    /// Decodes a sequence of frame-level predictions following the CTC rules, optionally returning
+    /// the frame offsets of the decoded characters and words.
+    ///
+    /// # Parameters
+    /// - token_ids (`&[i64]`): predicted token id for each frame
+    /// - skip_special_tokens (`bool`): flag indicating if special tokens (other than the padding/blank token) should be removed
+    /// - output_char_offsets (`bool`): flag indicating if character offsets should be returned
+    /// - output_word_offsets (`bool`): flag indicating if word offsets should be returned
+    ///
+    /// # Returns
+    /// - `CtcDecoding` with the decoded text and the requested offsets
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use rust_tokenizers::tokenizer::Wav2Vec2CtcTokenizer;
+    /// let tokenizer = Wav2Vec2CtcTokenizer::from_file("path/to/vocab/file", true).unwrap();
+    ///
+    /// let predicted_ids = vec![0, 11, 11, 0, 5, 4, 8, 8, 14];
+    /// let decoded = tokenizer.decode_with_offsets(&predicted_ids, true, true, true);
+    /// ```
+    pub fn decode_with_offsets(
+        &self,
+        token_ids: &[i64],
+        skip_special_tokens: bool,
+        output_char_offsets: bool,
+        output_word_offsets: bool,
+    ) -> CtcDecoding {
+        let tokens = self.decode_to_vec(token_ids, skip_special_tokens);
+        let char_offsets = self.ctc_char_offsets(tokens);
+        let text = char_offsets
+            .iter()
+            .map(|char_offset| char_offset.char.as_str())
+            .collect::<String>()
+            .trim()
+            .to_string();
+        let word_offsets = output_word_offsets.then(|| ctc_word_offsets(&char_offsets));
+        CtcDecoding {
+            text,
+            char_offsets: output_char_offsets.then_some(char_offsets),
+            word_offsets,
+        }
+    }
+
+    /// Applies the CTC decoding rules to frame-level tokens: collapses repeated tokens (if
+    /// `group_tokens` is set), removes blanks and converts word delimiters into (deduplicated) spaces.
+    fn ctc_char_offsets(&self, tokens: Vec<String>) -> Vec<CtcCharOffset> {
+        let pad_token = self.vocab.get_pad_value();
+        let mut groups: Vec<CtcCharOffset> = vec![];
+        for (frame, token) in tokens.into_iter().enumerate() {
+            match groups.last_mut() {
+                Some(previous) if self.group_tokens && previous.char == token => {
+                    previous.end_offset = frame + 1;
+                }
+                _ => groups.push(CtcCharOffset {
+                    char: token,
+                    start_offset: frame,
+                    end_offset: frame + 1,
+                }),
+            }
+        }
+
+        let mut char_offsets: Vec<CtcCharOffset> = vec![];
+        for mut group in groups {
+            if group.char == pad_token {
+                continue;
+            }
+            if group.char == self.word_delimiter_token {
+                if char_offsets
+                    .last()
+                    .is_none_or(|previous| previous.char == " ")
+                {
+                    continue;
+                }
+                group.char = " ".to_string();
+            }
+            char_offsets.push(group);
+        }
+        if char_offsets.last().is_some_and(|last| last.char == " ") {
+            char_offsets.pop();
+        }
+        char_offsets
+    }
+}
+
+fn ctc_word_offsets(char_offsets: &[CtcCharOffset]) -> Vec<CtcWordOffset> {
+    let mut word_offsets: Vec<CtcWordOffset> = vec![];
+    let mut in_word = false;
+    for char_offset in char_offsets {
+        if char_offset.char == " " {
+            in_word = false;
+            continue;
+        }
+        match word_offsets.last_mut() {
+            Some(word_offset) if in_word => {
+                word_offset.word.push_str(&char_offset.char);
+                word_offset.end_offset = char_offset.end_offset;
+            }
+            _ => word_offsets.push(CtcWordOffset {
+                word: char_offset.char.clone(),
+                start_offset: char_offset.start_offset,
+                end_offset: char_offset.end_offset,
+            }),
+        }
+        in_word = true;
+    }
+    word_offsets
+}
+
+impl Tokenizer<CharVocab> for Wav2Vec2CtcTokenizer {
+    fn vocab(&self) -> &CharVocab {
+        &self.vocab
+    }
+    fn vocab_mut(&mut self) -> &mut CharVocab {
+        &mut self.vocab
+    }
+
+    fn tokenize_to_tokens(&self, initial_token: TokenRef) -> Vec<Token> {
+        let tokens = split_on_special_tokens(initial_token, &self.vocab, &self.special_token_cache);
+
+        let mut sub_tokens: Vec<Token> = Vec::new();
+        let mut pending_delimiter: Option<u32> = None;
+        for token in tokens {
+            if token.mask != Mask::Special && token.mask != Mask::Unknown {
+                for (character, position) in token.text.chars().zip(token.reference_offsets) {
+                    if is_whitespace(&character) {
+                        if !sub_tokens.is_empty() && pending_delimiter.is_none() {
+                            pending_delimiter = Some(*position);
+                        }
+                        continue;
+                    }
+                    if let Some(delimiter_position) = pending_delimiter.take() {
+                        sub_tokens.push(Token {
+                            text: self.word_delimiter_token.clone(),
+                            offset: Offset::new(delimiter_position, delimiter_position + 1),
+                            reference_offsets: vec![delimiter_position],
+                            mask: Mask::Whitespace,
+                        });
+                    }
+                    let characters: Vec<char> = if self.upper_case {
+                        character.to_uppercase().collect()
+                    } else {
+                        vec![character]
+                    };
+                    for character in characters {
+                        sub_tokens.push(Token {
+                            text: character.to_string(),
+                            offset: Offset::new(*position, *position + 1),
+                            reference_offsets: vec![*position],
+                            mask: Mask::None,
+                        });
+                    }
+                }
+            } else {
+                pending_delimiter = None;
+                sub_tokens.push(token.to_owned());
+            }
+        }
+        sub_tokens
+    }
+
+    /// Returns one token per frame. Special tokens are replaced by the blank (padding) token when
+    /// skipped, preserving the frame alignment required for CTC decoding.
+    fn decode_to_vec(&self, token_ids: &[i64], skip_special_tokens: bool) -> Vec<String> {
+        let pad_token_id = self.vocab.token_to_id(self.vocab.get_pad_value());
+        token_ids
+            .iter()
+            .map(|id| {
+                if skip_special_tokens
+                    && *id != pad_token_id
+                    && self.vocab.special_indices.contains_key(id)
+                {
+                    self.vocab.get_pad_value().to_string()
+                } else {
+                    self.vocab.id_to_token(id)
+                }
+            })
+            .collect()
+    }
+
+    fn convert_tokens_to_string(&self, tokens: Vec<String>) -> String {
+        self.ctc_char_offsets(tokens)
+            .into_iter()
+            .map(|char_offset| char_offset.char)
+            .collect::<String>()
+            .trim()
+            .to_string()
+    }
+}
+
+impl MultiThreadedTokenizer<CharVocab> for Wav2Vec2CtcTokenizer {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vocab::base_vocab::SpecialTokenMap;
+    use std::collections::HashMap;
+
+    fn generate_test_vocab() -> CharVocab {
+        let values: HashMap<String, i64> = [
+            "<pad>", "<s>", "</s>", "<unk>", "|", "E", "T", "A", "O", "H", "L", "D", "W", "R",
+        ]
+        .iter()
+        .enumerate()
+        .map(|(index, token)| (token.to_string(), index as i64))
+        .collect();
+
+        let special_token_map = SpecialTokenMap {
+            unk_token: "<unk>".to_string(),
+            pad_token: Some("<pad>".to_string()),
+            bos_token: Some("<s>".to_string()),
+            sep_token: None,
+            cls_token: None,
+            eos_token: Some("</s>".to_string()),
+            mask_token: None,
+            additional_special_tokens: None,
+        };
+        CharVocab::from_values_and_special_token_map(values, special_token_map).unwrap()
+    }
+
+    #[test]
+    fn test_wav2vec2_tokenizer() {
+        //        Given
+        let tokenizer = Wav2Vec2CtcTokenizer::from_existing_vocab(generate_test_vocab(), true);
+        let test_tuples = [
+            (
+                "hello world",
+                vec!["H", "E", "L", "L", "O", "|", "W", "O", "R", "L", "D"],
+            ),
+            (" the  road ", vec!["T", "H", "E", "|", "R", "O", "A", "D"]),
+            ("</s>ed", vec!["</s>", "E", "D"]),
+            ("", vec![]),
+        ];
+
+        //        When & Then
+        for (source_text, expected_result) in test_tuples.iter() {
+            assert_eq!(tokenizer.tokenize(source_text), *expected_result);
+        }
+        let tokens = tokenizer.tokenize_with_offsets("he  is");
+        assert_eq!(tokens.offsets[2], Some(Offset::new(2, 3)));
+        assert_eq!(tokens.masks[2], Mask::Whitespace);
+        assert_eq!(tokens.offsets[3], Some(Offset::new(4, 5)));
+    }
+
+    #[test]
+    fn test_ctc_decode() {
+        //        Given
+        let tokenizer = Wav2Vec2CtcTokenizer::from_existing_vocab(generate_test_vocab(), true);
+        // <pad> H H E <pad> L L <pad> L O | | <pad> W O R L D D <pad> </s>
+        let predicted_ids = [
+            0, 9, 9, 5, 0, 10, 10, 0, 10, 8, 4, 4, 0, 12, 8, 13, 10, 11, 11, 0, 2,
+        ];
+
+        //        When
+        let decoded = tokenizer.decode(&predicted_ids, true, false);
+        let decoded_with_special_tokens = tokenizer.decode(&predicted_ids, false, false);
+
+        //        Then
+        assert_eq!(decoded, "HELLO WORLD");
+        assert_eq!(decoded_with_special_tokens, "HELLO WORLD</s>");
+    }
+
+    #[test]
+    fn test_decode_labels_without_grouping() {
+        //        Given
+        let mut tokenizer = Wav2Vec2CtcTokenizer::from_existing_vocab(generate_test_vocab(), true);
+        tokenizer.set_group_tokens(false);
+        let labels = tokenizer.convert_tokens_to_ids(&tokenizer.tokenize("hello"));
+
+        //        When
+        let decoded = tokenizer.decode(&labels, true, false);
+
+        //        Then
+        assert_eq!(decoded, "HELLO");
+    }
+
+    #[test]
+    fn test_ctc_decode_with_offsets() {
+        //        Given
+        let tokenizer = Wav2Vec2CtcTokenizer::from_existing_vocab(generate_test_vocab(), true);
+        // <pad> T H H E | | <pad> R O A D
+        let predicted_ids = [0, 6, 9, 9, 5, 4, 4, 0, 13, 8, 7, 11];
+
+        //        When
+        let decoded = tokenizer.decode_with_offsets(&predicted_ids, true, true, true);
+
+        //        Then
+        assert_eq!(decoded.text, "THE ROAD");
+        let char_offsets = decoded.char_offsets.unwrap();
+        assert_eq!(char_offsets.len(), 8);
+        assert_eq!(
+            char_offsets[1],
+            CtcCharOffset {
+                char: "H".to_string(),
+                start_offset: 2,
+                end_offset: 4,
+            }
+        );
+        assert_eq!(char_offsets[3].char, " ");
+        assert_eq!(
+            decoded.word_offsets.unwrap(),
+            vec![
+                CtcWordOffset {
+                    word: "THE".to_string(),
+                    start_offset: 1,
+                    end_offset: 5,
+                },
+                CtcWordOffset {
+                    word: "ROAD".to_string(),
+                    start_offset: 8,
+                    end_offset: 12,
+                },
+            ]
+        );
+        assert!(tokenizer
+            .decode_with_offsets(&predicted_ids, true, false, false)
+            .char_offsets
+            .is_none());
+    }
+}
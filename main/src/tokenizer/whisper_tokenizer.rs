@@ -0,0 +1,792 @@
+// Copyright 2022 The OpenAI Authors and The HuggingFace Inc. team.
+// Copyright 2019-2020 Guillaume Becquin
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//     http://www.apache.org/licenses/LICENSE-2.0
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::error::TokenizerError;
+use crate::tokenizer::base_tokenizer::{
+    Mask, Offset, OffsetSize, Token, TokenIdsWithOffsets, TokenIdsWithSpecialTokens, TokenRef,
+    Tokenizer,
+};
+use crate::tokenizer::constants::UNICODE_TO_BYTES;
+use crate::tokenizer::tokenization_utils::{
+    bpe, fix_mask, split_on_bpe_pairs, split_on_regex_with_lookahead, split_on_special_tokens,
+    BpeCache, SpecialTokenCache,
+};
+use crate::tokenizer::MultiThreadedTokenizer;
+use crate::vocab::bpe_vocab::BpePairVocab;
+use crate::vocab::{Gpt2Vocab, Vocab};
+use regex::Regex;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::RwLock;
+
+/// Languages supported by Whisper, as (language code, language name) pairs. The order matches the
+/// order of the language tokens in the vocabulary.
+const LANGUAGES: [(&str, &str); 99] = [
+    ("en", "english"),
+    ("zh", "chinese"),
+    ("de", "german"),
+    ("es", "spanish"),
+    ("ru", "russian"),
+    ("ko", "korean"),
+    ("fr", "french"),
+    ("ja", "japanese"),
+    ("pt", "portuguese"),
+    ("tr", "turkish"),
+    ("pl", "polish"),
+    ("ca", "catalan"),
+    ("nl", "dutch"),
+    ("ar", "arabic"),
+    ("sv", "swedish"),
+    ("it", "italian"),
+    ("id", "indonesian"),
+    ("hi", "hindi"),
+    ("fi", "finnish"),
+    ("vi", "vietnamese"),
+    ("he", "hebrew"),
+    ("uk", "ukrainian"),
+    ("el", "greek"),
+    ("ms", "malay"),
+    ("cs", "czech"),
+    ("ro", "romanian"),
+    ("da", "danish"),
+    ("hu", "hungarian"),
+    ("ta", "tamil"),
+    ("no", "norwegian"),
+    ("th", "thai"),
+    ("ur", "urdu"),
+    ("hr", "croatian"),
+    ("bg", "bulgarian"),
+    ("lt", "lithuanian"),
+    ("la", "latin"),
+    ("mi", "maori"),
+    ("ml", "malayalam"),
+    ("cy", "welsh"),
+    ("sk", "slovak"),
+    ("te", "telugu"),
+    ("fa", "persian"),
+    ("lv", "latvian"),
+    ("bn", "bengali"),
+    ("sr", "serbian"),
+    ("az", "azerbaijani"),
+    ("sl", "slovenian"),
+    ("kn", "kannada"),
+    ("et", "estonian"),
+    ("mk", "macedonian"),
+    ("br", "breton"),
+    ("eu", "basque"),
+    ("is", "icelandic"),
+    ("hy", "armenian"),
+    ("ne", "nepali"),
+    ("mn", "mongolian"),
+    ("bs", "bosnian"),
+    ("kk", "kazakh"),
+    ("sq", "albanian"),
+    ("sw", "swahili"),
+    ("gl", "galician"),
+    ("mr", "marathi"),
+    ("pa", "punjabi"),
+    ("si", "sinhala"),
+    ("km", "khmer"),
+    ("sn", "shona"),
+    ("yo", "yoruba"),
+    ("so", "somali"),
+    ("af", "afrikaans"),
+    ("oc", "occitan"),
+    ("ka", "georgian"),
+    ("be", "belarusian"),
+    ("tg", "tajik"),
+    ("sd", "sindhi"),
+    ("gu", "gujarati"),
+    ("am", "amharic"),
+    ("yi", "yiddish"),
+    ("lo", "lao"),
+    ("uz", "uzbek"),
+    ("fo", "faroese"),
+    ("ht", "haitian creole"),
+    ("ps", "pashto"),
+    ("tk", "turkmen"),
+    ("nn", "nynorsk"),
+    ("mt", "maltese"),
+    ("sa", "sanskrit"),
+    ("lb", "luxembourgish"),
+    ("my", "myanmar"),
+    ("bo", "tibetan"),
+    ("tl", "tagalog"),
+    ("mg", "malagasy"),
+    ("as", "assamese"),
+    ("tt", "tatar"),
+    ("haw", "hawaiian"),
+    ("ln", "lingala"),
+    ("ha", "hausa"),
+    ("ba", "bashkir"),
+    ("jw", "javanese"),
+    ("su", "sundanese"),
+];
+
+const END_OF_TEXT: &str = "<|endoftext|>";
+const START_OF_TRANSCRIPT: &str = "<|startoftranscript|>";
+const TRANSLATE: &str = "<|translate|>";
+const TRANSCRIBE: &str = "<|transcribe|>";
+const START_OF_LM: &str = "<|startoflm|>";
+const START_OF_PREV: &str = "<|startofprev|>";
+const NO_CAPTIONS: &str = "<|nocaptions|>";
+const NO_TIMESTAMPS: &str = "<|notimestamps|>";
+
+/// Number of timestamp tokens (`<|0.00|>` to `<|30.00|>`)
+const NUM_TIMESTAMPS: i64 = 1501;
+/// Time (in seconds) between two consecutive timestamp tokens
+const TIME_PRECISION: f32 = 0.02;
+
+/// # Whisper task
+/// Task to be performed by the Whisper decoder, used to build the decoder prompt prefix
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum WhisperTask {
+    /// Speech recognition in the source language
+    Transcribe,
+    /// Speech translation to English
+    Translate,
+}
+
+/// # Whisper tokenizer
+/// Whisper tokenizer performing:
+/// - splitting on special characters (including the task, language and timestamp tokens)
+/// - whitespace splitting
+/// - byte-level BPE tokenization (using the GPT2 vocabulary and merges)
+///
+/// The Whisper special tokens (`<|startoftranscript|>`, the 99 language tokens, `<|translate|>`,
+/// `<|transcribe|>`, `<|startoflm|>`, `<|startofprev|>`, `<|nocaptions|>`, `<|notimestamps|>` and
+/// the timestamp tokens `<|0.00|>` to `<|30.00|>`) are registered in the vocabulary on creation if
+/// they are not already present.
+pub struct WhisperTokenizer {
+    vocab: Gpt2Vocab,
+    special_token_cache: SpecialTokenCache,
+    bpe_ranks: BpePairVocab,
+    cache: BpeCache,
+    pattern_lookahead: Regex,
+    pattern_tokenization: Regex,
+    language: Option<String>,
+    task: Option<WhisperTask>,
+    predict_timestamps: bool,
+    timestamp_begin: i64,
+}
+
+impl WhisperTokenizer {
+    /// Create a new instance of a `WhisperTokenizer`
+    /// Expects a vocabulary json file and a merges file as an input.
+    ///
+    /// # Parameters
+    /// - vocab_path (`&str`): path to the vocabulary file
+    /// - merges_path (`&str`): path to the merges file (use as part of the BPE encoding process)
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use rust_tokenizers::tokenizer::{Tokenizer, WhisperTokenizer};
+    /// let tokenizer =
+    ///     WhisperTokenizer::from_file("path/to/vocab/file", "path/to/merges/file").unwrap();
+    /// ```
+    pub fn from_file<P: AsRef<Path>, M: AsRef<Path>>(
+        vocab_path: P,
+        merges_path: M,
+    ) -> Result<WhisperTokenizer, TokenizerError> {
+        let vocab = Gpt2Vocab::from_file(vocab_path)?;
+        let bpe_ranks = BpePairVocab::from_file(merges_path)?;
+        Ok(Self::from_existing_vocab_and_merges(vocab, bpe_ranks))
+    }
+
+    /// Create a new instance of a `WhisperTokenizer`
+    /// Expects a vocabulary json file and a merges file and special token mapping file as inputs.
+    ///
+    /// # Parameters
+    /// - vocab_path (`&str`): path to the vocabulary file
+    /// - merges_path (`&str`): path to the merges file (use as part of the BPE encoding process)
+    /// - special_token_mapping_path (`&str`): path to a special token mapping file to overwrite default special tokens
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use rust_tokenizers::tokenizer::{Tokenizer, WhisperTokenizer};
+    /// let tokenizer = WhisperTokenizer::from_file_with_special_token_mapping(
+    ///     "path/to/vocab/file",
+    ///     "path/to/merges/file",
+    ///     "path/to/special/token/mapping/file",
+    /// )
+    /// .unwrap();
+    /// ```
+    pub fn from_file_with_special_token_mapping<V: AsRef<Path>, M: AsRef<Path>, S: AsRef<Path>>(
+        vocab_path: V,
+        merges_path: M,
+        special_token_mapping_path: S,
+    ) -> Result<WhisperTokenizer, TokenizerError> {
+        let vocab = Gpt2Vocab::from_file_with_special_token_mapping(
+            vocab_path,
+            special_token_mapping_path,
+        )?;
+        let bpe_ranks = BpePairVocab::from_file(merges_path)?;
+        Ok(Self::from_existing_vocab_and_merges(vocab, bpe_ranks))
+    }
+
+    /// Create a new instance of a `WhisperTokenizer` from an existing vocabulary and merges
+    ///
+    /// # Parameters
+    /// - vocab (`Gpt2Vocab`): GPT-like vocabulary
+    /// - merges (`BpePairVocab`): BPE pairs vocabulary
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use rust_tokenizers::tokenizer::{Tokenizer, WhisperTokenizer};
+    /// use rust_tokenizers::vocab::{BpePairVocab, Gpt2Vocab, Vocab};
+    /// let vocab = Gpt2Vocab::from_file("path/to/vocab/file").unwrap();
+    /// let merges = BpePairVocab::from_file("path/to/merges/file").unwrap();
+    ///
+    /// let tokenizer = WhisperTokenizer::from_existing_vocab_and_merges(vocab, merges);
+    /// ```
+    pub fn from_existing_vocab_and_merges(
+        mut vocab: Gpt2Vocab,
+        merges: BpePairVocab,
+    ) -> WhisperTokenizer {
+        let mut special_tokens = vec![END_OF_TEXT.to_string(), START_OF_TRANSCRIPT.to_string()];
+        special_tokens.extend(LANGUAGES.iter().map(|(code, _)| format!("<|{code}|>")));
+        special_tokens.extend(
+            [
+                TRANSLATE,
+                TRANSCRIBE,
+                START_OF_LM,
+                START_OF_PREV,
+                NO_CAPTIONS,
+                NO_TIMESTAMPS,
+            ]
+            .iter()
+            .map(|token| token.to_string()),
+        );
+        special_tokens.extend((0..NUM_TIMESTAMPS).map(timestamp_token));
+        Self::register_special_tokens(&mut vocab, &special_tokens);
+
+        let timestamp_begin = vocab.token_to_id(&timestamp_token(0));
+        let cache = RwLock::new(HashMap::new());
+        let pattern_lookahead = Regex::new(r"\s+\S").unwrap();
+        let pattern_tokenization =
+            Regex::new(r"'s|'t|'re|'ve|'m|'ll|'d| ?\p{L}+| ?\p{N}+| ?[^\s\p{L}\p{N}]+|\s+")
+                .unwrap();
+        WhisperTokenizer {
+            vocab,
+            special_token_cache: RwLock::new(None),
+            bpe_ranks: merges,
+            cache,
+            pattern_lookahead,
+            pattern_tokenization,
+            language: None,
+            task: None,
+            predict_timestamps: false,
+            timestamp_begin,
+        }
+    }
+
+    /// Registers the Whisper special tokens as special values of the vocabulary. Tokens missing
+    /// from the vocabulary are appended to it, in order.
+    fn register_special_tokens(vocab: &mut Gpt2Vocab, special_tokens: &[String]) {
+        let mut tokens_to_add: Vec<&str> = Vec::new();
+        for token in special_tokens {
+            if let Some(id) = vocab.values.get(token).copied() {
+                vocab.special_values.insert(token.clone(), id);
+                vocab.special_indices.insert(id, token.clone());
+            } else {
+                tokens_to_add.push(token);
+            }
+        }
+        vocab.add_tokens(&tokens_to_add);
+    }
+
+    /// Sets the language of the decoder prompt prefix.
+    ///
+    /// # Parameters
+    /// - language (`Option<&str>`): language code (e.g. `"fr"`) or language name (e.g. `"french"`).
+    ///   If `None`, no language token is added to the prefix (the model detects the language).
+    ///
+    /// # Returns
+    /// - `TokenizerError::ValueError` if the language is not supported by Whisper
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use rust_tokenizers::tokenizer::WhisperTokenizer;
+    /// let mut tokenizer =
+    ///     WhisperTokenizer::from_file("path/to/vocab/file", "path/to/merges/file").unwrap();
+    /// tokenizer.set_language(Some("french")).unwrap();
+    /// ```
+    pub fn set_language(&mut self, language: Option<&str>) -> Result<(), TokenizerError> {
+        self.language = match language {
+            Some(language) => {
+                let language = language.to_lowercase();
+                let code = LANGUAGES
+                    .iter()
+                    .find(|(code, name)| *code == language || *name == language)
+                    .map(|(code, _)| code.to_string())
+                    .ok_or_else(|| {
+                        TokenizerError::ValueError(format!(
+                            "Unsupported language {language} for Whisper"
+                        ))
+                    })?;
+                Some(code)
+            }
+            None => None,
+        };
+        Ok(())
+    }
+
+    /// Sets the task of the decoder prompt prefix.
+    ///
+    /// # Parameters
+    /// - task (`Option<WhisperTask>`): task to perform. If `None`, no task token is added to the prefix.
+    pub fn set_task(&mut self, task: Option<WhisperTask>) {
+        self.task = task;
+    }
+
+    /// Sets whether the model should predict timestamps. If `false`, the `<|notimestamps|>` token
+    /// is appended to the decoder prompt prefix.
+    ///
+    /// # Parameters
+    /// - predict_timestamps (`bool`): flag indicating if timestamps should be predicted
+    pub fn set_predict_timestamps(&mut self, predict_timestamps: bool) {
+        self.predict_timestamps = predict_timestamps;
+    }
+
+    /// Returns the current language code of the decoder prompt prefix, if any
+    pub fn get_language(&self) -> Option<&str> {
+        self.language.as_deref()
+    }
+
+    /// Returns the current task of the decoder prompt prefix, if any
+    pub fn get_task(&self) -> Option<WhisperTask> {
+        self.task
+    }
+
+    /// Builds the decoder prompt prefix: `<|startoftranscript|>`, followed by the language token,
+    /// the task token and `<|notimestamps|>` (depending on the settings of the tokenizer).
+    ///
+    /// # Returns
+    /// - `Vec<i64>` containing the ids of the prefix tokens
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use rust_tokenizers::tokenizer::{WhisperTask, WhisperTokenizer};
+    /// let mut tokenizer =
+    ///     WhisperTokenizer::from_file("path/to/vocab/file", "path/to/merges/file").unwrap();
+    /// tokenizer.set_language(Some("en")).unwrap();
+    /// tokenizer.set_task(Some(WhisperTask::Transcribe));
+    ///
+    /// let prefix = tokenizer.prefix_tokens();
+    /// ```
+    pub fn prefix_tokens(&self) -> Vec<i64> {
+        let mut prefix = vec![self.vocab.token_to_id(START_OF_TRANSCRIPT)];
+        if let Some(language) = &self.language {
+            prefix.push(self.vocab.token_to_id(&format!("<|{language}|>")));
+        }
+        if let Some(task) = self.task {
+            prefix.push(self.vocab.token_to_id(match task {
+                WhisperTask::Transcribe => TRANSCRIBE,
+                WhisperTask::Translate => TRANSLATE,
+            }));
+        }
+        if !self.predict_timestamps {
+            prefix.push(self.vocab.token_to_id(NO_TIMESTAMPS));
+        }
+        prefix
+    }
+
+    /// Returns the id of the first timestamp token (`<|0.00|>`)
+    pub fn timestamp_begin(&self) -> i64 {
+        self.timestamp_begin
+    }
+
+    /// Returns the time (in seconds) of a timestamp token, or `None` if the id is not a timestamp.
+    ///
+    /// # Parameters
+    /// - token_id (`i64`): token id to convert
+    pub fn timestamp_to_seconds(&self, token_id: i64) -> Option<f32> {
+        if (self.timestamp_begin..self.timestamp_begin + NUM_TIMESTAMPS).contains(&token_id) {
+            Some((token_id - self.timestamp_begin) as f32 * TIME_PRECISION)
+        } else {
+            None
+        }
+    }
+
+    /// Decodes a sequence of token ids generated with timestamps into segments.
+    ///
+    /// A segment is delimited by a pair of timestamp tokens (e.g. `<|0.00|> Hello<|1.20|>`).
+    /// Text not preceded by an opening timestamp starts at the previous timestamp (or at 0 seconds),
+    /// and text without a closing timestamp ends at 30 seconds (the end of the audio window). Other
+    /// special tokens are skipped.
+    ///
+    /// # Parameters
+    /// - token_ids (`&[i64]`): token ids to decode
+    ///
+    /// # Returns
+    /// - `Vec<(f32, f32, String)>` containing the start time, end time (in seconds) and text of each segment
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use rust_tokenizers::tokenizer::WhisperTokenizer;
+    /// let tokenizer =
+    ///     WhisperTokenizer::from_file("path/to/vocab/file", "path/to/merges/file").unwrap();
+    /// let generated_ids = vec![50258, 50259, 50359, 50364, 2425, 50414];
+    ///
+    /// let segments = tokenizer.decode_with_timestamps(&generated_ids);
+    /// ```
+    pub fn decode_with_timestamps(&self, token_ids: &[i64]) -> Vec<(f32, f32, String)> {
+        let mut segments = Vec::new();
+        let mut start: Option<f32> = None;
+        let mut last_time = 0.0;
+        let mut text_ids: Vec<i64> = Vec::new();
+        for token_id in token_ids {
+            if let Some(time) = self.timestamp_to_seconds(*token_id) {
+                match start {
+                    Some(start_time) => {
+                        segments.push((start_time, time, self.decode(&text_ids, true, false)));
+                        text_ids.clear();
+                        start = None;
+                    }
+                    None if !text_ids.is_empty() => {
+                        segments.push((last_time, time, self.decode(&text_ids, true, false)));
+                        text_ids.clear();
+                    }
+                    None => start = Some(time),
+                }
+                last_time = time;
+            } else if !self.vocab.special_indices.contains_key(token_id) {
+                text_ids.push(*token_id);
+            }
+        }
+        if !text_ids.is_empty() {
+            segments.push((
+                start.unwrap_or(last_time),
+                (NUM_TIMESTAMPS - 1) as f32 * TIME_PRECISION,
+                self.decode(&text_ids, true, false),
+            ));
+        }
+        segments
+    }
+}
+
+/// Formats the timestamp token at the given position (e.g. `<|0.02|>` for position 1)
+fn timestamp_token(position: i64) -> String {
+    format!("<|{}.{:02}|>", position / 50, (position % 50) * 2)
+}
+
+impl Tokenizer<Gpt2Vocab> for WhisperTokenizer {
+    fn vocab(&self) -> &Gpt2Vocab {
+        &self.vocab
+    }
+    fn vocab_mut(&mut self) -> &mut Gpt2Vocab {
+        &mut self.vocab
+    }
+
+    fn tokenize_to_tokens(&self, initial_token: TokenRef) -> Vec<Token> {
+        let tokens = split_on_special_tokens(initial_token, &self.vocab, &self.special_token_cache);
+
+        let mut sub_tokens = Vec::new();
+        for token in tokens {
+            if token.mask != Mask::Special && token.mask != Mask::Unknown {
+                for token in split_on_regex_with_lookahead(
+                    token,
+                    &self.pattern_lookahead,
+                    &self.pattern_tokenization,
+                ) {
+                    sub_tokens.extend(split_on_bpe_pairs(
+                        token,
+                        bpe,
+                        &self.bpe_ranks,
+                        &self.cache,
+                        true,
+                    ));
+                }
+            } else {
+                sub_tokens.push(token.to_owned());
+            }
+        }
+
+        fix_mask(&mut sub_tokens);
+        sub_tokens
+    }
+
+    fn convert_tokens_to_string(&self, tokens: Vec<String>) -> String {
+        let mut bytes: Vec<u8> = Vec::new();
+        for character in tokens.concat().chars() {
+            match UNICODE_TO_BYTES.get(&character) {
+                Some(byte) => bytes.push(*byte),
+                None => bytes.extend_from_slice(character.to_string().as_bytes()),
+            }
+        }
+        String::from_utf8_lossy(&bytes).trim().to_owned()
+    }
+
+    fn build_input_with_special_tokens(
+        &self,
+        tokens_ids_with_offsets_1: TokenIdsWithOffsets,
+        tokens_ids_with_offsets_2: Option<TokenIdsWithOffsets>,
+    ) -> TokenIdsWithSpecialTokens {
+        let prefix = self.prefix_tokens();
+        let mut output: Vec<i64> = prefix.clone();
+        let mut special_tokens_mask: Vec<i8> = vec![1; prefix.len()];
+        let mut offsets: Vec<Option<Offset>> = vec![None; prefix.len()];
+        let mut original_offsets: Vec<Vec<OffsetSize>> = vec![vec![]; prefix.len()];
+        let mut mask: Vec<Mask> = vec![Mask::Special; prefix.len()];
+
+        special_tokens_mask.extend(vec![0; tokens_ids_with_offsets_1.ids.len()]);
+        output.extend(tokens_ids_with_offsets_1.ids);
+        offsets.extend(tokens_ids_with_offsets_1.offsets);
+        original_offsets.extend(tokens_ids_with_offsets_1.reference_offsets);
+        mask.extend(tokens_ids_with_offsets_1.masks);
+        if let Some(tokens_ids_with_offsets_2_value) = tokens_ids_with_offsets_2 {
+            special_tokens_mask.extend(vec![0; tokens_ids_with_offsets_2_value.ids.len()]);
+            output.extend(tokens_ids_with_offsets_2_value.ids);
+            offsets.extend(tokens_ids_with_offsets_2_value.offsets);
+            original_offsets.extend(tokens_ids_with_offsets_2_value.reference_offsets);
+            mask.extend(tokens_ids_with_offsets_2_value.masks);
+        }
+        special_tokens_mask.push(1);
+        output.push(self.vocab.token_to_id(self.vocab.get_eos_value()));
+        offsets.push(None);
+        original_offsets.push(vec![]);
+        mask.push(Mask::Special);
+
+        TokenIdsWithSpecialTokens {
+            segment_ids: vec![0; output.len()],
+            token_ids: output,
+            special_tokens_mask,
+            token_offsets: offsets,
+            reference_offsets: original_offsets,
+            mask,
+        }
+    }
+}
+
+impl MultiThreadedTokenizer<Gpt2Vocab> for WhisperTokenizer {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tokenizer::base_tokenizer::TruncationStrategy;
+    use crate::vocab::base_vocab::{swap_key_values, SpecialTokenMap};
+
+    fn generate_test_vocab() -> Gpt2Vocab {
+        let values: HashMap<String, i64> = [
+            ("t".to_owned(), 0),
+            ("h".to_owned(), 1),
+            ("e".to_owned(), 2),
+            ("the".to_owned(), 3),
+            ("Ġ".to_owned(), 4),
+            ("Ġear".to_owned(), 5),
+            ("th".to_owned(), 6),
+            ("<|endoftext|>".to_owned(), 7),
+        ]
+        .iter()
+        .cloned()
+        .collect();
+
+        let special_token_map = SpecialTokenMap {
+            unk_token: "<|endoftext|>".to_string(),
+            pad_token: None,
+            bos_token: Some("<|endoftext|>".to_string()),
+            sep_token: None,
+            cls_token: None,
+            eos_token: Some("<|endoftext|>".to_string()),
+            mask_token: None,
+            additional_special_tokens: None,
+        };
+
+        let special_values: HashMap<String, i64> =
+            [("<|endoftext|>".to_owned(), 7)].iter().cloned().collect();
+
+        let indices = swap_key_values(&values);
+        let special_indices = swap_key_values(&special_values);
+
+        Gpt2Vocab {
+            values,
+            indices,
+            special_token_map,
+            special_values,
+            special_indices,
+        }
+    }
+
+    fn generate_test_merges() -> BpePairVocab {
+        let values: HashMap<(String, String), i64> = [
+            (("Ġ".to_owned(), "e".to_owned()), 0),
+            (("Ġe".to_owned(), "a".to_owned()), 1),
+            (("Ġea".to_owned(), "r".to_owned()), 2),
+            (("t".to_owned(), "h".to_owned()), 3),
+            (("th".to_owned(), "e".to_owned()), 4),
+        ]
+        .iter()
+        .cloned()
+        .collect();
+
+        BpePairVocab { values }
+    }
+
+    #[test]
+    fn test_special_tokens_registration() {
+        //        Given
+        let whisper_tokenizer = WhisperTokenizer::from_existing_vocab_and_merges(
+            generate_test_vocab(),
+            generate_test_merges(),
+        );
+        let vocab = &whisper_tokenizer.vocab;
+
+        //        When & Then
+        assert_eq!(vocab.token_to_id("<|startoftranscript|>"), 8);
+        assert_eq!(vocab.token_to_id("<|en|>"), 9);
+        assert_eq!(vocab.token_to_id("<|su|>"), 107);
+        assert_eq!(vocab.token_to_id("<|translate|>"), 108);
+        assert_eq!(vocab.token_to_id("<|transcribe|>"), 109);
+        assert_eq!(vocab.token_to_id("<|notimestamps|>"), 113);
+        assert_eq!(vocab.token_to_id("<|0.00|>"), 114);
+        assert_eq!(vocab.token_to_id("<|1.02|>"), 165);
+        assert_eq!(vocab.token_to_id("<|30.00|>"), 1614);
+        assert_eq!(whisper_tokenizer.timestamp_begin(), 114);
+        assert_eq!(whisper_tokenizer.timestamp_to_seconds(164), Some(1.0));
+        assert_eq!(whisper_tokenizer.timestamp_to_seconds(113), None);
+        assert_eq!(whisper_tokenizer.timestamp_to_seconds(1615), None);
+        assert_eq!(vocab.special_values.len(), 1 + 1 + 99 + 6 + 1501);
+    }
+
+    #[test]
+    fn test_whisper_tokenizer() {
+        //        Given
+        let whisper_tokenizer = WhisperTokenizer::from_existing_vocab_and_merges(
+            generate_test_vocab(),
+            generate_test_merges(),
+        );
+        let test_tuples = [
+            ("the ear", vec!["the", "Ġear"]),
+            (
+                "<|startoftranscript|><|en|>the ear<|1.02|>",
+                vec!["<|startoftranscript|>", "<|en|>", "the", "Ġear", "<|1.02|>"],
+            ),
+            ("", vec![]),
+        ];
+        let source_texts: Vec<&str> = test_tuples.iter().map(|v| v.0).collect();
+        let expected_results: Vec<Vec<&str>> = test_tuples.iter().map(|v| v.1.clone()).collect();
+
+        //        When & Then
+        for (source_text, expected_result) in test_tuples.iter() {
+            assert_eq!(whisper_tokenizer.tokenize(source_text), *expected_result);
+        }
+        assert_eq!(
+            MultiThreadedTokenizer::tokenize_list(&whisper_tokenizer, &source_texts),
+            expected_results
+        );
+    }
+
+    #[test]
+    fn test_prefix_tokens() -> anyhow::Result<()> {
+        //        Given
+        let mut whisper_tokenizer = WhisperTokenizer::from_existing_vocab_and_merges(
+            generate_test_vocab(),
+            generate_test_merges(),
+        );
+
+        //        When & Then
+        assert_eq!(whisper_tokenizer.prefix_tokens(), vec![8, 113]);
+
+        whisper_tokenizer.set_language(Some("French"))?;
+        whisper_tokenizer.set_task(Some(WhisperTask::Translate));
+        assert_eq!(whisper_tokenizer.get_language(), Some("fr"));
+        assert_eq!(whisper_tokenizer.prefix_tokens(), vec![8, 15, 108, 113]);
+
+        whisper_tokenizer.set_language(Some("de"))?;
+        whisper_tokenizer.set_task(Some(WhisperTask::Transcribe));
+        whisper_tokenizer.set_predict_timestamps(true);
+        assert_eq!(whisper_tokenizer.prefix_tokens(), vec![8, 11, 109]);
+
+        assert!(whisper_tokenizer.set_language(Some("klingon")).is_err());
+        assert_eq!(whisper_tokenizer.get_language(), Some("de"));
+
+        let encoded =
+            whisper_tokenizer.encode("the ear", None, 128, &TruncationStrategy::LongestFirst, 0);
+        assert_eq!(encoded.token_ids, vec![8, 11, 109, 3, 5, 7]);
+        assert_eq!(encoded.special_tokens_mask, vec![1, 1, 1, 0, 0, 1]);
+        assert_eq!(
+            encoded.token_offsets,
+            vec![
+                None,
+                None,
+                None,
+                Some(Offset::new(0, 3)),
+                Some(Offset::new(3, 7)),
+                None
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_with_timestamps() {
+        //        Given
+        let whisper_tokenizer = WhisperTokenizer::from_existing_vocab_and_merges(
+            generate_test_vocab(),
+            generate_test_merges(),
+        );
+        let test_tuples = [
+            (
+                vec![8, 9, 109, 114, 3, 5, 164, 164, 6, 214, 7],
+                vec![
+                    (0.0, 1.0, "the ear".to_string()),
+                    (1.0, 2.0, "th".to_string()),
+                ],
+            ),
+            (
+                vec![8, 9, 109, 113, 3, 5, 7],
+                vec![(0.0, 30.0, "the ear".to_string())],
+            ),
+            (
+                vec![114, 3, 164, 5],
+                vec![
+                    (0.0, 1.0, "the".to_string()),
+                    (1.0, 30.0, "ear".to_string()),
+                ],
+            ),
+            (vec![8, 114, 164, 7], vec![(0.0, 1.0, "".to_string())]),
+        ];
+
+        //        When & Then
+        for (token_ids, expected_result) in test_tuples.iter() {
+            assert_eq!(
+                whisper_tokenizer.decode_with_timestamps(token_ids),
+                *expected_result
+            );
+        }
+    }
+
+    #[test]
+    fn test_decode() {
+        //        Given
+        let whisper_tokenizer = WhisperTokenizer::from_existing_vocab_and_merges(
+            generate_test_vocab(),
+            generate_test_merges(),
+        );
+
+        //        When & Then
+        assert_eq!(
+            whisper_tokenizer.decode(&[8, 9, 109, 114, 3, 5, 164, 7], true, false),
+            "the ear"
+        );
+        assert_eq!(
+            whisper_tokenizer.decode(&[114, 3, 5, 164], false, false),
+            "<|0.00|>the ear<|1.00|>"
+        );
+    }
+}
@@ -11,16 +11,18 @@
 // limitations under the License.
 
 use std::path::Path;
+use std::sync::RwLock;
 
 use crate::error::TokenizerError;
 use crate::tokenizer::base_tokenizer::{
     Mask, Offset, OffsetSize, Token, TokenIdsWithOffsets, TokenIdsWithSpecialTokens, TokenRef,
 };
 use crate::tokenizer::tokenization_utils::{
-    clean_text, decompose_nfkc, is_whitespace, lowercase, split_on_special_tokens,
+    clean_text, decompose_nfkc, is_whitespace, lowercase, required_special_token_id,
+    split_on_special_tokens, SpecialTokenCache,
 };
 use crate::tokenizer::{MultiThreadedTokenizer, Tokenizer};
-use crate::vocab::{SentencePieceModel, Vocab, XLMRobertaVocab};
+use crate::vocab::{SentencePieceModel, SpecialTokenValues, Vocab, XLMRobertaVocab};
 
 /// # XLM RoBERTa tokenizer
 /// XLM RoBERTa tokenizer performing:
@@ -29,11 +31,17 @@ use crate::vocab::{SentencePieceModel, Vocab, XLMRobertaVocab};
 /// - NFKC decomposition
 /// - (optional) lower casing
 /// - SentencePiece decomposition
+///
+/// The tokenizer is generic over its vocabulary, allowing the use of a memory-mapped `MmapVocab`
+/// compiled from a `XLMRobertaVocab`.
 #[allow(clippy::upper_case_acronyms)]
-pub struct XLMRobertaTokenizer {
+pub struct XLMRobertaTokenizer<V = XLMRobertaVocab> {
     model: SentencePieceModel,
-    vocab: XLMRobertaVocab,
+    vocab: V,
+    special_token_cache: SpecialTokenCache,
     lower_case: bool,
+    cls_id: i64,
+    sep_id: i64,
 }
 
 impl XLMRobertaTokenizer {
@@ -57,11 +65,7 @@ impl XLMRobertaTokenizer {
     ) -> Result<XLMRobertaTokenizer, TokenizerError> {
         let model = SentencePieceModel::from_file(&path)?;
         let vocab = XLMRobertaVocab::from_file(path)?;
-        Ok(XLMRobertaTokenizer {
-            model,
-            vocab,
-            lower_case,
-        })
+        XLMRobertaTokenizer::from_existing_vocab_and_model(vocab, model, lower_case)
     }
 
     /// Create a new instance of a `XLMRobertaTokenizer`
@@ -94,17 +98,17 @@ impl XLMRobertaTokenizer {
             path,
             special_token_mapping_path,
         )?;
-        Ok(XLMRobertaTokenizer {
-            model,
-            vocab,
-            lower_case,
-        })
+        XLMRobertaTokenizer::from_existing_vocab_and_model(vocab, model, lower_case)
     }
+}
 
-    /// Create a new instance of a `MarianTokenizer` from an existing vocabulary and model
+impl<V: SpecialTokenValues> XLMRobertaTokenizer<V> {
+    /// Create a new instance of a `XLMRobertaTokenizer` from an existing vocabulary and model.
+    /// Returns a `TokenizerError::TokenNotFound` if the vocabulary does not contain the
+    /// classification or separation tokens.
     ///
     /// # Parameters
-    /// - vocab (`XLMRobertaVocab`): vocabulary
+    /// - vocab (`XLMRobertaVocab` or `MmapVocab`): vocabulary
     /// - model (`SentencePieceModel`): SentencePiece model
     /// - lower_case (`bool`): flag indicating if the text should be lower-cased as part of the tokenization
     ///
@@ -117,31 +121,37 @@ impl XLMRobertaTokenizer {
     /// let vocab = XLMRobertaVocab::from_file("path/to/vocab/file").unwrap();
     /// let model = SentencePieceModel::from_file("path/to/model/file").unwrap();
     ///
-    /// let tokenizer = XLMRobertaTokenizer::from_existing_vocab_and_model(vocab, model, lower_case);
+    /// let tokenizer =
+    ///     XLMRobertaTokenizer::from_existing_vocab_and_model(vocab, model, lower_case).unwrap();
     /// ```
     pub fn from_existing_vocab_and_model(
-        vocab: XLMRobertaVocab,
+        vocab: V,
         model: SentencePieceModel,
         lower_case: bool,
-    ) -> XLMRobertaTokenizer {
-        XLMRobertaTokenizer {
+    ) -> Result<XLMRobertaTokenizer<V>, TokenizerError> {
+        let cls_id = required_special_token_id(&vocab, vocab.cls_value(), "classification")?;
+        let sep_id = required_special_token_id(&vocab, vocab.sep_value(), "separation")?;
+        Ok(XLMRobertaTokenizer {
             model,
             vocab,
             lower_case,
-        }
+            special_token_cache: RwLock::new(None),
+            cls_id,
+            sep_id,
+        })
     }
 }
 
-impl Tokenizer<XLMRobertaVocab> for XLMRobertaTokenizer {
-    fn vocab(&self) -> &XLMRobertaVocab {
+impl<V: SpecialTokenValues> Tokenizer<V> for XLMRobertaTokenizer<V> {
+    fn vocab(&self) -> &V {
         &self.vocab
     }
-    fn vocab_mut(&mut self) -> &mut XLMRobertaVocab {
+    fn vocab_mut(&mut self) -> &mut V {
         &mut self.vocab
     }
 
     fn tokenize_to_tokens(&self, text: TokenRef) -> Vec<Token> {
-        let mut tokens = split_on_special_tokens(text, &self.vocab)
+        let mut tokens = split_on_special_tokens(text, &self.vocab, &self.special_token_cache)
             .into_iter()
             .map(|token| token.to_owned())
             .collect::<Vec<Token>>();
@@ -194,9 +204,9 @@ impl Tokenizer<XLMRobertaVocab> for XLMRobertaTokenizer {
         special_tokens_mask.extend(vec![0; tokens_ids_with_offsets_1.ids.len()]);
         special_tokens_mask.push(1);
         token_segment_ids.extend(vec![0; tokens_ids_with_offsets_1.ids.len() + 2]);
-        output.push(self.vocab.token_to_id(self.vocab.get_cls_value()));
+        output.push(self.cls_id);
         output.extend(tokens_ids_with_offsets_1.ids);
-        output.push(self.vocab.token_to_id(self.vocab.get_sep_value()));
+        output.push(self.sep_id);
         offsets.push(None);
         offsets.extend(tokens_ids_with_offsets_1.offsets);
         offsets.push(None);
@@ -212,9 +222,9 @@ impl Tokenizer<XLMRobertaVocab> for XLMRobertaTokenizer {
             special_tokens_mask.extend(vec![0; length]);
             special_tokens_mask.push(1);
             token_segment_ids.extend(vec![1; length + 2]);
-            output.push(self.vocab.token_to_id(self.vocab.get_sep_value()));
+            output.push(self.sep_id);
             output.extend(tokens_ids_with_offsets_2_value.ids);
-            output.push(self.vocab.token_to_id(self.vocab.get_sep_value()));
+            output.push(self.sep_id);
             offsets.push(None);
             offsets.extend(tokens_ids_with_offsets_2_value.offsets);
             original_offsets.push(vec![]);
@@ -236,4 +246,4 @@ impl Tokenizer<XLMRobertaVocab> for XLMRobertaTokenizer {
     }
 }
 
-impl MultiThreadedTokenizer<XLMRobertaVocab> for XLMRobertaTokenizer {}
+impl<V: SpecialTokenValues + Send + Sync> MultiThreadedTokenizer<V> for XLMRobertaTokenizer<V> {}
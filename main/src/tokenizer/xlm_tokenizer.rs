@@ -0,0 +1,449 @@
+// Copyright 2019 Facebook AI Research and The HuggingFace Inc. team.
+// Copyright 2019-2020 Guillaume Becquin
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//     http://www.apache.org/licenses/LICENSE-2.0
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::error::TokenizerError;
+use crate::tokenizer::base_tokenizer::{
+    Mask, Offset, OffsetSize, Token, TokenIdsWithOffsets, TokenIdsWithSpecialTokens, TokenRef,
+    Tokenizer,
+};
+use crate::tokenizer::tokenization_utils::{
+    fix_mask, lowercase, openai_gpt_bpe, split_on_bpe_pairs, split_on_special_tokens,
+    strip_accents, BpeCache, SpecialTokenCache,
+};
+use crate::tokenizer::{MosesPreTokenizer, MultiThreadedTokenizer};
+use crate::vocab::bpe_vocab::BpePairVocab;
+use crate::vocab::{Vocab, XLMVocab};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::RwLock;
+
+/// # XLM tokenizer
+/// XLM tokenizer (also used by FlauBERT) performing:
+/// - splitting on special characters
+/// - Moses pre-tokenization (punctuation normalization and splitting, see `MosesPreTokenizer`)
+/// - (optional) lower casing and accent stripping
+/// - BPE tokenization (`</w>` end-of-word suffix)
+pub struct XLMTokenizer {
+    vocab: XLMVocab,
+    special_token_cache: SpecialTokenCache,
+    bpe_ranks: BpePairVocab,
+    cache: BpeCache,
+    moses_pre_tokenizer: MosesPreTokenizer,
+    lower_case: bool,
+}
+
+impl XLMTokenizer {
+    /// Create a new instance of a `XLMTokenizer`
+    /// Expects a vocabulary json file and a fastBPE merges file (`first second count` per line) as an input.
+    ///
+    /// # Parameters
+    /// - vocab_path (`&str`): path to the vocabulary file
+    /// - merges_path (`&str`): path to the merges file (use as part of the BPE encoding process)
+    /// - language (`&str`): ISO 639-1 code of the language used for the Moses pre-tokenization
+    /// - lower_case (`bool`): flag indicating if the text should be lower-cased and accents stripped as part of the tokenization
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use rust_tokenizers::tokenizer::{Tokenizer, XLMTokenizer};
+    /// let lower_case = true;
+    /// let tokenizer = XLMTokenizer::from_file(
+    ///     "path/to/vocab/file",
+    ///     "path/to/merges/file",
+    ///     "en",
+    ///     lower_case,
+    /// )
+    /// .unwrap();
+    /// ```
+    pub fn from_file<V: AsRef<Path>, M: AsRef<Path>>(
+        vocab_path: V,
+        merges_path: M,
+        language: &str,
+        lower_case: bool,
+    ) -> Result<XLMTokenizer, TokenizerError> {
+        let vocab = XLMVocab::from_file(vocab_path)?;
+        let bpe_ranks = BpePairVocab::from_fastbpe_file(merges_path)?;
+        Ok(Self::from_existing_vocab_and_merges(
+            vocab, bpe_ranks, language, lower_case,
+        ))
+    }
+
+    /// Create a new instance of a `XLMTokenizer`
+    /// Expects a vocabulary json file, a fastBPE merges file and special token mapping file as inputs.
+    ///
+    /// # Parameters
+    /// - vocab_path (`&str`): path to the vocabulary file
+    /// - merges_path (`&str`): path to the merges file (use as part of the BPE encoding process)
+    /// - language (`&str`): ISO 639-1 code of the language used for the Moses pre-tokenization
+    /// - lower_case (`bool`): flag indicating if the text should be lower-cased and accents stripped as part of the tokenization
+    /// - special_token_mapping_path (`&str`): path to a special token mapping file to overwrite default special tokens
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use rust_tokenizers::tokenizer::{Tokenizer, XLMTokenizer};
+    /// let lower_case = true;
+    /// let tokenizer = XLMTokenizer::from_file_with_special_token_mapping(
+    ///     "path/to/vocab/file",
+    ///     "path/to/merges/file",
+    ///     "en",
+    ///     lower_case,
+    ///     "path/to/special/token/mapping/file",
+    /// )
+    /// .unwrap();
+    /// ```
+    pub fn from_file_with_special_token_mapping<V: AsRef<Path>, M: AsRef<Path>, S: AsRef<Path>>(
+        vocab_path: V,
+        merges_path: M,
+        language: &str,
+        lower_case: bool,
+        special_token_mapping_path: S,
+    ) -> Result<XLMTokenizer, TokenizerError> {
+        let vocab =
+            XLMVocab::from_file_with_special_token_mapping(vocab_path, special_token_mapping_path)?;
+        let bpe_ranks = BpePairVocab::from_fastbpe_file(merges_path)?;
+        Ok(Self::from_existing_vocab_and_merges(
+            vocab, bpe_ranks, language, lower_case,
+        ))
+    }
+
+    /// Create a new instance of a `XLMTokenizer` from an existing vocabulary and merges
+    ///
+    /// # Parameters
+    /// - vocab (`XLMVocab`): XLM vocabulary
+    /// - merges (`BpePairVocab`): BPE pairs vocabulary
+    /// - language (`&str`): ISO 639-1 code of the language used for the Moses pre-tokenization
+    /// - lower_case (`bool`): flag indicating if the text should be lower-cased and accents stripped as part of the tokenization
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use rust_tokenizers::tokenizer::{Tokenizer, XLMTokenizer};
+    /// use rust_tokenizers::vocab::{BpePairVocab, Vocab, XLMVocab};
+    /// let lower_case = true;
+    /// let vocab = XLMVocab::from_file("path/to/vocab/file").unwrap();
+    /// let merges = BpePairVocab::from_fastbpe_file("path/to/merges/file").unwrap();
+    ///
+    /// let tokenizer = XLMTokenizer::from_existing_vocab_and_merges(vocab, merges, "en", lower_case);
+    /// ```
+    pub fn from_existing_vocab_and_merges(
+        vocab: XLMVocab,
+        merges: BpePairVocab,
+        language: &str,
+        lower_case: bool,
+    ) -> XLMTokenizer {
+        let mut moses_pre_tokenizer = MosesPreTokenizer::new(language);
+        moses_pre_tokenizer.set_escape(false);
+        XLMTokenizer {
+            vocab,
+            special_token_cache: RwLock::new(None),
+            bpe_ranks: merges,
+            cache: RwLock::new(HashMap::new()),
+            moses_pre_tokenizer,
+            lower_case,
+        }
+    }
+}
+
+impl Tokenizer<XLMVocab> for XLMTokenizer {
+    fn vocab(&self) -> &XLMVocab {
+        &self.vocab
+    }
+    fn vocab_mut(&mut self) -> &mut XLMVocab {
+        &mut self.vocab
+    }
+
+    fn tokenize_to_tokens(&self, initial_token: TokenRef) -> Vec<Token> {
+        let tokens = split_on_special_tokens(initial_token, &self.vocab, &self.special_token_cache);
+
+        let mut sub_tokens = Vec::new();
+        for token in tokens {
+            if token.mask != Mask::Special && token.mask != Mask::Unknown {
+                for mut word in self.moses_pre_tokenizer.tokenize_to_tokens(token) {
+                    if self.lower_case {
+                        lowercase(&mut word);
+                        strip_accents(&mut word);
+                    }
+                    sub_tokens.extend(split_on_bpe_pairs(
+                        word.as_ref(),
+                        openai_gpt_bpe,
+                        &self.bpe_ranks,
+                        &self.cache,
+                        false,
+                    ));
+                }
+            } else {
+                sub_tokens.push(token.to_owned());
+            }
+        }
+        fix_mask(&mut sub_tokens);
+        sub_tokens
+    }
+
+    fn convert_tokens_to_string(&self, tokens: Vec<String>) -> String {
+        tokens.join("").replace("</w>", " ").trim().to_owned()
+    }
+
+    fn build_input_with_special_tokens(
+        &self,
+        tokens_ids_with_offsets_1: TokenIdsWithOffsets,
+        tokens_ids_with_offsets_2: Option<TokenIdsWithOffsets>,
+    ) -> TokenIdsWithSpecialTokens {
+        let mut output: Vec<i64> = vec![];
+        let mut token_segment_ids: Vec<i8> = vec![0; tokens_ids_with_offsets_1.ids.len() + 2];
+        let mut special_tokens_mask: Vec<i8> = vec![];
+        let mut offsets: Vec<Option<Offset>> = vec![];
+        let mut original_offsets: Vec<Vec<OffsetSize>> = vec![];
+        let mut mask: Vec<Mask> = vec![];
+        special_tokens_mask.push(1);
+        special_tokens_mask.extend(vec![0; tokens_ids_with_offsets_1.ids.len()]);
+        special_tokens_mask.push(1);
+        output.push(self.vocab.token_to_id(self.vocab.get_bos_value()));
+        output.extend(tokens_ids_with_offsets_1.ids);
+        output.push(self.vocab.token_to_id(self.vocab.get_sep_value()));
+        offsets.push(None);
+        offsets.extend(tokens_ids_with_offsets_1.offsets);
+        offsets.push(None);
+        original_offsets.push(vec![]);
+        original_offsets.extend(tokens_ids_with_offsets_1.reference_offsets);
+        original_offsets.push(vec![]);
+        mask.push(Mask::Special);
+        mask.extend(tokens_ids_with_offsets_1.masks);
+        mask.push(Mask::Special);
+        if let Some(tokens_ids_with_offsets_2_value) = tokens_ids_with_offsets_2 {
+            let length = tokens_ids_with_offsets_2_value.ids.len();
+            special_tokens_mask.extend(vec![0; length]);
+            special_tokens_mask.push(1);
+            token_segment_ids.extend(vec![1; length + 1]);
+            output.extend(tokens_ids_with_offsets_2_value.ids);
+            output.push(self.vocab.token_to_id(self.vocab.get_sep_value()));
+            offsets.extend(tokens_ids_with_offsets_2_value.offsets);
+            original_offsets.extend(tokens_ids_with_offsets_2_value.reference_offsets);
+            offsets.push(None);
+            original_offsets.push(vec![]);
+            mask.extend(tokens_ids_with_offsets_2_value.masks);
+            mask.push(Mask::Special);
+        }
+        TokenIdsWithSpecialTokens {
+            token_ids: output,
+            segment_ids: token_segment_ids,
+            special_tokens_mask,
+            token_offsets: offsets,
+            reference_offsets: original_offsets,
+            mask,
+        }
+    }
+}
+
+impl MultiThreadedTokenizer<XLMVocab> for XLMTokenizer {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tokenizer::base_tokenizer::{TokenizedInput, TruncationStrategy};
+    use crate::vocab::base_vocab::{swap_key_values, SpecialTokenMap};
+
+    fn generate_test_vocab() -> XLMVocab {
+        let values: HashMap<String, i64> = [
+            ("<s>".to_owned(), 0),
+            ("</s>".to_owned(), 1),
+            ("<pad>".to_owned(), 2),
+            ("<unk>".to_owned(), 3),
+            ("<special1>".to_owned(), 4),
+            ("the</w>".to_owned(), 5),
+            ("ear".to_owned(), 6),
+            ("th</w>".to_owned(), 7),
+            ("'s</w>".to_owned(), 8),
+            (".</w>".to_owned(), 9),
+            ("e".to_owned(), 10),
+            ("cole</w>".to_owned(), 11),
+            ("l".to_owned(), 12),
+            ("'</w>".to_owned(), 13),
+        ]
+        .iter()
+        .cloned()
+        .collect();
+
+        let special_token_map = SpecialTokenMap {
+            unk_token: "<unk>".to_string(),
+            pad_token: Some("<pad>".to_string()),
+            bos_token: Some("<s>".to_string()),
+            sep_token: Some("</s>".to_string()),
+            cls_token: Some("</s>".to_string()),
+            eos_token: Some("</s>".to_string()),
+            mask_token: Some("<special1>".to_string()),
+            additional_special_tokens: None,
+        };
+        let special_values: HashMap<String, i64> = [
+            ("<s>".to_owned(), 0),
+            ("</s>".to_owned(), 1),
+            ("<pad>".to_owned(), 2),
+            ("<unk>".to_owned(), 3),
+            ("<special1>".to_owned(), 4),
+        ]
+        .iter()
+        .cloned()
+        .collect();
+
+        let indices = swap_key_values(&values);
+        let special_indices = swap_key_values(&special_values);
+
+        XLMVocab {
+            values,
+            indices,
+            special_token_map,
+            special_values,
+            special_indices,
+        }
+    }
+
+    fn generate_test_merges() -> BpePairVocab {
+        let values: HashMap<(String, String), i64> = [
+            (("t".to_owned(), "h".to_owned()), 0),
+            (("th".to_owned(), "e</w>".to_owned()), 1),
+            (("e".to_owned(), "a".to_owned()), 2),
+            (("ea".to_owned(), "r".to_owned()), 3),
+            (("t".to_owned(), "h</w>".to_owned()), 4),
+            (("'".to_owned(), "s</w>".to_owned()), 5),
+            (("c".to_owned(), "o".to_owned()), 6),
+            (("l".to_owned(), "e</w>".to_owned()), 7),
+            (("co".to_owned(), "le</w>".to_owned()), 8),
+        ]
+        .iter()
+        .cloned()
+        .collect();
+
+        BpePairVocab { values }
+    }
+
+    #[test]
+    fn test_xlm_tokenizer() {
+        //        Given
+        let vocab = generate_test_vocab();
+        let merges = generate_test_merges();
+        let xlm_tokenizer = XLMTokenizer::from_existing_vocab_and_merges(vocab, merges, "en", true);
+        let test_tuples = [
+            (
+                "The Earth's.",
+                vec!["the</w>", "ear", "th</w>", "'s</w>", ".</w>"],
+            ),
+            ("the <special1>", vec!["the</w>", "<special1>"]),
+            ("", vec![]),
+            (" ", vec![]),
+        ];
+        let source_texts: Vec<&str> = test_tuples.iter().map(|v| v.0).collect();
+        let expected_results: Vec<Vec<&str>> = test_tuples.iter().map(|v| v.1.clone()).collect();
+
+        //        When & Then
+        for (source_text, expected_result) in test_tuples.iter() {
+            assert_eq!(xlm_tokenizer.tokenize(source_text), *expected_result);
+        }
+
+        assert_eq!(
+            MultiThreadedTokenizer::tokenize_list(&xlm_tokenizer, &source_texts),
+            expected_results
+        );
+    }
+
+    #[test]
+    fn test_xlm_tokenizer_elision() {
+        //        Given
+        let vocab = generate_test_vocab();
+        let merges = generate_test_merges();
+        let xlm_tokenizer = XLMTokenizer::from_existing_vocab_and_merges(vocab, merges, "fr", true);
+
+        //        When
+        let tokens = xlm_tokenizer.tokenize_with_offsets("L'École");
+
+        //        Then
+        assert_eq!(tokens.tokens, vec!["l", "'</w>", "e", "cole</w>"]);
+        assert_eq!(
+            tokens.offsets,
+            vec![
+                Some(Offset::new(0, 1)),
+                Some(Offset::new(1, 2)),
+                Some(Offset::new(2, 3)),
+                Some(Offset::new(3, 7))
+            ]
+        );
+    }
+
+    #[test]
+    fn test_encode() {
+        //        Given
+        let vocab = generate_test_vocab();
+        let merges = generate_test_merges();
+        let xlm_tokenizer = XLMTokenizer::from_existing_vocab_and_merges(vocab, merges, "en", true);
+        let truncation_strategy = TruncationStrategy::LongestFirst;
+        let expected_result = TokenizedInput {
+            token_ids: vec![0, 5, 6, 7, 1, 9, 1],
+            segment_ids: vec![0, 0, 0, 0, 0, 1, 1],
+            special_tokens_mask: vec![1, 0, 0, 0, 1, 0, 1],
+            overflowing_tokens: vec![],
+            num_truncated_tokens: 0,
+            token_offsets: vec![
+                None,
+                Some(Offset::new(0, 3)),
+                Some(Offset::new(4, 7)),
+                Some(Offset::new(7, 9)),
+                None,
+                Some(Offset::new(0, 1)),
+                None,
+            ],
+            reference_offsets: vec![
+                vec![],
+                vec![0, 1, 2],
+                vec![4, 5, 6],
+                vec![7, 8],
+                vec![],
+                vec![0],
+                vec![],
+            ],
+            mask: vec![
+                Mask::Special,
+                Mask::None,
+                Mask::Begin,
+                Mask::Continuation,
+                Mask::Special,
+                Mask::None,
+                Mask::Special,
+            ],
+        };
+
+        //        When
+        let encoded_input =
+            xlm_tokenizer.encode("The Earth", Some("."), 128, &truncation_strategy, 0);
+
+        //        Then
+        assert_eq!(encoded_input, expected_result);
+    }
+
+    #[test]
+    fn test_decode() {
+        //        Given
+        let vocab = generate_test_vocab();
+        let merges = generate_test_merges();
+        let xlm_tokenizer = XLMTokenizer::from_existing_vocab_and_merges(vocab, merges, "en", true);
+        let test_tuples = [
+            (vec![0, 5, 6, 7, 8, 1], true, "the earth 's"),
+            (vec![0, 5, 6, 7, 1], false, "<s>the earth </s>"),
+        ];
+
+        //        When & Then
+        for (token_ids, skip_special_tokens, expected_result) in test_tuples.iter() {
+            assert_eq!(
+                xlm_tokenizer.decode(token_ids, *skip_special_tokens, false),
+                *expected_result
+            );
+        }
+    }
+}
@@ -11,12 +11,14 @@
 // limitations under the License.
 
 use std::path::Path;
+use std::sync::RwLock;
 
 use crate::error::TokenizerError;
 use crate::tokenizer::base_tokenizer::{TokenIdsWithOffsets, TokenIdsWithSpecialTokens};
 use crate::tokenizer::tokenization_utils::strip_accents;
 use crate::tokenizer::tokenization_utils::{
     clean_text, decompose_nfkc, is_whitespace, lowercase, replace_string, split_on_special_tokens,
+    SpecialTokenCache,
 };
 use crate::tokenizer::{MultiThreadedTokenizer, Tokenizer};
 use crate::vocab::{SentencePieceModel, Vocab, XLNetVocab};
@@ -34,6 +36,7 @@ use crate::{Mask, Offset, OffsetSize, Token, TokenRef};
 pub struct XLNetTokenizer {
     model: SentencePieceModel,
     vocab: XLNetVocab,
+    special_token_cache: SpecialTokenCache,
     lower_case: bool,
     strip_accents: bool,
 }
@@ -68,6 +71,7 @@ impl XLNetTokenizer {
             vocab,
             lower_case,
             strip_accents,
+            special_token_cache: RwLock::new(None),
         })
     }
 
@@ -108,6 +112,7 @@ impl XLNetTokenizer {
             vocab,
             lower_case,
             strip_accents,
+            special_token_cache: RwLock::new(None),
         })
     }
 
@@ -143,6 +148,7 @@ impl XLNetTokenizer {
             vocab,
             lower_case,
             strip_accents,
+            special_token_cache: RwLock::new(None),
         }
     }
 
@@ -201,7 +207,7 @@ impl Tokenizer<XLNetVocab> for XLNetTokenizer {
     }
 
     fn tokenize_to_tokens(&self, text: TokenRef) -> Vec<Token> {
-        let mut tokens = split_on_special_tokens(text, &self.vocab)
+        let mut tokens = split_on_special_tokens(text, &self.vocab, &self.special_token_cache)
             .into_iter()
             .map(|token| token.to_owned())
             .collect::<Vec<Token>>();
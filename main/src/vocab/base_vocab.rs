@@ -12,7 +12,7 @@
 use crate::error::TokenizerError;
 use crate::vocab::sentencepiece_proto::sentencepiece_model::ModelProto;
 use protobuf::Message;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 use std::fs::File;
 use std::hash::Hash;
@@ -153,7 +153,7 @@ pub(crate) fn register_as_special_value(
     Ok(())
 }
 
-#[derive(Debug, Default, Clone, Deserialize)]
+#[derive(Debug, Default, Clone, Deserialize, Serialize)]
 pub struct SpecialTokenMap {
     pub unk_token: String,
     pub pad_token: Option<String>,
@@ -201,6 +201,55 @@ impl SpecialTokenMap {
     }
 }
 
+/// # Special token values
+/// Special tokens of a vocabulary (as returned by its `get_*_value` methods), for tokenizers and
+/// utilities that are generic over the vocabulary. Tokens not used by a vocabulary are `None`.
+pub trait SpecialTokenValues: Vocab {
+    /// Returns the padding token
+    fn pad_value(&self) -> Option<&str> {
+        None
+    }
+
+    /// Returns the beginning of sequence token
+    fn bos_value(&self) -> Option<&str> {
+        None
+    }
+
+    /// Returns the separator token
+    fn sep_value(&self) -> Option<&str> {
+        None
+    }
+
+    /// Returns the classification token
+    fn cls_value(&self) -> Option<&str> {
+        None
+    }
+
+    /// Returns the end of sequence token
+    fn eos_value(&self) -> Option<&str> {
+        None
+    }
+
+    /// Returns the mask token
+    fn mask_value(&self) -> Option<&str> {
+        None
+    }
+}
+
+/// Implements `SpecialTokenValues` for a vocabulary from its `get_*_value` methods
+macro_rules! impl_special_token_values {
+    ($vocab:ty $(, $name:ident => $getter:ident)*) => {
+        impl $crate::vocab::base_vocab::SpecialTokenValues for $vocab {
+            $(
+                fn $name(&self) -> Option<&str> {
+                    Some(self.$getter())
+                }
+            )*
+        }
+    };
+}
+pub(crate) use impl_special_token_values;
+
 /// # Base Vocab trait
 /// Defines a common interface to the vocabularies for use in the tokenizers.
 pub trait Vocab {
@@ -432,6 +481,8 @@ pub struct BaseVocab {
 
 const DEFAULT_UNK_TOKEN: &str = "[UNK]";
 
+impl_special_token_values!(BaseVocab);
+
 impl Vocab for BaseVocab {
     fn get_unknown_value(&self) -> &str {
         &self.special_token_map.unk_token
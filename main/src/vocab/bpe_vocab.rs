@@ -13,6 +13,7 @@
 use crate::error::TokenizerError;
 use crate::vocab::sentencepiece_proto::sentencepiece_model::ModelProto;
 use protobuf::Message;
+use serde::Deserialize;
 use std::collections::HashMap;
 use std::fs::File;
 use std::io::{BufRead, BufReader, Read};
@@ -78,6 +79,47 @@ impl BpePairVocab {
         Ok(BpePairVocab { values: data })
     }
 
+    /// Create a new `BpePairVocab` from a fastBPE codes file containing merges in the format
+    /// `first_element second_element count` (as used by XLM, FlauBERT or PhoBERT). The indices are
+    /// implied by the line position of each pair in the codes file. Unlike `from_file`, the file has
+    /// no header line.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use rust_tokenizers::vocab::BpePairVocab;
+    /// let path = "path/to/bpe.codes";
+    ///
+    /// let bpe_vocab = BpePairVocab::from_fastbpe_file(path);
+    /// ```
+    pub fn from_fastbpe_file<P: AsRef<Path>>(path: P) -> Result<BpePairVocab, TokenizerError> {
+        let f = File::open(&path).map_err(|e| {
+            TokenizerError::FileNotFound(format!(
+                "{} vocabulary file not found :{}",
+                path.as_ref().display(),
+                e
+            ))
+        })?;
+        let br = BufReader::new(f);
+        let mut data = HashMap::new();
+        let mut index = 0;
+        for line in br.lines() {
+            let line = match line {
+                Ok(value) => value,
+                Err(e) => {
+                    return Err(TokenizerError::VocabularyParsingError(e.to_string()));
+                }
+            };
+            let mut elements = line.split_whitespace();
+            if let (Some(first), Some(second)) = (elements.next(), elements.next()) {
+                data.insert((first.to_owned(), second.to_owned()), index);
+                index += 1;
+            }
+        }
+
+        Ok(BpePairVocab { values: data })
+    }
+
     /// Create a new `BpePairVocab` from a SentencePiece file containing a BPE model.
     ///
     /// # Example
@@ -155,6 +197,92 @@ impl BpePairVocab {
     }
 }
 
+#[derive(Deserialize)]
+struct TokenizerJsonFile {
+    model: TokenizerJsonModel,
+    #[serde(default)]
+    added_tokens: Vec<TokenizerJsonAddedToken>,
+}
+
+#[derive(Deserialize)]
+struct TokenizerJsonModel {
+    #[serde(rename = "type")]
+    model_type: Option<String>,
+    vocab: HashMap<String, i64>,
+    merges: Vec<TokenizerJsonMerge>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum TokenizerJsonMerge {
+    Joined(String),
+    Pair(String, String),
+}
+
+#[derive(Deserialize)]
+struct TokenizerJsonAddedToken {
+    id: i64,
+    content: String,
+}
+
+/// # BPE model read from a `tokenizer.json` file
+/// Vocabulary, merges and added tokens (with their ids) of a serialized BPE model.
+pub(crate) struct TokenizerJsonBpeModel {
+    pub(crate) values: HashMap<String, i64>,
+    pub(crate) merges: BpePairVocab,
+    pub(crate) added_tokens: Vec<(String, i64)>,
+}
+
+/// Read a BPE model from a `tokenizer.json` file. Merges may be serialized either as
+/// `"first second"` strings or as `["first", "second"]` pairs.
+pub(crate) fn read_tokenizer_json_bpe_file<P: AsRef<Path>>(
+    path: P,
+) -> Result<TokenizerJsonBpeModel, TokenizerError> {
+    let f = File::open(&path).map_err(|e| {
+        TokenizerError::FileNotFound(format!(
+            "{} vocabulary file not found :{}",
+            path.as_ref().display(),
+            e
+        ))
+    })?;
+    let br = BufReader::new(f);
+    let tokenizer_file: TokenizerJsonFile = serde_json::from_reader(br)
+        .map_err(|e| TokenizerError::VocabularyParsingError(e.to_string()))?;
+    if let Some(model_type) = &tokenizer_file.model.model_type {
+        if model_type != "BPE" {
+            return Err(TokenizerError::VocabularyParsingError(format!(
+                "Expected a BPE model, got {model_type}"
+            )));
+        }
+    }
+
+    let mut merges = HashMap::with_capacity(tokenizer_file.model.merges.len());
+    for (index, merge) in tokenizer_file.model.merges.into_iter().enumerate() {
+        let pair = match merge {
+            TokenizerJsonMerge::Pair(first, second) => (first, second),
+            TokenizerJsonMerge::Joined(merge) => match merge.split_once(' ') {
+                Some((first, second)) => (first.to_owned(), second.to_owned()),
+                None => {
+                    return Err(TokenizerError::VocabularyParsingError(format!(
+                        "Invalid merge {merge}"
+                    )));
+                }
+            },
+        };
+        merges.insert(pair, index as i64);
+    }
+
+    Ok(TokenizerJsonBpeModel {
+        values: tokenizer_file.model.vocab,
+        merges: BpePairVocab { values: merges },
+        added_tokens: tokenizer_file
+            .added_tokens
+            .into_iter()
+            .map(|token| (token.content, token.id))
+            .collect(),
+    })
+}
+
 //==============================
 // Unit tests
 //==============================
@@ -204,6 +332,30 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_create_pair_vocab_from_fastbpe_file() -> anyhow::Result<()> {
+        //        Given
+        let mut codes_file = tempfile::NamedTempFile::new()?;
+        write!(codes_file, "t h 1520\na n 870\n\nth e</w> 12")?;
+        let path = codes_file.into_temp_path();
+        let target_values: HashMap<(String, String), i64> = [
+            (("t".to_owned(), "h".to_owned()), 0),
+            (("a".to_owned(), "n".to_owned()), 1),
+            (("th".to_owned(), "e</w>".to_owned()), 2),
+        ]
+        .iter()
+        .cloned()
+        .collect();
+
+        //        When
+        let pair_vocab = BpePairVocab::from_fastbpe_file(&path)?;
+
+        //        Then
+        assert_eq!(pair_vocab.values, target_values);
+        drop(path);
+        Ok(())
+    }
+
     #[test]
     fn test_encode_byte_pairs() -> anyhow::Result<()> {
         //        Given
@@ -0,0 +1,259 @@
+// Copyright 2021 Google T5 Authors and HuggingFace Inc. team.
+// Copyright 2019-2020 Guillaume Becquin
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//     http://www.apache.org/licenses/LICENSE-2.0
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::error::TokenizerError;
+use crate::vocab::base_vocab::impl_special_token_values;
+use crate::vocab::base_vocab::{
+    read_json_file, read_special_token_mapping_file, swap_key_values, SpecialTokenMap,
+};
+use crate::vocab::Vocab;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+/// # ByT5 Vocab
+/// Byte-level vocabulary for ByT5 tokenizer. Contains the following special values:
+/// - PAD token (id 0)
+/// - EOS token (id 1)
+/// - UNK token (id 2)
+/// - extra ids (`<extra_id_N>`), at the top of the id range (`<extra_id_0>` has the largest id)
+///
+/// Each of the 256 byte values is represented by the character with the same code point (e.g. `"A"`
+/// for byte `0x41` or `"Ã"` for byte `0xC3`) and has an id equal to the byte value offset by 3.
+///
+/// No file is required to create this vocabulary (see `ByT5Vocab::new`). Expects a JSON-format
+/// vocabulary when created from file.
+#[derive(Debug, Clone)]
+pub struct ByT5Vocab {
+    /// A mapping of tokens as string to indices (i.e. the encoder base)
+    pub values: HashMap<String, i64>,
+
+    /// A mapping of token ids to strings (i.e. the decoder base)
+    pub indices: HashMap<i64, String>,
+
+    /// Special tokens used by the vocabulary
+    pub special_token_map: SpecialTokenMap,
+
+    /// A mapping of special value tokens as strings to IDs (i.e. the encoder base for special
+    /// values), special values typically include things like BOS/EOS markers, class markers, mask
+    /// markers and padding markers
+    pub special_values: HashMap<String, i64>,
+
+    /// A mapping of special value tokens as IDs to strings (i.e. the decoder base for special values)
+    pub special_indices: HashMap<i64, String>,
+}
+
+const DEFAULT_UNK_TOKEN: &str = "<unk>";
+const DEFAULT_PAD_TOKEN: &str = "<pad>";
+const DEFAULT_EOS_TOKEN: &str = "</s>";
+const DEFAULT_NUM_EXTRA_IDS: i64 = 125;
+
+/// Offset of the byte ids (number of special tokens placed before the bytes)
+const BYTE_OFFSET: i64 = 3;
+
+impl ByT5Vocab {
+    /// Create a new byte-level vocabulary.
+    ///
+    /// # Parameters
+    /// - num_extra_ids (`i64`): number of sentinel tokens (`<extra_id_N>`) appended after the byte values
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_tokenizers::vocab::{ByT5Vocab, Vocab};
+    /// let vocab = ByT5Vocab::new(125);
+    ///
+    /// assert_eq!(vocab.token_to_id("A"), 0x41 + 3);
+    /// assert_eq!(vocab.token_to_id("<extra_id_0>"), 383);
+    /// ```
+    pub fn new(num_extra_ids: i64) -> ByT5Vocab {
+        let mut values = HashMap::new();
+        values.insert(DEFAULT_PAD_TOKEN.to_string(), 0);
+        values.insert(DEFAULT_EOS_TOKEN.to_string(), 1);
+        values.insert(DEFAULT_UNK_TOKEN.to_string(), 2);
+        for byte in 0..=255u8 {
+            values.insert(char::from(byte).to_string(), byte as i64 + BYTE_OFFSET);
+        }
+        let top_id = 255 + BYTE_OFFSET + num_extra_ids;
+        let mut additional_special_tokens = HashSet::new();
+        for extra_id in 0..num_extra_ids {
+            let token = format!("<extra_id_{extra_id}>");
+            values.insert(token.clone(), top_id - extra_id);
+            additional_special_tokens.insert(token);
+        }
+
+        let special_token_map = SpecialTokenMap {
+            unk_token: DEFAULT_UNK_TOKEN.to_string(),
+            pad_token: Some(DEFAULT_PAD_TOKEN.to_string()),
+            bos_token: None,
+            sep_token: None,
+            cls_token: None,
+            eos_token: Some(DEFAULT_EOS_TOKEN.to_string()),
+            mask_token: None,
+            additional_special_tokens: Some(additional_special_tokens),
+        };
+        // All special tokens are part of the values by construction
+        Self::from_values_and_special_token_map(values, special_token_map).unwrap()
+    }
+
+    pub fn get_pad_value(&self) -> &str {
+        self.special_token_map
+            .pad_token
+            .as_deref()
+            .unwrap_or(DEFAULT_PAD_TOKEN)
+    }
+
+    pub fn get_eos_value(&self) -> &str {
+        self.special_token_map
+            .eos_token
+            .as_deref()
+            .unwrap_or(DEFAULT_EOS_TOKEN)
+    }
+}
+
+impl Default for ByT5Vocab {
+    fn default() -> Self {
+        Self::new(DEFAULT_NUM_EXTRA_IDS)
+    }
+}
+
+impl_special_token_values!(ByT5Vocab, pad_value => get_pad_value, eos_value => get_eos_value);
+
+impl Vocab for ByT5Vocab {
+    fn get_unknown_value(&self) -> &str {
+        &self.special_token_map.unk_token
+    }
+
+    fn values(&self) -> &HashMap<String, i64> {
+        &self.values
+    }
+
+    fn indices(&self) -> &HashMap<i64, String> {
+        &self.indices
+    }
+
+    fn special_values(&self) -> &HashMap<String, i64> {
+        &self.special_values
+    }
+
+    fn special_indices(&self) -> &HashMap<i64, String> {
+        &self.special_indices
+    }
+
+    fn values_mut(&mut self) -> &mut HashMap<String, i64> {
+        &mut self.values
+    }
+
+    fn indices_mut(&mut self) -> &mut HashMap<i64, String> {
+        &mut self.indices
+    }
+
+    fn special_values_mut(&mut self) -> &mut HashMap<String, i64> {
+        &mut self.special_values
+    }
+
+    fn special_indices_mut(&mut self) -> &mut HashMap<i64, String> {
+        &mut self.special_indices
+    }
+
+    fn from_file<P: AsRef<Path>>(path: P) -> Result<ByT5Vocab, TokenizerError> {
+        let values = read_json_file(path)?;
+        let special_token_map = SpecialTokenMap {
+            unk_token: DEFAULT_UNK_TOKEN.to_string(),
+            pad_token: Some(DEFAULT_PAD_TOKEN.to_string()),
+            bos_token: None,
+            sep_token: None,
+            cls_token: None,
+            eos_token: Some(DEFAULT_EOS_TOKEN.to_string()),
+            mask_token: None,
+            additional_special_tokens: None,
+        };
+        Self::from_values_and_special_token_map(values, special_token_map)
+    }
+
+    fn from_file_with_special_token_mapping<P: AsRef<Path>, S: AsRef<Path>>(
+        path: P,
+        special_token_mapping_path: S,
+    ) -> Result<Self, TokenizerError> {
+        let values = read_json_file(path)?;
+        let special_token_map = read_special_token_mapping_file(special_token_mapping_path)?;
+        Self::from_values_and_special_token_map(values, special_token_map)
+    }
+
+    fn from_values_and_special_token_map(
+        values: HashMap<String, i64>,
+        special_token_map: SpecialTokenMap,
+    ) -> Result<Self, TokenizerError>
+    where
+        Self: Sized,
+    {
+        let mut special_values = HashMap::new();
+        special_token_map.register_special_values(&values, &mut special_values)?;
+
+        let indices = swap_key_values(&values);
+        let special_indices = swap_key_values(&special_values);
+        Ok(Self {
+            values,
+            indices,
+            special_token_map,
+            special_values,
+            special_indices,
+        })
+    }
+
+    fn token_to_id(&self, token: &str) -> i64 {
+        self._token_to_id(
+            token,
+            &self.values,
+            &self.special_values,
+            self.get_unknown_value(),
+        )
+    }
+
+    fn id_to_token(&self, id: &i64) -> String {
+        self._id_to_token(
+            id,
+            &self.indices,
+            &self.special_indices,
+            self.get_unknown_value(),
+        )
+    }
+}
+
+//==============================
+// Unit tests
+//==============================
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_create_vocab() {
+        //        Given
+        let byt5_vocab = ByT5Vocab::default();
+
+        //        When & Then
+        assert_eq!(byt5_vocab.values.len(), 3 + 256 + 125);
+        assert_eq!(byt5_vocab.special_values.len(), 3 + 125);
+        assert_eq!(byt5_vocab.token_to_id("<pad>"), 0);
+        assert_eq!(byt5_vocab.token_to_id("</s>"), 1);
+        assert_eq!(byt5_vocab.token_to_id("<unk>"), 2);
+        assert_eq!(byt5_vocab.token_to_id("\u{0}"), 3);
+        assert_eq!(byt5_vocab.token_to_id("a"), 100);
+        assert_eq!(byt5_vocab.token_to_id("\u{FF}"), 258);
+        assert_eq!(byt5_vocab.token_to_id("<extra_id_0>"), 383);
+        assert_eq!(byt5_vocab.token_to_id("<extra_id_124>"), 259);
+        assert_eq!(byt5_vocab.token_to_id("ab"), 2);
+        assert_eq!(byt5_vocab.id_to_token(&100), "a");
+        assert_eq!(byt5_vocab.id_to_token(&382), "<extra_id_1>");
+        assert_eq!(byt5_vocab.id_to_token(&1000), "<unk>");
+    }
+}
@@ -0,0 +1,316 @@
+// Copyright 2021 Google AI and HuggingFace Inc. team.
+// Copyright 2019-2020 Guillaume Becquin
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//     http://www.apache.org/licenses/LICENSE-2.0
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::error::TokenizerError;
+use crate::vocab::base_vocab::impl_special_token_values;
+use crate::vocab::base_vocab::{
+    read_json_file, read_special_token_mapping_file, swap_key_values, SpecialTokenMap,
+};
+use crate::vocab::Vocab;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::path::Path;
+
+/// # CANINE Vocab
+/// Codepoint-level vocabulary for CANINE tokenizer: the id of a character is its Unicode code point.
+/// Contains the following special values, mapped to code points of the Private Use Area:
+/// - PAD token (`[PAD]`, id 0)
+/// - CLS token (`[CLS]`, id 0xE000)
+/// - SEP token (`[SEP]`, id 0xE001)
+/// - BOS token (`[BOS]`, id 0xE002)
+/// - MASK token (`[MASK]`, id 0xE003)
+/// - UNK token (`[UNK]`, reserved id 0xE004), used for strings that are neither a special token nor
+///   a single character
+///
+/// The `values` and `indices` only hold the special values (all other ids are computed from the
+/// code points). No file is required to create this vocabulary (see `CanineVocab::new`). Expects a
+/// JSON-format mapping of special tokens to ids when created from file.
+#[derive(Debug, Clone)]
+pub struct CanineVocab {
+    /// A mapping of special tokens as string to indices (character ids are computed from their code point)
+    pub values: HashMap<String, i64>,
+
+    /// A mapping of special token ids to strings (character tokens are computed from their id)
+    pub indices: HashMap<i64, String>,
+
+    /// Special tokens used by the vocabulary
+    pub special_token_map: SpecialTokenMap,
+
+    /// A mapping of special value tokens as strings to IDs (i.e. the encoder base for special
+    /// values), special values typically include things like BOS/EOS markers, class markers, mask
+    /// markers and padding markers
+    pub special_values: HashMap<String, i64>,
+
+    /// A mapping of special value tokens as IDs to strings (i.e. the decoder base for special values)
+    pub special_indices: HashMap<i64, String>,
+}
+
+const DEFAULT_UNK_TOKEN: &str = "[UNK]";
+const DEFAULT_PAD_TOKEN: &str = "[PAD]";
+const DEFAULT_CLS_TOKEN: &str = "[CLS]";
+const DEFAULT_SEP_TOKEN: &str = "[SEP]";
+const DEFAULT_BOS_TOKEN: &str = "[BOS]";
+const DEFAULT_MASK_TOKEN: &str = "[MASK]";
+
+const PAD_CODEPOINT: i64 = 0;
+const CLS_CODEPOINT: i64 = 0xE000;
+const SEP_CODEPOINT: i64 = 0xE001;
+const BOS_CODEPOINT: i64 = 0xE002;
+const MASK_CODEPOINT: i64 = 0xE003;
+const RESERVED_CODEPOINT: i64 = 0xE004;
+
+impl CanineVocab {
+    /// Create a new codepoint-level vocabulary
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_tokenizers::vocab::{CanineVocab, Vocab};
+    /// let vocab = CanineVocab::new();
+    ///
+    /// assert_eq!(vocab.token_to_id("é"), 0xE9);
+    /// assert_eq!(vocab.token_to_id("[CLS]"), 0xE000);
+    /// ```
+    pub fn new() -> CanineVocab {
+        let values: HashMap<String, i64> = [
+            (DEFAULT_PAD_TOKEN.to_string(), PAD_CODEPOINT),
+            (DEFAULT_CLS_TOKEN.to_string(), CLS_CODEPOINT),
+            (DEFAULT_SEP_TOKEN.to_string(), SEP_CODEPOINT),
+            (DEFAULT_BOS_TOKEN.to_string(), BOS_CODEPOINT),
+            (DEFAULT_MASK_TOKEN.to_string(), MASK_CODEPOINT),
+            (DEFAULT_UNK_TOKEN.to_string(), RESERVED_CODEPOINT),
+        ]
+        .iter()
+        .cloned()
+        .collect();
+
+        let special_token_map = SpecialTokenMap {
+            unk_token: DEFAULT_UNK_TOKEN.to_string(),
+            pad_token: Some(DEFAULT_PAD_TOKEN.to_string()),
+            bos_token: Some(DEFAULT_BOS_TOKEN.to_string()),
+            sep_token: Some(DEFAULT_SEP_TOKEN.to_string()),
+            cls_token: Some(DEFAULT_CLS_TOKEN.to_string()),
+            eos_token: None,
+            mask_token: Some(DEFAULT_MASK_TOKEN.to_string()),
+            additional_special_tokens: None,
+        };
+        // All special tokens are part of the values by construction
+        Self::from_values_and_special_token_map(values, special_token_map).unwrap()
+    }
+
+    pub fn get_pad_value(&self) -> &str {
+        self.special_token_map
+            .pad_token
+            .as_deref()
+            .unwrap_or(DEFAULT_PAD_TOKEN)
+    }
+
+    pub fn get_sep_value(&self) -> &str {
+        self.special_token_map
+            .sep_token
+            .as_deref()
+            .unwrap_or(DEFAULT_SEP_TOKEN)
+    }
+
+    pub fn get_cls_value(&self) -> &str {
+        self.special_token_map
+            .cls_token
+            .as_deref()
+            .unwrap_or(DEFAULT_CLS_TOKEN)
+    }
+
+    pub fn get_bos_value(&self) -> &str {
+        self.special_token_map
+            .bos_token
+            .as_deref()
+            .unwrap_or(DEFAULT_BOS_TOKEN)
+    }
+
+    pub fn get_mask_value(&self) -> &str {
+        self.special_token_map
+            .mask_token
+            .as_deref()
+            .unwrap_or(DEFAULT_MASK_TOKEN)
+    }
+}
+
+impl Default for CanineVocab {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl_special_token_values!(CanineVocab, pad_value => get_pad_value, bos_value => get_bos_value, sep_value => get_sep_value, cls_value => get_cls_value, mask_value => get_mask_value);
+
+impl Vocab for CanineVocab {
+    fn get_unknown_value(&self) -> &str {
+        &self.special_token_map.unk_token
+    }
+
+    fn values(&self) -> &HashMap<String, i64> {
+        &self.values
+    }
+
+    fn indices(&self) -> &HashMap<i64, String> {
+        &self.indices
+    }
+
+    fn special_values(&self) -> &HashMap<String, i64> {
+        &self.special_values
+    }
+
+    fn special_indices(&self) -> &HashMap<i64, String> {
+        &self.special_indices
+    }
+
+    fn values_mut(&mut self) -> &mut HashMap<String, i64> {
+        &mut self.values
+    }
+
+    fn indices_mut(&mut self) -> &mut HashMap<i64, String> {
+        &mut self.indices
+    }
+
+    fn special_values_mut(&mut self) -> &mut HashMap<String, i64> {
+        &mut self.special_values
+    }
+
+    fn special_indices_mut(&mut self) -> &mut HashMap<i64, String> {
+        &mut self.special_indices
+    }
+
+    fn from_file<P: AsRef<Path>>(path: P) -> Result<CanineVocab, TokenizerError> {
+        let values = read_json_file(path)?;
+        let special_token_map = SpecialTokenMap {
+            unk_token: DEFAULT_UNK_TOKEN.to_string(),
+            pad_token: Some(DEFAULT_PAD_TOKEN.to_string()),
+            bos_token: Some(DEFAULT_BOS_TOKEN.to_string()),
+            sep_token: Some(DEFAULT_SEP_TOKEN.to_string()),
+            cls_token: Some(DEFAULT_CLS_TOKEN.to_string()),
+            eos_token: None,
+            mask_token: Some(DEFAULT_MASK_TOKEN.to_string()),
+            additional_special_tokens: None,
+        };
+        Self::from_values_and_special_token_map(values, special_token_map)
+    }
+
+    fn from_file_with_special_token_mapping<P: AsRef<Path>, S: AsRef<Path>>(
+        path: P,
+        special_token_mapping_path: S,
+    ) -> Result<Self, TokenizerError> {
+        let values = read_json_file(path)?;
+        let special_token_map = read_special_token_mapping_file(special_token_mapping_path)?;
+        Self::from_values_and_special_token_map(values, special_token_map)
+    }
+
+    fn from_values_and_special_token_map(
+        values: HashMap<String, i64>,
+        special_token_map: SpecialTokenMap,
+    ) -> Result<Self, TokenizerError>
+    where
+        Self: Sized,
+    {
+        let mut special_values = HashMap::new();
+        special_token_map.register_special_values(&values, &mut special_values)?;
+
+        let indices = swap_key_values(&values);
+        let special_indices = swap_key_values(&special_values);
+        Ok(Self {
+            values,
+            indices,
+            special_token_map,
+            special_values,
+            special_indices,
+        })
+    }
+
+    fn token_to_id(&self, token: &str) -> i64 {
+        if let Some(id) = self.values.get(token) {
+            return *id;
+        }
+        let mut characters = token.chars();
+        match (characters.next(), characters.next()) {
+            (Some(character), None) => u32::from(character) as i64,
+            _ => self.values[self.get_unknown_value()],
+        }
+    }
+
+    fn id_to_token(&self, id: &i64) -> String {
+        match self.indices.get(id) {
+            Some(token) => token.clone(),
+            None => u32::try_from(*id)
+                .ok()
+                .and_then(char::from_u32)
+                .unwrap_or(char::REPLACEMENT_CHARACTER)
+                .to_string(),
+        }
+    }
+
+    /// Add arbitrary tokens to the vocabulary.
+    ///
+    /// The tokens are assigned to unused code points of the Private Use Area, following the
+    /// reserved code point.
+    ///
+    /// # Parameters
+    /// - tokens (`&[&str]`): list of tokens to add to the vocabulary
+    fn add_tokens(&mut self, tokens: &[&str]) {
+        let mut current_index = self
+            .indices
+            .keys()
+            .copied()
+            .max()
+            .unwrap_or(RESERVED_CODEPOINT)
+            .max(RESERVED_CODEPOINT)
+            + 1;
+        for token in tokens {
+            if !self.values.contains_key(*token) {
+                self.values.insert(token.to_string(), current_index);
+                self.indices.insert(current_index, token.to_string());
+                self.special_values.insert(token.to_string(), current_index);
+                self.special_indices
+                    .insert(current_index, token.to_string());
+                current_index += 1;
+            }
+        }
+    }
+}
+
+//==============================
+// Unit tests
+//==============================
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_decode_tokens() {
+        //        Given
+        let mut canine_vocab = CanineVocab::new();
+
+        //        When & Then
+        assert_eq!(canine_vocab.token_to_id("a"), 97);
+        assert_eq!(canine_vocab.token_to_id("😀"), 0x1F600);
+        assert_eq!(canine_vocab.token_to_id("[CLS]"), 0xE000);
+        assert_eq!(canine_vocab.token_to_id("[PAD]"), 0);
+        assert_eq!(canine_vocab.token_to_id("ab"), 0xE004);
+        assert_eq!(canine_vocab.token_to_id(""), 0xE004);
+        assert_eq!(canine_vocab.id_to_token(&97), "a");
+        assert_eq!(canine_vocab.id_to_token(&0xE001), "[SEP]");
+        assert_eq!(canine_vocab.id_to_token(&0xD800), "\u{FFFD}");
+        assert_eq!(canine_vocab.id_to_token(&-1), "\u{FFFD}");
+
+        canine_vocab.add_tokens(&["[NEW]", "[CLS]"]);
+        assert_eq!(canine_vocab.token_to_id("[NEW]"), 0xE005);
+        assert_eq!(canine_vocab.id_to_token(&0xE005), "[NEW]");
+        assert_eq!(canine_vocab.special_values.len(), 7);
+    }
+}
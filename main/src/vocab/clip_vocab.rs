@@ -0,0 +1,239 @@
+// Copyright 2021 The Open AI Team Authors and The HuggingFace Inc. team.
+// Copyright 2019 Guillaume Becquin
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//     http://www.apache.org/licenses/LICENSE-2.0
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::error::TokenizerError;
+use crate::vocab::base_vocab::impl_special_token_values;
+use crate::vocab::base_vocab::{
+    read_json_file, read_special_token_mapping_file, swap_key_values, SpecialTokenMap, Vocab,
+};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// # CLIP Vocab
+/// Vocabulary for CLIP tokenizer. Contains the following special values:
+/// - BOS token
+/// - EOS token
+/// - PAD token
+///
+/// Expects a JSON-format vocabulary when created from file.
+#[derive(Debug, Clone)]
+pub struct ClipVocab {
+    /// A mapping of tokens as string to indices (i.e. the encoder base)
+    pub values: HashMap<String, i64>,
+
+    /// A mapping of token ids to strings (i.e. the decoder base)
+    pub indices: HashMap<i64, String>,
+
+    /// Special tokens used by the vocabulary
+    pub special_token_map: SpecialTokenMap,
+
+    /// A mapping of special value tokens as strings to IDs (i.e. the encoder base for special
+    /// values), special values typically include things like BOS/EOS markers, class markers, mask
+    /// markers and padding markers
+    pub special_values: HashMap<String, i64>,
+
+    /// A mapping of special value tokens as IDs to strings (i.e. the decoder base for special values)
+    pub special_indices: HashMap<i64, String>,
+}
+
+const DEFAULT_UNK_TOKEN: &str = "<|endoftext|>";
+const DEFAULT_BOS_TOKEN: &str = "<|startoftext|>";
+const DEFAULT_EOS_TOKEN: &str = DEFAULT_UNK_TOKEN;
+const DEFAULT_PAD_TOKEN: &str = DEFAULT_UNK_TOKEN;
+
+impl ClipVocab {
+    pub fn get_bos_value(&self) -> &str {
+        self.special_token_map
+            .bos_token
+            .as_deref()
+            .unwrap_or(DEFAULT_BOS_TOKEN)
+    }
+
+    pub fn get_eos_value(&self) -> &str {
+        self.special_token_map
+            .eos_token
+            .as_deref()
+            .unwrap_or(DEFAULT_EOS_TOKEN)
+    }
+
+    pub fn get_pad_value(&self) -> &str {
+        self.special_token_map
+            .pad_token
+            .as_deref()
+            .unwrap_or(DEFAULT_PAD_TOKEN)
+    }
+}
+
+impl_special_token_values!(ClipVocab, pad_value => get_pad_value, bos_value => get_bos_value, eos_value => get_eos_value);
+
+impl Vocab for ClipVocab {
+    fn get_unknown_value(&self) -> &str {
+        &self.special_token_map.unk_token
+    }
+
+    fn values(&self) -> &HashMap<String, i64> {
+        &self.values
+    }
+
+    fn indices(&self) -> &HashMap<i64, String> {
+        &self.indices
+    }
+
+    fn special_values(&self) -> &HashMap<String, i64> {
+        &self.special_values
+    }
+
+    fn special_indices(&self) -> &HashMap<i64, String> {
+        &self.special_indices
+    }
+
+    fn values_mut(&mut self) -> &mut HashMap<String, i64> {
+        &mut self.values
+    }
+
+    fn indices_mut(&mut self) -> &mut HashMap<i64, String> {
+        &mut self.indices
+    }
+
+    fn special_values_mut(&mut self) -> &mut HashMap<String, i64> {
+        &mut self.special_values
+    }
+
+    fn special_indices_mut(&mut self) -> &mut HashMap<i64, String> {
+        &mut self.special_indices
+    }
+
+    fn from_file<P: AsRef<Path>>(path: P) -> Result<ClipVocab, TokenizerError> {
+        let values = read_json_file(path)?;
+
+        let special_token_map = SpecialTokenMap {
+            unk_token: DEFAULT_UNK_TOKEN.to_string(),
+            pad_token: Some(DEFAULT_PAD_TOKEN.to_string()),
+            bos_token: Some(DEFAULT_BOS_TOKEN.to_string()),
+            sep_token: None,
+            cls_token: None,
+            eos_token: Some(DEFAULT_EOS_TOKEN.to_string()),
+            mask_token: None,
+            additional_special_tokens: None,
+        };
+        Self::from_values_and_special_token_map(values, special_token_map)
+    }
+
+    fn from_file_with_special_token_mapping<P: AsRef<Path>, S: AsRef<Path>>(
+        path: P,
+        special_token_mapping_path: S,
+    ) -> Result<Self, TokenizerError> {
+        let values = read_json_file(path)?;
+        let special_token_map = read_special_token_mapping_file(special_token_mapping_path)?;
+        Self::from_values_and_special_token_map(values, special_token_map)
+    }
+
+    fn from_values_and_special_token_map(
+        values: HashMap<String, i64>,
+        special_token_map: SpecialTokenMap,
+    ) -> Result<Self, TokenizerError>
+    where
+        Self: Sized,
+    {
+        let mut special_values = HashMap::new();
+        special_token_map.register_special_values(&values, &mut special_values)?;
+
+        let indices = swap_key_values(&values);
+        let special_indices = swap_key_values(&special_values);
+        Ok(Self {
+            values,
+            indices,
+            special_token_map,
+            special_values,
+            special_indices,
+        })
+    }
+
+    fn token_to_id(&self, token: &str) -> i64 {
+        self._token_to_id(
+            token,
+            &self.values,
+            &self.special_values,
+            self.get_unknown_value(),
+        )
+    }
+
+    fn id_to_token(&self, id: &i64) -> String {
+        self._id_to_token(
+            id,
+            &self.indices,
+            &self.special_indices,
+            self.get_unknown_value(),
+        )
+    }
+}
+
+//==============================
+// Unit tests
+//==============================
+#[cfg(test)]
+mod tests {
+    extern crate anyhow;
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn test_create_object_from_file() -> anyhow::Result<()> {
+        //        Given
+        let mut vocab_file = tempfile::NamedTempFile::new()?;
+        write!(
+            vocab_file,
+            "{{\"hello</w>\": 1,\n \"world</w>\": 0,\n \"<|startoftext|>\": 2,\n \"<|endoftext|>\": 3\n}}"
+        )?;
+        let path = vocab_file.into_temp_path();
+        let special_values: HashMap<String, i64> = [
+            ("<|startoftext|>".to_owned(), 2),
+            ("<|endoftext|>".to_owned(), 3),
+        ]
+        .iter()
+        .cloned()
+        .collect();
+
+        //        When
+        let clip_vocab = ClipVocab::from_file(&path)?;
+
+        //        Then
+        assert_eq!(clip_vocab.get_unknown_value(), "<|endoftext|>");
+        assert_eq!(clip_vocab.get_bos_value(), "<|startoftext|>");
+        assert_eq!(clip_vocab.get_eos_value(), "<|endoftext|>");
+        assert_eq!(clip_vocab.get_pad_value(), "<|endoftext|>");
+        assert_eq!(clip_vocab.special_values, special_values);
+        drop(path);
+        Ok(())
+    }
+
+    #[test]
+    fn test_encode_decode_tokens() -> anyhow::Result<()> {
+        //        Given
+        let mut vocab_file = tempfile::NamedTempFile::new()?;
+        write!(
+            vocab_file,
+            "{{\"hello</w>\": 1,\n \"world</w>\": 0,\n \"<|startoftext|>\": 2,\n \"<|endoftext|>\": 3\n}}"
+        )?;
+        let path = vocab_file.into_temp_path();
+        let clip_vocab = ClipVocab::from_file(&path)?;
+
+        //        When & Then
+        assert_eq!(clip_vocab.token_to_id("hello</w>"), 1);
+        assert_eq!(clip_vocab.token_to_id("<|startoftext|>"), 2);
+        assert_eq!(clip_vocab.token_to_id("oov_value"), 3);
+        assert_eq!(clip_vocab.id_to_token(&(0_i64)), "world</w>");
+        assert_eq!(clip_vocab.id_to_token(&(3_i64)), "<|endoftext|>");
+        drop(path);
+        Ok(())
+    }
+}
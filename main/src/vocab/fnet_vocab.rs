@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use crate::error::TokenizerError;
+use crate::vocab::base_vocab::impl_special_token_values;
 use crate::vocab::base_vocab::{
     read_protobuf_file, read_special_token_mapping_file, swap_key_values, SpecialTokenMap,
 };
@@ -84,6 +85,8 @@ impl FNetVocab {
     }
 }
 
+impl_special_token_values!(FNetVocab, pad_value => get_pad_value, sep_value => get_sep_value, cls_value => get_cls_value, mask_value => get_mask_value);
+
 impl Vocab for FNetVocab {
     fn get_unknown_value(&self) -> &str {
         &self.special_token_map.unk_token
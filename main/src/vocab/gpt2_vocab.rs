@@ -12,6 +12,7 @@
 // limitations under the License.
 
 use crate::error::TokenizerError;
+use crate::vocab::base_vocab::impl_special_token_values;
 use crate::vocab::base_vocab::{
     read_json_file, read_special_token_mapping_file, swap_key_values, SpecialTokenMap, Vocab,
 };
@@ -64,6 +65,8 @@ impl Gpt2Vocab {
     }
 }
 
+impl_special_token_values!(Gpt2Vocab, bos_value => get_bos_value, eos_value => get_eos_value);
+
 impl Vocab for Gpt2Vocab {
     fn get_unknown_value(&self) -> &str {
         &self.special_token_map.unk_token
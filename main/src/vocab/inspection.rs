@@ -0,0 +1,621 @@
+// Copyright 2019-2020 Guillaume Becquin
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//     http://www.apache.org/licenses/LICENSE-2.0
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::error::TokenizerError;
+use crate::vocab::base_vocab::{open_protobuf_file, SpecialTokenMap};
+use crate::vocab::sentencepiece_proto::sentencepiece_model::ModelProto_SentencePiece_Type;
+use crate::vocab::{BpePairVocab, Vocab};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::ops::Range;
+use std::path::Path;
+
+/// # Vocabulary inconsistency
+/// Disagreement between the maps of a vocabulary, as reported by `inspect_vocab`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub enum VocabMismatch {
+    /// Token of `values` whose id is absent from `indices`
+    MissingIndex { token: String, id: i64 },
+    /// Entry of `indices` whose token is absent from `values`
+    MissingValue { id: i64, token: String },
+    /// Token of `values` whose id maps to a different token in `indices`
+    ConflictingIndex {
+        token: String,
+        id: i64,
+        indexed_token: String,
+    },
+    /// Special token whose id differs from the id in `values` (`None` if absent from `values`)
+    SpecialValue {
+        token: String,
+        special_id: i64,
+        value_id: Option<i64>,
+    },
+    /// Special token whose id is absent from, or maps to another token in, `special_indices`
+    SpecialIndex {
+        token: String,
+        special_id: i64,
+        indexed_token: Option<String>,
+    },
+    /// The unknown token is not part of `values`
+    MissingUnknownToken { token: String },
+}
+
+/// # Vocabulary report
+/// Summary of a vocabulary returned by `inspect_vocab`. All lists are sorted by id, then token.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct VocabReport {
+    /// Number of entries in `values`
+    pub num_values: usize,
+    /// Number of entries in `indices`
+    pub num_indices: usize,
+    /// Unknown token of the vocabulary
+    pub unknown_token: String,
+    /// Special tokens and their ids
+    pub special_tokens: Vec<(String, i64)>,
+    /// Ids shared by several tokens of `values`, with the tokens mapped to them
+    pub duplicate_ids: Vec<(i64, Vec<String>)>,
+    /// Ranges of ids unused between 0 and the largest id of `values`
+    pub id_gaps: Vec<Range<i64>>,
+    /// Inconsistencies between `values`, `indices` and the special maps
+    pub mismatches: Vec<VocabMismatch>,
+}
+
+impl VocabReport {
+    /// Returns `true` if the vocabulary has no duplicate ids, no id gaps and no mismatches
+    pub fn is_consistent(&self) -> bool {
+        self.duplicate_ids.is_empty() && self.id_gaps.is_empty() && self.mismatches.is_empty()
+    }
+}
+
+/// Inspects a vocabulary, listing its special tokens and reporting duplicate ids, id gaps and
+/// mismatches between `values`, `indices`, `special_values` and `special_indices`.
+///
+/// Vocabularies with gaps or duplicate ids silently break `add_tokens`, which allocates new ids
+/// from the number of existing values.
+///
+/// # Parameters
+/// - vocab (`&V`): vocabulary to inspect
+///
+/// # Example
+///
+/// ```no_run
+/// use rust_tokenizers::vocab::{inspect_vocab, BertVocab, Vocab};
+/// let vocab = BertVocab::from_file("path/to/vocab/file").unwrap();
+///
+/// let report = inspect_vocab(&vocab);
+/// assert!(report.is_consistent());
+/// ```
+pub fn inspect_vocab<V: Vocab + ?Sized>(vocab: &V) -> VocabReport {
+    let values = vocab.values();
+    let indices = vocab.indices();
+    let special_indices = vocab.special_indices();
+
+    let mut tokens_by_id: BTreeMap<i64, Vec<String>> = BTreeMap::new();
+    for (token, id) in values {
+        tokens_by_id.entry(*id).or_default().push(token.clone());
+    }
+    let duplicate_ids = tokens_by_id
+        .iter()
+        .filter(|(_, tokens)| tokens.len() > 1)
+        .map(|(id, tokens)| {
+            let mut tokens = tokens.clone();
+            tokens.sort();
+            (*id, tokens)
+        })
+        .collect();
+
+    let mut id_gaps = Vec::new();
+    let mut next_id = 0;
+    for &id in tokens_by_id.keys().filter(|id| **id >= 0) {
+        if id > next_id {
+            id_gaps.push(next_id..id);
+        }
+        next_id = id + 1;
+    }
+
+    let mut mismatches = Vec::new();
+    for (token, id) in sorted_by_id(values) {
+        match indices.get(&id) {
+            None => mismatches.push(VocabMismatch::MissingIndex { token, id }),
+            Some(indexed_token) if *indexed_token != token => {
+                mismatches.push(VocabMismatch::ConflictingIndex {
+                    token,
+                    id,
+                    indexed_token: indexed_token.clone(),
+                })
+            }
+            Some(_) => {}
+        }
+    }
+    let mut missing_values: Vec<(i64, &String)> = indices
+        .iter()
+        .filter(|(_, token)| !values.contains_key(*token))
+        .map(|(id, token)| (*id, token))
+        .collect();
+    missing_values.sort();
+    mismatches.extend(
+        missing_values
+            .into_iter()
+            .map(|(id, token)| VocabMismatch::MissingValue {
+                id,
+                token: token.clone(),
+            }),
+    );
+    let special_tokens = sorted_by_id(vocab.special_values());
+    for (token, special_id) in special_tokens.iter() {
+        let value_id = values.get(token).copied();
+        if value_id != Some(*special_id) {
+            mismatches.push(VocabMismatch::SpecialValue {
+                token: token.clone(),
+                special_id: *special_id,
+                value_id,
+            });
+        }
+        let indexed_token = special_indices.get(special_id);
+        if indexed_token != Some(token) {
+            mismatches.push(VocabMismatch::SpecialIndex {
+                token: token.clone(),
+                special_id: *special_id,
+                indexed_token: indexed_token.cloned(),
+            });
+        }
+    }
+    let unknown_token = vocab.get_unknown_value().to_string();
+    if !values.contains_key(&unknown_token) {
+        mismatches.push(VocabMismatch::MissingUnknownToken {
+            token: unknown_token.clone(),
+        });
+    }
+
+    VocabReport {
+        num_values: values.len(),
+        num_indices: indices.len(),
+        unknown_token,
+        special_tokens,
+        duplicate_ids,
+        id_gaps,
+        mismatches,
+    }
+}
+
+/// # Special token entry
+/// Special token registered by a `SpecialTokenMap`, as returned by `list_special_tokens`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SpecialTokenEntry {
+    /// Role of the token in the map (`unk`, `pad`, `bos`, `sep`, `cls`, `eos`, `mask` or `additional`)
+    pub role: &'static str,
+    /// Token value
+    pub token: String,
+    /// Id of the token in the vocabulary, `None` if the vocabulary does not contain it
+    pub id: Option<i64>,
+}
+
+/// Lists the special tokens registered by a `SpecialTokenMap` with their role and their id in a
+/// vocabulary. Additional special tokens are sorted by id, then token.
+///
+/// # Parameters
+/// - special_token_map (`&SpecialTokenMap`): special token map to list
+/// - vocab (`&V`): vocabulary used to look up the token ids
+///
+/// # Example
+///
+/// ```no_run
+/// use rust_tokenizers::vocab::{list_special_tokens, BertVocab, Vocab};
+/// let vocab = BertVocab::from_file("path/to/vocab/file").unwrap();
+///
+/// for entry in list_special_tokens(&vocab.special_token_map, &vocab) {
+///     println!("{}: {} ({:?})", entry.role, entry.token, entry.id);
+/// }
+/// ```
+pub fn list_special_tokens<V: Vocab + ?Sized>(
+    special_token_map: &SpecialTokenMap,
+    vocab: &V,
+) -> Vec<SpecialTokenEntry> {
+    let entry = |role: &'static str, token: &str| SpecialTokenEntry {
+        role,
+        token: token.to_string(),
+        id: vocab
+            .special_values()
+            .get(token)
+            .or_else(|| vocab.values().get(token))
+            .copied(),
+    };
+    let mut entries = vec![entry("unk", &special_token_map.unk_token)];
+    for (role, token) in [
+        ("pad", &special_token_map.pad_token),
+        ("bos", &special_token_map.bos_token),
+        ("sep", &special_token_map.sep_token),
+        ("cls", &special_token_map.cls_token),
+        ("eos", &special_token_map.eos_token),
+        ("mask", &special_token_map.mask_token),
+    ] {
+        if let Some(token) = token {
+            entries.push(entry(role, token));
+        }
+    }
+    if let Some(additional_special_tokens) = &special_token_map.additional_special_tokens {
+        let mut additional_entries: Vec<SpecialTokenEntry> = additional_special_tokens
+            .iter()
+            .map(|token| entry("additional", token))
+            .collect();
+        additional_entries.sort_by(|a, b| (a.id, &a.token).cmp(&(b.id, &b.token)));
+        entries.extend(additional_entries);
+    }
+    entries
+}
+
+/// # Vocabulary diff
+/// Differences between two vocabularies returned by `diff_vocabs`. All lists are sorted by id of
+/// the first vocabulary (second vocabulary for `only_in_second`), then token.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct VocabDiff {
+    /// Tokens only present in the first vocabulary, with their id
+    pub only_in_first: Vec<(String, i64)>,
+    /// Tokens only present in the second vocabulary, with their id
+    pub only_in_second: Vec<(String, i64)>,
+    /// Tokens with different ids: (token, first id, second id)
+    pub changed_ids: Vec<(String, i64, i64)>,
+    /// Tokens present in both vocabularies but special in only one: (token, special in first)
+    pub changed_special_status: Vec<(String, bool)>,
+}
+
+impl VocabDiff {
+    /// Returns `true` if both vocabularies are identical
+    pub fn is_empty(&self) -> bool {
+        self.only_in_first.is_empty()
+            && self.only_in_second.is_empty()
+            && self.changed_ids.is_empty()
+            && self.changed_special_status.is_empty()
+    }
+}
+
+/// Compares the token to id mappings of two vocabularies, which may be of different types.
+///
+/// # Parameters
+/// - first (`&A`): first vocabulary
+/// - second (`&B`): second vocabulary
+///
+/// # Example
+///
+/// ```no_run
+/// use rust_tokenizers::vocab::{diff_vocabs, RobertaVocab, Vocab};
+/// let first = RobertaVocab::from_file("path/to/first/vocab").unwrap();
+/// let second = RobertaVocab::from_file("path/to/second/vocab").unwrap();
+///
+/// let diff = diff_vocabs(&first, &second);
+/// println!("{} tokens were removed", diff.only_in_first.len());
+/// ```
+pub fn diff_vocabs<A: Vocab + ?Sized, B: Vocab + ?Sized>(first: &A, second: &B) -> VocabDiff {
+    let mut diff = VocabDiff::default();
+    for (token, first_id) in sorted_by_id(first.values()) {
+        match second.values().get(&token) {
+            None => diff.only_in_first.push((token, first_id)),
+            Some(&second_id) => {
+                let first_special = first.special_values().contains_key(&token);
+                let second_special = second.special_values().contains_key(&token);
+                if first_special != second_special {
+                    diff.changed_special_status
+                        .push((token.clone(), first_special));
+                }
+                if first_id != second_id {
+                    diff.changed_ids.push((token, first_id, second_id));
+                }
+            }
+        }
+    }
+    diff.only_in_second = sorted_by_id(second.values())
+        .into_iter()
+        .filter(|(token, _)| !first.values().contains_key(token))
+        .collect();
+    diff
+}
+
+/// # Merges diff
+/// Differences between two BPE merge tables returned by `diff_merges`. All lists are sorted by
+/// rank, then pair.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct MergesDiff {
+    /// Merges only present in the first table, with their rank
+    pub only_in_first: Vec<((String, String), i64)>,
+    /// Merges only present in the second table, with their rank
+    pub only_in_second: Vec<((String, String), i64)>,
+    /// Merges with a different rank: (pair, first rank, second rank)
+    pub changed_ranks: Vec<((String, String), i64, i64)>,
+}
+
+impl MergesDiff {
+    /// Returns `true` if both merge tables are identical
+    pub fn is_empty(&self) -> bool {
+        self.only_in_first.is_empty()
+            && self.only_in_second.is_empty()
+            && self.changed_ranks.is_empty()
+    }
+}
+
+/// Compares two BPE merge tables.
+///
+/// # Parameters
+/// - first (`&BpePairVocab`): first merge table
+/// - second (`&BpePairVocab`): second merge table
+///
+/// # Example
+///
+/// ```no_run
+/// use rust_tokenizers::vocab::{diff_merges, BpePairVocab};
+/// let first = BpePairVocab::from_file("path/to/first/merges").unwrap();
+/// let second = BpePairVocab::from_file("path/to/second/merges").unwrap();
+///
+/// assert!(diff_merges(&first, &second).is_empty());
+/// ```
+pub fn diff_merges(first: &BpePairVocab, second: &BpePairVocab) -> MergesDiff {
+    let mut diff = MergesDiff::default();
+    for (pair, first_rank) in sorted_by_id(&first.values) {
+        match second.values.get(&pair) {
+            None => diff.only_in_first.push((pair, first_rank)),
+            Some(&second_rank) if second_rank != first_rank => {
+                diff.changed_ranks.push((pair, first_rank, second_rank))
+            }
+            Some(_) => {}
+        }
+    }
+    diff.only_in_second = sorted_by_id(&second.values)
+        .into_iter()
+        .filter(|(pair, _)| !first.values.contains_key(pair))
+        .collect();
+    diff
+}
+
+/// # SentencePiece model report
+/// Agreement between a SentencePiece model and a vocabulary, returned by
+/// `inspect_sentencepiece_model`. All lists are sorted by model id, then piece.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct SentencePieceReport {
+    /// Number of pieces in the model
+    pub num_pieces: usize,
+    /// Pieces of the model absent from the vocabulary, with their model id
+    pub missing_in_vocab: Vec<(String, i64)>,
+    /// Non-special tokens of the vocabulary absent from the model, with their vocabulary id
+    pub missing_in_model: Vec<(String, i64)>,
+    /// Pieces present several times in the model, with all their model ids
+    pub duplicate_pieces: Vec<(String, Vec<i64>)>,
+    /// Pieces with a different id in the model and the vocabulary: (piece, model id, vocab id).
+    /// For duplicate pieces, reported if the vocabulary id matches none of the model ids.
+    pub id_mismatches: Vec<(String, i64, i64)>,
+    /// Control, unknown and user-defined pieces not registered as special values in the vocabulary
+    pub unregistered_special_pieces: Vec<(String, i64)>,
+    /// Normal pieces with a non-finite or positive score (scores are log-probabilities)
+    pub invalid_scores: Vec<(String, f32)>,
+}
+
+impl SentencePieceReport {
+    /// Returns `true` if the model and the vocabulary agree
+    pub fn is_consistent(&self) -> bool {
+        self.missing_in_vocab.is_empty()
+            && self.missing_in_model.is_empty()
+            && self.duplicate_pieces.is_empty()
+            && self.id_mismatches.is_empty()
+            && self.unregistered_special_pieces.is_empty()
+            && self.invalid_scores.is_empty()
+    }
+}
+
+/// Checks that the pieces, types and scores of a SentencePiece model agree with a vocabulary.
+/// Tokens added to the vocabulary as special values (e.g. T5 extra ids) are not reported as
+/// missing from the model.
+///
+/// # Parameters
+/// - path (`&Path`): path to the SentencePiece model file
+/// - vocab (`&V`): vocabulary to compare with the model
+///
+/// # Example
+///
+/// ```no_run
+/// use rust_tokenizers::vocab::{inspect_sentencepiece_model, AlbertVocab, Vocab};
+/// let path = "path/to/spiece.model";
+/// let vocab = AlbertVocab::from_file(path).unwrap();
+///
+/// let report = inspect_sentencepiece_model(path, &vocab).unwrap();
+/// assert!(report.is_consistent());
+/// ```
+pub fn inspect_sentencepiece_model<P: AsRef<Path>, V: Vocab + ?Sized>(
+    path: P,
+    vocab: &V,
+) -> Result<SentencePieceReport, TokenizerError> {
+    let proto = open_protobuf_file(path)?;
+    let values = vocab.values();
+    let mut report = SentencePieceReport {
+        num_pieces: proto.get_pieces().len(),
+        ..Default::default()
+    };
+    let mut model_ids: HashMap<&str, Vec<i64>> = HashMap::new();
+    for (model_id, piece) in proto.get_pieces().iter().enumerate() {
+        model_ids
+            .entry(piece.get_piece())
+            .or_default()
+            .push(model_id as i64);
+    }
+    for (model_id, piece) in proto.get_pieces().iter().enumerate() {
+        let model_id = model_id as i64;
+        let token = piece.get_piece();
+        let piece_ids = &model_ids[token];
+        if piece_ids.len() > 1 && piece_ids[0] == model_id {
+            report
+                .duplicate_pieces
+                .push((token.to_string(), piece_ids.clone()));
+        }
+        match values.get(token) {
+            None => report.missing_in_vocab.push((token.to_string(), model_id)),
+            Some(vocab_id) if piece_ids[0] == model_id && !piece_ids.contains(vocab_id) => report
+                .id_mismatches
+                .push((token.to_string(), model_id, *vocab_id)),
+            Some(_) => {}
+        }
+        let is_special_piece = matches!(
+            piece.get_field_type(),
+            ModelProto_SentencePiece_Type::CONTROL
+                | ModelProto_SentencePiece_Type::UNKNOWN
+                | ModelProto_SentencePiece_Type::USER_DEFINED
+        );
+        if is_special_piece
+            && values.contains_key(token)
+            && !vocab.special_values().contains_key(token)
+        {
+            report
+                .unregistered_special_pieces
+                .push((token.to_string(), model_id));
+        }
+        let score = piece.get_score();
+        if piece.get_field_type() == ModelProto_SentencePiece_Type::NORMAL
+            && (!score.is_finite() || score > 0.0)
+        {
+            report.invalid_scores.push((token.to_string(), score));
+        }
+    }
+    report.missing_in_model = sorted_by_id(values)
+        .into_iter()
+        .filter(|(token, _)| {
+            !model_ids.contains_key(token.as_str()) && !vocab.special_values().contains_key(token)
+        })
+        .collect();
+    Ok(report)
+}
+
+fn sorted_by_id<K: Clone + Ord>(map: &HashMap<K, i64>) -> Vec<(K, i64)> {
+    let mut entries: Vec<(K, i64)> = map.iter().map(|(key, id)| (key.clone(), *id)).collect();
+    entries.sort_by(|a, b| (a.1, &a.0).cmp(&(b.1, &b.0)));
+    entries
+}
+
+//==============================
+// Unit tests
+//==============================
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vocab::{BaseVocab, BigBirdVocab};
+
+    fn generate_test_vocab(tokens: &[(&str, i64)]) -> BaseVocab {
+        let values: HashMap<String, i64> = tokens
+            .iter()
+            .map(|(token, id)| (token.to_string(), *id))
+            .collect();
+        let special_token_map = SpecialTokenMap {
+            unk_token: "[UNK]".to_string(),
+            ..Default::default()
+        };
+        BaseVocab::from_values_and_special_token_map(values, special_token_map).unwrap()
+    }
+
+    #[test]
+    fn test_inspect_vocab() {
+        //        Given
+        let consistent = generate_test_vocab(&[("[UNK]", 0), ("a", 1), ("b", 2)]);
+        let mut inconsistent = generate_test_vocab(&[("[UNK]", 0), ("a", 1), ("b", 1), ("c", 4)]);
+        inconsistent.indices.insert(7, "d".to_string());
+        inconsistent.special_indices = HashMap::new();
+
+        //        When
+        let consistent_report = inspect_vocab(&consistent);
+        let report = inspect_vocab(&inconsistent);
+
+        //        Then
+        assert!(consistent_report.is_consistent());
+        assert_eq!(
+            consistent_report.special_tokens,
+            vec![("[UNK]".to_string(), 0)]
+        );
+        assert!(!report.is_consistent());
+        assert_eq!(
+            report.duplicate_ids,
+            vec![(1, vec!["a".to_string(), "b".to_string()])]
+        );
+        assert_eq!(report.id_gaps, vec![2..4]);
+        assert_eq!(report.mismatches.len(), 3);
+        assert!(report.mismatches.contains(&VocabMismatch::MissingValue {
+            id: 7,
+            token: "d".to_string()
+        }));
+        assert!(report.mismatches.contains(&VocabMismatch::SpecialIndex {
+            token: "[UNK]".to_string(),
+            special_id: 0,
+            indexed_token: None
+        }));
+    }
+
+    #[test]
+    fn test_diff_vocabs_and_merges() {
+        //        Given
+        let first = generate_test_vocab(&[("[UNK]", 0), ("a", 1), ("b", 2)]);
+        let second = generate_test_vocab(&[("[UNK]", 0), ("b", 1), ("c", 2)]);
+        let first_merges = BpePairVocab {
+            values: vec![(("a".to_string(), "b".to_string()), 0)]
+                .into_iter()
+                .collect(),
+        };
+        let second_merges = BpePairVocab {
+            values: vec![
+                (("b".to_string(), "c".to_string()), 0),
+                (("a".to_string(), "b".to_string()), 1),
+            ]
+            .into_iter()
+            .collect(),
+        };
+
+        //        When
+        let diff = diff_vocabs(&first, &second);
+        let merges_diff = diff_merges(&first_merges, &second_merges);
+
+        //        Then
+        assert_eq!(diff.only_in_first, vec![("a".to_string(), 1)]);
+        assert_eq!(diff.only_in_second, vec![("c".to_string(), 2)]);
+        assert_eq!(diff.changed_ids, vec![("b".to_string(), 2, 1)]);
+        assert!(diff_vocabs(&first, &first).is_empty());
+        assert!(merges_diff.only_in_first.is_empty());
+        assert_eq!(
+            merges_diff.only_in_second,
+            vec![(("b".to_string(), "c".to_string()), 0)]
+        );
+        assert_eq!(
+            merges_diff.changed_ranks,
+            vec![(("a".to_string(), "b".to_string()), 0, 1)]
+        );
+    }
+
+    #[test]
+    fn test_inspect_sentencepiece_model() {
+        //        Given
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/fixtures/bigbird/spiece.model"
+        );
+        let vocab = BigBirdVocab::from_file(path).unwrap();
+        let mut shifted_vocab = BigBirdVocab::from_file(path).unwrap();
+        shifted_vocab.values.insert("<pad>".to_string(), 1000);
+
+        //        When
+        let report = inspect_sentencepiece_model(path, &vocab).unwrap();
+        let shifted_report = inspect_sentencepiece_model(path, &shifted_vocab).unwrap();
+
+        //        Then
+        assert!(report.num_pieces > 0);
+        assert!(report.missing_in_vocab.is_empty());
+        assert!(report.missing_in_model.is_empty());
+        assert_eq!(
+            report.duplicate_pieces,
+            vec![("s".to_string(), vec![12, 43])]
+        );
+        assert!(report.id_mismatches.is_empty());
+        assert_eq!(
+            shifted_report.id_mismatches,
+            vec![("<pad>".to_string(), 0, 1000)]
+        );
+    }
+}
@@ -11,6 +11,7 @@
 // limitations under the License.
 
 use crate::error::TokenizerError;
+use crate::vocab::base_vocab::impl_special_token_values;
 use crate::vocab::base_vocab::{
     read_json_file, read_special_token_mapping_file, register_as_special_value, swap_key_values,
     SpecialTokenMap,
@@ -97,6 +98,8 @@ impl M2M100Vocab {
     }
 }
 
+impl_special_token_values!(M2M100Vocab, pad_value => get_pad_value, bos_value => get_bos_value, sep_value => get_sep_value, eos_value => get_eos_value);
+
 impl Vocab for M2M100Vocab {
     fn get_unknown_value(&self) -> &str {
         &self.special_token_map.unk_token
@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use crate::error::TokenizerError;
+use crate::vocab::base_vocab::impl_special_token_values;
 use crate::vocab::base_vocab::{
     read_json_file, read_special_token_mapping_file, swap_key_values, SpecialTokenMap,
 };
@@ -66,6 +67,8 @@ impl MarianVocab {
     }
 }
 
+impl_special_token_values!(MarianVocab, pad_value => get_pad_value, eos_value => get_eos_value);
+
 impl Vocab for MarianVocab {
     fn get_unknown_value(&self) -> &str {
         &self.special_token_map.unk_token
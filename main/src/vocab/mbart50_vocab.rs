@@ -11,6 +11,7 @@
 // limitations under the License.
 
 use crate::error::TokenizerError;
+use crate::vocab::base_vocab::impl_special_token_values;
 use crate::vocab::base_vocab::{
     open_protobuf_file, read_special_token_mapping_file, register_as_special_value,
     swap_key_values, SpecialTokenMap,
@@ -105,6 +106,8 @@ impl MBart50Vocab {
     }
 }
 
+impl_special_token_values!(MBart50Vocab, pad_value => get_pad_value, sep_value => get_sep_value, cls_value => get_cls_value, eos_value => get_eos_value, mask_value => get_mask_value);
+
 impl Vocab for MBart50Vocab {
     fn get_unknown_value(&self) -> &str {
         &self.special_token_map.unk_token
@@ -0,0 +1,915 @@
+// Copyright 2019 Guillaume Becquin
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//     http://www.apache.org/licenses/LICENSE-2.0
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::error::TokenizerError;
+use crate::vocab::base_vocab::{
+    read_special_token_mapping_file, SpecialTokenMap, SpecialTokenValues, Vocab,
+};
+use memmap2::Mmap;
+use std::collections::HashMap;
+use std::convert::TryInto;
+use std::fmt;
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+use std::sync::OnceLock;
+
+const MAGIC: &[u8; 8] = b"RTKVOCAB";
+const FORMAT_VERSION: u32 = 1;
+const HEADER_SIZE: usize = 32;
+const ENTRY_SIZE: usize = 16;
+const BUCKET_SIZE: usize = 4;
+const SPECIAL_ENTRY_SIZE: usize = 4;
+
+/// FNV-1a hash, stable across processes and platforms (unlike the default `HashMap` hasher)
+fn fnv1a(bytes: &[u8]) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in bytes {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash
+}
+
+fn read_u32(data: &[u8], position: usize) -> u32 {
+    u32::from_le_bytes(data[position..position + 4].try_into().unwrap())
+}
+
+fn read_i64(data: &[u8], position: usize) -> i64 {
+    i64::from_le_bytes(data[position..position + 8].try_into().unwrap())
+}
+
+enum VocabData {
+    Mapped(Mmap),
+    Owned(Vec<u8>),
+}
+
+impl VocabData {
+    fn as_slice(&self) -> &[u8] {
+        match self {
+            VocabData::Mapped(mmap) => mmap,
+            VocabData::Owned(bytes) => bytes.as_slice(),
+        }
+    }
+}
+
+/// Serializes a vocabulary to the compiled binary format. The layout is (little-endian):
+/// - header: magic, format version, number of tokens, number of hash buckets, number of special
+///   tokens, length of the special token map
+/// - entries sorted by id: `(id: i64, string offset: u32, string length: u32)`
+/// - hash buckets (open addressing): entry position + 1, 0 for an empty bucket
+/// - special tokens: entry positions
+/// - special token map (JSON)
+/// - concatenated token strings (UTF-8)
+fn compile(
+    values: &HashMap<String, i64>,
+    special_values: &HashMap<String, i64>,
+    special_token_map: &SpecialTokenMap,
+) -> Result<Vec<u8>, TokenizerError> {
+    let mut entries: Vec<(&str, i64)> = values.iter().map(|(k, v)| (k.as_str(), *v)).collect();
+    entries.sort_by(|a, b| a.1.cmp(&b.1).then_with(|| a.0.cmp(b.0)));
+
+    let num_buckets = (entries.len() * 2).max(1).next_power_of_two();
+    let mut buckets = vec![0u32; num_buckets];
+    let mut strings: Vec<u8> = Vec::new();
+    let mut entry_bytes: Vec<u8> = Vec::with_capacity(entries.len() * ENTRY_SIZE);
+    let mut entry_positions: HashMap<&str, u32> = HashMap::with_capacity(entries.len());
+    for (position, (token, id)) in entries.iter().enumerate() {
+        entry_bytes.extend_from_slice(&id.to_le_bytes());
+        entry_bytes.extend_from_slice(&(strings.len() as u32).to_le_bytes());
+        entry_bytes.extend_from_slice(&(token.len() as u32).to_le_bytes());
+        strings.extend_from_slice(token.as_bytes());
+
+        let mut bucket = fnv1a(token.as_bytes()) as usize & (num_buckets - 1);
+        while buckets[bucket] != 0 {
+            bucket = (bucket + 1) & (num_buckets - 1);
+        }
+        buckets[bucket] = position as u32 + 1;
+        entry_positions.insert(token, position as u32);
+    }
+    if strings.len() > u32::MAX as usize {
+        return Err(TokenizerError::ValueError(
+            "Vocabulary too large for the compiled format".into(),
+        ));
+    }
+
+    let mut special_positions = Vec::with_capacity(special_values.len());
+    for token in special_values.keys() {
+        match entry_positions.get(token.as_str()) {
+            Some(position) => special_positions.push(*position),
+            None => {
+                return Err(TokenizerError::TokenNotFound(format!(
+                    "The special value {token} could not be found in the vocabulary"
+                )));
+            }
+        }
+    }
+    special_positions.sort_unstable();
+
+    let special_token_map = serde_json::to_vec(special_token_map)
+        .map_err(|e| TokenizerError::VocabularyParsingError(e.to_string()))?;
+
+    let mut output = Vec::with_capacity(
+        HEADER_SIZE
+            + entry_bytes.len()
+            + num_buckets * BUCKET_SIZE
+            + special_positions.len() * SPECIAL_ENTRY_SIZE
+            + special_token_map.len()
+            + strings.len(),
+    );
+    output.extend_from_slice(MAGIC);
+    output.extend_from_slice(&FORMAT_VERSION.to_le_bytes());
+    output.extend_from_slice(&(entries.len() as u32).to_le_bytes());
+    output.extend_from_slice(&(num_buckets as u32).to_le_bytes());
+    output.extend_from_slice(&(special_positions.len() as u32).to_le_bytes());
+    output.extend_from_slice(&(special_token_map.len() as u32).to_le_bytes());
+    output.extend_from_slice(&0u32.to_le_bytes());
+    output.extend_from_slice(&entry_bytes);
+    for bucket in buckets {
+        output.extend_from_slice(&bucket.to_le_bytes());
+    }
+    for position in special_positions {
+        output.extend_from_slice(&position.to_le_bytes());
+    }
+    output.extend_from_slice(&special_token_map);
+    output.extend_from_slice(&strings);
+    Ok(output)
+}
+
+/// # Memory-mapped vocabulary
+/// Vocabulary backed by a compiled binary file that is memory-mapped rather than parsed. Token to
+/// id and id to token lookups read directly from the mapped pages, so that several processes
+/// loading the same file on a host share a single copy of the vocabulary.
+///
+/// The compiled file is generated from any existing vocabulary with `MmapVocab::convert`. Only the
+/// special values are loaded in memory. The full `values` and `indices` maps required by the
+/// `Vocab` trait accessors are only materialized if these accessors are called (e.g. by a
+/// WordPiece tokenizer or when adding tokens to the vocabulary).
+///
+/// The vocabulary can be used with the tokenizers generic over their vocabulary (XLM-RoBERTa, NLLB
+/// and M2M100), e.g. `XLMRobertaTokenizer<MmapVocab>`.
+pub struct MmapVocab {
+    data: VocabData,
+
+    num_tokens: usize,
+
+    num_buckets: usize,
+
+    buckets_start: usize,
+
+    strings_start: usize,
+
+    /// Special tokens used by the vocabulary
+    pub special_token_map: SpecialTokenMap,
+
+    /// A mapping of special value tokens as strings to IDs (i.e. the encoder base for special
+    /// values), special values typically include things like BOS/EOS markers, class markers, mask
+    /// markers and padding markers
+    pub special_values: HashMap<String, i64>,
+
+    /// A mapping of special value tokens as IDs to strings (i.e. the decoder base for special values)
+    pub special_indices: HashMap<i64, String>,
+
+    unk_id: i64,
+
+    values: OnceLock<HashMap<String, i64>>,
+
+    indices: OnceLock<HashMap<i64, String>>,
+}
+
+impl fmt::Debug for MmapVocab {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MmapVocab")
+            .field("num_tokens", &self.num_tokens)
+            .field("special_token_map", &self.special_token_map)
+            .field("special_values", &self.special_values)
+            .finish()
+    }
+}
+
+impl MmapVocab {
+    /// Converts an existing vocabulary to the compiled binary format and writes it to a file.
+    /// The resulting file can be loaded with `MmapVocab::from_file`.
+    ///
+    /// # Parameters
+    /// - vocab (`&impl Vocab`): vocabulary to convert
+    /// - special_token_map (`&SpecialTokenMap`): special tokens used by the vocabulary
+    /// - output_path (`&Path`): path to the compiled vocabulary file to create
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use rust_tokenizers::vocab::{MmapVocab, Vocab, XLMRobertaVocab};
+    /// let vocab = XLMRobertaVocab::from_file("path/to/sentencepiece.bpe.model").unwrap();
+    ///
+    /// MmapVocab::convert(&vocab, &vocab.special_token_map, "path/to/vocab.bin").unwrap();
+    /// let mmap_vocab = MmapVocab::from_file("path/to/vocab.bin").unwrap();
+    /// ```
+    pub fn convert<V: Vocab, P: AsRef<Path>>(
+        vocab: &V,
+        special_token_map: &SpecialTokenMap,
+        output_path: P,
+    ) -> Result<(), TokenizerError> {
+        let bytes = compile(vocab.values(), vocab.special_values(), special_token_map)?;
+        let mut f = File::create(&output_path).map_err(|e| {
+            TokenizerError::IOError(format!(
+                "{} could not be created: {}",
+                output_path.as_ref().display(),
+                e
+            ))
+        })?;
+        f.write_all(&bytes)
+            .map_err(|e| TokenizerError::IOError(e.to_string()))
+    }
+
+    /// Writes the vocabulary (including tokens added since its creation) to a compiled file.
+    pub fn save<P: AsRef<Path>>(&self, output_path: P) -> Result<(), TokenizerError> {
+        match self.values.get() {
+            Some(_) => Self::convert(self, &self.special_token_map, output_path),
+            None => {
+                let mut f = File::create(&output_path).map_err(|e| {
+                    TokenizerError::IOError(format!(
+                        "{} could not be created: {}",
+                        output_path.as_ref().display(),
+                        e
+                    ))
+                })?;
+                f.write_all(self.data.as_slice())
+                    .map_err(|e| TokenizerError::IOError(e.to_string()))
+            }
+        }
+    }
+
+    /// Returns the number of tokens in the vocabulary
+    pub fn len(&self) -> usize {
+        match self.values.get() {
+            Some(values) => values.len(),
+            None => self.num_tokens,
+        }
+    }
+
+    /// Returns true if the vocabulary does not contain any token
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn from_data(data: VocabData) -> Result<MmapVocab, TokenizerError> {
+        let bytes = data.as_slice();
+        if bytes.len() < HEADER_SIZE || &bytes[..8] != MAGIC {
+            return Err(TokenizerError::VocabularyParsingError(
+                "Invalid compiled vocabulary file (missing header)".into(),
+            ));
+        }
+        let version = read_u32(bytes, 8);
+        if version != FORMAT_VERSION {
+            return Err(TokenizerError::VocabularyParsingError(format!(
+                "Unsupported compiled vocabulary version {version}"
+            )));
+        }
+        let num_tokens = read_u32(bytes, 12) as usize;
+        let num_buckets = read_u32(bytes, 16) as usize;
+        let num_special = read_u32(bytes, 20) as usize;
+        let special_token_map_len = read_u32(bytes, 24) as usize;
+
+        let buckets_start = HEADER_SIZE + num_tokens * ENTRY_SIZE;
+        let special_start = buckets_start + num_buckets * BUCKET_SIZE;
+        let special_token_map_start = special_start + num_special * SPECIAL_ENTRY_SIZE;
+        let strings_start = special_token_map_start + special_token_map_len;
+        if !num_buckets.is_power_of_two()
+            || num_buckets <= num_tokens
+            || strings_start > bytes.len()
+        {
+            return Err(TokenizerError::VocabularyParsingError(
+                "Invalid compiled vocabulary file (truncated or corrupted)".into(),
+            ));
+        }
+        let special_token_map: SpecialTokenMap =
+            serde_json::from_slice(&bytes[special_token_map_start..strings_start])
+                .map_err(|e| TokenizerError::VocabularyParsingError(e.to_string()))?;
+
+        let mut vocab = MmapVocab {
+            data,
+            num_tokens,
+            num_buckets,
+            buckets_start,
+            strings_start,
+            special_token_map,
+            special_values: HashMap::with_capacity(num_special),
+            special_indices: HashMap::with_capacity(num_special),
+            unk_id: 0,
+            values: OnceLock::new(),
+            indices: OnceLock::new(),
+        };
+        for special_position in 0..num_special {
+            let position = read_u32(
+                vocab.bytes(),
+                special_start + special_position * SPECIAL_ENTRY_SIZE,
+            ) as usize;
+            let (token, id) = vocab.entry(position)?;
+            let token = token.to_owned();
+            vocab.special_values.insert(token.clone(), id);
+            vocab.special_indices.insert(id, token);
+        }
+        vocab.unk_id = vocab.find_id(vocab.get_unknown_value()).ok_or_else(|| {
+            TokenizerError::VocabularyParsingError(format!(
+                "The unknown token {} could not be found in the compiled vocabulary",
+                vocab.get_unknown_value()
+            ))
+        })?;
+        Ok(vocab)
+    }
+
+    fn bytes(&self) -> &[u8] {
+        self.data.as_slice()
+    }
+
+    fn entry(&self, position: usize) -> Result<(&str, i64), TokenizerError> {
+        let bytes = self.bytes();
+        let entry_start = HEADER_SIZE + position * ENTRY_SIZE;
+        if position >= self.num_tokens {
+            return Err(TokenizerError::VocabularyParsingError(format!(
+                "Invalid entry position {position} in compiled vocabulary"
+            )));
+        }
+        let id = read_i64(bytes, entry_start);
+        let string_start = self.strings_start + read_u32(bytes, entry_start + 8) as usize;
+        let string_end = string_start + read_u32(bytes, entry_start + 12) as usize;
+        let token = bytes
+            .get(string_start..string_end)
+            .and_then(|token| std::str::from_utf8(token).ok())
+            .ok_or_else(|| {
+                TokenizerError::VocabularyParsingError(format!(
+                    "Invalid token string for entry {position} in compiled vocabulary"
+                ))
+            })?;
+        Ok((token, id))
+    }
+
+    /// Looks up the id of a token directly in the compiled data (zero-copy)
+    fn find_id(&self, token: &str) -> Option<i64> {
+        if self.num_tokens == 0 {
+            return None;
+        }
+        let mut bucket = fnv1a(token.as_bytes()) as usize & (self.num_buckets - 1);
+        loop {
+            let position = read_u32(self.bytes(), self.buckets_start + bucket * BUCKET_SIZE);
+            if position == 0 {
+                return None;
+            }
+            if let Ok((candidate, id)) = self.entry(position as usize - 1) {
+                if candidate == token {
+                    return Some(id);
+                }
+            }
+            bucket = (bucket + 1) & (self.num_buckets - 1);
+        }
+    }
+
+    /// Looks up the token for an id directly in the compiled data (zero-copy)
+    fn find_token(&self, id: i64) -> Option<&str> {
+        let bytes = self.bytes();
+        let (mut low, mut high) = (0usize, self.num_tokens);
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if read_i64(bytes, HEADER_SIZE + mid * ENTRY_SIZE) < id {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+        if low < self.num_tokens {
+            match self.entry(low) {
+                Ok((token, entry_id)) if entry_id == id => Some(token),
+                _ => None,
+            }
+        } else {
+            None
+        }
+    }
+
+    fn set_special_token_map(
+        &mut self,
+        special_token_map: SpecialTokenMap,
+    ) -> Result<(), TokenizerError> {
+        let mut special_tokens = vec![special_token_map.unk_token.as_str()];
+        for token in [
+            &special_token_map.pad_token,
+            &special_token_map.bos_token,
+            &special_token_map.sep_token,
+            &special_token_map.cls_token,
+            &special_token_map.eos_token,
+            &special_token_map.mask_token,
+        ]
+        .iter()
+        .filter_map(|token| token.as_deref())
+        {
+            special_tokens.push(token);
+        }
+        if let Some(additional_special_tokens) = &special_token_map.additional_special_tokens {
+            special_tokens.extend(additional_special_tokens.iter().map(String::as_str));
+        }
+        let mut special_values = HashMap::with_capacity(special_tokens.len());
+        for token in special_tokens {
+            match self.find_id(token) {
+                Some(id) => {
+                    special_values.insert(token.to_owned(), id);
+                }
+                None => {
+                    return Err(TokenizerError::TokenNotFound(format!(
+                        "The special value {token} could not be found in the vocabulary"
+                    )));
+                }
+            }
+        }
+        self.unk_id = special_values[special_token_map.unk_token.as_str()];
+        self.special_indices = special_values
+            .iter()
+            .map(|(token, id)| (*id, token.clone()))
+            .collect();
+        self.special_values = special_values;
+        self.special_token_map = special_token_map;
+        Ok(())
+    }
+
+    fn materialize_values(&self) -> HashMap<String, i64> {
+        (0..self.num_tokens)
+            .filter_map(|position| self.entry(position).ok())
+            .map(|(token, id)| (token.to_owned(), id))
+            .collect()
+    }
+
+    fn materialize_indices(&self) -> HashMap<i64, String> {
+        (0..self.num_tokens)
+            .rev()
+            .filter_map(|position| self.entry(position).ok())
+            .map(|(token, id)| (id, token.to_owned()))
+            .collect()
+    }
+}
+
+impl Vocab for MmapVocab {
+    fn get_unknown_value(&self) -> &str {
+        &self.special_token_map.unk_token
+    }
+
+    fn values(&self) -> &HashMap<String, i64> {
+        self.values.get_or_init(|| self.materialize_values())
+    }
+
+    fn indices(&self) -> &HashMap<i64, String> {
+        self.indices.get_or_init(|| self.materialize_indices())
+    }
+
+    fn special_values(&self) -> &HashMap<String, i64> {
+        &self.special_values
+    }
+
+    fn special_indices(&self) -> &HashMap<i64, String> {
+        &self.special_indices
+    }
+
+    fn values_mut(&mut self) -> &mut HashMap<String, i64> {
+        self.values();
+        self.values.get_mut().unwrap()
+    }
+
+    fn indices_mut(&mut self) -> &mut HashMap<i64, String> {
+        self.indices();
+        self.indices.get_mut().unwrap()
+    }
+
+    fn special_values_mut(&mut self) -> &mut HashMap<String, i64> {
+        &mut self.special_values
+    }
+
+    fn special_indices_mut(&mut self) -> &mut HashMap<i64, String> {
+        &mut self.special_indices
+    }
+
+    /// Memory-maps a compiled vocabulary file (generated with `MmapVocab::convert`).
+    ///
+    /// The file must not be modified while it is mapped.
+    fn from_file<P: AsRef<Path>>(path: P) -> Result<MmapVocab, TokenizerError> {
+        let f = File::open(&path).map_err(|e| {
+            TokenizerError::FileNotFound(format!(
+                "{} vocabulary file not found :{}",
+                path.as_ref().display(),
+                e
+            ))
+        })?;
+        // Safety: the mapping is read-only, compiled vocabulary files are not expected to be
+        // modified once generated.
+        let mmap = unsafe { Mmap::map(&f) }.map_err(|e| TokenizerError::IOError(e.to_string()))?;
+        Self::from_data(VocabData::Mapped(mmap))
+    }
+
+    fn from_file_with_special_token_mapping<P: AsRef<Path>, S: AsRef<Path>>(
+        path: P,
+        special_token_mapping_path: S,
+    ) -> Result<Self, TokenizerError> {
+        let mut vocab = Self::from_file(path)?;
+        let special_token_map = read_special_token_mapping_file(special_token_mapping_path)?;
+        vocab.set_special_token_map(special_token_map)?;
+        Ok(vocab)
+    }
+
+    fn from_values_and_special_token_map(
+        values: HashMap<String, i64>,
+        special_token_map: SpecialTokenMap,
+    ) -> Result<Self, TokenizerError>
+    where
+        Self: Sized,
+    {
+        let mut special_values = HashMap::new();
+        special_token_map.register_special_values(&values, &mut special_values)?;
+        let bytes = compile(&values, &special_values, &special_token_map)?;
+        Self::from_data(VocabData::Owned(bytes))
+    }
+
+    fn token_to_id(&self, token: &str) -> i64 {
+        if let Some(values) = self.values.get() {
+            return self._token_to_id(
+                token,
+                values,
+                &self.special_values,
+                self.get_unknown_value(),
+            );
+        }
+        match self.special_values.get(token) {
+            Some(index) => *index,
+            None => match self.find_id(token) {
+                Some(index) => index,
+                None => self.unk_id,
+            },
+        }
+    }
+
+    fn id_to_token(&self, id: &i64) -> String {
+        if let Some(indices) = self.indices.get() {
+            return self._id_to_token(id, indices, &self.special_indices, self.get_unknown_value());
+        }
+        match self.special_indices.get(id) {
+            Some(token) => token.clone(),
+            None => match self.find_token(*id) {
+                Some(token) => token.to_owned(),
+                None => self.get_unknown_value().to_owned(),
+            },
+        }
+    }
+
+    /// Add arbitrary tokens to the vocabulary. Ids are assigned after the largest id of the
+    /// vocabulary, which may have gaps in its ids.
+    ///
+    /// # Parameters
+    /// - tokens (`&[&str]`): list of tokens to add to the vocabulary
+    fn add_tokens(&mut self, tokens: &[&str]) {
+        let mut current_index = self.values().values().max().map_or(0, |id| id + 1);
+        for token in tokens {
+            if self.values().contains_key(*token) {
+                continue;
+            }
+            self.values_mut().insert(token.to_string(), current_index);
+            self.indices_mut().insert(current_index, token.to_string());
+            self.special_values_mut()
+                .insert(token.to_string(), current_index);
+            self.special_indices_mut()
+                .insert(current_index, token.to_string());
+            current_index += 1;
+        }
+    }
+}
+
+impl SpecialTokenValues for MmapVocab {
+    fn pad_value(&self) -> Option<&str> {
+        self.special_token_map.pad_token.as_deref()
+    }
+
+    fn bos_value(&self) -> Option<&str> {
+        self.special_token_map.bos_token.as_deref()
+    }
+
+    fn sep_value(&self) -> Option<&str> {
+        self.special_token_map.sep_token.as_deref()
+    }
+
+    fn cls_value(&self) -> Option<&str> {
+        self.special_token_map.cls_token.as_deref()
+    }
+
+    fn eos_value(&self) -> Option<&str> {
+        self.special_token_map.eos_token.as_deref()
+    }
+
+    fn mask_value(&self) -> Option<&str> {
+        self.special_token_map.mask_token.as_deref()
+    }
+}
+
+//==============================
+// Unit tests
+//==============================
+#[cfg(test)]
+mod tests {
+    extern crate anyhow;
+    use super::*;
+    use crate::tokenizer::{
+        M2M100Tokenizer, NLLBTokenizer, Tokenizer, TruncationStrategy, XLMRobertaTokenizer,
+    };
+    use crate::vocab::{Gpt2Vocab, XLMRobertaVocab};
+    use crate::vocab::{SentencePieceBpeModel, SentencePieceModel};
+    use std::io::Write;
+
+    fn generate_special_token_map() -> SpecialTokenMap {
+        SpecialTokenMap {
+            unk_token: "<unk>".to_string(),
+            pad_token: Some("<pad>".to_string()),
+            bos_token: None,
+            sep_token: None,
+            cls_token: None,
+            eos_token: Some("</s>".to_string()),
+            mask_token: None,
+            additional_special_tokens: None,
+        }
+    }
+
+    fn generate_values() -> HashMap<String, i64> {
+        [
+            ("<pad>".to_owned(), 0),
+            ("<unk>".to_owned(), 1),
+            ("</s>".to_owned(), 2),
+            ("hello".to_owned(), 3),
+            ("wörld".to_owned(), 4),
+            ("▁中华".to_owned(), 5),
+            ("!".to_owned(), 7),
+        ]
+        .iter()
+        .cloned()
+        .collect()
+    }
+
+    #[test]
+    fn test_create_vocab() -> anyhow::Result<()> {
+        //        Given
+        let values = generate_values();
+        let special_token_map = generate_special_token_map();
+
+        //        When
+        let vocab =
+            MmapVocab::from_values_and_special_token_map(values.clone(), special_token_map)?;
+
+        //        Then
+        assert_eq!(vocab.len(), 7);
+        assert_eq!(vocab.get_unknown_value(), "<unk>");
+        assert_eq!(vocab.special_values.len(), 3);
+        assert_eq!(vocab.special_values.get("</s>"), Some(&2));
+        assert_eq!(vocab.special_indices.get(&0), Some(&"<pad>".to_string()));
+        assert!(vocab.values.get().is_none());
+        assert_eq!(*vocab.values(), values);
+        Ok(())
+    }
+
+    #[test]
+    fn test_encode_decode_tokens() -> anyhow::Result<()> {
+        //        Given
+        let vocab = MmapVocab::from_values_and_special_token_map(
+            generate_values(),
+            generate_special_token_map(),
+        )?;
+
+        //        When & Then
+        assert_eq!(vocab.token_to_id("hello"), 3);
+        assert_eq!(vocab.token_to_id("wörld"), 4);
+        assert_eq!(vocab.token_to_id("▁中华"), 5);
+        assert_eq!(vocab.token_to_id("</s>"), 2);
+        assert_eq!(vocab.token_to_id("oov_value"), 1);
+        assert_eq!(vocab.id_to_token(&3), "hello");
+        assert_eq!(vocab.id_to_token(&7), "!");
+        assert_eq!(vocab.id_to_token(&6), "<unk>");
+        assert_eq!(vocab.id_to_token(&0), "<pad>");
+        assert!(vocab.values.get().is_none());
+        assert!(vocab.indices.get().is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn test_convert_and_map_file() -> anyhow::Result<()> {
+        //        Given
+        let mut vocab_file = tempfile::NamedTempFile::new()?;
+        write!(
+            vocab_file,
+            "{{\"hello\": 1,\n \"world\": 0,\n \"<|endoftext|>\": 2,\n \"!\": 3\n}}"
+        )?;
+        let path = vocab_file.into_temp_path();
+        let mut gpt2_vocab = Gpt2Vocab::from_file(&path)?;
+        gpt2_vocab.add_tokens(&["<added>"]);
+        let compiled_path = tempfile::NamedTempFile::new()?.into_temp_path();
+
+        //        When
+        MmapVocab::convert(&gpt2_vocab, &gpt2_vocab.special_token_map, &compiled_path)?;
+        let vocab = MmapVocab::from_file(&compiled_path)?;
+
+        //        Then
+        assert_eq!(vocab.get_unknown_value(), "<|endoftext|>");
+        assert_eq!(vocab.special_values, gpt2_vocab.special_values);
+        assert_eq!(vocab.special_indices, gpt2_vocab.special_indices);
+        for (token, id) in gpt2_vocab.values.iter() {
+            assert_eq!(vocab.token_to_id(token), *id);
+            assert_eq!(vocab.id_to_token(id), *token);
+        }
+        assert_eq!(*vocab.values(), gpt2_vocab.values);
+        drop(path);
+        drop(compiled_path);
+        Ok(())
+    }
+
+    #[test]
+    fn test_add_tokens() -> anyhow::Result<()> {
+        //        Given
+        let mut vocab = MmapVocab::from_values_and_special_token_map(
+            generate_values(),
+            generate_special_token_map(),
+        )?;
+
+        //        When
+        vocab.add_tokens(&["<new>", "hello"]);
+
+        //        Then
+        assert_eq!(vocab.token_to_id("<new>"), 8);
+        assert_eq!(vocab.special_values.get("<new>"), Some(&8));
+        assert_eq!(vocab.id_to_token(&7), "!");
+        assert_eq!(vocab.token_to_id("hello"), 3);
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_file() -> anyhow::Result<()> {
+        //        Given
+        let mut vocab_file = tempfile::NamedTempFile::new()?;
+        write!(vocab_file, "not a compiled vocabulary")?;
+        let path = vocab_file.into_temp_path();
+
+        //        When
+        let vocab = MmapVocab::from_file(&path);
+
+        //        Then
+        assert!(matches!(
+            vocab,
+            Err(TokenizerError::VocabularyParsingError(_))
+        ));
+        drop(path);
+        Ok(())
+    }
+
+    #[test]
+    fn test_missing_unknown_token() -> anyhow::Result<()> {
+        //        Given
+        let mut values = generate_values();
+        values.remove("<unk>");
+        let data = compile(&values, &HashMap::new(), &generate_special_token_map())?;
+
+        //        When
+        let vocab = MmapVocab::from_data(VocabData::Owned(data));
+
+        //        Then
+        assert!(matches!(
+            vocab,
+            Err(TokenizerError::VocabularyParsingError(_))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_full_hash_table() -> anyhow::Result<()> {
+        //        Given
+        let mut values = generate_values();
+        values.insert("▁".to_owned(), 8);
+        let special_token_map = generate_special_token_map();
+        let mut special_values = HashMap::new();
+        special_token_map.register_special_values(&values, &mut special_values)?;
+        let bytes = compile(&values, &special_values, &special_token_map)?;
+        let buckets_start = HEADER_SIZE + values.len() * ENTRY_SIZE;
+        let num_buckets = read_u32(&bytes, 16) as usize;
+        let mut corrupted = bytes[..buckets_start].to_vec();
+        corrupted[16..20].copy_from_slice(&(values.len() as u32).to_le_bytes());
+        for position in 0..values.len() {
+            corrupted.extend_from_slice(&(position as u32 + 1).to_le_bytes());
+        }
+        corrupted.extend_from_slice(&bytes[buckets_start + num_buckets * BUCKET_SIZE..]);
+
+        //        When
+        let vocab = MmapVocab::from_data(VocabData::Owned(corrupted));
+
+        //        Then
+        assert_eq!(num_buckets, 16);
+        assert!(matches!(
+            vocab,
+            Err(TokenizerError::VocabularyParsingError(_))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_tokenizer_with_mmap_vocab() -> anyhow::Result<()> {
+        //        Given
+        let model_path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/fixtures/bigbird/spiece.model"
+        );
+        let xlm_roberta_vocab = XLMRobertaVocab::from_file(model_path)?;
+        let vocab = MmapVocab::from_values_and_special_token_map(
+            xlm_roberta_vocab.values.clone(),
+            xlm_roberta_vocab.special_token_map.clone(),
+        )?;
+        let reference_tokenizer = XLMRobertaTokenizer::from_existing_vocab_and_model(
+            xlm_roberta_vocab,
+            SentencePieceModel::from_file(model_path)?,
+            false,
+        )?;
+        let tokenizer = XLMRobertaTokenizer::from_existing_vocab_and_model(
+            vocab,
+            SentencePieceModel::from_file(model_path)?,
+            false,
+        )?;
+        let text = "Hello, memory-mapped world!";
+
+        //        When
+        let encoding = tokenizer.encode(text, None, 128, &TruncationStrategy::LongestFirst, 0);
+        let reference_encoding =
+            reference_tokenizer.encode(text, None, 128, &TruncationStrategy::LongestFirst, 0);
+
+        //        Then
+        assert_eq!(encoding, reference_encoding);
+        assert_eq!(encoding.token_ids[0], tokenizer.vocab().token_to_id("<s>"));
+        assert_eq!(
+            tokenizer.decode(&encoding.token_ids, true, true),
+            reference_tokenizer.decode(&reference_encoding.token_ids, true, true)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_tokenizer_missing_special_tokens() -> anyhow::Result<()> {
+        //        Given
+        let model_path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/fixtures/bigbird/spiece.model"
+        );
+        let vocab = || {
+            MmapVocab::from_values_and_special_token_map(
+                generate_values(),
+                generate_special_token_map(),
+            )
+        };
+        let vocab_without_eos = MmapVocab::from_values_and_special_token_map(
+            generate_values(),
+            SpecialTokenMap {
+                eos_token: None,
+                ..generate_special_token_map()
+            },
+        )?;
+
+        //        When
+        let xlm_roberta_tokenizer = XLMRobertaTokenizer::from_existing_vocab_and_model(
+            vocab()?,
+            SentencePieceModel::from_file(model_path)?,
+            false,
+        );
+        let nllb_tokenizer = NLLBTokenizer::from_existing_vocab_and_model(
+            vocab()?,
+            SentencePieceBpeModel::from_file(model_path)?,
+        );
+        let m2m100_tokenizer = M2M100Tokenizer::from_existing_vocab_and_model(
+            vocab_without_eos,
+            SentencePieceBpeModel::from_file(model_path)?,
+            false,
+        );
+
+        //        Then
+        assert!(matches!(
+            xlm_roberta_tokenizer,
+            Err(TokenizerError::TokenNotFound(_))
+        ));
+        assert_eq!(
+            nllb_tokenizer?
+                .encode("hello", None, 128, &TruncationStrategy::LongestFirst, 0)
+                .token_ids
+                .iter()
+                .filter(|id| **id == 2)
+                .count(),
+            1
+        );
+        assert!(matches!(
+            m2m100_tokenizer,
+            Err(TokenizerError::TokenNotFound(_))
+        ));
+        Ok(())
+    }
+}
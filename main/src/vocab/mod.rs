@@ -19,15 +19,32 @@
 //!
 //! The following vocabularies have been implemented:
 //! - BERT
+//! - MPNet
 //! - ALBERT
 //! - GPT2
+//! - CLIP
 //! - GPT
+//! - XLM
+//! - PhoBERT (fairseq dictionary)
 //! - Marian
 //! - RoBERTa
+//! - Longformer
+//! - BigBird
 //! - T5
 //! - XLMRoBERTa
 //! - XLNet
 //! - SentencePiece
+//! - ByT5 (bytes) and CANINE (Unicode code points), built without vocabulary file
+//! - Character vocabularies for CTC models (Wav2Vec2)
+//! - ESM (protein residues)
+//!
+//! Any vocabulary can be converted to a compiled binary format and memory-mapped with `MmapVocab`,
+//! avoiding the parsing and copy of large vocabularies when loading many tokenizer instances. The
+//! XLM-RoBERTa, NLLB and M2M100 tokenizers are generic over their vocabulary and accept a `MmapVocab`.
+//!
+//! Vocabularies can be checked for consistency (duplicate ids, id gaps, mismatches between the
+//! token and id maps) with `inspect_vocab`, compared with `diff_vocabs` and `diff_merges`, and
+//! checked against their SentencePiece model with `inspect_sentencepiece_model`.
 //!
 //! All vocabularies implement the `Vocab` trait exposing a standard interface for integration with
 //! the tokenizers.
@@ -35,17 +52,28 @@
 mod albert_vocab;
 pub(crate) mod base_vocab;
 mod bert_vocab;
+mod bigbird_vocab;
 pub(crate) mod bpe_vocab;
+mod byt5_vocab;
+mod canine_vocab;
+mod char_vocab;
+mod clip_vocab;
 mod deberta_v2_vocab;
 mod deberta_vocab;
+mod esm_vocab;
 mod fnet_vocab;
 mod gpt2_vocab;
+mod inspection;
+mod longformer_vocab;
 mod m2m100_vocab;
 mod marian_vocab;
 mod mbart50_vocab;
+mod mmap_vocab;
+mod mpnet_vocab;
 mod nllb_vocab;
 mod openai_gpt_vocab;
 mod pegasus_vocab;
+mod phobert_vocab;
 mod prophetnet_vocab;
 mod reformer_vocab;
 mod roberta_vocab;
@@ -55,23 +83,38 @@ mod sentence_piece_vocab;
 pub(crate) mod sentencepiece_proto;
 mod t5_vocab;
 mod xlm_roberta_vocab;
+mod xlm_vocab;
 mod xlnet_vocab;
 
 pub use albert_vocab::AlbertVocab;
-pub use base_vocab::{BaseVocab, Vocab};
+pub use base_vocab::{BaseVocab, SpecialTokenMap, SpecialTokenValues, Vocab};
 pub use bert_vocab::BertVocab;
+pub use bigbird_vocab::BigBirdVocab;
 pub use bpe_vocab::{BpePairRef, BpePairVocab};
+pub use byt5_vocab::ByT5Vocab;
+pub use canine_vocab::CanineVocab;
+pub use char_vocab::CharVocab;
+pub use clip_vocab::ClipVocab;
 pub use deberta_v2_vocab::DeBERTaV2Vocab;
 pub use deberta_vocab::DeBERTaVocab;
+pub use esm_vocab::EsmVocab;
 pub use fnet_vocab::FNetVocab;
 pub use gpt2_vocab::Gpt2Vocab;
+pub use inspection::{
+    diff_merges, diff_vocabs, inspect_sentencepiece_model, inspect_vocab, list_special_tokens,
+    MergesDiff, SentencePieceReport, SpecialTokenEntry, VocabDiff, VocabMismatch, VocabReport,
+};
+pub use longformer_vocab::LongformerVocab;
 pub use m2m100_vocab::M2M100Vocab;
 pub use marian_vocab::MarianVocab;
 pub use mbart50_vocab::MBart50Vocab;
+pub use mmap_vocab::MmapVocab;
+pub use mpnet_vocab::MPNetVocab;
 pub use nllb_vocab::NLLBVocab;
 pub(crate) use nllb_vocab::EXTENDED_FAIRSEQ_LANGUAGE_CODES;
 pub use openai_gpt_vocab::OpenAiGptVocab;
 pub use pegasus_vocab::PegasusVocab;
+pub use phobert_vocab::PhoBertVocab;
 pub use prophetnet_vocab::ProphetNetVocab;
 pub use reformer_vocab::ReformerVocab;
 pub use roberta_vocab::RobertaVocab;
@@ -80,4 +123,5 @@ pub use sentence_piece_unigram_model::SentencePieceModel;
 pub use sentence_piece_vocab::SentencePieceVocab;
 pub use t5_vocab::T5Vocab;
 pub use xlm_roberta_vocab::XLMRobertaVocab;
+pub use xlm_vocab::XLMVocab;
 pub use xlnet_vocab::XLNetVocab;
@@ -10,6 +10,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::vocab::base_vocab::impl_special_token_values;
 use std::{
     collections::{HashMap, HashSet},
     fs::File,
@@ -161,6 +162,8 @@ impl NLLBVocab {
     }
 }
 
+impl_special_token_values!(NLLBVocab, pad_value => get_pad_value, bos_value => get_bos_value, sep_value => get_sep_value, eos_value => get_eos_value);
+
 impl Vocab for NLLBVocab {
     fn get_unknown_value(&self) -> &str {
         &self.special_token_map.unk_token
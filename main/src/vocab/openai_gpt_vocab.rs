@@ -12,6 +12,7 @@
 // limitations under the License.
 
 use crate::error::TokenizerError;
+use crate::vocab::base_vocab::impl_special_token_values;
 use crate::vocab::base_vocab::{
     read_json_file, read_special_token_mapping_file, swap_key_values, SpecialTokenMap, Vocab,
 };
@@ -43,6 +44,8 @@ pub struct OpenAiGptVocab {
 
 const DEFAULT_UNK_TOKEN: &str = "<unk>";
 
+impl_special_token_values!(OpenAiGptVocab);
+
 impl Vocab for OpenAiGptVocab {
     fn get_unknown_value(&self) -> &str {
         &self.special_token_map.unk_token
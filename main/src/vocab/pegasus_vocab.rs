@@ -11,6 +11,7 @@
 // limitations under the License.
 
 use crate::error::TokenizerError;
+use crate::vocab::base_vocab::impl_special_token_values;
 use crate::vocab::base_vocab::{
     open_protobuf_file, read_special_token_mapping_file, register_as_special_value,
     swap_key_values, SpecialTokenMap,
@@ -88,6 +89,8 @@ impl PegasusVocab {
     }
 }
 
+impl_special_token_values!(PegasusVocab, pad_value => get_pad_value, eos_value => get_eos_value, mask_value => get_mask_value);
+
 impl Vocab for PegasusVocab {
     fn get_unknown_value(&self) -> &str {
         &self.special_token_map.unk_token
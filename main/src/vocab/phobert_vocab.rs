@@ -0,0 +1,346 @@
+// Copyright 2020 VinAI Research and The HuggingFace Inc. team.
+// Copyright 2019-2020 Guillaume Becquin
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//     http://www.apache.org/licenses/LICENSE-2.0
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::error::TokenizerError;
+use crate::vocab::base_vocab::impl_special_token_values;
+use crate::vocab::base_vocab::{
+    read_special_token_mapping_file, swap_key_values, SpecialTokenMap, Vocab,
+};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+/// # PhoBERT Vocab
+/// Vocabulary for PhoBERT tokenizer. Contains the following special values:
+/// - PAD token
+/// - BOS token
+/// - EOS token
+/// - SEP token
+/// - MASK token
+/// - CLS token
+///
+/// Expects a fairseq dictionary (`dict.txt`, one `token count` pair per line) when created from
+/// file. Following fairseq, the `<s>`, `<pad>`, `</s>` and `<unk>` tokens are assigned the ids 0 to 3,
+/// the dictionary entries follow in order and the `<mask>` token is appended at the end.
+#[derive(Debug, Clone)]
+pub struct PhoBertVocab {
+    /// A mapping of tokens as string to indices (i.e. the encoder base)
+    pub values: HashMap<String, i64>,
+
+    /// A mapping of token IDs to strings (i.e. the decoder base)
+    pub indices: HashMap<i64, String>,
+
+    /// Special tokens used by the vocabulary
+    pub special_token_map: SpecialTokenMap,
+
+    /// A mapping of special value tokens as strings to IDs (i.e. the encoder base for special
+    /// values), special values typically include things like BOS/EOS markers, class markers, mask
+    /// markers and padding markers
+    pub special_values: HashMap<String, i64>,
+
+    /// A mapping of special value tokens as IDs to strings (i.e. the decoder base for special values)
+    pub special_indices: HashMap<i64, String>,
+}
+
+const DEFAULT_UNK_TOKEN: &str = "<unk>";
+const DEFAULT_PAD_TOKEN: &str = "<pad>";
+const DEFAULT_BOS_TOKEN: &str = "<s>";
+const DEFAULT_SEP_TOKEN: &str = "</s>";
+const DEFAULT_CLS_TOKEN: &str = "<s>";
+const DEFAULT_EOS_TOKEN: &str = "</s>";
+const DEFAULT_MASK_TOKEN: &str = "<mask>";
+
+/// Read a fairseq dictionary file (`token count` per line), placing the `prefix_tokens` first and
+/// the `suffix_tokens` last if they are not already part of the dictionary.
+fn read_fairseq_dict_file<P: AsRef<Path>>(
+    path: P,
+    prefix_tokens: &[&str],
+    suffix_tokens: &[&str],
+) -> Result<HashMap<String, i64>, TokenizerError> {
+    let f = File::open(&path).map_err(|e| {
+        TokenizerError::FileNotFound(format!(
+            "{} vocabulary file not found :{}",
+            path.as_ref().display(),
+            e
+        ))
+    })?;
+    let br = BufReader::new(f);
+    let mut values = HashMap::new();
+    for token in prefix_tokens {
+        let index = values.len() as i64;
+        values.entry(token.to_string()).or_insert(index);
+    }
+    for line in br.lines() {
+        let line = line.map_err(|e| TokenizerError::VocabularyParsingError(e.to_string()))?;
+        let line = line.trim_end();
+        if line.is_empty() {
+            continue;
+        }
+        let token = match line.rsplit_once(' ') {
+            Some((token, count)) if count.parse::<i64>().is_ok() => token,
+            _ => {
+                return Err(TokenizerError::VocabularyParsingError(format!(
+                    "Invalid fairseq dictionary line: {line}"
+                )));
+            }
+        };
+        let index = values.len() as i64;
+        values.entry(token.to_string()).or_insert(index);
+    }
+    for token in suffix_tokens {
+        let index = values.len() as i64;
+        values.entry(token.to_string()).or_insert(index);
+    }
+    Ok(values)
+}
+
+impl PhoBertVocab {
+    pub fn get_pad_value(&self) -> &str {
+        self.special_token_map
+            .pad_token
+            .as_deref()
+            .unwrap_or(DEFAULT_PAD_TOKEN)
+    }
+
+    pub fn get_bos_value(&self) -> &str {
+        self.special_token_map
+            .bos_token
+            .as_deref()
+            .unwrap_or(DEFAULT_BOS_TOKEN)
+    }
+
+    pub fn get_sep_value(&self) -> &str {
+        self.special_token_map
+            .sep_token
+            .as_deref()
+            .unwrap_or(DEFAULT_SEP_TOKEN)
+    }
+
+    pub fn get_cls_value(&self) -> &str {
+        self.special_token_map
+            .cls_token
+            .as_deref()
+            .unwrap_or(DEFAULT_CLS_TOKEN)
+    }
+
+    pub fn get_eos_value(&self) -> &str {
+        self.special_token_map
+            .eos_token
+            .as_deref()
+            .unwrap_or(DEFAULT_EOS_TOKEN)
+    }
+
+    pub fn get_mask_value(&self) -> &str {
+        self.special_token_map
+            .mask_token
+            .as_deref()
+            .unwrap_or(DEFAULT_MASK_TOKEN)
+    }
+}
+
+impl_special_token_values!(PhoBertVocab, pad_value => get_pad_value, bos_value => get_bos_value, sep_value => get_sep_value, cls_value => get_cls_value, eos_value => get_eos_value, mask_value => get_mask_value);
+
+impl Vocab for PhoBertVocab {
+    fn get_unknown_value(&self) -> &str {
+        &self.special_token_map.unk_token
+    }
+
+    fn values(&self) -> &HashMap<String, i64> {
+        &self.values
+    }
+
+    fn indices(&self) -> &HashMap<i64, String> {
+        &self.indices
+    }
+
+    fn special_values(&self) -> &HashMap<String, i64> {
+        &self.special_values
+    }
+
+    fn special_indices(&self) -> &HashMap<i64, String> {
+        &self.special_indices
+    }
+
+    fn values_mut(&mut self) -> &mut HashMap<String, i64> {
+        &mut self.values
+    }
+
+    fn indices_mut(&mut self) -> &mut HashMap<i64, String> {
+        &mut self.indices
+    }
+
+    fn special_values_mut(&mut self) -> &mut HashMap<String, i64> {
+        &mut self.special_values
+    }
+
+    fn special_indices_mut(&mut self) -> &mut HashMap<i64, String> {
+        &mut self.special_indices
+    }
+
+    /// Read a fairseq-style dict.txt file
+    fn from_file<P: AsRef<Path>>(path: P) -> Result<PhoBertVocab, TokenizerError> {
+        let values = read_fairseq_dict_file(
+            path,
+            &[
+                DEFAULT_BOS_TOKEN,
+                DEFAULT_PAD_TOKEN,
+                DEFAULT_EOS_TOKEN,
+                DEFAULT_UNK_TOKEN,
+            ],
+            &[DEFAULT_MASK_TOKEN],
+        )?;
+
+        let special_token_map = SpecialTokenMap {
+            unk_token: DEFAULT_UNK_TOKEN.to_string(),
+            pad_token: Some(DEFAULT_PAD_TOKEN.to_string()),
+            bos_token: Some(DEFAULT_BOS_TOKEN.to_string()),
+            sep_token: Some(DEFAULT_SEP_TOKEN.to_string()),
+            cls_token: Some(DEFAULT_CLS_TOKEN.to_string()),
+            eos_token: Some(DEFAULT_EOS_TOKEN.to_string()),
+            mask_token: Some(DEFAULT_MASK_TOKEN.to_string()),
+            additional_special_tokens: None,
+        };
+        Self::from_values_and_special_token_map(values, special_token_map)
+    }
+
+    fn from_file_with_special_token_mapping<P: AsRef<Path>, S: AsRef<Path>>(
+        path: P,
+        special_token_mapping_path: S,
+    ) -> Result<Self, TokenizerError> {
+        let special_token_map = read_special_token_mapping_file(special_token_mapping_path)?;
+        let values = read_fairseq_dict_file(
+            path,
+            &[
+                special_token_map
+                    .bos_token
+                    .as_deref()
+                    .unwrap_or(DEFAULT_BOS_TOKEN),
+                special_token_map
+                    .pad_token
+                    .as_deref()
+                    .unwrap_or(DEFAULT_PAD_TOKEN),
+                special_token_map
+                    .eos_token
+                    .as_deref()
+                    .unwrap_or(DEFAULT_EOS_TOKEN),
+                special_token_map.unk_token.as_str(),
+            ],
+            &[special_token_map
+                .mask_token
+                .as_deref()
+                .unwrap_or(DEFAULT_MASK_TOKEN)],
+        )?;
+        Self::from_values_and_special_token_map(values, special_token_map)
+    }
+
+    fn from_values_and_special_token_map(
+        values: HashMap<String, i64>,
+        special_token_map: SpecialTokenMap,
+    ) -> Result<Self, TokenizerError>
+    where
+        Self: Sized,
+    {
+        let mut special_values = HashMap::new();
+        special_token_map.register_special_values(&values, &mut special_values)?;
+
+        let indices = swap_key_values(&values);
+        let special_indices = swap_key_values(&special_values);
+        Ok(Self {
+            values,
+            indices,
+            special_token_map,
+            special_values,
+            special_indices,
+        })
+    }
+
+    fn token_to_id(&self, token: &str) -> i64 {
+        self._token_to_id(
+            token,
+            &self.values,
+            &self.special_values,
+            self.get_unknown_value(),
+        )
+    }
+
+    fn id_to_token(&self, id: &i64) -> String {
+        self._id_to_token(
+            id,
+            &self.indices,
+            &self.special_indices,
+            self.get_unknown_value(),
+        )
+    }
+}
+
+//==============================
+// Unit tests
+//==============================
+#[cfg(test)]
+mod tests {
+    extern crate anyhow;
+
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn test_create_object_from_file() -> anyhow::Result<()> {
+        //        Given
+        let mut vocab_file = tempfile::NamedTempFile::new()?;
+        write!(vocab_file, "Tôi 1000\nlà 900\nsinh_viên 800\nsinh@@ 700\n")?;
+        let path = vocab_file.into_temp_path();
+        let target_values: HashMap<String, i64> = [
+            ("<s>".to_owned(), 0),
+            ("<pad>".to_owned(), 1),
+            ("</s>".to_owned(), 2),
+            ("<unk>".to_owned(), 3),
+            ("Tôi".to_owned(), 4),
+            ("là".to_owned(), 5),
+            ("sinh_viên".to_owned(), 6),
+            ("sinh@@".to_owned(), 7),
+            ("<mask>".to_owned(), 8),
+        ]
+        .iter()
+        .cloned()
+        .collect();
+
+        //        When
+        let phobert_vocab = PhoBertVocab::from_file(&path)?;
+
+        //        Then
+        assert_eq!(phobert_vocab.get_unknown_value(), "<unk>");
+        assert_eq!(phobert_vocab.values, target_values);
+        assert_eq!(phobert_vocab.special_values.len(), 5);
+        assert_eq!(phobert_vocab.token_to_id("<mask>"), 8);
+        assert_eq!(phobert_vocab.token_to_id("oov_value"), 3);
+        assert_eq!(phobert_vocab.id_to_token(&(6_i64)), "sinh_viên");
+        drop(path);
+        Ok(())
+    }
+
+    #[test]
+    fn test_create_object_from_invalid_file() -> anyhow::Result<()> {
+        //        Given
+        let mut vocab_file = tempfile::NamedTempFile::new()?;
+        write!(vocab_file, "Tôi 1000\nlà\n")?;
+        let path = vocab_file.into_temp_path();
+
+        //        When
+        let phobert_vocab = PhoBertVocab::from_file(&path);
+
+        //        Then
+        assert!(phobert_vocab.is_err());
+        drop(path);
+        Ok(())
+    }
+}
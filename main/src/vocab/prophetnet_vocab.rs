@@ -11,6 +11,7 @@
 // limitations under the License.
 
 use crate::error::TokenizerError;
+use crate::vocab::base_vocab::impl_special_token_values;
 use crate::vocab::base_vocab::{
     read_flat_file, read_special_token_mapping_file, swap_key_values, SpecialTokenMap, Vocab,
 };
@@ -83,6 +84,8 @@ impl ProphetNetVocab {
     }
 }
 
+impl_special_token_values!(ProphetNetVocab, pad_value => get_pad_value, sep_value => get_sep_value, cls_value => get_cls_value, mask_value => get_mask_value);
+
 impl Vocab for ProphetNetVocab {
     fn get_unknown_value(&self) -> &str {
         &self.special_token_map.unk_token
@@ -12,6 +12,7 @@
 // limitations under the License.
 
 use crate::error::TokenizerError;
+use crate::vocab::base_vocab::impl_special_token_values;
 use crate::vocab::base_vocab::{
     read_protobuf_file, read_special_token_mapping_file, swap_key_values, SpecialTokenMap,
 };
@@ -56,6 +57,8 @@ impl ReformerVocab {
     }
 }
 
+impl_special_token_values!(ReformerVocab, eos_value => get_eos_value);
+
 impl Vocab for ReformerVocab {
     fn get_unknown_value(&self) -> &str {
         &self.special_token_map.unk_token
@@ -11,6 +11,7 @@
 // limitations under the License.
 
 use crate::error::TokenizerError;
+use crate::vocab::base_vocab::impl_special_token_values;
 use crate::vocab::base_vocab::{
     read_protobuf_file, read_special_token_mapping_file, swap_key_values, SpecialTokenMap,
 };
@@ -44,6 +45,8 @@ pub struct SentencePieceVocab {
 
 const DEFAULT_UNK_TOKEN: &str = "<unk>";
 
+impl_special_token_values!(SentencePieceVocab);
+
 impl Vocab for SentencePieceVocab {
     fn get_unknown_value(&self) -> &str {
         &self.special_token_map.unk_token
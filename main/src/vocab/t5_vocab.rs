@@ -11,6 +11,7 @@
 // limitations under the License.
 
 use crate::error::TokenizerError;
+use crate::vocab::base_vocab::impl_special_token_values;
 use crate::vocab::base_vocab::{
     read_protobuf_file, read_special_token_mapping_file, swap_key_values, SpecialTokenMap,
 };
@@ -64,6 +65,8 @@ impl T5Vocab {
     }
 }
 
+impl_special_token_values!(T5Vocab, pad_value => get_pad_value, eos_value => get_eos_value);
+
 impl Vocab for T5Vocab {
     fn get_unknown_value(&self) -> &str {
         &self.special_token_map.unk_token
@@ -12,6 +12,7 @@
 // limitations under the License.
 
 use crate::error::TokenizerError;
+use crate::vocab::base_vocab::impl_special_token_values;
 use crate::vocab::base_vocab::{
     open_protobuf_file, read_special_token_mapping_file, register_as_special_value,
     swap_key_values, SpecialTokenMap,
@@ -103,6 +104,8 @@ impl XLMRobertaVocab {
     }
 }
 
+impl_special_token_values!(XLMRobertaVocab, pad_value => get_pad_value, bos_value => get_bos_value, sep_value => get_sep_value, cls_value => get_cls_value, eos_value => get_eos_value, mask_value => get_mask_value);
+
 impl Vocab for XLMRobertaVocab {
     fn get_unknown_value(&self) -> &str {
         &self.special_token_map.unk_token
@@ -0,0 +1,137 @@
+use rust_tokenizers::tokenizer::{BigBirdTokenizer, Tokenizer, TruncationStrategy};
+use rust_tokenizers::{Offset, TokenizedInput};
+
+#[test]
+fn test_bigbird_tokenization() -> anyhow::Result<()> {
+    let bigbird_tokenizer =
+        BigBirdTokenizer::from_file("tests/fixtures/bigbird/spiece.model", false)?;
+
+    let original_strings = [
+        "The quick brown fox jumps over the lazy dog.",
+        "hello [MASK] world!",
+        "tokenized dogs",
+    ];
+
+    let expected_results = [
+        TokenizedInput {
+            token_ids: vec![4, 22, 8, 9, 10, 11, 43, 14, 7, 15, 16, 17, 5],
+            segment_ids: vec![0; 13],
+            special_tokens_mask: vec![1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1],
+            overflowing_tokens: vec![],
+            num_truncated_tokens: 0,
+            token_offsets: vec![
+                None,
+                Some(Offset { begin: 0, end: 3 }),
+                Some(Offset { begin: 3, end: 9 }),
+                Some(Offset { begin: 9, end: 15 }),
+                Some(Offset { begin: 15, end: 19 }),
+                Some(Offset { begin: 19, end: 24 }),
+                Some(Offset { begin: 24, end: 25 }),
+                Some(Offset { begin: 25, end: 30 }),
+                Some(Offset { begin: 30, end: 34 }),
+                Some(Offset { begin: 34, end: 39 }),
+                Some(Offset { begin: 39, end: 43 }),
+                Some(Offset { begin: 43, end: 44 }),
+                None,
+            ],
+            reference_offsets: vec![],
+            mask: vec![],
+        },
+        TokenizedInput {
+            token_ids: vec![4, 20, 6, 21, 18, 5],
+            segment_ids: vec![0; 6],
+            special_tokens_mask: vec![1, 0, 0, 0, 0, 1],
+            overflowing_tokens: vec![],
+            num_truncated_tokens: 0,
+            token_offsets: vec![
+                None,
+                Some(Offset { begin: 0, end: 5 }),
+                // the mask token absorbs the preceding space
+                Some(Offset { begin: 5, end: 12 }),
+                Some(Offset { begin: 12, end: 18 }),
+                Some(Offset { begin: 18, end: 19 }),
+                None,
+            ],
+            reference_offsets: vec![],
+            mask: vec![],
+        },
+        TokenizedInput {
+            token_ids: vec![4, 23, 24, 16, 43, 5],
+            segment_ids: vec![0; 6],
+            special_tokens_mask: vec![1, 0, 0, 0, 0, 1],
+            overflowing_tokens: vec![],
+            num_truncated_tokens: 0,
+            token_offsets: vec![
+                None,
+                Some(Offset { begin: 0, end: 5 }),
+                Some(Offset { begin: 5, end: 9 }),
+                Some(Offset { begin: 9, end: 13 }),
+                Some(Offset { begin: 13, end: 14 }),
+                None,
+            ],
+            reference_offsets: vec![],
+            mask: vec![],
+        },
+    ]
+    .to_vec();
+
+    let output =
+        bigbird_tokenizer.encode_list(&original_strings, 128, &TruncationStrategy::LongestFirst, 0);
+
+    for (predicted, expected) in output.iter().zip(expected_results.iter()) {
+        assert_eq!(predicted.token_ids, expected.token_ids);
+        assert_eq!(predicted.segment_ids, expected.segment_ids);
+        assert_eq!(predicted.special_tokens_mask, expected.special_tokens_mask);
+        assert_eq!(predicted.token_offsets, expected.token_offsets);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_bigbird_pair_encoding() -> anyhow::Result<()> {
+    let bigbird_tokenizer =
+        BigBirdTokenizer::from_file("tests/fixtures/bigbird/spiece.model", false)?;
+
+    let output = bigbird_tokenizer.encode_pair_list(
+        &[("hello world!", "the dog")],
+        128,
+        &TruncationStrategy::LongestFirst,
+        0,
+    );
+
+    assert_eq!(output[0].token_ids, vec![4, 20, 21, 18, 5, 7, 16, 5]);
+    assert_eq!(output[0].segment_ids, vec![0, 0, 0, 0, 0, 1, 1, 1]);
+    assert_eq!(output[0].special_tokens_mask, vec![1, 0, 0, 0, 1, 0, 0, 1]);
+    assert_eq!(
+        output[0].token_offsets,
+        vec![
+            None,
+            Some(Offset { begin: 0, end: 5 }),
+            Some(Offset { begin: 5, end: 11 }),
+            Some(Offset { begin: 11, end: 12 }),
+            None,
+            Some(Offset { begin: 0, end: 3 }),
+            Some(Offset { begin: 3, end: 7 }),
+            None,
+        ]
+    );
+    assert_eq!(
+        bigbird_tokenizer.decode(&[4, 20, 6, 21, 18, 5], false, false),
+        "[CLS] hello[MASK] world![SEP]"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_bigbird_mask_token_lstrip() -> anyhow::Result<()> {
+    let bigbird_tokenizer =
+        BigBirdTokenizer::from_file("tests/fixtures/bigbird/spiece.model", false)?;
+
+    let tokens = bigbird_tokenizer.tokenize_with_offsets("hello [MASK]!");
+
+    // the mask token absorbs the space preceding it
+    assert_eq!(tokens.tokens[1], "[MASK]");
+    assert_eq!(tokens.offsets[1], Some(Offset { begin: 5, end: 12 }));
+    assert_eq!(tokens.reference_offsets[1], vec![5, 6, 7, 8, 9, 10, 11]);
+    Ok(())
+}
@@ -0,0 +1,159 @@
+use rust_tokenizers::tokenizer::{LongformerTokenizer, Tokenizer, TruncationStrategy};
+use rust_tokenizers::{Offset, TokenizedInput};
+
+#[test]
+fn test_longformer_tokenization() -> anyhow::Result<()> {
+    let longformer_tokenizer = LongformerTokenizer::from_file(
+        "tests/fixtures/longformer/vocab.json",
+        "tests/fixtures/longformer/merges.txt",
+        false,
+        false,
+    )?;
+
+    let original_strings = [
+        "The quick brown fox jumps over the lazy dog.",
+        "Hello <mask> world!",
+        "Hello tokenized?",
+    ];
+
+    let expected_results = [
+        TokenizedInput {
+            token_ids: vec![0, 47, 52, 57, 60, 65, 69, 72, 76, 79, 34, 2],
+            segment_ids: vec![0; 12],
+            special_tokens_mask: vec![1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1],
+            overflowing_tokens: vec![],
+            num_truncated_tokens: 0,
+            token_offsets: vec![
+                None,
+                Some(Offset { begin: 0, end: 3 }),
+                Some(Offset { begin: 3, end: 9 }),
+                Some(Offset { begin: 9, end: 15 }),
+                Some(Offset { begin: 15, end: 19 }),
+                Some(Offset { begin: 19, end: 25 }),
+                Some(Offset { begin: 25, end: 30 }),
+                Some(Offset { begin: 30, end: 34 }),
+                Some(Offset { begin: 34, end: 39 }),
+                Some(Offset { begin: 39, end: 43 }),
+                Some(Offset { begin: 43, end: 44 }),
+                None,
+            ],
+            reference_offsets: vec![],
+            mask: vec![],
+        },
+        TokenizedInput {
+            token_ids: vec![0, 40, 92, 45, 33, 2],
+            segment_ids: vec![0; 6],
+            special_tokens_mask: vec![1, 0, 0, 0, 0, 1],
+            overflowing_tokens: vec![],
+            num_truncated_tokens: 0,
+            token_offsets: vec![
+                None,
+                Some(Offset { begin: 0, end: 5 }),
+                // the mask token absorbs the preceding space
+                Some(Offset { begin: 5, end: 12 }),
+                Some(Offset { begin: 12, end: 18 }),
+                Some(Offset { begin: 18, end: 19 }),
+                None,
+            ],
+            reference_offsets: vec![],
+            mask: vec![],
+        },
+        TokenizedInput {
+            token_ids: vec![0, 40, 88, 91, 36, 2],
+            segment_ids: vec![0; 6],
+            special_tokens_mask: vec![1, 0, 0, 0, 0, 1],
+            overflowing_tokens: vec![],
+            num_truncated_tokens: 0,
+            token_offsets: vec![
+                None,
+                Some(Offset { begin: 0, end: 5 }),
+                Some(Offset { begin: 5, end: 11 }),
+                Some(Offset { begin: 11, end: 15 }),
+                Some(Offset { begin: 15, end: 16 }),
+                None,
+            ],
+            reference_offsets: vec![],
+            mask: vec![],
+        },
+    ]
+    .to_vec();
+
+    let output = longformer_tokenizer.encode_list(
+        &original_strings,
+        128,
+        &TruncationStrategy::LongestFirst,
+        0,
+    );
+
+    for (predicted, expected) in output.iter().zip(expected_results.iter()) {
+        assert_eq!(predicted.token_ids, expected.token_ids);
+        assert_eq!(predicted.segment_ids, expected.segment_ids);
+        assert_eq!(predicted.special_tokens_mask, expected.special_tokens_mask);
+        assert_eq!(predicted.token_offsets, expected.token_offsets);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_longformer_pair_encoding() -> anyhow::Result<()> {
+    let longformer_tokenizer = LongformerTokenizer::from_file(
+        "tests/fixtures/longformer/vocab.json",
+        "tests/fixtures/longformer/merges.txt",
+        false,
+        false,
+    )?;
+
+    let output = longformer_tokenizer.encode_pair_list(
+        &[("Hello world!", "The dog")],
+        128,
+        &TruncationStrategy::LongestFirst,
+        0,
+    );
+
+    assert_eq!(output[0].token_ids, vec![0, 40, 45, 33, 2, 2, 47, 79, 2]);
+    assert_eq!(output[0].segment_ids, vec![0; 9]);
+    assert_eq!(
+        output[0].special_tokens_mask,
+        vec![1, 0, 0, 0, 1, 1, 0, 0, 1]
+    );
+    assert_eq!(
+        output[0].token_offsets,
+        vec![
+            None,
+            Some(Offset { begin: 0, end: 5 }),
+            Some(Offset { begin: 5, end: 11 }),
+            Some(Offset { begin: 11, end: 12 }),
+            None,
+            None,
+            Some(Offset { begin: 0, end: 3 }),
+            Some(Offset { begin: 3, end: 7 }),
+            None,
+        ]
+    );
+    assert_eq!(output[0].mask.len(), output[0].token_ids.len());
+    assert_eq!(output[0].reference_offsets.len(), output[0].token_ids.len());
+    assert_eq!(
+        longformer_tokenizer.decode(&output[0].token_ids, false, false),
+        "<s>Hello world!</s></s>The dog</s>"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_longformer_mask_token_lstrip() -> anyhow::Result<()> {
+    let longformer_tokenizer = LongformerTokenizer::from_file(
+        "tests/fixtures/longformer/vocab.json",
+        "tests/fixtures/longformer/merges.txt",
+        false,
+        false,
+    )?;
+
+    let tokens = longformer_tokenizer.tokenize_with_offsets("Hello <mask>!");
+
+    // the mask token absorbs the space preceding it
+    assert_eq!(tokens.tokens[1], "<mask>");
+    assert_eq!(tokens.offsets[1], Some(Offset { begin: 5, end: 12 }));
+    assert_eq!(tokens.reference_offsets[1], vec![5, 6, 7, 8, 9, 10, 11]);
+    assert_eq!(tokens.offsets[2], Some(Offset { begin: 12, end: 13 }));
+    Ok(())
+}
@@ -0,0 +1,138 @@
+use rust_tokenizers::tokenizer::{MPNetTokenizer, Tokenizer, TruncationStrategy};
+use rust_tokenizers::{Offset, TokenizedInput};
+
+#[test]
+fn test_mpnet_tokenization() -> anyhow::Result<()> {
+    let mpnet_tokenizer = MPNetTokenizer::from_file("tests/fixtures/mpnet/vocab.txt", true, true)?;
+
+    let original_strings = [
+        "The quick brown fox jumps over the lazy dog.",
+        "Hello <mask> world!",
+        "Tokenized ?",
+    ];
+
+    let expected_results = [
+        TokenizedInput {
+            token_ids: vec![0, 9, 10, 11, 12, 13, 14, 16, 9, 17, 18, 19, 2],
+            segment_ids: vec![0; 13],
+            special_tokens_mask: vec![1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1],
+            overflowing_tokens: vec![],
+            num_truncated_tokens: 0,
+            token_offsets: vec![
+                None,
+                Some(Offset { begin: 0, end: 3 }),
+                Some(Offset { begin: 4, end: 9 }),
+                Some(Offset { begin: 10, end: 15 }),
+                Some(Offset { begin: 16, end: 19 }),
+                Some(Offset { begin: 20, end: 24 }),
+                Some(Offset { begin: 24, end: 25 }),
+                Some(Offset { begin: 26, end: 30 }),
+                Some(Offset { begin: 31, end: 34 }),
+                Some(Offset { begin: 35, end: 39 }),
+                Some(Offset { begin: 40, end: 43 }),
+                Some(Offset { begin: 43, end: 44 }),
+                None,
+            ],
+            reference_offsets: vec![],
+            mask: vec![],
+        },
+        TokenizedInput {
+            token_ids: vec![0, 23, 28, 24, 20, 2],
+            segment_ids: vec![0; 6],
+            special_tokens_mask: vec![1, 0, 0, 0, 0, 1],
+            overflowing_tokens: vec![],
+            num_truncated_tokens: 0,
+            token_offsets: vec![
+                None,
+                Some(Offset { begin: 0, end: 5 }),
+                // the mask token absorbs the preceding space
+                Some(Offset { begin: 5, end: 12 }),
+                Some(Offset { begin: 13, end: 18 }),
+                Some(Offset { begin: 18, end: 19 }),
+                None,
+            ],
+            reference_offsets: vec![],
+            mask: vec![],
+        },
+        TokenizedInput {
+            token_ids: vec![0, 25, 26, 21, 2],
+            segment_ids: vec![0; 5],
+            special_tokens_mask: vec![1, 0, 0, 0, 1],
+            overflowing_tokens: vec![],
+            num_truncated_tokens: 0,
+            token_offsets: vec![
+                None,
+                Some(Offset { begin: 0, end: 5 }),
+                Some(Offset { begin: 5, end: 9 }),
+                Some(Offset { begin: 10, end: 11 }),
+                None,
+            ],
+            reference_offsets: vec![],
+            mask: vec![],
+        },
+    ]
+    .to_vec();
+
+    let output =
+        mpnet_tokenizer.encode_list(&original_strings, 128, &TruncationStrategy::LongestFirst, 0);
+
+    for (predicted, expected) in output.iter().zip(expected_results.iter()) {
+        assert_eq!(predicted.token_ids, expected.token_ids);
+        assert_eq!(predicted.segment_ids, expected.segment_ids);
+        assert_eq!(predicted.special_tokens_mask, expected.special_tokens_mask);
+        assert_eq!(predicted.token_offsets, expected.token_offsets);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_mpnet_pair_encoding() -> anyhow::Result<()> {
+    let mpnet_tokenizer = MPNetTokenizer::from_file("tests/fixtures/mpnet/vocab.txt", true, true)?;
+
+    let output = mpnet_tokenizer.encode_pair_list(
+        &[("Hello world!", "the dog")],
+        128,
+        &TruncationStrategy::LongestFirst,
+        0,
+    );
+
+    assert_eq!(output[0].token_ids, vec![0, 23, 24, 20, 2, 2, 9, 18, 2]);
+    assert_eq!(output[0].segment_ids, vec![0; 9]);
+    assert_eq!(
+        output[0].special_tokens_mask,
+        vec![1, 0, 0, 0, 1, 1, 0, 0, 1]
+    );
+    assert_eq!(
+        output[0].token_offsets,
+        vec![
+            None,
+            Some(Offset { begin: 0, end: 5 }),
+            Some(Offset { begin: 6, end: 11 }),
+            Some(Offset { begin: 11, end: 12 }),
+            None,
+            None,
+            Some(Offset { begin: 0, end: 3 }),
+            Some(Offset { begin: 4, end: 7 }),
+            None,
+        ]
+    );
+    assert_eq!(
+        mpnet_tokenizer.decode(&output[0].token_ids, true, true),
+        "hello world! the dog"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_mpnet_mask_token_lstrip() -> anyhow::Result<()> {
+    let mpnet_tokenizer = MPNetTokenizer::from_file("tests/fixtures/mpnet/vocab.txt", true, true)?;
+
+    let tokens = mpnet_tokenizer.tokenize_with_offsets("Hello <mask>!");
+
+    // the mask token absorbs the space preceding it
+    assert_eq!(tokens.tokens[1], "<mask>");
+    assert_eq!(tokens.offsets[1], Some(Offset { begin: 5, end: 12 }));
+    assert_eq!(tokens.reference_offsets[1], vec![5, 6, 7, 8, 9, 10, 11]);
+    assert_eq!(tokens.offsets[2], Some(Offset { begin: 12, end: 13 }));
+    Ok(())
+}
@@ -72,6 +72,7 @@ setup(
     rust_extensions=[RustExtension("rust_tokenizers.rust_tokenizers", "Cargo.toml", debug=False)],
     setup_requires=setup_requires,
     test_requires=test_requires,
+    extras_require={"numpy": ["numpy"]},
     include_package_data=True,
     zip_safe=False,
     cmdclass={"test": PyTest, "sdist": CargoModifiedSdist},
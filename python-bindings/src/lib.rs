@@ -1,14 +1,18 @@
 use pyo3::exceptions;
 use pyo3::prelude::*;
+use pyo3::types::{PyByteArray, PyDict};
+use std::collections::HashMap;
 
 extern crate rust_tokenizers as rust_tokenizers_base;
 
+use rust_tokenizers_base::{Mask, Offset, OffsetSize, TokenizedInput, TokensWithOffsets};
+
 use rust_tokenizers_base::tokenizer::{
     AlbertTokenizer, BertTokenizer, CtrlTokenizer, DeBERTaTokenizer, DeBERTaV2Tokenizer,
     FNetTokenizer, Gpt2Tokenizer, M2M100Tokenizer, MBart50Tokenizer, MultiThreadedTokenizer,
     NLLBTokenizer, OpenAiGptTokenizer, PegasusTokenizer, ProphetNetTokenizer, ReformerTokenizer,
     RobertaTokenizer, SentencePieceBpeTokenizer, SentencePieceTokenizer, T5Tokenizer, Tokenizer,
-    TruncationStrategy, XLMRobertaTokenizer, XLNetTokenizer,
+    TruncationSide, TruncationStrategy, XLMRobertaTokenizer, XLNetTokenizer,
 };
 use rust_tokenizers_base::vocab::{
     AlbertVocab, BertVocab, DeBERTaV2Vocab, DeBERTaVocab, FNetVocab, Gpt2Vocab, M2M100Vocab,
@@ -29,11 +33,86 @@ pub struct PyTokenizedInput {
     pub overflowing_tokens: Vec<i64>,
     #[pyo3(get)]
     pub num_truncated_tokens: usize,
+    #[pyo3(get)]
+    pub token_offsets: Vec<Option<(OffsetSize, OffsetSize)>>,
+    #[pyo3(get)]
+    pub reference_offsets: Vec<Vec<OffsetSize>>,
+    #[pyo3(get)]
+    pub mask: Vec<&'static str>,
+}
+
+impl From<TokenizedInput> for PyTokenizedInput {
+    fn from(tokenized_input: TokenizedInput) -> Self {
+        PyTokenizedInput {
+            token_ids: tokenized_input.token_ids,
+            segment_ids: tokenized_input.segment_ids,
+            special_tokens_mask: tokenized_input.special_tokens_mask,
+            overflowing_tokens: tokenized_input.overflowing_tokens,
+            num_truncated_tokens: tokenized_input.num_truncated_tokens,
+            token_offsets: convert_offsets(tokenized_input.token_offsets),
+            reference_offsets: tokenized_input.reference_offsets,
+            mask: convert_masks(tokenized_input.mask),
+        }
+    }
+}
+
+#[pyclass]
+#[derive(Debug, PartialEq, PartialOrd, Clone)]
+pub struct PyTokensWithOffsets {
+    #[pyo3(get)]
+    pub tokens: Vec<String>,
+    #[pyo3(get)]
+    pub offsets: Vec<Option<(OffsetSize, OffsetSize)>>,
+    #[pyo3(get)]
+    pub reference_offsets: Vec<Vec<OffsetSize>>,
+    #[pyo3(get)]
+    pub masks: Vec<&'static str>,
+}
+
+impl From<TokensWithOffsets> for PyTokensWithOffsets {
+    fn from(tokens_with_offsets: TokensWithOffsets) -> Self {
+        PyTokensWithOffsets {
+            tokens: tokens_with_offsets.tokens,
+            offsets: convert_offsets(tokens_with_offsets.offsets),
+            reference_offsets: tokens_with_offsets.reference_offsets,
+            masks: convert_masks(tokens_with_offsets.masks),
+        }
+    }
+}
+
+fn convert_offsets(offsets: Vec<Option<Offset>>) -> Vec<Option<(OffsetSize, OffsetSize)>> {
+    offsets
+        .into_iter()
+        .map(|offset| offset.map(|offset| (offset.begin, offset.end)))
+        .collect()
+}
+
+fn convert_masks(masks: Vec<Mask>) -> Vec<&'static str> {
+    masks
+        .into_iter()
+        .map(|mask| match mask {
+            Mask::None => "none",
+            Mask::Whitespace => "whitespace",
+            Mask::Punctuation => "punctuation",
+            Mask::CJK => "cjk",
+            Mask::Special => "special",
+            Mask::Begin => "begin",
+            Mask::Continuation => "continuation",
+            Mask::Unfinished => "unfinished",
+            Mask::Unknown => "unknown",
+        })
+        .collect()
 }
 
 trait PyTokenizer<T: Tokenizer<U>, U: Vocab> {
     fn tokenizer(&self) -> &T;
 
+    fn tokenizer_mut(&mut self) -> &mut T;
+
+    fn pad_token_id(&self) -> Option<i64> {
+        None
+    }
+
     fn tokenize(&self, text: &str) -> PyResult<Vec<String>> {
         Ok(self.tokenizer().tokenize(text))
     }
@@ -42,35 +121,70 @@ trait PyTokenizer<T: Tokenizer<U>, U: Vocab> {
         Ok(self.tokenizer().tokenize_list(text_list.as_slice()))
     }
 
+    fn tokenize_with_offsets(&self, text: &str) -> PyResult<PyTokensWithOffsets> {
+        Ok(self.tokenizer().tokenize_with_offsets(text).into())
+    }
+
+    fn convert_tokens_to_ids(&self, tokens: Vec<&str>) -> PyResult<Vec<i64>> {
+        Ok(self.tokenizer().convert_tokens_to_ids(tokens.as_slice()))
+    }
+
+    fn convert_ids_to_tokens(&self, token_ids: Vec<i64>) -> PyResult<Vec<String>> {
+        Ok(self.tokenizer().decode_to_vec(token_ids.as_slice(), false))
+    }
+
+    fn decode(
+        &self,
+        token_ids: Vec<i64>,
+        skip_special_tokens: bool,
+        clean_up_tokenization_spaces: bool,
+    ) -> PyResult<String> {
+        Ok(self.tokenizer().decode(
+            token_ids.as_slice(),
+            skip_special_tokens,
+            clean_up_tokenization_spaces,
+        ))
+    }
+
+    fn decode_list(
+        &self,
+        token_ids_list: Vec<Vec<i64>>,
+        skip_special_tokens: bool,
+        clean_up_tokenization_spaces: bool,
+    ) -> PyResult<Vec<String>> {
+        Ok(self.tokenizer().decode_list(
+            token_ids_list.as_slice(),
+            skip_special_tokens,
+            clean_up_tokenization_spaces,
+        ))
+    }
+
+    fn add_tokens(&mut self, tokens: Vec<&str>) -> PyResult<()> {
+        self.tokenizer_mut().add_tokens(tokens.as_slice());
+        Ok(())
+    }
+
+    fn vocab_size(&self) -> PyResult<usize> {
+        Ok(self.tokenizer().vocab().values().len())
+    }
+
+    fn special_tokens(&self) -> PyResult<HashMap<String, i64>> {
+        Ok(self.tokenizer().vocab().special_values().clone())
+    }
+
     fn encode(
         &self,
         text: &str,
         max_len: usize,
         truncation_strategy: &str,
         stride: usize,
+        truncation_side: &str,
     ) -> PyResult<PyTokenizedInput> {
-        let truncation_strategy = match truncation_strategy {
-            "longest_first" => Ok(TruncationStrategy::LongestFirst),
-            "only_first" => Ok(TruncationStrategy::OnlyFirst),
-            "only_second" => Ok(TruncationStrategy::OnlySecond),
-            "do_not_truncate" => Ok(TruncationStrategy::DoNotTruncate),
-            _ => Err("Invalid truncation strategy provided. Must be one of `longest_first`, `only_first`, `only_second` or `do_not_truncate`")
-        };
-        match truncation_strategy {
-            Ok(truncation_strategy) => {
-                let tokenized_input =
-                    self.tokenizer()
-                        .encode(text, None, max_len, &truncation_strategy, stride);
-                Ok(PyTokenizedInput {
-                    token_ids: tokenized_input.token_ids,
-                    segment_ids: tokenized_input.segment_ids,
-                    special_tokens_mask: tokenized_input.special_tokens_mask,
-                    overflowing_tokens: tokenized_input.overflowing_tokens,
-                    num_truncated_tokens: tokenized_input.num_truncated_tokens,
-                })
-            }
-            Err(e) => Err(exceptions::PyValueError::new_err(e)),
-        }
+        let truncation_strategy = parse_truncation_strategy(truncation_strategy, truncation_side)?;
+        let tokenized_input =
+            self.tokenizer()
+                .encode(text, None, max_len, &truncation_strategy, stride);
+        Ok(tokenized_input.into())
     }
 
     fn encode_pair(
@@ -80,33 +194,13 @@ trait PyTokenizer<T: Tokenizer<U>, U: Vocab> {
         max_len: usize,
         truncation_strategy: &str,
         stride: usize,
+        truncation_side: &str,
     ) -> PyResult<PyTokenizedInput> {
-        let truncation_strategy = match truncation_strategy {
-            "longest_first" => Ok(TruncationStrategy::LongestFirst),
-            "only_first" => Ok(TruncationStrategy::OnlyFirst),
-            "only_second" => Ok(TruncationStrategy::OnlySecond),
-            "do_not_truncate" => Ok(TruncationStrategy::DoNotTruncate),
-            _ => Err("Invalid truncation strategy provided. Must be one of `longest_first`, `only_first`, `only_second` or `do_not_truncate`")
-        };
-        match truncation_strategy {
-            Ok(truncation_strategy) => {
-                let tokenized_input = self.tokenizer().encode(
-                    text_a,
-                    Some(text_b),
-                    max_len,
-                    &truncation_strategy,
-                    stride,
-                );
-                Ok(PyTokenizedInput {
-                    token_ids: tokenized_input.token_ids,
-                    segment_ids: tokenized_input.segment_ids,
-                    special_tokens_mask: tokenized_input.special_tokens_mask,
-                    overflowing_tokens: tokenized_input.overflowing_tokens,
-                    num_truncated_tokens: tokenized_input.num_truncated_tokens,
-                })
-            }
-            Err(e) => Err(exceptions::PyValueError::new_err(e)),
-        }
+        let truncation_strategy = parse_truncation_strategy(truncation_strategy, truncation_side)?;
+        let tokenized_input =
+            self.tokenizer()
+                .encode(text_a, Some(text_b), max_len, &truncation_strategy, stride);
+        Ok(tokenized_input.into())
     }
 
     fn encode_list(
@@ -115,35 +209,19 @@ trait PyTokenizer<T: Tokenizer<U>, U: Vocab> {
         max_len: usize,
         truncation_strategy: &str,
         stride: usize,
+        truncation_side: &str,
     ) -> PyResult<Vec<PyTokenizedInput>> {
-        let truncation_strategy = match truncation_strategy {
-            "longest_first" => Ok(TruncationStrategy::LongestFirst),
-            "only_first" => Ok(TruncationStrategy::OnlyFirst),
-            "only_second" => Ok(TruncationStrategy::OnlySecond),
-            "do_not_truncate" => Ok(TruncationStrategy::DoNotTruncate),
-            _ => Err("Invalid truncation strategy provided. Must be one of `longest_first`, `only_first`, `only_second` or `do_not_truncate`")
-        };
-        match truncation_strategy {
-            Ok(truncation_strategy) => {
-                let tokenized_inputs = self.tokenizer().encode_list(
-                    text_list.as_slice(),
-                    max_len,
-                    &truncation_strategy,
-                    stride,
-                );
-                Ok(tokenized_inputs
-                    .into_iter()
-                    .map(|tokenized_input| PyTokenizedInput {
-                        token_ids: tokenized_input.token_ids,
-                        segment_ids: tokenized_input.segment_ids,
-                        special_tokens_mask: tokenized_input.special_tokens_mask,
-                        overflowing_tokens: tokenized_input.overflowing_tokens,
-                        num_truncated_tokens: tokenized_input.num_truncated_tokens,
-                    })
-                    .collect::<Vec<PyTokenizedInput>>())
-            }
-            Err(e) => Err(exceptions::PyValueError::new_err(e)),
-        }
+        let truncation_strategy = parse_truncation_strategy(truncation_strategy, truncation_side)?;
+        let tokenized_inputs = self.tokenizer().encode_list(
+            text_list.as_slice(),
+            max_len,
+            &truncation_strategy,
+            stride,
+        );
+        Ok(tokenized_inputs
+            .into_iter()
+            .map(PyTokenizedInput::from)
+            .collect::<Vec<PyTokenizedInput>>())
     }
 
     fn encode_pair_list(
@@ -152,47 +230,137 @@ trait PyTokenizer<T: Tokenizer<U>, U: Vocab> {
         max_len: usize,
         truncation_strategy: &str,
         stride: usize,
+        truncation_side: &str,
     ) -> PyResult<Vec<PyTokenizedInput>> {
-        let truncation_strategy = match truncation_strategy {
-            "longest_first" => Ok(TruncationStrategy::LongestFirst),
-            "only_first" => Ok(TruncationStrategy::OnlyFirst),
-            "only_second" => Ok(TruncationStrategy::OnlySecond),
-            "do_not_truncate" => Ok(TruncationStrategy::DoNotTruncate),
-            _ => Err("Invalid truncation strategy provided. Must be one of `longest_first`, `only_first`, `only_second` or `do_not_truncate`")
-        };
-        match truncation_strategy {
-            Ok(truncation_strategy) => {
-                let tokenized_inputs = self.tokenizer().encode_pair_list(
-                    text_list.as_slice(),
-                    max_len,
-                    &truncation_strategy,
-                    stride,
-                );
-                Ok(tokenized_inputs
-                    .into_iter()
-                    .map(|tokenized_input| PyTokenizedInput {
-                        token_ids: tokenized_input.token_ids,
-                        segment_ids: tokenized_input.segment_ids,
-                        special_tokens_mask: tokenized_input.special_tokens_mask,
-                        overflowing_tokens: tokenized_input.overflowing_tokens,
-                        num_truncated_tokens: tokenized_input.num_truncated_tokens,
-                    })
-                    .collect::<Vec<PyTokenizedInput>>())
+        let truncation_strategy = parse_truncation_strategy(truncation_strategy, truncation_side)?;
+        let tokenized_inputs = self.tokenizer().encode_pair_list(
+            text_list.as_slice(),
+            max_len,
+            &truncation_strategy,
+            stride,
+        );
+        Ok(tokenized_inputs
+            .into_iter()
+            .map(PyTokenizedInput::from)
+            .collect::<Vec<PyTokenizedInput>>())
+    }
+}
+
+fn parse_truncation_strategy(
+    truncation_strategy: &str,
+    truncation_side: &str,
+) -> PyResult<TruncationStrategy> {
+    let truncation_strategy = match truncation_strategy {
+        "longest_first" => TruncationStrategy::LongestFirst,
+        "only_first" => TruncationStrategy::OnlyFirst,
+        "only_second" => TruncationStrategy::OnlySecond,
+        "do_not_truncate" => TruncationStrategy::DoNotTruncate,
+        _ => return Err(exceptions::PyValueError::new_err("Invalid truncation strategy provided. Must be one of `longest_first`, `only_first`, `only_second` or `do_not_truncate`"))
+    };
+    match truncation_side {
+        "right" => Ok(truncation_strategy),
+        "left" => Ok(truncation_strategy.with_side(TruncationSide::Left)),
+        _ => Err(exceptions::PyValueError::new_err(
+            "Invalid truncation side provided. Must be one of `right` or `left`",
+        )),
+    }
+}
+
+/// Padded batch of token ids, attention masks and segment ids, stored row-major as
+/// `batch_size x sequence_length` native-endian `int64` buffers
+struct PaddedBatch {
+    batch_size: usize,
+    sequence_length: usize,
+    token_ids: Vec<u8>,
+    attention_mask: Vec<u8>,
+    segment_ids: Vec<u8>,
+}
+
+impl PaddedBatch {
+    fn new(tokenized_inputs: &[TokenizedInput], pad_token_id: i64) -> PaddedBatch {
+        let batch_size = tokenized_inputs.len();
+        let sequence_length = tokenized_inputs
+            .iter()
+            .map(|tokenized_input| tokenized_input.token_ids.len())
+            .max()
+            .unwrap_or(0);
+        let buffer_size = batch_size * sequence_length * std::mem::size_of::<i64>();
+        let mut token_ids = Vec::with_capacity(buffer_size);
+        let mut attention_mask = Vec::with_capacity(buffer_size);
+        let mut segment_ids = Vec::with_capacity(buffer_size);
+        for tokenized_input in tokenized_inputs {
+            let padding_length = sequence_length - tokenized_input.token_ids.len();
+            for (token_id, segment_id) in tokenized_input
+                .token_ids
+                .iter()
+                .zip(tokenized_input.segment_ids.iter())
+            {
+                token_ids.extend_from_slice(&token_id.to_ne_bytes());
+                attention_mask.extend_from_slice(&1i64.to_ne_bytes());
+                segment_ids.extend_from_slice(&(*segment_id as i64).to_ne_bytes());
+            }
+            for _ in 0..padding_length {
+                token_ids.extend_from_slice(&pad_token_id.to_ne_bytes());
+                attention_mask.extend_from_slice(&0i64.to_ne_bytes());
+                segment_ids.extend_from_slice(&0i64.to_ne_bytes());
             }
-            Err(e) => Err(exceptions::PyValueError::new_err(e)),
+        }
+        PaddedBatch {
+            batch_size,
+            sequence_length,
+            token_ids,
+            attention_mask,
+            segment_ids,
         }
     }
+
+    /// Converts the batch to a dictionary of NumPy `int64` arrays. NumPy is imported at runtime
+    /// and is only required when NumPy outputs are requested.
+    fn into_numpy(self, py: Python<'_>) -> PyResult<PyObject> {
+        let numpy = py.import("numpy")?;
+        let shape = (self.batch_size, self.sequence_length);
+        let output = PyDict::new(py);
+        for (name, buffer) in [
+            ("token_ids", self.token_ids),
+            ("attention_mask", self.attention_mask),
+            ("segment_ids", self.segment_ids),
+        ] {
+            let array = numpy
+                .call_method1("frombuffer", (PyByteArray::new(py, &buffer), "int64"))?
+                .call_method1("reshape", (shape,))?;
+            output.set_item(name, array)?;
+        }
+        Ok(output.into())
+    }
 }
 
 trait PyMultiThreadTokenizer<T: MultiThreadedTokenizer<U>, U: Vocab>
 where
     Self: PyTokenizer<T, U>,
 {
-    fn tokenize_list(&self, text_list: Vec<&str>) -> PyResult<Vec<Vec<String>>> {
-        Ok(MultiThreadedTokenizer::tokenize_list(
-            self.tokenizer(),
-            text_list.as_slice(),
-        ))
+    fn tokenize_list(&self, py: Python<'_>, text_list: Vec<&str>) -> PyResult<Vec<Vec<String>>> {
+        let tokenizer = self.tokenizer();
+        Ok(py.allow_threads(|| {
+            MultiThreadedTokenizer::tokenize_list(tokenizer, text_list.as_slice())
+        }))
+    }
+
+    fn decode_list(
+        &self,
+        py: Python<'_>,
+        token_ids_list: Vec<Vec<i64>>,
+        skip_special_tokens: bool,
+        clean_up_tokenization_spaces: bool,
+    ) -> PyResult<Vec<String>> {
+        let tokenizer = self.tokenizer();
+        Ok(py.allow_threads(|| {
+            MultiThreadedTokenizer::decode_list(
+                tokenizer,
+                token_ids_list.as_slice(),
+                skip_special_tokens,
+                clean_up_tokenization_spaces,
+            )
+        }))
     }
 
     fn encode_list(
@@ -201,36 +369,24 @@ where
         max_len: usize,
         truncation_strategy: &str,
         stride: usize,
-    ) -> PyResult<Vec<PyTokenizedInput>> {
-        let truncation_strategy = match truncation_strategy {
-            "longest_first" => Ok(TruncationStrategy::LongestFirst),
-            "only_first" => Ok(TruncationStrategy::OnlyFirst),
-            "only_second" => Ok(TruncationStrategy::OnlySecond),
-            "do_not_truncate" => Ok(TruncationStrategy::DoNotTruncate),
-            _ => Err("Invalid truncation strategy provided. Must be one of `longest_first`, `only_first`, `only_second` or `do_not_truncate`")
-        };
-        match truncation_strategy {
-            Ok(truncation_strategy) => {
-                let tokenized_inputs = MultiThreadedTokenizer::encode_list(
-                    self.tokenizer(),
+        return_numpy: bool,
+        pad_token_id: Option<i64>,
+        truncation_side: &str,
+    ) -> PyResult<PyObject> {
+        let truncation_strategy = parse_truncation_strategy(truncation_strategy, truncation_side)?;
+        let tokenizer = self.tokenizer();
+        Python::with_gil(|py| {
+            let tokenized_inputs = py.allow_threads(|| {
+                MultiThreadedTokenizer::encode_list(
+                    tokenizer,
                     &text_list,
                     max_len,
                     &truncation_strategy,
                     stride,
-                );
-                Ok(tokenized_inputs
-                    .into_iter()
-                    .map(|tokenized_input| PyTokenizedInput {
-                        token_ids: tokenized_input.token_ids,
-                        segment_ids: tokenized_input.segment_ids,
-                        special_tokens_mask: tokenized_input.special_tokens_mask,
-                        overflowing_tokens: tokenized_input.overflowing_tokens,
-                        num_truncated_tokens: tokenized_input.num_truncated_tokens,
-                    })
-                    .collect::<Vec<PyTokenizedInput>>())
-            }
-            Err(e) => Err(exceptions::PyValueError::new_err(e)),
-        }
+                )
+            });
+            self.convert_tokenized_inputs(py, tokenized_inputs, return_numpy, pad_token_id)
+        })
     }
 
     fn encode_pair_list(
@@ -239,35 +395,65 @@ where
         max_len: usize,
         truncation_strategy: &str,
         stride: usize,
-    ) -> PyResult<Vec<PyTokenizedInput>> {
-        let truncation_strategy = match truncation_strategy {
-            "longest_first" => Ok(TruncationStrategy::LongestFirst),
-            "only_first" => Ok(TruncationStrategy::OnlyFirst),
-            "only_second" => Ok(TruncationStrategy::OnlySecond),
-            "do_not_truncate" => Ok(TruncationStrategy::DoNotTruncate),
-            _ => Err("Invalid truncation strategy provided. Must be one of `longest_first`, `only_first`, `only_second` or `do_not_truncate`")
-        };
-        match truncation_strategy {
-            Ok(truncation_strategy) => {
-                let tokenized_inputs = MultiThreadedTokenizer::encode_pair_list(
-                    self.tokenizer(),
+        return_numpy: bool,
+        pad_token_id: Option<i64>,
+        truncation_side: &str,
+    ) -> PyResult<PyObject> {
+        let truncation_strategy = parse_truncation_strategy(truncation_strategy, truncation_side)?;
+        let tokenizer = self.tokenizer();
+        Python::with_gil(|py| {
+            let tokenized_inputs = py.allow_threads(|| {
+                MultiThreadedTokenizer::encode_pair_list(
+                    tokenizer,
                     &text_list,
                     max_len,
                     &truncation_strategy,
                     stride,
-                );
-                Ok(tokenized_inputs
-                    .into_iter()
-                    .map(|tokenized_input| PyTokenizedInput {
-                        token_ids: tokenized_input.token_ids,
-                        segment_ids: tokenized_input.segment_ids,
-                        special_tokens_mask: tokenized_input.special_tokens_mask,
-                        overflowing_tokens: tokenized_input.overflowing_tokens,
-                        num_truncated_tokens: tokenized_input.num_truncated_tokens,
-                    })
-                    .collect::<Vec<PyTokenizedInput>>())
-            }
-            Err(e) => Err(exceptions::PyValueError::new_err(e)),
+                )
+            });
+            self.convert_tokenized_inputs(py, tokenized_inputs, return_numpy, pad_token_id)
+        })
+    }
+
+    fn convert_tokenized_inputs(
+        &self,
+        py: Python<'_>,
+        tokenized_inputs: Vec<TokenizedInput>,
+        return_numpy: bool,
+        pad_token_id: Option<i64>,
+    ) -> PyResult<PyObject> {
+        if return_numpy {
+            let pad_token_id = pad_token_id
+                .or_else(|| self.pad_token_id())
+                .ok_or_else(|| {
+                    exceptions::PyValueError::new_err(
+                        "This tokenizer has no padding token, a `pad_token_id` must be provided",
+                    )
+                })?;
+            py.allow_threads(|| PaddedBatch::new(&tokenized_inputs, pad_token_id))
+                .into_numpy(py)
+        } else {
+            Ok(tokenized_inputs
+                .into_iter()
+                .map(PyTokenizedInput::from)
+                .collect::<Vec<PyTokenizedInput>>()
+                .into_py(py))
+        }
+    }
+}
+
+/// Serializable state of a Python tokenizer: the tokenizer is rebuilt from its constructor
+/// arguments when unpickled, and the tokens added since its creation are registered again.
+struct PyTokenizerState {
+    init_args: PyObject,
+    added_tokens: Vec<String>,
+}
+
+impl PyTokenizerState {
+    fn new(init_args: PyObject) -> Self {
+        PyTokenizerState {
+            init_args,
+            added_tokens: vec![],
         }
     }
 }
@@ -275,12 +461,22 @@ where
 #[pyclass(dict, module = "rust_tokenizers")]
 struct PyBertTokenizer {
     tokenizer: BertTokenizer,
+    state: PyTokenizerState,
 }
 
 impl PyTokenizer<BertTokenizer, BertVocab> for PyBertTokenizer {
     fn tokenizer(&self) -> &BertTokenizer {
         &self.tokenizer
     }
+
+    fn tokenizer_mut(&mut self) -> &mut BertTokenizer {
+        &mut self.tokenizer
+    }
+
+    fn pad_token_id(&self) -> Option<i64> {
+        let vocab = Tokenizer::vocab(&self.tokenizer);
+        Some(vocab.token_to_id(vocab.get_pad_value()))
+    }
 }
 
 impl PyMultiThreadTokenizer<BertTokenizer, BertVocab> for PyBertTokenizer {}
@@ -288,10 +484,11 @@ impl PyMultiThreadTokenizer<BertTokenizer, BertVocab> for PyBertTokenizer {}
 #[pymethods]
 impl PyBertTokenizer {
     #[new]
-    fn new(path: String, do_lower_case: bool, strip_accents: bool) -> Self {
+    fn new(py: Python<'_>, path: String, do_lower_case: bool, strip_accents: bool) -> Self {
         PyBertTokenizer {
             tokenizer: BertTokenizer::from_file(path.as_str(), do_lower_case, strip_accents)
                 .unwrap(),
+            state: PyTokenizerState::new((path, do_lower_case, strip_accents).into_py(py)),
         }
     }
 
@@ -299,16 +496,20 @@ impl PyBertTokenizer {
         <Self as PyTokenizer<BertTokenizer, BertVocab>>::tokenize(self, text)
     }
 
-    fn tokenize_list(&self, text_list: Vec<&str>) -> PyResult<Vec<Vec<String>>> {
-        <Self as PyMultiThreadTokenizer<BertTokenizer, BertVocab>>::tokenize_list(self, text_list)
+    fn tokenize_list(&self, py: Python<'_>, text_list: Vec<&str>) -> PyResult<Vec<Vec<String>>> {
+        <Self as PyMultiThreadTokenizer<BertTokenizer, BertVocab>>::tokenize_list(
+            self, py, text_list,
+        )
     }
 
+    #[pyo3(signature = (text, max_len, truncation_strategy, stride, truncation_side = "right"))]
     fn encode(
         &self,
         text: &str,
         max_len: usize,
         truncation_strategy: &str,
         stride: usize,
+        truncation_side: &str,
     ) -> PyResult<PyTokenizedInput> {
         <Self as PyTokenizer<BertTokenizer, BertVocab>>::encode(
             self,
@@ -316,9 +517,11 @@ impl PyBertTokenizer {
             max_len,
             truncation_strategy,
             stride,
+            truncation_side,
         )
     }
 
+    #[pyo3(signature = (text_a, text_b, max_len, truncation_strategy, stride, truncation_side = "right"))]
     fn encode_pair(
         &self,
         text_a: &str,
@@ -326,6 +529,7 @@ impl PyBertTokenizer {
         max_len: usize,
         truncation_strategy: &str,
         stride: usize,
+        truncation_side: &str,
     ) -> PyResult<PyTokenizedInput> {
         <Self as PyTokenizer<BertTokenizer, BertVocab>>::encode_pair(
             self,
@@ -334,51 +538,149 @@ impl PyBertTokenizer {
             max_len,
             truncation_strategy,
             stride,
+            truncation_side,
         )
     }
 
+    #[pyo3(signature = (text_list, max_len, truncation_strategy, stride, return_numpy = false, pad_token_id = None, truncation_side = "right"))]
     fn encode_list(
         &self,
         text_list: Vec<&str>,
         max_len: usize,
         truncation_strategy: &str,
         stride: usize,
-    ) -> PyResult<Vec<PyTokenizedInput>> {
+        return_numpy: bool,
+        pad_token_id: Option<i64>,
+        truncation_side: &str,
+    ) -> PyResult<PyObject> {
         <Self as PyMultiThreadTokenizer<BertTokenizer, BertVocab>>::encode_list(
             self,
             text_list,
             max_len,
             truncation_strategy,
             stride,
+            return_numpy,
+            pad_token_id,
+            truncation_side,
         )
     }
 
+    #[pyo3(signature = (text_list, max_len, truncation_strategy, stride, return_numpy = false, pad_token_id = None, truncation_side = "right"))]
     fn encode_pair_list(
         &self,
         text_list: Vec<(&str, &str)>,
         max_len: usize,
         truncation_strategy: &str,
         stride: usize,
-    ) -> PyResult<Vec<PyTokenizedInput>> {
+        return_numpy: bool,
+        pad_token_id: Option<i64>,
+        truncation_side: &str,
+    ) -> PyResult<PyObject> {
         <Self as PyMultiThreadTokenizer<BertTokenizer, BertVocab>>::encode_pair_list(
             self,
             text_list,
             max_len,
             truncation_strategy,
             stride,
+            return_numpy,
+            pad_token_id,
+            truncation_side,
+        )
+    }
+
+    fn tokenize_with_offsets(&self, text: &str) -> PyResult<PyTokensWithOffsets> {
+        <Self as PyTokenizer<BertTokenizer, BertVocab>>::tokenize_with_offsets(self, text)
+    }
+
+    fn convert_tokens_to_ids(&self, tokens: Vec<&str>) -> PyResult<Vec<i64>> {
+        <Self as PyTokenizer<BertTokenizer, BertVocab>>::convert_tokens_to_ids(self, tokens)
+    }
+
+    fn convert_ids_to_tokens(&self, token_ids: Vec<i64>) -> PyResult<Vec<String>> {
+        <Self as PyTokenizer<BertTokenizer, BertVocab>>::convert_ids_to_tokens(self, token_ids)
+    }
+
+    fn decode(
+        &self,
+        token_ids: Vec<i64>,
+        skip_special_tokens: bool,
+        clean_up_tokenization_spaces: bool,
+    ) -> PyResult<String> {
+        <Self as PyTokenizer<BertTokenizer, BertVocab>>::decode(
+            self,
+            token_ids,
+            skip_special_tokens,
+            clean_up_tokenization_spaces,
+        )
+    }
+
+    fn decode_list(
+        &self,
+        py: Python<'_>,
+        token_ids_list: Vec<Vec<i64>>,
+        skip_special_tokens: bool,
+        clean_up_tokenization_spaces: bool,
+    ) -> PyResult<Vec<String>> {
+        <Self as PyMultiThreadTokenizer<BertTokenizer, BertVocab>>::decode_list(
+            self,
+            py,
+            token_ids_list,
+            skip_special_tokens,
+            clean_up_tokenization_spaces,
         )
     }
+
+    fn add_tokens(&mut self, tokens: Vec<&str>) -> PyResult<()> {
+        self.state
+            .added_tokens
+            .extend(tokens.iter().map(|token| token.to_string()));
+        <Self as PyTokenizer<BertTokenizer, BertVocab>>::add_tokens(self, tokens)
+    }
+
+    fn vocab_size(&self) -> PyResult<usize> {
+        <Self as PyTokenizer<BertTokenizer, BertVocab>>::vocab_size(self)
+    }
+
+    fn special_tokens(&self) -> PyResult<HashMap<String, i64>> {
+        <Self as PyTokenizer<BertTokenizer, BertVocab>>::special_tokens(self)
+    }
+
+    fn __getnewargs__(&self, py: Python<'_>) -> PyObject {
+        self.state.init_args.clone_ref(py)
+    }
+
+    fn __getstate__(&self) -> Vec<String> {
+        self.state.added_tokens.clone()
+    }
+
+    fn __setstate__(&mut self, added_tokens: Vec<String>) -> PyResult<()> {
+        self.add_tokens(added_tokens.iter().map(String::as_str).collect())
+    }
 }
 
 #[pyclass(module = "rust_tokenizers")]
 struct PyCtrlTokenizer {
     tokenizer: CtrlTokenizer,
+    state: PyTokenizerState,
 }
 
 impl PyTokenizer<CtrlTokenizer, OpenAiGptVocab> for PyCtrlTokenizer {
     fn tokenizer(&self) -> &CtrlTokenizer {
         &self.tokenizer
     }
+
+    fn tokenizer_mut(&mut self) -> &mut CtrlTokenizer {
+        &mut self.tokenizer
+    }
+
+    fn pad_token_id(&self) -> Option<i64> {
+        let vocab = Tokenizer::vocab(&self.tokenizer);
+        vocab
+            .special_token_map
+            .pad_token
+            .as_deref()
+            .map(|pad_token| vocab.token_to_id(pad_token))
+    }
 }
 
 impl PyMultiThreadTokenizer<CtrlTokenizer, OpenAiGptVocab> for PyCtrlTokenizer {}
@@ -386,7 +688,7 @@ impl PyMultiThreadTokenizer<CtrlTokenizer, OpenAiGptVocab> for PyCtrlTokenizer {
 #[pymethods]
 impl PyCtrlTokenizer {
     #[new]
-    fn new(vocab_path: String, merges_path: String, do_lower_case: bool) -> Self {
+    fn new(py: Python<'_>, vocab_path: String, merges_path: String, do_lower_case: bool) -> Self {
         PyCtrlTokenizer {
             tokenizer: CtrlTokenizer::from_file(
                 vocab_path.as_str(),
@@ -394,6 +696,7 @@ impl PyCtrlTokenizer {
                 do_lower_case,
             )
             .unwrap(),
+            state: PyTokenizerState::new((vocab_path, merges_path, do_lower_case).into_py(py)),
         }
     }
 
@@ -401,18 +704,20 @@ impl PyCtrlTokenizer {
         <Self as PyTokenizer<CtrlTokenizer, OpenAiGptVocab>>::tokenize(self, text)
     }
 
-    fn tokenize_list(&self, text_list: Vec<&str>) -> PyResult<Vec<Vec<String>>> {
+    fn tokenize_list(&self, py: Python<'_>, text_list: Vec<&str>) -> PyResult<Vec<Vec<String>>> {
         <Self as PyMultiThreadTokenizer<CtrlTokenizer, OpenAiGptVocab>>::tokenize_list(
-            self, text_list,
+            self, py, text_list,
         )
     }
 
+    #[pyo3(signature = (text, max_len, truncation_strategy, stride, truncation_side = "right"))]
     fn encode(
         &self,
         text: &str,
         max_len: usize,
         truncation_strategy: &str,
         stride: usize,
+        truncation_side: &str,
     ) -> PyResult<PyTokenizedInput> {
         <Self as PyTokenizer<CtrlTokenizer, OpenAiGptVocab>>::encode(
             self,
@@ -420,9 +725,11 @@ impl PyCtrlTokenizer {
             max_len,
             truncation_strategy,
             stride,
+            truncation_side,
         )
     }
 
+    #[pyo3(signature = (text_a, text_b, max_len, truncation_strategy, stride, truncation_side = "right"))]
     fn encode_pair(
         &self,
         text_a: &str,
@@ -430,6 +737,7 @@ impl PyCtrlTokenizer {
         max_len: usize,
         truncation_strategy: &str,
         stride: usize,
+        truncation_side: &str,
     ) -> PyResult<PyTokenizedInput> {
         <Self as PyTokenizer<CtrlTokenizer, OpenAiGptVocab>>::encode_pair(
             self,
@@ -438,51 +746,149 @@ impl PyCtrlTokenizer {
             max_len,
             truncation_strategy,
             stride,
+            truncation_side,
         )
     }
 
+    #[pyo3(signature = (text_list, max_len, truncation_strategy, stride, return_numpy = false, pad_token_id = None, truncation_side = "right"))]
     fn encode_list(
         &self,
         text_list: Vec<&str>,
         max_len: usize,
         truncation_strategy: &str,
         stride: usize,
-    ) -> PyResult<Vec<PyTokenizedInput>> {
+        return_numpy: bool,
+        pad_token_id: Option<i64>,
+        truncation_side: &str,
+    ) -> PyResult<PyObject> {
         <Self as PyMultiThreadTokenizer<CtrlTokenizer, OpenAiGptVocab>>::encode_list(
             self,
             text_list,
             max_len,
             truncation_strategy,
             stride,
+            return_numpy,
+            pad_token_id,
+            truncation_side,
         )
     }
 
+    #[pyo3(signature = (text_list, max_len, truncation_strategy, stride, return_numpy = false, pad_token_id = None, truncation_side = "right"))]
     fn encode_pair_list(
         &self,
         text_list: Vec<(&str, &str)>,
         max_len: usize,
         truncation_strategy: &str,
         stride: usize,
-    ) -> PyResult<Vec<PyTokenizedInput>> {
+        return_numpy: bool,
+        pad_token_id: Option<i64>,
+        truncation_side: &str,
+    ) -> PyResult<PyObject> {
         <Self as PyMultiThreadTokenizer<CtrlTokenizer, OpenAiGptVocab>>::encode_pair_list(
             self,
             text_list,
             max_len,
             truncation_strategy,
             stride,
+            return_numpy,
+            pad_token_id,
+            truncation_side,
+        )
+    }
+
+    fn tokenize_with_offsets(&self, text: &str) -> PyResult<PyTokensWithOffsets> {
+        <Self as PyTokenizer<CtrlTokenizer, OpenAiGptVocab>>::tokenize_with_offsets(self, text)
+    }
+
+    fn convert_tokens_to_ids(&self, tokens: Vec<&str>) -> PyResult<Vec<i64>> {
+        <Self as PyTokenizer<CtrlTokenizer, OpenAiGptVocab>>::convert_tokens_to_ids(self, tokens)
+    }
+
+    fn convert_ids_to_tokens(&self, token_ids: Vec<i64>) -> PyResult<Vec<String>> {
+        <Self as PyTokenizer<CtrlTokenizer, OpenAiGptVocab>>::convert_ids_to_tokens(self, token_ids)
+    }
+
+    fn decode(
+        &self,
+        token_ids: Vec<i64>,
+        skip_special_tokens: bool,
+        clean_up_tokenization_spaces: bool,
+    ) -> PyResult<String> {
+        <Self as PyTokenizer<CtrlTokenizer, OpenAiGptVocab>>::decode(
+            self,
+            token_ids,
+            skip_special_tokens,
+            clean_up_tokenization_spaces,
+        )
+    }
+
+    fn decode_list(
+        &self,
+        py: Python<'_>,
+        token_ids_list: Vec<Vec<i64>>,
+        skip_special_tokens: bool,
+        clean_up_tokenization_spaces: bool,
+    ) -> PyResult<Vec<String>> {
+        <Self as PyMultiThreadTokenizer<CtrlTokenizer, OpenAiGptVocab>>::decode_list(
+            self,
+            py,
+            token_ids_list,
+            skip_special_tokens,
+            clean_up_tokenization_spaces,
         )
     }
+
+    fn add_tokens(&mut self, tokens: Vec<&str>) -> PyResult<()> {
+        self.state
+            .added_tokens
+            .extend(tokens.iter().map(|token| token.to_string()));
+        <Self as PyTokenizer<CtrlTokenizer, OpenAiGptVocab>>::add_tokens(self, tokens)
+    }
+
+    fn vocab_size(&self) -> PyResult<usize> {
+        <Self as PyTokenizer<CtrlTokenizer, OpenAiGptVocab>>::vocab_size(self)
+    }
+
+    fn special_tokens(&self) -> PyResult<HashMap<String, i64>> {
+        <Self as PyTokenizer<CtrlTokenizer, OpenAiGptVocab>>::special_tokens(self)
+    }
+
+    fn __getnewargs__(&self, py: Python<'_>) -> PyObject {
+        self.state.init_args.clone_ref(py)
+    }
+
+    fn __getstate__(&self) -> Vec<String> {
+        self.state.added_tokens.clone()
+    }
+
+    fn __setstate__(&mut self, added_tokens: Vec<String>) -> PyResult<()> {
+        self.add_tokens(added_tokens.iter().map(String::as_str).collect())
+    }
 }
 
 #[pyclass(module = "rust_tokenizers")]
 struct PyGpt2Tokenizer {
     tokenizer: Gpt2Tokenizer,
+    state: PyTokenizerState,
 }
 
 impl PyTokenizer<Gpt2Tokenizer, Gpt2Vocab> for PyGpt2Tokenizer {
     fn tokenizer(&self) -> &Gpt2Tokenizer {
         &self.tokenizer
     }
+
+    fn tokenizer_mut(&mut self) -> &mut Gpt2Tokenizer {
+        &mut self.tokenizer
+    }
+
+    fn pad_token_id(&self) -> Option<i64> {
+        let vocab = Tokenizer::vocab(&self.tokenizer);
+        vocab
+            .special_token_map
+            .pad_token
+            .as_deref()
+            .map(|pad_token| vocab.token_to_id(pad_token))
+    }
 }
 
 impl PyMultiThreadTokenizer<Gpt2Tokenizer, Gpt2Vocab> for PyGpt2Tokenizer {}
@@ -490,7 +896,7 @@ impl PyMultiThreadTokenizer<Gpt2Tokenizer, Gpt2Vocab> for PyGpt2Tokenizer {}
 #[pymethods]
 impl PyGpt2Tokenizer {
     #[new]
-    fn new(vocab_path: String, merges_path: String, do_lower_case: bool) -> Self {
+    fn new(py: Python<'_>, vocab_path: String, merges_path: String, do_lower_case: bool) -> Self {
         PyGpt2Tokenizer {
             tokenizer: Gpt2Tokenizer::from_file(
                 vocab_path.as_str(),
@@ -498,6 +904,7 @@ impl PyGpt2Tokenizer {
                 do_lower_case,
             )
             .unwrap(),
+            state: PyTokenizerState::new((vocab_path, merges_path, do_lower_case).into_py(py)),
         }
     }
 
@@ -505,16 +912,20 @@ impl PyGpt2Tokenizer {
         <Self as PyTokenizer<Gpt2Tokenizer, Gpt2Vocab>>::tokenize(self, text)
     }
 
-    fn tokenize_list(&self, text_list: Vec<&str>) -> PyResult<Vec<Vec<String>>> {
-        <Self as PyMultiThreadTokenizer<Gpt2Tokenizer, Gpt2Vocab>>::tokenize_list(self, text_list)
+    fn tokenize_list(&self, py: Python<'_>, text_list: Vec<&str>) -> PyResult<Vec<Vec<String>>> {
+        <Self as PyMultiThreadTokenizer<Gpt2Tokenizer, Gpt2Vocab>>::tokenize_list(
+            self, py, text_list,
+        )
     }
 
+    #[pyo3(signature = (text, max_len, truncation_strategy, stride, truncation_side = "right"))]
     fn encode(
         &self,
         text: &str,
         max_len: usize,
         truncation_strategy: &str,
         stride: usize,
+        truncation_side: &str,
     ) -> PyResult<PyTokenizedInput> {
         <Self as PyTokenizer<Gpt2Tokenizer, Gpt2Vocab>>::encode(
             self,
@@ -522,9 +933,11 @@ impl PyGpt2Tokenizer {
             max_len,
             truncation_strategy,
             stride,
+            truncation_side,
         )
     }
 
+    #[pyo3(signature = (text_a, text_b, max_len, truncation_strategy, stride, truncation_side = "right"))]
     fn encode_pair(
         &self,
         text_a: &str,
@@ -532,6 +945,7 @@ impl PyGpt2Tokenizer {
         max_len: usize,
         truncation_strategy: &str,
         stride: usize,
+        truncation_side: &str,
     ) -> PyResult<PyTokenizedInput> {
         <Self as PyTokenizer<Gpt2Tokenizer, Gpt2Vocab>>::encode_pair(
             self,
@@ -540,51 +954,145 @@ impl PyGpt2Tokenizer {
             max_len,
             truncation_strategy,
             stride,
+            truncation_side,
         )
     }
 
+    #[pyo3(signature = (text_list, max_len, truncation_strategy, stride, return_numpy = false, pad_token_id = None, truncation_side = "right"))]
     fn encode_list(
         &self,
         text_list: Vec<&str>,
         max_len: usize,
         truncation_strategy: &str,
         stride: usize,
-    ) -> PyResult<Vec<PyTokenizedInput>> {
+        return_numpy: bool,
+        pad_token_id: Option<i64>,
+        truncation_side: &str,
+    ) -> PyResult<PyObject> {
         <Self as PyMultiThreadTokenizer<Gpt2Tokenizer, Gpt2Vocab>>::encode_list(
             self,
             text_list,
             max_len,
             truncation_strategy,
             stride,
+            return_numpy,
+            pad_token_id,
+            truncation_side,
         )
     }
 
+    #[pyo3(signature = (text_list, max_len, truncation_strategy, stride, return_numpy = false, pad_token_id = None, truncation_side = "right"))]
     fn encode_pair_list(
         &self,
         text_list: Vec<(&str, &str)>,
         max_len: usize,
         truncation_strategy: &str,
         stride: usize,
-    ) -> PyResult<Vec<PyTokenizedInput>> {
+        return_numpy: bool,
+        pad_token_id: Option<i64>,
+        truncation_side: &str,
+    ) -> PyResult<PyObject> {
         <Self as PyMultiThreadTokenizer<Gpt2Tokenizer, Gpt2Vocab>>::encode_pair_list(
             self,
             text_list,
             max_len,
             truncation_strategy,
             stride,
+            return_numpy,
+            pad_token_id,
+            truncation_side,
         )
     }
+
+    fn tokenize_with_offsets(&self, text: &str) -> PyResult<PyTokensWithOffsets> {
+        <Self as PyTokenizer<Gpt2Tokenizer, Gpt2Vocab>>::tokenize_with_offsets(self, text)
+    }
+
+    fn convert_tokens_to_ids(&self, tokens: Vec<&str>) -> PyResult<Vec<i64>> {
+        <Self as PyTokenizer<Gpt2Tokenizer, Gpt2Vocab>>::convert_tokens_to_ids(self, tokens)
+    }
+
+    fn convert_ids_to_tokens(&self, token_ids: Vec<i64>) -> PyResult<Vec<String>> {
+        <Self as PyTokenizer<Gpt2Tokenizer, Gpt2Vocab>>::convert_ids_to_tokens(self, token_ids)
+    }
+
+    fn decode(
+        &self,
+        token_ids: Vec<i64>,
+        skip_special_tokens: bool,
+        clean_up_tokenization_spaces: bool,
+    ) -> PyResult<String> {
+        <Self as PyTokenizer<Gpt2Tokenizer, Gpt2Vocab>>::decode(
+            self,
+            token_ids,
+            skip_special_tokens,
+            clean_up_tokenization_spaces,
+        )
+    }
+
+    fn decode_list(
+        &self,
+        py: Python<'_>,
+        token_ids_list: Vec<Vec<i64>>,
+        skip_special_tokens: bool,
+        clean_up_tokenization_spaces: bool,
+    ) -> PyResult<Vec<String>> {
+        <Self as PyMultiThreadTokenizer<Gpt2Tokenizer, Gpt2Vocab>>::decode_list(
+            self,
+            py,
+            token_ids_list,
+            skip_special_tokens,
+            clean_up_tokenization_spaces,
+        )
+    }
+
+    fn add_tokens(&mut self, tokens: Vec<&str>) -> PyResult<()> {
+        self.state
+            .added_tokens
+            .extend(tokens.iter().map(|token| token.to_string()));
+        <Self as PyTokenizer<Gpt2Tokenizer, Gpt2Vocab>>::add_tokens(self, tokens)
+    }
+
+    fn vocab_size(&self) -> PyResult<usize> {
+        <Self as PyTokenizer<Gpt2Tokenizer, Gpt2Vocab>>::vocab_size(self)
+    }
+
+    fn special_tokens(&self) -> PyResult<HashMap<String, i64>> {
+        <Self as PyTokenizer<Gpt2Tokenizer, Gpt2Vocab>>::special_tokens(self)
+    }
+
+    fn __getnewargs__(&self, py: Python<'_>) -> PyObject {
+        self.state.init_args.clone_ref(py)
+    }
+
+    fn __getstate__(&self) -> Vec<String> {
+        self.state.added_tokens.clone()
+    }
+
+    fn __setstate__(&mut self, added_tokens: Vec<String>) -> PyResult<()> {
+        self.add_tokens(added_tokens.iter().map(String::as_str).collect())
+    }
 }
 
 #[pyclass(module = "rust_tokenizers")]
 struct PyRobertaTokenizer {
     tokenizer: RobertaTokenizer,
+    state: PyTokenizerState,
 }
 
 impl PyTokenizer<RobertaTokenizer, RobertaVocab> for PyRobertaTokenizer {
     fn tokenizer(&self) -> &RobertaTokenizer {
         &self.tokenizer
     }
+
+    fn tokenizer_mut(&mut self) -> &mut RobertaTokenizer {
+        &mut self.tokenizer
+    }
+
+    fn pad_token_id(&self) -> Option<i64> {
+        let vocab = Tokenizer::vocab(&self.tokenizer);
+        Some(vocab.token_to_id(vocab.get_pad_value()))
+    }
 }
 
 impl PyMultiThreadTokenizer<RobertaTokenizer, RobertaVocab> for PyRobertaTokenizer {}
@@ -593,6 +1101,7 @@ impl PyMultiThreadTokenizer<RobertaTokenizer, RobertaVocab> for PyRobertaTokeniz
 impl PyRobertaTokenizer {
     #[new]
     fn new(
+        py: Python<'_>,
         vocab_path: String,
         merges_path: String,
         do_lower_case: bool,
@@ -606,6 +1115,9 @@ impl PyRobertaTokenizer {
                 add_prefix_space,
             )
             .unwrap(),
+            state: PyTokenizerState::new(
+                (vocab_path, merges_path, do_lower_case, add_prefix_space).into_py(py),
+            ),
         }
     }
 
@@ -613,18 +1125,20 @@ impl PyRobertaTokenizer {
         <Self as PyTokenizer<RobertaTokenizer, RobertaVocab>>::tokenize(self, text)
     }
 
-    fn tokenize_list(&self, text_list: Vec<&str>) -> PyResult<Vec<Vec<String>>> {
+    fn tokenize_list(&self, py: Python<'_>, text_list: Vec<&str>) -> PyResult<Vec<Vec<String>>> {
         <Self as PyMultiThreadTokenizer<RobertaTokenizer, RobertaVocab>>::tokenize_list(
-            self, text_list,
+            self, py, text_list,
         )
     }
 
+    #[pyo3(signature = (text, max_len, truncation_strategy, stride, truncation_side = "right"))]
     fn encode(
         &self,
         text: &str,
         max_len: usize,
         truncation_strategy: &str,
         stride: usize,
+        truncation_side: &str,
     ) -> PyResult<PyTokenizedInput> {
         <Self as PyTokenizer<RobertaTokenizer, RobertaVocab>>::encode(
             self,
@@ -632,9 +1146,11 @@ impl PyRobertaTokenizer {
             max_len,
             truncation_strategy,
             stride,
+            truncation_side,
         )
     }
 
+    #[pyo3(signature = (text_a, text_b, max_len, truncation_strategy, stride, truncation_side = "right"))]
     fn encode_pair(
         &self,
         text_a: &str,
@@ -642,6 +1158,7 @@ impl PyRobertaTokenizer {
         max_len: usize,
         truncation_strategy: &str,
         stride: usize,
+        truncation_side: &str,
     ) -> PyResult<PyTokenizedInput> {
         <Self as PyTokenizer<RobertaTokenizer, RobertaVocab>>::encode_pair(
             self,
@@ -650,51 +1167,151 @@ impl PyRobertaTokenizer {
             max_len,
             truncation_strategy,
             stride,
+            truncation_side,
         )
     }
 
+    #[pyo3(signature = (text_list, max_len, truncation_strategy, stride, return_numpy = false, pad_token_id = None, truncation_side = "right"))]
     fn encode_list(
         &self,
         text_list: Vec<&str>,
         max_len: usize,
         truncation_strategy: &str,
         stride: usize,
-    ) -> PyResult<Vec<PyTokenizedInput>> {
+        return_numpy: bool,
+        pad_token_id: Option<i64>,
+        truncation_side: &str,
+    ) -> PyResult<PyObject> {
         <Self as PyMultiThreadTokenizer<RobertaTokenizer, RobertaVocab>>::encode_list(
             self,
             text_list,
             max_len,
             truncation_strategy,
             stride,
+            return_numpy,
+            pad_token_id,
+            truncation_side,
         )
     }
 
+    #[pyo3(signature = (text_list, max_len, truncation_strategy, stride, return_numpy = false, pad_token_id = None, truncation_side = "right"))]
     fn encode_pair_list(
         &self,
         text_list: Vec<(&str, &str)>,
         max_len: usize,
         truncation_strategy: &str,
         stride: usize,
-    ) -> PyResult<Vec<PyTokenizedInput>> {
+        return_numpy: bool,
+        pad_token_id: Option<i64>,
+        truncation_side: &str,
+    ) -> PyResult<PyObject> {
         <Self as PyMultiThreadTokenizer<RobertaTokenizer, RobertaVocab>>::encode_pair_list(
             self,
             text_list,
             max_len,
             truncation_strategy,
             stride,
+            return_numpy,
+            pad_token_id,
+            truncation_side,
         )
     }
+
+    fn tokenize_with_offsets(&self, text: &str) -> PyResult<PyTokensWithOffsets> {
+        <Self as PyTokenizer<RobertaTokenizer, RobertaVocab>>::tokenize_with_offsets(self, text)
+    }
+
+    fn convert_tokens_to_ids(&self, tokens: Vec<&str>) -> PyResult<Vec<i64>> {
+        <Self as PyTokenizer<RobertaTokenizer, RobertaVocab>>::convert_tokens_to_ids(self, tokens)
+    }
+
+    fn convert_ids_to_tokens(&self, token_ids: Vec<i64>) -> PyResult<Vec<String>> {
+        <Self as PyTokenizer<RobertaTokenizer, RobertaVocab>>::convert_ids_to_tokens(
+            self, token_ids,
+        )
+    }
+
+    fn decode(
+        &self,
+        token_ids: Vec<i64>,
+        skip_special_tokens: bool,
+        clean_up_tokenization_spaces: bool,
+    ) -> PyResult<String> {
+        <Self as PyTokenizer<RobertaTokenizer, RobertaVocab>>::decode(
+            self,
+            token_ids,
+            skip_special_tokens,
+            clean_up_tokenization_spaces,
+        )
+    }
+
+    fn decode_list(
+        &self,
+        py: Python<'_>,
+        token_ids_list: Vec<Vec<i64>>,
+        skip_special_tokens: bool,
+        clean_up_tokenization_spaces: bool,
+    ) -> PyResult<Vec<String>> {
+        <Self as PyMultiThreadTokenizer<RobertaTokenizer, RobertaVocab>>::decode_list(
+            self,
+            py,
+            token_ids_list,
+            skip_special_tokens,
+            clean_up_tokenization_spaces,
+        )
+    }
+
+    fn add_tokens(&mut self, tokens: Vec<&str>) -> PyResult<()> {
+        self.state
+            .added_tokens
+            .extend(tokens.iter().map(|token| token.to_string()));
+        <Self as PyTokenizer<RobertaTokenizer, RobertaVocab>>::add_tokens(self, tokens)
+    }
+
+    fn vocab_size(&self) -> PyResult<usize> {
+        <Self as PyTokenizer<RobertaTokenizer, RobertaVocab>>::vocab_size(self)
+    }
+
+    fn special_tokens(&self) -> PyResult<HashMap<String, i64>> {
+        <Self as PyTokenizer<RobertaTokenizer, RobertaVocab>>::special_tokens(self)
+    }
+
+    fn __getnewargs__(&self, py: Python<'_>) -> PyObject {
+        self.state.init_args.clone_ref(py)
+    }
+
+    fn __getstate__(&self) -> Vec<String> {
+        self.state.added_tokens.clone()
+    }
+
+    fn __setstate__(&mut self, added_tokens: Vec<String>) -> PyResult<()> {
+        self.add_tokens(added_tokens.iter().map(String::as_str).collect())
+    }
 }
 
 #[pyclass(module = "rust_tokenizers")]
 struct PyOpenAiGptTokenizer {
     tokenizer: OpenAiGptTokenizer,
+    state: PyTokenizerState,
 }
 
 impl PyTokenizer<OpenAiGptTokenizer, OpenAiGptVocab> for PyOpenAiGptTokenizer {
     fn tokenizer(&self) -> &OpenAiGptTokenizer {
         &self.tokenizer
     }
+
+    fn tokenizer_mut(&mut self) -> &mut OpenAiGptTokenizer {
+        &mut self.tokenizer
+    }
+
+    fn pad_token_id(&self) -> Option<i64> {
+        let vocab = Tokenizer::vocab(&self.tokenizer);
+        vocab
+            .special_token_map
+            .pad_token
+            .as_deref()
+            .map(|pad_token| vocab.token_to_id(pad_token))
+    }
 }
 
 impl PyMultiThreadTokenizer<OpenAiGptTokenizer, OpenAiGptVocab> for PyOpenAiGptTokenizer {}
@@ -702,7 +1319,7 @@ impl PyMultiThreadTokenizer<OpenAiGptTokenizer, OpenAiGptVocab> for PyOpenAiGptT
 #[pymethods]
 impl PyOpenAiGptTokenizer {
     #[new]
-    fn new(vocab_path: String, merges_path: String, do_lower_case: bool) -> Self {
+    fn new(py: Python<'_>, vocab_path: String, merges_path: String, do_lower_case: bool) -> Self {
         PyOpenAiGptTokenizer {
             tokenizer: OpenAiGptTokenizer::from_file(
                 vocab_path.as_str(),
@@ -710,6 +1327,7 @@ impl PyOpenAiGptTokenizer {
                 do_lower_case,
             )
             .unwrap(),
+            state: PyTokenizerState::new((vocab_path, merges_path, do_lower_case).into_py(py)),
         }
     }
 
@@ -717,18 +1335,20 @@ impl PyOpenAiGptTokenizer {
         <Self as PyTokenizer<OpenAiGptTokenizer, OpenAiGptVocab>>::tokenize(self, text)
     }
 
-    fn tokenize_list(&self, text_list: Vec<&str>) -> PyResult<Vec<Vec<String>>> {
+    fn tokenize_list(&self, py: Python<'_>, text_list: Vec<&str>) -> PyResult<Vec<Vec<String>>> {
         <Self as PyMultiThreadTokenizer<OpenAiGptTokenizer, OpenAiGptVocab>>::tokenize_list(
-            self, text_list,
+            self, py, text_list,
         )
     }
 
+    #[pyo3(signature = (text, max_len, truncation_strategy, stride, truncation_side = "right"))]
     fn encode(
         &self,
         text: &str,
         max_len: usize,
         truncation_strategy: &str,
         stride: usize,
+        truncation_side: &str,
     ) -> PyResult<PyTokenizedInput> {
         <Self as PyTokenizer<OpenAiGptTokenizer, OpenAiGptVocab>>::encode(
             self,
@@ -736,9 +1356,11 @@ impl PyOpenAiGptTokenizer {
             max_len,
             truncation_strategy,
             stride,
+            truncation_side,
         )
     }
 
+    #[pyo3(signature = (text_a, text_b, max_len, truncation_strategy, stride, truncation_side = "right"))]
     fn encode_pair(
         &self,
         text_a: &str,
@@ -746,6 +1368,7 @@ impl PyOpenAiGptTokenizer {
         max_len: usize,
         truncation_strategy: &str,
         stride: usize,
+        truncation_side: &str,
     ) -> PyResult<PyTokenizedInput> {
         <Self as PyTokenizer<OpenAiGptTokenizer, OpenAiGptVocab>>::encode_pair(
             self,
@@ -754,51 +1377,153 @@ impl PyOpenAiGptTokenizer {
             max_len,
             truncation_strategy,
             stride,
+            truncation_side,
         )
     }
 
+    #[pyo3(signature = (text_list, max_len, truncation_strategy, stride, return_numpy = false, pad_token_id = None, truncation_side = "right"))]
     fn encode_list(
         &self,
         text_list: Vec<&str>,
         max_len: usize,
         truncation_strategy: &str,
         stride: usize,
-    ) -> PyResult<Vec<PyTokenizedInput>> {
+        return_numpy: bool,
+        pad_token_id: Option<i64>,
+        truncation_side: &str,
+    ) -> PyResult<PyObject> {
         <Self as PyMultiThreadTokenizer<OpenAiGptTokenizer, OpenAiGptVocab>>::encode_list(
             self,
             text_list,
             max_len,
             truncation_strategy,
             stride,
+            return_numpy,
+            pad_token_id,
+            truncation_side,
         )
     }
 
+    #[pyo3(signature = (text_list, max_len, truncation_strategy, stride, return_numpy = false, pad_token_id = None, truncation_side = "right"))]
     fn encode_pair_list(
         &self,
         text_list: Vec<(&str, &str)>,
         max_len: usize,
         truncation_strategy: &str,
         stride: usize,
-    ) -> PyResult<Vec<PyTokenizedInput>> {
+        return_numpy: bool,
+        pad_token_id: Option<i64>,
+        truncation_side: &str,
+    ) -> PyResult<PyObject> {
         <Self as PyMultiThreadTokenizer<OpenAiGptTokenizer, OpenAiGptVocab>>::encode_pair_list(
             self,
             text_list,
             max_len,
             truncation_strategy,
             stride,
+            return_numpy,
+            pad_token_id,
+            truncation_side,
+        )
+    }
+
+    fn tokenize_with_offsets(&self, text: &str) -> PyResult<PyTokensWithOffsets> {
+        <Self as PyTokenizer<OpenAiGptTokenizer, OpenAiGptVocab>>::tokenize_with_offsets(self, text)
+    }
+
+    fn convert_tokens_to_ids(&self, tokens: Vec<&str>) -> PyResult<Vec<i64>> {
+        <Self as PyTokenizer<OpenAiGptTokenizer, OpenAiGptVocab>>::convert_tokens_to_ids(
+            self, tokens,
         )
     }
+
+    fn convert_ids_to_tokens(&self, token_ids: Vec<i64>) -> PyResult<Vec<String>> {
+        <Self as PyTokenizer<OpenAiGptTokenizer, OpenAiGptVocab>>::convert_ids_to_tokens(
+            self, token_ids,
+        )
+    }
+
+    fn decode(
+        &self,
+        token_ids: Vec<i64>,
+        skip_special_tokens: bool,
+        clean_up_tokenization_spaces: bool,
+    ) -> PyResult<String> {
+        <Self as PyTokenizer<OpenAiGptTokenizer, OpenAiGptVocab>>::decode(
+            self,
+            token_ids,
+            skip_special_tokens,
+            clean_up_tokenization_spaces,
+        )
+    }
+
+    fn decode_list(
+        &self,
+        py: Python<'_>,
+        token_ids_list: Vec<Vec<i64>>,
+        skip_special_tokens: bool,
+        clean_up_tokenization_spaces: bool,
+    ) -> PyResult<Vec<String>> {
+        <Self as PyMultiThreadTokenizer<OpenAiGptTokenizer, OpenAiGptVocab>>::decode_list(
+            self,
+            py,
+            token_ids_list,
+            skip_special_tokens,
+            clean_up_tokenization_spaces,
+        )
+    }
+
+    fn add_tokens(&mut self, tokens: Vec<&str>) -> PyResult<()> {
+        self.state
+            .added_tokens
+            .extend(tokens.iter().map(|token| token.to_string()));
+        <Self as PyTokenizer<OpenAiGptTokenizer, OpenAiGptVocab>>::add_tokens(self, tokens)
+    }
+
+    fn vocab_size(&self) -> PyResult<usize> {
+        <Self as PyTokenizer<OpenAiGptTokenizer, OpenAiGptVocab>>::vocab_size(self)
+    }
+
+    fn special_tokens(&self) -> PyResult<HashMap<String, i64>> {
+        <Self as PyTokenizer<OpenAiGptTokenizer, OpenAiGptVocab>>::special_tokens(self)
+    }
+
+    fn __getnewargs__(&self, py: Python<'_>) -> PyObject {
+        self.state.init_args.clone_ref(py)
+    }
+
+    fn __getstate__(&self) -> Vec<String> {
+        self.state.added_tokens.clone()
+    }
+
+    fn __setstate__(&mut self, added_tokens: Vec<String>) -> PyResult<()> {
+        self.add_tokens(added_tokens.iter().map(String::as_str).collect())
+    }
 }
 
 #[pyclass(module = "rust_tokenizers")]
 struct PySentencePieceTokenizer {
     tokenizer: SentencePieceTokenizer,
+    state: PyTokenizerState,
 }
 
 impl PyTokenizer<SentencePieceTokenizer, SentencePieceVocab> for PySentencePieceTokenizer {
     fn tokenizer(&self) -> &SentencePieceTokenizer {
         &self.tokenizer
     }
+
+    fn tokenizer_mut(&mut self) -> &mut SentencePieceTokenizer {
+        &mut self.tokenizer
+    }
+
+    fn pad_token_id(&self) -> Option<i64> {
+        let vocab = Tokenizer::vocab(&self.tokenizer);
+        vocab
+            .special_token_map
+            .pad_token
+            .as_deref()
+            .map(|pad_token| vocab.token_to_id(pad_token))
+    }
 }
 
 impl PyMultiThreadTokenizer<SentencePieceTokenizer, SentencePieceVocab>
@@ -809,9 +1534,10 @@ impl PyMultiThreadTokenizer<SentencePieceTokenizer, SentencePieceVocab>
 #[pymethods]
 impl PySentencePieceTokenizer {
     #[new]
-    fn new(path: String, do_lower_case: bool) -> Self {
+    fn new(py: Python<'_>, path: String, do_lower_case: bool) -> Self {
         PySentencePieceTokenizer {
             tokenizer: SentencePieceTokenizer::from_file(path.as_str(), do_lower_case).unwrap(),
+            state: PyTokenizerState::new((path, do_lower_case).into_py(py)),
         }
     }
 
@@ -819,18 +1545,20 @@ impl PySentencePieceTokenizer {
         <Self as PyTokenizer<SentencePieceTokenizer, SentencePieceVocab>>::tokenize(self, text)
     }
 
-    fn tokenize_list(&self, text_list: Vec<&str>) -> PyResult<Vec<Vec<String>>> {
+    fn tokenize_list(&self, py: Python<'_>, text_list: Vec<&str>) -> PyResult<Vec<Vec<String>>> {
         <Self as PyMultiThreadTokenizer<SentencePieceTokenizer, SentencePieceVocab>>::tokenize_list(
-            self, text_list,
+            self, py, text_list,
         )
     }
 
+    #[pyo3(signature = (text, max_len, truncation_strategy, stride, truncation_side = "right"))]
     fn encode(
         &self,
         text: &str,
         max_len: usize,
         truncation_strategy: &str,
         stride: usize,
+        truncation_side: &str,
     ) -> PyResult<PyTokenizedInput> {
         <Self as PyTokenizer<SentencePieceTokenizer, SentencePieceVocab>>::encode(
             self,
@@ -838,9 +1566,11 @@ impl PySentencePieceTokenizer {
             max_len,
             truncation_strategy,
             stride,
+            truncation_side,
         )
     }
 
+    #[pyo3(signature = (text_a, text_b, max_len, truncation_strategy, stride, truncation_side = "right"))]
     fn encode_pair(
         &self,
         text_a: &str,
@@ -848,6 +1578,7 @@ impl PySentencePieceTokenizer {
         max_len: usize,
         truncation_strategy: &str,
         stride: usize,
+        truncation_side: &str,
     ) -> PyResult<PyTokenizedInput> {
         <Self as PyTokenizer<SentencePieceTokenizer, SentencePieceVocab>>::encode_pair(
             self,
@@ -856,45 +1587,151 @@ impl PySentencePieceTokenizer {
             max_len,
             truncation_strategy,
             stride,
+            truncation_side,
         )
     }
 
+    #[pyo3(signature = (text_list, max_len, truncation_strategy, stride, return_numpy = false, pad_token_id = None, truncation_side = "right"))]
     fn encode_list(
         &self,
         text_list: Vec<&str>,
         max_len: usize,
         truncation_strategy: &str,
         stride: usize,
-    ) -> PyResult<Vec<PyTokenizedInput>> {
+        return_numpy: bool,
+        pad_token_id: Option<i64>,
+        truncation_side: &str,
+    ) -> PyResult<PyObject> {
         <Self as PyMultiThreadTokenizer<SentencePieceTokenizer, SentencePieceVocab>>::encode_list(
             self,
             text_list,
             max_len,
             truncation_strategy,
             stride,
+            return_numpy,
+            pad_token_id,
+            truncation_side,
         )
     }
 
+    #[pyo3(signature = (text_list, max_len, truncation_strategy, stride, return_numpy = false, pad_token_id = None, truncation_side = "right"))]
     fn encode_pair_list(
         &self,
         text_list: Vec<(&str, &str)>,
         max_len: usize,
         truncation_strategy: &str,
         stride: usize,
-    ) -> PyResult<Vec<PyTokenizedInput>> {
-        <Self as PyMultiThreadTokenizer<SentencePieceTokenizer, SentencePieceVocab>>::encode_pair_list(self, text_list, max_len, truncation_strategy, stride)
+        return_numpy: bool,
+        pad_token_id: Option<i64>,
+        truncation_side: &str,
+    ) -> PyResult<PyObject> {
+        <Self as PyMultiThreadTokenizer<SentencePieceTokenizer, SentencePieceVocab>>::encode_pair_list(
+            self,
+            text_list,
+            max_len,
+            truncation_strategy,
+            stride,
+            return_numpy,
+            pad_token_id,
+            truncation_side,
+        )
+    }
+
+    fn tokenize_with_offsets(&self, text: &str) -> PyResult<PyTokensWithOffsets> {
+        <Self as PyTokenizer<SentencePieceTokenizer, SentencePieceVocab>>::tokenize_with_offsets(
+            self, text,
+        )
+    }
+
+    fn convert_tokens_to_ids(&self, tokens: Vec<&str>) -> PyResult<Vec<i64>> {
+        <Self as PyTokenizer<SentencePieceTokenizer, SentencePieceVocab>>::convert_tokens_to_ids(
+            self, tokens,
+        )
+    }
+
+    fn convert_ids_to_tokens(&self, token_ids: Vec<i64>) -> PyResult<Vec<String>> {
+        <Self as PyTokenizer<SentencePieceTokenizer, SentencePieceVocab>>::convert_ids_to_tokens(
+            self, token_ids,
+        )
+    }
+
+    fn decode(
+        &self,
+        token_ids: Vec<i64>,
+        skip_special_tokens: bool,
+        clean_up_tokenization_spaces: bool,
+    ) -> PyResult<String> {
+        <Self as PyTokenizer<SentencePieceTokenizer, SentencePieceVocab>>::decode(
+            self,
+            token_ids,
+            skip_special_tokens,
+            clean_up_tokenization_spaces,
+        )
+    }
+
+    fn decode_list(
+        &self,
+        py: Python<'_>,
+        token_ids_list: Vec<Vec<i64>>,
+        skip_special_tokens: bool,
+        clean_up_tokenization_spaces: bool,
+    ) -> PyResult<Vec<String>> {
+        <Self as PyMultiThreadTokenizer<SentencePieceTokenizer, SentencePieceVocab>>::decode_list(
+            self,
+            py,
+            token_ids_list,
+            skip_special_tokens,
+            clean_up_tokenization_spaces,
+        )
+    }
+
+    fn add_tokens(&mut self, tokens: Vec<&str>) -> PyResult<()> {
+        self.state
+            .added_tokens
+            .extend(tokens.iter().map(|token| token.to_string()));
+        <Self as PyTokenizer<SentencePieceTokenizer, SentencePieceVocab>>::add_tokens(self, tokens)
+    }
+
+    fn vocab_size(&self) -> PyResult<usize> {
+        <Self as PyTokenizer<SentencePieceTokenizer, SentencePieceVocab>>::vocab_size(self)
+    }
+
+    fn special_tokens(&self) -> PyResult<HashMap<String, i64>> {
+        <Self as PyTokenizer<SentencePieceTokenizer, SentencePieceVocab>>::special_tokens(self)
+    }
+
+    fn __getnewargs__(&self, py: Python<'_>) -> PyObject {
+        self.state.init_args.clone_ref(py)
+    }
+
+    fn __getstate__(&self) -> Vec<String> {
+        self.state.added_tokens.clone()
+    }
+
+    fn __setstate__(&mut self, added_tokens: Vec<String>) -> PyResult<()> {
+        self.add_tokens(added_tokens.iter().map(String::as_str).collect())
     }
 }
 
 #[pyclass(module = "rust_tokenizers")]
 struct PyAlbertTokenizer {
     tokenizer: AlbertTokenizer,
+    state: PyTokenizerState,
 }
 
 impl PyTokenizer<AlbertTokenizer, AlbertVocab> for PyAlbertTokenizer {
     fn tokenizer(&self) -> &AlbertTokenizer {
         &self.tokenizer
     }
+
+    fn tokenizer_mut(&mut self) -> &mut AlbertTokenizer {
+        &mut self.tokenizer
+    }
+
+    fn pad_token_id(&self) -> Option<i64> {
+        let vocab = Tokenizer::vocab(&self.tokenizer);
+        Some(vocab.token_to_id(vocab.get_pad_value()))
+    }
 }
 
 impl PyMultiThreadTokenizer<AlbertTokenizer, AlbertVocab> for PyAlbertTokenizer {}
@@ -902,10 +1739,11 @@ impl PyMultiThreadTokenizer<AlbertTokenizer, AlbertVocab> for PyAlbertTokenizer
 #[pymethods]
 impl PyAlbertTokenizer {
     #[new]
-    fn new(path: String, do_lower_case: bool, strip_accents: bool) -> Self {
+    fn new(py: Python<'_>, path: String, do_lower_case: bool, strip_accents: bool) -> Self {
         PyAlbertTokenizer {
             tokenizer: AlbertTokenizer::from_file(path.as_str(), do_lower_case, strip_accents)
                 .unwrap(),
+            state: PyTokenizerState::new((path, do_lower_case, strip_accents).into_py(py)),
         }
     }
 
@@ -913,18 +1751,20 @@ impl PyAlbertTokenizer {
         <Self as PyTokenizer<AlbertTokenizer, AlbertVocab>>::tokenize(self, text)
     }
 
-    fn tokenize_list(&self, text_list: Vec<&str>) -> PyResult<Vec<Vec<String>>> {
+    fn tokenize_list(&self, py: Python<'_>, text_list: Vec<&str>) -> PyResult<Vec<Vec<String>>> {
         <Self as PyMultiThreadTokenizer<AlbertTokenizer, AlbertVocab>>::tokenize_list(
-            self, text_list,
+            self, py, text_list,
         )
     }
 
+    #[pyo3(signature = (text, max_len, truncation_strategy, stride, truncation_side = "right"))]
     fn encode(
         &self,
         text: &str,
         max_len: usize,
         truncation_strategy: &str,
         stride: usize,
+        truncation_side: &str,
     ) -> PyResult<PyTokenizedInput> {
         <Self as PyTokenizer<AlbertTokenizer, AlbertVocab>>::encode(
             self,
@@ -932,9 +1772,11 @@ impl PyAlbertTokenizer {
             max_len,
             truncation_strategy,
             stride,
+            truncation_side,
         )
     }
 
+    #[pyo3(signature = (text_a, text_b, max_len, truncation_strategy, stride, truncation_side = "right"))]
     fn encode_pair(
         &self,
         text_a: &str,
@@ -942,6 +1784,7 @@ impl PyAlbertTokenizer {
         max_len: usize,
         truncation_strategy: &str,
         stride: usize,
+        truncation_side: &str,
     ) -> PyResult<PyTokenizedInput> {
         <Self as PyTokenizer<AlbertTokenizer, AlbertVocab>>::encode_pair(
             self,
@@ -950,51 +1793,145 @@ impl PyAlbertTokenizer {
             max_len,
             truncation_strategy,
             stride,
+            truncation_side,
         )
     }
 
+    #[pyo3(signature = (text_list, max_len, truncation_strategy, stride, return_numpy = false, pad_token_id = None, truncation_side = "right"))]
     fn encode_list(
         &self,
         text_list: Vec<&str>,
         max_len: usize,
         truncation_strategy: &str,
         stride: usize,
-    ) -> PyResult<Vec<PyTokenizedInput>> {
+        return_numpy: bool,
+        pad_token_id: Option<i64>,
+        truncation_side: &str,
+    ) -> PyResult<PyObject> {
         <Self as PyMultiThreadTokenizer<AlbertTokenizer, AlbertVocab>>::encode_list(
             self,
             text_list,
             max_len,
             truncation_strategy,
             stride,
+            return_numpy,
+            pad_token_id,
+            truncation_side,
         )
     }
 
+    #[pyo3(signature = (text_list, max_len, truncation_strategy, stride, return_numpy = false, pad_token_id = None, truncation_side = "right"))]
     fn encode_pair_list(
         &self,
         text_list: Vec<(&str, &str)>,
         max_len: usize,
         truncation_strategy: &str,
         stride: usize,
-    ) -> PyResult<Vec<PyTokenizedInput>> {
+        return_numpy: bool,
+        pad_token_id: Option<i64>,
+        truncation_side: &str,
+    ) -> PyResult<PyObject> {
         <Self as PyMultiThreadTokenizer<AlbertTokenizer, AlbertVocab>>::encode_pair_list(
             self,
             text_list,
             max_len,
             truncation_strategy,
             stride,
+            return_numpy,
+            pad_token_id,
+            truncation_side,
+        )
+    }
+
+    fn tokenize_with_offsets(&self, text: &str) -> PyResult<PyTokensWithOffsets> {
+        <Self as PyTokenizer<AlbertTokenizer, AlbertVocab>>::tokenize_with_offsets(self, text)
+    }
+
+    fn convert_tokens_to_ids(&self, tokens: Vec<&str>) -> PyResult<Vec<i64>> {
+        <Self as PyTokenizer<AlbertTokenizer, AlbertVocab>>::convert_tokens_to_ids(self, tokens)
+    }
+
+    fn convert_ids_to_tokens(&self, token_ids: Vec<i64>) -> PyResult<Vec<String>> {
+        <Self as PyTokenizer<AlbertTokenizer, AlbertVocab>>::convert_ids_to_tokens(self, token_ids)
+    }
+
+    fn decode(
+        &self,
+        token_ids: Vec<i64>,
+        skip_special_tokens: bool,
+        clean_up_tokenization_spaces: bool,
+    ) -> PyResult<String> {
+        <Self as PyTokenizer<AlbertTokenizer, AlbertVocab>>::decode(
+            self,
+            token_ids,
+            skip_special_tokens,
+            clean_up_tokenization_spaces,
         )
     }
+
+    fn decode_list(
+        &self,
+        py: Python<'_>,
+        token_ids_list: Vec<Vec<i64>>,
+        skip_special_tokens: bool,
+        clean_up_tokenization_spaces: bool,
+    ) -> PyResult<Vec<String>> {
+        <Self as PyMultiThreadTokenizer<AlbertTokenizer, AlbertVocab>>::decode_list(
+            self,
+            py,
+            token_ids_list,
+            skip_special_tokens,
+            clean_up_tokenization_spaces,
+        )
+    }
+
+    fn add_tokens(&mut self, tokens: Vec<&str>) -> PyResult<()> {
+        self.state
+            .added_tokens
+            .extend(tokens.iter().map(|token| token.to_string()));
+        <Self as PyTokenizer<AlbertTokenizer, AlbertVocab>>::add_tokens(self, tokens)
+    }
+
+    fn vocab_size(&self) -> PyResult<usize> {
+        <Self as PyTokenizer<AlbertTokenizer, AlbertVocab>>::vocab_size(self)
+    }
+
+    fn special_tokens(&self) -> PyResult<HashMap<String, i64>> {
+        <Self as PyTokenizer<AlbertTokenizer, AlbertVocab>>::special_tokens(self)
+    }
+
+    fn __getnewargs__(&self, py: Python<'_>) -> PyObject {
+        self.state.init_args.clone_ref(py)
+    }
+
+    fn __getstate__(&self) -> Vec<String> {
+        self.state.added_tokens.clone()
+    }
+
+    fn __setstate__(&mut self, added_tokens: Vec<String>) -> PyResult<()> {
+        self.add_tokens(added_tokens.iter().map(String::as_str).collect())
+    }
 }
 
 #[pyclass(module = "rust_tokenizers")]
 struct PyXLNetTokenizer {
     tokenizer: XLNetTokenizer,
+    state: PyTokenizerState,
 }
 
 impl PyTokenizer<XLNetTokenizer, XLNetVocab> for PyXLNetTokenizer {
     fn tokenizer(&self) -> &XLNetTokenizer {
         &self.tokenizer
     }
+
+    fn tokenizer_mut(&mut self) -> &mut XLNetTokenizer {
+        &mut self.tokenizer
+    }
+
+    fn pad_token_id(&self) -> Option<i64> {
+        let vocab = Tokenizer::vocab(&self.tokenizer);
+        Some(vocab.token_to_id(vocab.get_pad_value()))
+    }
 }
 
 impl PyMultiThreadTokenizer<XLNetTokenizer, XLNetVocab> for PyXLNetTokenizer {}
@@ -1002,10 +1939,11 @@ impl PyMultiThreadTokenizer<XLNetTokenizer, XLNetVocab> for PyXLNetTokenizer {}
 #[pymethods]
 impl PyXLNetTokenizer {
     #[new]
-    fn new(path: String, do_lower_case: bool, strip_accents: bool) -> Self {
+    fn new(py: Python<'_>, path: String, do_lower_case: bool, strip_accents: bool) -> Self {
         PyXLNetTokenizer {
             tokenizer: XLNetTokenizer::from_file(path.as_str(), do_lower_case, strip_accents)
                 .unwrap(),
+            state: PyTokenizerState::new((path, do_lower_case, strip_accents).into_py(py)),
         }
     }
 
@@ -1013,16 +1951,20 @@ impl PyXLNetTokenizer {
         <Self as PyTokenizer<XLNetTokenizer, XLNetVocab>>::tokenize(self, text)
     }
 
-    fn tokenize_list(&self, text_list: Vec<&str>) -> PyResult<Vec<Vec<String>>> {
-        <Self as PyMultiThreadTokenizer<XLNetTokenizer, XLNetVocab>>::tokenize_list(self, text_list)
+    fn tokenize_list(&self, py: Python<'_>, text_list: Vec<&str>) -> PyResult<Vec<Vec<String>>> {
+        <Self as PyMultiThreadTokenizer<XLNetTokenizer, XLNetVocab>>::tokenize_list(
+            self, py, text_list,
+        )
     }
 
+    #[pyo3(signature = (text, max_len, truncation_strategy, stride, truncation_side = "right"))]
     fn encode(
         &self,
         text: &str,
         max_len: usize,
         truncation_strategy: &str,
         stride: usize,
+        truncation_side: &str,
     ) -> PyResult<PyTokenizedInput> {
         <Self as PyTokenizer<XLNetTokenizer, XLNetVocab>>::encode(
             self,
@@ -1030,9 +1972,11 @@ impl PyXLNetTokenizer {
             max_len,
             truncation_strategy,
             stride,
+            truncation_side,
         )
     }
 
+    #[pyo3(signature = (text_a, text_b, max_len, truncation_strategy, stride, truncation_side = "right"))]
     fn encode_pair(
         &self,
         text_a: &str,
@@ -1040,6 +1984,7 @@ impl PyXLNetTokenizer {
         max_len: usize,
         truncation_strategy: &str,
         stride: usize,
+        truncation_side: &str,
     ) -> PyResult<PyTokenizedInput> {
         <Self as PyTokenizer<XLNetTokenizer, XLNetVocab>>::encode_pair(
             self,
@@ -1048,51 +1993,145 @@ impl PyXLNetTokenizer {
             max_len,
             truncation_strategy,
             stride,
+            truncation_side,
         )
     }
 
+    #[pyo3(signature = (text_list, max_len, truncation_strategy, stride, return_numpy = false, pad_token_id = None, truncation_side = "right"))]
     fn encode_list(
         &self,
         text_list: Vec<&str>,
         max_len: usize,
         truncation_strategy: &str,
         stride: usize,
-    ) -> PyResult<Vec<PyTokenizedInput>> {
+        return_numpy: bool,
+        pad_token_id: Option<i64>,
+        truncation_side: &str,
+    ) -> PyResult<PyObject> {
         <Self as PyMultiThreadTokenizer<XLNetTokenizer, XLNetVocab>>::encode_list(
             self,
             text_list,
             max_len,
             truncation_strategy,
             stride,
+            return_numpy,
+            pad_token_id,
+            truncation_side,
         )
     }
 
+    #[pyo3(signature = (text_list, max_len, truncation_strategy, stride, return_numpy = false, pad_token_id = None, truncation_side = "right"))]
     fn encode_pair_list(
         &self,
         text_list: Vec<(&str, &str)>,
         max_len: usize,
         truncation_strategy: &str,
         stride: usize,
-    ) -> PyResult<Vec<PyTokenizedInput>> {
+        return_numpy: bool,
+        pad_token_id: Option<i64>,
+        truncation_side: &str,
+    ) -> PyResult<PyObject> {
         <Self as PyMultiThreadTokenizer<XLNetTokenizer, XLNetVocab>>::encode_pair_list(
             self,
             text_list,
             max_len,
             truncation_strategy,
             stride,
+            return_numpy,
+            pad_token_id,
+            truncation_side,
+        )
+    }
+
+    fn tokenize_with_offsets(&self, text: &str) -> PyResult<PyTokensWithOffsets> {
+        <Self as PyTokenizer<XLNetTokenizer, XLNetVocab>>::tokenize_with_offsets(self, text)
+    }
+
+    fn convert_tokens_to_ids(&self, tokens: Vec<&str>) -> PyResult<Vec<i64>> {
+        <Self as PyTokenizer<XLNetTokenizer, XLNetVocab>>::convert_tokens_to_ids(self, tokens)
+    }
+
+    fn convert_ids_to_tokens(&self, token_ids: Vec<i64>) -> PyResult<Vec<String>> {
+        <Self as PyTokenizer<XLNetTokenizer, XLNetVocab>>::convert_ids_to_tokens(self, token_ids)
+    }
+
+    fn decode(
+        &self,
+        token_ids: Vec<i64>,
+        skip_special_tokens: bool,
+        clean_up_tokenization_spaces: bool,
+    ) -> PyResult<String> {
+        <Self as PyTokenizer<XLNetTokenizer, XLNetVocab>>::decode(
+            self,
+            token_ids,
+            skip_special_tokens,
+            clean_up_tokenization_spaces,
+        )
+    }
+
+    fn decode_list(
+        &self,
+        py: Python<'_>,
+        token_ids_list: Vec<Vec<i64>>,
+        skip_special_tokens: bool,
+        clean_up_tokenization_spaces: bool,
+    ) -> PyResult<Vec<String>> {
+        <Self as PyMultiThreadTokenizer<XLNetTokenizer, XLNetVocab>>::decode_list(
+            self,
+            py,
+            token_ids_list,
+            skip_special_tokens,
+            clean_up_tokenization_spaces,
         )
     }
+
+    fn add_tokens(&mut self, tokens: Vec<&str>) -> PyResult<()> {
+        self.state
+            .added_tokens
+            .extend(tokens.iter().map(|token| token.to_string()));
+        <Self as PyTokenizer<XLNetTokenizer, XLNetVocab>>::add_tokens(self, tokens)
+    }
+
+    fn vocab_size(&self) -> PyResult<usize> {
+        <Self as PyTokenizer<XLNetTokenizer, XLNetVocab>>::vocab_size(self)
+    }
+
+    fn special_tokens(&self) -> PyResult<HashMap<String, i64>> {
+        <Self as PyTokenizer<XLNetTokenizer, XLNetVocab>>::special_tokens(self)
+    }
+
+    fn __getnewargs__(&self, py: Python<'_>) -> PyObject {
+        self.state.init_args.clone_ref(py)
+    }
+
+    fn __getstate__(&self) -> Vec<String> {
+        self.state.added_tokens.clone()
+    }
+
+    fn __setstate__(&mut self, added_tokens: Vec<String>) -> PyResult<()> {
+        self.add_tokens(added_tokens.iter().map(String::as_str).collect())
+    }
 }
 
 #[pyclass(module = "rust_tokenizers")]
 struct PyT5Tokenizer {
     tokenizer: T5Tokenizer,
+    state: PyTokenizerState,
 }
 
 impl PyTokenizer<T5Tokenizer, T5Vocab> for PyT5Tokenizer {
     fn tokenizer(&self) -> &T5Tokenizer {
         &self.tokenizer
     }
+
+    fn tokenizer_mut(&mut self) -> &mut T5Tokenizer {
+        &mut self.tokenizer
+    }
+
+    fn pad_token_id(&self) -> Option<i64> {
+        let vocab = Tokenizer::vocab(&self.tokenizer);
+        Some(vocab.token_to_id(vocab.get_pad_value()))
+    }
 }
 
 impl PyMultiThreadTokenizer<T5Tokenizer, T5Vocab> for PyT5Tokenizer {}
@@ -1100,9 +2139,10 @@ impl PyMultiThreadTokenizer<T5Tokenizer, T5Vocab> for PyT5Tokenizer {}
 #[pymethods]
 impl PyT5Tokenizer {
     #[new]
-    fn new(path: String, do_lower_case: bool) -> Self {
+    fn new(py: Python<'_>, path: String, do_lower_case: bool) -> Self {
         PyT5Tokenizer {
             tokenizer: T5Tokenizer::from_file(path.as_str(), do_lower_case).unwrap(),
+            state: PyTokenizerState::new((path, do_lower_case).into_py(py)),
         }
     }
 
@@ -1110,16 +2150,18 @@ impl PyT5Tokenizer {
         <Self as PyTokenizer<T5Tokenizer, T5Vocab>>::tokenize(self, text)
     }
 
-    fn tokenize_list(&self, text_list: Vec<&str>) -> PyResult<Vec<Vec<String>>> {
-        <Self as PyMultiThreadTokenizer<T5Tokenizer, T5Vocab>>::tokenize_list(self, text_list)
+    fn tokenize_list(&self, py: Python<'_>, text_list: Vec<&str>) -> PyResult<Vec<Vec<String>>> {
+        <Self as PyMultiThreadTokenizer<T5Tokenizer, T5Vocab>>::tokenize_list(self, py, text_list)
     }
 
+    #[pyo3(signature = (text, max_len, truncation_strategy, stride, truncation_side = "right"))]
     fn encode(
         &self,
         text: &str,
         max_len: usize,
         truncation_strategy: &str,
         stride: usize,
+        truncation_side: &str,
     ) -> PyResult<PyTokenizedInput> {
         <Self as PyTokenizer<T5Tokenizer, T5Vocab>>::encode(
             self,
@@ -1127,9 +2169,11 @@ impl PyT5Tokenizer {
             max_len,
             truncation_strategy,
             stride,
+            truncation_side,
         )
     }
 
+    #[pyo3(signature = (text_a, text_b, max_len, truncation_strategy, stride, truncation_side = "right"))]
     fn encode_pair(
         &self,
         text_a: &str,
@@ -1137,6 +2181,7 @@ impl PyT5Tokenizer {
         max_len: usize,
         truncation_strategy: &str,
         stride: usize,
+        truncation_side: &str,
     ) -> PyResult<PyTokenizedInput> {
         <Self as PyTokenizer<T5Tokenizer, T5Vocab>>::encode_pair(
             self,
@@ -1145,51 +2190,145 @@ impl PyT5Tokenizer {
             max_len,
             truncation_strategy,
             stride,
+            truncation_side,
         )
     }
 
+    #[pyo3(signature = (text_list, max_len, truncation_strategy, stride, return_numpy = false, pad_token_id = None, truncation_side = "right"))]
     fn encode_list(
         &self,
         text_list: Vec<&str>,
         max_len: usize,
         truncation_strategy: &str,
         stride: usize,
-    ) -> PyResult<Vec<PyTokenizedInput>> {
+        return_numpy: bool,
+        pad_token_id: Option<i64>,
+        truncation_side: &str,
+    ) -> PyResult<PyObject> {
         <Self as PyMultiThreadTokenizer<T5Tokenizer, T5Vocab>>::encode_list(
             self,
             text_list,
             max_len,
             truncation_strategy,
             stride,
+            return_numpy,
+            pad_token_id,
+            truncation_side,
         )
     }
 
+    #[pyo3(signature = (text_list, max_len, truncation_strategy, stride, return_numpy = false, pad_token_id = None, truncation_side = "right"))]
     fn encode_pair_list(
         &self,
         text_list: Vec<(&str, &str)>,
         max_len: usize,
         truncation_strategy: &str,
         stride: usize,
-    ) -> PyResult<Vec<PyTokenizedInput>> {
+        return_numpy: bool,
+        pad_token_id: Option<i64>,
+        truncation_side: &str,
+    ) -> PyResult<PyObject> {
         <Self as PyMultiThreadTokenizer<T5Tokenizer, T5Vocab>>::encode_pair_list(
             self,
             text_list,
             max_len,
             truncation_strategy,
             stride,
+            return_numpy,
+            pad_token_id,
+            truncation_side,
+        )
+    }
+
+    fn tokenize_with_offsets(&self, text: &str) -> PyResult<PyTokensWithOffsets> {
+        <Self as PyTokenizer<T5Tokenizer, T5Vocab>>::tokenize_with_offsets(self, text)
+    }
+
+    fn convert_tokens_to_ids(&self, tokens: Vec<&str>) -> PyResult<Vec<i64>> {
+        <Self as PyTokenizer<T5Tokenizer, T5Vocab>>::convert_tokens_to_ids(self, tokens)
+    }
+
+    fn convert_ids_to_tokens(&self, token_ids: Vec<i64>) -> PyResult<Vec<String>> {
+        <Self as PyTokenizer<T5Tokenizer, T5Vocab>>::convert_ids_to_tokens(self, token_ids)
+    }
+
+    fn decode(
+        &self,
+        token_ids: Vec<i64>,
+        skip_special_tokens: bool,
+        clean_up_tokenization_spaces: bool,
+    ) -> PyResult<String> {
+        <Self as PyTokenizer<T5Tokenizer, T5Vocab>>::decode(
+            self,
+            token_ids,
+            skip_special_tokens,
+            clean_up_tokenization_spaces,
+        )
+    }
+
+    fn decode_list(
+        &self,
+        py: Python<'_>,
+        token_ids_list: Vec<Vec<i64>>,
+        skip_special_tokens: bool,
+        clean_up_tokenization_spaces: bool,
+    ) -> PyResult<Vec<String>> {
+        <Self as PyMultiThreadTokenizer<T5Tokenizer, T5Vocab>>::decode_list(
+            self,
+            py,
+            token_ids_list,
+            skip_special_tokens,
+            clean_up_tokenization_spaces,
         )
     }
+
+    fn add_tokens(&mut self, tokens: Vec<&str>) -> PyResult<()> {
+        self.state
+            .added_tokens
+            .extend(tokens.iter().map(|token| token.to_string()));
+        <Self as PyTokenizer<T5Tokenizer, T5Vocab>>::add_tokens(self, tokens)
+    }
+
+    fn vocab_size(&self) -> PyResult<usize> {
+        <Self as PyTokenizer<T5Tokenizer, T5Vocab>>::vocab_size(self)
+    }
+
+    fn special_tokens(&self) -> PyResult<HashMap<String, i64>> {
+        <Self as PyTokenizer<T5Tokenizer, T5Vocab>>::special_tokens(self)
+    }
+
+    fn __getnewargs__(&self, py: Python<'_>) -> PyObject {
+        self.state.init_args.clone_ref(py)
+    }
+
+    fn __getstate__(&self) -> Vec<String> {
+        self.state.added_tokens.clone()
+    }
+
+    fn __setstate__(&mut self, added_tokens: Vec<String>) -> PyResult<()> {
+        self.add_tokens(added_tokens.iter().map(String::as_str).collect())
+    }
 }
 
 #[pyclass(module = "rust_tokenizers")]
 struct PyXLMRobertaTokenizer {
     tokenizer: XLMRobertaTokenizer,
+    state: PyTokenizerState,
 }
 
 impl PyTokenizer<XLMRobertaTokenizer, XLMRobertaVocab> for PyXLMRobertaTokenizer {
     fn tokenizer(&self) -> &XLMRobertaTokenizer {
         &self.tokenizer
     }
+
+    fn tokenizer_mut(&mut self) -> &mut XLMRobertaTokenizer {
+        &mut self.tokenizer
+    }
+
+    fn pad_token_id(&self) -> Option<i64> {
+        let vocab = Tokenizer::vocab(&self.tokenizer);
+        Some(vocab.token_to_id(vocab.get_pad_value()))
+    }
 }
 
 impl PyMultiThreadTokenizer<XLMRobertaTokenizer, XLMRobertaVocab> for PyXLMRobertaTokenizer {}
@@ -1197,9 +2336,10 @@ impl PyMultiThreadTokenizer<XLMRobertaTokenizer, XLMRobertaVocab> for PyXLMRober
 #[pymethods]
 impl PyXLMRobertaTokenizer {
     #[new]
-    fn new(path: String, do_lower_case: bool) -> Self {
+    fn new(py: Python<'_>, path: String, do_lower_case: bool) -> Self {
         PyXLMRobertaTokenizer {
             tokenizer: XLMRobertaTokenizer::from_file(path.as_str(), do_lower_case).unwrap(),
+            state: PyTokenizerState::new((path, do_lower_case).into_py(py)),
         }
     }
 
@@ -1207,18 +2347,20 @@ impl PyXLMRobertaTokenizer {
         <Self as PyTokenizer<XLMRobertaTokenizer, XLMRobertaVocab>>::tokenize(self, text)
     }
 
-    fn tokenize_list(&self, text_list: Vec<&str>) -> PyResult<Vec<Vec<String>>> {
+    fn tokenize_list(&self, py: Python<'_>, text_list: Vec<&str>) -> PyResult<Vec<Vec<String>>> {
         <Self as PyMultiThreadTokenizer<XLMRobertaTokenizer, XLMRobertaVocab>>::tokenize_list(
-            self, text_list,
+            self, py, text_list,
         )
     }
 
+    #[pyo3(signature = (text, max_len, truncation_strategy, stride, truncation_side = "right"))]
     fn encode(
         &self,
         text: &str,
         max_len: usize,
         truncation_strategy: &str,
         stride: usize,
+        truncation_side: &str,
     ) -> PyResult<PyTokenizedInput> {
         <Self as PyTokenizer<XLMRobertaTokenizer, XLMRobertaVocab>>::encode(
             self,
@@ -1226,9 +2368,11 @@ impl PyXLMRobertaTokenizer {
             max_len,
             truncation_strategy,
             stride,
+            truncation_side,
         )
     }
 
+    #[pyo3(signature = (text_a, text_b, max_len, truncation_strategy, stride, truncation_side = "right"))]
     fn encode_pair(
         &self,
         text_a: &str,
@@ -1236,6 +2380,7 @@ impl PyXLMRobertaTokenizer {
         max_len: usize,
         truncation_strategy: &str,
         stride: usize,
+        truncation_side: &str,
     ) -> PyResult<PyTokenizedInput> {
         <Self as PyTokenizer<XLMRobertaTokenizer, XLMRobertaVocab>>::encode_pair(
             self,
@@ -1244,51 +2389,155 @@ impl PyXLMRobertaTokenizer {
             max_len,
             truncation_strategy,
             stride,
+            truncation_side,
         )
     }
 
+    #[pyo3(signature = (text_list, max_len, truncation_strategy, stride, return_numpy = false, pad_token_id = None, truncation_side = "right"))]
     fn encode_list(
         &self,
         text_list: Vec<&str>,
         max_len: usize,
         truncation_strategy: &str,
         stride: usize,
-    ) -> PyResult<Vec<PyTokenizedInput>> {
+        return_numpy: bool,
+        pad_token_id: Option<i64>,
+        truncation_side: &str,
+    ) -> PyResult<PyObject> {
         <Self as PyMultiThreadTokenizer<XLMRobertaTokenizer, XLMRobertaVocab>>::encode_list(
             self,
             text_list,
             max_len,
             truncation_strategy,
             stride,
+            return_numpy,
+            pad_token_id,
+            truncation_side,
         )
     }
 
+    #[pyo3(signature = (text_list, max_len, truncation_strategy, stride, return_numpy = false, pad_token_id = None, truncation_side = "right"))]
     fn encode_pair_list(
         &self,
         text_list: Vec<(&str, &str)>,
         max_len: usize,
         truncation_strategy: &str,
         stride: usize,
-    ) -> PyResult<Vec<PyTokenizedInput>> {
+        return_numpy: bool,
+        pad_token_id: Option<i64>,
+        truncation_side: &str,
+    ) -> PyResult<PyObject> {
         <Self as PyMultiThreadTokenizer<XLMRobertaTokenizer, XLMRobertaVocab>>::encode_pair_list(
             self,
             text_list,
             max_len,
             truncation_strategy,
             stride,
+            return_numpy,
+            pad_token_id,
+            truncation_side,
+        )
+    }
+
+    fn tokenize_with_offsets(&self, text: &str) -> PyResult<PyTokensWithOffsets> {
+        <Self as PyTokenizer<XLMRobertaTokenizer, XLMRobertaVocab>>::tokenize_with_offsets(
+            self, text,
+        )
+    }
+
+    fn convert_tokens_to_ids(&self, tokens: Vec<&str>) -> PyResult<Vec<i64>> {
+        <Self as PyTokenizer<XLMRobertaTokenizer, XLMRobertaVocab>>::convert_tokens_to_ids(
+            self, tokens,
+        )
+    }
+
+    fn convert_ids_to_tokens(&self, token_ids: Vec<i64>) -> PyResult<Vec<String>> {
+        <Self as PyTokenizer<XLMRobertaTokenizer, XLMRobertaVocab>>::convert_ids_to_tokens(
+            self, token_ids,
+        )
+    }
+
+    fn decode(
+        &self,
+        token_ids: Vec<i64>,
+        skip_special_tokens: bool,
+        clean_up_tokenization_spaces: bool,
+    ) -> PyResult<String> {
+        <Self as PyTokenizer<XLMRobertaTokenizer, XLMRobertaVocab>>::decode(
+            self,
+            token_ids,
+            skip_special_tokens,
+            clean_up_tokenization_spaces,
+        )
+    }
+
+    fn decode_list(
+        &self,
+        py: Python<'_>,
+        token_ids_list: Vec<Vec<i64>>,
+        skip_special_tokens: bool,
+        clean_up_tokenization_spaces: bool,
+    ) -> PyResult<Vec<String>> {
+        <Self as PyMultiThreadTokenizer<XLMRobertaTokenizer, XLMRobertaVocab>>::decode_list(
+            self,
+            py,
+            token_ids_list,
+            skip_special_tokens,
+            clean_up_tokenization_spaces,
         )
     }
+
+    fn add_tokens(&mut self, tokens: Vec<&str>) -> PyResult<()> {
+        self.state
+            .added_tokens
+            .extend(tokens.iter().map(|token| token.to_string()));
+        <Self as PyTokenizer<XLMRobertaTokenizer, XLMRobertaVocab>>::add_tokens(self, tokens)
+    }
+
+    fn vocab_size(&self) -> PyResult<usize> {
+        <Self as PyTokenizer<XLMRobertaTokenizer, XLMRobertaVocab>>::vocab_size(self)
+    }
+
+    fn special_tokens(&self) -> PyResult<HashMap<String, i64>> {
+        <Self as PyTokenizer<XLMRobertaTokenizer, XLMRobertaVocab>>::special_tokens(self)
+    }
+
+    fn __getnewargs__(&self, py: Python<'_>) -> PyObject {
+        self.state.init_args.clone_ref(py)
+    }
+
+    fn __getstate__(&self) -> Vec<String> {
+        self.state.added_tokens.clone()
+    }
+
+    fn __setstate__(&mut self, added_tokens: Vec<String>) -> PyResult<()> {
+        self.add_tokens(added_tokens.iter().map(String::as_str).collect())
+    }
 }
 
 #[pyclass(module = "rust_tokenizers")]
 struct PyReformerTokenizer {
     tokenizer: ReformerTokenizer,
+    state: PyTokenizerState,
 }
 
 impl PyTokenizer<ReformerTokenizer, ReformerVocab> for PyReformerTokenizer {
     fn tokenizer(&self) -> &ReformerTokenizer {
         &self.tokenizer
     }
+
+    fn tokenizer_mut(&mut self) -> &mut ReformerTokenizer {
+        &mut self.tokenizer
+    }
+
+    fn pad_token_id(&self) -> Option<i64> {
+        let vocab = Tokenizer::vocab(&self.tokenizer);
+        vocab
+            .special_token_map
+            .pad_token
+            .as_deref()
+            .map(|pad_token| vocab.token_to_id(pad_token))
+    }
 }
 
 impl PyMultiThreadTokenizer<ReformerTokenizer, ReformerVocab> for PyReformerTokenizer {}
@@ -1296,9 +2545,10 @@ impl PyMultiThreadTokenizer<ReformerTokenizer, ReformerVocab> for PyReformerToke
 #[pymethods]
 impl PyReformerTokenizer {
     #[new]
-    fn new(path: String, do_lower_case: bool) -> Self {
+    fn new(py: Python<'_>, path: String, do_lower_case: bool) -> Self {
         PyReformerTokenizer {
             tokenizer: ReformerTokenizer::from_file(path.as_str(), do_lower_case).unwrap(),
+            state: PyTokenizerState::new((path, do_lower_case).into_py(py)),
         }
     }
 
@@ -1306,18 +2556,20 @@ impl PyReformerTokenizer {
         <Self as PyTokenizer<ReformerTokenizer, ReformerVocab>>::tokenize(self, text)
     }
 
-    fn tokenize_list(&self, text_list: Vec<&str>) -> PyResult<Vec<Vec<String>>> {
+    fn tokenize_list(&self, py: Python<'_>, text_list: Vec<&str>) -> PyResult<Vec<Vec<String>>> {
         <Self as PyMultiThreadTokenizer<ReformerTokenizer, ReformerVocab>>::tokenize_list(
-            self, text_list,
+            self, py, text_list,
         )
     }
 
+    #[pyo3(signature = (text, max_len, truncation_strategy, stride, truncation_side = "right"))]
     fn encode(
         &self,
         text: &str,
         max_len: usize,
         truncation_strategy: &str,
         stride: usize,
+        truncation_side: &str,
     ) -> PyResult<PyTokenizedInput> {
         <Self as PyTokenizer<ReformerTokenizer, ReformerVocab>>::encode(
             self,
@@ -1325,9 +2577,11 @@ impl PyReformerTokenizer {
             max_len,
             truncation_strategy,
             stride,
+            truncation_side,
         )
     }
 
+    #[pyo3(signature = (text_a, text_b, max_len, truncation_strategy, stride, truncation_side = "right"))]
     fn encode_pair(
         &self,
         text_a: &str,
@@ -1335,6 +2589,7 @@ impl PyReformerTokenizer {
         max_len: usize,
         truncation_strategy: &str,
         stride: usize,
+        truncation_side: &str,
     ) -> PyResult<PyTokenizedInput> {
         <Self as PyTokenizer<ReformerTokenizer, ReformerVocab>>::encode_pair(
             self,
@@ -1343,51 +2598,147 @@ impl PyReformerTokenizer {
             max_len,
             truncation_strategy,
             stride,
+            truncation_side,
         )
     }
 
+    #[pyo3(signature = (text_list, max_len, truncation_strategy, stride, return_numpy = false, pad_token_id = None, truncation_side = "right"))]
     fn encode_list(
         &self,
         text_list: Vec<&str>,
         max_len: usize,
         truncation_strategy: &str,
         stride: usize,
-    ) -> PyResult<Vec<PyTokenizedInput>> {
+        return_numpy: bool,
+        pad_token_id: Option<i64>,
+        truncation_side: &str,
+    ) -> PyResult<PyObject> {
         <Self as PyMultiThreadTokenizer<ReformerTokenizer, ReformerVocab>>::encode_list(
             self,
             text_list,
             max_len,
             truncation_strategy,
             stride,
+            return_numpy,
+            pad_token_id,
+            truncation_side,
         )
     }
 
+    #[pyo3(signature = (text_list, max_len, truncation_strategy, stride, return_numpy = false, pad_token_id = None, truncation_side = "right"))]
     fn encode_pair_list(
         &self,
         text_list: Vec<(&str, &str)>,
         max_len: usize,
         truncation_strategy: &str,
         stride: usize,
-    ) -> PyResult<Vec<PyTokenizedInput>> {
+        return_numpy: bool,
+        pad_token_id: Option<i64>,
+        truncation_side: &str,
+    ) -> PyResult<PyObject> {
         <Self as PyMultiThreadTokenizer<ReformerTokenizer, ReformerVocab>>::encode_pair_list(
             self,
             text_list,
             max_len,
             truncation_strategy,
             stride,
+            return_numpy,
+            pad_token_id,
+            truncation_side,
+        )
+    }
+
+    fn tokenize_with_offsets(&self, text: &str) -> PyResult<PyTokensWithOffsets> {
+        <Self as PyTokenizer<ReformerTokenizer, ReformerVocab>>::tokenize_with_offsets(self, text)
+    }
+
+    fn convert_tokens_to_ids(&self, tokens: Vec<&str>) -> PyResult<Vec<i64>> {
+        <Self as PyTokenizer<ReformerTokenizer, ReformerVocab>>::convert_tokens_to_ids(self, tokens)
+    }
+
+    fn convert_ids_to_tokens(&self, token_ids: Vec<i64>) -> PyResult<Vec<String>> {
+        <Self as PyTokenizer<ReformerTokenizer, ReformerVocab>>::convert_ids_to_tokens(
+            self, token_ids,
+        )
+    }
+
+    fn decode(
+        &self,
+        token_ids: Vec<i64>,
+        skip_special_tokens: bool,
+        clean_up_tokenization_spaces: bool,
+    ) -> PyResult<String> {
+        <Self as PyTokenizer<ReformerTokenizer, ReformerVocab>>::decode(
+            self,
+            token_ids,
+            skip_special_tokens,
+            clean_up_tokenization_spaces,
+        )
+    }
+
+    fn decode_list(
+        &self,
+        py: Python<'_>,
+        token_ids_list: Vec<Vec<i64>>,
+        skip_special_tokens: bool,
+        clean_up_tokenization_spaces: bool,
+    ) -> PyResult<Vec<String>> {
+        <Self as PyMultiThreadTokenizer<ReformerTokenizer, ReformerVocab>>::decode_list(
+            self,
+            py,
+            token_ids_list,
+            skip_special_tokens,
+            clean_up_tokenization_spaces,
         )
     }
+
+    fn add_tokens(&mut self, tokens: Vec<&str>) -> PyResult<()> {
+        self.state
+            .added_tokens
+            .extend(tokens.iter().map(|token| token.to_string()));
+        <Self as PyTokenizer<ReformerTokenizer, ReformerVocab>>::add_tokens(self, tokens)
+    }
+
+    fn vocab_size(&self) -> PyResult<usize> {
+        <Self as PyTokenizer<ReformerTokenizer, ReformerVocab>>::vocab_size(self)
+    }
+
+    fn special_tokens(&self) -> PyResult<HashMap<String, i64>> {
+        <Self as PyTokenizer<ReformerTokenizer, ReformerVocab>>::special_tokens(self)
+    }
+
+    fn __getnewargs__(&self, py: Python<'_>) -> PyObject {
+        self.state.init_args.clone_ref(py)
+    }
+
+    fn __getstate__(&self) -> Vec<String> {
+        self.state.added_tokens.clone()
+    }
+
+    fn __setstate__(&mut self, added_tokens: Vec<String>) -> PyResult<()> {
+        self.add_tokens(added_tokens.iter().map(String::as_str).collect())
+    }
 }
 
 #[pyclass(module = "rust_tokenizers")]
 struct PyProphetNetTokenizer {
     tokenizer: ProphetNetTokenizer,
+    state: PyTokenizerState,
 }
 
 impl PyTokenizer<ProphetNetTokenizer, ProphetNetVocab> for PyProphetNetTokenizer {
     fn tokenizer(&self) -> &ProphetNetTokenizer {
         &self.tokenizer
     }
+
+    fn tokenizer_mut(&mut self) -> &mut ProphetNetTokenizer {
+        &mut self.tokenizer
+    }
+
+    fn pad_token_id(&self) -> Option<i64> {
+        let vocab = Tokenizer::vocab(&self.tokenizer);
+        Some(vocab.token_to_id(vocab.get_pad_value()))
+    }
 }
 
 impl PyMultiThreadTokenizer<ProphetNetTokenizer, ProphetNetVocab> for PyProphetNetTokenizer {}
@@ -1395,10 +2746,11 @@ impl PyMultiThreadTokenizer<ProphetNetTokenizer, ProphetNetVocab> for PyProphetN
 #[pymethods]
 impl PyProphetNetTokenizer {
     #[new]
-    fn new(path: String, do_lower_case: bool, strip_accents: bool) -> Self {
+    fn new(py: Python<'_>, path: String, do_lower_case: bool, strip_accents: bool) -> Self {
         PyProphetNetTokenizer {
             tokenizer: ProphetNetTokenizer::from_file(path.as_str(), do_lower_case, strip_accents)
                 .unwrap(),
+            state: PyTokenizerState::new((path, do_lower_case, strip_accents).into_py(py)),
         }
     }
 
@@ -1406,18 +2758,20 @@ impl PyProphetNetTokenizer {
         <Self as PyTokenizer<ProphetNetTokenizer, ProphetNetVocab>>::tokenize(self, text)
     }
 
-    fn tokenize_list(&self, text_list: Vec<&str>) -> PyResult<Vec<Vec<String>>> {
+    fn tokenize_list(&self, py: Python<'_>, text_list: Vec<&str>) -> PyResult<Vec<Vec<String>>> {
         <Self as PyMultiThreadTokenizer<ProphetNetTokenizer, ProphetNetVocab>>::tokenize_list(
-            self, text_list,
+            self, py, text_list,
         )
     }
 
+    #[pyo3(signature = (text, max_len, truncation_strategy, stride, truncation_side = "right"))]
     fn encode(
         &self,
         text: &str,
         max_len: usize,
         truncation_strategy: &str,
         stride: usize,
+        truncation_side: &str,
     ) -> PyResult<PyTokenizedInput> {
         <Self as PyTokenizer<ProphetNetTokenizer, ProphetNetVocab>>::encode(
             self,
@@ -1425,9 +2779,11 @@ impl PyProphetNetTokenizer {
             max_len,
             truncation_strategy,
             stride,
+            truncation_side,
         )
     }
 
+    #[pyo3(signature = (text_a, text_b, max_len, truncation_strategy, stride, truncation_side = "right"))]
     fn encode_pair(
         &self,
         text_a: &str,
@@ -1435,6 +2791,7 @@ impl PyProphetNetTokenizer {
         max_len: usize,
         truncation_strategy: &str,
         stride: usize,
+        truncation_side: &str,
     ) -> PyResult<PyTokenizedInput> {
         <Self as PyTokenizer<ProphetNetTokenizer, ProphetNetVocab>>::encode_pair(
             self,
@@ -1443,51 +2800,151 @@ impl PyProphetNetTokenizer {
             max_len,
             truncation_strategy,
             stride,
+            truncation_side,
         )
     }
 
+    #[pyo3(signature = (text_list, max_len, truncation_strategy, stride, return_numpy = false, pad_token_id = None, truncation_side = "right"))]
     fn encode_list(
         &self,
         text_list: Vec<&str>,
         max_len: usize,
         truncation_strategy: &str,
         stride: usize,
-    ) -> PyResult<Vec<PyTokenizedInput>> {
+        return_numpy: bool,
+        pad_token_id: Option<i64>,
+        truncation_side: &str,
+    ) -> PyResult<PyObject> {
         <Self as PyMultiThreadTokenizer<ProphetNetTokenizer, ProphetNetVocab>>::encode_list(
             self,
             text_list,
             max_len,
             truncation_strategy,
             stride,
+            return_numpy,
+            pad_token_id,
+            truncation_side,
         )
     }
 
+    #[pyo3(signature = (text_list, max_len, truncation_strategy, stride, return_numpy = false, pad_token_id = None, truncation_side = "right"))]
     fn encode_pair_list(
         &self,
         text_list: Vec<(&str, &str)>,
         max_len: usize,
         truncation_strategy: &str,
         stride: usize,
-    ) -> PyResult<Vec<PyTokenizedInput>> {
+        return_numpy: bool,
+        pad_token_id: Option<i64>,
+        truncation_side: &str,
+    ) -> PyResult<PyObject> {
         <Self as PyMultiThreadTokenizer<ProphetNetTokenizer, ProphetNetVocab>>::encode_pair_list(
             self,
             text_list,
             max_len,
             truncation_strategy,
             stride,
+            return_numpy,
+            pad_token_id,
+            truncation_side,
+        )
+    }
+
+    fn tokenize_with_offsets(&self, text: &str) -> PyResult<PyTokensWithOffsets> {
+        <Self as PyTokenizer<ProphetNetTokenizer, ProphetNetVocab>>::tokenize_with_offsets(
+            self, text,
+        )
+    }
+
+    fn convert_tokens_to_ids(&self, tokens: Vec<&str>) -> PyResult<Vec<i64>> {
+        <Self as PyTokenizer<ProphetNetTokenizer, ProphetNetVocab>>::convert_tokens_to_ids(
+            self, tokens,
+        )
+    }
+
+    fn convert_ids_to_tokens(&self, token_ids: Vec<i64>) -> PyResult<Vec<String>> {
+        <Self as PyTokenizer<ProphetNetTokenizer, ProphetNetVocab>>::convert_ids_to_tokens(
+            self, token_ids,
+        )
+    }
+
+    fn decode(
+        &self,
+        token_ids: Vec<i64>,
+        skip_special_tokens: bool,
+        clean_up_tokenization_spaces: bool,
+    ) -> PyResult<String> {
+        <Self as PyTokenizer<ProphetNetTokenizer, ProphetNetVocab>>::decode(
+            self,
+            token_ids,
+            skip_special_tokens,
+            clean_up_tokenization_spaces,
+        )
+    }
+
+    fn decode_list(
+        &self,
+        py: Python<'_>,
+        token_ids_list: Vec<Vec<i64>>,
+        skip_special_tokens: bool,
+        clean_up_tokenization_spaces: bool,
+    ) -> PyResult<Vec<String>> {
+        <Self as PyMultiThreadTokenizer<ProphetNetTokenizer, ProphetNetVocab>>::decode_list(
+            self,
+            py,
+            token_ids_list,
+            skip_special_tokens,
+            clean_up_tokenization_spaces,
         )
     }
+
+    fn add_tokens(&mut self, tokens: Vec<&str>) -> PyResult<()> {
+        self.state
+            .added_tokens
+            .extend(tokens.iter().map(|token| token.to_string()));
+        <Self as PyTokenizer<ProphetNetTokenizer, ProphetNetVocab>>::add_tokens(self, tokens)
+    }
+
+    fn vocab_size(&self) -> PyResult<usize> {
+        <Self as PyTokenizer<ProphetNetTokenizer, ProphetNetVocab>>::vocab_size(self)
+    }
+
+    fn special_tokens(&self) -> PyResult<HashMap<String, i64>> {
+        <Self as PyTokenizer<ProphetNetTokenizer, ProphetNetVocab>>::special_tokens(self)
+    }
+
+    fn __getnewargs__(&self, py: Python<'_>) -> PyObject {
+        self.state.init_args.clone_ref(py)
+    }
+
+    fn __getstate__(&self) -> Vec<String> {
+        self.state.added_tokens.clone()
+    }
+
+    fn __setstate__(&mut self, added_tokens: Vec<String>) -> PyResult<()> {
+        self.add_tokens(added_tokens.iter().map(String::as_str).collect())
+    }
 }
 
 #[pyclass(module = "rust_tokenizers")]
 struct PyPegasusTokenizer {
     tokenizer: PegasusTokenizer,
+    state: PyTokenizerState,
 }
 
 impl PyTokenizer<PegasusTokenizer, PegasusVocab> for PyPegasusTokenizer {
     fn tokenizer(&self) -> &PegasusTokenizer {
         &self.tokenizer
     }
+
+    fn tokenizer_mut(&mut self) -> &mut PegasusTokenizer {
+        &mut self.tokenizer
+    }
+
+    fn pad_token_id(&self) -> Option<i64> {
+        let vocab = Tokenizer::vocab(&self.tokenizer);
+        Some(vocab.token_to_id(vocab.get_pad_value()))
+    }
 }
 
 impl PyMultiThreadTokenizer<PegasusTokenizer, PegasusVocab> for PyPegasusTokenizer {}
@@ -1495,9 +2952,10 @@ impl PyMultiThreadTokenizer<PegasusTokenizer, PegasusVocab> for PyPegasusTokeniz
 #[pymethods]
 impl PyPegasusTokenizer {
     #[new]
-    fn new(path: String, do_lower_case: bool) -> Self {
+    fn new(py: Python<'_>, path: String, do_lower_case: bool) -> Self {
         PyPegasusTokenizer {
             tokenizer: PegasusTokenizer::from_file(path.as_str(), do_lower_case).unwrap(),
+            state: PyTokenizerState::new((path, do_lower_case).into_py(py)),
         }
     }
 
@@ -1505,18 +2963,20 @@ impl PyPegasusTokenizer {
         <Self as PyTokenizer<PegasusTokenizer, PegasusVocab>>::tokenize(self, text)
     }
 
-    fn tokenize_list(&self, text_list: Vec<&str>) -> PyResult<Vec<Vec<String>>> {
+    fn tokenize_list(&self, py: Python<'_>, text_list: Vec<&str>) -> PyResult<Vec<Vec<String>>> {
         <Self as PyMultiThreadTokenizer<PegasusTokenizer, PegasusVocab>>::tokenize_list(
-            self, text_list,
+            self, py, text_list,
         )
     }
 
+    #[pyo3(signature = (text, max_len, truncation_strategy, stride, truncation_side = "right"))]
     fn encode(
         &self,
         text: &str,
         max_len: usize,
         truncation_strategy: &str,
         stride: usize,
+        truncation_side: &str,
     ) -> PyResult<PyTokenizedInput> {
         <Self as PyTokenizer<PegasusTokenizer, PegasusVocab>>::encode(
             self,
@@ -1524,9 +2984,11 @@ impl PyPegasusTokenizer {
             max_len,
             truncation_strategy,
             stride,
+            truncation_side,
         )
     }
 
+    #[pyo3(signature = (text_a, text_b, max_len, truncation_strategy, stride, truncation_side = "right"))]
     fn encode_pair(
         &self,
         text_a: &str,
@@ -1534,6 +2996,7 @@ impl PyPegasusTokenizer {
         max_len: usize,
         truncation_strategy: &str,
         stride: usize,
+        truncation_side: &str,
     ) -> PyResult<PyTokenizedInput> {
         <Self as PyTokenizer<PegasusTokenizer, PegasusVocab>>::encode_pair(
             self,
@@ -1542,51 +3005,147 @@ impl PyPegasusTokenizer {
             max_len,
             truncation_strategy,
             stride,
+            truncation_side,
         )
     }
 
+    #[pyo3(signature = (text_list, max_len, truncation_strategy, stride, return_numpy = false, pad_token_id = None, truncation_side = "right"))]
     fn encode_list(
         &self,
         text_list: Vec<&str>,
         max_len: usize,
         truncation_strategy: &str,
         stride: usize,
-    ) -> PyResult<Vec<PyTokenizedInput>> {
+        return_numpy: bool,
+        pad_token_id: Option<i64>,
+        truncation_side: &str,
+    ) -> PyResult<PyObject> {
         <Self as PyMultiThreadTokenizer<PegasusTokenizer, PegasusVocab>>::encode_list(
             self,
             text_list,
             max_len,
             truncation_strategy,
             stride,
+            return_numpy,
+            pad_token_id,
+            truncation_side,
         )
     }
 
+    #[pyo3(signature = (text_list, max_len, truncation_strategy, stride, return_numpy = false, pad_token_id = None, truncation_side = "right"))]
     fn encode_pair_list(
         &self,
         text_list: Vec<(&str, &str)>,
         max_len: usize,
         truncation_strategy: &str,
         stride: usize,
-    ) -> PyResult<Vec<PyTokenizedInput>> {
+        return_numpy: bool,
+        pad_token_id: Option<i64>,
+        truncation_side: &str,
+    ) -> PyResult<PyObject> {
         <Self as PyMultiThreadTokenizer<PegasusTokenizer, PegasusVocab>>::encode_pair_list(
             self,
             text_list,
             max_len,
             truncation_strategy,
             stride,
+            return_numpy,
+            pad_token_id,
+            truncation_side,
+        )
+    }
+
+    fn tokenize_with_offsets(&self, text: &str) -> PyResult<PyTokensWithOffsets> {
+        <Self as PyTokenizer<PegasusTokenizer, PegasusVocab>>::tokenize_with_offsets(self, text)
+    }
+
+    fn convert_tokens_to_ids(&self, tokens: Vec<&str>) -> PyResult<Vec<i64>> {
+        <Self as PyTokenizer<PegasusTokenizer, PegasusVocab>>::convert_tokens_to_ids(self, tokens)
+    }
+
+    fn convert_ids_to_tokens(&self, token_ids: Vec<i64>) -> PyResult<Vec<String>> {
+        <Self as PyTokenizer<PegasusTokenizer, PegasusVocab>>::convert_ids_to_tokens(
+            self, token_ids,
+        )
+    }
+
+    fn decode(
+        &self,
+        token_ids: Vec<i64>,
+        skip_special_tokens: bool,
+        clean_up_tokenization_spaces: bool,
+    ) -> PyResult<String> {
+        <Self as PyTokenizer<PegasusTokenizer, PegasusVocab>>::decode(
+            self,
+            token_ids,
+            skip_special_tokens,
+            clean_up_tokenization_spaces,
+        )
+    }
+
+    fn decode_list(
+        &self,
+        py: Python<'_>,
+        token_ids_list: Vec<Vec<i64>>,
+        skip_special_tokens: bool,
+        clean_up_tokenization_spaces: bool,
+    ) -> PyResult<Vec<String>> {
+        <Self as PyMultiThreadTokenizer<PegasusTokenizer, PegasusVocab>>::decode_list(
+            self,
+            py,
+            token_ids_list,
+            skip_special_tokens,
+            clean_up_tokenization_spaces,
         )
     }
+
+    fn add_tokens(&mut self, tokens: Vec<&str>) -> PyResult<()> {
+        self.state
+            .added_tokens
+            .extend(tokens.iter().map(|token| token.to_string()));
+        <Self as PyTokenizer<PegasusTokenizer, PegasusVocab>>::add_tokens(self, tokens)
+    }
+
+    fn vocab_size(&self) -> PyResult<usize> {
+        <Self as PyTokenizer<PegasusTokenizer, PegasusVocab>>::vocab_size(self)
+    }
+
+    fn special_tokens(&self) -> PyResult<HashMap<String, i64>> {
+        <Self as PyTokenizer<PegasusTokenizer, PegasusVocab>>::special_tokens(self)
+    }
+
+    fn __getnewargs__(&self, py: Python<'_>) -> PyObject {
+        self.state.init_args.clone_ref(py)
+    }
+
+    fn __getstate__(&self) -> Vec<String> {
+        self.state.added_tokens.clone()
+    }
+
+    fn __setstate__(&mut self, added_tokens: Vec<String>) -> PyResult<()> {
+        self.add_tokens(added_tokens.iter().map(String::as_str).collect())
+    }
 }
 
 #[pyclass(module = "rust_tokenizers")]
 struct PyMBart50Tokenizer {
     tokenizer: MBart50Tokenizer,
+    state: PyTokenizerState,
 }
 
 impl PyTokenizer<MBart50Tokenizer, MBart50Vocab> for PyMBart50Tokenizer {
     fn tokenizer(&self) -> &MBart50Tokenizer {
         &self.tokenizer
     }
+
+    fn tokenizer_mut(&mut self) -> &mut MBart50Tokenizer {
+        &mut self.tokenizer
+    }
+
+    fn pad_token_id(&self) -> Option<i64> {
+        let vocab = Tokenizer::vocab(&self.tokenizer);
+        Some(vocab.token_to_id(vocab.get_pad_value()))
+    }
 }
 
 impl PyMultiThreadTokenizer<MBart50Tokenizer, MBart50Vocab> for PyMBart50Tokenizer {}
@@ -1594,9 +3153,10 @@ impl PyMultiThreadTokenizer<MBart50Tokenizer, MBart50Vocab> for PyMBart50Tokeniz
 #[pymethods]
 impl PyMBart50Tokenizer {
     #[new]
-    fn new(path: String, do_lower_case: bool) -> Self {
+    fn new(py: Python<'_>, path: String, do_lower_case: bool) -> Self {
         PyMBart50Tokenizer {
             tokenizer: MBart50Tokenizer::from_file(path.as_str(), do_lower_case).unwrap(),
+            state: PyTokenizerState::new((path, do_lower_case).into_py(py)),
         }
     }
 
@@ -1604,18 +3164,20 @@ impl PyMBart50Tokenizer {
         <Self as PyTokenizer<MBart50Tokenizer, MBart50Vocab>>::tokenize(self, text)
     }
 
-    fn tokenize_list(&self, text_list: Vec<&str>) -> PyResult<Vec<Vec<String>>> {
+    fn tokenize_list(&self, py: Python<'_>, text_list: Vec<&str>) -> PyResult<Vec<Vec<String>>> {
         <Self as PyMultiThreadTokenizer<MBart50Tokenizer, MBart50Vocab>>::tokenize_list(
-            self, text_list,
+            self, py, text_list,
         )
     }
 
+    #[pyo3(signature = (text, max_len, truncation_strategy, stride, truncation_side = "right"))]
     fn encode(
         &self,
         text: &str,
         max_len: usize,
         truncation_strategy: &str,
         stride: usize,
+        truncation_side: &str,
     ) -> PyResult<PyTokenizedInput> {
         <Self as PyTokenizer<MBart50Tokenizer, MBart50Vocab>>::encode(
             self,
@@ -1623,9 +3185,11 @@ impl PyMBart50Tokenizer {
             max_len,
             truncation_strategy,
             stride,
+            truncation_side,
         )
     }
 
+    #[pyo3(signature = (text_a, text_b, max_len, truncation_strategy, stride, truncation_side = "right"))]
     fn encode_pair(
         &self,
         text_a: &str,
@@ -1633,6 +3197,7 @@ impl PyMBart50Tokenizer {
         max_len: usize,
         truncation_strategy: &str,
         stride: usize,
+        truncation_side: &str,
     ) -> PyResult<PyTokenizedInput> {
         <Self as PyTokenizer<MBart50Tokenizer, MBart50Vocab>>::encode_pair(
             self,
@@ -1641,51 +3206,151 @@ impl PyMBart50Tokenizer {
             max_len,
             truncation_strategy,
             stride,
+            truncation_side,
         )
     }
 
+    #[pyo3(signature = (text_list, max_len, truncation_strategy, stride, return_numpy = false, pad_token_id = None, truncation_side = "right"))]
     fn encode_list(
         &self,
         text_list: Vec<&str>,
         max_len: usize,
         truncation_strategy: &str,
         stride: usize,
-    ) -> PyResult<Vec<PyTokenizedInput>> {
+        return_numpy: bool,
+        pad_token_id: Option<i64>,
+        truncation_side: &str,
+    ) -> PyResult<PyObject> {
         <Self as PyMultiThreadTokenizer<MBart50Tokenizer, MBart50Vocab>>::encode_list(
             self,
             text_list,
             max_len,
             truncation_strategy,
             stride,
+            return_numpy,
+            pad_token_id,
+            truncation_side,
         )
     }
 
+    #[pyo3(signature = (text_list, max_len, truncation_strategy, stride, return_numpy = false, pad_token_id = None, truncation_side = "right"))]
     fn encode_pair_list(
         &self,
         text_list: Vec<(&str, &str)>,
         max_len: usize,
         truncation_strategy: &str,
         stride: usize,
-    ) -> PyResult<Vec<PyTokenizedInput>> {
+        return_numpy: bool,
+        pad_token_id: Option<i64>,
+        truncation_side: &str,
+    ) -> PyResult<PyObject> {
         <Self as PyMultiThreadTokenizer<MBart50Tokenizer, MBart50Vocab>>::encode_pair_list(
             self,
             text_list,
             max_len,
             truncation_strategy,
             stride,
+            return_numpy,
+            pad_token_id,
+            truncation_side,
+        )
+    }
+
+    fn tokenize_with_offsets(&self, text: &str) -> PyResult<PyTokensWithOffsets> {
+        <Self as PyTokenizer<MBart50Tokenizer, MBart50Vocab>>::tokenize_with_offsets(self, text)
+    }
+
+    fn convert_tokens_to_ids(&self, tokens: Vec<&str>) -> PyResult<Vec<i64>> {
+        <Self as PyTokenizer<MBart50Tokenizer, MBart50Vocab>>::convert_tokens_to_ids(self, tokens)
+    }
+
+    fn convert_ids_to_tokens(&self, token_ids: Vec<i64>) -> PyResult<Vec<String>> {
+        <Self as PyTokenizer<MBart50Tokenizer, MBart50Vocab>>::convert_ids_to_tokens(
+            self, token_ids,
+        )
+    }
+
+    fn decode(
+        &self,
+        token_ids: Vec<i64>,
+        skip_special_tokens: bool,
+        clean_up_tokenization_spaces: bool,
+    ) -> PyResult<String> {
+        <Self as PyTokenizer<MBart50Tokenizer, MBart50Vocab>>::decode(
+            self,
+            token_ids,
+            skip_special_tokens,
+            clean_up_tokenization_spaces,
+        )
+    }
+
+    fn decode_list(
+        &self,
+        py: Python<'_>,
+        token_ids_list: Vec<Vec<i64>>,
+        skip_special_tokens: bool,
+        clean_up_tokenization_spaces: bool,
+    ) -> PyResult<Vec<String>> {
+        <Self as PyMultiThreadTokenizer<MBart50Tokenizer, MBart50Vocab>>::decode_list(
+            self,
+            py,
+            token_ids_list,
+            skip_special_tokens,
+            clean_up_tokenization_spaces,
         )
     }
+
+    fn add_tokens(&mut self, tokens: Vec<&str>) -> PyResult<()> {
+        self.state
+            .added_tokens
+            .extend(tokens.iter().map(|token| token.to_string()));
+        <Self as PyTokenizer<MBart50Tokenizer, MBart50Vocab>>::add_tokens(self, tokens)
+    }
+
+    fn vocab_size(&self) -> PyResult<usize> {
+        <Self as PyTokenizer<MBart50Tokenizer, MBart50Vocab>>::vocab_size(self)
+    }
+
+    fn special_tokens(&self) -> PyResult<HashMap<String, i64>> {
+        <Self as PyTokenizer<MBart50Tokenizer, MBart50Vocab>>::special_tokens(self)
+    }
+
+    fn __getnewargs__(&self, py: Python<'_>) -> PyObject {
+        self.state.init_args.clone_ref(py)
+    }
+
+    fn __getstate__(&self) -> Vec<String> {
+        self.state.added_tokens.clone()
+    }
+
+    fn __setstate__(&mut self, added_tokens: Vec<String>) -> PyResult<()> {
+        self.add_tokens(added_tokens.iter().map(String::as_str).collect())
+    }
 }
 
 #[pyclass(module = "rust_tokenizers")]
 struct PySentencePieceBpeTokenizer {
     tokenizer: SentencePieceBpeTokenizer,
+    state: PyTokenizerState,
 }
 
 impl PyTokenizer<SentencePieceBpeTokenizer, SentencePieceVocab> for PySentencePieceBpeTokenizer {
     fn tokenizer(&self) -> &SentencePieceBpeTokenizer {
         &self.tokenizer
     }
+
+    fn tokenizer_mut(&mut self) -> &mut SentencePieceBpeTokenizer {
+        &mut self.tokenizer
+    }
+
+    fn pad_token_id(&self) -> Option<i64> {
+        let vocab = Tokenizer::vocab(&self.tokenizer);
+        vocab
+            .special_token_map
+            .pad_token
+            .as_deref()
+            .map(|pad_token| vocab.token_to_id(pad_token))
+    }
 }
 
 impl PyMultiThreadTokenizer<SentencePieceBpeTokenizer, SentencePieceVocab>
@@ -1696,9 +3361,10 @@ impl PyMultiThreadTokenizer<SentencePieceBpeTokenizer, SentencePieceVocab>
 #[pymethods]
 impl PySentencePieceBpeTokenizer {
     #[new]
-    fn new(path: String, do_lower_case: bool) -> Self {
+    fn new(py: Python<'_>, path: String, do_lower_case: bool) -> Self {
         PySentencePieceBpeTokenizer {
             tokenizer: SentencePieceBpeTokenizer::from_file(path.as_str(), do_lower_case).unwrap(),
+            state: PyTokenizerState::new((path, do_lower_case).into_py(py)),
         }
     }
 
@@ -1706,18 +3372,18 @@ impl PySentencePieceBpeTokenizer {
         <Self as PyTokenizer<SentencePieceBpeTokenizer, SentencePieceVocab>>::tokenize(self, text)
     }
 
-    fn tokenize_list(&self, text_list: Vec<&str>) -> PyResult<Vec<Vec<String>>> {
-        <Self as PyMultiThreadTokenizer<SentencePieceBpeTokenizer, SentencePieceVocab>>::tokenize_list(
-            self, text_list,
-        )
+    fn tokenize_list(&self, py: Python<'_>, text_list: Vec<&str>) -> PyResult<Vec<Vec<String>>> {
+        <Self as PyMultiThreadTokenizer<SentencePieceBpeTokenizer, SentencePieceVocab>>::tokenize_list(self, py, text_list)
     }
 
+    #[pyo3(signature = (text, max_len, truncation_strategy, stride, truncation_side = "right"))]
     fn encode(
         &self,
         text: &str,
         max_len: usize,
         truncation_strategy: &str,
         stride: usize,
+        truncation_side: &str,
     ) -> PyResult<PyTokenizedInput> {
         <Self as PyTokenizer<SentencePieceBpeTokenizer, SentencePieceVocab>>::encode(
             self,
@@ -1725,9 +3391,11 @@ impl PySentencePieceBpeTokenizer {
             max_len,
             truncation_strategy,
             stride,
+            truncation_side,
         )
     }
 
+    #[pyo3(signature = (text_a, text_b, max_len, truncation_strategy, stride, truncation_side = "right"))]
     fn encode_pair(
         &self,
         text_a: &str,
@@ -1735,6 +3403,7 @@ impl PySentencePieceBpeTokenizer {
         max_len: usize,
         truncation_strategy: &str,
         stride: usize,
+        truncation_side: &str,
     ) -> PyResult<PyTokenizedInput> {
         <Self as PyTokenizer<SentencePieceBpeTokenizer, SentencePieceVocab>>::encode_pair(
             self,
@@ -1743,51 +3412,153 @@ impl PySentencePieceBpeTokenizer {
             max_len,
             truncation_strategy,
             stride,
+            truncation_side,
         )
     }
 
+    #[pyo3(signature = (text_list, max_len, truncation_strategy, stride, return_numpy = false, pad_token_id = None, truncation_side = "right"))]
     fn encode_list(
         &self,
         text_list: Vec<&str>,
         max_len: usize,
         truncation_strategy: &str,
         stride: usize,
-    ) -> PyResult<Vec<PyTokenizedInput>> {
+        return_numpy: bool,
+        pad_token_id: Option<i64>,
+        truncation_side: &str,
+    ) -> PyResult<PyObject> {
         <Self as PyMultiThreadTokenizer<SentencePieceBpeTokenizer, SentencePieceVocab>>::encode_list(
             self,
             text_list,
             max_len,
             truncation_strategy,
             stride,
+            return_numpy,
+            pad_token_id,
+            truncation_side,
         )
     }
 
+    #[pyo3(signature = (text_list, max_len, truncation_strategy, stride, return_numpy = false, pad_token_id = None, truncation_side = "right"))]
     fn encode_pair_list(
         &self,
         text_list: Vec<(&str, &str)>,
         max_len: usize,
         truncation_strategy: &str,
         stride: usize,
-    ) -> PyResult<Vec<PyTokenizedInput>> {
+        return_numpy: bool,
+        pad_token_id: Option<i64>,
+        truncation_side: &str,
+    ) -> PyResult<PyObject> {
         <Self as PyMultiThreadTokenizer<SentencePieceBpeTokenizer, SentencePieceVocab>>::encode_pair_list(
             self,
             text_list,
             max_len,
             truncation_strategy,
             stride,
+            return_numpy,
+            pad_token_id,
+            truncation_side,
+        )
+    }
+
+    fn tokenize_with_offsets(&self, text: &str) -> PyResult<PyTokensWithOffsets> {
+        <Self as PyTokenizer<SentencePieceBpeTokenizer, SentencePieceVocab>>::tokenize_with_offsets(
+            self, text,
+        )
+    }
+
+    fn convert_tokens_to_ids(&self, tokens: Vec<&str>) -> PyResult<Vec<i64>> {
+        <Self as PyTokenizer<SentencePieceBpeTokenizer, SentencePieceVocab>>::convert_tokens_to_ids(
+            self, tokens,
+        )
+    }
+
+    fn convert_ids_to_tokens(&self, token_ids: Vec<i64>) -> PyResult<Vec<String>> {
+        <Self as PyTokenizer<SentencePieceBpeTokenizer, SentencePieceVocab>>::convert_ids_to_tokens(
+            self, token_ids,
+        )
+    }
+
+    fn decode(
+        &self,
+        token_ids: Vec<i64>,
+        skip_special_tokens: bool,
+        clean_up_tokenization_spaces: bool,
+    ) -> PyResult<String> {
+        <Self as PyTokenizer<SentencePieceBpeTokenizer, SentencePieceVocab>>::decode(
+            self,
+            token_ids,
+            skip_special_tokens,
+            clean_up_tokenization_spaces,
+        )
+    }
+
+    fn decode_list(
+        &self,
+        py: Python<'_>,
+        token_ids_list: Vec<Vec<i64>>,
+        skip_special_tokens: bool,
+        clean_up_tokenization_spaces: bool,
+    ) -> PyResult<Vec<String>> {
+        <Self as PyMultiThreadTokenizer<SentencePieceBpeTokenizer, SentencePieceVocab>>::decode_list(
+            self,
+            py,
+            token_ids_list,
+            skip_special_tokens,
+            clean_up_tokenization_spaces,
+        )
+    }
+
+    fn add_tokens(&mut self, tokens: Vec<&str>) -> PyResult<()> {
+        self.state
+            .added_tokens
+            .extend(tokens.iter().map(|token| token.to_string()));
+        <Self as PyTokenizer<SentencePieceBpeTokenizer, SentencePieceVocab>>::add_tokens(
+            self, tokens,
         )
     }
+
+    fn vocab_size(&self) -> PyResult<usize> {
+        <Self as PyTokenizer<SentencePieceBpeTokenizer, SentencePieceVocab>>::vocab_size(self)
+    }
+
+    fn special_tokens(&self) -> PyResult<HashMap<String, i64>> {
+        <Self as PyTokenizer<SentencePieceBpeTokenizer, SentencePieceVocab>>::special_tokens(self)
+    }
+
+    fn __getnewargs__(&self, py: Python<'_>) -> PyObject {
+        self.state.init_args.clone_ref(py)
+    }
+
+    fn __getstate__(&self) -> Vec<String> {
+        self.state.added_tokens.clone()
+    }
+
+    fn __setstate__(&mut self, added_tokens: Vec<String>) -> PyResult<()> {
+        self.add_tokens(added_tokens.iter().map(String::as_str).collect())
+    }
 }
 
 #[pyclass(module = "rust_tokenizers")]
 struct PyM2M100Tokenizer {
     tokenizer: M2M100Tokenizer,
+    state: PyTokenizerState,
 }
 
 impl PyTokenizer<M2M100Tokenizer, M2M100Vocab> for PyM2M100Tokenizer {
     fn tokenizer(&self) -> &M2M100Tokenizer {
         &self.tokenizer
     }
+
+    fn tokenizer_mut(&mut self) -> &mut M2M100Tokenizer {
+        &mut self.tokenizer
+    }
+
+    fn pad_token_id(&self) -> Option<i64> {
+        let vocab = Tokenizer::vocab(&self.tokenizer);
+        Some(vocab.token_to_id(vocab.get_pad_value()))
+    }
 }
 
 impl PyMultiThreadTokenizer<M2M100Tokenizer, M2M100Vocab> for PyM2M100Tokenizer {}
@@ -1795,7 +3566,7 @@ impl PyMultiThreadTokenizer<M2M100Tokenizer, M2M100Vocab> for PyM2M100Tokenizer
 #[pymethods]
 impl PyM2M100Tokenizer {
     #[new]
-    fn new(vocab_path: String, merges_path: String, do_lower_case: bool) -> Self {
+    fn new(py: Python<'_>, vocab_path: String, merges_path: String, do_lower_case: bool) -> Self {
         PyM2M100Tokenizer {
             tokenizer: M2M100Tokenizer::from_files(
                 vocab_path.as_str(),
@@ -1803,6 +3574,7 @@ impl PyM2M100Tokenizer {
                 do_lower_case,
             )
             .unwrap(),
+            state: PyTokenizerState::new((vocab_path, merges_path, do_lower_case).into_py(py)),
         }
     }
 
@@ -1810,18 +3582,20 @@ impl PyM2M100Tokenizer {
         <Self as PyTokenizer<M2M100Tokenizer, M2M100Vocab>>::tokenize(self, text)
     }
 
-    fn tokenize_list(&self, text_list: Vec<&str>) -> PyResult<Vec<Vec<String>>> {
+    fn tokenize_list(&self, py: Python<'_>, text_list: Vec<&str>) -> PyResult<Vec<Vec<String>>> {
         <Self as PyMultiThreadTokenizer<M2M100Tokenizer, M2M100Vocab>>::tokenize_list(
-            self, text_list,
+            self, py, text_list,
         )
     }
 
+    #[pyo3(signature = (text, max_len, truncation_strategy, stride, truncation_side = "right"))]
     fn encode(
         &self,
         text: &str,
         max_len: usize,
         truncation_strategy: &str,
         stride: usize,
+        truncation_side: &str,
     ) -> PyResult<PyTokenizedInput> {
         <Self as PyTokenizer<M2M100Tokenizer, M2M100Vocab>>::encode(
             self,
@@ -1829,9 +3603,11 @@ impl PyM2M100Tokenizer {
             max_len,
             truncation_strategy,
             stride,
+            truncation_side,
         )
     }
 
+    #[pyo3(signature = (text_a, text_b, max_len, truncation_strategy, stride, truncation_side = "right"))]
     fn encode_pair(
         &self,
         text_a: &str,
@@ -1839,6 +3615,7 @@ impl PyM2M100Tokenizer {
         max_len: usize,
         truncation_strategy: &str,
         stride: usize,
+        truncation_side: &str,
     ) -> PyResult<PyTokenizedInput> {
         <Self as PyTokenizer<M2M100Tokenizer, M2M100Vocab>>::encode_pair(
             self,
@@ -1847,51 +3624,145 @@ impl PyM2M100Tokenizer {
             max_len,
             truncation_strategy,
             stride,
+            truncation_side,
         )
     }
 
+    #[pyo3(signature = (text_list, max_len, truncation_strategy, stride, return_numpy = false, pad_token_id = None, truncation_side = "right"))]
     fn encode_list(
         &self,
         text_list: Vec<&str>,
         max_len: usize,
         truncation_strategy: &str,
         stride: usize,
-    ) -> PyResult<Vec<PyTokenizedInput>> {
+        return_numpy: bool,
+        pad_token_id: Option<i64>,
+        truncation_side: &str,
+    ) -> PyResult<PyObject> {
         <Self as PyMultiThreadTokenizer<M2M100Tokenizer, M2M100Vocab>>::encode_list(
             self,
             text_list,
             max_len,
             truncation_strategy,
             stride,
+            return_numpy,
+            pad_token_id,
+            truncation_side,
         )
     }
 
+    #[pyo3(signature = (text_list, max_len, truncation_strategy, stride, return_numpy = false, pad_token_id = None, truncation_side = "right"))]
     fn encode_pair_list(
         &self,
         text_list: Vec<(&str, &str)>,
         max_len: usize,
         truncation_strategy: &str,
         stride: usize,
-    ) -> PyResult<Vec<PyTokenizedInput>> {
+        return_numpy: bool,
+        pad_token_id: Option<i64>,
+        truncation_side: &str,
+    ) -> PyResult<PyObject> {
         <Self as PyMultiThreadTokenizer<M2M100Tokenizer, M2M100Vocab>>::encode_pair_list(
             self,
             text_list,
             max_len,
             truncation_strategy,
             stride,
+            return_numpy,
+            pad_token_id,
+            truncation_side,
+        )
+    }
+
+    fn tokenize_with_offsets(&self, text: &str) -> PyResult<PyTokensWithOffsets> {
+        <Self as PyTokenizer<M2M100Tokenizer, M2M100Vocab>>::tokenize_with_offsets(self, text)
+    }
+
+    fn convert_tokens_to_ids(&self, tokens: Vec<&str>) -> PyResult<Vec<i64>> {
+        <Self as PyTokenizer<M2M100Tokenizer, M2M100Vocab>>::convert_tokens_to_ids(self, tokens)
+    }
+
+    fn convert_ids_to_tokens(&self, token_ids: Vec<i64>) -> PyResult<Vec<String>> {
+        <Self as PyTokenizer<M2M100Tokenizer, M2M100Vocab>>::convert_ids_to_tokens(self, token_ids)
+    }
+
+    fn decode(
+        &self,
+        token_ids: Vec<i64>,
+        skip_special_tokens: bool,
+        clean_up_tokenization_spaces: bool,
+    ) -> PyResult<String> {
+        <Self as PyTokenizer<M2M100Tokenizer, M2M100Vocab>>::decode(
+            self,
+            token_ids,
+            skip_special_tokens,
+            clean_up_tokenization_spaces,
+        )
+    }
+
+    fn decode_list(
+        &self,
+        py: Python<'_>,
+        token_ids_list: Vec<Vec<i64>>,
+        skip_special_tokens: bool,
+        clean_up_tokenization_spaces: bool,
+    ) -> PyResult<Vec<String>> {
+        <Self as PyMultiThreadTokenizer<M2M100Tokenizer, M2M100Vocab>>::decode_list(
+            self,
+            py,
+            token_ids_list,
+            skip_special_tokens,
+            clean_up_tokenization_spaces,
         )
     }
+
+    fn add_tokens(&mut self, tokens: Vec<&str>) -> PyResult<()> {
+        self.state
+            .added_tokens
+            .extend(tokens.iter().map(|token| token.to_string()));
+        <Self as PyTokenizer<M2M100Tokenizer, M2M100Vocab>>::add_tokens(self, tokens)
+    }
+
+    fn vocab_size(&self) -> PyResult<usize> {
+        <Self as PyTokenizer<M2M100Tokenizer, M2M100Vocab>>::vocab_size(self)
+    }
+
+    fn special_tokens(&self) -> PyResult<HashMap<String, i64>> {
+        <Self as PyTokenizer<M2M100Tokenizer, M2M100Vocab>>::special_tokens(self)
+    }
+
+    fn __getnewargs__(&self, py: Python<'_>) -> PyObject {
+        self.state.init_args.clone_ref(py)
+    }
+
+    fn __getstate__(&self) -> Vec<String> {
+        self.state.added_tokens.clone()
+    }
+
+    fn __setstate__(&mut self, added_tokens: Vec<String>) -> PyResult<()> {
+        self.add_tokens(added_tokens.iter().map(String::as_str).collect())
+    }
 }
 
 #[pyclass(module = "rust_tokenizers")]
 struct PyFNetTokenizer {
     tokenizer: FNetTokenizer,
+    state: PyTokenizerState,
 }
 
 impl PyTokenizer<FNetTokenizer, FNetVocab> for PyFNetTokenizer {
     fn tokenizer(&self) -> &FNetTokenizer {
         &self.tokenizer
     }
+
+    fn tokenizer_mut(&mut self) -> &mut FNetTokenizer {
+        &mut self.tokenizer
+    }
+
+    fn pad_token_id(&self) -> Option<i64> {
+        let vocab = Tokenizer::vocab(&self.tokenizer);
+        Some(vocab.token_to_id(vocab.get_pad_value()))
+    }
 }
 
 impl PyMultiThreadTokenizer<FNetTokenizer, FNetVocab> for PyFNetTokenizer {}
@@ -1899,10 +3770,11 @@ impl PyMultiThreadTokenizer<FNetTokenizer, FNetVocab> for PyFNetTokenizer {}
 #[pymethods]
 impl PyFNetTokenizer {
     #[new]
-    fn new(vocab_path: String, do_lower_case: bool, strip_accents: bool) -> Self {
+    fn new(py: Python<'_>, vocab_path: String, do_lower_case: bool, strip_accents: bool) -> Self {
         PyFNetTokenizer {
             tokenizer: FNetTokenizer::from_file(vocab_path.as_str(), do_lower_case, strip_accents)
                 .unwrap(),
+            state: PyTokenizerState::new((vocab_path, do_lower_case, strip_accents).into_py(py)),
         }
     }
 
@@ -1910,16 +3782,20 @@ impl PyFNetTokenizer {
         <Self as PyTokenizer<FNetTokenizer, FNetVocab>>::tokenize(self, text)
     }
 
-    fn tokenize_list(&self, text_list: Vec<&str>) -> PyResult<Vec<Vec<String>>> {
-        <Self as PyMultiThreadTokenizer<FNetTokenizer, FNetVocab>>::tokenize_list(self, text_list)
+    fn tokenize_list(&self, py: Python<'_>, text_list: Vec<&str>) -> PyResult<Vec<Vec<String>>> {
+        <Self as PyMultiThreadTokenizer<FNetTokenizer, FNetVocab>>::tokenize_list(
+            self, py, text_list,
+        )
     }
 
+    #[pyo3(signature = (text, max_len, truncation_strategy, stride, truncation_side = "right"))]
     fn encode(
         &self,
         text: &str,
         max_len: usize,
         truncation_strategy: &str,
         stride: usize,
+        truncation_side: &str,
     ) -> PyResult<PyTokenizedInput> {
         <Self as PyTokenizer<FNetTokenizer, FNetVocab>>::encode(
             self,
@@ -1927,9 +3803,11 @@ impl PyFNetTokenizer {
             max_len,
             truncation_strategy,
             stride,
+            truncation_side,
         )
     }
 
+    #[pyo3(signature = (text_a, text_b, max_len, truncation_strategy, stride, truncation_side = "right"))]
     fn encode_pair(
         &self,
         text_a: &str,
@@ -1937,6 +3815,7 @@ impl PyFNetTokenizer {
         max_len: usize,
         truncation_strategy: &str,
         stride: usize,
+        truncation_side: &str,
     ) -> PyResult<PyTokenizedInput> {
         <Self as PyTokenizer<FNetTokenizer, FNetVocab>>::encode_pair(
             self,
@@ -1945,51 +3824,145 @@ impl PyFNetTokenizer {
             max_len,
             truncation_strategy,
             stride,
+            truncation_side,
         )
     }
 
+    #[pyo3(signature = (text_list, max_len, truncation_strategy, stride, return_numpy = false, pad_token_id = None, truncation_side = "right"))]
     fn encode_list(
         &self,
         text_list: Vec<&str>,
         max_len: usize,
         truncation_strategy: &str,
         stride: usize,
-    ) -> PyResult<Vec<PyTokenizedInput>> {
+        return_numpy: bool,
+        pad_token_id: Option<i64>,
+        truncation_side: &str,
+    ) -> PyResult<PyObject> {
         <Self as PyMultiThreadTokenizer<FNetTokenizer, FNetVocab>>::encode_list(
             self,
             text_list,
             max_len,
             truncation_strategy,
             stride,
+            return_numpy,
+            pad_token_id,
+            truncation_side,
         )
     }
 
+    #[pyo3(signature = (text_list, max_len, truncation_strategy, stride, return_numpy = false, pad_token_id = None, truncation_side = "right"))]
     fn encode_pair_list(
         &self,
         text_list: Vec<(&str, &str)>,
         max_len: usize,
         truncation_strategy: &str,
         stride: usize,
-    ) -> PyResult<Vec<PyTokenizedInput>> {
+        return_numpy: bool,
+        pad_token_id: Option<i64>,
+        truncation_side: &str,
+    ) -> PyResult<PyObject> {
         <Self as PyMultiThreadTokenizer<FNetTokenizer, FNetVocab>>::encode_pair_list(
             self,
             text_list,
             max_len,
             truncation_strategy,
             stride,
+            return_numpy,
+            pad_token_id,
+            truncation_side,
+        )
+    }
+
+    fn tokenize_with_offsets(&self, text: &str) -> PyResult<PyTokensWithOffsets> {
+        <Self as PyTokenizer<FNetTokenizer, FNetVocab>>::tokenize_with_offsets(self, text)
+    }
+
+    fn convert_tokens_to_ids(&self, tokens: Vec<&str>) -> PyResult<Vec<i64>> {
+        <Self as PyTokenizer<FNetTokenizer, FNetVocab>>::convert_tokens_to_ids(self, tokens)
+    }
+
+    fn convert_ids_to_tokens(&self, token_ids: Vec<i64>) -> PyResult<Vec<String>> {
+        <Self as PyTokenizer<FNetTokenizer, FNetVocab>>::convert_ids_to_tokens(self, token_ids)
+    }
+
+    fn decode(
+        &self,
+        token_ids: Vec<i64>,
+        skip_special_tokens: bool,
+        clean_up_tokenization_spaces: bool,
+    ) -> PyResult<String> {
+        <Self as PyTokenizer<FNetTokenizer, FNetVocab>>::decode(
+            self,
+            token_ids,
+            skip_special_tokens,
+            clean_up_tokenization_spaces,
+        )
+    }
+
+    fn decode_list(
+        &self,
+        py: Python<'_>,
+        token_ids_list: Vec<Vec<i64>>,
+        skip_special_tokens: bool,
+        clean_up_tokenization_spaces: bool,
+    ) -> PyResult<Vec<String>> {
+        <Self as PyMultiThreadTokenizer<FNetTokenizer, FNetVocab>>::decode_list(
+            self,
+            py,
+            token_ids_list,
+            skip_special_tokens,
+            clean_up_tokenization_spaces,
         )
     }
+
+    fn add_tokens(&mut self, tokens: Vec<&str>) -> PyResult<()> {
+        self.state
+            .added_tokens
+            .extend(tokens.iter().map(|token| token.to_string()));
+        <Self as PyTokenizer<FNetTokenizer, FNetVocab>>::add_tokens(self, tokens)
+    }
+
+    fn vocab_size(&self) -> PyResult<usize> {
+        <Self as PyTokenizer<FNetTokenizer, FNetVocab>>::vocab_size(self)
+    }
+
+    fn special_tokens(&self) -> PyResult<HashMap<String, i64>> {
+        <Self as PyTokenizer<FNetTokenizer, FNetVocab>>::special_tokens(self)
+    }
+
+    fn __getnewargs__(&self, py: Python<'_>) -> PyObject {
+        self.state.init_args.clone_ref(py)
+    }
+
+    fn __getstate__(&self) -> Vec<String> {
+        self.state.added_tokens.clone()
+    }
+
+    fn __setstate__(&mut self, added_tokens: Vec<String>) -> PyResult<()> {
+        self.add_tokens(added_tokens.iter().map(String::as_str).collect())
+    }
 }
 
 #[pyclass(module = "rust_tokenizers")]
 struct PyDeBertaTokenizer {
     tokenizer: DeBERTaTokenizer,
+    state: PyTokenizerState,
 }
 
 impl PyTokenizer<DeBERTaTokenizer, DeBERTaVocab> for PyDeBertaTokenizer {
     fn tokenizer(&self) -> &DeBERTaTokenizer {
         &self.tokenizer
     }
+
+    fn tokenizer_mut(&mut self) -> &mut DeBERTaTokenizer {
+        &mut self.tokenizer
+    }
+
+    fn pad_token_id(&self) -> Option<i64> {
+        let vocab = Tokenizer::vocab(&self.tokenizer);
+        Some(vocab.token_to_id(vocab.get_pad_value()))
+    }
 }
 
 impl PyMultiThreadTokenizer<DeBERTaTokenizer, DeBERTaVocab> for PyDeBertaTokenizer {}
@@ -1997,7 +3970,7 @@ impl PyMultiThreadTokenizer<DeBERTaTokenizer, DeBERTaVocab> for PyDeBertaTokeniz
 #[pymethods]
 impl PyDeBertaTokenizer {
     #[new]
-    fn new(vocab_path: String, merges_path: String, do_lower_case: bool) -> Self {
+    fn new(py: Python<'_>, vocab_path: String, merges_path: String, do_lower_case: bool) -> Self {
         PyDeBertaTokenizer {
             tokenizer: DeBERTaTokenizer::from_file(
                 vocab_path.as_str(),
@@ -2005,6 +3978,7 @@ impl PyDeBertaTokenizer {
                 do_lower_case,
             )
             .unwrap(),
+            state: PyTokenizerState::new((vocab_path, merges_path, do_lower_case).into_py(py)),
         }
     }
 
@@ -2012,18 +3986,20 @@ impl PyDeBertaTokenizer {
         <Self as PyTokenizer<DeBERTaTokenizer, DeBERTaVocab>>::tokenize(self, text)
     }
 
-    fn tokenize_list(&self, text_list: Vec<&str>) -> PyResult<Vec<Vec<String>>> {
+    fn tokenize_list(&self, py: Python<'_>, text_list: Vec<&str>) -> PyResult<Vec<Vec<String>>> {
         <Self as PyMultiThreadTokenizer<DeBERTaTokenizer, DeBERTaVocab>>::tokenize_list(
-            self, text_list,
+            self, py, text_list,
         )
     }
 
+    #[pyo3(signature = (text, max_len, truncation_strategy, stride, truncation_side = "right"))]
     fn encode(
         &self,
         text: &str,
         max_len: usize,
         truncation_strategy: &str,
         stride: usize,
+        truncation_side: &str,
     ) -> PyResult<PyTokenizedInput> {
         <Self as PyTokenizer<DeBERTaTokenizer, DeBERTaVocab>>::encode(
             self,
@@ -2031,9 +4007,11 @@ impl PyDeBertaTokenizer {
             max_len,
             truncation_strategy,
             stride,
+            truncation_side,
         )
     }
 
+    #[pyo3(signature = (text_a, text_b, max_len, truncation_strategy, stride, truncation_side = "right"))]
     fn encode_pair(
         &self,
         text_a: &str,
@@ -2041,6 +4019,7 @@ impl PyDeBertaTokenizer {
         max_len: usize,
         truncation_strategy: &str,
         stride: usize,
+        truncation_side: &str,
     ) -> PyResult<PyTokenizedInput> {
         <Self as PyTokenizer<DeBERTaTokenizer, DeBERTaVocab>>::encode_pair(
             self,
@@ -2049,51 +4028,147 @@ impl PyDeBertaTokenizer {
             max_len,
             truncation_strategy,
             stride,
+            truncation_side,
         )
     }
 
+    #[pyo3(signature = (text_list, max_len, truncation_strategy, stride, return_numpy = false, pad_token_id = None, truncation_side = "right"))]
     fn encode_list(
         &self,
         text_list: Vec<&str>,
         max_len: usize,
         truncation_strategy: &str,
         stride: usize,
-    ) -> PyResult<Vec<PyTokenizedInput>> {
+        return_numpy: bool,
+        pad_token_id: Option<i64>,
+        truncation_side: &str,
+    ) -> PyResult<PyObject> {
         <Self as PyMultiThreadTokenizer<DeBERTaTokenizer, DeBERTaVocab>>::encode_list(
             self,
             text_list,
             max_len,
             truncation_strategy,
             stride,
+            return_numpy,
+            pad_token_id,
+            truncation_side,
         )
     }
 
+    #[pyo3(signature = (text_list, max_len, truncation_strategy, stride, return_numpy = false, pad_token_id = None, truncation_side = "right"))]
     fn encode_pair_list(
         &self,
         text_list: Vec<(&str, &str)>,
         max_len: usize,
         truncation_strategy: &str,
         stride: usize,
-    ) -> PyResult<Vec<PyTokenizedInput>> {
+        return_numpy: bool,
+        pad_token_id: Option<i64>,
+        truncation_side: &str,
+    ) -> PyResult<PyObject> {
         <Self as PyMultiThreadTokenizer<DeBERTaTokenizer, DeBERTaVocab>>::encode_pair_list(
             self,
             text_list,
             max_len,
             truncation_strategy,
             stride,
+            return_numpy,
+            pad_token_id,
+            truncation_side,
+        )
+    }
+
+    fn tokenize_with_offsets(&self, text: &str) -> PyResult<PyTokensWithOffsets> {
+        <Self as PyTokenizer<DeBERTaTokenizer, DeBERTaVocab>>::tokenize_with_offsets(self, text)
+    }
+
+    fn convert_tokens_to_ids(&self, tokens: Vec<&str>) -> PyResult<Vec<i64>> {
+        <Self as PyTokenizer<DeBERTaTokenizer, DeBERTaVocab>>::convert_tokens_to_ids(self, tokens)
+    }
+
+    fn convert_ids_to_tokens(&self, token_ids: Vec<i64>) -> PyResult<Vec<String>> {
+        <Self as PyTokenizer<DeBERTaTokenizer, DeBERTaVocab>>::convert_ids_to_tokens(
+            self, token_ids,
+        )
+    }
+
+    fn decode(
+        &self,
+        token_ids: Vec<i64>,
+        skip_special_tokens: bool,
+        clean_up_tokenization_spaces: bool,
+    ) -> PyResult<String> {
+        <Self as PyTokenizer<DeBERTaTokenizer, DeBERTaVocab>>::decode(
+            self,
+            token_ids,
+            skip_special_tokens,
+            clean_up_tokenization_spaces,
+        )
+    }
+
+    fn decode_list(
+        &self,
+        py: Python<'_>,
+        token_ids_list: Vec<Vec<i64>>,
+        skip_special_tokens: bool,
+        clean_up_tokenization_spaces: bool,
+    ) -> PyResult<Vec<String>> {
+        <Self as PyMultiThreadTokenizer<DeBERTaTokenizer, DeBERTaVocab>>::decode_list(
+            self,
+            py,
+            token_ids_list,
+            skip_special_tokens,
+            clean_up_tokenization_spaces,
         )
     }
+
+    fn add_tokens(&mut self, tokens: Vec<&str>) -> PyResult<()> {
+        self.state
+            .added_tokens
+            .extend(tokens.iter().map(|token| token.to_string()));
+        <Self as PyTokenizer<DeBERTaTokenizer, DeBERTaVocab>>::add_tokens(self, tokens)
+    }
+
+    fn vocab_size(&self) -> PyResult<usize> {
+        <Self as PyTokenizer<DeBERTaTokenizer, DeBERTaVocab>>::vocab_size(self)
+    }
+
+    fn special_tokens(&self) -> PyResult<HashMap<String, i64>> {
+        <Self as PyTokenizer<DeBERTaTokenizer, DeBERTaVocab>>::special_tokens(self)
+    }
+
+    fn __getnewargs__(&self, py: Python<'_>) -> PyObject {
+        self.state.init_args.clone_ref(py)
+    }
+
+    fn __getstate__(&self) -> Vec<String> {
+        self.state.added_tokens.clone()
+    }
+
+    fn __setstate__(&mut self, added_tokens: Vec<String>) -> PyResult<()> {
+        self.add_tokens(added_tokens.iter().map(String::as_str).collect())
+    }
 }
 
 #[pyclass(module = "rust_tokenizers")]
 struct PyDeBertaV2Tokenizer {
     tokenizer: DeBERTaV2Tokenizer,
+    state: PyTokenizerState,
 }
 
 impl PyTokenizer<DeBERTaV2Tokenizer, DeBERTaV2Vocab> for PyDeBertaV2Tokenizer {
     fn tokenizer(&self) -> &DeBERTaV2Tokenizer {
         &self.tokenizer
     }
+
+    fn tokenizer_mut(&mut self) -> &mut DeBERTaV2Tokenizer {
+        &mut self.tokenizer
+    }
+
+    fn pad_token_id(&self) -> Option<i64> {
+        let vocab = Tokenizer::vocab(&self.tokenizer);
+        Some(vocab.token_to_id(vocab.get_pad_value()))
+    }
 }
 
 impl PyMultiThreadTokenizer<DeBERTaV2Tokenizer, DeBERTaV2Vocab> for PyDeBertaV2Tokenizer {}
@@ -2102,6 +4177,7 @@ impl PyMultiThreadTokenizer<DeBERTaV2Tokenizer, DeBERTaV2Vocab> for PyDeBertaV2T
 impl PyDeBertaV2Tokenizer {
     #[new]
     fn new(
+        py: Python<'_>,
         vocab_path: String,
         do_lower_case: bool,
         strip_accents: bool,
@@ -2115,6 +4191,9 @@ impl PyDeBertaV2Tokenizer {
                 add_prefix_space,
             )
             .unwrap(),
+            state: PyTokenizerState::new(
+                (vocab_path, do_lower_case, strip_accents, add_prefix_space).into_py(py),
+            ),
         }
     }
 
@@ -2122,18 +4201,20 @@ impl PyDeBertaV2Tokenizer {
         <Self as PyTokenizer<DeBERTaV2Tokenizer, DeBERTaV2Vocab>>::tokenize(self, text)
     }
 
-    fn tokenize_list(&self, text_list: Vec<&str>) -> PyResult<Vec<Vec<String>>> {
+    fn tokenize_list(&self, py: Python<'_>, text_list: Vec<&str>) -> PyResult<Vec<Vec<String>>> {
         <Self as PyMultiThreadTokenizer<DeBERTaV2Tokenizer, DeBERTaV2Vocab>>::tokenize_list(
-            self, text_list,
+            self, py, text_list,
         )
     }
 
+    #[pyo3(signature = (text, max_len, truncation_strategy, stride, truncation_side = "right"))]
     fn encode(
         &self,
         text: &str,
         max_len: usize,
         truncation_strategy: &str,
         stride: usize,
+        truncation_side: &str,
     ) -> PyResult<PyTokenizedInput> {
         <Self as PyTokenizer<DeBERTaV2Tokenizer, DeBERTaV2Vocab>>::encode(
             self,
@@ -2141,9 +4222,11 @@ impl PyDeBertaV2Tokenizer {
             max_len,
             truncation_strategy,
             stride,
+            truncation_side,
         )
     }
 
+    #[pyo3(signature = (text_a, text_b, max_len, truncation_strategy, stride, truncation_side = "right"))]
     fn encode_pair(
         &self,
         text_a: &str,
@@ -2151,6 +4234,7 @@ impl PyDeBertaV2Tokenizer {
         max_len: usize,
         truncation_strategy: &str,
         stride: usize,
+        truncation_side: &str,
     ) -> PyResult<PyTokenizedInput> {
         <Self as PyTokenizer<DeBERTaV2Tokenizer, DeBERTaV2Vocab>>::encode_pair(
             self,
@@ -2159,51 +4243,149 @@ impl PyDeBertaV2Tokenizer {
             max_len,
             truncation_strategy,
             stride,
+            truncation_side,
         )
     }
 
+    #[pyo3(signature = (text_list, max_len, truncation_strategy, stride, return_numpy = false, pad_token_id = None, truncation_side = "right"))]
     fn encode_list(
         &self,
         text_list: Vec<&str>,
         max_len: usize,
         truncation_strategy: &str,
         stride: usize,
-    ) -> PyResult<Vec<PyTokenizedInput>> {
+        return_numpy: bool,
+        pad_token_id: Option<i64>,
+        truncation_side: &str,
+    ) -> PyResult<PyObject> {
         <Self as PyMultiThreadTokenizer<DeBERTaV2Tokenizer, DeBERTaV2Vocab>>::encode_list(
             self,
             text_list,
             max_len,
             truncation_strategy,
             stride,
+            return_numpy,
+            pad_token_id,
+            truncation_side,
         )
     }
 
+    #[pyo3(signature = (text_list, max_len, truncation_strategy, stride, return_numpy = false, pad_token_id = None, truncation_side = "right"))]
     fn encode_pair_list(
         &self,
         text_list: Vec<(&str, &str)>,
         max_len: usize,
         truncation_strategy: &str,
         stride: usize,
-    ) -> PyResult<Vec<PyTokenizedInput>> {
+        return_numpy: bool,
+        pad_token_id: Option<i64>,
+        truncation_side: &str,
+    ) -> PyResult<PyObject> {
         <Self as PyMultiThreadTokenizer<DeBERTaV2Tokenizer, DeBERTaV2Vocab>>::encode_pair_list(
             self,
             text_list,
             max_len,
             truncation_strategy,
             stride,
+            return_numpy,
+            pad_token_id,
+            truncation_side,
+        )
+    }
+
+    fn tokenize_with_offsets(&self, text: &str) -> PyResult<PyTokensWithOffsets> {
+        <Self as PyTokenizer<DeBERTaV2Tokenizer, DeBERTaV2Vocab>>::tokenize_with_offsets(self, text)
+    }
+
+    fn convert_tokens_to_ids(&self, tokens: Vec<&str>) -> PyResult<Vec<i64>> {
+        <Self as PyTokenizer<DeBERTaV2Tokenizer, DeBERTaV2Vocab>>::convert_tokens_to_ids(
+            self, tokens,
+        )
+    }
+
+    fn convert_ids_to_tokens(&self, token_ids: Vec<i64>) -> PyResult<Vec<String>> {
+        <Self as PyTokenizer<DeBERTaV2Tokenizer, DeBERTaV2Vocab>>::convert_ids_to_tokens(
+            self, token_ids,
+        )
+    }
+
+    fn decode(
+        &self,
+        token_ids: Vec<i64>,
+        skip_special_tokens: bool,
+        clean_up_tokenization_spaces: bool,
+    ) -> PyResult<String> {
+        <Self as PyTokenizer<DeBERTaV2Tokenizer, DeBERTaV2Vocab>>::decode(
+            self,
+            token_ids,
+            skip_special_tokens,
+            clean_up_tokenization_spaces,
+        )
+    }
+
+    fn decode_list(
+        &self,
+        py: Python<'_>,
+        token_ids_list: Vec<Vec<i64>>,
+        skip_special_tokens: bool,
+        clean_up_tokenization_spaces: bool,
+    ) -> PyResult<Vec<String>> {
+        <Self as PyMultiThreadTokenizer<DeBERTaV2Tokenizer, DeBERTaV2Vocab>>::decode_list(
+            self,
+            py,
+            token_ids_list,
+            skip_special_tokens,
+            clean_up_tokenization_spaces,
         )
     }
+
+    fn add_tokens(&mut self, tokens: Vec<&str>) -> PyResult<()> {
+        self.state
+            .added_tokens
+            .extend(tokens.iter().map(|token| token.to_string()));
+        <Self as PyTokenizer<DeBERTaV2Tokenizer, DeBERTaV2Vocab>>::add_tokens(self, tokens)
+    }
+
+    fn vocab_size(&self) -> PyResult<usize> {
+        <Self as PyTokenizer<DeBERTaV2Tokenizer, DeBERTaV2Vocab>>::vocab_size(self)
+    }
+
+    fn special_tokens(&self) -> PyResult<HashMap<String, i64>> {
+        <Self as PyTokenizer<DeBERTaV2Tokenizer, DeBERTaV2Vocab>>::special_tokens(self)
+    }
+
+    fn __getnewargs__(&self, py: Python<'_>) -> PyObject {
+        self.state.init_args.clone_ref(py)
+    }
+
+    fn __getstate__(&self) -> Vec<String> {
+        self.state.added_tokens.clone()
+    }
+
+    fn __setstate__(&mut self, added_tokens: Vec<String>) -> PyResult<()> {
+        self.add_tokens(added_tokens.iter().map(String::as_str).collect())
+    }
 }
 
 #[pyclass(module = "rust_tokenizers")]
 struct PyNLLBTokenizer {
     tokenizer: NLLBTokenizer,
+    state: PyTokenizerState,
 }
 
 impl PyTokenizer<NLLBTokenizer, NLLBVocab> for PyNLLBTokenizer {
     fn tokenizer(&self) -> &NLLBTokenizer {
         &self.tokenizer
     }
+
+    fn tokenizer_mut(&mut self) -> &mut NLLBTokenizer {
+        &mut self.tokenizer
+    }
+
+    fn pad_token_id(&self) -> Option<i64> {
+        let vocab = Tokenizer::vocab(&self.tokenizer);
+        Some(vocab.token_to_id(vocab.get_pad_value()))
+    }
 }
 
 impl PyMultiThreadTokenizer<NLLBTokenizer, NLLBVocab> for PyNLLBTokenizer {}
@@ -2211,7 +4393,12 @@ impl PyMultiThreadTokenizer<NLLBTokenizer, NLLBVocab> for PyNLLBTokenizer {}
 #[pymethods]
 impl PyNLLBTokenizer {
     #[new]
-    fn new(vocab_path: String, merges_path: String, special_token_map: String) -> Self {
+    fn new(
+        py: Python<'_>,
+        vocab_path: String,
+        merges_path: String,
+        special_token_map: String,
+    ) -> Self {
         PyNLLBTokenizer {
             tokenizer: NLLBTokenizer::from_files_with_special_token_map(
                 vocab_path.as_str(),
@@ -2219,6 +4406,7 @@ impl PyNLLBTokenizer {
                 special_token_map.as_str(),
             )
             .unwrap(),
+            state: PyTokenizerState::new((vocab_path, merges_path, special_token_map).into_py(py)),
         }
     }
 
@@ -2226,16 +4414,20 @@ impl PyNLLBTokenizer {
         <Self as PyTokenizer<NLLBTokenizer, NLLBVocab>>::tokenize(self, text)
     }
 
-    fn tokenize_list(&self, text_list: Vec<&str>) -> PyResult<Vec<Vec<String>>> {
-        <Self as PyMultiThreadTokenizer<NLLBTokenizer, NLLBVocab>>::tokenize_list(self, text_list)
+    fn tokenize_list(&self, py: Python<'_>, text_list: Vec<&str>) -> PyResult<Vec<Vec<String>>> {
+        <Self as PyMultiThreadTokenizer<NLLBTokenizer, NLLBVocab>>::tokenize_list(
+            self, py, text_list,
+        )
     }
 
+    #[pyo3(signature = (text, max_len, truncation_strategy, stride, truncation_side = "right"))]
     fn encode(
         &self,
         text: &str,
         max_len: usize,
         truncation_strategy: &str,
         stride: usize,
+        truncation_side: &str,
     ) -> PyResult<PyTokenizedInput> {
         <Self as PyTokenizer<NLLBTokenizer, NLLBVocab>>::encode(
             self,
@@ -2243,9 +4435,11 @@ impl PyNLLBTokenizer {
             max_len,
             truncation_strategy,
             stride,
+            truncation_side,
         )
     }
 
+    #[pyo3(signature = (text_a, text_b, max_len, truncation_strategy, stride, truncation_side = "right"))]
     fn encode_pair(
         &self,
         text_a: &str,
@@ -2253,6 +4447,7 @@ impl PyNLLBTokenizer {
         max_len: usize,
         truncation_strategy: &str,
         stride: usize,
+        truncation_side: &str,
     ) -> PyResult<PyTokenizedInput> {
         <Self as PyTokenizer<NLLBTokenizer, NLLBVocab>>::encode_pair(
             self,
@@ -2261,44 +4456,130 @@ impl PyNLLBTokenizer {
             max_len,
             truncation_strategy,
             stride,
+            truncation_side,
         )
     }
 
+    #[pyo3(signature = (text_list, max_len, truncation_strategy, stride, return_numpy = false, pad_token_id = None, truncation_side = "right"))]
     fn encode_list(
         &self,
         text_list: Vec<&str>,
         max_len: usize,
         truncation_strategy: &str,
         stride: usize,
-    ) -> PyResult<Vec<PyTokenizedInput>> {
+        return_numpy: bool,
+        pad_token_id: Option<i64>,
+        truncation_side: &str,
+    ) -> PyResult<PyObject> {
         <Self as PyMultiThreadTokenizer<NLLBTokenizer, NLLBVocab>>::encode_list(
             self,
             text_list,
             max_len,
             truncation_strategy,
             stride,
+            return_numpy,
+            pad_token_id,
+            truncation_side,
         )
     }
 
+    #[pyo3(signature = (text_list, max_len, truncation_strategy, stride, return_numpy = false, pad_token_id = None, truncation_side = "right"))]
     fn encode_pair_list(
         &self,
         text_list: Vec<(&str, &str)>,
         max_len: usize,
         truncation_strategy: &str,
         stride: usize,
-    ) -> PyResult<Vec<PyTokenizedInput>> {
+        return_numpy: bool,
+        pad_token_id: Option<i64>,
+        truncation_side: &str,
+    ) -> PyResult<PyObject> {
         <Self as PyMultiThreadTokenizer<NLLBTokenizer, NLLBVocab>>::encode_pair_list(
             self,
             text_list,
             max_len,
             truncation_strategy,
             stride,
+            return_numpy,
+            pad_token_id,
+            truncation_side,
+        )
+    }
+
+    fn tokenize_with_offsets(&self, text: &str) -> PyResult<PyTokensWithOffsets> {
+        <Self as PyTokenizer<NLLBTokenizer, NLLBVocab>>::tokenize_with_offsets(self, text)
+    }
+
+    fn convert_tokens_to_ids(&self, tokens: Vec<&str>) -> PyResult<Vec<i64>> {
+        <Self as PyTokenizer<NLLBTokenizer, NLLBVocab>>::convert_tokens_to_ids(self, tokens)
+    }
+
+    fn convert_ids_to_tokens(&self, token_ids: Vec<i64>) -> PyResult<Vec<String>> {
+        <Self as PyTokenizer<NLLBTokenizer, NLLBVocab>>::convert_ids_to_tokens(self, token_ids)
+    }
+
+    fn decode(
+        &self,
+        token_ids: Vec<i64>,
+        skip_special_tokens: bool,
+        clean_up_tokenization_spaces: bool,
+    ) -> PyResult<String> {
+        <Self as PyTokenizer<NLLBTokenizer, NLLBVocab>>::decode(
+            self,
+            token_ids,
+            skip_special_tokens,
+            clean_up_tokenization_spaces,
+        )
+    }
+
+    fn decode_list(
+        &self,
+        py: Python<'_>,
+        token_ids_list: Vec<Vec<i64>>,
+        skip_special_tokens: bool,
+        clean_up_tokenization_spaces: bool,
+    ) -> PyResult<Vec<String>> {
+        <Self as PyMultiThreadTokenizer<NLLBTokenizer, NLLBVocab>>::decode_list(
+            self,
+            py,
+            token_ids_list,
+            skip_special_tokens,
+            clean_up_tokenization_spaces,
         )
     }
+
+    fn add_tokens(&mut self, tokens: Vec<&str>) -> PyResult<()> {
+        self.state
+            .added_tokens
+            .extend(tokens.iter().map(|token| token.to_string()));
+        <Self as PyTokenizer<NLLBTokenizer, NLLBVocab>>::add_tokens(self, tokens)
+    }
+
+    fn vocab_size(&self) -> PyResult<usize> {
+        <Self as PyTokenizer<NLLBTokenizer, NLLBVocab>>::vocab_size(self)
+    }
+
+    fn special_tokens(&self) -> PyResult<HashMap<String, i64>> {
+        <Self as PyTokenizer<NLLBTokenizer, NLLBVocab>>::special_tokens(self)
+    }
+
+    fn __getnewargs__(&self, py: Python<'_>) -> PyObject {
+        self.state.init_args.clone_ref(py)
+    }
+
+    fn __getstate__(&self) -> Vec<String> {
+        self.state.added_tokens.clone()
+    }
+
+    fn __setstate__(&mut self, added_tokens: Vec<String>) -> PyResult<()> {
+        self.add_tokens(added_tokens.iter().map(String::as_str).collect())
+    }
 }
 
 #[pymodule]
 fn rust_tokenizers(_py: Python<'_>, m: &PyModule) -> PyResult<()> {
+    m.add_class::<PyTokenizedInput>()?;
+    m.add_class::<PyTokensWithOffsets>()?;
     m.add_class::<PyBertTokenizer>()?;
     m.add_class::<PyCtrlTokenizer>()?;
     m.add_class::<PyGpt2Tokenizer>()?;
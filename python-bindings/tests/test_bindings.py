@@ -0,0 +1,162 @@
+# Copyright 2019 Guillaume Becquin
+# Licensed under the Apache License, Version 2.0 (the "License");
+# you may not use this file except in compliance with the License.
+# You may obtain a copy of the License at
+#     http://www.apache.org/licenses/LICENSE-2.0
+# Unless required by applicable law or agreed to in writing, software
+# distributed under the License is distributed on an "AS IS" BASIS,
+# WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+# See the License for the specific language governing permissions and
+# limitations under the License.
+import json
+import pickle
+import threading
+import time
+
+import pytest
+from rust_tokenizers import PyBertTokenizer, PyGpt2Tokenizer
+
+VOCAB = ['[PAD]', '[UNK]', '[CLS]', '[SEP]', '[MASK]', 'hello', 'world', '!', 'un', '##aff', '##able']
+
+
+@pytest.fixture
+def tokenizer(tmp_path):
+    vocab_path = tmp_path / 'vocab.txt'
+    vocab_path.write_text('\n'.join(VOCAB) + '\n', encoding='utf-8')
+    return PyBertTokenizer(str(vocab_path), do_lower_case=True, strip_accents=True)
+
+
+class TestBindings:
+    def test_decode(self, tokenizer):
+        token_ids = [2, 5, 6, 7, 3]
+
+        assert tokenizer.decode(token_ids, False, False) == '[CLS] hello world ! [SEP]'
+        assert tokenizer.decode(token_ids, True, False) == 'hello world !'
+        assert tokenizer.decode(token_ids, True, True) == 'hello world!'
+
+    def test_decode_list(self, tokenizer):
+        token_ids_list = [[2, 5, 6, 7, 3], [2, 8, 9, 10, 3]]
+
+        assert tokenizer.decode_list(token_ids_list, True, True) == ['hello world!', 'unaffable']
+
+    def test_tokenize_with_offsets(self, tokenizer):
+        output = tokenizer.tokenize_with_offsets('Hello unaffable world!')
+
+        assert output.tokens == ['hello', 'un', '##aff', '##able', 'world', '!']
+        assert output.offsets == [(0, 5), (6, 8), (8, 11), (11, 15), (16, 21), (21, 22)]
+        assert output.reference_offsets[1] == [6, 7]
+        assert output.masks == ['none', 'begin', 'continuation', 'continuation', 'none', 'punctuation']
+
+    def test_encode_offsets_and_masks(self, tokenizer):
+        output = tokenizer.encode_pair('Hello world', 'unaffable', 128, 'longest_first', 0)
+
+        assert output.token_ids == [2, 5, 6, 3, 8, 9, 10, 3]
+        assert output.segment_ids == [0, 0, 0, 0, 1, 1, 1, 1]
+        assert output.special_tokens_mask == [1, 0, 0, 1, 0, 0, 0, 1]
+        assert output.token_offsets == [None, (0, 5), (6, 11), None, (0, 2), (2, 5), (5, 9), None]
+        assert output.mask == ['special', 'none', 'none', 'special', 'begin', 'continuation', 'continuation',
+                               'special']
+
+    def test_encode_invalid_truncation_strategy(self, tokenizer):
+        with pytest.raises(ValueError):
+            tokenizer.encode('Hello world', 128, 'longest', 0)
+
+    def test_encode_truncation_side(self, tokenizer):
+        right = tokenizer.encode('Hello world unaffable', 5, 'longest_first', 0)
+        left = tokenizer.encode('Hello world unaffable', 5, 'longest_first', 0, truncation_side='left')
+
+        assert right.token_ids == [2, 5, 6, 8, 3]
+        assert left.token_ids == [2, 8, 9, 10, 3]
+        assert left.overflowing_tokens == [5, 6]
+        with pytest.raises(ValueError):
+            tokenizer.encode('Hello world unaffable', 5, 'longest_first', 0, truncation_side='middle')
+
+    def test_convert_tokens_and_ids(self, tokenizer):
+        assert tokenizer.convert_tokens_to_ids(['hello', 'world', 'unknown']) == [5, 6, 1]
+        assert tokenizer.convert_ids_to_tokens([2, 5, 6, 3]) == ['[CLS]', 'hello', 'world', '[SEP]']
+
+    def test_vocab_helpers(self, tokenizer):
+        assert tokenizer.vocab_size() == len(VOCAB)
+        assert tokenizer.special_tokens() == {'[PAD]': 0, '[UNK]': 1, '[CLS]': 2, '[SEP]': 3, '[MASK]': 4}
+
+    def test_add_tokens(self, tokenizer):
+        tokenizer.add_tokens(['<new>'])
+
+        assert tokenizer.vocab_size() == len(VOCAB) + 1
+        assert tokenizer.tokenize('Hello<new>world!') == ['hello', '<new>', 'world', '!']
+        assert tokenizer.convert_tokens_to_ids(['<new>']) == [len(VOCAB)]
+
+
+@pytest.fixture
+def gpt2_tokenizer(tmp_path):
+    vocab_path = tmp_path / 'vocab.json'
+    vocab_path.write_text(json.dumps({'<|endoftext|>': 0, 'h': 1, 'i': 2, 'hi': 3}), encoding='utf-8')
+    merges_path = tmp_path / 'merges.txt'
+    merges_path.write_text('#version: 0.2\nh i\n', encoding='utf-8')
+    return PyGpt2Tokenizer(str(vocab_path), str(merges_path), do_lower_case=False)
+
+
+class TestBatchEncoding:
+    def test_encode_list_numpy(self, tokenizer):
+        np = pytest.importorskip('numpy')
+
+        output = tokenizer.encode_list(['Hello world!', 'Hello'], 128, 'longest_first', 0, return_numpy=True)
+
+        assert np.asarray(output['token_ids']).tolist() == [[2, 5, 6, 7, 3], [2, 5, 3, 0, 0]]
+        assert np.asarray(output['attention_mask']).tolist() == [[1, 1, 1, 1, 1], [1, 1, 1, 0, 0]]
+        assert np.asarray(output['segment_ids']).tolist() == [[0, 0, 0, 0, 0], [0, 0, 0, 0, 0]]
+
+    def test_encode_pair_list_numpy_custom_padding(self, tokenizer):
+        np = pytest.importorskip('numpy')
+
+        output = tokenizer.encode_pair_list([('Hello', 'world'), ('Hello', 'world!')], 128, 'longest_first', 0,
+                                            return_numpy=True, pad_token_id=-1)
+
+        assert np.asarray(output['token_ids']).tolist() == [[2, 5, 3, 6, 3, -1], [2, 5, 3, 6, 7, 3]]
+        assert np.asarray(output['segment_ids']).tolist() == [[0, 0, 0, 1, 1, 0], [0, 0, 0, 1, 1, 1]]
+
+    def test_encode_list_numpy_without_padding_token(self, gpt2_tokenizer):
+        np = pytest.importorskip('numpy')
+
+        with pytest.raises(ValueError):
+            gpt2_tokenizer.encode_list(['hi', 'h'], 128, 'longest_first', 0, return_numpy=True)
+        output = gpt2_tokenizer.encode_list(['hi', 'hih'], 128, 'longest_first', 0, return_numpy=True,
+                                            pad_token_id=0)
+
+        assert np.asarray(output['token_ids']).tolist() == [[3, 0], [3, 1]]
+
+    def test_encode_list_releases_gil(self, tokenizer):
+        running_time = 0.0
+        done = threading.Event()
+
+        def run():
+            nonlocal running_time
+            last = time.perf_counter()
+            while not done.is_set():
+                now = time.perf_counter()
+                if now - last < 1e-3:
+                    running_time += now - last
+                last = now
+
+        thread = threading.Thread(target=run)
+        thread.start()
+        try:
+            start = time.perf_counter()
+            tokenizer.encode_list(['Hello unaffable world!'] * 100000, 128, 'longest_first', 0)
+            elapsed = time.perf_counter() - start
+        finally:
+            done.set()
+            thread.join()
+
+        assert running_time > elapsed / 4
+
+
+class TestPickle:
+    def test_pickle_round_trip(self, tokenizer):
+        tokenizer.add_tokens(['<new>'])
+
+        restored = pickle.loads(pickle.dumps(tokenizer))
+
+        assert restored.vocab_size() == tokenizer.vocab_size()
+        assert restored.tokenize('Hello<new>world!') == ['hello', '<new>', 'world', '!']
+        assert restored.encode('Hello world!', 128, 'longest_first', 0).token_ids == [2, 5, 6, 7, 3]